@@ -0,0 +1,219 @@
+//! Command alias expansion.
+//!
+//! Rewrites argv before clap parsing, using `[aliases]` and `[cli]` from config.toml:
+//! ```toml
+//! [cli]
+//! default_command = "view"   # `moss foo` runs `moss view foo`
+//!
+//! [aliases]
+//! al = "analyze --health"    # `moss al` runs `moss analyze --health`
+//! ```
+//!
+//! Built-in subcommands always win over aliases of the same name. A few default
+//! aliases (see [`DEFAULT_ALIASES`]) are always available unless config overrides them.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+
+/// Aliases available without any config: `moss callers foo --tree` runs
+/// `moss analyze --callers foo --tree`.
+pub const DEFAULT_ALIASES: &[(&str, &str)] = &[
+    ("callers", "analyze --callers"),
+    ("callees", "analyze --callees"),
+];
+
+/// Merge config aliases over [`DEFAULT_ALIASES`].
+pub fn with_defaults(aliases: &HashMap<String, String>) -> HashMap<String, String> {
+    let mut merged: HashMap<String, String> = DEFAULT_ALIASES
+        .iter()
+        .map(|(name, expansion)| (name.to_string(), expansion.to_string()))
+        .collect();
+    merged.extend(aliases.iter().map(|(k, v)| (k.clone(), v.clone())));
+    merged
+}
+
+/// Global flags that take a separate value argument (`--jq EXPR`, `-o PATH`).
+const VALUE_FLAGS: &[&str] = &["--jq", "--output", "-o"];
+
+/// Expand aliases and the default command in `args` (including argv[0]).
+///
+/// `builtins` lists the real subcommand names. Arguments that aren't valid
+/// UTF-8 pass through untouched. Returns an error on alias recursion or an
+/// alias that expands to nothing.
+pub fn expand_args(
+    args: Vec<OsString>,
+    builtins: &[String],
+    aliases: &HashMap<String, String>,
+    default_command: Option<&str>,
+) -> Result<Vec<OsString>, String> {
+    let Some(pos) = command_position(&args) else {
+        return Ok(args);
+    };
+
+    let mut args = args;
+    let mut chain: Vec<String> = Vec::new();
+
+    while let Some(name) = args[pos].to_str().map(String::from) {
+        if builtins.contains(&name) {
+            return Ok(args);
+        }
+
+        let Some(expansion) = aliases.get(&name) else {
+            break;
+        };
+        if chain.contains(&name) {
+            chain.push(name);
+            return Err(format!("alias recursion: {}", chain.join(" -> ")));
+        }
+        chain.push(name.clone());
+
+        let replacement: Vec<OsString> = expansion.split_whitespace().map(OsString::from).collect();
+        if replacement.is_empty() {
+            return Err(format!("alias '{}' is empty", name));
+        }
+        args.splice(pos..=pos, replacement);
+    }
+
+    // An unknown command after alias expansion: fall back to the default command.
+    // Only applies to the user's own token, not to the result of an alias.
+    if chain.is_empty() {
+        if let Some(default) = default_command {
+            args.insert(pos, OsString::from(default));
+        }
+    }
+    Ok(args)
+}
+
+/// Index of the subcommand token, skipping global flags. None if there is no command.
+fn command_position(args: &[OsString]) -> Option<usize> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].to_string_lossy();
+        if VALUE_FLAGS.contains(&arg.as_ref()) {
+            i += 2;
+        } else if arg.starts_with('-') {
+            i += 1;
+        } else {
+            return Some(i);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<OsString> {
+        s.split_whitespace().map(OsString::from).collect()
+    }
+
+    fn builtins() -> Vec<String> {
+        vec!["view".into(), "analyze".into(), "grep".into()]
+    }
+
+    #[test]
+    fn test_alias_expands() {
+        let aliases = HashMap::from([("al".to_string(), "analyze --health".to_string())]);
+        let expanded = expand_args(args("moss --json al src"), &builtins(), &aliases, None);
+        assert_eq!(expanded.unwrap(), args("moss --json analyze --health src"));
+    }
+
+    #[test]
+    fn test_alias_chain_expands() {
+        let aliases = HashMap::from([
+            ("h".to_string(), "al --compact".to_string()),
+            ("al".to_string(), "analyze --health".to_string()),
+        ]);
+        let expanded = expand_args(args("moss h"), &builtins(), &aliases, None);
+        assert_eq!(expanded.unwrap(), args("moss analyze --health --compact"));
+    }
+
+    #[test]
+    fn test_recursive_alias_rejected() {
+        let aliases = HashMap::from([
+            ("a".to_string(), "b --x".to_string()),
+            ("b".to_string(), "a".to_string()),
+        ]);
+        let err = expand_args(args("moss a"), &builtins(), &aliases, None).unwrap_err();
+        assert_eq!(err, "alias recursion: a -> b -> a");
+    }
+
+    #[test]
+    fn test_builtin_wins_over_alias() {
+        let aliases = HashMap::from([("view".to_string(), "grep".to_string())]);
+        let expanded = expand_args(args("moss view foo"), &builtins(), &aliases, None);
+        assert_eq!(expanded.unwrap(), args("moss view foo"));
+    }
+
+    #[test]
+    fn test_default_aliases() {
+        let merged = with_defaults(&HashMap::new());
+        let expanded = expand_args(
+            args("moss callers main --tree --depth 3"),
+            &builtins(),
+            &merged,
+            None,
+        );
+        assert_eq!(
+            expanded.unwrap(),
+            args("moss analyze --callers main --tree --depth 3")
+        );
+
+        let config = HashMap::from([("callers".to_string(), "grep".to_string())]);
+        let expanded = expand_args(
+            args("moss callers x"),
+            &builtins(),
+            &with_defaults(&config),
+            None,
+        );
+        assert_eq!(expanded.unwrap(), args("moss grep x"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_args_pass_through() {
+        use std::os::unix::ffi::OsStringExt;
+        let path = OsString::from_vec(b"caf\xe9.py".to_vec());
+        let aliases = HashMap::from([("al".to_string(), "analyze --health".to_string())]);
+
+        let mut argv = args("moss al");
+        argv.push(path.clone());
+        let mut expected = args("moss analyze --health");
+        expected.push(path.clone());
+        assert_eq!(
+            expand_args(argv, &builtins(), &aliases, None).unwrap(),
+            expected
+        );
+
+        // A non-UTF-8 command token is left to the default command
+        let argv = vec![OsString::from("moss"), path.clone()];
+        let expected = vec![OsString::from("moss"), OsString::from("view"), path];
+        let expanded = expand_args(argv, &builtins(), &aliases, Some("view"));
+        assert_eq!(expanded.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_default_command() {
+        let aliases = HashMap::new();
+        let expanded = expand_args(
+            args("moss --jq .name foo"),
+            &builtins(),
+            &aliases,
+            Some("view"),
+        );
+        assert_eq!(expanded.unwrap(), args("moss --jq .name view foo"));
+
+        let expanded = expand_args(
+            args("moss -o out.json foo"),
+            &builtins(),
+            &aliases,
+            Some("view"),
+        );
+        assert_eq!(expanded.unwrap(), args("moss -o out.json view foo"));
+
+        // No command at all: leave it to clap (prints help)
+        let expanded = expand_args(args("moss --help"), &builtins(), &aliases, Some("view"));
+        assert_eq!(expanded.unwrap(), args("moss --help"));
+    }
+}
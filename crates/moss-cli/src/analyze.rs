@@ -3,6 +3,7 @@
 //! `moss analyze [path]` with flags:
 //! - `--health` - codebase health metrics
 //! - `--complexity` - cyclomatic complexity analysis
+//! - `--cognitive` - cognitive complexity analysis
 //! - `--security` - security vulnerability scanning
 //! - (no flags) - run all analyses
 
@@ -10,10 +11,14 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
-use crate::complexity::{ComplexityAnalyzer, ComplexityReport};
+use crate::complexity::{
+    ComplexityAnalyzer, ComplexityReport, FunctionComplexity, DEFAULT_COGNITIVE_THRESHOLD,
+    DEFAULT_THRESHOLD,
+};
 use crate::filter::Filter;
 use crate::health::{analyze_health, HealthReport};
 use crate::path_resolve;
+use moss_languages::support_for_path;
 
 /// Severity levels for security findings
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -134,11 +139,70 @@ impl SecurityReport {
 pub struct AnalyzeReport {
     pub health: Option<HealthReport>,
     pub complexity: Option<ComplexityReport>,
+    /// Functions above this complexity are flagged
+    pub complexity_threshold: usize,
+    /// Same functions as `complexity`, ordered by cognitive complexity
+    pub cognitive: Option<ComplexityReport>,
+    /// Functions above this cognitive complexity are flagged
+    pub cognitive_threshold: usize,
     pub security: Option<SecurityReport>,
     pub target_path: String,
     pub skipped: Vec<String>,
 }
 
+/// Table of `file:line  name  score`, most complex first: every function
+/// above `threshold`, and at least the top 10.
+fn push_function_rows(
+    sections: &mut Vec<String>,
+    heading: &str,
+    report: &ComplexityReport,
+    score: impl Fn(&FunctionComplexity) -> usize,
+    threshold: usize,
+) {
+    if report.functions.is_empty() {
+        return;
+    }
+    sections.push(String::new());
+    sections.push(heading.to_string());
+    let flagged = report
+        .functions
+        .iter()
+        .filter(|f| score(f) > threshold)
+        .count();
+    let shown = flagged.max(10);
+    let rows: Vec<_> = report
+        .functions
+        .iter()
+        .take(shown)
+        .map(|func| {
+            let file = func.file_path.as_deref().unwrap_or(&report.file_path);
+            (format!("{}:{}", file, func.start_line), func)
+        })
+        .collect();
+    let loc_width = rows.iter().map(|(loc, _)| loc.len()).max().unwrap_or(0);
+    let name_width = rows
+        .iter()
+        .map(|(_, f)| f.short_name().len())
+        .max()
+        .unwrap_or(0);
+    for (loc, func) in &rows {
+        let flag = if score(func) > threshold { "  !" } else { "" };
+        sections.push(format!(
+            "  {:loc_width$}  {:name_width$}  {:3}{}",
+            loc,
+            func.short_name(),
+            score(func),
+            flag
+        ));
+    }
+    if report.functions.len() > shown {
+        sections.push(format!(
+            "  ... {} more (--json for the full list)",
+            report.functions.len() - shown
+        ));
+    }
+}
+
 impl AnalyzeReport {
     pub fn format(&self) -> String {
         let mut sections = Vec::new();
@@ -158,28 +222,44 @@ impl AnalyzeReport {
             sections.push(format!("Average: {:.1}", complexity.avg_complexity()));
             sections.push(format!("Maximum: {}", complexity.max_complexity()));
             sections.push(format!("High risk (>10): {}", complexity.high_risk_count()));
+            let flagged = complexity.above_threshold(self.complexity_threshold);
+            sections.push(format!(
+                "Above threshold (>{}): {}",
+                self.complexity_threshold, flagged
+            ));
 
-            if !complexity.functions.is_empty() {
-                sections.push(String::new());
-                sections.push("## Top Complex Functions".to_string());
-                let mut sorted: Vec<_> = complexity.functions.iter().collect();
-                sorted.sort_by(|a, b| b.complexity.cmp(&a.complexity));
-                for func in sorted.iter().take(10) {
-                    // Use short_name for cleaner display (no file path prefix)
-                    let display_name = if func.file_path.is_some() {
-                        // For codebase-wide reports, show file:short_name
-                        format!("{}:{}", func.file_path.as_ref().unwrap(), func.short_name())
-                    } else {
-                        func.short_name()
-                    };
-                    sections.push(format!(
-                        "  {:3} {} ({})",
-                        func.complexity,
-                        display_name,
-                        func.risk_level()
-                    ));
-                }
-            }
+            push_function_rows(
+                &mut sections,
+                "## Functions by Complexity",
+                complexity,
+                |f| f.complexity,
+                self.complexity_threshold,
+            );
+            sections.push(String::new());
+        }
+
+        if let Some(ref cognitive) = self.cognitive {
+            sections.push("# Cognitive Complexity".to_string());
+            sections.push(String::new());
+            sections.push(format!("Functions: {}", cognitive.functions.len()));
+            sections.push(format!("Average: {:.1}", cognitive.avg_cognitive()));
+            sections.push(format!("Maximum: {}", cognitive.max_cognitive()));
+            let flagged = cognitive
+                .functions
+                .iter()
+                .filter(|f| f.cognitive > self.cognitive_threshold)
+                .count();
+            sections.push(format!(
+                "Above threshold (>{}): {}",
+                self.cognitive_threshold, flagged
+            ));
+            push_function_rows(
+                &mut sections,
+                "## Functions by Cognitive Complexity",
+                cognitive,
+                |f| f.cognitive,
+                self.cognitive_threshold,
+            );
             sections.push(String::new());
         }
 
@@ -216,17 +296,34 @@ impl AnalyzeReport {
                     })
                 })
                 .collect();
+            let diagnostics = health.diagnostics.as_ref().map(|d| {
+                serde_json::json!({
+                    "errors": d.errors,
+                    "warnings": d.warnings,
+                    "tools": d.tools,
+                })
+            });
+            let dependencies: serde_json::Map<_, _> = health
+                .dependencies
+                .iter()
+                .map(|d| (d.ecosystem.clone(), serde_json::json!(d.count)))
+                .collect();
             obj.insert(
                 "health".to_string(),
                 serde_json::json!({
+                    "score": health.score(),
+                    "grade": health.grade(),
                     "total_files": health.total_files,
                     "files_by_language": health.files_by_language,
                     "total_lines": health.total_lines,
+                    "total_symbols": health.total_symbols,
                     "avg_complexity": health.avg_complexity,
                     "max_complexity": health.max_complexity,
                     "high_risk_functions": health.high_risk_functions,
                     "total_functions": health.total_functions,
                     "large_files": large_files,
+                    "diagnostics": diagnostics,
+                    "dependencies": dependencies,
                 }),
             );
         }
@@ -241,9 +338,13 @@ impl AnalyzeReport {
                         "parent": f.parent,
                         "short_name": f.short_name(),
                         "qualified_name": f.qualified_name(),
+                        "file": f.file_path.as_deref().unwrap_or(&complexity.file_path),
                         "complexity": f.complexity,
+                        "cognitive": f.cognitive,
                         "line": f.start_line,
+                        "end_line": f.end_line,
                         "risk_level": f.risk_level(),
+                        "flagged": f.complexity > self.complexity_threshold,
                     })
                 })
                 .collect();
@@ -256,6 +357,38 @@ impl AnalyzeReport {
                     "avg_complexity": complexity.avg_complexity(),
                     "max_complexity": complexity.max_complexity(),
                     "high_risk_count": complexity.high_risk_count(),
+                    "threshold": self.complexity_threshold,
+                    "above_threshold": complexity.above_threshold(self.complexity_threshold),
+                }),
+            );
+        }
+
+        if let Some(ref cognitive) = self.cognitive {
+            let functions: Vec<_> = cognitive
+                .functions
+                .iter()
+                .map(|f| {
+                    serde_json::json!({
+                        "name": f.name,
+                        "parent": f.parent,
+                        "short_name": f.short_name(),
+                        "file": f.file_path.as_deref().unwrap_or(&cognitive.file_path),
+                        "line": f.start_line,
+                        "end_line": f.end_line,
+                        "cognitive": f.cognitive,
+                        "flagged": f.cognitive > self.cognitive_threshold,
+                    })
+                })
+                .collect();
+
+            obj.insert(
+                "cognitive".to_string(),
+                serde_json::json!({
+                    "file": cognitive.file_path,
+                    "functions": functions,
+                    "avg_cognitive": cognitive.avg_cognitive(),
+                    "max_cognitive": cognitive.max_cognitive(),
+                    "threshold": self.cognitive_threshold,
                 }),
             );
         }
@@ -400,28 +533,15 @@ pub fn analyze_file_complexity(file_path: &Path) -> Option<ComplexityReport> {
     Some(analyzer.analyze(file_path, &content))
 }
 
-/// Analyze complexity across entire codebase, returning top N functions
-pub fn analyze_codebase_complexity(
-    root: &Path,
-    limit: usize,
-    threshold: Option<usize>,
-    filter: Option<&Filter>,
-) -> ComplexityReport {
+/// Analyze complexity of every function in the codebase, most complex first
+pub fn analyze_codebase_complexity(root: &Path, filter: Option<&Filter>) -> ComplexityReport {
     use crate::path_resolve;
     use rayon::prelude::*;
 
     let all_files = path_resolve::all_files(root);
     let code_files: Vec<_> = all_files
         .iter()
-        .filter(|f| {
-            f.kind == "file" && {
-                let ext = std::path::Path::new(&f.path)
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .unwrap_or("");
-                matches!(ext, "py" | "rs")
-            }
-        })
+        .filter(|f| f.kind == "file" && support_for_path(Path::new(&f.path)).is_some())
         // Apply filter if provided
         .filter(|f| {
             filter
@@ -453,24 +573,12 @@ pub fn analyze_codebase_complexity(
         .flatten()
         .collect();
 
-    // Filter by threshold if specified
-    let mut filtered: Vec<_> = if let Some(t) = threshold {
-        all_functions
-            .into_iter()
-            .filter(|f| f.complexity >= t)
-            .collect()
-    } else {
-        all_functions
-    };
-
-    // Sort by complexity descending and take top N
-    filtered.sort_by(|a, b| b.complexity.cmp(&a.complexity));
-    filtered.truncate(limit);
-
-    ComplexityReport {
-        functions: filtered,
+    let mut report = ComplexityReport {
+        functions: all_functions,
         file_path: root.to_string_lossy().to_string(),
-    }
+    };
+    report.sort();
+    report
 }
 
 /// Run unified analysis on a path
@@ -479,6 +587,7 @@ pub fn analyze(
     root: &Path,
     run_health: bool,
     run_complexity: bool,
+    run_cognitive: bool,
     run_security: bool,
     complexity_threshold: Option<usize>,
     kind_filter: Option<&str>,
@@ -533,21 +642,17 @@ pub fn analyze(
         None
     };
 
-    let complexity = if run_complexity {
+    // Both complexity metrics come from the same per-function pass
+    let complexity = if run_complexity || run_cognitive {
         if !is_file {
-            // Codebase-wide complexity: show top 10 most complex functions
+            // Codebase-wide complexity
             let analysis_root = if let Some(ref fp) = file_path {
                 root.join(fp)
             } else {
                 root.to_path_buf()
             };
             if analysis_root.is_dir() {
-                Some(analyze_codebase_complexity(
-                    &analysis_root,
-                    10,
-                    complexity_threshold,
-                    filter,
-                ))
+                Some(analyze_codebase_complexity(&analysis_root, filter))
             } else {
                 None
             }
@@ -580,11 +685,6 @@ pub fn analyze(
                 }
             }
 
-            // Apply threshold filter
-            if let (Some(ref mut r), Some(threshold)) = (&mut report, complexity_threshold) {
-                r.functions.retain(|f| f.complexity >= threshold);
-            }
-
             // Apply kind filter (function = no parent, method = has parent)
             if let (Some(ref mut r), Some(k)) = (&mut report, &kind) {
                 match *k {
@@ -594,6 +694,11 @@ pub fn analyze(
                 }
             }
 
+            if let Some(ref mut r) = report {
+                r.file_path = fp.clone();
+                r.sort();
+            }
+
             report
         } else {
             None
@@ -602,6 +707,16 @@ pub fn analyze(
         None
     };
 
+    let cognitive = if run_cognitive {
+        complexity.clone().map(|mut report| {
+            report.sort_by_cognitive();
+            report
+        })
+    } else {
+        None
+    };
+    let complexity = if run_complexity { complexity } else { None };
+
     let security = if run_security && !has_symbol_target {
         // Security doesn't apply to single symbols
         let analysis_root = if let Some(ref fp) = file_path {
@@ -617,6 +732,9 @@ pub fn analyze(
     AnalyzeReport {
         health,
         complexity,
+        complexity_threshold: complexity_threshold.unwrap_or(DEFAULT_THRESHOLD),
+        cognitive,
+        cognitive_threshold: complexity_threshold.unwrap_or(DEFAULT_COGNITIVE_THRESHOLD),
         security,
         target_path: target_path.to_string(),
         skipped,
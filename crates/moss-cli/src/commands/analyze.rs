@@ -1,6 +1,7 @@
 //! Analyze command - run analysis on target.
 
 use crate::analyze;
+use crate::commands::cache::{dir_size, format_size, global_cache_dir};
 use crate::commands::filter::detect_project_languages;
 use crate::config::MossConfig;
 use crate::daemon;
@@ -8,8 +9,13 @@ use crate::filter::Filter;
 use crate::index;
 use crate::overview;
 use crate::path_resolve;
-use moss_tools::registry_with_custom;
-use std::path::{Path, PathBuf};
+use crate::paths::current_project_root;
+use crate::tool_cache::{self, ToolCache};
+use moss_tools::{
+    registry_with_custom, run_concurrently, DiagnosticSeverity, Tool, ToolRegistry, ToolResult,
+};
+use std::collections::HashMap;
+use std::path::Path;
 
 /// Run analysis on a target (file or directory)
 #[allow(clippy::too_many_arguments)]
@@ -18,6 +24,7 @@ pub fn cmd_analyze(
     root: Option<&Path>,
     health: bool,
     complexity: bool,
+    cognitive: bool,
     security: bool,
     show_overview: bool,
     show_storage: bool,
@@ -26,9 +33,18 @@ pub fn cmd_analyze(
     kind_filter: Option<&str>,
     callees: bool,
     callers: bool,
+    tree_depth: Option<usize>,
+    include_tests: bool,
     lint: bool,
+    fix: bool,
+    allow_dirty: bool,
+    no_cache: bool,
+    min_severity: Option<DiagnosticSeverity>,
+    error_on: Option<DiagnosticSeverity>,
     hotspots: bool,
     check_refs: bool,
+    dead_code: bool,
+    import_cycles: bool,
     json: bool,
     exclude: &[String],
     only: &[String],
@@ -45,7 +61,7 @@ pub fn cmd_analyze(
 
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     // Ensure daemon is running if configured
     daemon::maybe_start_daemon(&root);
@@ -73,6 +89,10 @@ pub fn cmd_analyze(
     };
 
     // --callees or --callers: show call graph info
+    if tree_depth.is_some() && !(callees || callers) {
+        eprintln!("--tree requires --callers or --callees");
+        return 1;
+    }
     if callees || callers {
         let target = match target {
             Some(t) => t,
@@ -81,12 +101,26 @@ pub fn cmd_analyze(
                 return 1;
             }
         };
-        return cmd_call_graph(&root, target, callers, callees, json);
+        let scope = if include_tests {
+            index::CallerScope::All
+        } else {
+            index::CallerScope::SkipExcluded
+        };
+        if let Some(depth) = tree_depth {
+            return cmd_call_tree(&root, target, callers, callees, depth, scope, json);
+        }
+        return cmd_call_graph(&root, target, callers, callees, scope, json);
     }
 
-    // --lint runs linter analysis
-    if lint {
-        return cmd_lint_analyze(&root, target, json);
+    // --lint runs linter analysis (--fix applies fixes first)
+    if fix {
+        if !crate::commands::lint::fix_allowed(&root, allow_dirty) {
+            return 1;
+        }
+        return cmd_lint_analyze(&root, target, true, no_cache, min_severity, error_on, json);
+    }
+    if lint || min_severity.is_some() || error_on.is_some() {
+        return cmd_lint_analyze(&root, target, false, no_cache, min_severity, error_on, json);
     }
 
     // --hotspots runs git history hotspot analysis
@@ -99,8 +133,18 @@ pub fn cmd_analyze(
         return cmd_check_refs(&root, json);
     }
 
+    // --dead-code lists functions nothing calls
+    if dead_code {
+        return cmd_dead_code(&root, json);
+    }
+
+    // --import-cycles finds circular imports
+    if import_cycles {
+        return cmd_import_cycles(&root, json);
+    }
+
     // If no specific flags, run all analyses
-    let any_flag = health || complexity || security;
+    let any_flag = health || complexity || cognitive || security;
     let (run_health, run_complexity, run_security) = if !any_flag {
         (true, true, true)
     } else {
@@ -112,6 +156,7 @@ pub fn cmd_analyze(
         &root,
         run_health,
         run_complexity,
+        cognitive,
         run_security,
         threshold,
         kind_filter,
@@ -127,8 +172,23 @@ pub fn cmd_analyze(
     0
 }
 
-/// Run linter analysis on the codebase
-fn cmd_lint_analyze(root: &Path, target: Option<&str>, json: bool) -> i32 {
+/// Run linter analysis on the codebase. With `fix`, tools that can fix
+/// issues apply their fixes first, and the remaining issues are reported
+/// along with how many were resolved. Unless `no_cache`, file-scoped tools
+/// only re-check files that changed since their last run.
+///
+/// Diagnostics below `min_severity` are dropped before reporting. Exits
+/// non-zero if a tool failed, or if any reported diagnostic is at or above
+/// `error_on`.
+fn cmd_lint_analyze(
+    root: &Path,
+    target: Option<&str>,
+    fix: bool,
+    no_cache: bool,
+    min_severity: Option<DiagnosticSeverity>,
+    error_on: Option<DiagnosticSeverity>,
+    json: bool,
+) -> i32 {
     let registry = registry_with_custom(root);
     let detected = registry.detect(root);
 
@@ -144,70 +204,139 @@ fn cmd_lint_analyze(root: &Path, target: Option<&str>, json: bool) -> i32 {
     let paths: Vec<&Path> = target.map(|t| vec![Path::new(t)]).unwrap_or_default();
     let mut all_results = Vec::new();
     let mut tools_run = Vec::new();
+    // Issues resolved by fixing, per tool name
+    let mut fixed: HashMap<String, usize> = HashMap::new();
+    let cache = if no_cache {
+        None
+    } else {
+        ToolCache::open(root)
+            .map_err(|e| eprintln!("warning: tool cache unavailable: {}", e))
+            .ok()
+    };
 
-    for (tool, _reason) in &detected {
-        let info = tool.info();
+    let available: Vec<&dyn Tool> = detected
+        .iter()
+        .map(|(tool, _)| *tool)
+        .filter(|tool| tool.is_available())
+        .collect();
 
-        if !tool.is_available() {
-            continue;
+    // Fixers rewrite the same files (e.g. black and isort), so they take turns
+    let mut before: HashMap<&str, usize> = HashMap::new();
+    if fix {
+        for tool in available.iter().filter(|tool| tool.can_fix()) {
+            let info = tool.info();
+            if !json {
+                eprintln!("{}: fixing...", info.name);
+            }
+
+            // Count issues before fixing, so re-checking afterwards shows what was resolved
+            if let Ok(result) = tool.run(&paths, root) {
+                before.insert(info.name, result.diagnostics.len());
+            }
+            match tool.fix(&paths, root) {
+                Ok(result) if !result.success => {
+                    if !json {
+                        eprintln!(
+                            "{}: fix failed: {}",
+                            info.name,
+                            result.error.as_deref().unwrap_or("unknown error")
+                        );
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    if !json {
+                        eprintln!("{}: fix failed: {}", info.name, e);
+                    }
+                }
+            }
         }
+    }
 
+    let jobs = MossConfig::load(root).lint.jobs();
+    let outcomes = run_concurrently(&available, jobs, |tool| {
+        let info = tool.info();
         if !json {
             eprintln!("{}: checking...", info.name);
         }
+        match &cache {
+            Some(cache) if info.file_scoped => tool_cache::run_cached(tool, target, root, cache),
+            _ => tool.run(&paths, root),
+        }
+    });
 
-        match tool.run(&paths.iter().copied().collect::<Vec<_>>(), root) {
+    let mut failed = false;
+    for (tool, outcome) in available.iter().zip(outcomes) {
+        let info = tool.info();
+        match outcome {
             Ok(result) => {
+                if let Some(before) = before.get(info.name) {
+                    fixed.insert(
+                        info.name.to_string(),
+                        before.saturating_sub(result.diagnostics.len()),
+                    );
+                }
                 tools_run.push(info.name);
                 all_results.push(result);
             }
             Err(e) => {
+                failed = true;
                 if !json {
                     eprintln!("{}: {}", info.name, e);
                 }
             }
         }
     }
+    tools_run.sort();
+    ToolRegistry::sort_results(&mut all_results);
+    if let Some(min) = min_severity {
+        for result in &mut all_results {
+            result.diagnostics.retain(|d| d.severity.at_least(min));
+        }
+    }
 
     let total_errors: usize = all_results.iter().map(|r| r.error_count()).sum();
     let total_warnings: usize = all_results.iter().map(|r| r.warning_count()).sum();
+    let total_info = count_severity(&all_results, DiagnosticSeverity::Info);
+    let total_hints = count_severity(&all_results, DiagnosticSeverity::Hint);
+    let total_fixed: usize = fixed.values().sum();
 
     if json {
-        let diagnostics = moss_tools::ToolRegistry::collect_diagnostics(&all_results);
-        let output = serde_json::json!({
+        let diagnostics = ToolRegistry::collect_diagnostics(&all_results);
+        let mut output = serde_json::json!({
             "tools": tools_run,
             "summary": {
                 "errors": total_errors,
                 "warnings": total_warnings,
+                "info": total_info,
+                "hints": total_hints,
             },
             "results": all_results.iter().map(|r| {
-                serde_json::json!({
+                let mut result = serde_json::json!({
                     "tool": r.tool,
                     "success": r.success,
                     "errors": r.error_count(),
                     "warnings": r.warning_count(),
-                })
+                });
+                if let Some(n) = fixed.get(&r.tool) {
+                    result["fixed"] = serde_json::json!(n);
+                }
+                result
             }).collect::<Vec<_>>(),
             "diagnostics": diagnostics,
         });
+        if fix {
+            output["summary"]["fixed"] = serde_json::json!(total_fixed);
+        }
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
     } else {
         // Print diagnostics
         for result in &all_results {
             for diag in &result.diagnostics {
-                let severity = match diag.severity {
-                    moss_tools::DiagnosticSeverity::Error => "error",
-                    moss_tools::DiagnosticSeverity::Warning => "warning",
-                    moss_tools::DiagnosticSeverity::Info => "info",
-                    moss_tools::DiagnosticSeverity::Hint => "hint",
-                };
-
                 println!(
-                    "{}:{}:{}: {} [{}] {}",
-                    diag.location.file.display(),
-                    diag.location.line,
-                    diag.location.column,
-                    severity,
+                    "{}: {} [{}] {}",
+                    diag.location,
+                    diag.severity.as_str(),
                     diag.rule_id,
                     diag.message
                 );
@@ -220,26 +349,181 @@ fn cmd_lint_analyze(root: &Path, target: Option<&str>, json: bool) -> i32 {
         println!("  Tools: {}", tools_run.join(", "));
         println!("  Errors: {}", total_errors);
         println!("  Warnings: {}", total_warnings);
+        if total_info > 0 {
+            println!("  Info: {}", total_info);
+        }
+        if total_hints > 0 {
+            println!("  Hints: {}", total_hints);
+        }
+        if fix {
+            let mut tools: Vec<_> = fixed.iter().filter(|(_, n)| **n > 0).collect();
+            tools.sort();
+            let detail: Vec<String> = tools.iter().map(|(t, n)| format!("{} {}", t, n)).collect();
+            if detail.is_empty() {
+                println!("  Fixed: {}", total_fixed);
+            } else {
+                println!("  Fixed: {} ({})", total_fixed, detail.join(", "));
+            }
+        }
 
-        if total_errors > 0 {
+        if total_errors > 0 && !fix {
             println!();
-            println!("Run 'moss lint --fix' to auto-fix issues where possible.");
+            println!("Run 'moss analyze --fix' to auto-fix issues where possible.");
         }
     }
 
-    if total_errors > 0 {
+    let tool_failed = failed || all_results.iter().any(|r| !r.success);
+    let threshold_hit = error_on.is_some_and(|threshold| {
+        all_results
+            .iter()
+            .flat_map(|r| &r.diagnostics)
+            .any(|d| d.severity.at_least(threshold))
+    });
+    if tool_failed || threshold_hit {
         1
     } else {
         0
     }
 }
 
+/// Number of diagnostics with exactly this severity.
+fn count_severity(results: &[ToolResult], severity: DiagnosticSeverity) -> usize {
+    results
+        .iter()
+        .flat_map(|r| &r.diagnostics)
+        .filter(|d| d.severity == severity)
+        .count()
+}
+
+/// Failure message for [`open_call_graph`] in `--json` envelopes (its
+/// stderr output has the specifics).
+pub const CALL_GRAPH_UNAVAILABLE: &str =
+    "Call graph index unavailable. Run: moss index rebuild --call-graph";
+
+/// Open the index for call graph queries, re-parsing files edited since the
+/// call graph was built.
+pub fn open_call_graph(root: &Path) -> Option<index::FileIndex> {
+    let mut idx = match index::FileIndex::open(root) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!(
+                "Failed to open index: {}. Run: moss index rebuild --call-graph",
+                e
+            );
+            return None;
+        }
+    };
+
+    if idx.needs_call_graph_refresh() {
+        eprintln!("Call graph not indexed. Run: moss index rebuild --call-graph");
+        return None;
+    }
+    if let Err(e) = idx.incremental_refresh() {
+        eprintln!("warning: index may be stale: {}", e);
+    }
+    if let Err(e) = idx.incremental_call_graph_refresh() {
+        eprintln!("warning: call graph may be stale: {}", e);
+    }
+    Some(idx)
+}
+
+/// Show transitive callers/callees of a symbol as trees
+fn cmd_call_tree(
+    root: &Path,
+    target: &str,
+    show_callers: bool,
+    show_callees: bool,
+    depth: usize,
+    scope: index::CallerScope,
+    json: bool,
+) -> i32 {
+    let symbol = parse_file_symbol_string(target)
+        .map(|(sym, _)| sym)
+        .unwrap_or_else(|| target.to_string());
+    let Some(idx) = open_call_graph(root) else {
+        return 1;
+    };
+
+    let mut directions = Vec::new();
+    if show_callers {
+        directions.push(("callers", index::CallDirection::Callers));
+    }
+    if show_callees {
+        directions.push(("callees", index::CallDirection::Callees));
+    }
+
+    let mut trees = Vec::new();
+    for (label, direction) in directions {
+        match idx.call_tree(&symbol, depth, direction, scope) {
+            Ok(tree) => trees.push((label, tree)),
+            Err(e) => {
+                eprintln!("Error building call tree: {}", e);
+                return 1;
+            }
+        }
+    }
+
+    if trees.iter().all(|(_, tree)| tree.children.is_empty()) {
+        if json {
+            println!("{{}}");
+        } else {
+            let direction = if show_callers && show_callees {
+                "callers or callees"
+            } else if show_callers {
+                "callers"
+            } else {
+                "callees"
+            };
+            eprintln!("No {} found for: {}", direction, symbol);
+        }
+        return 1;
+    }
+
+    if json {
+        let output: serde_json::Map<String, serde_json::Value> = trees
+            .iter()
+            .map(|(label, tree)| (label.to_string(), serde_json::json!(tree)))
+            .collect();
+        println!("{}", serde_json::Value::Object(output));
+        return 0;
+    }
+
+    for (i, (label, tree)) in trees.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let label = if *label == "callers" {
+            "Callers"
+        } else {
+            "Callees"
+        };
+        println!("{} of {} (depth {}):", label, symbol, depth);
+        print_call_tree(tree, 0);
+    }
+    0
+}
+
+fn print_call_tree(node: &index::CallTreeNode, indent: usize) {
+    let mut line = format!("{}{}", "  ".repeat(indent + 1), node.symbol);
+    if let (Some(file), Some(call_line)) = (&node.file, node.line) {
+        line.push_str(&format!("  {}:{}", file, call_line));
+    }
+    if node.cycle {
+        line.push_str(" (recursive)");
+    }
+    println!("{}", line);
+    for child in &node.children {
+        print_call_tree(child, indent + 1);
+    }
+}
+
 /// Show callers/callees of a symbol
 fn cmd_call_graph(
     root: &Path,
     target: &str,
     show_callers: bool,
     show_callees: bool,
+    scope: index::CallerScope,
     json: bool,
 ) -> i32 {
     // Try to parse target as file:symbol or just symbol
@@ -249,32 +533,32 @@ fn cmd_call_graph(
         (target.to_string(), None)
     };
 
-    // Try index first
-    let idx = match index::FileIndex::open(root) {
-        Ok(i) => i,
-        Err(e) => {
-            eprintln!(
-                "Failed to open index: {}. Run: moss reindex --call-graph",
-                e
-            );
-            return 1;
-        }
-    };
-
-    let stats = idx.call_graph_stats().unwrap_or_default();
-    if stats.calls == 0 {
-        eprintln!("Call graph not indexed. Run: moss reindex --call-graph");
+    let Some(idx) = open_call_graph(root) else {
         return 1;
-    }
+    };
 
-    let mut results: Vec<(String, String, usize, &str)> = Vec::new(); // (file, symbol, line, direction)
+    let mut results: Vec<CallEdge> = Vec::new();
+    let mut hidden_callers = 0;
 
     // Get callers if requested
     if show_callers {
-        match idx.find_callers(&symbol) {
+        match idx.find_callers(&symbol, scope) {
             Ok(callers) => {
+                if scope == index::CallerScope::SkipExcluded {
+                    let all = idx
+                        .find_callers(&symbol, index::CallerScope::All)
+                        .map_or(0, |all| all.len());
+                    hidden_callers = all.saturating_sub(callers.len());
+                }
                 for (file, sym, line) in callers {
-                    results.push((file, sym, line, "caller"));
+                    results.push(CallEdge {
+                        file,
+                        symbol: sym,
+                        line,
+                        direction: "caller",
+                        definition: None,
+                        ambiguous: false,
+                    });
                 }
             }
             Err(e) => {
@@ -285,29 +569,36 @@ fn cmd_call_graph(
 
     // Get callees if requested
     if show_callees {
-        // Need to find file for symbol first
-        let file_path = if let Some(f) = &file_hint {
-            let matches = path_resolve::resolve(f, root);
-            matches
-                .iter()
+        // file:symbol only looks at the definition in that file
+        let file_path = file_hint.as_ref().and_then(|f| {
+            path_resolve::resolve(f, root)
+                .into_iter()
                 .find(|m| m.kind == "file")
-                .map(|m| m.path.clone())
-        } else {
-            idx.find_symbol(&symbol)
-                .ok()
-                .and_then(|syms| syms.first().map(|(f, _, _, _)| f.clone()))
-        };
+                .map(|m| m.path)
+        });
 
-        if let Some(file_path) = file_path {
-            match idx.find_callees(&file_path, &symbol) {
-                Ok(callees) => {
-                    for (name, line) in callees {
-                        results.push((file_path.clone(), name, line, "callee"));
+        match idx.find_callees_of(&symbol) {
+            Ok(callees) => {
+                for callee in callees {
+                    if file_path.as_ref().is_some_and(|f| *f != callee.file) {
+                        continue;
                     }
+                    let definition = callee
+                        .definition_file
+                        .zip(callee.definition_line)
+                        .map(|(file, line)| format!("{}:{}", file, line));
+                    results.push(CallEdge {
+                        file: callee.file,
+                        symbol: callee.callee,
+                        line: callee.line,
+                        direction: "callee",
+                        definition,
+                        ambiguous: callee.ambiguous,
+                    });
                 }
-                Err(e) => {
-                    eprintln!("Error finding callees: {}", e);
-                }
+            }
+            Err(e) => {
+                eprintln!("Error finding callees: {}", e);
             }
         }
     }
@@ -324,23 +615,29 @@ fn cmd_call_graph(
                 "callees"
             };
             eprintln!("No {} found for: {}", direction, symbol);
+            print_hidden_callers(hidden_callers);
         }
         return 1;
     }
 
     // Sort by file, then line
-    results.sort_by(|a, b| (&a.0, a.2).cmp(&(&b.0, b.2)));
+    results.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
 
     if json {
         let output: Vec<_> = results
             .iter()
-            .map(|(file, sym, line, direction)| {
-                serde_json::json!({
-                    "file": file,
-                    "symbol": sym,
-                    "line": line,
-                    "direction": direction
-                })
+            .map(|edge| {
+                let mut value = serde_json::json!({
+                    "file": edge.file,
+                    "symbol": edge.symbol,
+                    "line": edge.line,
+                    "direction": edge.direction
+                });
+                if edge.direction == "callee" {
+                    value["definition"] = serde_json::json!(edge.definition);
+                    value["ambiguous"] = serde_json::json!(edge.ambiguous);
+                }
+                value
             })
             .collect();
         println!("{}", serde_json::to_string(&output).unwrap());
@@ -353,16 +650,50 @@ fn cmd_call_graph(
             format!("Callees of {}", symbol)
         };
         println!("{}:", header);
-        for (file, sym, line, _direction) in &results {
-            println!("  {}:{}:{}", file, line, sym);
+        for edge in &results {
+            match (&edge.definition, edge.ambiguous) {
+                (Some(def), true) => println!(
+                    "  {}:{}:{} -> {} (ambiguous)",
+                    edge.file, edge.line, edge.symbol, def
+                ),
+                (Some(def), false) => {
+                    println!("  {}:{}:{} -> {}", edge.file, edge.line, edge.symbol, def)
+                }
+                (None, _) => println!("  {}:{}:{}", edge.file, edge.line, edge.symbol),
+            }
         }
+        print_hidden_callers(hidden_callers);
     }
 
     0
 }
 
+/// Note callers left out by `[index] call_graph_exclude`.
+fn print_hidden_callers(hidden: usize) {
+    if hidden > 0 {
+        eprintln!(
+            "({} caller(s) in excluded files hidden; use --include-tests to show them)",
+            hidden
+        );
+    }
+}
+
+/// A caller of, or call made by, the symbol being inspected.
+struct CallEdge {
+    /// File and line of the call
+    file: String,
+    line: usize,
+    /// Calling symbol (for callers) or called name (for callees)
+    symbol: String,
+    direction: &'static str,
+    /// Callee definition as file:line, if indexed
+    definition: Option<String>,
+    /// The callee matched several same-named definitions
+    ambiguous: bool,
+}
+
 /// Try various separators to parse file:symbol format
-fn parse_file_symbol_string(s: &str) -> Option<(String, String)> {
+pub fn parse_file_symbol_string(s: &str) -> Option<(String, String)> {
     // Try various separators: #, ::, :
     for sep in ["#", "::", ":"] {
         if let Some(idx) = s.find(sep) {
@@ -385,7 +716,7 @@ fn looks_like_file(s: &str) -> bool {
 fn cmd_overview(root: Option<&Path>, compact: bool, json: bool) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     let report = overview::analyze_overview(&root);
 
@@ -425,18 +756,25 @@ fn cmd_overview(root: Option<&Path>, compact: bool, json: bool) -> i32 {
 fn cmd_storage(root: Option<&Path>, json: bool) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     // Project index: .moss/index.sqlite
     let index_path = root.join(".moss").join("index.sqlite");
     let index_size = std::fs::metadata(&index_path).map(|m| m.len()).unwrap_or(0);
 
+    // Lint results: .moss/tool-cache.sqlite
+    let tool_cache_path = ToolCache::path(&root);
+    let tool_cache_size = std::fs::metadata(&tool_cache_path)
+        .map(|m| m.len())
+        .unwrap_or(0);
+    let project_size = index_size + tool_cache_size;
+
     // Package cache: ~/.cache/moss/packages/
-    let cache_dir = get_cache_dir().map(|d| d.join("packages"));
+    let cache_dir = global_cache_dir().map(|d| d.join("packages"));
     let cache_size = cache_dir.as_ref().map(|d| dir_size(d)).unwrap_or(0);
 
     // Global cache: ~/.cache/moss/ (total)
-    let global_cache_dir = get_cache_dir();
+    let global_cache_dir = global_cache_dir();
     let global_size = global_cache_dir.as_ref().map(|d| dir_size(d)).unwrap_or(0);
 
     if json {
@@ -448,6 +786,11 @@ fn cmd_storage(root: Option<&Path>, json: bool) -> i32 {
                     "bytes": index_size,
                     "human": format_size(index_size),
                 },
+                "tool_cache": {
+                    "path": tool_cache_path.display().to_string(),
+                    "bytes": tool_cache_size,
+                    "human": format_size(tool_cache_size),
+                },
                 "package_cache": {
                     "path": cache_dir.as_ref().map(|d| d.display().to_string()),
                     "bytes": cache_size,
@@ -458,8 +801,8 @@ fn cmd_storage(root: Option<&Path>, json: bool) -> i32 {
                     "bytes": global_size,
                     "human": format_size(global_size),
                 },
-                "total_bytes": index_size + global_size,
-                "total_human": format_size(index_size + global_size),
+                "total_bytes": project_size + global_size,
+                "total_human": format_size(project_size + global_size),
             })
         );
     } else {
@@ -470,6 +813,11 @@ fn cmd_storage(root: Option<&Path>, json: bool) -> i32 {
             format_size(index_size),
             index_path.display()
         );
+        println!(
+            "Lint cache:      {:>10}  {}",
+            format_size(tool_cache_size),
+            tool_cache_path.display()
+        );
         if let Some(ref cache) = cache_dir {
             println!(
                 "Package cache:   {:>10}  {}",
@@ -487,63 +835,13 @@ fn cmd_storage(root: Option<&Path>, json: bool) -> i32 {
         println!();
         println!(
             "Total:           {:>10}",
-            format_size(index_size + global_size)
+            format_size(project_size + global_size)
         );
     }
 
     0
 }
 
-/// Get cache directory: ~/.cache/moss
-fn get_cache_dir() -> Option<PathBuf> {
-    if let Ok(cache) = std::env::var("XDG_CACHE_HOME") {
-        Some(PathBuf::from(cache).join("moss"))
-    } else if let Ok(home) = std::env::var("HOME") {
-        Some(PathBuf::from(home).join(".cache").join("moss"))
-    } else if let Ok(home) = std::env::var("USERPROFILE") {
-        Some(PathBuf::from(home).join(".cache").join("moss"))
-    } else {
-        None
-    }
-}
-
-/// Calculate total size of a directory recursively
-fn dir_size(path: &Path) -> u64 {
-    if !path.exists() {
-        return 0;
-    }
-
-    let mut total = 0;
-    if let Ok(entries) = std::fs::read_dir(path) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_file() {
-                total += std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
-            } else if path.is_dir() {
-                total += dir_size(&path);
-            }
-        }
-    }
-    total
-}
-
-/// Format bytes as human-readable size
-fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
-    }
-}
-
 /// Hotspot data for a file
 #[derive(Debug)]
 struct FileHotspot {
@@ -770,6 +1068,80 @@ struct BrokenRef {
     context: String,
 }
 
+/// List functions and methods with no recorded callers
+fn cmd_dead_code(root: &Path, json: bool) -> i32 {
+    let Some(idx) = open_call_graph(root) else {
+        return 1;
+    };
+    let unused = match crate::dead_code::find_possibly_unused(
+        &idx,
+        root,
+        &MossConfig::load(root).symbols.rust_cfg(),
+    ) {
+        Ok(u) => u,
+        Err(e) => {
+            eprintln!("Error reading call graph: {}", e);
+            return 1;
+        }
+    };
+
+    if json {
+        println!("{}", serde_json::json!({ "possibly_unused": unused }));
+        return 0;
+    }
+    if unused.is_empty() {
+        println!("No possibly unused functions found");
+        return 0;
+    }
+    println!(
+        "Possibly unused (no recorded callers; dynamic dispatch and reflection aren't tracked):"
+    );
+    for u in &unused {
+        let name = match &u.parent {
+            Some(parent) => format!("{}.{}", parent, u.name),
+            None => u.name.clone(),
+        };
+        println!("  {}:{} {} {}", u.file, u.line, u.kind, name);
+    }
+    println!();
+    println!("{} possibly unused", unused.len());
+    0
+}
+
+fn cmd_import_cycles(root: &Path, json: bool) -> i32 {
+    let Some(idx) = open_call_graph(root) else {
+        return 1;
+    };
+    let cycles = match idx.import_cycles() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading imports: {}", e);
+            return 1;
+        }
+    };
+
+    if json {
+        println!("{}", serde_json::json!({ "cycles": cycles }));
+    } else if cycles.is_empty() {
+        println!("No import cycles found");
+    } else {
+        for cycle in &cycles {
+            println!("Import cycle ({} files):", cycle.len());
+            for file in cycle {
+                println!("  {}", file);
+            }
+        }
+        println!();
+        println!("{} import cycles", cycles.len());
+    }
+
+    if cycles.is_empty() {
+        0
+    } else {
+        1
+    }
+}
+
 /// Check documentation references for broken links
 fn cmd_check_refs(root: &Path, json: bool) -> i32 {
     use regex::Regex;
@@ -0,0 +1,323 @@
+//! Cache command - report and trim the global cache (~/.cache/moss).
+
+use crate::config::{CacheConfig, MossConfig};
+use crate::paths::current_project_root;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// SQLite sidecar suffixes, removed together with their database.
+const SIDECARS: &[&str] = &["-wal", "-shm", "-journal"];
+
+/// Get cache directory: ~/.cache/moss
+pub fn global_cache_dir() -> Option<PathBuf> {
+    if let Ok(cache) = std::env::var("XDG_CACHE_HOME") {
+        Some(PathBuf::from(cache).join("moss"))
+    } else if let Ok(home) = std::env::var("HOME") {
+        Some(PathBuf::from(home).join(".cache").join("moss"))
+    } else if let Ok(home) = std::env::var("USERPROFILE") {
+        Some(PathBuf::from(home).join(".cache").join("moss"))
+    } else {
+        None
+    }
+}
+
+/// Calculate total size of a directory recursively
+pub fn dir_size(path: &Path) -> u64 {
+    if !path.exists() {
+        return 0;
+    }
+
+    let mut total = 0;
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_file() {
+                total += std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            } else if path.is_dir() {
+                total += dir_size(&path);
+            }
+        }
+    }
+    total
+}
+
+/// Format bytes as human-readable size
+pub fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = KB * 1024;
+    const GB: u64 = MB * 1024;
+
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// A cache entry: one file plus any SQLite sidecars next to it.
+struct CacheEntry {
+    path: PathBuf,
+    bytes: u64,
+    modified: SystemTime,
+}
+
+/// Outcome of an eviction pass.
+#[derive(Debug, Default)]
+pub struct Eviction {
+    pub removed: usize,
+    pub freed: u64,
+    pub remaining: u64,
+}
+
+fn is_sidecar(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    SIDECARS.iter().any(|s| name.ends_with(s))
+}
+
+fn collect_entries(dir: &Path, out: &mut Vec<CacheEntry>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if meta.is_dir() {
+            collect_entries(&path, out);
+        } else if meta.is_file() && !is_sidecar(&path) {
+            let sidecar_bytes: u64 = SIDECARS
+                .iter()
+                .filter_map(|s| std::fs::metadata(format!("{}{}", path.display(), s)).ok())
+                .map(|m| m.len())
+                .sum();
+            out.push(CacheEntry {
+                bytes: meta.len() + sidecar_bytes,
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                path,
+            });
+        }
+    }
+}
+
+fn remove_entry(entry: &CacheEntry) -> bool {
+    if std::fs::remove_file(&entry.path).is_err() {
+        return false;
+    }
+    for s in SIDECARS {
+        let _ = std::fs::remove_file(format!("{}{}", entry.path.display(), s));
+    }
+    true
+}
+
+/// Trim a cache directory.
+///
+/// Removes files last written more than `max_age` ago, then the least recently
+/// written files until the directory fits in `max_bytes`.
+pub fn evict(dir: &Path, max_age: Option<Duration>, max_bytes: Option<u64>) -> Eviction {
+    let mut entries = Vec::new();
+    collect_entries(dir, &mut entries);
+    entries.sort_by_key(|e| e.modified);
+
+    let mut result = Eviction {
+        remaining: entries.iter().map(|e| e.bytes).sum(),
+        ..Default::default()
+    };
+    let cutoff = max_age.and_then(|age| SystemTime::now().checked_sub(age));
+
+    for entry in &entries {
+        let expired = cutoff.is_some_and(|cutoff| entry.modified < cutoff);
+        let over_budget = max_bytes.is_some_and(|max| result.remaining > max);
+        if !expired && !over_budget {
+            // Sorted oldest first: nothing later is expired either.
+            break;
+        }
+        if remove_entry(entry) {
+            result.removed += 1;
+            result.freed += entry.bytes;
+            result.remaining -= entry.bytes;
+        }
+    }
+    result
+}
+
+/// Enforce the configured size budget after writing to the global cache.
+pub fn enforce_limits(config: &CacheConfig) {
+    let (Some(max_bytes), Some(dir)) = (config.max_bytes(), global_cache_dir()) else {
+        return;
+    };
+    evict(&dir, None, Some(max_bytes));
+}
+
+/// Report or trim the global cache.
+pub fn cmd_cache(
+    clean: bool,
+    older_than: Option<u64>,
+    max_size: Option<u64>,
+    root: Option<&Path>,
+    json: bool,
+) -> i32 {
+    let Some(dir) = global_cache_dir() else {
+        eprintln!("Cannot determine cache directory (set HOME or XDG_CACHE_HOME)");
+        return 1;
+    };
+
+    if clean {
+        let root = root
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(current_project_root);
+        let config = MossConfig::load(&root).cache;
+        let max_age = match older_than.map(CacheConfig::days_to_duration) {
+            Some(None) => {
+                eprintln!("--older-than is too large");
+                return 1;
+            }
+            Some(age) => age,
+            None => config.max_age(),
+        };
+        let max_bytes = match max_size.map(CacheConfig::mb_to_bytes) {
+            Some(None) => {
+                eprintln!("--max-size is too large");
+                return 1;
+            }
+            Some(bytes) => bytes,
+            None => config.max_bytes(),
+        };
+        if max_age.is_none() && max_bytes.is_none() {
+            eprintln!(
+                "Nothing to clean by: pass --older-than/--max-size or set [cache] max_age_days/max_size_mb"
+            );
+            return 1;
+        }
+
+        let result = evict(&dir, max_age, max_bytes);
+        if json {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "path": dir.display().to_string(),
+                    "removed": result.removed,
+                    "freed_bytes": result.freed,
+                    "remaining_bytes": result.remaining,
+                })
+            );
+        } else {
+            println!(
+                "Removed {} file(s), freed {} ({} remaining)",
+                result.removed,
+                format_size(result.freed),
+                format_size(result.remaining)
+            );
+        }
+        return 0;
+    }
+
+    let mut entries: Vec<(String, u64)> = std::fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| {
+            let path = e.path();
+            let bytes = if path.is_dir() {
+                dir_size(&path)
+            } else {
+                e.metadata().map(|m| m.len()).unwrap_or(0)
+            };
+            (e.file_name().to_string_lossy().to_string(), bytes)
+        })
+        .collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let total: u64 = entries.iter().map(|(_, bytes)| bytes).sum();
+
+    if json {
+        let items: Vec<_> = entries
+            .iter()
+            .map(|(name, bytes)| serde_json::json!({"name": name, "bytes": bytes}))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "path": dir.display().to_string(),
+                "entries": items,
+                "total_bytes": total,
+                "total_human": format_size(total),
+            })
+        );
+    } else {
+        println!("Cache: {}", dir.display());
+        println!();
+        for (name, bytes) in &entries {
+            println!("  {:>10}  {}", format_size(*bytes), name);
+        }
+        println!();
+        println!("Total: {}", format_size(total));
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_aged(dir: &Path, name: &str, bytes: usize, age_secs: u64) {
+        let path = dir.join(name);
+        std::fs::write(&path, vec![0u8; bytes]).unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(age_secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_size_budget_evicts_oldest() {
+        let dir = TempDir::new().unwrap();
+        write_aged(dir.path(), "old.db", 100, 300);
+        write_aged(dir.path(), "old.db-wal", 20, 300);
+        write_aged(dir.path(), "mid.db", 100, 200);
+        write_aged(dir.path(), "new.db", 100, 100);
+
+        let result = evict(dir.path(), None, Some(250));
+        assert_eq!(result.removed, 1);
+        assert_eq!(result.freed, 120);
+        assert_eq!(result.remaining, 200);
+        assert!(!dir.path().join("old.db").exists());
+        assert!(!dir.path().join("old.db-wal").exists());
+        assert!(dir.path().join("mid.db").exists());
+        assert!(dir.path().join("new.db").exists());
+    }
+
+    #[test]
+    fn test_unit_conversion_overflow() {
+        assert_eq!(CacheConfig::mb_to_bytes(2), Some(2 * 1024 * 1024));
+        assert_eq!(CacheConfig::mb_to_bytes(u64::MAX), None);
+        assert_eq!(
+            CacheConfig::days_to_duration(1),
+            Some(Duration::from_secs(24 * 60 * 60))
+        );
+        assert_eq!(CacheConfig::days_to_duration(u64::MAX), None);
+
+        let config = CacheConfig {
+            max_size_mb: Some(u64::MAX),
+            max_age_days: Some(u64::MAX),
+        };
+        assert_eq!(config.max_bytes(), Some(u64::MAX));
+        assert_eq!(config.max_age(), Some(Duration::MAX));
+    }
+
+    #[test]
+    fn test_age_limit() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("packages")).unwrap();
+        write_aged(&dir.path().join("packages"), "stale", 10, 10 * 24 * 60 * 60);
+        write_aged(dir.path(), "fresh", 10, 60);
+
+        let result = evict(dir.path(), Some(Duration::from_secs(24 * 60 * 60)), None);
+        assert_eq!(result.removed, 1);
+        assert!(!dir.path().join("packages/stale").exists());
+        assert!(dir.path().join("fresh").exists());
+    }
+}
@@ -0,0 +1,241 @@
+//! Callgraph command - export the call graph for rendering.
+//!
+//! Writes the indexed calls as Graphviz DOT (`moss callgraph --format dot | dot -Tpng`)
+//! or a plain edge list, for the whole project or the neighborhood of one symbol.
+
+use crate::commands::analyze::open_call_graph;
+use crate::index::CallGraphEdge;
+use crate::paths::current_project_root;
+use std::collections::HashSet;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Output format for `moss callgraph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallGraphFormat {
+    /// One `file::caller -> file::callee` line per edge
+    Text,
+    /// Graphviz DOT
+    Dot,
+}
+
+impl FromStr for CallGraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(CallGraphFormat::Text),
+            "dot" => Ok(CallGraphFormat::Dot),
+            _ => Err(format!("unknown format '{}' (expected text or dot)", s)),
+        }
+    }
+}
+
+/// Export the call graph. With `focus`, only edges within `depth` calls of
+/// that symbol are kept, following callers, callees, or both (when neither
+/// direction is given).
+pub fn cmd_callgraph(
+    root: Option<&Path>,
+    format: CallGraphFormat,
+    focus: Option<&str>,
+    depth: usize,
+    callers: bool,
+    callees: bool,
+    json: bool,
+) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(current_project_root);
+
+    let Some(idx) = open_call_graph(&root) else {
+        return 1;
+    };
+    let edges = match idx.call_edges() {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("Error reading call graph: {}", e);
+            return 1;
+        }
+    };
+
+    let edges: Vec<&CallGraphEdge> = match focus {
+        Some(symbol) => {
+            let both = !callers && !callees;
+            let edges = neighborhood(&edges, symbol, depth, callers || both, callees || both);
+            if edges.is_empty() {
+                eprintln!("No calls found for: {}", symbol);
+                return 1;
+            }
+            edges
+        }
+        None => edges.iter().collect(),
+    };
+
+    if json {
+        println!("{}", serde_json::to_string(&edges).unwrap());
+        return 0;
+    }
+    match format {
+        CallGraphFormat::Dot => print!("{}", format_dot(&edges)),
+        CallGraphFormat::Text => {
+            for edge in &edges {
+                println!(
+                    "{} -> {}",
+                    node_id(&edge.caller_file, &edge.caller),
+                    node_id(&edge.callee_file, &edge.callee)
+                );
+            }
+        }
+    }
+    0
+}
+
+/// Edges reachable from symbols named `symbol` within `depth` calls,
+/// following edges backwards (`callers`) and/or forwards (`callees`).
+fn neighborhood<'a>(
+    edges: &'a [CallGraphEdge],
+    symbol: &str,
+    depth: usize,
+    callers: bool,
+    callees: bool,
+) -> Vec<&'a CallGraphEdge> {
+    let mut kept: HashSet<&CallGraphEdge> = HashSet::new();
+    let mut visited: HashSet<&str> = HashSet::from([symbol]);
+    let mut frontier: Vec<&str> = vec![symbol];
+
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for edge in edges {
+            if callees && frontier.contains(&edge.caller.as_str()) {
+                kept.insert(edge);
+                if visited.insert(&edge.callee) {
+                    next.push(edge.callee.as_str());
+                }
+            }
+            if callers && frontier.contains(&edge.callee.as_str()) {
+                kept.insert(edge);
+                if visited.insert(&edge.caller) {
+                    next.push(edge.caller.as_str());
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    // Keep the index's sorted order
+    edges.iter().filter(|e| kept.contains(e)).collect()
+}
+
+fn node_id(file: &str, symbol: &str) -> String {
+    format!("{}::{}", file, symbol)
+}
+
+/// Quote a DOT identifier.
+fn dot_quote(id: &str) -> String {
+    let escaped = id
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+/// Format edges as a Graphviz digraph with `file::symbol` nodes.
+fn format_dot(edges: &[&CallGraphEdge]) -> String {
+    let mut out = String::from("digraph callgraph {\n    rankdir=LR;\n    node [shape=box];\n");
+    let mut seen = HashSet::new();
+    for edge in edges {
+        let line = format!(
+            "    {} -> {};\n",
+            dot_quote(&node_id(&edge.caller_file, &edge.caller)),
+            dot_quote(&node_id(&edge.callee_file, &edge.callee))
+        );
+        if seen.insert(line.clone()) {
+            out.push_str(&line);
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::FileIndex;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn edge(caller: &str, callee: &str) -> CallGraphEdge {
+        CallGraphEdge {
+            caller_file: "a.py".to_string(),
+            caller: caller.to_string(),
+            callee_file: "a.py".to_string(),
+            callee: callee.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_dot() {
+        let quoted = CallGraphEdge {
+            caller_file: "dir \"x\"/a.py".to_string(),
+            ..edge("main", "helper")
+        };
+        let plain = edge("main", "helper");
+        let dot = format_dot(&[&plain, &plain, &quoted]);
+
+        assert!(dot.starts_with("digraph callgraph {\n"));
+        assert!(dot.ends_with("}\n"));
+        // Duplicate edges collapse
+        assert_eq!(
+            dot.matches("\"a.py::main\" -> \"a.py::helper\";").count(),
+            1
+        );
+        assert!(dot.contains("\"dir \\\"x\\\"/a.py::main\" -> \"a.py::helper\";"));
+    }
+
+    #[test]
+    fn test_neighborhood() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.py"),
+            "def main():\n    parse()\n\ndef parse():\n    walk()\n\ndef walk():\n    walk()\n\ndef other():\n    walk()\n",
+        )
+        .unwrap();
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+        let edges = index.call_edges().unwrap();
+        assert_eq!(edges.len(), 4);
+
+        let pairs = |edges: Vec<&CallGraphEdge>| -> Vec<(String, String)> {
+            edges
+                .iter()
+                .map(|e| (e.caller.clone(), e.callee.clone()))
+                .collect()
+        };
+        let pair = |a: &str, b: &str| (a.to_string(), b.to_string());
+
+        assert_eq!(
+            pairs(neighborhood(&edges, "parse", 1, true, true)),
+            vec![pair("main", "parse"), pair("parse", "walk")]
+        );
+        assert_eq!(
+            pairs(neighborhood(&edges, "main", 5, false, true)),
+            vec![
+                pair("main", "parse"),
+                pair("parse", "walk"),
+                pair("walk", "walk")
+            ]
+        );
+        assert_eq!(
+            pairs(neighborhood(&edges, "walk", 1, true, false)),
+            vec![
+                pair("other", "walk"),
+                pair("parse", "walk"),
+                pair("walk", "walk")
+            ]
+        );
+    }
+}
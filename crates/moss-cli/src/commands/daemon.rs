@@ -1,7 +1,7 @@
 //! Daemon management commands for moss CLI.
 
 use crate::daemon;
-use crate::paths::get_moss_dir;
+use crate::paths::{current_project_root, get_moss_dir};
 use clap::Subcommand;
 use std::path::Path;
 
@@ -24,7 +24,7 @@ pub enum DaemonAction {
 pub fn cmd_daemon(action: DaemonAction, root: Option<&Path>, json: bool) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     let client = daemon::DaemonClient::new(&root);
 
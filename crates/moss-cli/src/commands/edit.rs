@@ -1,9 +1,13 @@
 //! Edit command for moss CLI.
 
+use crate::commands::analyze::open_call_graph;
 use crate::commands::filter::detect_project_languages;
 use crate::config::MossConfig;
 use crate::filter::Filter;
+use crate::paths::current_project_root;
+use crate::rename::{self, RenameScope};
 use crate::{daemon, edit, path_resolve};
+use std::io::Read;
 use std::path::Path;
 
 /// Perform structural edits on a file
@@ -26,6 +30,11 @@ pub fn cmd_edit(
     copy_prepend: Option<&str>,
     copy_append: Option<&str>,
     swap: Option<&str>,
+    replace_body: Option<&str>,
+    no_backup: bool,
+    rename: Option<&str>,
+    scope: RenameScope,
+    apply: bool,
     dry_run: bool,
     json: bool,
     exclude: &[String],
@@ -33,7 +42,7 @@ pub fn cmd_edit(
 ) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     // Ensure daemon is running if configured (will pick up edits)
     daemon::maybe_start_daemon(&root);
@@ -55,11 +64,13 @@ pub fn cmd_edit(
         copy_prepend.is_some(),
         copy_append.is_some(),
         swap.is_some(),
+        replace_body.is_some(),
+        rename.is_some(),
     ];
     let op_count = ops.iter().filter(|&&x| x).count();
 
     if op_count == 0 {
-        eprintln!("Error: No operation specified. Use --delete, --replace, --before, --after, --prepend, --append, --move-*, --copy-*, or --swap");
+        eprintln!("Error: No operation specified. Use --delete, --replace, --before, --after, --prepend, --append, --move-*, --copy-*, --swap, --replace-body, or --rename");
         return 1;
     }
     if op_count > 1 {
@@ -110,6 +121,22 @@ pub fn cmd_edit(
         }
     }
 
+    if let Some(new_name) = rename {
+        let Some(old_name) = unified.symbol_path.last() else {
+            eprintln!("Error: --rename requires a symbol target");
+            return 1;
+        };
+        return cmd_rename(
+            &root,
+            &unified.file_path,
+            old_name,
+            new_name,
+            scope,
+            apply,
+            json,
+        );
+    }
+
     let file_path = root.join(&unified.file_path);
     let content = match std::fs::read_to_string(&file_path) {
         Ok(c) => c,
@@ -396,6 +423,36 @@ pub fn cmd_edit(
         new.replace_range(second_loc.start_byte..second_loc.end_byte, &first_content);
         new.replace_range(first_loc.start_byte..first_loc.end_byte, &second_content);
         ("swap", new)
+    } else if let Some(new_body) = replace_body {
+        let body = match editor.find_function_body(&file_path, &content, symbol_name) {
+            Some(b) => b,
+            None => {
+                eprintln!("Error: '{}' is not a function with a body", symbol_name);
+                return 1;
+            }
+        };
+        let new_body = if new_body == "-" {
+            let mut input = String::new();
+            if let Err(e) = std::io::stdin().read_to_string(&mut input) {
+                eprintln!("Error reading body from stdin: {}", e);
+                return 1;
+            }
+            input
+        } else {
+            new_body.to_string()
+        };
+        let new = editor.replace_function_body(&content, &body, &new_body);
+        // Reject bodies that break the file, but not files that were already broken
+        if editor.has_syntax_errors(&file_path, &new)
+            && !editor.has_syntax_errors(&file_path, &content)
+        {
+            eprintln!(
+                "Error: new body for {} doesn't parse; {} left unchanged",
+                symbol_name, unified.file_path
+            );
+            return 1;
+        }
+        ("replace_body", new)
     } else {
         eprintln!("Error: No valid operation");
         return 1;
@@ -420,6 +477,15 @@ pub fn cmd_edit(
         return 0;
     }
 
+    if replace_body.is_some() && !no_backup {
+        let mut backup = file_path.clone().into_os_string();
+        backup.push(".bak");
+        if let Err(e) = std::fs::write(&backup, &content) {
+            eprintln!("Error writing backup: {}", e);
+            return 1;
+        }
+    }
+
     if let Err(e) = std::fs::write(&file_path, &new_content) {
         eprintln!("Error writing file: {}", e);
         return 1;
@@ -441,3 +507,91 @@ pub fn cmd_edit(
 
     0
 }
+
+/// Rename a symbol and the references the call graph ties to it
+fn cmd_rename(
+    root: &Path,
+    file: &str,
+    old_name: &str,
+    new_name: &str,
+    scope: RenameScope,
+    apply: bool,
+    json: bool,
+) -> i32 {
+    let Some(index) = open_call_graph(root) else {
+        return 1;
+    };
+    let plan = match rename::plan_rename(&index, root, file, old_name, new_name, scope) {
+        Ok(plan) => plan,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+
+    if apply {
+        let mut transaction = edit::EditTransaction::new(root);
+        let staged = plan
+            .files
+            .iter()
+            .try_for_each(|renamed| transaction.write(&renamed.file, renamed.content.clone()));
+        if let Err(e) = staged.and_then(|()| transaction.commit()) {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    }
+
+    if json {
+        let files: Vec<_> = plan
+            .files
+            .iter()
+            .map(|f| serde_json::json!({"file": f.file, "changes": f.changes}))
+            .collect();
+        println!(
+            "{}",
+            serde_json::json!({
+                "applied": apply,
+                "files": files,
+                "skipped": plan.skipped,
+            })
+        );
+        return 0;
+    }
+
+    for renamed in &plan.files {
+        println!("--- {}", renamed.file);
+        println!("+++ {}", renamed.file);
+        for change in &renamed.changes {
+            println!("@@ {} @@", change.line);
+            println!("-{}", change.before);
+            println!("+{}", change.after);
+        }
+    }
+    if !plan.skipped.is_empty() {
+        println!();
+        println!(
+            "Outside --scope file, not renamed: {}",
+            plan.skipped.join(", ")
+        );
+    }
+    let lines: usize = plan.files.iter().map(|f| f.changes.len()).sum();
+    println!();
+    if apply {
+        println!(
+            "Renamed {} -> {} on {} lines in {} files",
+            old_name,
+            new_name,
+            lines,
+            plan.files.len()
+        );
+    } else {
+        println!(
+            "Would rename {} -> {} on {} lines in {} files (re-run with --apply to write)",
+            old_name,
+            new_name,
+            lines,
+            plan.files.len()
+        );
+    }
+    0
+}
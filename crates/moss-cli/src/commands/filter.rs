@@ -2,6 +2,7 @@
 
 use crate::config::MossConfig;
 use crate::filter::{list_aliases, AliasStatus};
+use crate::paths::current_project_root;
 use crate::FilterAction;
 use std::path::Path;
 
@@ -9,7 +10,7 @@ use std::path::Path;
 pub fn cmd_filter(action: FilterAction, root: Option<&Path>, json: bool) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     match action {
         FilterAction::Aliases => cmd_filter_aliases(&root, json),
@@ -0,0 +1,489 @@
+//! Graph command - import dependency graphs.
+//!
+//! `moss graph files` lists which files import which, and finds import
+//! cycles. `moss graph modules` collapses the same graph into directories
+//! and checks the edges against the `[graph.allow]` layering rules.
+
+use crate::commands::analyze::open_call_graph;
+use crate::config::MossConfig;
+use crate::index::FileImport;
+use crate::paths::current_project_root;
+use crate::GraphAction;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Output format for `moss graph`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// One `from -> to (imports)` line per edge
+    Text,
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+impl FromStr for GraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(GraphFormat::Text),
+            "dot" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            _ => Err(format!(
+                "unknown format '{}' (expected text, dot or mermaid)",
+                s
+            )),
+        }
+    }
+}
+
+/// Imports from one module into another.
+#[derive(Debug, Clone, serde::Serialize)]
+struct ModuleEdge {
+    from: String,
+    to: String,
+    /// File-level imports collapsed into this edge
+    imports: usize,
+    /// `from` has layering rules and `to` isn't allowed by them
+    violation: bool,
+}
+
+/// Handle graph subcommands.
+pub fn cmd_graph(action: GraphAction, root: Option<&Path>, json: bool) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(current_project_root);
+
+    match action {
+        GraphAction::Files { format, cycles } => cmd_graph_files(&root, format, cycles, json),
+        GraphAction::Modules { format, depth } => cmd_graph_modules(&root, format, depth, json),
+    }
+}
+
+/// File-level import graph. With `cycles_only`, lists just the import
+/// cycles and exits non-zero if there are any.
+fn cmd_graph_files(root: &Path, format: GraphFormat, cycles_only: bool, json: bool) -> i32 {
+    let Some(idx) = open_call_graph(root) else {
+        return 1;
+    };
+    let (imports, cycles) = match (idx.resolved_imports(), idx.import_cycles()) {
+        (Ok(i), Ok(c)) => (i, c),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Error reading imports: {}", e);
+            return 1;
+        }
+    };
+
+    if cycles_only {
+        if json {
+            println!("{}", serde_json::json!({ "cycles": cycles }));
+        } else if cycles.is_empty() {
+            println!("No import cycles");
+        } else {
+            for cycle in &cycles {
+                println!("Import cycle ({} files):", cycle.len());
+                for file in cycle {
+                    println!("  {}", file);
+                }
+            }
+        }
+        return if cycles.is_empty() { 0 } else { 1 };
+    }
+
+    let cycle_of: HashMap<&str, usize> = cycles
+        .iter()
+        .enumerate()
+        .flat_map(|(i, cycle)| cycle.iter().map(move |file| (file.as_str(), i)))
+        .collect();
+    let edges: Vec<FileEdge> = imports
+        .iter()
+        .map(|i| {
+            let from = cycle_of.get(i.file.as_str());
+            FileEdge {
+                from: i.file.clone(),
+                to: i.target.clone(),
+                cycle: from.is_some() && from == cycle_of.get(i.target.as_str()),
+            }
+        })
+        .collect();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "edges": edges, "cycles": cycles })
+        );
+    } else {
+        match format {
+            GraphFormat::Text => {
+                for edge in &edges {
+                    let mark = if edge.cycle { "  (cycle)" } else { "" };
+                    println!("{} -> {}{}", edge.from, edge.to, mark);
+                }
+            }
+            GraphFormat::Dot => print!("{}", format_files_dot(&edges)),
+            GraphFormat::Mermaid => print!("{}", format_files_mermaid(&edges)),
+        }
+        if !cycles.is_empty() {
+            eprintln!("{} import cycles (see --cycles)", cycles.len());
+        }
+    }
+    0
+}
+
+/// An import from one file into another.
+#[derive(Debug, Clone, serde::Serialize)]
+struct FileEdge {
+    from: String,
+    to: String,
+    /// Part of an import cycle
+    cycle: bool,
+}
+
+fn cmd_graph_modules(root: &Path, format: GraphFormat, depth: usize, json: bool) -> i32 {
+    let Some(idx) = open_call_graph(root) else {
+        return 1;
+    };
+    let imports = match idx.resolved_imports() {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("Error reading imports: {}", e);
+            return 1;
+        }
+    };
+    let allow = MossConfig::load(root).graph.allow;
+    let edges = module_edges(&imports, depth.max(1), &allow);
+    let violations = edges.iter().filter(|e| e.violation).count();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "edges": edges, "violations": violations })
+        );
+    } else {
+        match format {
+            GraphFormat::Text => print!("{}", format_text(&edges, &allow)),
+            GraphFormat::Dot => print!("{}", format_dot(&edges)),
+            GraphFormat::Mermaid => print!("{}", format_mermaid(&edges)),
+        }
+        if violations > 0 {
+            eprintln!("{} layering violations", violations);
+        }
+    }
+
+    if violations > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+/// The module a file belongs to: its first `depth` directory components,
+/// or "." for files at the root.
+fn module_of(path: &str, depth: usize) -> String {
+    let Some((dir, _)) = path.rsplit_once('/') else {
+        return ".".to_string();
+    };
+    dir.split('/').take(depth).collect::<Vec<_>>().join("/")
+}
+
+/// Collapse file imports into counted module edges, flagging edges that
+/// `allow` doesn't permit. Imports within a module are dropped.
+fn module_edges(
+    imports: &[FileImport],
+    depth: usize,
+    allow: &HashMap<String, Vec<String>>,
+) -> Vec<ModuleEdge> {
+    let mut counts: BTreeMap<(String, String), usize> = BTreeMap::new();
+    for import in imports {
+        let from = module_of(&import.file, depth);
+        let to = module_of(&import.target, depth);
+        if from != to {
+            *counts.entry((from, to)).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|((from, to), imports)| {
+            let violation = allow
+                .get(&from)
+                .is_some_and(|allowed| !allowed.contains(&to));
+            ModuleEdge {
+                from,
+                to,
+                imports,
+                violation,
+            }
+        })
+        .collect()
+}
+
+fn format_text(edges: &[ModuleEdge], allow: &HashMap<String, Vec<String>>) -> String {
+    let mut out = String::new();
+    for edge in edges {
+        out.push_str(&format!("{} -> {} ({})", edge.from, edge.to, edge.imports));
+        if edge.violation {
+            out.push_str(&format!(
+                "  violation: {} may only depend on [{}]",
+                edge.from,
+                allow[&edge.from].join(", ")
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a DOT label.
+fn quote(label: &str) -> String {
+    format!("\"{}\"", label.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quote a Mermaid label. Mermaid has no backslash escapes; a quote is the
+/// `#quot;` entity.
+fn mermaid_quote(label: &str) -> String {
+    format!("\"{}\"", label.replace('"', "#quot;"))
+}
+
+fn format_dot(edges: &[ModuleEdge]) -> String {
+    let mut out = String::from("digraph modules {\n    rankdir=LR;\n    node [shape=box];\n");
+    for edge in edges {
+        let style = if edge.violation { ", color=red" } else { "" };
+        out.push_str(&format!(
+            "    {} -> {} [label=\"{}\"{}];\n",
+            quote(&edge.from),
+            quote(&edge.to),
+            edge.imports,
+            style
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Mermaid node ids can't contain `/` or `.`, so nodes get numbered ids
+/// with the module as label. Violations are drawn red via `linkStyle`.
+fn format_mermaid(edges: &[ModuleEdge]) -> String {
+    let mut ids: BTreeMap<&str, usize> = BTreeMap::new();
+    for edge in edges {
+        let next = ids.len();
+        ids.entry(&edge.from).or_insert(next);
+        let next = ids.len();
+        ids.entry(&edge.to).or_insert(next);
+    }
+
+    let mut out = String::from("graph LR\n");
+    for (module, id) in &ids {
+        out.push_str(&format!("    m{}[{}]\n", id, mermaid_quote(module)));
+    }
+    for edge in edges {
+        out.push_str(&format!(
+            "    m{} -->|{}| m{}\n",
+            ids[edge.from.as_str()],
+            edge.imports,
+            ids[edge.to.as_str()]
+        ));
+    }
+    for (i, edge) in edges.iter().enumerate() {
+        if edge.violation {
+            out.push_str(&format!("    linkStyle {} stroke:red\n", i));
+        }
+    }
+    out
+}
+
+/// Cycle edges are drawn red.
+fn format_files_dot(edges: &[FileEdge]) -> String {
+    let mut out = String::from("digraph files {\n    rankdir=LR;\n    node [shape=box];\n");
+    for edge in edges {
+        let style = if edge.cycle { " [color=red]" } else { "" };
+        out.push_str(&format!(
+            "    {} -> {}{};\n",
+            quote(&edge.from),
+            quote(&edge.to),
+            style
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Like [`format_mermaid`], with cycle edges drawn red.
+fn format_files_mermaid(edges: &[FileEdge]) -> String {
+    let mut ids: BTreeMap<&str, usize> = BTreeMap::new();
+    for edge in edges {
+        let next = ids.len();
+        ids.entry(&edge.from).or_insert(next);
+        let next = ids.len();
+        ids.entry(&edge.to).or_insert(next);
+    }
+
+    let mut out = String::from("graph LR\n");
+    for (file, id) in &ids {
+        out.push_str(&format!("    f{}[{}]\n", id, mermaid_quote(file)));
+    }
+    for edge in edges {
+        out.push_str(&format!(
+            "    f{} --> f{}\n",
+            ids[edge.from.as_str()],
+            ids[edge.to.as_str()]
+        ));
+    }
+    for (i, edge) in edges.iter().enumerate() {
+        if edge.cycle {
+            out.push_str(&format!("    linkStyle {} stroke:red\n", i));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::FileIndex;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_module_of() {
+        assert_eq!(module_of("setup.py", 1), ".");
+        assert_eq!(module_of("src/a/x.py", 1), "src");
+        assert_eq!(module_of("src/a/x.py", 2), "src/a");
+        assert_eq!(module_of("src/x.py", 2), "src");
+    }
+
+    #[test]
+    fn test_label_quoting() {
+        let edges = [ModuleEdge {
+            from: r#"say "hi""#.to_string(),
+            to: r"win\path".to_string(),
+            imports: 1,
+            violation: false,
+        }];
+        let mermaid = format_mermaid(&edges);
+        assert!(mermaid.contains(r#"["say #quot;hi#quot;"]"#), "{}", mermaid);
+        assert!(mermaid.contains(r#"["win\path"]"#), "{}", mermaid);
+        let dot = format_dot(&edges);
+        assert!(dot.contains(r#""say \"hi\"" -> "win\\path""#), "{}", dot);
+    }
+
+    #[test]
+    fn test_relative_imports_resolve() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("pkg")).unwrap();
+        fs::create_dir_all(dir.path().join("web/lib")).unwrap();
+        fs::write(dir.path().join("pkg/__init__.py"), "").unwrap();
+        fs::write(
+            dir.path().join("pkg/a.py"),
+            "from .b import helper
+
+def run():
+    helper()
+",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("pkg/b.py"),
+            "from . import a
+
+def helper():
+    a.run()
+",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("web/lib/util.ts"),
+            "import { main } from '../main';
+export function util() { main(); }
+",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("web/main.ts"),
+            "import { util } from './lib/util';
+import * as fs from 'fs';
+export function main() { util(); }
+",
+        )
+        .unwrap();
+
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+        let imports = index.resolved_imports().unwrap();
+        let edges: Vec<(&str, &str)> = imports
+            .iter()
+            .map(|i| (i.file.as_str(), i.target.as_str()))
+            .collect();
+        // `from . import a` names the package; 'fs' is external and dropped
+        assert_eq!(
+            edges,
+            [
+                ("pkg/a.py", "pkg/b.py"),
+                ("pkg/b.py", "pkg/__init__.py"),
+                ("web/lib/util.ts", "web/main.ts"),
+                ("web/main.ts", "web/lib/util.ts"),
+            ]
+        );
+        assert_eq!(
+            index.import_cycles().unwrap(),
+            [vec!["web/lib/util.ts", "web/main.ts"]]
+        );
+    }
+
+    #[test]
+    fn test_layering_violation() {
+        let dir = tempdir().unwrap();
+        for d in ["cli", "core", "db", "util"] {
+            fs::create_dir_all(dir.path().join(d)).unwrap();
+        }
+        fs::write(
+            dir.path().join("cli/main.py"),
+            "from core.engine import run\nfrom db.store import save\n\ndef main():\n    run()\n    save()\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("cli/args.py"),
+            "import core.engine\n\ndef parse():\n    core.engine.run()\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("core/engine.py"),
+            "from util.helpers import log\n\ndef run():\n    log()\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("db/store.py"), "def save():\n    pass\n").unwrap();
+        fs::write(dir.path().join("util/helpers.py"), "def log():\n    pass\n").unwrap();
+
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+        let imports = index.resolved_imports().unwrap();
+
+        let allow = HashMap::from([
+            ("cli".to_string(), vec!["core".to_string()]),
+            ("core".to_string(), vec!["util".to_string()]),
+        ]);
+        let edges = module_edges(&imports, 1, &allow);
+        let summary: Vec<(&str, &str, usize, bool)> = edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str(), e.imports, e.violation))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("cli", "core", 2, false),
+                // cli isn't allowed to reach into db
+                ("cli", "db", 1, true),
+                ("core", "util", 1, false),
+            ]
+        );
+
+        let dot = format_dot(&edges);
+        assert!(dot.contains("\"cli\" -> \"db\" [label=\"1\", color=red];"));
+        let mermaid = format_mermaid(&edges);
+        assert!(mermaid.contains("linkStyle 1 stroke:red"));
+    }
+}
@@ -5,6 +5,7 @@ use crate::config::MossConfig;
 use crate::filter::Filter;
 use crate::grep;
 use crate::output::{OutputFormat, OutputFormatter};
+use crate::paths::current_project_root;
 use std::path::Path;
 
 /// Search file contents for a pattern
@@ -20,7 +21,7 @@ pub fn cmd_grep(
 ) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     // Build filter for --exclude and --only
     let filter = if !exclude.is_empty() || !only.is_empty() {
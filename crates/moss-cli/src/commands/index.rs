@@ -1,7 +1,8 @@
 //! Index management commands.
 
+use crate::config::MossConfig;
 use crate::index;
-use crate::paths::get_moss_dir;
+use crate::paths::{current_project_root, get_moss_dir};
 use crate::skeleton;
 use clap::Subcommand;
 use moss_languages::external_packages;
@@ -14,6 +15,11 @@ pub enum IndexAction {
         /// Also rebuild the call graph (slower, parses all files)
         #[arg(short, long = "call-graph")]
         call_graph: bool,
+
+        /// Descend into symlinked directories, even ones pointing outside the
+        /// project. Later refreshes of this index keep following them.
+        #[arg(long)]
+        follow_symlinks: bool,
     },
 
     /// Show index statistics (DB size vs codebase size)
@@ -38,18 +44,42 @@ pub enum IndexAction {
         /// Clear existing index before re-indexing
         #[arg(long)]
         clear: bool,
+
+        /// Remove packages whose path no longer exists instead of indexing
+        #[arg(long, conflicts_with_all = ["clear", "only"])]
+        prune: bool,
+
+        /// Python versions to index (3.9,3.11,3.12), each from its own
+        /// interpreter (project venv, pyenv, or pythonX.Y on PATH). Defaults to
+        /// the project's interpreter.
+        #[arg(long, value_delimiter = ',', conflicts_with = "prune")]
+        python_version: Vec<String>,
     },
 }
 
 /// Run an index management action
 pub fn cmd_index(action: IndexAction, root: Option<&Path>, json: bool) -> i32 {
     match action {
-        IndexAction::Rebuild { call_graph } => cmd_rebuild(root, call_graph),
+        IndexAction::Rebuild {
+            call_graph,
+            follow_symlinks,
+        } => cmd_rebuild(root, call_graph, follow_symlinks),
         IndexAction::Stats => cmd_stats(root, json),
         IndexAction::Files { prefix, limit } => {
             cmd_list_files(prefix.as_deref(), root, limit, json)
         }
-        IndexAction::Packages { only, clear } => cmd_packages(&only, clear, root, json),
+        IndexAction::Packages {
+            only,
+            clear,
+            prune,
+            python_version,
+        } => {
+            if prune {
+                cmd_prune_packages(json)
+            } else {
+                cmd_packages(&only, clear, &python_version, root, json)
+            }
+        }
     }
 }
 
@@ -57,13 +87,16 @@ pub fn cmd_index(action: IndexAction, root: Option<&Path>, json: bool) -> i32 {
 // Rebuild
 // =============================================================================
 
-fn cmd_rebuild(root: Option<&Path>, call_graph: bool) -> i32 {
+fn cmd_rebuild(root: Option<&Path>, call_graph: bool, follow_symlinks: bool) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     match index::FileIndex::open(&root) {
-        Ok(mut idx) => match idx.refresh() {
+        Ok(mut idx) => match idx
+            .set_follow_symlinks(follow_symlinks)
+            .and_then(|_| idx.refresh())
+        {
             Ok(count) => {
                 println!("Indexed {} files", count);
 
@@ -74,6 +107,18 @@ fn cmd_rebuild(root: Option<&Path>, call_graph: bool) -> i32 {
                                 "Indexed {} symbols, {} calls, {} imports",
                                 stats.symbols, stats.calls, stats.imports
                             );
+                            if stats.partial_files > 0 {
+                                println!(
+                                    "{} files partially indexed (symbol cap reached, see symbols.max_per_file)",
+                                    stats.partial_files
+                                );
+                            }
+                            if stats.excluded_files > 0 {
+                                println!(
+                                    "{} files excluded by build constraints (see index.go_target)",
+                                    stats.excluded_files
+                                );
+                            }
                         }
                         Err(e) => {
                             eprintln!("Error indexing call graph: {}", e);
@@ -99,26 +144,10 @@ fn cmd_rebuild(root: Option<&Path>, call_graph: bool) -> i32 {
 // Stats
 // =============================================================================
 
-/// Check if a file is binary by looking for null bytes
-fn is_binary_file(path: &Path) -> bool {
-    use std::io::Read;
-
-    let Ok(mut file) = std::fs::File::open(path) else {
-        return false;
-    };
-
-    let mut buffer = [0u8; 8192];
-    let Ok(bytes_read) = file.read(&mut buffer) else {
-        return false;
-    };
-
-    buffer[..bytes_read].contains(&0)
-}
-
 fn cmd_stats(root: Option<&Path>, json: bool) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     let moss_dir = get_moss_dir(&root);
     let db_path = moss_dir.join("index.sqlite");
@@ -153,14 +182,8 @@ fn cmd_stats(root: Option<&Path>, json: bool) -> i32 {
         let path = std::path::Path::new(&f.path);
         let ext = match path.extension().and_then(|e| e.to_str()) {
             Some(e) => e.to_string(),
-            None => {
-                let full_path = root.join(&f.path);
-                if is_binary_file(&full_path) {
-                    "(binary)".to_string()
-                } else {
-                    "(no ext)".to_string()
-                }
-            }
+            None if f.is_binary => "(binary)".to_string(),
+            None => "(no ext)".to_string(),
         };
         *ext_counts.entry(ext).or_insert(0) += 1;
     }
@@ -169,6 +192,8 @@ fn cmd_stats(root: Option<&Path>, json: bool) -> i32 {
     ext_list.sort_by(|a, b| b.1.cmp(&a.1));
 
     let stats = idx.call_graph_stats().unwrap_or_default();
+    let partial_files = idx.partial_files().unwrap_or_default();
+    let excluded_files = idx.excluded_files().unwrap_or_default();
 
     // Calculate codebase size
     let mut codebase_size: u64 = 0;
@@ -191,6 +216,8 @@ fn cmd_stats(root: Option<&Path>, json: bool) -> i32 {
             "symbol_count": stats.symbols,
             "call_count": stats.calls,
             "import_count": stats.imports,
+            "partial_files": partial_files,
+            "excluded_files": excluded_files,
             "extensions": ext_list.iter().take(20).map(|(e, c)| serde_json::json!({"ext": e, "count": c})).collect::<Vec<_>>()
         });
         println!("{}", serde_json::to_string_pretty(&output).unwrap());
@@ -220,6 +247,23 @@ fn cmd_stats(root: Option<&Path>, json: bool) -> i32 {
         println!("Symbols:      {}", stats.symbols);
         println!("Calls:        {}", stats.calls);
         println!("Imports:      {}", stats.imports);
+        if !partial_files.is_empty() {
+            println!();
+            println!("Partially indexed (symbol cap reached):");
+            for file in &partial_files {
+                println!("  {} ({} symbols)", file.path, file.total_symbols);
+            }
+        }
+        if !excluded_files.is_empty() {
+            println!();
+            println!(
+                "Excluded by build constraints (target {}):",
+                MossConfig::load(&root).index.go_target()
+            );
+            for file in &excluded_files {
+                println!("  {} ({})", file.path, file.reason);
+            }
+        }
         println!();
         println!("Top extensions:");
         for (ext, count) in ext_list.iter().take(15) {
@@ -237,7 +281,7 @@ fn cmd_stats(root: Option<&Path>, json: bool) -> i32 {
 fn cmd_list_files(prefix: Option<&str>, root: Option<&Path>, limit: usize, json: bool) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
 
     let idx = match index::FileIndex::open(&root) {
         Ok(idx) => idx,
@@ -284,10 +328,16 @@ struct IndexedCounts {
     symbols: usize,
 }
 
-fn cmd_packages(only: &[String], clear: bool, root: Option<&Path>, json: bool) -> i32 {
+fn cmd_packages(
+    only: &[String],
+    clear: bool,
+    python_versions: &[String],
+    root: Option<&Path>,
+    json: bool,
+) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+        .unwrap_or_else(current_project_root);
 
     let pkg_index = match external_packages::PackageIndex::open() {
         Ok(idx) => idx,
@@ -343,9 +393,15 @@ fn cmd_packages(only: &[String], clear: bool, root: Option<&Path>, json: bool) -
         if results.contains_key(lang_key) {
             continue;
         }
-        let counts = index_language_packages(lang, &pkg_index, &root, json);
+        let counts = if lang_key == "python" && !python_versions.is_empty() {
+            index_python_versions(lang, &pkg_index, &root, python_versions, json)
+        } else {
+            index_language_packages(lang, &pkg_index, &root, json)
+        };
         results.insert(lang_key, counts);
     }
+    drop(pkg_index);
+    super::cache::enforce_limits(&MossConfig::load(&root).cache);
 
     if json {
         let mut json_obj = serde_json::Map::new();
@@ -373,6 +429,40 @@ fn cmd_packages(only: &[String], clear: bool, root: Option<&Path>, json: bool) -
     0
 }
 
+fn cmd_prune_packages(json: bool) -> i32 {
+    let pkg_index = match external_packages::PackageIndex::open() {
+        Ok(idx) => idx,
+        Err(e) => {
+            eprintln!("Failed to open package index: {}", e);
+            return 1;
+        }
+    };
+
+    let counts = match pkg_index.prune() {
+        Ok(counts) => counts,
+        Err(e) => {
+            eprintln!("Failed to prune index: {}", e);
+            return 1;
+        }
+    };
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "pruned_packages": counts.packages,
+                "pruned_symbols": counts.symbols,
+            })
+        );
+    } else {
+        println!(
+            "Pruned {} packages, {} symbols",
+            counts.packages, counts.symbols
+        );
+    }
+    0
+}
+
 fn count_and_insert_symbols(
     pkg_index: &external_packages::PackageIndex,
     pkg_id: i64,
@@ -475,6 +565,96 @@ fn index_language_packages(
     }
 }
 
+/// Versions a package path was found under, as the range it's indexed with.
+struct VersionRange {
+    min: external_packages::Version,
+    max: external_packages::Version,
+}
+
+/// Index the stdlib and site-packages of several Python versions. A package
+/// directory found under several interpreters is recorded once, with the
+/// range from the lowest to the highest of them.
+fn index_python_versions(
+    lang: &dyn moss_languages::Language,
+    pkg_index: &external_packages::PackageIndex,
+    project_root: &Path,
+    versions: &[String],
+    json: bool,
+) -> IndexedCounts {
+    let mut ranges: std::collections::BTreeMap<(String, PathBuf), VersionRange> =
+        std::collections::BTreeMap::new();
+    for version_str in versions {
+        let Some(version) = external_packages::Version::parse(version_str) else {
+            eprintln!(
+                "Error: invalid Python version '{}' (expected X.Y)",
+                version_str
+            );
+            continue;
+        };
+        let version_str = format!("{}.{}", version.major, version.minor);
+        let Some(install) = moss_languages::python::find_python_install(project_root, &version_str)
+        else {
+            if !json {
+                println!("Python {}: no interpreter found", version_str);
+            }
+            continue;
+        };
+        if !json {
+            println!("Indexing Python {} packages...", version_str);
+        }
+
+        let dirs = std::iter::once(("stdlib", install.stdlib))
+            .chain(install.site_packages.map(|site| ("site-packages", site)));
+        for (name, path) in dirs {
+            if !json {
+                println!("  {}: {}", name, path.display());
+            }
+            let source = moss_languages::PackageSource {
+                name,
+                path,
+                kind: moss_languages::PackageSourceKind::Flat,
+                version_specific: true,
+            };
+            for found in lang.discover_packages(&source) {
+                ranges
+                    .entry(found)
+                    .and_modify(|range| {
+                        range.min = range.min.min(version);
+                        range.max = range.max.max(version);
+                    })
+                    .or_insert(VersionRange {
+                        min: version,
+                        max: version,
+                    });
+            }
+        }
+    }
+
+    let mut extractor = skeleton::SkeletonExtractor::new();
+    let mut counts = IndexedCounts {
+        packages: 0,
+        symbols: 0,
+    };
+    for ((pkg_name, pkg_path), range) in ranges {
+        let path = pkg_path.to_string_lossy();
+        if let Ok(true) = pkg_index.is_path_indexed(lang.lang_key(), &pkg_name, &path) {
+            continue;
+        }
+        let Ok(pkg_id) = pkg_index.insert_package(
+            lang.lang_key(),
+            &pkg_name,
+            &path,
+            range.min,
+            Some(range.max),
+        ) else {
+            continue;
+        };
+        counts.packages += 1;
+        counts.symbols += index_package_symbols(lang, pkg_index, &mut extractor, pkg_id, &pkg_path);
+    }
+    counts
+}
+
 fn index_package_symbols(
     lang: &dyn moss_languages::Language,
     pkg_index: &external_packages::PackageIndex,
@@ -487,10 +667,68 @@ fn index_package_symbols(
         None => return 0,
     };
 
-    if let Ok(content) = std::fs::read_to_string(&entry) {
-        let result = extractor.extract(&entry, &content);
-        return count_and_insert_symbols(pkg_index, pkg_id, &result.symbols);
+    // Packages without a single entry file (Go) index every source file in the directory
+    let files = if entry.is_dir() {
+        let mut files: Vec<PathBuf> = std::fs::read_dir(&entry)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                e.path().is_file() && !lang.should_skip_package_entry(&name, false)
+            })
+            .map(|e| e.path())
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![entry]
+    };
+
+    let mut count = 0;
+    for file in files {
+        if let Ok(content) = std::fs::read_to_string(&file) {
+            let result = extractor.extract(&file, &content);
+            count += count_and_insert_symbols(pkg_index, pkg_id, &result.symbols);
+        }
     }
+    count
+}
 
-    0
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_namespace_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("acme");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(pkg.join("billing.py"), "def charge():\n    pass\n").unwrap();
+        std::fs::write(pkg.join("shipping.py"), "class Parcel:\n    pass\n").unwrap();
+        std::fs::write(pkg.join("notes.txt"), "not code\n").unwrap();
+
+        let pkg_index = external_packages::PackageIndex::open_in_memory().unwrap();
+        let version = external_packages::Version {
+            major: 3,
+            minor: 12,
+        };
+        let pkg_id = pkg_index
+            .insert_package("python", "acme", &pkg.to_string_lossy(), version, None)
+            .unwrap();
+        let lang = moss_languages::support_for_path(Path::new("x.py")).unwrap();
+        let mut extractor = skeleton::SkeletonExtractor::new();
+
+        let count = index_package_symbols(lang, &pkg_index, &mut extractor, pkg_id, &pkg);
+        let names: Vec<String> = pkg_index
+            .get_symbols(pkg_id)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(count, 2);
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"charge".to_string()));
+        assert!(names.contains(&"Parcel".to_string()));
+    }
 }
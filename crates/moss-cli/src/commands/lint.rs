@@ -1,15 +1,29 @@
 //! Lint command - run linters, formatters, and type checkers.
 
+use crate::config::MossConfig;
 use crate::output::{OutputFormat, OutputFormatter};
-use moss_tools::{registry_with_custom, SarifReport, ToolCategory, ToolRegistry};
+use crate::paths::current_project_root;
+use moss_tools::{
+    registry_with_custom, run_concurrently, SarifReport, Tool, ToolCategory, ToolRegistry,
+    ToolResult,
+};
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 use rayon::prelude::*;
 use serde::Serialize;
 use std::fmt::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::channel;
 use std::time::{Duration, Instant};
 
+/// A target as typed: relative to the working directory, which may be below
+/// the project root the tools run in.
+fn target_path(target: &str, root: &Path) -> PathBuf {
+    match std::env::current_dir() {
+        Ok(cwd) if cwd != root && cwd.join(target).exists() => cwd.join(target),
+        _ => PathBuf::from(target),
+    }
+}
+
 /// Tool info for lint list output
 #[derive(Debug, Serialize)]
 pub struct ToolListItem {
@@ -47,17 +61,88 @@ impl OutputFormatter for LintListResult {
     }
 }
 
+/// Files with uncommitted changes under `root`, per `git status --porcelain -z`.
+/// Empty when the tree is clean or `root` isn't in a git repository.
+/// moss's own `.moss/` directory doesn't count.
+fn dirty_files(root: &Path) -> Vec<String> {
+    std::process::Command::new("git")
+        .args(["status", "--porcelain", "-z", "--", "."])
+        .current_dir(root)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| {
+            porcelain_paths(&String::from_utf8_lossy(&o.stdout))
+                .into_iter()
+                .filter(|f| !f.starts_with(".moss/"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Paths in `git status --porcelain -z` output: NUL-separated `XY path`
+/// entries, where a rename or copy is followed by its source path.
+/// Both sides of a rename are reported.
+fn porcelain_paths(output: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut entries = output.split('\0').filter(|e| !e.is_empty());
+    while let Some(entry) = entries.next() {
+        let Some(path) = entry.get(3..) else {
+            continue;
+        };
+        paths.push(path.to_string());
+        if entry.as_bytes()[..2]
+            .iter()
+            .any(|b| matches!(b, b'R' | b'C'))
+        {
+            paths.extend(entries.next().map(String::from));
+        }
+    }
+    paths
+}
+
+/// Whether fixes may be applied under `root`. Fixers rewrite files in place,
+/// so refuse to mix their changes with uncommitted work unless `allow_dirty`.
+pub fn fix_allowed(root: &Path, allow_dirty: bool) -> bool {
+    if allow_dirty {
+        return true;
+    }
+    let dirty = dirty_files(root);
+    if dirty.is_empty() {
+        return true;
+    }
+    eprintln!(
+        "error: refusing to apply fixes: {} file(s) have uncommitted changes",
+        dirty.len()
+    );
+    for file in dirty.iter().take(10) {
+        eprintln!("  {}", file);
+    }
+    if dirty.len() > 10 {
+        eprintln!("  ... and {} more", dirty.len() - 10);
+    }
+    eprintln!("hint: commit or stash them first, or pass --allow-dirty");
+    false
+}
+
 /// Run linting tools on the codebase.
+#[allow(clippy::too_many_arguments)]
 pub fn cmd_lint_run(
     target: Option<&str>,
     root: Option<&Path>,
     fix: bool,
+    allow_dirty: bool,
     tools: Option<&str>,
     category: Option<&str>,
     sarif: bool,
     json: bool,
 ) -> i32 {
-    let root = root.unwrap_or_else(|| Path::new("."));
+    let root = &root
+        .map(Path::to_path_buf)
+        .unwrap_or_else(current_project_root);
+    if fix && !fix_allowed(root, allow_dirty) {
+        return 1;
+    }
     // Load built-in tools + custom tools from .moss/tools.toml
     let registry = registry_with_custom(root);
 
@@ -106,59 +191,15 @@ pub fn cmd_lint_run(
     }
 
     // Prepare paths
-    let paths: Vec<&Path> = target.map(|t| vec![Path::new(t)]).unwrap_or_default();
+    let target = target.map(|t| target_path(t, root));
+    let paths: Vec<&Path> = target.as_deref().into_iter().collect();
 
     // Run tools
-    let mut all_results = Vec::new();
-    let mut had_errors = false;
-
-    for tool in &tools_to_run {
-        let info = tool.info();
-
-        if !tool.is_available() {
-            if !json {
-                eprintln!("{}: not installed", info.name);
-            }
-            continue;
-        }
-
-        if !json {
-            let action = if fix && tool.can_fix() {
-                "fixing"
-            } else {
-                "checking"
-            };
-            eprintln!("{}: {}...", info.name, action);
-        }
-
-        let result = if fix && tool.can_fix() {
-            tool.fix(&paths.iter().copied().collect::<Vec<_>>(), root)
-        } else {
-            tool.run(&paths.iter().copied().collect::<Vec<_>>(), root)
-        };
-
-        match result {
-            Ok(result) => {
-                if !result.success {
-                    had_errors = true;
-                    if let Some(err) = &result.error {
-                        if !json {
-                            eprintln!("{}: {}", info.name, err);
-                        }
-                    }
-                } else if result.error_count() > 0 {
-                    had_errors = true;
-                }
-                all_results.push(result);
-            }
-            Err(e) => {
-                had_errors = true;
-                if !json {
-                    eprintln!("{}: {}", info.name, e);
-                }
-            }
-        }
-    }
+    let jobs = MossConfig::load(root).lint.jobs();
+    let LintRun {
+        results: all_results,
+        had_errors,
+    } = run_tools(&tools_to_run, &paths, root, fix, jobs, json);
 
     // Output results
     if sarif {
@@ -193,19 +234,10 @@ pub fn cmd_lint_run(
         // Print diagnostics
         for result in &all_results {
             for diag in &result.diagnostics {
-                let severity = match diag.severity {
-                    moss_tools::DiagnosticSeverity::Error => "error",
-                    moss_tools::DiagnosticSeverity::Warning => "warning",
-                    moss_tools::DiagnosticSeverity::Info => "info",
-                    moss_tools::DiagnosticSeverity::Hint => "hint",
-                };
-
                 println!(
-                    "{}:{}:{}: {} [{}] {}",
-                    diag.location.file.display(),
-                    diag.location.line,
-                    diag.location.column,
-                    severity,
+                    "{}: {} [{}] {}",
+                    diag.location,
+                    diag.severity.as_str(),
                     diag.rule_id,
                     diag.message
                 );
@@ -238,7 +270,9 @@ pub fn cmd_lint_run(
 
 /// List available linting tools.
 pub fn cmd_lint_list(root: Option<&Path>, json: bool, jq: Option<&str>) -> i32 {
-    let root = root.unwrap_or_else(|| Path::new("."));
+    let root = &root
+        .map(Path::to_path_buf)
+        .unwrap_or_else(current_project_root);
     let registry = registry_with_custom(root);
 
     // Only check tools relevant to this codebase (detected via config files, lockfiles, etc.)
@@ -273,11 +307,18 @@ pub fn cmd_lint_watch(
     target: Option<&str>,
     root: Option<&Path>,
     fix: bool,
+    allow_dirty: bool,
     tools: Option<&str>,
     category: Option<&str>,
     json: bool,
 ) -> i32 {
-    let root = root.unwrap_or_else(|| Path::new("."));
+    let root = &root
+        .map(Path::to_path_buf)
+        .unwrap_or_else(current_project_root);
+    // Checked once: fixes applied while watching dirty the tree themselves
+    if fix && !fix_allowed(root, allow_dirty) {
+        return 1;
+    }
 
     // Initial run
     eprintln!("Running initial lint check...");
@@ -399,57 +440,13 @@ fn run_lint_once(
         return 0;
     }
 
-    let paths: Vec<&Path> = target.map(|t| vec![Path::new(t)]).unwrap_or_default();
-    let mut all_results = Vec::new();
-    let mut had_errors = false;
-
-    for tool in &tools_to_run {
-        let info = tool.info();
-
-        if !tool.is_available() {
-            if !json {
-                eprintln!("{}: not installed", info.name);
-            }
-            continue;
-        }
-
-        if !json {
-            let action = if fix && tool.can_fix() {
-                "fixing"
-            } else {
-                "checking"
-            };
-            eprintln!("{}: {}...", info.name, action);
-        }
-
-        let result = if fix && tool.can_fix() {
-            tool.fix(&paths.iter().copied().collect::<Vec<_>>(), root)
-        } else {
-            tool.run(&paths.iter().copied().collect::<Vec<_>>(), root)
-        };
-
-        match result {
-            Ok(result) => {
-                if !result.success {
-                    had_errors = true;
-                    if let Some(err) = &result.error {
-                        if !json {
-                            eprintln!("{}: {}", info.name, err);
-                        }
-                    }
-                } else if result.error_count() > 0 {
-                    had_errors = true;
-                }
-                all_results.push(result);
-            }
-            Err(e) => {
-                had_errors = true;
-                if !json {
-                    eprintln!("{}: {}", info.name, e);
-                }
-            }
-        }
-    }
+    let target = target.map(|t| target_path(t, root));
+    let paths: Vec<&Path> = target.as_deref().into_iter().collect();
+    let jobs = MossConfig::load(root).lint.jobs();
+    let LintRun {
+        results: all_results,
+        had_errors,
+    } = run_tools(&tools_to_run, &paths, root, fix, jobs, json);
 
     // Output results
     if json {
@@ -470,19 +467,10 @@ fn run_lint_once(
     } else {
         for result in &all_results {
             for diag in &result.diagnostics {
-                let severity = match diag.severity {
-                    moss_tools::DiagnosticSeverity::Error => "error",
-                    moss_tools::DiagnosticSeverity::Warning => "warning",
-                    moss_tools::DiagnosticSeverity::Info => "info",
-                    moss_tools::DiagnosticSeverity::Hint => "hint",
-                };
-
                 println!(
-                    "{}:{}:{}: {} [{}] {}",
-                    diag.location.file.display(),
-                    diag.location.line,
-                    diag.location.column,
-                    severity,
+                    "{}: {} [{}] {}",
+                    diag.location,
+                    diag.severity.as_str(),
                     diag.rule_id,
                     diag.message
                 );
@@ -511,3 +499,96 @@ fn run_lint_once(
         0
     }
 }
+
+/// Tool results from one lint pass.
+struct LintRun {
+    results: Vec<ToolResult>,
+    /// A tool failed or reported errors.
+    had_errors: bool,
+}
+
+/// Run tools (fixing where they can, with `fix`), at most `jobs` at a time.
+/// Results are ordered by tool name regardless of which finished first.
+fn run_tools(
+    tools: &[&dyn Tool],
+    paths: &[&Path],
+    root: &Path,
+    fix: bool,
+    jobs: usize,
+    json: bool,
+) -> LintRun {
+    // Fixers rewrite the same files (e.g. black and isort), so they take turns
+    let jobs = if fix { 1 } else { jobs };
+    let outcomes = run_concurrently(tools, jobs, |tool| {
+        let info = tool.info();
+
+        if !tool.is_available() {
+            if !json {
+                eprintln!("{}: not installed", info.name);
+            }
+            return None;
+        }
+
+        if !json {
+            let action = if fix && tool.can_fix() {
+                "fixing"
+            } else {
+                "checking"
+            };
+            eprintln!("{}: {}...", info.name, action);
+        }
+
+        if fix && tool.can_fix() {
+            Some(tool.fix(paths, root))
+        } else {
+            Some(tool.run(paths, root))
+        }
+    });
+
+    let mut results = Vec::new();
+    let mut had_errors = false;
+    for (tool, outcome) in tools.iter().zip(outcomes) {
+        let name = tool.info().name;
+        match outcome {
+            Some(Ok(result)) => {
+                if !result.success {
+                    had_errors = true;
+                    if let Some(err) = &result.error {
+                        if !json {
+                            eprintln!("{}: {}", name, err);
+                        }
+                    }
+                } else if result.error_count() > 0 {
+                    had_errors = true;
+                }
+                results.push(result);
+            }
+            Some(Err(e)) => {
+                had_errors = true;
+                if !json {
+                    eprintln!("{}: {}", name, e);
+                }
+            }
+            None => {}
+        }
+    }
+    ToolRegistry::sort_results(&mut results);
+    LintRun {
+        results,
+        had_errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_porcelain_paths() {
+        let output = " M src/a.rs\0R  new name.rs\0old name.rs\0?? notes.txt\0";
+        assert_eq!(
+            porcelain_paths(output),
+            ["src/a.rs", "new name.rs", "old name.rs", "notes.txt"]
+        );
+    }
+}
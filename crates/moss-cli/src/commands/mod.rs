@@ -1,15 +1,24 @@
 //! CLI command implementations - one module per top-level command.
 
 pub mod analyze;
+pub mod cache;
+pub mod callgraph;
 pub mod daemon;
 pub mod edit;
 pub mod filter;
+pub mod graph;
 pub mod grep;
 pub mod index;
 pub mod lint;
 pub mod package;
+pub mod path;
+pub mod pick;
 pub mod plans;
 pub mod sessions;
+pub mod signature;
+pub mod similar;
+pub mod symbols;
 pub mod update;
 pub mod view;
+pub mod watch;
 pub mod workflow;
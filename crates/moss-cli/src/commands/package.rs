@@ -1,10 +1,14 @@
 //! Package registry queries.
 
+use crate::config::MossConfig;
+use crate::paths::current_project_root;
 use clap::Subcommand;
+use moss_languages::external_packages::{PackageIndex, Version};
 use moss_packages::{
-    all_ecosystems, detect_all_ecosystems, AuditResult, PackageError, PackageInfo,
-    VulnerabilitySeverity,
+    all_ecosystems, compare_versions, detect_all_ecosystems, AuditResult, CacheMode, LicenseStatus,
+    LicenseSummary, PackageError, PackageInfo, PackageLicense, QueryOptions, VulnerabilitySeverity,
 };
+use std::cmp::Ordering;
 use std::path::Path;
 
 #[derive(Subcommand)]
@@ -15,9 +19,15 @@ pub enum PackageAction {
         package: String,
     },
     /// List declared dependencies from manifest
-    List,
+    List {
+        /// Show exact versions pinned by the lockfile (including transitive)
+        #[arg(long)]
+        resolved: bool,
+    },
     /// Show dependency tree from lockfile
     Tree,
+    /// Show resolved dependency graph (nodes and classified edges)
+    Graph,
     /// Show why a dependency is in the tree
     Why {
         /// Package name to trace
@@ -27,21 +37,85 @@ pub enum PackageAction {
     Outdated,
     /// Check for security vulnerabilities
     Audit,
+    /// Summarize dependency licenses, flagging missing and non-SPDX ones
+    Licenses,
+    /// Find which indexed packages define a symbol (see `moss index packages`)
+    Symbol {
+        /// Symbol name (exact)
+        name: String,
+
+        /// Only search this language's packages (python, go, js, rust, ...)
+        #[arg(long)]
+        lang: Option<String>,
+
+        /// Language version to match (e.g. 3.11); detected from the project if omitted
+        #[arg(long, requires = "lang")]
+        version: Option<String>,
+
+        /// Maximum matches to show
+        #[arg(short, long, default_value = "50")]
+        limit: usize,
+
+        /// Skip this many matches (for paging)
+        #[arg(long, default_value = "0")]
+        offset: usize,
+    },
 }
 
 pub fn cmd_package(
     action: PackageAction,
     ecosystem: Option<&str>,
     root: Option<&Path>,
+    cache_mode: CacheMode,
     json: bool,
 ) -> i32 {
-    let project_root = root.unwrap_or(Path::new("."));
+    let project_root = &root
+        .map(Path::to_path_buf)
+        .unwrap_or_else(current_project_root);
+    let config = MossConfig::load(project_root);
+    let options = QueryOptions {
+        cache_ttl: config.package.cache_ttl(),
+        cache_mode,
+    };
+
+    // Symbol search reads the local package index, not a registry
+    if let PackageAction::Symbol {
+        name,
+        lang,
+        version,
+        limit,
+        offset,
+    } = &action
+    {
+        return cmd_symbol(
+            name,
+            lang.as_deref(),
+            version.as_deref(),
+            *limit,
+            *offset,
+            project_root,
+            json,
+        );
+    }
+
+    let exit_code = run_package(&action, ecosystem, project_root, &options, json);
+    // Registry queries may have grown the global cache
+    super::cache::enforce_limits(&config.cache);
+    exit_code
+}
 
+fn run_package(
+    action: &PackageAction,
+    ecosystem: Option<&str>,
+    project_root: &Path,
+    options: &QueryOptions,
+    json: bool,
+) -> i32 {
     // Get ecosystem either by name or by detection
     if let Some(name) = ecosystem {
         // Explicit ecosystem specified
         match find_ecosystem_by_name(name) {
-            Some(eco) => run_for_ecosystem(eco, &action, project_root, json),
+            Some(eco) => run_for_ecosystem(eco, action, project_root, options, json),
             None => {
                 eprintln!("error: unknown ecosystem '{}'", name);
                 eprintln!("available: {}", available_ecosystems().join(", "));
@@ -58,20 +132,20 @@ pub fn cmd_package(
             return 1;
         }
 
-        // For list/tree, run for all detected ecosystems
+        // For list/tree/graph, run for all detected ecosystems
         // For info/outdated, use first ecosystem only
-        match &action {
-            PackageAction::List | PackageAction::Tree => {
+        match action {
+            PackageAction::List { .. } | PackageAction::Tree | PackageAction::Graph => {
                 if json && ecosystems.len() > 1 {
                     // Collect all results into a JSON array
-                    run_all_ecosystems_json(&ecosystems, &action, project_root)
+                    run_all_ecosystems_json(&ecosystems, action, project_root)
                 } else {
                     let mut exit_code = 0;
                     for (i, eco) in ecosystems.iter().enumerate() {
                         if i > 0 {
                             println!(); // Separator between ecosystems
                         }
-                        let result = run_for_ecosystem(*eco, &action, project_root, json);
+                        let result = run_for_ecosystem(*eco, action, project_root, options, json);
                         if result != 0 {
                             exit_code = result;
                         }
@@ -85,7 +159,7 @@ pub fn cmd_package(
                     eprintln!("note: multiple ecosystems detected: {}", names.join(", "));
                     eprintln!("hint: use --ecosystem to specify which one");
                 }
-                run_for_ecosystem(ecosystems[0], &action, project_root, json)
+                run_for_ecosystem(ecosystems[0], action, project_root, options, json)
             }
         }
     }
@@ -100,7 +174,27 @@ fn run_all_ecosystems_json(
 
     for eco in ecosystems {
         match action {
-            PackageAction::List => match eco.list_dependencies(project_root) {
+            PackageAction::List { resolved: true } => {
+                match eco.resolved_dependencies(project_root) {
+                    Ok(deps) => {
+                        results.insert(
+                            eco.name().to_string(),
+                            serde_json::json!({
+                                "resolved": deps
+                            }),
+                        );
+                    }
+                    Err(e) => {
+                        results.insert(
+                            eco.name().to_string(),
+                            serde_json::json!({
+                                "error": e.to_string()
+                            }),
+                        );
+                    }
+                }
+            }
+            PackageAction::List { resolved: false } => match eco.list_dependencies(project_root) {
                 Ok(deps) => {
                     results.insert(
                         eco.name().to_string(),
@@ -140,6 +234,25 @@ fn run_all_ecosystems_json(
                     );
                 }
             },
+            PackageAction::Graph => match eco.dependency_graph(project_root) {
+                Ok(graph) => {
+                    results.insert(
+                        eco.name().to_string(),
+                        serde_json::json!({
+                            "nodes": graph.nodes,
+                            "edges": graph.edges,
+                        }),
+                    );
+                }
+                Err(e) => {
+                    results.insert(
+                        eco.name().to_string(),
+                        serde_json::json!({
+                            "error": e.to_string()
+                        }),
+                    );
+                }
+            },
             _ => {}
         }
     }
@@ -152,15 +265,20 @@ fn run_for_ecosystem(
     eco: &dyn moss_packages::Ecosystem,
     action: &PackageAction,
     project_root: &Path,
+    options: &QueryOptions,
     json: bool,
 ) -> i32 {
     match action {
-        PackageAction::Info { package } => cmd_info(eco, package, project_root, json),
-        PackageAction::List => cmd_list(eco, project_root, json),
+        PackageAction::Info { package } => cmd_info(eco, package, project_root, options, json),
+        PackageAction::List { resolved: true } => cmd_list_resolved(eco, project_root, json),
+        PackageAction::List { resolved: false } => cmd_list(eco, project_root, json),
         PackageAction::Tree => cmd_tree(eco, project_root, json),
+        PackageAction::Graph => cmd_graph(eco, project_root, json),
         PackageAction::Why { package } => cmd_why(eco, package, project_root, json),
-        PackageAction::Outdated => cmd_outdated(eco, project_root, json),
+        PackageAction::Outdated => cmd_outdated(eco, project_root, options, json),
         PackageAction::Audit => cmd_audit(eco, project_root, json),
+        PackageAction::Licenses => cmd_licenses(eco, project_root, options, json),
+        PackageAction::Symbol { .. } => unreachable!("handled before ecosystem detection"),
     }
 }
 
@@ -168,9 +286,10 @@ fn cmd_info(
     eco: &dyn moss_packages::Ecosystem,
     package: &str,
     project_root: &Path,
+    options: &QueryOptions,
     json: bool,
 ) -> i32 {
-    match eco.query(package, project_root) {
+    match eco.query(package, project_root, options) {
         Ok(info) => {
             if json {
                 print_json(&info);
@@ -192,6 +311,10 @@ fn cmd_info(
                     eprintln!("error: no {} tools found in PATH", eco.name());
                     eprintln!("hint: install one of: {:?}", eco.tools());
                 }
+                PackageError::NotCached(_) => {
+                    eprintln!("error: {}", e);
+                    eprintln!("hint: run without --offline to fetch it");
+                }
                 _ => {
                     eprintln!("error: {}", e);
                 }
@@ -234,6 +357,33 @@ fn cmd_list(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: bool)
     }
 }
 
+fn cmd_list_resolved(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: bool) -> i32 {
+    match eco.resolved_dependencies(project_root) {
+        Ok(deps) => {
+            if json {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "ecosystem": eco.name(),
+                        "resolved": deps,
+                    })
+                );
+            } else {
+                println!("{} resolved packages ({})", deps.len(), eco.name());
+                println!();
+                for dep in &deps {
+                    println!("  {} {}", dep.name, dep.version);
+                }
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
 fn cmd_tree(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: bool) -> i32 {
     match eco.dependency_tree(project_root) {
         Ok(tree) => {
@@ -257,6 +407,39 @@ fn cmd_tree(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: bool)
     }
 }
 
+fn cmd_graph(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: bool) -> i32 {
+    match eco.dependency_graph(project_root) {
+        Ok(graph) => {
+            if json {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "ecosystem": eco.name(),
+                        "nodes": graph.nodes,
+                        "edges": graph.edges,
+                    })
+                );
+            } else {
+                println!(
+                    "{} packages, {} edges ({})",
+                    graph.nodes.len(),
+                    graph.edges.len(),
+                    eco.name()
+                );
+                println!();
+                for edge in &graph.edges {
+                    println!("  {} -> {} ({})", edge.from, edge.to, edge.kind.as_str());
+                }
+            }
+            0
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
 fn print_tree(tree: &moss_packages::DependencyTree) {
     for root in &tree.roots {
         print_node(root, 0);
@@ -378,7 +561,117 @@ fn find_paths_recursive(
     }
 }
 
-fn cmd_outdated(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: bool) -> i32 {
+fn cmd_licenses(
+    eco: &dyn moss_packages::Ecosystem,
+    project_root: &Path,
+    options: &QueryOptions,
+    json: bool,
+) -> i32 {
+    let deps = match eco.resolved_dependencies(project_root) {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return 1;
+        }
+    };
+
+    let mut packages = Vec::new();
+    let mut errors = Vec::new();
+
+    for dep in &deps {
+        let query = format!("{}@{}", dep.name, dep.version);
+        match eco.query(&query, project_root, options) {
+            Ok(info) => {
+                packages.push(PackageLicense::new(
+                    &dep.name,
+                    &dep.version,
+                    info.license.as_deref(),
+                ));
+            }
+            Err(PackageError::NoToolFound) => {
+                eprintln!("error: no {} tools found in PATH", eco.name());
+                eprintln!("hint: install one of: {:?}", eco.tools());
+                return 1;
+            }
+            Err(e) => {
+                errors.push((query, e.to_string()));
+            }
+        }
+    }
+
+    let summary = LicenseSummary::from_packages(packages);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "ecosystem": eco.name(),
+                "packages": summary.packages,
+                "counts": summary.counts,
+                "missing": summary.missing,
+                "non_spdx": summary.non_spdx,
+                "errors": errors.iter().map(|(n, e)| serde_json::json!({"name": n, "error": e})).collect::<Vec<_>>()
+            })
+        );
+        return 0;
+    }
+
+    println!(
+        "Licenses of {} packages ({}):",
+        summary.packages.len(),
+        eco.name()
+    );
+    println!();
+    let width = summary
+        .counts
+        .iter()
+        .map(|c| c.license.len())
+        .max()
+        .unwrap_or(0);
+    for count in &summary.counts {
+        println!("  {:<width$}  {}", count.license, count.count);
+    }
+    if summary.missing > 0 {
+        println!("  {:<width$}  {}", "(missing)", summary.missing);
+    }
+
+    let flagged: Vec<&PackageLicense> = summary
+        .packages
+        .iter()
+        .filter(|p| p.status != LicenseStatus::Spdx)
+        .collect();
+    if !flagged.is_empty() {
+        println!();
+        println!("Flagged ({}):", flagged.len());
+        for pkg in flagged {
+            match &pkg.license {
+                Some(license) => println!(
+                    "  {} {}: {} \"{}\"",
+                    pkg.name,
+                    pkg.version,
+                    pkg.status.as_str(),
+                    license
+                ),
+                None => println!("  {} {}: {}", pkg.name, pkg.version, pkg.status.as_str()),
+            }
+        }
+    }
+    if !errors.is_empty() {
+        println!();
+        println!("Errors ({}):", errors.len());
+        for (name, err) in &errors {
+            println!("  {}: {}", name, err);
+        }
+    }
+    0
+}
+
+fn cmd_outdated(
+    eco: &dyn moss_packages::Ecosystem,
+    project_root: &Path,
+    options: &QueryOptions,
+    json: bool,
+) -> i32 {
     // Get declared dependencies
     let deps = match eco.list_dependencies(project_root) {
         Ok(d) => d,
@@ -400,27 +693,33 @@ fn cmd_outdated(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: b
     let mut errors = Vec::new();
 
     for dep in &deps {
-        // Get installed version from lockfile
+        // Lockfile pin if there is one, otherwise the version the manifest asks for
         let installed = eco.installed_version(&dep.name, project_root);
+        let current = installed
+            .clone()
+            .or_else(|| dep.version_req.as_deref().and_then(requirement_version));
 
-        // Get latest version from registry
-        match eco.query(&dep.name, project_root) {
+        match eco.query(&format!("{}@latest", dep.name), project_root, options) {
             Ok(info) => {
-                // Only show if installed differs from latest
-                let is_outdated = match &installed {
-                    Some(v) => v != &info.version,
+                let is_outdated = match &current {
+                    Some(v) => compare_versions(v, &info.version) == Ordering::Less,
                     None => true, // Not installed = show it
                 };
 
                 if is_outdated {
                     outdated.push(OutdatedPackage {
                         name: dep.name.clone(),
-                        installed: installed.clone(),
+                        installed: current,
                         latest: info.version,
                         wanted: dep.version_req.clone(),
                     });
                 }
             }
+            Err(PackageError::NoToolFound) => {
+                eprintln!("error: no {} tools found in PATH", eco.name());
+                eprintln!("hint: install one of: {:?}", eco.tools());
+                return 1;
+            }
             Err(e) => {
                 errors.push((dep.name.clone(), e.to_string()));
             }
@@ -442,9 +741,30 @@ fn cmd_outdated(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: b
             if !outdated.is_empty() {
                 println!("Outdated packages ({}):", outdated.len());
                 println!();
-                for pkg in &outdated {
-                    let installed = pkg.installed.as_deref().unwrap_or("(not installed)");
-                    println!("  {} {} → {}", pkg.name, installed, pkg.latest);
+                let rows: Vec<[&str; 3]> = outdated
+                    .iter()
+                    .map(|pkg| {
+                        [
+                            pkg.name.as_str(),
+                            pkg.installed.as_deref().unwrap_or("(not installed)"),
+                            pkg.latest.as_str(),
+                        ]
+                    })
+                    .collect();
+                let header = ["name", "current", "latest"];
+                let width = |col: usize| {
+                    rows.iter()
+                        .map(|row| row[col].len())
+                        .chain([header[col].len()])
+                        .max()
+                        .unwrap_or(0)
+                };
+                let (name_width, current_width) = (width(0), width(1));
+                for row in std::iter::once(header).chain(rows.iter().copied()) {
+                    println!(
+                        "  {:<name_width$}  {:<current_width$}  {}",
+                        row[0], row[1], row[2]
+                    );
                 }
             }
             if !errors.is_empty() {
@@ -460,6 +780,17 @@ fn cmd_outdated(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: b
     0
 }
 
+/// The version a requirement asks for: "^1.2" -> "1.2", ">=2.0,<3" -> "2.0".
+/// None for wildcards and requirements without a version.
+fn requirement_version(req: &str) -> Option<String> {
+    let first = req.split([',', ' ', '|']).find(|part| !part.is_empty())?;
+    let version = first.trim_start_matches(['^', '~', '=', '>', '<', '!']);
+    if version.is_empty() || !version.starts_with(|c: char| c.is_ascii_digit() || c == 'v') {
+        return None;
+    }
+    Some(version.trim_end_matches(".*").to_string())
+}
+
 fn cmd_audit(eco: &dyn moss_packages::Ecosystem, project_root: &Path, json: bool) -> i32 {
     match eco.audit(project_root) {
         Ok(result) => {
@@ -604,3 +935,132 @@ fn print_human(info: &PackageInfo, ecosystem: &str) {
         }
     }
 }
+
+/// A symbol definition found in the global package index.
+#[derive(serde::Serialize)]
+struct PackageSymbol {
+    language: String,
+    package: String,
+    name: String,
+    kind: String,
+    signature: String,
+    path: String,
+    line: u32,
+}
+
+/// Search the global package index for packages defining `name`.
+///
+/// Each language's matches are filtered to the version given with
+/// `--version`, else the version the project uses (when detectable).
+fn cmd_symbol(
+    name: &str,
+    lang: Option<&str>,
+    version: Option<&str>,
+    limit: usize,
+    offset: usize,
+    project_root: &Path,
+    json: bool,
+) -> i32 {
+    let explicit_version = match version {
+        Some(v) => match Version::parse(v) {
+            Some(parsed) => Some(parsed),
+            None => {
+                eprintln!("error: invalid version '{}' (expected MAJOR.MINOR)", v);
+                return 1;
+            }
+        },
+        None => None,
+    };
+
+    let mut languages: Vec<&dyn moss_languages::Language> = Vec::new();
+    for l in moss_languages::supported_languages() {
+        let key = l.lang_key();
+        if !key.is_empty()
+            && lang.is_none_or(|wanted| wanted == key)
+            && !languages.iter().any(|seen| seen.lang_key() == key)
+        {
+            languages.push(l);
+        }
+    }
+    if languages.is_empty() {
+        eprintln!("error: unknown language '{}'", lang.unwrap_or_default());
+        return 1;
+    }
+
+    let index = match PackageIndex::open() {
+        Ok(index) => index,
+        Err(e) => {
+            let message = format!("Failed to open package index: {}", e);
+            if json {
+                return crate::output::print_json_error("index", &message);
+            }
+            eprintln!("{}", message);
+            return 1;
+        }
+    };
+
+    let mut matches = Vec::new();
+    for language in languages {
+        let key = language.lang_key();
+        let found = match index.find_symbol(key, name, None) {
+            Ok(found) if !found.is_empty() => found,
+            _ => continue,
+        };
+        // Only ask the toolchain for its version when there is something to filter
+        let version = explicit_version.or_else(|| {
+            language
+                .get_version(project_root)
+                .and_then(|v| Version::parse(&v))
+        });
+        for (package, symbol) in found {
+            if let Some(v) = version {
+                if !v.in_range(package.min_version(), package.max_version()) {
+                    continue;
+                }
+            }
+            matches.push(PackageSymbol {
+                language: key.to_string(),
+                package: package.name,
+                name: symbol.name,
+                kind: symbol.kind,
+                signature: symbol.signature,
+                path: package.path,
+                line: symbol.line,
+            });
+        }
+    }
+    matches.sort_by(|a, b| {
+        a.language
+            .cmp(&b.language)
+            .then_with(|| a.package.cmp(&b.package))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    let total = matches.len();
+    let page: Vec<PackageSymbol> = matches.into_iter().skip(offset).take(limit).collect();
+
+    if json {
+        return crate::output::print_json_results(&page);
+    }
+    if page.is_empty() {
+        eprintln!("No indexed package defines '{}'", name);
+        eprintln!("hint: index packages with: moss index packages");
+        return 1;
+    }
+    for m in &page {
+        let what = if m.signature.is_empty() {
+            format!("{} {}", m.kind, m.name)
+        } else {
+            m.signature.clone()
+        };
+        println!("{} ({}): {}", m.package, m.language, what);
+        println!("    {}:{}", m.path, m.line);
+    }
+    if offset + page.len() < total {
+        println!(
+            "... {} more (use --offset {})",
+            total - offset - page.len(),
+            offset + page.len()
+        );
+    }
+    0
+}
@@ -0,0 +1,157 @@
+//! Path command - resolve fuzzy queries to files and directories.
+//!
+//! With `--kind`, indexed symbols of that kind are matched by name too, so
+//! one query finds both `src/parser.rs` and `fn parse` in `src/lib.rs`.
+
+use crate::config::{FuzzyConfig, MossConfig};
+use crate::index::FileIndex;
+use crate::output;
+use crate::path_resolve::{self, MatchOptions, MatchTier};
+use crate::paths::current_project_root;
+use serde::Serialize;
+use std::path::Path;
+
+/// Symbols fetched from the index when no `--limit` is given.
+const DEFAULT_SYMBOL_LIMIT: usize = 100;
+
+/// A path or symbol match, in the order results are printed.
+#[derive(Serialize)]
+struct Hit {
+    path: String,
+    /// Line of the symbol's definition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    /// Symbol name, absent for path matches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    kind: String,
+    #[serde(skip)]
+    tier: MatchTier,
+    #[serde(rename = "tier")]
+    tier_name: &'static str,
+}
+
+/// Resolve `query` and print the matching paths. Options set in `fuzzy`
+/// override the project's `[fuzzy]` config. `kinds` restricts results to
+/// those kinds (`file`, `directory`, or symbol kinds like `function`) and
+/// adds matching symbols from the index.
+pub fn cmd_path(
+    query: &str,
+    root: Option<&Path>,
+    all: bool,
+    kinds: &[String],
+    limit: Option<usize>,
+    fuzzy: FuzzyConfig,
+    json: bool,
+) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(current_project_root);
+
+    let options = MatchOptions::from_config(&fuzzy.or(MossConfig::load(&root).fuzzy));
+    let mut hits: Vec<Hit> = path_resolve::resolve_with_options(query, &root, all, &options)
+        .into_iter()
+        .filter(|m| kinds.is_empty() || kinds.contains(&m.kind))
+        .map(|m| Hit {
+            path: m.path,
+            line: None,
+            name: None,
+            kind: m.kind,
+            tier: m.tier,
+            tier_name: m.tier.as_str(),
+        })
+        .collect();
+
+    let symbol_kinds: Vec<&String> = kinds
+        .iter()
+        .filter(|k| *k != "file" && *k != "directory")
+        .collect();
+    if !symbol_kinds.is_empty() {
+        match symbol_hits(query, &root, &symbol_kinds, limit) {
+            Some(symbols) => hits.extend(symbols),
+            None => return 1,
+        }
+        // Stable: within a tier, paths stay ahead of symbols and keep their order
+        hits.sort_by_key(|h| h.tier);
+    }
+    if let Some(limit) = limit {
+        hits.truncate(limit);
+    }
+
+    if json {
+        return output::print_json_results(&hits);
+    }
+    if hits.is_empty() {
+        eprintln!("No matches for: {}", query);
+        return 1;
+    }
+    for hit in &hits {
+        let location = match (&hit.name, hit.line) {
+            (Some(name), Some(line)) => format!("{}:{} {}", hit.path, line, name),
+            _ => hit.path.clone(),
+        };
+        if all {
+            println!("{} ({}, {})", location, hit.kind, hit.tier_name);
+        } else {
+            println!("{} ({})", location, hit.kind);
+        }
+    }
+    0
+}
+
+/// Indexed symbols of the given kinds whose names contain `query`, ranked
+/// exact name, then prefix, then substring. None if the index can't be read.
+fn symbol_hits(
+    query: &str,
+    root: &Path,
+    kinds: &[&String],
+    limit: Option<usize>,
+) -> Option<Vec<Hit>> {
+    let idx = match FileIndex::open(root) {
+        Ok(idx) => idx,
+        Err(e) => {
+            eprintln!("Failed to open index: {}", e);
+            return None;
+        }
+    };
+    if idx.call_graph_stats().unwrap_or_default().symbols == 0 {
+        eprintln!("Symbols not indexed. Run: moss index rebuild --call-graph");
+        return None;
+    }
+
+    let query_lower = query.to_lowercase();
+    let mut hits = Vec::new();
+    for kind in kinds {
+        let symbols = match idx.find_symbols(
+            query,
+            Some(kind),
+            true,
+            limit.unwrap_or(DEFAULT_SYMBOL_LIMIT),
+        ) {
+            Ok(symbols) => symbols,
+            Err(e) => {
+                eprintln!("Failed to search symbols: {}", e);
+                return None;
+            }
+        };
+        for symbol in symbols {
+            let name_lower = symbol.name.to_lowercase();
+            let tier = if name_lower == query_lower {
+                MatchTier::Exact
+            } else if name_lower.starts_with(&query_lower) {
+                MatchTier::Name
+            } else {
+                MatchTier::Fuzzy
+            };
+            hits.push(Hit {
+                path: symbol.file,
+                line: Some(symbol.start_line),
+                name: Some(symbol.name),
+                kind: symbol.kind,
+                tier,
+                tier_name: tier.as_str(),
+            });
+        }
+    }
+    Some(hits)
+}
@@ -0,0 +1,170 @@
+//! Pick command - interactive fuzzy file picker.
+//!
+//! `moss pick` re-ranks the indexed paths on every keystroke with the same
+//! tiers and nucleo scoring as `moss path`, then prints the chosen path or
+//! opens it with `--open`. The picker draws on stderr so stdout stays
+//! capturable, as in `vim $(moss pick)`. Requires the `tui` feature.
+
+use crate::paths::current_project_root;
+use std::path::Path;
+
+/// Pick a path interactively, starting from `query`.
+pub fn cmd_pick(query: Option<&str>, open: bool, root: Option<&Path>) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(current_project_root);
+
+    #[cfg(feature = "tui")]
+    {
+        use crate::path_resolve::{self, MatchOptions};
+
+        let paths: Vec<(String, bool)> = path_resolve::all_files(&root)
+            .into_iter()
+            .map(|m| {
+                let is_dir = m.kind == "directory";
+                (m.path, is_dir)
+            })
+            .collect();
+        let options = MatchOptions::load(&root);
+
+        match tui::run(query.unwrap_or(""), &paths, &options) {
+            Ok(Some(path)) if open => open_in_editor(&root.join(path)),
+            Ok(Some(path)) => {
+                println!("{}", path);
+                0
+            }
+            // Cancelled, like fzf
+            Ok(None) => 130,
+            Err(e) => {
+                eprintln!("Picker failed: {}", e);
+                1
+            }
+        }
+    }
+
+    #[cfg(not(feature = "tui"))]
+    {
+        let _ = (query, open, root);
+        eprintln!("moss pick requires the 'tui' feature.");
+        eprintln!("Rebuild with: cargo build --features tui");
+        1
+    }
+}
+
+/// Open a file in `$VISUAL` or `$EDITOR` (which may include arguments), else `vi`.
+#[cfg(feature = "tui")]
+fn open_in_editor(path: &Path) -> i32 {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let Some(program) = parts.next() else {
+        eprintln!("$EDITOR is empty");
+        return 1;
+    };
+    match std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+    {
+        Ok(status) => status.code().unwrap_or(1),
+        Err(e) => {
+            eprintln!("Failed to run {}: {}", program, e);
+            1
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+mod tui {
+    use crate::path_resolve::{rank_paths, MatchOptions, PathMatch};
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use crossterm::style::{Attribute, Print, SetAttribute};
+    use crossterm::terminal::{self, ClearType};
+    use crossterm::{cursor, execute, queue};
+    use std::io::{self, Write};
+
+    /// Matches shown below the prompt
+    const MAX_ROWS: usize = 10;
+
+    /// Run the picker until a path is chosen (Enter) or it's cancelled (Esc, Ctrl-C).
+    pub fn run(
+        initial: &str,
+        paths: &[(String, bool)],
+        options: &MatchOptions,
+    ) -> io::Result<Option<String>> {
+        let mut out = io::stderr();
+        terminal::enable_raw_mode()?;
+        execute!(out, terminal::EnterAlternateScreen)?;
+        let result = pick_loop(&mut out, initial, paths, options);
+        execute!(out, terminal::LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+        result
+    }
+
+    fn pick_loop(
+        out: &mut impl Write,
+        initial: &str,
+        paths: &[(String, bool)],
+        options: &MatchOptions,
+    ) -> io::Result<Option<String>> {
+        let mut query = initial.to_string();
+        let mut selected = 0;
+        loop {
+            let matches = rank_paths(&query, paths, options);
+            let shown = matches.len().min(MAX_ROWS);
+            selected = selected.min(shown.saturating_sub(1));
+            draw(out, &query, &matches[..shown], selected)?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            match key.code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Char('c') if ctrl => return Ok(None),
+                KeyCode::Enter => return Ok(matches.get(selected).map(|m| m.path.clone())),
+                KeyCode::Up => selected = selected.saturating_sub(1),
+                KeyCode::Char('p') if ctrl => selected = selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Tab => selected += 1,
+                KeyCode::Char('n') if ctrl => selected += 1,
+                KeyCode::Backspace => {
+                    query.pop();
+                    selected = 0;
+                }
+                KeyCode::Char(c) if !ctrl => {
+                    query.push(c);
+                    selected = 0;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(
+        out: &mut impl Write,
+        query: &str,
+        matches: &[PathMatch],
+        selected: usize,
+    ) -> io::Result<()> {
+        queue!(out, terminal::Clear(ClearType::All))?;
+        for (i, m) in matches.iter().enumerate() {
+            queue!(out, cursor::MoveTo(0, i as u16 + 1))?;
+            if i == selected {
+                queue!(
+                    out,
+                    SetAttribute(Attribute::Reverse),
+                    Print(&m.path),
+                    SetAttribute(Attribute::Reset)
+                )?;
+            } else {
+                queue!(out, Print(&m.path))?;
+            }
+        }
+        queue!(out, cursor::MoveTo(0, 0), Print(format!("> {}", query)))?;
+        out.flush()
+    }
+}
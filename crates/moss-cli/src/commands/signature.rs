@@ -0,0 +1,219 @@
+//! Signature command - print just a symbol's signature.
+//!
+//! The lightest view: `moss signature parse_args` finds the symbol in the index
+//! and prints its declaration line, no body or context. `file:symbol` and
+//! `Parent.method` narrow the lookup; overloads print one line each.
+
+use crate::commands::analyze::{open_call_graph, parse_file_symbol_string, CALL_GRAPH_UNAVAILABLE};
+use crate::extract::{ExtractOptions, Extractor, FunctionDetails};
+use crate::index::SymbolMatch;
+use crate::output;
+use crate::path_resolve;
+use crate::paths::current_project_root;
+use moss_languages::Symbol;
+use std::path::Path;
+
+/// A symbol's signature and where it's defined.
+#[derive(Debug, Clone, serde::Serialize)]
+struct SignatureMatch {
+    name: String,
+    signature: String,
+    file: String,
+    line: usize,
+    /// Parameters and return type, for functions
+    #[serde(flatten)]
+    details: Option<FunctionDetails>,
+}
+
+/// Print the signature of every symbol matching `target`
+/// (`name`, `Parent.name`, or either prefixed with `file:`).
+pub fn cmd_signature(target: &str, root: Option<&Path>, json: bool) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(current_project_root);
+    let (symbol, file_hint) = match parse_file_symbol_string(target) {
+        Some((sym, file)) => (sym, Some(file)),
+        None => (target.to_string(), None),
+    };
+    let file_path = match &file_hint {
+        Some(f) => match path_resolve::resolve(f, &root)
+            .into_iter()
+            .find(|m| m.kind == "file")
+        {
+            Some(m) => Some(m.path),
+            None => {
+                let message = format!("File not found: {}", f);
+                if json {
+                    return output::print_json_error("not_found", &message);
+                }
+                eprintln!("{}", message);
+                return 1;
+            }
+        },
+        None => None,
+    };
+
+    let Some(idx) = open_call_graph(&root) else {
+        if json {
+            return output::print_json_error("index", CALL_GRAPH_UNAVAILABLE);
+        }
+        return 1;
+    };
+    let (parent, name) = match symbol.rsplit_once('.') {
+        Some((parent, name)) => (Some(parent), name),
+        None => (None, symbol.as_str()),
+    };
+    let candidates = match idx.find_symbols(name, None, false, 1000) {
+        Ok(c) => c,
+        Err(e) => {
+            let message = format!("Error reading index: {}", e);
+            if json {
+                return output::print_json_error("index", &message);
+            }
+            eprintln!("{}", message);
+            return 1;
+        }
+    };
+    let candidates: Vec<&SymbolMatch> = candidates
+        .iter()
+        .filter(|s| s.name == name)
+        .filter(|s| parent.is_none_or(|p| s.parent.as_deref() == Some(p)))
+        .filter(|s| file_path.as_ref().is_none_or(|f| *f == s.file))
+        .collect();
+
+    let matches = signatures(&candidates, &root);
+    if json {
+        return output::print_json_results(&matches);
+    }
+    if matches.is_empty() {
+        eprintln!("Symbol not found: {}", target);
+        return 1;
+    }
+
+    if let [only] = matches.as_slice() {
+        println!("{}", only.signature);
+    } else {
+        for m in &matches {
+            println!("{}:{}: {}", m.file, m.line, m.signature);
+        }
+    }
+    0
+}
+
+/// Look up each symbol's signature in its file, ordered by file and line.
+fn signatures(symbols: &[&SymbolMatch], root: &Path) -> Vec<SignatureMatch> {
+    let extractor = Extractor::with_options(ExtractOptions {
+        include_private: true,
+        include_nested: true,
+    });
+    let mut matches = Vec::new();
+    let mut files: Vec<&str> = symbols.iter().map(|s| s.file.as_str()).collect();
+    files.sort();
+    files.dedup();
+
+    for file in files {
+        let path = root.join(file);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let extracted = extractor.extract(&path, &content);
+        let details = extractor.function_details(&path, &content);
+        for sym in symbols.iter().filter(|s| s.file == file) {
+            let signature = find_at_line(&extracted.symbols, &sym.name, sym.start_line)
+                .map(|s| s.signature.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .or_else(|| {
+                    content
+                        .lines()
+                        .nth(sym.start_line.saturating_sub(1))
+                        .map(|l| l.trim().to_string())
+                });
+            if let Some(signature) = signature {
+                matches.push(SignatureMatch {
+                    name: sym.name.clone(),
+                    signature,
+                    file: sym.file.clone(),
+                    line: sym.start_line,
+                    details: details.get(&sym.start_line).cloned(),
+                });
+            }
+        }
+    }
+    matches.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    matches
+}
+
+/// The extracted symbol named `name` that starts at `line`, at any depth.
+fn find_at_line<'a>(symbols: &'a [Symbol], name: &str, line: usize) -> Option<&'a Symbol> {
+    symbols.iter().find_map(|s| {
+        if s.name == name && s.start_line == line {
+            Some(s)
+        } else {
+            find_at_line(&s.children, name, line)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::FileIndex;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_overloaded_method_signature() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("Calc.java"),
+            "public class Calc {\n    public int add(int a, int b) {\n        return a + b;\n    }\n\n    \
+             public double add(double a, double b) {\n        return a + b;\n    }\n}\n\n\
+             class Other {\n    void add(String s) {}\n}\n",
+        )
+        .unwrap();
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+
+        let symbols = index.find_symbols("add", None, false, 10).unwrap();
+        let calc: Vec<&SymbolMatch> = symbols
+            .iter()
+            .filter(|s| s.parent.as_deref() == Some("Calc"))
+            .collect();
+        let matches = signatures(&calc, dir.path());
+        let found: Vec<(&str, usize)> = matches
+            .iter()
+            .map(|m| (m.signature.as_str(), m.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("add(int a, int b)", 2), ("add(double a, double b)", 6)]
+        );
+    }
+
+    #[test]
+    fn test_signature_details() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("lib.rs"),
+            "pub fn open(path: &Path, create: bool) -> io::Result<File> {\n    todo!()\n}\n",
+        )
+        .unwrap();
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+
+        let symbols = index.find_symbols("open", None, false, 10).unwrap();
+        let refs: Vec<&SymbolMatch> = symbols.iter().collect();
+        let matches = signatures(&refs, dir.path());
+        let json = serde_json::to_value(&matches[0]).unwrap();
+        assert_eq!(json["return_type"], "io::Result<File>");
+        assert_eq!(
+            json["parameters"],
+            serde_json::json!([
+                {"name": "path", "type": "&Path", "optional": false},
+                {"name": "create", "type": "bool", "optional": false},
+            ])
+        );
+    }
+}
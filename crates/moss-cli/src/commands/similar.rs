@@ -0,0 +1,324 @@
+//! Similar command - find symbols that do similar things.
+//!
+//! Scores every indexed function or method against a target by Jaccard overlap
+//! of what they call (from the call graph), their signature tokens, and the
+//! words in their names. Call profile weighs most, so `load_users` and
+//! `fetch_accounts` calling the same helpers rank above `load_config`.
+
+use crate::commands::analyze::{open_call_graph, parse_file_symbol_string, CALL_GRAPH_UNAVAILABLE};
+use crate::index::SymbolMatch;
+use crate::output;
+use crate::path_resolve;
+use crate::paths::current_project_root;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+const CALLEE_WEIGHT: f64 = 0.5;
+const SIGNATURE_WEIGHT: f64 = 0.3;
+const NAME_WEIGHT: f64 = 0.2;
+
+/// Declaration keywords that say nothing about what a function does.
+const SIGNATURE_STOPWORDS: &[&str] = &[
+    "def",
+    "fn",
+    "func",
+    "function",
+    "pub",
+    "crate",
+    "async",
+    "await",
+    "self",
+    "this",
+    "mut",
+    "const",
+    "static",
+    "public",
+    "private",
+    "protected",
+    "return",
+    "returns",
+    "void",
+    "impl",
+    "override",
+    "virtual",
+    "export",
+    "default",
+];
+
+/// Name, signature and call profile of a function, as compared by `similar`.
+struct Profile<'a> {
+    symbol: &'a SymbolMatch,
+    name_words: HashSet<String>,
+    signature: HashSet<String>,
+    callees: HashSet<String>,
+}
+
+/// A symbol's similarity to the target, overall and per component.
+#[derive(Debug, Clone, serde::Serialize)]
+struct SimilarMatch {
+    file: String,
+    name: String,
+    kind: String,
+    line: usize,
+    score: f64,
+    callees: f64,
+    signature: f64,
+    name_words: f64,
+}
+
+/// Find the `limit` symbols most similar to `target` (`name` or `file:name`).
+pub fn cmd_similar(target: &str, root: Option<&Path>, limit: usize, json: bool) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(current_project_root);
+    let (symbol, file_hint) = match parse_file_symbol_string(target) {
+        Some((sym, file)) => (sym, Some(file)),
+        None => (target.to_string(), None),
+    };
+    let file_path = file_hint.as_ref().and_then(|f| {
+        path_resolve::resolve(f, &root)
+            .into_iter()
+            .find(|m| m.kind == "file")
+            .map(|m| m.path)
+    });
+
+    let Some(idx) = open_call_graph(&root) else {
+        if json {
+            return output::print_json_error("index", CALL_GRAPH_UNAVAILABLE);
+        }
+        return 1;
+    };
+    let (symbols, profiles) = match (idx.all_symbols(), idx.call_profiles()) {
+        (Ok(s), Ok(p)) => (s, p),
+        (Err(e), _) | (_, Err(e)) => {
+            let message = format!("Error reading index: {}", e);
+            if json {
+                return output::print_json_error("index", &message);
+            }
+            eprintln!("{}", message);
+            return 1;
+        }
+    };
+    let callees: HashMap<(&str, &str), &HashSet<String>> = profiles
+        .iter()
+        .map(|p| ((p.file.as_str(), p.symbol.as_str()), &p.callees))
+        .collect();
+
+    let functions: Vec<&SymbolMatch> = symbols
+        .iter()
+        .filter(|s| s.kind == "function" || s.kind == "method")
+        .collect();
+    let lines = DeclarationLines::load(&functions, &root);
+    let profiles: Vec<Profile> = functions
+        .iter()
+        .map(|s| profile(s, &lines, &callees))
+        .collect();
+
+    let Some(target_profile) = profiles.iter().find(|p| {
+        p.symbol.name == symbol && file_path.as_ref().is_none_or(|f| *f == p.symbol.file)
+    }) else {
+        let message = format!("Function not found: {}", target);
+        if json {
+            return output::print_json_error("not_found", &message);
+        }
+        eprintln!("{}", message);
+        return 1;
+    };
+
+    let mut matches = rank_similar(target_profile, &profiles);
+    matches.truncate(limit);
+
+    if json {
+        return output::print_json_results(&matches);
+    }
+    if matches.is_empty() {
+        eprintln!("No similar functions found for: {}", symbol);
+        return 1;
+    }
+    println!(
+        "Similar to {} ({}:{}):",
+        symbol, target_profile.symbol.file, target_profile.symbol.start_line
+    );
+    for m in &matches {
+        println!(
+            "  {:.2}  {}:{} {}  (callees {:.2}, signature {:.2}, name {:.2})",
+            m.score, m.file, m.line, m.name, m.callees, m.signature, m.name_words
+        );
+    }
+    0
+}
+
+/// Score every other profile against `target`, best first. Zero scores are dropped.
+fn rank_similar(target: &Profile, profiles: &[Profile]) -> Vec<SimilarMatch> {
+    let mut matches: Vec<SimilarMatch> = profiles
+        .iter()
+        .filter(|p| !std::ptr::eq(p.symbol, target.symbol))
+        .filter_map(|p| {
+            let callees = jaccard(&target.callees, &p.callees);
+            let signature = jaccard(&target.signature, &p.signature);
+            let name_words = jaccard(&target.name_words, &p.name_words);
+            let score =
+                CALLEE_WEIGHT * callees + SIGNATURE_WEIGHT * signature + NAME_WEIGHT * name_words;
+            (score > 0.0).then(|| SimilarMatch {
+                file: p.symbol.file.clone(),
+                name: p.symbol.name.clone(),
+                kind: p.symbol.kind.clone(),
+                line: p.symbol.start_line,
+                score,
+                callees,
+                signature,
+                name_words,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| (&a.file, a.line).cmp(&(&b.file, b.line)))
+    });
+    matches
+}
+
+fn profile<'a>(
+    symbol: &'a SymbolMatch,
+    lines: &DeclarationLines,
+    callees: &HashMap<(&str, &str), &HashSet<String>>,
+) -> Profile<'a> {
+    let name_words: HashSet<String> = words(&symbol.name).collect();
+    let signature = lines
+        .get(&symbol.file, symbol.start_line)
+        .map(|line| {
+            words(line)
+                .filter(|w| !name_words.contains(w) && !SIGNATURE_STOPWORDS.contains(&w.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+    Profile {
+        symbol,
+        name_words,
+        signature,
+        callees: callees
+            .get(&(symbol.file.as_str(), symbol.name.as_str()))
+            .map(|c| (*c).clone())
+            .unwrap_or_default(),
+    }
+}
+
+/// |a ∩ b| / |a ∪ b|, or 0 when both are empty.
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Lowercase words of identifiers in `text`, split at `_` and camelCase humps.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .flat_map(|ident| {
+            let mut parts = Vec::new();
+            let mut current = String::new();
+            let mut prev_lower = false;
+            for c in ident.chars() {
+                if c.is_uppercase() && prev_lower && !current.is_empty() {
+                    parts.push(std::mem::take(&mut current));
+                }
+                prev_lower = c.is_lowercase() || c.is_ascii_digit();
+                current.extend(c.to_lowercase());
+            }
+            parts.push(current);
+            parts
+        })
+        .filter(|w| w.len() > 1 && !w.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// The declaration line of each function, read once per file.
+struct DeclarationLines {
+    files: HashMap<String, Vec<String>>,
+}
+
+impl DeclarationLines {
+    fn load(symbols: &[&SymbolMatch], root: &Path) -> Self {
+        let mut files = HashMap::new();
+        for sym in symbols {
+            if files.contains_key(&sym.file) {
+                continue;
+            }
+            if let Ok(content) = std::fs::read_to_string(root.join(&sym.file)) {
+                files.insert(
+                    sym.file.clone(),
+                    content.lines().map(str::to_string).collect(),
+                );
+            }
+        }
+        Self { files }
+    }
+
+    /// 1-based line lookup.
+    fn get(&self, file: &str, line: usize) -> Option<&str> {
+        self.files
+            .get(file)?
+            .get(line.checked_sub(1)?)
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::FileIndex;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_words() {
+        let got: Vec<String> = words("def parseHTTPRequest(raw_bytes, n=2):").collect();
+        assert_eq!(got, vec!["def", "parse", "httprequest", "raw", "bytes"]);
+    }
+
+    #[test]
+    fn test_shared_callees_rank_similar() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.py"),
+            "def load_users(path):\n    data = read_file(path)\n    rows = parse_csv(data)\n    validate(rows)\n\n\
+             def load_config(name):\n    return CONFIG[name]\n\n\
+             def render(x):\n    print(x)\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b.py"),
+            "def fetch_accounts(path):\n    raw = read_file(path)\n    parsed = parse_csv(raw)\n    validate(parsed)\n",
+        )
+        .unwrap();
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+
+        let symbols = index.all_symbols().unwrap();
+        let call_profiles = index.call_profiles().unwrap();
+        let callees: HashMap<(&str, &str), &HashSet<String>> = call_profiles
+            .iter()
+            .map(|p| ((p.file.as_str(), p.symbol.as_str()), &p.callees))
+            .collect();
+        let functions: Vec<&SymbolMatch> = symbols.iter().collect();
+        let lines = DeclarationLines::load(&functions, dir.path());
+        let profiles: Vec<Profile> = functions
+            .iter()
+            .map(|s| profile(s, &lines, &callees))
+            .collect();
+        let target = profiles
+            .iter()
+            .find(|p| p.symbol.name == "load_users")
+            .unwrap();
+
+        let matches = rank_similar(target, &profiles);
+        // Same callees and parameters outrank a shared name prefix
+        assert_eq!(matches[0].name, "fetch_accounts");
+        assert_eq!(matches[0].callees, 1.0);
+        assert_eq!(matches[1].name, "load_config");
+        assert!(matches.iter().all(|m| m.name != "load_users"));
+        assert!(matches.iter().all(|m| m.name != "render"));
+    }
+}
@@ -0,0 +1,506 @@
+//! Symbols command - export the symbol index as a symbol map or editor tag files.
+//!
+//! `--all` lists every symbol in the `symbols` table; text output is streamed
+//! row by row so large projects don't build the whole map in memory. Writes ctags (vim,
+//! less, most editors) or etags (Emacs) files, so editors can jump to
+//! definitions without a language server. Can also report symbols that may
+//! be stale because their file changed since it was indexed.
+
+use crate::index::{self, ChangedSource, SymbolEntry, SymbolMatch};
+use crate::paths::current_project_root;
+use moss_languages::support_for_path;
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Which symbols `moss symbols --all` lists. Empty lists match everything.
+pub struct SymbolFilter {
+    pub kinds: Vec<String>,
+    /// Language names (`rust`, `python`, `c++`), matched case-insensitively
+    pub langs: Vec<String>,
+}
+
+impl SymbolFilter {
+    fn matches_file(&self, file: &str) -> bool {
+        if self.langs.is_empty() {
+            return true;
+        }
+        support_for_path(Path::new(file)).is_some_and(|lang| {
+            self.langs.iter().any(|want| {
+                want.eq_ignore_ascii_case(lang.name())
+                    || want.eq_ignore_ascii_case(lang.grammar_name())
+            })
+        })
+    }
+}
+
+/// Export symbols to tag files, list stale symbols, or list every symbol.
+/// Tag files list paths relative to `root`, so they belong in the project root.
+pub fn cmd_symbols(
+    list_all: Option<SymbolFilter>,
+    export_ctags: Option<&Path>,
+    export_etags: Option<&Path>,
+    changed_since_index: bool,
+    root: Option<&Path>,
+    json: bool,
+) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(current_project_root);
+
+    if list_all.is_none()
+        && export_ctags.is_none()
+        && export_etags.is_none()
+        && !changed_since_index
+    {
+        eprintln!("error: nothing to export");
+        eprintln!("hint: use --all, --export-ctags, --export-etags or --changed-since-index");
+        return 1;
+    }
+
+    let idx = match index::FileIndex::open(&root) {
+        Ok(i) => i,
+        Err(e) => {
+            eprintln!("Failed to open index: {}", e);
+            return 1;
+        }
+    };
+    if idx.call_graph_stats().unwrap_or_default().symbols == 0 {
+        eprintln!("Symbols not indexed. Run: moss index rebuild --call-graph");
+        return 1;
+    }
+    if let Some(filter) = list_all {
+        let listed = if json {
+            json_all(&idx, &filter)
+        } else {
+            print_all(&idx, &filter).map(|()| 0)
+        };
+        return match listed {
+            Ok(code) => code,
+            Err(e) => {
+                eprintln!("Error reading symbols: {}", e);
+                1
+            }
+        };
+    }
+    let symbols = match idx.all_symbols() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Error reading symbols: {}", e);
+            return 1;
+        }
+    };
+
+    if changed_since_index {
+        let changed = match idx.changed_since_index() {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error checking indexed files: {}", e);
+                return 1;
+            }
+        };
+        print_stale(&stale_symbols(&changed, &symbols), json);
+        if export_ctags.is_none() && export_etags.is_none() {
+            return 0;
+        }
+    }
+
+    let sources = SourceLines::load_all(&symbols, &root);
+    let mut written: Vec<PathBuf> = Vec::new();
+    if let Some(path) = export_ctags {
+        let path = root.join(path);
+        if let Err(e) = std::fs::write(&path, format_ctags(&symbols, &sources)) {
+            eprintln!("error: cannot write {}: {}", path.display(), e);
+            return 1;
+        }
+        written.push(path);
+    }
+    if let Some(path) = export_etags {
+        let path = root.join(path);
+        if let Err(e) = std::fs::write(&path, format_etags(&symbols, &sources)) {
+            eprintln!("error: cannot write {}: {}", path.display(), e);
+            return 1;
+        }
+        written.push(path);
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "symbols": symbols.len(),
+                "files": written,
+            })
+        );
+    } else {
+        for path in &written {
+            println!("Wrote {} symbols to {}", symbols.len(), path.display());
+        }
+    }
+    0
+}
+
+/// Print every matching symbol in the `--json` list envelope.
+fn json_all(idx: &index::FileIndex, filter: &SymbolFilter) -> rusqlite::Result<i32> {
+    let mut entries = Vec::new();
+    for_each_matching(idx, filter, |entry| {
+        entries.push(entry);
+        true
+    })?;
+    Ok(crate::output::print_json_results(&entries))
+}
+
+/// Stream every matching symbol to stdout, one tab-separated
+/// `file:start-end kind name signature` line each. A closed pipe (`| head`)
+/// ends the listing quietly.
+fn print_all(idx: &index::FileIndex, filter: &SymbolFilter) -> rusqlite::Result<()> {
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+    let mut result = Ok(());
+    for_each_matching(idx, filter, |entry| {
+        let name = match &entry.parent {
+            Some(parent) => format!("{}.{}", parent, entry.name),
+            None => entry.name,
+        };
+        result = writeln!(
+            out,
+            "{}:{}-{}\t{}\t{}\t{}",
+            entry.file, entry.start_line, entry.end_line, entry.kind, name, entry.signature
+        );
+        result.is_ok()
+    })?;
+    if result.is_ok() {
+        result = out.flush();
+    }
+    match result {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+            eprintln!("error: {}", e);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Call `f` with each indexed symbol `filter` matches, until it returns false.
+fn for_each_matching(
+    idx: &index::FileIndex,
+    filter: &SymbolFilter,
+    mut f: impl FnMut(SymbolEntry) -> bool,
+) -> rusqlite::Result<()> {
+    // Language lookups are per extension, not per symbol
+    let mut file_matches: Option<(String, bool)> = None;
+    idx.for_each_symbol(&filter.kinds, |entry| {
+        let matches = match &file_matches {
+            Some((file, matches)) if *file == entry.file => *matches,
+            _ => {
+                let matches = filter.matches_file(&entry.file);
+                file_matches = Some((entry.file.clone(), matches));
+                matches
+            }
+        };
+        !matches || f(entry)
+    })
+}
+
+/// An indexed file that changed on disk, with the symbols indexed from it.
+struct StaleFile<'a> {
+    source: &'a ChangedSource,
+    symbols: Vec<&'a SymbolMatch>,
+}
+
+/// Group the symbols of changed files by file, in path order.
+fn stale_symbols<'a>(
+    changed: &'a [ChangedSource],
+    symbols: &'a [SymbolMatch],
+) -> Vec<StaleFile<'a>> {
+    let paths: HashSet<&str> = changed.iter().map(|c| c.path.as_str()).collect();
+    let mut by_file: HashMap<&str, Vec<&SymbolMatch>> = HashMap::new();
+    for sym in symbols.iter().filter(|s| paths.contains(s.file.as_str())) {
+        by_file.entry(&sym.file).or_default().push(sym);
+    }
+    changed
+        .iter()
+        .map(|source| StaleFile {
+            source,
+            symbols: by_file.remove(source.path.as_str()).unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn print_stale(stale: &[StaleFile], json: bool) {
+    if json {
+        let files: Vec<_> = stale
+            .iter()
+            .map(|f| {
+                serde_json::json!({
+                    "file": f.source.path,
+                    "deleted": f.source.deleted,
+                    "symbols": f.symbols.iter().map(|s| serde_json::json!({
+                        "name": s.name,
+                        "kind": s.kind,
+                        "line": s.start_line,
+                        "parent": s.parent,
+                    })).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::json!({ "stale": files }));
+        return;
+    }
+
+    if stale.is_empty() {
+        println!("Symbol index is up to date");
+        return;
+    }
+    let count: usize = stale.iter().map(|f| f.symbols.len()).sum();
+    println!(
+        "{} potentially stale symbols in {} changed files:",
+        count,
+        stale.len()
+    );
+    for file in stale {
+        let status = if file.source.deleted {
+            "deleted"
+        } else {
+            "modified"
+        };
+        println!("  {} ({})", file.source.path, status);
+        for sym in &file.symbols {
+            let name = match &sym.parent {
+                Some(parent) => format!("{}.{}", parent, sym.name),
+                None => sym.name.clone(),
+            };
+            println!("    {} {} (line {})", sym.kind, name, sym.start_line);
+        }
+    }
+    println!();
+    println!("Run: moss index rebuild --call-graph");
+}
+
+/// Source lines of indexed files, for tag search patterns and byte offsets.
+struct SourceLines {
+    lines: Vec<String>,
+    /// Byte offset of each line's start.
+    offsets: Vec<usize>,
+}
+
+impl SourceLines {
+    fn load_all(symbols: &[SymbolMatch], root: &Path) -> HashMap<String, SourceLines> {
+        let mut sources = HashMap::new();
+        for sym in symbols {
+            if sources.contains_key(&sym.file) {
+                continue;
+            }
+            if let Ok(content) = std::fs::read_to_string(root.join(&sym.file)) {
+                sources.insert(sym.file.clone(), SourceLines::new(&content));
+            }
+        }
+        sources
+    }
+
+    fn new(content: &str) -> Self {
+        let mut lines = Vec::new();
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+        for line in content.split_inclusive('\n') {
+            offsets.push(offset);
+            offset += line.len();
+            lines.push(line.trim_end_matches(['\n', '\r']).to_string());
+        }
+        Self { lines, offsets }
+    }
+
+    /// 1-based line lookup.
+    fn line(&self, line: usize) -> Option<&str> {
+        self.lines.get(line.checked_sub(1)?).map(String::as_str)
+    }
+}
+
+/// ctags kind letter for a moss symbol kind.
+fn ctags_kind(kind: &str) -> &'static str {
+    match kind {
+        "function" => "f",
+        "method" => "m",
+        "class" => "c",
+        "struct" => "s",
+        "enum" => "g",
+        "trait" | "interface" => "i",
+        "module" => "n",
+        "type" => "t",
+        "constant" => "C",
+        "variable" => "v",
+        "heading" => "S",
+        _ => "x",
+    }
+}
+
+/// Format symbols as an extended-format ctags file, sorted by tag name.
+///
+/// Each line: `name<TAB>file<TAB>/^line$/;"<TAB>kind<TAB>line:N[<TAB>scope:Parent]`.
+/// Symbols whose source can't be read fall back to a line-number address.
+fn format_ctags(symbols: &[SymbolMatch], sources: &HashMap<String, SourceLines>) -> String {
+    // Parent kinds for the scope field, e.g. "class:Greeter" vs "struct:Config"
+    let parent_kinds: HashMap<(&str, &str), &str> = symbols
+        .iter()
+        .map(|s| ((s.file.as_str(), s.name.as_str()), s.kind.as_str()))
+        .collect();
+
+    let mut lines: Vec<String> = symbols
+        .iter()
+        .map(|sym| {
+            let address = match sources.get(&sym.file).and_then(|s| s.line(sym.start_line)) {
+                Some(text) => format!("/^{}$/", text.replace('\\', "\\\\").replace('/', "\\/")),
+                None => sym.start_line.to_string(),
+            };
+            let mut line = format!(
+                "{}\t{}\t{};\"\t{}\tline:{}",
+                sym.name,
+                sym.file,
+                address,
+                ctags_kind(&sym.kind),
+                sym.start_line
+            );
+            if let Some(parent) = &sym.parent {
+                let scope = parent_kinds
+                    .get(&(sym.file.as_str(), parent.as_str()))
+                    .copied()
+                    .unwrap_or("class");
+                line.push_str(&format!("\t{}:{}", scope, parent));
+            }
+            line
+        })
+        .collect();
+    // Byte order, as editors binary-search the file
+    lines.sort();
+    lines.dedup();
+
+    let mut out = String::from(
+        "!_TAG_FILE_FORMAT\t2\t/extended format; --format=1 will not append ;\" to lines/\n\
+         !_TAG_FILE_SORTED\t1\t/0=unsorted, 1=sorted, 2=foldcase/\n\
+         !_TAG_PROGRAM_NAME\tmoss\t//\n",
+    );
+    for line in lines {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Format symbols as an Emacs etags (TAGS) file.
+///
+/// One section per source file: `\x0c\nfile,size\n` followed by
+/// `line text\x7fname\x01line,offset` entries.
+fn format_etags(symbols: &[SymbolMatch], sources: &HashMap<String, SourceLines>) -> String {
+    let mut out = String::new();
+    let mut by_file: Vec<(&str, Vec<&SymbolMatch>)> = Vec::new();
+    for sym in symbols {
+        match by_file.last_mut() {
+            Some((file, syms)) if *file == sym.file => syms.push(sym),
+            _ => by_file.push((&sym.file, vec![sym])),
+        }
+    }
+
+    for (file, syms) in by_file {
+        let Some(source) = sources.get(file) else {
+            continue;
+        };
+        let mut section = String::new();
+        for sym in syms {
+            let Some(text) = source.line(sym.start_line) else {
+                continue;
+            };
+            let offset = source.offsets[sym.start_line - 1];
+            section.push_str(&format!(
+                "{}\x7f{}\x01{},{}\n",
+                text, sym.name, sym.start_line, offset
+            ));
+        }
+        out.push_str(&format!("\x0c\n{},{}\n{}", file, section.len(), section));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::FileIndex;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn indexed_symbols(root: &Path) -> Vec<SymbolMatch> {
+        let mut index = FileIndex::open(root).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+        index.all_symbols().unwrap()
+    }
+
+    #[test]
+    fn test_export_ctags() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("greet.py"),
+            "class Greeter:\n    def greet(self):\n        pass\n\ndef main():\n    pass\n",
+        )
+        .unwrap();
+
+        let symbols = indexed_symbols(dir.path());
+        let sources = SourceLines::load_all(&symbols, dir.path());
+        let tags = format_ctags(&symbols, &sources);
+
+        assert!(tags.starts_with("!_TAG_FILE_FORMAT"));
+        assert!(tags.contains("Greeter\tgreet.py\t/^class Greeter:$/;\"\tc\tline:1\n"));
+        assert!(tags
+            .contains("greet\tgreet.py\t/^    def greet(self):$/;\"\tm\tline:2\tclass:Greeter\n"));
+        assert!(tags.contains("main\tgreet.py\t/^def main():$/;\"\tf\tline:5\n"));
+
+        // Sorted by tag name for binary search
+        let names: Vec<&str> = tags
+            .lines()
+            .filter(|l| !l.starts_with("!_"))
+            .map(|l| l.split('\t').next().unwrap())
+            .collect();
+        assert_eq!(names, vec!["Greeter", "greet", "main"]);
+    }
+
+    #[test]
+    fn test_export_etags() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.py"), "x = 1\ndef foo():\n    pass\n").unwrap();
+
+        let symbols = indexed_symbols(dir.path());
+        let sources = SourceLines::load_all(&symbols, dir.path());
+        let tags = format_etags(&symbols, &sources);
+
+        let entry = "def foo():\x7ffoo\x012,6\n";
+        assert!(tags.contains(entry));
+        assert!(tags.starts_with("\x0c\na.py,"));
+    }
+
+    #[test]
+    fn test_changed_since_index() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.py"), "def foo():\n    pass\n").unwrap();
+        fs::write(dir.path().join("b.py"), "def bar():\n    pass\n").unwrap();
+        fs::write(dir.path().join("c.py"), "def baz():\n    pass\n").unwrap();
+        let symbols = indexed_symbols(dir.path());
+        let index = FileIndex::open(dir.path()).unwrap();
+        assert!(index.changed_since_index().unwrap().is_empty());
+
+        fs::write(dir.path().join("a.py"), "def foo(x):\n    return x\n").unwrap();
+        fs::remove_file(dir.path().join("c.py")).unwrap();
+        let changed = index.changed_since_index().unwrap();
+        let stale = stale_symbols(&changed, &symbols);
+
+        let summary: Vec<(&str, bool, Vec<&str>)> = stale
+            .iter()
+            .map(|f| {
+                let names = f.symbols.iter().map(|s| s.name.as_str()).collect();
+                (f.source.path.as_str(), f.source.deleted, names)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("a.py", false, vec!["foo"]), ("c.py", true, vec!["baz"])]
+        );
+    }
+}
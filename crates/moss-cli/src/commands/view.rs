@@ -2,10 +2,14 @@
 
 use crate::commands::filter::detect_project_languages;
 use crate::config::MossConfig;
+use crate::extract::Extractor;
 use crate::filter::Filter;
+use crate::highlight::SourceHighlighter;
+use crate::paths::current_project_root;
 use crate::tree::{FormatOptions, ViewNode, ViewNodeKind};
-use crate::{daemon, deps, index, path_resolve, skeleton, symbols, tree};
+use crate::{daemon, deps, index, output, path_resolve, skeleton, symbols, tree};
 use moss_languages::support_for_path;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Check if a file has language support (symbols can be extracted)
@@ -42,6 +46,7 @@ fn cmd_view_symbol_direct(
     root: &Path,
     depth: i32,
     full: bool,
+    highlighter: &mut SourceHighlighter,
     json: bool,
 ) -> i32 {
     cmd_view_symbol(
@@ -50,6 +55,7 @@ fn cmd_view_symbol_direct(
         root,
         depth,
         full,
+        highlighter,
         json,
     )
 }
@@ -63,19 +69,37 @@ pub fn cmd_view(
     show_deps: bool,
     kind_filter: Option<&str>,
     types_only: bool,
+    group: bool,
     raw: bool,
     focus: Option<&str>,
     resolve_imports: bool,
     include_private: bool,
     full: bool,
     context: bool,
+    window: LineWindow,
+    skeleton: bool,
+    highlight: bool,
+    theme: Option<&str>,
     json: bool,
     exclude: &[String],
     only: &[String],
 ) -> i32 {
     let root = root
         .map(|p| p.to_path_buf())
-        .unwrap_or_else(|| std::env::current_dir().unwrap());
+        .unwrap_or_else(current_project_root);
+
+    // JSON always carries the raw source
+    let mut highlighter = if json {
+        SourceHighlighter::plain()
+    } else {
+        match SourceHighlighter::new(highlight, theme) {
+            Ok(h) => h,
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return 1;
+            }
+        }
+    };
 
     // Ensure daemon is running if configured
     daemon::maybe_start_daemon(&root);
@@ -106,7 +130,7 @@ pub fn cmd_view(
     // If kind filter is specified without target (or with "."), list matching symbols
     if let Some(kind) = kind_filter {
         let scope = target.unwrap_or(".");
-        return cmd_view_filtered(&root, scope, kind, json);
+        return cmd_view_filtered(&root, scope, kind, group, json);
     }
 
     // --focus requires a file target
@@ -137,7 +161,30 @@ pub fn cmd_view(
         (0, 1) => {
             // Single symbol match - construct path to it
             let sym = &symbol_matches[0];
-            return cmd_view_symbol_direct(&sym.file, &sym.name, &root, depth, full, json);
+            if skeleton {
+                return cmd_view_skeleton(&sym.file, std::slice::from_ref(&sym.name), &root, json);
+            }
+            if window.is_set() {
+                let symbol_path = [sym.name.clone()];
+                return cmd_view_lines(
+                    &sym.file,
+                    &symbol_path,
+                    &root,
+                    window,
+                    line_numbers,
+                    &mut highlighter,
+                    json,
+                );
+            }
+            return cmd_view_symbol_direct(
+                &sym.file,
+                &sym.name,
+                &root,
+                depth,
+                full,
+                &mut highlighter,
+                json,
+            );
         }
         _ => {
             // Multiple matches - list files and symbols
@@ -191,7 +238,27 @@ pub fn cmd_view(
         }
     };
 
-    if unified.is_directory {
+    if window.is_set() {
+        if unified.is_directory {
+            eprintln!("--lines, --around and -C need a file or symbol target");
+            return 1;
+        }
+        cmd_view_lines(
+            &unified.file_path,
+            &unified.symbol_path,
+            &root,
+            window,
+            line_numbers,
+            &mut highlighter,
+            json,
+        )
+    } else if skeleton {
+        if unified.is_directory {
+            eprintln!("--skeleton needs a file or symbol target");
+            return 1;
+        }
+        cmd_view_skeleton(&unified.file_path, &unified.symbol_path, &root, json)
+    } else if unified.is_directory {
         // View directory
         cmd_view_directory(
             &root.join(&unified.file_path),
@@ -211,10 +278,12 @@ pub fn cmd_view(
             line_numbers,
             show_deps,
             types_only,
+            group,
             focus,
             resolve_imports,
             include_private,
             context,
+            &mut highlighter,
             json,
         )
     } else {
@@ -225,13 +294,288 @@ pub fn cmd_view(
             &root,
             depth,
             full,
+            &mut highlighter,
             json,
         )
     }
 }
 
+/// Lines on either side of `--around` when `-C` isn't given.
+const DEFAULT_AROUND_CONTEXT: usize = 5;
+
+/// Line selection from `--lines`, `--around` and `-C`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LineWindow<'a> {
+    /// `START:END`, 1-based and inclusive; either side may be left out
+    pub lines: Option<&'a str>,
+    /// Line to center the window on
+    pub around: Option<usize>,
+    /// Lines on either side of `--around` or of the target symbol
+    pub context: Option<usize>,
+}
+
+impl LineWindow<'_> {
+    fn is_set(&self) -> bool {
+        self.lines.is_some() || self.around.is_some() || self.context.is_some()
+    }
+
+    /// Resolve to an inclusive 1-based range within a file of `total` lines.
+    /// Without `--lines` or `--around`, the window is the symbol's own lines.
+    fn resolve(
+        &self,
+        symbol: Option<(usize, usize)>,
+        total: usize,
+    ) -> Result<(usize, usize), String> {
+        if let Some(spec) = self.lines {
+            let (start, end) = spec
+                .split_once(':')
+                .ok_or_else(|| format!("invalid line range '{}': expected START:END", spec))?;
+            let parse = |s: &str, default: usize| {
+                let s = s.trim();
+                if s.is_empty() {
+                    Ok(default)
+                } else {
+                    s.parse::<usize>().map_err(|_| {
+                        format!(
+                            "invalid line range '{}': '{}' is not a line number",
+                            spec, s
+                        )
+                    })
+                }
+            };
+            let (start, end) = (parse(start, 1)?, parse(end, total)?);
+            if start == 0 {
+                return Err(format!("invalid line range '{}': lines start at 1", spec));
+            }
+            if start > end {
+                return Err(format!("invalid line range '{}': start is after end", spec));
+            }
+            if end > total {
+                return Err(format!(
+                    "line range {}:{} is past the end of the file ({} lines)",
+                    start, end, total
+                ));
+            }
+            return Ok((start, end));
+        }
+
+        let (first, last, context) = match (self.around, symbol) {
+            (Some(line), _) => {
+                if line == 0 || line > total {
+                    return Err(format!(
+                        "line {} is outside the file ({} lines)",
+                        line, total
+                    ));
+                }
+                (line, line, self.context.unwrap_or(DEFAULT_AROUND_CONTEXT))
+            }
+            (None, Some((start, end))) => (start, end.min(total), self.context.unwrap_or(0)),
+            (None, None) => return Err("-C needs --around or a symbol target".to_string()),
+        };
+        Ok((
+            first.saturating_sub(context).max(1),
+            (last + context).min(total),
+        ))
+    }
+}
+
+/// Print a range of lines from a file, or around a symbol in it.
+fn cmd_view_lines(
+    file_path: &str,
+    symbol_path: &[String],
+    root: &Path,
+    window: LineWindow,
+    line_numbers: bool,
+    highlighter: &mut SourceHighlighter,
+    json: bool,
+) -> i32 {
+    let full_path = root.join(file_path);
+    let content = match std::fs::read_to_string(&full_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", file_path, e);
+            return 1;
+        }
+    };
+    let lines: Vec<&str> = content.lines().collect();
+
+    let symbol = match symbol_path.last() {
+        Some(name) => {
+            let skeleton_result =
+                skeleton::SkeletonExtractor::with_all().extract(&full_path, &content);
+            match find_symbol(&skeleton_result.symbols, name) {
+                Some(sym) if sym.start_line > 0 => Some((sym.start_line, sym.end_line)),
+                _ => {
+                    eprintln!("Symbol not found: {}", name);
+                    return 1;
+                }
+            }
+        }
+        None => None,
+    };
+
+    let (start, end) = match window.resolve(symbol, lines.len()) {
+        Ok(range) => range,
+        Err(message) if json => return output::print_json_error("invalid_range", &message),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return 1;
+        }
+    };
+    let selected = &lines[start - 1..end];
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "type": "lines",
+                "path": file_path,
+                "start_line": start,
+                "end_line": end,
+                "content": selected.join("\n"),
+            })
+        );
+    } else {
+        // Highlight the whole file so constructs opened before the range keep their colors
+        let rendered = highlighter.lines(&full_path, &content);
+        for (i, line) in rendered[start - 1..end].iter().enumerate() {
+            if line_numbers {
+                println!("{:4} {}", start + i, line);
+            } else {
+                println!("{}", line);
+            }
+        }
+    }
+    0
+}
+
+/// Lines shown for files with no outline support.
+const SKELETON_FALLBACK_LINES: usize = 40;
+
+/// Print a file's (or symbol's) outline: imports, then signatures indented by
+/// nesting with bodies elided as `...`.
+fn cmd_view_skeleton(file_path: &str, symbol_path: &[String], root: &Path, json: bool) -> i32 {
+    let full_path = root.join(file_path);
+    let content = match std::fs::read_to_string(&full_path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", file_path, e);
+            return 1;
+        }
+    };
+
+    if support_for_path(&full_path).is_none() {
+        let head: Vec<&str> = content.lines().take(SKELETON_FALLBACK_LINES).collect();
+        if json {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "type": "skeleton",
+                    "path": file_path,
+                    "supported": false,
+                    "content": head.join("\n"),
+                })
+            );
+        } else {
+            eprintln!(
+                "note: no outline support for {}; showing the first {} lines",
+                file_path, SKELETON_FALLBACK_LINES
+            );
+            for line in head {
+                println!("{}", line);
+            }
+        }
+        return 0;
+    }
+
+    let skeleton_result = skeleton::SkeletonExtractor::with_all().extract(&full_path, &content);
+    let (symbols, imports) = match symbol_path.last() {
+        Some(name) => match find_symbol(&skeleton_result.symbols, name) {
+            Some(sym) => (vec![sym.clone()], Vec::new()),
+            None => {
+                eprintln!("Symbol not found: {}", name);
+                return 1;
+            }
+        },
+        None => {
+            let deps_extractor =
+                deps::DepsExtractor::with_cfg(MossConfig::load(root).symbols.rust_cfg());
+            let deps = deps_extractor.extract(&full_path, &content);
+            (
+                skeleton_result.symbols,
+                import_statements(&deps.imports, &content),
+            )
+        }
+    };
+    let outline = skeleton::SkeletonResult {
+        symbols,
+        file_path: file_path.to_string(),
+    };
+
+    if json {
+        let tree = outline.to_view_node();
+        println!(
+            "{}",
+            serde_json::json!({
+                "type": "skeleton",
+                "path": file_path,
+                "supported": true,
+                "imports": imports,
+                "symbols": tree.children,
+            })
+        );
+    } else {
+        println!("# {}", file_path);
+        for import in &imports {
+            println!("{}", import);
+        }
+        if !imports.is_empty() {
+            println!();
+        }
+        for line in outline.outline() {
+            println!("{}", line);
+        }
+    }
+    0
+}
+
+/// Source text of each import statement, in file order. Statements that
+/// span lines (parenthesized or braced name lists) are kept whole.
+fn import_statements(imports: &[deps::Import], content: &str) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let starts: std::collections::BTreeSet<usize> = imports.iter().map(|i| i.line).collect();
+    let mut statements = Vec::new();
+    let mut covered = 0;
+    for start in starts {
+        if start == 0 || start <= covered || start > lines.len() {
+            continue;
+        }
+        let mut statement = Vec::new();
+        let mut depth = 0i32;
+        for (i, line) in lines.iter().enumerate().skip(start - 1) {
+            statement.push(line.trim_end());
+            covered = i + 1;
+            for c in line.chars() {
+                match c {
+                    '(' | '{' | '[' => depth += 1,
+                    ')' | '}' | ']' => depth -= 1,
+                    _ => {}
+                }
+            }
+            if depth <= 0 {
+                break;
+            }
+        }
+        statements.push(statement.join("\n"));
+    }
+    statements
+}
+
+/// A symbol listing row: (file, name, kind, line, parent)
+type SymbolRow = (String, String, String, usize, Option<String>);
+
 /// List symbols matching a kind filter within a scope
-fn cmd_view_filtered(root: &Path, scope: &str, kind: &str, json: bool) -> i32 {
+fn cmd_view_filtered(root: &Path, scope: &str, kind: &str, group: bool, json: bool) -> i32 {
     // Normalize kind
     let kind_lower = kind.to_lowercase();
     let kind_filter = match kind_lower.as_str() {
@@ -266,7 +610,7 @@ fn cmd_view_filtered(root: &Path, scope: &str, kind: &str, json: bool) -> i32 {
             .collect()
     };
 
-    let mut all_symbols: Vec<(String, String, String, usize, Option<String>)> = Vec::new();
+    let mut all_symbols: Vec<SymbolRow> = Vec::new();
     let parser = symbols::SymbolParser::new();
 
     for file_path in files_to_search {
@@ -312,6 +656,10 @@ fn cmd_view_filtered(root: &Path, scope: &str, kind: &str, json: bool) -> i32 {
     // Sort by file, then line
     all_symbols.sort_by(|a, b| (&a.0, a.3).cmp(&(&b.0, b.3)));
 
+    if group {
+        return print_grouped_symbols(&all_symbols, json);
+    }
+
     if json {
         let output: Vec<_> = all_symbols
             .iter()
@@ -340,9 +688,84 @@ fn cmd_view_filtered(root: &Path, scope: &str, kind: &str, json: bool) -> i32 {
     0
 }
 
+/// Same-named symbols under one parent, with every definition site.
+struct SymbolGroup<'a> {
+    name: &'a str,
+    kind: &'a str,
+    parent: Option<&'a str>,
+    defs: Vec<(&'a str, usize)>,
+}
+
+/// Print symbols grouped by (parent, name): `foo (3 defs): a.rs:10, a.rs:20, b.rs:5`
+fn print_grouped_symbols(all_symbols: &[SymbolRow], json: bool) -> i32 {
+    let mut groups: Vec<SymbolGroup> = Vec::new();
+    let mut index: HashMap<(Option<&str>, &str), usize> = HashMap::new();
+    for (file, name, kind, line, parent) in all_symbols {
+        let parent = parent.as_deref();
+        let i = *index.entry((parent, name)).or_insert_with(|| {
+            groups.push(SymbolGroup {
+                name,
+                kind,
+                parent,
+                defs: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        groups[i].defs.push((file, *line));
+    }
+
+    if json {
+        let output: Vec<_> = groups
+            .iter()
+            .map(|g| {
+                let defs: Vec<_> = g
+                    .defs
+                    .iter()
+                    .map(|(file, line)| serde_json::json!({"file": file, "line": line}))
+                    .collect();
+                serde_json::json!({
+                    "name": g.name,
+                    "kind": g.kind,
+                    "parent": g.parent,
+                    "defs": defs
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string(&output).unwrap());
+    } else {
+        for g in &groups {
+            let parent_str = g.parent.map(|p| format!(" (in {})", p)).unwrap_or_default();
+            let locations: Vec<_> = g
+                .defs
+                .iter()
+                .map(|(file, line)| format!("{}:{}", file, line))
+                .collect();
+            if g.defs.len() == 1 {
+                println!("{} {} {}{}", locations[0], g.kind, g.name, parent_str);
+            } else {
+                println!(
+                    "{} {} ({} defs){}: {}",
+                    g.kind,
+                    g.name,
+                    g.defs.len(),
+                    parent_str,
+                    locations.join(", ")
+                );
+            }
+        }
+        eprintln!(
+            "\n{} symbols found ({} distinct names)",
+            all_symbols.len(),
+            groups.len()
+        );
+    }
+
+    0
+}
+
 fn cmd_view_directory(
     dir: &Path,
-    _root: &Path,
+    root: &Path,
     depth: i32,
     raw: bool,
     json: bool,
@@ -364,6 +787,7 @@ fn cmd_view_directory(
             max_depth: effective_depth,
             collapse_single: !raw,
             include_symbols,
+            walk: MossConfig::load(root).walk,
             ..Default::default()
         },
     );
@@ -468,10 +892,12 @@ fn cmd_view_file(
     line_numbers: bool,
     show_deps: bool,
     types_only: bool,
+    group: bool,
     focus: Option<&str>,
     resolve_imports: bool,
     include_private: bool,
     context: bool,
+    highlighter: &mut SourceHighlighter,
     json: bool,
 ) -> i32 {
     let full_path = root.join(file_path);
@@ -500,9 +926,11 @@ fn cmd_view_file(
                 })
             );
         } else if line_numbers {
-            for (i, line) in content.lines().enumerate() {
+            for (i, line) in highlighter.lines(&full_path, &content).iter().enumerate() {
                 println!("{:4} {}", i + 1, line);
             }
+        } else if highlighter.is_active() {
+            print_source(highlighter, &full_path, &content);
         } else {
             print!("{}", content);
         }
@@ -524,9 +952,17 @@ fn cmd_view_file(
         skeleton_result
     };
 
+    // Collapse overloads into one entry if requested
+    let skeleton_result = if group {
+        skeleton_result.group_overloads()
+    } else {
+        skeleton_result
+    };
+
     // Get deps if showing deps, focus, resolve_imports, or context mode
     let deps_result = if show_deps || focus.is_some() || resolve_imports || context {
-        let deps_extractor = deps::DepsExtractor::new();
+        let deps_extractor =
+            deps::DepsExtractor::with_cfg(MossConfig::load(root).symbols.rust_cfg());
         Some(deps_extractor.extract(&full_path, &content))
     } else {
         None
@@ -534,7 +970,8 @@ fn cmd_view_file(
 
     if json {
         // Use ViewNode for consistent structured output
-        let view_node = skeleton_result.to_view_node();
+        let details = Extractor::new().function_details(&full_path, &content);
+        let view_node = skeleton_result.to_view_node().with_details(&details);
         println!("{}", serde_json::to_string(&view_node).unwrap());
     } else {
         println!("# {}", file_path);
@@ -765,12 +1202,20 @@ fn find_symbol_signature(symbols: &[skeleton::SkeletonSymbol], name: &str) -> Op
     find_symbol(symbols, name).map(|sym| sym.signature.clone())
 }
 
+/// Print source text, colored when the highlighter is active.
+fn print_source(highlighter: &mut SourceHighlighter, path: &Path, source: &str) {
+    for line in highlighter.lines(path, source) {
+        println!("{}", line);
+    }
+}
+
 fn cmd_view_symbol(
     file_path: &str,
     symbol_path: &[String],
     root: &Path,
     depth: i32,
     full: bool,
+    highlighter: &mut SourceHighlighter,
     json: bool,
 ) -> i32 {
     let full_path = root.join(file_path);
@@ -804,7 +1249,7 @@ fn cmd_view_symbol(
             if depth >= 0 {
                 println!("# {}", full_symbol_path);
             }
-            println!("{}", source);
+            print_source(highlighter, &full_path, &source);
         }
         0
     } else {
@@ -839,7 +1284,7 @@ fn cmd_view_symbol(
                     if depth >= 0 {
                         println!("# {}", full_symbol_path);
                     }
-                    println!("{}", source);
+                    print_source(highlighter, &full_path, &source);
                 }
                 return 0;
             }
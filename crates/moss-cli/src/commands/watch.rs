@@ -0,0 +1,152 @@
+//! Watch command - keep the index fresh as files change.
+
+use crate::index::{CallGraphStats, ChangedFiles, FileIndex};
+use crate::paths::{current_project_root, get_moss_dir};
+use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::Duration;
+
+/// Quiet period after the last event before a batch is applied.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watch the tree and update the index until interrupted.
+pub fn cmd_watch(root: Option<&Path>, files_only: bool, json: bool) -> i32 {
+    let root = root
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(current_project_root);
+    let root = root.canonicalize().unwrap_or(root);
+
+    let mut idx = match FileIndex::open(&root) {
+        Ok(idx) => idx,
+        Err(e) => {
+            eprintln!("Failed to open index: {}", e);
+            return 1;
+        }
+    };
+
+    // Start from a current index so later deltas are relative to the tree
+    let file_count = match idx.refresh() {
+        Ok(count) => count,
+        Err(e) => {
+            eprintln!("Failed to index files: {}", e);
+            return 1;
+        }
+    };
+    if files_only {
+        eprintln!("Indexed {} files", file_count);
+    } else {
+        match idx.incremental_call_graph_refresh() {
+            Ok(stats) => eprintln!(
+                "Indexed {} files ({} symbols, {} calls re-parsed)",
+                file_count, stats.symbols, stats.calls
+            ),
+            Err(e) => {
+                eprintln!("Failed to build call graph: {}", e);
+                return 1;
+            }
+        }
+    }
+
+    let (tx, rx) = channel();
+    let mut watcher = match RecommendedWatcher::new(tx, Config::default()) {
+        Ok(w) => w,
+        Err(e) => {
+            eprintln!("Failed to create file watcher: {}", e);
+            return 1;
+        }
+    };
+    if let Err(e) = watcher.watch(&root, RecursiveMode::Recursive) {
+        eprintln!("Failed to watch directory: {}", e);
+        return 1;
+    }
+    eprintln!("Watching {} (Ctrl+C to stop)", root.display());
+
+    let moss_dir = get_moss_dir(&root);
+    let git_dir = root.join(".git");
+    // Index writes land in .moss and would otherwise retrigger the watcher
+    let relevant = |path: &Path| !path.starts_with(&moss_dir) && !path.starts_with(&git_dir);
+
+    loop {
+        // Block for the first event, then gather until the tree goes quiet
+        let mut batch: BTreeSet<PathBuf> = BTreeSet::new();
+        let Ok(mut result) = rx.recv() else {
+            break;
+        };
+        loop {
+            // Reads (including the index's own) show up as access events
+            if let Some(event) = result.ok().filter(|e| !e.kind.is_access()) {
+                batch.extend(event.paths.into_iter().filter(|p| relevant(p)));
+            }
+            result = match rx.recv_timeout(DEBOUNCE) {
+                Ok(result) => result,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return 0,
+            };
+        }
+        if batch.is_empty() {
+            continue;
+        }
+
+        let paths: Vec<PathBuf> = batch.into_iter().collect();
+        let changed = match idx.update_paths(&paths) {
+            Ok(changed) => changed,
+            Err(e) => {
+                eprintln!("Error updating index: {}", e);
+                continue;
+            }
+        };
+        if changed.added.is_empty() && changed.modified.is_empty() && changed.deleted.is_empty() {
+            continue;
+        }
+
+        let stats = if files_only {
+            None
+        } else {
+            match idx.incremental_call_graph_refresh() {
+                Ok(stats) => Some(stats),
+                Err(e) => {
+                    eprintln!("Error during call graph refresh: {}", e);
+                    None
+                }
+            }
+        };
+        print_delta(&changed, stats, json);
+    }
+
+    0
+}
+
+/// Log one applied batch.
+fn print_delta(changed: &ChangedFiles, stats: Option<CallGraphStats>, json: bool) {
+    if json {
+        let mut value = serde_json::json!({
+            "added": changed.added,
+            "modified": changed.modified,
+            "deleted": changed.deleted,
+        });
+        if let Some(stats) = stats {
+            value["symbols"] = serde_json::json!(stats.symbols);
+            value["calls"] = serde_json::json!(stats.calls);
+        }
+        println!("{}", value);
+        return;
+    }
+
+    for (mark, paths) in [
+        ('+', &changed.added),
+        ('~', &changed.modified),
+        ('-', &changed.deleted),
+    ] {
+        for path in paths {
+            println!("{} {}", mark, path);
+        }
+    }
+    if let Some(stats) = stats {
+        println!(
+            "  {} symbols, {} calls re-indexed",
+            stats.symbols, stats.calls
+        );
+    }
+}
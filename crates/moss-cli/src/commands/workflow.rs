@@ -4,6 +4,10 @@ use std::path::Path;
 
 use clap::Subcommand;
 
+#[cfg(feature = "lua")]
+use crate::config::MossConfig;
+#[cfg(feature = "lua")]
+use crate::paths::{current_project_root, get_moss_dir};
 #[cfg(feature = "lua")]
 use crate::workflow::LuaRuntime;
 
@@ -20,20 +24,34 @@ pub enum WorkflowAction {
         /// Task description (available as `task` variable in Lua)
         #[arg(short, long)]
         task: Option<String>,
+
+        /// Continue from the checkpoint left by an interrupted run
+        #[arg(long)]
+        resume: bool,
+
+        /// Print the commands, shell calls and LLM loops the workflow would
+        /// run, without running them
+        #[arg(long, conflicts_with = "resume")]
+        dry_run: bool,
     },
 }
 
 pub fn cmd_workflow(action: WorkflowAction, root: Option<&Path>, json: bool) -> i32 {
     match action {
         WorkflowAction::List => cmd_workflow_list(root, json),
-        WorkflowAction::Run { workflow, task } => {
-            cmd_workflow_run(&workflow, task.as_deref(), root, json)
-        }
+        WorkflowAction::Run {
+            workflow,
+            task,
+            resume,
+            dry_run,
+        } => cmd_workflow_run(&workflow, task.as_deref(), resume, dry_run, root, json),
     }
 }
 
 fn cmd_workflow_list(root: Option<&Path>, json: bool) -> i32 {
-    let root = root.unwrap_or_else(|| Path::new("."));
+    let root = &root
+        .map(Path::to_path_buf)
+        .unwrap_or_else(current_project_root);
     let workflows_dir = root.join(".moss").join("workflows");
 
     if !workflows_dir.exists() {
@@ -74,8 +92,17 @@ fn cmd_workflow_list(root: Option<&Path>, json: bool) -> i32 {
 }
 
 #[cfg(feature = "lua")]
-fn cmd_workflow_run(workflow: &str, task: Option<&str>, root: Option<&Path>, json: bool) -> i32 {
-    let root = root.unwrap_or_else(|| Path::new("."));
+fn cmd_workflow_run(
+    workflow: &str,
+    task: Option<&str>,
+    resume: bool,
+    dry_run: bool,
+    root: Option<&Path>,
+    json: bool,
+) -> i32 {
+    let root = &root
+        .map(Path::to_path_buf)
+        .unwrap_or_else(current_project_root);
 
     let workflow_path = if workflow.ends_with(".lua") {
         root.join(workflow)
@@ -102,6 +129,30 @@ fn cmd_workflow_run(workflow: &str, task: Option<&str>, root: Option<&Path>, jso
         }
     };
 
+    // Checkpoints live in .moss/workflow-state/<name>.json
+    let name = workflow_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let checkpoint = get_moss_dir(root)
+        .join("workflow-state")
+        .join(format!("{}.json", name));
+    if resume {
+        if !checkpoint.exists() {
+            eprintln!("No checkpoint to resume: {}", checkpoint.display());
+            return 1;
+        }
+        if let Err(e) = runtime.resume(&checkpoint) {
+            eprintln!("Failed to resume: {}", e);
+            return 1;
+        }
+    }
+    if dry_run {
+        runtime.enable_dry_run();
+    } else if resume || MossConfig::load(root).workflow.persistence.enabled() {
+        runtime.enable_checkpoints(checkpoint.clone());
+    }
+
     // Set task variable if provided
     if let Some(t) = task {
         if let Err(e) = runtime.run_string(&format!("task = {:?}", t)) {
@@ -110,8 +161,15 @@ fn cmd_workflow_run(workflow: &str, task: Option<&str>, root: Option<&Path>, jso
         }
     }
 
-    match runtime.run_file(&workflow_path) {
+    let result = runtime.run_file(&workflow_path);
+    if dry_run {
+        return print_dry_run(&runtime.dry_run_actions(), result, json);
+    }
+
+    match result {
         Ok(()) => {
+            // Finished cleanly; nothing left to resume
+            let _ = std::fs::remove_file(&checkpoint);
             if json {
                 println!("{}", serde_json::json!({"success": true}));
             }
@@ -128,10 +186,42 @@ fn cmd_workflow_run(workflow: &str, task: Option<&str>, root: Option<&Path>, jso
     }
 }
 
+/// Report the actions a dry run recorded, and the error that stopped it, if any.
+#[cfg(feature = "lua")]
+fn print_dry_run(actions: &[String], result: mlua::Result<()>, json: bool) -> i32 {
+    if json {
+        let mut out = serde_json::json!({
+            "success": result.is_ok(),
+            "actions": actions,
+        });
+        if let Err(e) = &result {
+            out["error"] = serde_json::Value::String(e.to_string());
+        }
+        println!("{}", out);
+    } else {
+        for (i, action) in actions.iter().enumerate() {
+            println!("{:>3}. {}", i + 1, action);
+        }
+        if actions.is_empty() {
+            println!("(no actions)");
+        }
+        if let Err(e) = &result {
+            eprintln!("Lua error: {}", e);
+        }
+    }
+    if result.is_ok() {
+        0
+    } else {
+        1
+    }
+}
+
 #[cfg(not(feature = "lua"))]
 fn cmd_workflow_run(
     _workflow: &str,
     _task: Option<&str>,
+    _resume: bool,
+    _dry_run: bool,
     _root: Option<&Path>,
     _json: bool,
 ) -> i32 {
@@ -139,3 +229,45 @@ fn cmd_workflow_run(
     eprintln!("Rebuild with: cargo build --features lua");
     1
 }
+
+#[cfg(all(test, feature = "lua"))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_checkpoint_removed_on_completion() {
+        let dir = tempdir().unwrap();
+        let workflows = dir.path().join(".moss/workflows");
+        fs::create_dir_all(&workflows).unwrap();
+        fs::write(
+            dir.path().join(".moss/config.toml"),
+            "[workflow.persistence]\nenabled = true\n",
+        )
+        .unwrap();
+        let checkpoint = dir.path().join(".moss/workflow-state/w.json");
+
+        fs::write(
+            workflows.join("w.lua"),
+            "step('a', function() return 'ok' end)\nerror('stop')\n",
+        )
+        .unwrap();
+        assert_eq!(
+            cmd_workflow_run("w", None, false, false, Some(dir.path()), false),
+            1
+        );
+        assert!(checkpoint.exists());
+
+        fs::write(
+            workflows.join("w.lua"),
+            "step('a', function() error('step a ran twice') end)\n",
+        )
+        .unwrap();
+        assert_eq!(
+            cmd_workflow_run("w", None, true, false, Some(dir.path()), false),
+            0
+        );
+        assert!(!checkpoint.exists());
+    }
+}
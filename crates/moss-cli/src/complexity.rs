@@ -1,20 +1,48 @@
-//! Cyclomatic complexity analysis.
+//! Cyclomatic and cognitive complexity analysis.
 //!
 //! Calculates McCabe cyclomatic complexity for functions.
 //! Complexity = number of decision points + 1
+//!
+//! Cognitive complexity (as defined by SonarSource) weights control flow by
+//! how deeply it is nested: each structure adds 1 plus its nesting depth,
+//! `else`/`elif` add a flat 1, and each run of like boolean operators adds 1.
+//! Which nodes count and which nest comes from each language's
+//! `complexity_nodes` and `nesting_nodes`.
 
 use crate::parsers::Parsers;
 use arborium::tree_sitter;
 use moss_languages::{support_for_path, Language};
 use std::path::Path;
 
+/// Boolean operator tokens; a run of the same one counts once
+const LOGICAL_OPERATORS: &[&str] = &["&&", "||", "and", "or"];
+
+/// Expressions scored only through their boolean operators, if any
+const OPERATOR_KINDS: &[&str] = &["binary_expression", "boolean_operator", "and", "or"];
+
+/// Branches of a switch/match, covered by the switch itself
+const ARM_KINDS: &[&str] = &[
+    "case_clause",
+    "switch_case",
+    "switch_default",
+    "match_arm",
+    "expression_case",
+    "type_case",
+    "default_case",
+    "communication_case",
+];
+
+/// Alternatives that add a flat 1 without nesting
+const ELSE_KINDS: &[&str] = &["else_clause", "elif_clause"];
+
 /// Complexity data for a function
 #[derive(Debug, Clone)]
 pub struct FunctionComplexity {
     pub name: String,
     pub complexity: usize,
+    /// Cognitive complexity score
+    pub cognitive: usize,
     pub start_line: usize,
-    #[allow(dead_code)] // Part of public API, may be used by consumers
     pub end_line: usize,
     pub parent: Option<String>,    // class/struct name for methods
     pub file_path: Option<String>, // file path for codebase-wide reports
@@ -61,8 +89,16 @@ impl FunctionComplexity {
     }
 }
 
+/// Complexity above which a function is flagged, unless `--threshold` says
+/// otherwise. McCabe's suggested upper limit.
+pub const DEFAULT_THRESHOLD: usize = 10;
+
+/// Complexity above which a function's cognitive score is flagged, unless
+/// `--threshold` says otherwise. SonarSource's default.
+pub const DEFAULT_COGNITIVE_THRESHOLD: usize = 15;
+
 /// Complexity report for a file
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ComplexityReport {
     pub functions: Vec<FunctionComplexity>,
     pub file_path: String,
@@ -89,6 +125,50 @@ impl ComplexityReport {
     pub fn high_risk_count(&self) -> usize {
         self.functions.iter().filter(|f| f.complexity > 10).count()
     }
+
+    /// Number of functions with complexity above `threshold`.
+    pub fn above_threshold(&self, threshold: usize) -> usize {
+        self.functions
+            .iter()
+            .filter(|f| f.complexity > threshold)
+            .count()
+    }
+
+    /// Most complex first; ties in file and line order.
+    pub fn sort(&mut self) {
+        self.sort_by_key(|f| f.complexity);
+    }
+
+    /// Highest cognitive score first; ties in file and line order.
+    pub fn sort_by_cognitive(&mut self) {
+        self.sort_by_key(|f| f.cognitive);
+    }
+
+    fn sort_by_key(&mut self, key: impl Fn(&FunctionComplexity) -> usize) {
+        self.functions.sort_by(|a, b| {
+            key(b)
+                .cmp(&key(a))
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.start_line.cmp(&b.start_line))
+        });
+    }
+
+    pub fn avg_cognitive(&self) -> f64 {
+        if self.functions.is_empty() {
+            0.0
+        } else {
+            let total: usize = self.functions.iter().map(|f| f.cognitive).sum();
+            total as f64 / self.functions.len() as f64
+        }
+    }
+
+    pub fn max_cognitive(&self) -> usize {
+        self.functions
+            .iter()
+            .map(|f| f.cognitive)
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 pub struct ComplexityAnalyzer {
@@ -149,10 +229,13 @@ impl ComplexityAnalyzer {
                 if let Some(name) = support.node_name(&node, content) {
                     let mut complexity = 1; // Base complexity
                     self.count_complexity_with_trait(&node, support, &mut complexity);
+                    let mut cognitive = 0;
+                    count_cognitive(&node, support, 0, &mut cognitive);
 
                     functions.push(FunctionComplexity {
                         name: name.to_string(),
                         complexity,
+                        cognitive,
                         start_line: node.start_position().row + 1,
                         end_line: node.end_position().row + 1,
                         parent: parent.map(String::from),
@@ -162,27 +245,30 @@ impl ComplexityAnalyzer {
             }
             // Check if this is a container (class, impl, module)
             else if support.container_kinds().contains(&kind) {
-                if let Some(name) = support.node_name(&node, content) {
-                    // Recurse into container with the container name as parent
-                    if cursor.goto_first_child() {
-                        self.collect_functions_with_trait(
-                            cursor,
-                            content,
-                            support,
-                            functions,
-                            Some(name),
-                        );
-                        cursor.goto_parent();
-                    }
-                    if cursor.goto_next_sibling() {
-                        continue;
-                    }
-                    break;
+                // Same name the symbol outline gives it (impl blocks are named
+                // by their type); unnamed containers keep the outer parent
+                let name = support
+                    .extract_container(&node, content)
+                    .map(|sym| sym.name)
+                    .or_else(|| support.node_name(&node, content).map(String::from));
+                if cursor.goto_first_child() {
+                    self.collect_functions_with_trait(
+                        cursor,
+                        content,
+                        support,
+                        functions,
+                        name.as_deref().or(parent),
+                    );
+                    cursor.goto_parent();
+                }
+                if cursor.goto_next_sibling() {
+                    continue;
                 }
+                break;
             }
 
             // Recurse into other nodes
-            if !support.container_kinds().contains(&kind) && cursor.goto_first_child() {
+            if cursor.goto_first_child() {
                 self.collect_functions_with_trait(cursor, content, support, functions, parent);
                 cursor.goto_parent();
             }
@@ -200,6 +286,7 @@ impl ComplexityAnalyzer {
         complexity: &mut usize,
     ) {
         let complexity_nodes = support.complexity_nodes();
+        let function_kinds = support.function_kinds();
         let mut cursor = node.walk();
 
         if !cursor.goto_first_child() {
@@ -215,8 +302,9 @@ impl ComplexityAnalyzer {
                 *complexity += 1;
             }
 
-            // Depth-first traversal
-            if cursor.goto_first_child() {
+            // Depth-first traversal. Nested functions are reported on their
+            // own, so their decision points aren't counted here too.
+            if !function_kinds.contains(&kind) && cursor.goto_first_child() {
                 continue;
             }
 
@@ -236,6 +324,55 @@ impl ComplexityAnalyzer {
     }
 }
 
+/// Add the cognitive complexity of `node`'s children at nesting `depth`.
+/// Nested functions are skipped; they're scored on their own.
+fn count_cognitive(
+    node: &tree_sitter::Node,
+    support: &dyn Language,
+    depth: usize,
+    score: &mut usize,
+) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        let kind = child.kind();
+        if support.function_kinds().contains(&kind) {
+            continue;
+        }
+        let nests = support.nesting_nodes().contains(&kind);
+        // `else if`: the else already counted, and the if doesn't nest deeper
+        let else_if = ELSE_KINDS.contains(&node.kind()) && nests;
+
+        if LOGICAL_OPERATORS.contains(&kind) {
+            if OPERATOR_KINDS.contains(&node.kind()) && starts_operator_run(node, kind) {
+                *score += 1;
+            }
+        } else if ELSE_KINDS.contains(&kind) {
+            *score += 1;
+        } else if !else_if
+            && (nests || support.complexity_nodes().contains(&kind))
+            && !support.container_kinds().contains(&kind)
+            && !OPERATOR_KINDS.contains(&kind)
+            && !ARM_KINDS.contains(&kind)
+        {
+            *score += 1 + depth;
+        }
+
+        let child_depth = if nests && !else_if { depth + 1 } else { depth };
+        count_cognitive(&child, support, child_depth, score);
+    }
+}
+
+/// Whether the operator `op` of `expr` begins a new run, rather than
+/// continuing the same operator in its left operand (`a && b && c` is one run).
+fn starts_operator_run(expr: &tree_sitter::Node, op: &str) -> bool {
+    match expr.child_by_field_name("left") {
+        Some(left) if left.kind() == expr.kind() => {
+            left.child_by_field_name("operator").map(|o| o.kind()) != Some(op)
+        }
+        _ => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -285,6 +422,160 @@ def with_loop(items):
         assert_eq!(with_loop.complexity, 3); // 1 base + 1 for + 1 if
     }
 
+    #[test]
+    fn test_cpp_complexity() {
+        let analyzer = ComplexityAnalyzer::new();
+        let content = r#"
+class Grid {
+public:
+    int sum(const std::vector<std::vector<int>>& rows) {
+        int total = 0;
+        for (const auto& row : rows) {
+            for (int i = 0; i < row.size(); i++) {
+                if (row[i] > 0 && row[i] < 100) {
+                    total += row[i];
+                }
+            }
+        }
+        try {
+            check(total);
+        } catch (const std::exception& e) {
+            throw;
+        }
+        return total > 0 ? total : 0;
+    }
+};
+
+int main() {
+    return 0;
+}
+"#;
+        let report = analyzer.analyze(&PathBuf::from("test.cpp"), content);
+
+        let sum = report.functions.iter().find(|f| f.name == "sum").unwrap();
+        assert_eq!(sum.parent.as_deref(), Some("Grid"));
+        // 1 base + range for + for + if + && + catch + ?:
+        assert_eq!(sum.complexity, 7);
+
+        let main = report.functions.iter().find(|f| f.name == "main").unwrap();
+        assert_eq!(main.complexity, 1);
+    }
+
+    #[test]
+    fn test_dart_complexity() {
+        let analyzer = ComplexityAnalyzer::new();
+        let content = r#"
+class Counter {
+  int _count = 0;
+
+  void step(List<int> xs) {
+    for (final x in xs) {
+      if (x > 0 && x < 10) {
+        _count += x;
+      }
+    }
+  }
+
+  int get count => _count;
+}
+"#;
+        let report = analyzer.analyze(&PathBuf::from("counter.dart"), content);
+
+        // The statements are in the function_body after the signature
+        let step = report.functions.iter().find(|f| f.name == "step").unwrap();
+        assert_eq!(step.parent.as_deref(), Some("Counter"));
+        // 1 base + for + if + &&
+        assert_eq!(step.complexity, 4);
+        assert_eq!(report.functions.len(), 2);
+    }
+
+    #[test]
+    fn test_nested_function_counted_once() {
+        let analyzer = ComplexityAnalyzer::new();
+        let content = r#"
+def outer(items):
+    def keep(x):
+        if x > 0 and x < 10:
+            return True
+        return False
+    for item in items:
+        if keep(item):
+            yield item
+"#;
+        let report = analyzer.analyze(&PathBuf::from("test.py"), content);
+
+        let outer = report.functions.iter().find(|f| f.name == "outer").unwrap();
+        // 1 base + for + if; keep's branches belong to keep
+        assert_eq!(outer.complexity, 3);
+        assert_eq!((outer.start_line, outer.end_line), (2, 9));
+
+        let keep = report.functions.iter().find(|f| f.name == "keep").unwrap();
+        // 1 base + if + and
+        assert_eq!(keep.complexity, 3);
+    }
+
+    #[test]
+    fn test_python_cognitive() {
+        let analyzer = ComplexityAnalyzer::new();
+        let content = r#"
+def check(items, strict):
+    for item in items:
+        if item and strict:
+            return True
+        elif item or not strict:
+            continue
+        else:
+            pass
+    return False
+"#;
+        let report = analyzer.analyze(&PathBuf::from("test.py"), content);
+        // for +1, if +2 (nested), and +1, elif +1, or +1, else +1
+        assert_eq!(report.functions[0].cognitive, 7);
+    }
+
+    #[test]
+    fn test_rust_cognitive() {
+        let analyzer = ComplexityAnalyzer::new();
+        let content = r#"
+fn pick(xs: &[i32], limit: i32) -> i32 {
+    for x in xs {
+        if *x > limit && *x < 100 || *x == 0 {
+            return *x;
+        } else if *x < 0 {
+            match x {
+                0 => {}
+                _ => return 0,
+            }
+        }
+    }
+    -1
+}
+"#;
+        let report = analyzer.analyze(&PathBuf::from("test.rs"), content);
+        // for +1, if +2, && +1, || +1, else if +1, match +3 (two levels deep)
+        assert_eq!(report.functions[0].cognitive, 9);
+    }
+
+    #[test]
+    fn test_typescript_cognitive() {
+        let analyzer = ComplexityAnalyzer::new();
+        let content = r#"
+function load(path: string, retries: number): string {
+    try {
+        return read(path);
+    } catch (e) {
+        while (retries > 0) {
+            retries--;
+        }
+        return retries > 0 ? "retry" : "fail";
+    }
+}
+"#;
+        let report = analyzer.analyze(&PathBuf::from("test.ts"), content);
+        // catch +1, while +2, ternary +2; try itself adds nothing
+        assert_eq!(report.functions[0].cognitive, 5);
+    }
+
     #[test]
     fn test_rust_complexity() {
         let analyzer = ComplexityAnalyzer::new();
@@ -307,6 +598,12 @@ fn with_match(x: Option<i32>) -> i32 {
         None => 0,
     }
 }
+
+impl Point {
+    fn norm(&self) -> i32 {
+        if self.x > 0 { self.x } else { -self.x }
+    }
+}
 "#;
         let report = analyzer.analyze(&PathBuf::from("test.rs"), content);
 
@@ -338,5 +635,8 @@ fn with_match(x: Option<i32>) -> i32 {
             "with_match should have complexity >= 1, got {}",
             with_match.complexity
         );
+
+        let norm = report.functions.iter().find(|f| f.name == "norm").unwrap();
+        assert_eq!(norm.parent.as_deref(), Some("Point"));
     }
 }
@@ -12,6 +12,51 @@
 //!
 //! [index]
 //! enabled = true
+//! go_target = "linux/amd64" # GOOS/GOARCH for Go build constraints (default: host)
+//! call_graph_exclude = ["@tests", "**/generated/**"]  # hide callers in these files
+//!
+//! [walk]
+//! max_file_size_kb = 1024   # skip larger files in the index, tree and path lookup
+//! follow_symlinks = true    # descend into symlinked directories (may reach outside the root)
+//!
+//! [symbols]
+//! include_nested = false  # index closures, lambdas, nested functions
+//! max_per_file = 10000      # stop indexing a file after this many symbols (0 = no cap)
+//! rust_cfg = ["test", 'feature = "llm"']  # cfgs analysis treats as set (default: host, all features)
+//!
+//! [package]
+//! cache_ttl_hours = 24      # how long registry query results stay fresh
+//!
+//! [cache]
+//! max_size_mb = 500         # evict least recently written files in ~/.cache/moss past this
+//! max_age_days = 30         # `moss cache --clean` removes files older than this
+//!
+//! [lint]
+//! jobs = 4                  # tools run at once by lint/analyze (0 = one per CPU)
+//!
+//! [fuzzy]
+//! match_paths = true        # rank matches at path separators higher
+//! case = "ignore"           # ignore | smart (uppercase in query is exact) | respect
+//! normalize = true          # let ASCII letters match accented ones
+//! limit = 10                # fuzzy matches kept, best first
+//! min_score = 0             # drop fuzzy matches scoring below this
+//! basename = false          # match file names only, not the whole path
+//!
+//! [graph.allow]             # layering rules for `moss graph modules`
+//! cli = ["core", "util"]    # cli may only import from core and util
+//! core = ["util"]
+//!
+//! [workflow.persistence]
+//! enabled = true            # checkpoint workflows for `moss workflow run --resume`
+//!
+//! [workflow.context]
+//! strategy = "json"         # flat | json: how context() and auto{} build prompts
+//!
+//! [cli]
+//! default_command = "view"  # run when the first argument isn't a command
+//!
+//! [aliases]
+//! al = "analyze --health"   # `moss al` expands to `moss analyze --health`
 //!
 //! [filter.aliases]
 //! tests = ["*_test.*", "my_custom_tests/**"]  # override built-in
@@ -19,9 +64,12 @@
 //! config = []                                   # disable built-in
 //! ```
 
+use moss_languages::go_build::GoTarget;
+use moss_languages::rust_cfg::CfgSet;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
+use std::str::FromStr;
 
 /// Daemon configuration.
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -39,6 +87,277 @@ pub struct DaemonConfig {
 pub struct IndexConfig {
     /// Whether to create and use the file index.
     pub enabled: bool,
+    /// Platform Go files are selected for, as "GOOS/GOARCH" or "GOOS".
+    /// Defaults to the GOOS/GOARCH env vars, then the host.
+    pub go_target: Option<String>,
+    /// Files whose calls are left out of caller results, as globs or filter
+    /// aliases like "@tests". They are still indexed and still count as uses
+    /// for dead-code analysis; `moss analyze --callers --include-tests` shows them.
+    pub call_graph_exclude: Option<Vec<String>>,
+}
+
+impl IndexConfig {
+    /// Patterns for files whose callers are hidden.
+    pub fn call_graph_exclude(&self) -> &[String] {
+        self.call_graph_exclude.as_deref().unwrap_or_default()
+    }
+
+    /// Target for Go build constraints. Invalid values fall back to the host.
+    pub fn go_target(&self) -> GoTarget {
+        self.go_target
+            .as_deref()
+            .and_then(GoTarget::parse)
+            .unwrap_or_else(GoTarget::host)
+    }
+}
+
+/// Directory walk configuration, shared by every walk over project files.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct WalkConfig {
+    /// Files larger than this many kilobytes are skipped. Unlimited if unset.
+    pub max_file_size_kb: Option<u64>,
+    /// Descend into symlinked directories and index linked files. Links can
+    /// point outside the project, so those files get indexed too. Default false.
+    pub follow_symlinks: Option<bool>,
+}
+
+impl WalkConfig {
+    pub fn max_file_size(&self) -> Option<u64> {
+        self.max_file_size_kb.map(|kb| kb * 1024)
+    }
+
+    pub fn follow_symlinks(&self) -> bool {
+        self.follow_symlinks.unwrap_or(false)
+    }
+}
+
+/// Symbol extraction configuration.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct SymbolsConfig {
+    /// Index nested functions and anonymous functions (closures, lambdas).
+    /// Defaults to false: only top-level and named symbols are indexed.
+    pub include_nested: bool,
+    /// Maximum symbols indexed per file; files beyond it are partially indexed.
+    /// Defaults to 10000. 0 disables the cap.
+    pub max_per_file: Option<usize>,
+    /// Active Rust cfg options for analysis, such as `test` or `feature = "llm"`.
+    /// Items whose `#[cfg]` doesn't hold are skipped. Defaults to the host
+    /// target with every feature on; listing a feature enables only those listed.
+    pub rust_cfg: Option<Vec<String>>,
+}
+
+impl SymbolsConfig {
+    pub fn rust_cfg(&self) -> CfgSet {
+        CfgSet::with_options(self.rust_cfg.as_deref().unwrap_or_default())
+    }
+
+    pub fn max_per_file(&self) -> Option<usize> {
+        match self.max_per_file {
+            Some(0) => None,
+            Some(n) => Some(n),
+            None => Some(10_000),
+        }
+    }
+}
+
+/// Package registry configuration.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct PackageConfig {
+    /// Hours before a cached registry query is refetched. Defaults to 24.
+    pub cache_ttl_hours: Option<u64>,
+}
+
+impl PackageConfig {
+    pub fn cache_ttl(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.cache_ttl_hours.unwrap_or(24) * 60 * 60)
+    }
+}
+
+/// Global cache (~/.cache/moss) limits.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Size budget in megabytes, enforced after cache writes. Unlimited if unset.
+    pub max_size_mb: Option<u64>,
+    /// Age in days past which `moss cache --clean` removes files. Unlimited if unset.
+    pub max_age_days: Option<u64>,
+}
+
+impl CacheConfig {
+    /// Values too large to represent saturate, which is effectively unlimited.
+    pub fn max_bytes(&self) -> Option<u64> {
+        self.max_size_mb
+            .map(|mb| CacheConfig::mb_to_bytes(mb).unwrap_or(u64::MAX))
+    }
+
+    pub fn max_age(&self) -> Option<std::time::Duration> {
+        self.max_age_days
+            .map(|days| CacheConfig::days_to_duration(days).unwrap_or(std::time::Duration::MAX))
+    }
+
+    /// Megabytes to bytes, or None on overflow.
+    pub fn mb_to_bytes(mb: u64) -> Option<u64> {
+        mb.checked_mul(1024 * 1024)
+    }
+
+    /// Days to a duration, or None on overflow.
+    pub fn days_to_duration(days: u64) -> Option<std::time::Duration> {
+        days.checked_mul(24 * 60 * 60)
+            .map(std::time::Duration::from_secs)
+    }
+}
+
+/// Lint tool execution configuration.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct LintConfig {
+    /// Maximum tools run concurrently. Defaults to 0 (one per CPU).
+    pub jobs: Option<usize>,
+}
+
+impl LintConfig {
+    pub fn jobs(&self) -> usize {
+        self.jobs.unwrap_or(0)
+    }
+}
+
+/// Case sensitivity of fuzzy path matching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FuzzyCase {
+    #[default]
+    Ignore,
+    /// Case-sensitive only when the query contains uppercase
+    Smart,
+    Respect,
+}
+
+impl FromStr for FuzzyCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ignore" => Ok(FuzzyCase::Ignore),
+            "smart" => Ok(FuzzyCase::Smart),
+            "respect" => Ok(FuzzyCase::Respect),
+            _ => Err(format!(
+                "unknown case mode '{}' (expected ignore, smart or respect)",
+                s
+            )),
+        }
+    }
+}
+
+/// Fuzzy path matching configuration.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct FuzzyConfig {
+    /// Score matches starting at path separators higher. Defaults to true.
+    pub match_paths: Option<bool>,
+    /// Case sensitivity. Defaults to ignore.
+    pub case: Option<FuzzyCase>,
+    /// Let ASCII letters in the query match accented letters. Defaults to true.
+    pub normalize: Option<bool>,
+    /// Number of fuzzy matches kept. Defaults to 10.
+    pub limit: Option<usize>,
+    /// Fuzzy matches scoring below this are dropped. Defaults to 0.
+    pub min_score: Option<u32>,
+    /// Match against file names instead of whole relative paths. Defaults to false.
+    pub basename: Option<bool>,
+}
+
+impl FuzzyConfig {
+    pub fn match_paths(&self) -> bool {
+        self.match_paths.unwrap_or(true)
+    }
+
+    pub fn case(&self) -> FuzzyCase {
+        self.case.unwrap_or_default()
+    }
+
+    pub fn normalize(&self) -> bool {
+        self.normalize.unwrap_or(true)
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit.unwrap_or(10)
+    }
+
+    pub fn min_score(&self) -> u32 {
+        self.min_score.unwrap_or(0)
+    }
+
+    pub fn basename(&self) -> bool {
+        self.basename.unwrap_or(false)
+    }
+
+    /// These options, falling back to `base` for the unset ones.
+    pub fn or(self, base: FuzzyConfig) -> FuzzyConfig {
+        FuzzyConfig {
+            match_paths: self.match_paths.or(base.match_paths),
+            case: self.case.or(base.case),
+            normalize: self.normalize.or(base.normalize),
+            limit: self.limit.or(base.limit),
+            min_score: self.min_score.or(base.min_score),
+            basename: self.basename.or(base.basename),
+        }
+    }
+}
+
+/// Command-line behavior configuration.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct CliConfig {
+    /// Subcommand to run when the first argument isn't a known command or alias.
+    pub default_command: Option<String>,
+}
+
+/// Module graph configuration.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct GraphConfig {
+    /// Modules each module may depend on. Modules without an entry are unconstrained.
+    pub allow: HashMap<String, Vec<String>>,
+}
+
+/// Workflow configuration.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct WorkflowConfig {
+    pub persistence: WorkflowPersistenceConfig,
+    pub context: WorkflowContextConfig,
+}
+
+/// Workflow checkpointing, for resuming interrupted runs.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct WorkflowPersistenceConfig {
+    /// Save progress to .moss/workflow-state/<name>.json after each step.
+    /// Defaults to false.
+    pub enabled: Option<bool>,
+}
+
+impl WorkflowPersistenceConfig {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+}
+
+/// How workflow context is assembled for LLM prompts.
+#[derive(Debug, Clone, Deserialize, Default)]
+#[serde(default)]
+pub struct WorkflowContextConfig {
+    /// "flat" (`key: value` lines) or "json" (one JSON object). Defaults to "flat".
+    pub strategy: Option<String>,
+}
+
+impl WorkflowContextConfig {
+    pub fn strategy(&self) -> &str {
+        self.strategy.as_deref().unwrap_or("flat")
+    }
 }
 
 /// Filter configuration for --exclude and --only flags.
@@ -56,7 +375,18 @@ pub struct FilterConfig {
 pub struct MossConfig {
     pub daemon: DaemonConfig,
     pub index: IndexConfig,
+    pub walk: WalkConfig,
+    pub symbols: SymbolsConfig,
     pub filter: FilterConfig,
+    pub package: PackageConfig,
+    pub cache: CacheConfig,
+    pub lint: LintConfig,
+    pub fuzzy: FuzzyConfig,
+    pub graph: GraphConfig,
+    pub workflow: WorkflowConfig,
+    pub cli: CliConfig,
+    /// Command aliases. Keys are alias names, values are the expanded arguments.
+    pub aliases: HashMap<String, String>,
 }
 
 impl MossConfig {
@@ -90,8 +420,22 @@ impl MossConfig {
                 enabled: true,
                 auto_start: true,
             },
-            index: IndexConfig { enabled: true },
+            index: IndexConfig {
+                enabled: true,
+                go_target: None,
+                call_graph_exclude: None,
+            },
+            walk: WalkConfig::default(),
+            symbols: SymbolsConfig::default(),
             filter: FilterConfig::default(),
+            package: PackageConfig::default(),
+            cache: CacheConfig::default(),
+            lint: LintConfig::default(),
+            fuzzy: FuzzyConfig::default(),
+            graph: GraphConfig::default(),
+            workflow: WorkflowConfig::default(),
+            cli: CliConfig::default(),
+            aliases: HashMap::new(),
         }
     }
 
@@ -119,6 +463,10 @@ impl MossConfig {
         for (k, v) in other.filter.aliases {
             merged_aliases.insert(k, v);
         }
+        let mut merged_layers = self.graph.allow;
+        merged_layers.extend(other.graph.allow);
+        let mut merged_command_aliases = self.aliases;
+        merged_command_aliases.extend(other.aliases);
 
         Self {
             daemon: DaemonConfig {
@@ -127,10 +475,61 @@ impl MossConfig {
             },
             index: IndexConfig {
                 enabled: other.index.enabled,
+                go_target: other.index.go_target.or(self.index.go_target),
+                call_graph_exclude: other
+                    .index
+                    .call_graph_exclude
+                    .or(self.index.call_graph_exclude),
+            },
+            walk: WalkConfig {
+                max_file_size_kb: other.walk.max_file_size_kb.or(self.walk.max_file_size_kb),
+                follow_symlinks: other.walk.follow_symlinks.or(self.walk.follow_symlinks),
+            },
+            symbols: SymbolsConfig {
+                include_nested: other.symbols.include_nested,
+                max_per_file: other.symbols.max_per_file.or(self.symbols.max_per_file),
+                rust_cfg: other.symbols.rust_cfg.or(self.symbols.rust_cfg),
             },
             filter: FilterConfig {
                 aliases: merged_aliases,
             },
+            package: PackageConfig {
+                cache_ttl_hours: other
+                    .package
+                    .cache_ttl_hours
+                    .or(self.package.cache_ttl_hours),
+            },
+            cache: CacheConfig {
+                max_size_mb: other.cache.max_size_mb.or(self.cache.max_size_mb),
+                max_age_days: other.cache.max_age_days.or(self.cache.max_age_days),
+            },
+            lint: LintConfig {
+                jobs: other.lint.jobs.or(self.lint.jobs),
+            },
+            fuzzy: other.fuzzy.or(self.fuzzy),
+            graph: GraphConfig {
+                allow: merged_layers,
+            },
+            workflow: WorkflowConfig {
+                persistence: WorkflowPersistenceConfig {
+                    enabled: other
+                        .workflow
+                        .persistence
+                        .enabled
+                        .or(self.workflow.persistence.enabled),
+                },
+                context: WorkflowContextConfig {
+                    strategy: other
+                        .workflow
+                        .context
+                        .strategy
+                        .or(self.workflow.context.strategy),
+                },
+            },
+            cli: CliConfig {
+                default_command: other.cli.default_command.or(self.cli.default_command),
+            },
+            aliases: merged_command_aliases,
         }
     }
 }
@@ -147,6 +546,7 @@ mod tests {
         assert!(config.daemon.enabled);
         assert!(config.daemon.auto_start);
         assert!(config.index.enabled);
+        assert!(!config.symbols.include_nested);
     }
 
     #[test]
@@ -232,4 +632,71 @@ config = []
         // Empty array disables alias
         assert_eq!(config.filter.aliases.get("config"), Some(&vec![]));
     }
+
+    #[test]
+    fn test_symbols_config() {
+        let dir = TempDir::new().unwrap();
+        let moss_dir = dir.path().join(".moss");
+        std::fs::create_dir_all(&moss_dir).unwrap();
+
+        let config_path = moss_dir.join("config.toml");
+        let mut file = std::fs::File::create(&config_path).unwrap();
+        writeln!(
+            file,
+            r#"
+[symbols]
+include_nested = true
+"#
+        )
+        .unwrap();
+
+        let config = MossConfig::load(dir.path());
+        assert!(config.symbols.include_nested);
+    }
+
+    #[test]
+    fn test_package_cache_ttl_config() {
+        let dir = TempDir::new().unwrap();
+        let moss_dir = dir.path().join(".moss");
+        std::fs::create_dir_all(&moss_dir).unwrap();
+
+        let config = MossConfig::load(dir.path());
+        assert_eq!(config.package.cache_ttl().as_secs(), 24 * 60 * 60);
+
+        std::fs::write(
+            moss_dir.join("config.toml"),
+            "[package]\ncache_ttl_hours = 2\n",
+        )
+        .unwrap();
+        let config = MossConfig::load(dir.path());
+        assert_eq!(config.package.cache_ttl().as_secs(), 2 * 60 * 60);
+    }
+
+    #[test]
+    fn test_command_aliases_config() {
+        let dir = TempDir::new().unwrap();
+        let moss_dir = dir.path().join(".moss");
+        std::fs::create_dir_all(&moss_dir).unwrap();
+
+        let config_path = moss_dir.join("config.toml");
+        let mut file = std::fs::File::create(&config_path).unwrap();
+        writeln!(
+            file,
+            r#"
+[cli]
+default_command = "view"
+
+[aliases]
+al = "analyze --health"
+"#
+        )
+        .unwrap();
+
+        let config = MossConfig::load(dir.path());
+        assert_eq!(config.cli.default_command.as_deref(), Some("view"));
+        assert_eq!(
+            config.aliases.get("al").map(String::as_str),
+            Some("analyze --health")
+        );
+    }
 }
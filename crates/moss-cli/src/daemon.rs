@@ -11,7 +11,9 @@ use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
 use tokio::net::UnixListener;
 
-use crate::index::FileIndex;
+use crate::index::{CallerScope, FileIndex};
+use crate::path_resolve;
+use crate::skeleton::SkeletonExtractor;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "cmd")]
@@ -295,9 +297,106 @@ impl ServerResponse {
     }
 }
 
+// ============================================================================
+// JSON-RPC 2.0 interface
+// ============================================================================
+
+/// A JSON-RPC 2.0 request, accepted on the daemon socket alongside `Request`.
+///
+/// Methods: resolvePath, view, searchTree, findCallers, symbols, shutdown.
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    #[serde(default)]
+    id: serde_json::Value,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    const PARSE_ERROR: i64 = -32700;
+    const INVALID_REQUEST: i64 = -32600;
+    const METHOD_NOT_FOUND: i64 = -32601;
+    const INVALID_PARAMS: i64 = -32602;
+    /// Query failed (index error, file not found, ...)
+    const SERVER_ERROR: i64 = -32000;
+
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn server(e: impl std::fmt::Display) -> Self {
+        Self::new(Self::SERVER_ERROR, e.to_string())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    fn new(id: serde_json::Value, outcome: Result<serde_json::Value, RpcError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(value) => (Some(value), None),
+            Err(e) => (None, Some(e)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct QueryParams {
+    query: String,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct TargetParams {
+    target: String,
+}
+
+#[derive(Deserialize)]
+struct SymbolParams {
+    symbol: String,
+}
+
+#[derive(Deserialize)]
+struct FileParams {
+    file: String,
+}
+
+fn rpc_params<T: serde::de::DeserializeOwned>(params: serde_json::Value) -> Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|e| RpcError::new(RpcError::INVALID_PARAMS, e.to_string()))
+}
+
 struct DaemonServer {
     root: PathBuf,
+    socket_path: PathBuf,
     index: Mutex<FileIndex>,
+    /// Kept warm across `view` calls so grammars load once
+    skeleton: SkeletonExtractor,
     start_time: std::time::Instant,
     query_count: std::sync::atomic::AtomicUsize,
 }
@@ -306,8 +405,10 @@ impl DaemonServer {
     fn new(root: PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
         let index = FileIndex::open(&root)?;
         Ok(Self {
+            socket_path: get_moss_dir(&root).join("daemon.sock"),
             root,
             index: Mutex::new(index),
+            skeleton: SkeletonExtractor::new(),
             start_time: std::time::Instant::now(),
             query_count: std::sync::atomic::AtomicUsize::new(0),
         })
@@ -364,14 +465,14 @@ impl DaemonServer {
             }
             Request::Callers { symbol } => {
                 let idx = self.index.lock().unwrap();
-                match idx.find_callers(&symbol) {
+                match idx.find_callers(&symbol, CallerScope::SkipExcluded) {
                     Ok(callers) => ServerResponse::ok(serde_json::json!(callers)),
                     Err(e) => ServerResponse::err(&e.to_string()),
                 }
             }
             Request::Callees { symbol, file } => {
                 let idx = self.index.lock().unwrap();
-                match idx.find_callees(&symbol, &file) {
+                match idx.find_callees(&file, &symbol) {
                     Ok(callees) => ServerResponse::ok(serde_json::json!(callees)),
                     Err(e) => ServerResponse::err(&e.to_string()),
                 }
@@ -472,12 +573,123 @@ impl DaemonServer {
         }
     }
 
+    fn handle_rpc(&self, req: RpcRequest) -> RpcResponse {
+        use std::sync::atomic::Ordering;
+        self.query_count.fetch_add(1, Ordering::Relaxed);
+
+        let outcome = match req.method.as_str() {
+            "resolvePath" => rpc_params(req.params).and_then(|p| self.rpc_resolve_path(p)),
+            "view" => rpc_params(req.params).and_then(|p| self.rpc_view(p)),
+            "searchTree" => rpc_params(req.params).and_then(|p| self.rpc_search_tree(p)),
+            "findCallers" => rpc_params(req.params).and_then(|p| self.rpc_find_callers(p)),
+            "symbols" => rpc_params(req.params).and_then(|p| self.rpc_symbols(p)),
+            "shutdown" => Ok(serde_json::json!({"message": "shutting down"})),
+            other => Err(RpcError::new(
+                RpcError::METHOD_NOT_FOUND,
+                format!("Unknown method: {}", other),
+            )),
+        };
+        RpcResponse::new(req.id, outcome)
+    }
+
+    /// Resolve a unified path (`src/main.py/Foo/bar`) to file and symbol parts.
+    fn rpc_resolve_path(&self, params: QueryParams) -> Result<serde_json::Value, RpcError> {
+        Ok(
+            match path_resolve::resolve_unified(&params.query, &self.root) {
+                Some(unified) => serde_json::json!({
+                    "file": unified.file_path,
+                    "symbol": unified.symbol_path,
+                    "is_directory": unified.is_directory,
+                }),
+                None => serde_json::Value::Null,
+            },
+        )
+    }
+
+    /// Skeleton of a file, or of one symbol when the target names one.
+    fn rpc_view(&self, params: TargetParams) -> Result<serde_json::Value, RpcError> {
+        let unified = path_resolve::resolve_unified(&params.target, &self.root)
+            .ok_or_else(|| RpcError::server(format!("No match for: {}", params.target)))?;
+        if unified.is_directory {
+            return Err(RpcError::server(format!(
+                "{} is a directory",
+                unified.file_path
+            )));
+        }
+        let path = self.root.join(&unified.file_path);
+        let content = std::fs::read_to_string(&path).map_err(RpcError::server)?;
+        let skeleton = self
+            .skeleton
+            .extract(Path::new(&unified.file_path), &content);
+
+        let mut parent = unified.file_path.clone();
+        let mut symbols = &skeleton.symbols;
+        let mut found = None;
+        for name in &unified.symbol_path {
+            let symbol = symbols
+                .iter()
+                .find(|s| &s.name == name)
+                .ok_or_else(|| RpcError::server(format!("Symbol not found: {}", name)))?;
+            if let Some(prev) = found.replace(symbol) {
+                parent = format!("{}/{}", parent, prev.name);
+            }
+            symbols = &symbol.children;
+        }
+        let node = match found {
+            Some(symbol) => symbol.to_view_node(&parent),
+            None => skeleton.to_view_node(),
+        };
+        serde_json::to_value(node).map_err(RpcError::server)
+    }
+
+    /// Fuzzy search over indexed file paths and symbol names.
+    fn rpc_search_tree(&self, params: QueryParams) -> Result<serde_json::Value, RpcError> {
+        let limit = params.limit.unwrap_or(50);
+        let idx = self.index.lock().unwrap();
+        let mut files = idx.find_like(&params.query).map_err(RpcError::server)?;
+        files.truncate(limit);
+        let symbols = idx
+            .find_symbols(&params.query, None, true, limit)
+            .map_err(RpcError::server)?;
+        Ok(serde_json::json!({"files": files, "symbols": symbols}))
+    }
+
+    fn rpc_find_callers(&self, params: SymbolParams) -> Result<serde_json::Value, RpcError> {
+        let idx = self.index.lock().unwrap();
+        let callers = idx
+            .find_callers(&params.symbol, CallerScope::SkipExcluded)
+            .map_err(RpcError::server)?;
+        Ok(callers
+            .into_iter()
+            .map(|(file, caller, line)| {
+                serde_json::json!({"file": file, "caller": caller, "line": line})
+            })
+            .collect())
+    }
+
+    /// Symbols defined in one file.
+    fn rpc_symbols(&self, params: FileParams) -> Result<serde_json::Value, RpcError> {
+        let idx = self.index.lock().unwrap();
+        let symbols = idx.file_symbols(&params.file).map_err(RpcError::server)?;
+        Ok(serde_json::json!(symbols))
+    }
+
+    /// Remove the socket and exit.
+    fn shutdown(&self) -> ! {
+        let _ = std::fs::remove_file(&self.socket_path);
+        std::process::exit(0);
+    }
+
     fn trigger_incremental_refresh(&self) {
         if let Ok(mut idx) = self.index.lock() {
             // Incremental file refresh
             if let Err(e) = idx.incremental_refresh() {
                 eprintln!("Error during incremental refresh: {}", e);
             }
+            // Re-parse changed files so the call graph doesn't go stale
+            if let Err(e) = idx.incremental_call_graph_refresh() {
+                eprintln!("Error during call graph refresh: {}", e);
+            }
         }
     }
 }
@@ -554,8 +766,13 @@ pub async fn run_daemon(root: &Path) -> Result<i32, Box<dyn std::error::Error>>
     let listener = UnixListener::bind(&socket_path)?;
     eprintln!("Daemon listening on {}", socket_path.display());
 
+    let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
     loop {
-        let (stream, _) = listener.accept().await?;
+        let stream = tokio::select! {
+            accepted = listener.accept() => accepted?.0,
+            _ = tokio::signal::ctrl_c() => break,
+            _ = terminate.recv() => break,
+        };
         let server = server.clone();
 
         tokio::spawn(async move {
@@ -564,25 +781,101 @@ pub async fn run_daemon(root: &Path) -> Result<i32, Box<dyn std::error::Error>>
             let mut line = String::new();
 
             while reader.read_line(&mut line).await.unwrap_or(0) > 0 {
-                let response = match serde_json::from_str::<Request>(&line) {
-                    Ok(Request::Shutdown) => {
-                        let resp = server.handle_request(Request::Shutdown);
-                        let resp_str = serde_json::to_string(&resp).unwrap();
-                        let _ = writer.write_all(resp_str.as_bytes()).await;
-                        let _ = writer.write_all(b"\n").await;
-                        std::process::exit(0);
-                    }
-                    Ok(req) => server.handle_request(req),
-                    Err(e) => ServerResponse::err(&format!("Invalid request: {}", e)),
-                };
-
-                let resp_str = serde_json::to_string(&response).unwrap();
-                let _ = writer.write_all(resp_str.as_bytes()).await;
-                let _ = writer.write_all(b"\n").await;
+                let reply = handle_line(&server, &line);
+                if let Some(text) = reply.text {
+                    let _ = writer.write_all(text.as_bytes()).await;
+                    let _ = writer.write_all(b"\n").await;
+                }
+                if reply.shutdown {
+                    server.shutdown();
+                }
                 line.clear();
             }
         });
     }
+
+    let _ = std::fs::remove_file(&socket_path);
+    Ok(0)
+}
+
+/// Response line for one request (None for notifications), and whether the
+/// daemon should then exit.
+struct Reply {
+    text: Option<String>,
+    shutdown: bool,
+}
+
+/// Answer one request line. Lines with a `jsonrpc` member are JSON-RPC 2.0;
+/// anything else is the `{"cmd": ...}` protocol used by `DaemonClient`.
+fn handle_line(server: &DaemonServer, line: &str) -> Reply {
+    let value: Option<serde_json::Value> = serde_json::from_str(line).ok();
+    let is_rpc = match &value {
+        Some(v) => v.get("jsonrpc").is_some(),
+        None => line.contains("\"jsonrpc\""),
+    };
+
+    if is_rpc {
+        let Some(value) = value else {
+            let response = RpcResponse::new(
+                serde_json::Value::Null,
+                Err(RpcError::new(RpcError::PARSE_ERROR, "Parse error")),
+            );
+            return Reply {
+                text: Some(serde_json::to_string(&response).unwrap()),
+                shutdown: false,
+            };
+        };
+        // A request without an id is a notification: run it, but send nothing back.
+        let is_notification = value.get("id").is_none();
+        let id = value.get("id").cloned().unwrap_or_default();
+        let (response, shutdown) = match serde_json::from_value::<RpcRequest>(value) {
+            Ok(req) if req.jsonrpc != "2.0" => (
+                RpcResponse::new(
+                    id,
+                    Err(RpcError::new(
+                        RpcError::INVALID_REQUEST,
+                        format!("Unsupported jsonrpc version: {}", req.jsonrpc),
+                    )),
+                ),
+                false,
+            ),
+            Ok(req) => {
+                let shutdown = req.method == "shutdown";
+                let response = server.handle_rpc(req);
+                if is_notification {
+                    return Reply {
+                        text: None,
+                        shutdown,
+                    };
+                }
+                (response, shutdown)
+            }
+            Err(e) => (
+                RpcResponse::new(
+                    id,
+                    Err(RpcError::new(RpcError::INVALID_REQUEST, e.to_string())),
+                ),
+                false,
+            ),
+        };
+        return Reply {
+            text: Some(serde_json::to_string(&response).unwrap()),
+            shutdown,
+        };
+    }
+
+    let (response, shutdown) = match serde_json::from_str::<Request>(line) {
+        Ok(Request::Shutdown) => (server.handle_request(Request::Shutdown), true),
+        Ok(req) => (server.handle_request(req), false),
+        Err(e) => (
+            ServerResponse::err(&format!("Invalid request: {}", e)),
+            false,
+        ),
+    };
+    Reply {
+        text: Some(serde_json::to_string(&response).unwrap()),
+        shutdown,
+    }
 }
 
 // ============================================================================
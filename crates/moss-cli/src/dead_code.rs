@@ -0,0 +1,241 @@
+//! Dead code detection.
+//!
+//! Lists functions and methods with no recorded callers that don't look like
+//! entry points or public API. Calls are matched by name, and dynamic dispatch,
+//! reflection and framework callbacks can't be seen, so results are only
+//! "possibly unused". Rust items compiled out under the active cfg set
+//! (by default, anything `#[cfg(test)]`) are skipped.
+
+use crate::index::{FileIndex, SymbolMatch};
+use moss_languages::rust_cfg::CfgSet;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A function or method nothing appears to call.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct UnusedSymbol {
+    pub file: String,
+    pub line: usize,
+    pub kind: String,
+    pub name: String,
+    pub parent: Option<String>,
+}
+
+/// Find functions and methods with no callers in the index, skipping entry
+/// points, tests, anything exported and items `cfg` compiles out.
+pub fn find_possibly_unused(
+    index: &FileIndex,
+    root: &Path,
+    cfg: &CfgSet,
+) -> rusqlite::Result<Vec<UnusedSymbol>> {
+    let referenced = index.referenced_names()?;
+    let symbols = index.all_symbols()?;
+    let candidates: Vec<&SymbolMatch> = symbols
+        .iter()
+        .filter(|s| (s.kind == "function" || s.kind == "method") && !referenced.contains(&s.name))
+        .filter(|s| s.cfg.as_deref().is_none_or(|c| cfg.eval(c)))
+        .collect();
+
+    let mut sources: HashMap<&str, Vec<String>> = HashMap::new();
+    for sym in &candidates {
+        if !sources.contains_key(sym.file.as_str()) {
+            let content = std::fs::read_to_string(root.join(&sym.file)).unwrap_or_default();
+            sources.insert(&sym.file, content.lines().map(str::to_string).collect());
+        }
+    }
+
+    Ok(candidates
+        .into_iter()
+        .filter(|sym| !is_entry_point(sym, &sources[sym.file.as_str()]))
+        .map(|sym| UnusedSymbol {
+            file: sym.file.clone(),
+            line: sym.start_line,
+            kind: sym.kind.clone(),
+            name: sym.name.clone(),
+            parent: sym.parent.clone(),
+        })
+        .collect())
+}
+
+/// Whether a symbol is reachable from outside the call graph: exported,
+/// an entry point, a test, or invoked by a language or framework.
+fn is_entry_point(sym: &SymbolMatch, lines: &[String]) -> bool {
+    if sym.name == "main" {
+        return true;
+    }
+    let decl = Declaration::find(lines, sym.start_line);
+    let ext = Path::new(&sym.file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+
+    match ext {
+        "rs" => {
+            decl.line.starts_with("pub")
+                || decl
+                    .attributes
+                    .iter()
+                    .any(|a| a.contains("test") || a.contains("no_mangle"))
+                || in_trait_or_trait_impl(lines, decl.index)
+        }
+        "py" | "pyi" => {
+            // Dunders are called by the runtime, decorated functions by frameworks
+            (sym.name.starts_with("__") && sym.name.ends_with("__"))
+                || sym.name.starts_with("test")
+                || sym.file.ends_with("__main__.py")
+                || !decl.attributes.is_empty()
+        }
+        "go" => sym.name == "init" || sym.name.chars().next().is_some_and(char::is_uppercase),
+        _ => {
+            let line = decl.line;
+            line.starts_with("export") || line.starts_with("public") || line.starts_with("pub ")
+        }
+    }
+}
+
+/// A definition's first line and the attributes or decorators above it.
+struct Declaration<'a> {
+    line: &'a str,
+    /// 0-based index of `line`
+    index: usize,
+    attributes: Vec<&'a str>,
+}
+
+impl<'a> Declaration<'a> {
+    /// Symbol ranges may or may not include attributes, so look both above
+    /// and at the start line.
+    fn find(lines: &'a [String], start_line: usize) -> Self {
+        let is_attribute = |l: &str| l.starts_with("#[") || l.starts_with('@');
+        let mut index = start_line.saturating_sub(1);
+        let mut attributes = Vec::new();
+
+        let mut above = index;
+        while above > 0 {
+            let line = lines[above - 1].trim();
+            if is_attribute(line) {
+                attributes.push(line);
+            } else if !line.starts_with("///") && !line.starts_with("//") {
+                break;
+            }
+            above -= 1;
+        }
+        while let Some(line) = lines.get(index).map(|l| l.trim()) {
+            if !is_attribute(line) {
+                break;
+            }
+            attributes.push(line);
+            index += 1;
+        }
+
+        Self {
+            line: lines.get(index).map(|l| l.trim()).unwrap_or(""),
+            index,
+            attributes,
+        }
+    }
+}
+
+/// Whether the Rust item at `index` sits in a `trait` block or an
+/// `impl Trait for Type` block, where calls go through the trait.
+fn in_trait_or_trait_impl(lines: &[String], index: usize) -> bool {
+    let indent = |l: &str| l.len() - l.trim_start().len();
+    let Some(item_indent) = lines.get(index).map(|l| indent(l)) else {
+        return false;
+    };
+    for line in lines[..index].iter().rev() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || indent(line) >= item_indent {
+            continue;
+        }
+        let header = trimmed
+            .trim_start_matches("pub ")
+            .trim_start_matches("unsafe ");
+        if header.starts_with("impl") {
+            return header.contains(" for ");
+        }
+        return header.starts_with("trait ");
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn unused_names(index: &FileIndex, root: &Path, cfg: &CfgSet) -> HashSet<String> {
+        find_possibly_unused(index, root, cfg)
+            .unwrap()
+            .into_iter()
+            .map(|u| u.name)
+            .collect()
+    }
+
+    fn indexed(root: &Path) -> FileIndex {
+        let mut index = FileIndex::open(root).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+        index
+    }
+
+    #[test]
+    fn test_python_dead_code() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("app.py"),
+            "def main():\n    used()\n\ndef used():\n    pass\n\ndef orphan():\n    pass\n\n\
+             @route('/x')\ndef handler():\n    pass\n\n\
+             class A:\n    def __init__(self):\n        pass\n\n    def stale(self):\n        pass\n\n\
+             def test_thing():\n    pass\n\ndef exported():\n    pass\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("lib.py"), "from app import exported\n").unwrap();
+
+        let index = indexed(dir.path());
+        let unused = unused_names(&index, dir.path(), &CfgSet::host());
+        assert_eq!(
+            unused,
+            HashSet::from(["orphan".to_string(), "stale".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_rust_dead_code() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("lib.rs"),
+            "pub fn api() {\n    helper();\n}\n\nfn helper() {}\n\nfn orphan() {}\n\n\
+             struct S;\n\nimpl std::fmt::Display for S {\n    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {\n        Ok(())\n    }\n}\n\n\
+             impl S {\n    fn stale(&self) {}\n}\n\n\
+             #[test]\nfn it_works() {}\n\n\
+             #[cfg(test)]\nmod tests {\n    fn fixture() {}\n}\n\n\
+             #[cfg(feature = \"extra\")]\nfn gated() {}\n",
+        )
+        .unwrap();
+
+        let index = indexed(dir.path());
+        // cfg(test) items are compiled out by default; features are all on
+        let unused = unused_names(&index, dir.path(), &CfgSet::host());
+        assert_eq!(
+            unused,
+            HashSet::from([
+                "orphan".to_string(),
+                "stale".to_string(),
+                "gated".to_string()
+            ])
+        );
+
+        let cfg = CfgSet::with_options(&["test".to_string(), "feature = \"other\"".to_string()]);
+        let unused = unused_names(&index, dir.path(), &cfg);
+        assert_eq!(
+            unused,
+            HashSet::from([
+                "orphan".to_string(),
+                "stale".to_string(),
+                "fixture".to_string()
+            ])
+        );
+    }
+}
@@ -4,6 +4,7 @@
 
 use crate::parsers::Parsers;
 use arborium::tree_sitter;
+use moss_languages::rust_cfg::{self, CfgSet};
 use moss_languages::Export as LangExport;
 use moss_languages::Import as LangImport;
 use moss_languages::{
@@ -116,6 +117,8 @@ impl DepsResult {
 
 pub struct DepsExtractor {
     parsers: Parsers,
+    /// Rust items whose `#[cfg]` doesn't hold under this set aren't exports
+    cfg: CfgSet,
 }
 
 /// Convert trait Import to deps Import
@@ -154,8 +157,14 @@ fn convert_export(exp: &LangExport) -> Export {
 
 impl DepsExtractor {
     pub fn new() -> Self {
+        Self::with_cfg(CfgSet::host())
+    }
+
+    /// Extractor that evaluates Rust `#[cfg]` attributes against `cfg`.
+    pub fn with_cfg(cfg: CfgSet) -> Self {
         Self {
             parsers: Parsers::new(),
+            cfg,
         }
     }
 
@@ -272,7 +281,9 @@ impl DepsExtractor {
             }
 
             // Check for public symbol nodes
-            if support.public_symbol_kinds().contains(&kind) {
+            if support.public_symbol_kinds().contains(&kind)
+                && self.cfg_holds(&node, content, support)
+            {
                 let lang_exports = support.extract_public_symbols(&node, content);
                 exports.extend(lang_exports.iter().map(convert_export));
             }
@@ -289,6 +300,12 @@ impl DepsExtractor {
         }
     }
 
+    /// Whether a Rust item survives conditional compilation; other languages always do.
+    fn cfg_holds(&self, node: &tree_sitter::Node, content: &str, support: &dyn Language) -> bool {
+        support.grammar_name() != "rust"
+            || rust_cfg::item_cfg(node, content).is_none_or(|c| self.cfg.eval(&c))
+    }
+
     fn extract_typescript(&self, content: &str) -> ExtractedDeps {
         let tree = match self.parsers.parse_with_grammar("typescript", content) {
             Some(t) => t,
@@ -681,6 +698,40 @@ pub struct Bar {}
         assert!(result.exports.iter().any(|e| e.name == "Bar"));
     }
 
+    #[test]
+    fn test_rust_cfg_exports() {
+        let content = r#"
+pub fn api() {}
+
+#[cfg(test)]
+pub fn test_helper() {}
+
+#[cfg(test)]
+mod tests {
+    pub fn fixture() {}
+}
+
+#[cfg(feature = "extra")]
+pub fn extra() {}
+"#;
+        let path = PathBuf::from("lib.rs");
+        let names = |extractor: DepsExtractor| -> Vec<String> {
+            extractor
+                .extract(&path, content)
+                .exports
+                .into_iter()
+                .map(|e| e.name)
+                .collect()
+        };
+
+        assert_eq!(names(DepsExtractor::new()), vec!["api", "extra"]);
+        let cfg = CfgSet::with_options(&["test".to_string(), "feature = \"other\"".to_string()]);
+        assert_eq!(
+            names(DepsExtractor::with_cfg(cfg)),
+            vec!["api", "test_helper", "fixture"]
+        );
+    }
+
     #[test]
     fn test_typescript_imports() {
         let extractor = DepsExtractor::new();
@@ -747,6 +798,130 @@ export { foo, bar } from './specific';
         assert!(named.names.contains(&"bar".to_string()));
     }
 
+    #[test]
+    fn test_nix_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("lib/overlays")).unwrap();
+        std::fs::write(dir.path().join("lib/helpers.nix"), "{ }").unwrap();
+        std::fs::write(dir.path().join("lib/overlays/default.nix"), "{ }").unwrap();
+
+        let extractor = DepsExtractor::new();
+        let content = r#"{ pkgs }:
+let
+  helpers = import ./lib/helpers.nix { inherit pkgs; };
+  overlays = import ./lib/overlays;
+  nixpkgs = import <nixpkgs> { };
+in {
+  greet = helpers.greet;
+}
+"#;
+        let file = dir.path().join("default.nix");
+        let result = extractor.extract(&file, content);
+
+        let modules: Vec<&str> = result.imports.iter().map(|i| i.module.as_str()).collect();
+        assert_eq!(
+            modules,
+            vec!["./lib/helpers.nix", "./lib/overlays", "<nixpkgs>"]
+        );
+        // Top-level attrset bindings are exports; let bindings aren't
+        let exports: Vec<&str> = result.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(exports, vec!["greet"]);
+
+        let nix = moss_languages::support_for_path(&file).unwrap();
+        assert_eq!(
+            nix.resolve_local_import("./lib/helpers.nix", &file, dir.path()),
+            Some(dir.path().join("lib/helpers.nix"))
+        );
+        // Directories resolve to their default.nix
+        assert_eq!(
+            nix.resolve_local_import("./lib/overlays", &file, dir.path()),
+            Some(dir.path().join("lib/overlays/default.nix"))
+        );
+        assert_eq!(
+            nix.resolve_local_import("./missing.nix", &file, dir.path()),
+            None
+        );
+        assert!(nix.is_stdlib_import("<nixpkgs>", dir.path()));
+    }
+
+    #[test]
+    fn test_graphql_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("reviews.graphql"),
+            "type Review { id: ID! }\n",
+        )
+        .unwrap();
+        let extractor = DepsExtractor::new();
+        let content = r#"# import Review, Rating from "reviews.graphql"
+# import * from "./common.graphql"
+# Not an import
+
+type Query {
+  reviews: [Review!]!
+}
+"#;
+        let file = dir.path().join("schema.graphql");
+        let result = extractor.extract(&file, content);
+
+        let modules: Vec<&str> = result.imports.iter().map(|i| i.module.as_str()).collect();
+        assert_eq!(modules, vec!["reviews.graphql", "./common.graphql"]);
+        assert_eq!(result.imports[0].names, vec!["Review", "Rating"]);
+        assert!(result.imports[1].names.is_empty());
+
+        let graphql = moss_languages::support_for_path(&file).unwrap();
+        assert_eq!(
+            graphql.resolve_local_import("reviews.graphql", &file, dir.path()),
+            Some(dir.path().join("reviews.graphql"))
+        );
+        assert_eq!(
+            graphql.resolve_local_import("./common.graphql", &file, dir.path()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_terraform_module_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let vpc = dir.path().join("envs/modules/vpc");
+        std::fs::create_dir_all(&vpc).unwrap();
+        std::fs::write(vpc.join("variables.tf"), "variable \"cidr\" {}\n").unwrap();
+        std::fs::write(vpc.join("outputs.tf"), "output \"id\" {}\n").unwrap();
+        let extractor = DepsExtractor::new();
+        let content = r#"module "vpc" {
+  source = "./modules/vpc"
+  cidr   = "10.0.0.0/16"
+}
+
+module "consul" {
+  source = "hashicorp/consul/aws"
+}
+"#;
+        let file = dir.path().join("envs/main.tf");
+        let result = extractor.extract(&file, content);
+        let modules: Vec<&str> = result.imports.iter().map(|i| i.module.as_str()).collect();
+        assert_eq!(modules, vec!["./modules/vpc", "hashicorp/consul/aws"]);
+        assert!(result.imports[0].is_relative);
+        assert!(!result.imports[1].is_relative);
+
+        // Resolved against the calling module's directory; without a main.tf
+        // the module's first .tf file stands in for it
+        let hcl = moss_languages::support_for_path(&file).unwrap();
+        assert_eq!(
+            hcl.resolve_local_import("./modules/vpc", &file, dir.path()),
+            Some(vpc.join("outputs.tf"))
+        );
+        std::fs::write(vpc.join("main.tf"), "").unwrap();
+        assert_eq!(
+            hcl.resolve_local_import("./modules/vpc", &file, dir.path()),
+            Some(vpc.join("main.tf"))
+        );
+        assert_eq!(
+            hcl.resolve_local_import("hashicorp/consul/aws", &file, dir.path()),
+            None
+        );
+    }
+
     #[test]
     fn test_go_imports() {
         let extractor = DepsExtractor::new();
@@ -1,7 +1,7 @@
 use crate::parsers::Parsers;
 use arborium::tree_sitter;
 use moss_languages::{support_for_path, Language};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Result of finding a symbol in a file
 #[derive(Debug)]
@@ -29,6 +29,21 @@ pub struct ContainerBody {
     pub is_empty: bool,
 }
 
+/// Location of a function's body (for replace-body)
+#[derive(Debug)]
+pub struct FunctionBody {
+    /// Byte offset of the body node (the opening brace, or the first statement)
+    pub start: usize,
+    /// Byte offset where the body node ends
+    pub end: usize,
+    /// Body is delimited by braces rather than indentation
+    pub braced: bool,
+    /// Indentation of the function itself (for the closing brace)
+    pub indent: String,
+    /// Indentation of statements inside the body
+    pub inner_indent: String,
+}
+
 /// Editor for structural code modifications
 pub struct Editor {
     parsers: Parsers,
@@ -587,6 +602,114 @@ impl Editor {
         result
     }
 
+    /// Find the body of a function or method, for replacing it wholesale
+    pub fn find_function_body(
+        &self,
+        path: &Path,
+        content: &str,
+        name: &str,
+    ) -> Option<FunctionBody> {
+        let support = support_for_path(path)?;
+        let tree = self
+            .parsers
+            .parse_with_grammar(support.grammar_name(), content)?;
+        self.find_function_body_with_trait(tree.root_node(), content, name, support)
+    }
+
+    fn find_function_body_with_trait(
+        &self,
+        node: tree_sitter::Node,
+        content: &str,
+        name: &str,
+        support: &dyn Language,
+    ) -> Option<FunctionBody> {
+        if support.function_kinds().contains(&node.kind())
+            && node
+                .child_by_field_name("name")
+                .is_some_and(|n| &content[n.byte_range()] == name)
+        {
+            let body = node.child_by_field_name("body")?;
+            let (start, end) = (body.start_byte(), body.end_byte());
+            let braced = content[start..end].starts_with('{');
+
+            let indent = line_indent(content, node.start_byte());
+            let inner_indent = if braced {
+                // Indentation of the first statement on its own line
+                content[start..end]
+                    .lines()
+                    .skip(1)
+                    .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('}'))
+                    .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+            } else {
+                // Statements start at the body; it's indented unless on the header line
+                let line_start = content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+                let prefix = &content[line_start..start];
+                (!prefix.is_empty() && prefix.trim().is_empty()).then(|| prefix.to_string())
+            }
+            .unwrap_or_else(|| format!("{}    ", indent));
+
+            return Some(FunctionBody {
+                start,
+                end,
+                braced,
+                indent,
+                inner_indent,
+            });
+        }
+
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if let Some(body) = self.find_function_body_with_trait(child, content, name, support) {
+                return Some(body);
+            }
+        }
+        None
+    }
+
+    /// Replace a function's body with `new_body`, keeping its signature.
+    ///
+    /// `new_body` is the statements alone; it's dedented and then indented to
+    /// match the function.
+    pub fn replace_function_body(
+        &self,
+        content: &str,
+        body: &FunctionBody,
+        new_body: &str,
+    ) -> String {
+        let indented = self.apply_indent(&dedent(new_body), &body.inner_indent);
+        let mut result = String::new();
+        if body.braced {
+            // Keep the braces, replace what's between them
+            result.push_str(&content[..body.start + 1]);
+            if !indented.is_empty() {
+                result.push('\n');
+                result.push_str(&indented);
+                result.push('\n');
+                result.push_str(&body.indent);
+            }
+            result.push_str(&content[body.end - 1..]);
+        } else {
+            // Back up to the end of the header (the `:`), so a body that was on
+            // the header line moves onto its own
+            let header_end = content[..body.start].trim_end().len();
+            result.push_str(&content[..header_end]);
+            result.push('\n');
+            result.push_str(&indented);
+            result.push_str(&content[body.end..]);
+        }
+        result
+    }
+
+    /// Whether the content fails to parse cleanly as the language of `path`.
+    pub fn has_syntax_errors(&self, path: &Path, content: &str) -> bool {
+        support_for_path(path)
+            .and_then(|support| {
+                self.parsers
+                    .parse_with_grammar(support.grammar_name(), content)
+            })
+            .is_some_and(|tree| tree.root_node().has_error())
+    }
+
     /// Apply indentation to content
     fn apply_indent(&self, content: &str, indent: &str) -> String {
         content
@@ -606,6 +729,214 @@ impl Editor {
     }
 }
 
+/// A batch of file edits applied all-or-nothing.
+///
+/// Edits are staged in memory, each against the file's latest staged
+/// content. `commit` checks that no edited file gains a syntax error, writes
+/// every file to a temp file beside it (beside a symlink's target, with the
+/// original's permissions) and renames them into place; if any
+/// step fails, files already replaced are restored, so the tree is never
+/// left half-edited.
+pub struct EditTransaction {
+    root: PathBuf,
+    editor: Editor,
+    files: Vec<StagedFile>,
+}
+
+struct StagedFile {
+    /// Relative to the transaction root
+    path: String,
+    /// Content on disk when first staged; None for a new file
+    original: Option<String>,
+    content: String,
+}
+
+impl EditTransaction {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            editor: Editor::new(),
+            files: Vec::new(),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Files with staged edits, in staging order.
+    pub fn staged_files(&self) -> Vec<String> {
+        self.files.iter().map(|f| f.path.clone()).collect()
+    }
+
+    /// Stage new content for a file, replacing any earlier staged edits.
+    pub fn write(&mut self, path: &str, content: String) -> Result<(), String> {
+        self.staged(path)?.content = content;
+        Ok(())
+    }
+
+    /// Stage an edit computed from the file's current staged content.
+    pub fn edit(
+        &mut self,
+        path: &str,
+        f: impl FnOnce(&Editor, &Path, &str) -> Result<String, String>,
+    ) -> Result<(), String> {
+        let full_path = self.root.join(path);
+        let index = self.staged_index(path)?;
+        let content = f(&self.editor, &full_path, &self.files[index].content)?;
+        self.files[index].content = content;
+        Ok(())
+    }
+
+    fn staged(&mut self, path: &str) -> Result<&mut StagedFile, String> {
+        let index = self.staged_index(path)?;
+        Ok(&mut self.files[index])
+    }
+
+    fn staged_index(&mut self, path: &str) -> Result<usize, String> {
+        if let Some(i) = self.files.iter().position(|f| f.path == path) {
+            return Ok(i);
+        }
+        let original = match std::fs::read_to_string(self.root.join(path)) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(format!("Error reading {}: {}", path, e)),
+        };
+        self.files.push(StagedFile {
+            path: path.to_string(),
+            content: original.clone().unwrap_or_default(),
+            original,
+        });
+        Ok(self.files.len() - 1)
+    }
+
+    /// Write every staged edit, or none of them. Returns the files changed.
+    pub fn commit(self) -> Result<Vec<String>, String> {
+        let changed: Vec<&StagedFile> = self
+            .files
+            .iter()
+            .filter(|f| f.original.as_deref() != Some(f.content.as_str()))
+            .collect();
+
+        // A file that was already broken may stay broken; it may not break
+        for file in &changed {
+            let path = self.root.join(&file.path);
+            if self.editor.has_syntax_errors(&path, &file.content)
+                && !file
+                    .original
+                    .as_deref()
+                    .is_some_and(|o| self.editor.has_syntax_errors(&path, o))
+            {
+                return Err(format!(
+                    "{} doesn't parse after the edit; nothing was written",
+                    file.path
+                ));
+            }
+        }
+
+        // Write through symlinks to the file they point at
+        let targets: Vec<PathBuf> = changed
+            .iter()
+            .map(|file| {
+                let path = self.root.join(&file.path);
+                path.canonicalize().unwrap_or(path)
+            })
+            .collect();
+
+        let mut temps = Vec::new();
+        for (file, target) in changed.iter().zip(&targets) {
+            let temp = temp_path(target);
+            let written = match target.parent() {
+                Some(parent) => std::fs::create_dir_all(parent),
+                None => Ok(()),
+            }
+            .and_then(|()| std::fs::write(&temp, &file.content))
+            .and_then(|()| copy_permissions(target, &temp));
+            if let Err(e) = written {
+                remove_all(&temps);
+                let _ = std::fs::remove_file(&temp);
+                return Err(format!(
+                    "Error writing {}: {}; nothing was written",
+                    file.path, e
+                ));
+            }
+            temps.push(temp);
+        }
+
+        for (i, (file, temp)) in changed.iter().zip(&temps).enumerate() {
+            if let Err(e) = std::fs::rename(temp, &targets[i]) {
+                for (done, path) in changed[..i].iter().zip(&targets) {
+                    let _ = match &done.original {
+                        Some(original) => std::fs::write(path, original),
+                        None => std::fs::remove_file(path),
+                    };
+                }
+                remove_all(&temps[i..]);
+                return Err(format!(
+                    "Error replacing {}: {}; edits rolled back",
+                    file.path, e
+                ));
+            }
+        }
+
+        Ok(changed.iter().map(|f| f.path.clone()).collect())
+    }
+}
+
+/// Hidden sibling a file's new content is written to before the rename.
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{}.moss-edit", name))
+}
+
+/// Give `temp` the mode of the file it will replace, if that file exists.
+fn copy_permissions(target: &Path, temp: &Path) -> std::io::Result<()> {
+    match std::fs::metadata(target) {
+        Ok(meta) => std::fs::set_permissions(temp, meta.permissions()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn remove_all(paths: &[PathBuf]) {
+    for path in paths {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Leading whitespace of the line containing `pos`.
+fn line_indent(content: &str, pos: usize) -> String {
+    let line_start = content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    content[line_start..pos]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect()
+}
+
+/// Strip surrounding blank lines and the indentation common to every line.
+fn dedent(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().skip_while(|l| l.trim().is_empty()).collect();
+    let end = lines
+        .iter()
+        .rposition(|l| !l.trim().is_empty())
+        .map_or(0, |i| i + 1);
+    let lines = &lines[..end];
+    let common = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| l.get(common..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -737,4 +1068,110 @@ def bar():
         // Should still have closing brace
         assert!(result.contains("}"));
     }
+
+    #[test]
+    fn test_replace_python_body() {
+        let editor = Editor::new();
+        let path = PathBuf::from("test.py");
+        let content = "class A:\n    def f(self, x):\n        \"\"\"Doc.\"\"\"\n        return x\n\n    def g(self): return 1\n";
+        let body = editor.find_function_body(&path, content, "f").unwrap();
+        let result = editor.replace_function_body(content, &body, "\n  y = x * 2\n  return y\n");
+        assert_eq!(
+            result,
+            "class A:\n    def f(self, x):\n        y = x * 2\n        return y\n\n    def g(self): return 1\n"
+        );
+
+        // A body on the header line moves to its own line
+        let body = editor.find_function_body(&path, &result, "g").unwrap();
+        let result = editor.replace_function_body(&result, &body, "return 2");
+        assert!(result.ends_with("    def g(self):\n        return 2\n"));
+        assert!(!editor.has_syntax_errors(&path, &result));
+
+        let body = editor.find_function_body(&path, &result, "g").unwrap();
+        assert!(editor.has_syntax_errors(
+            &path,
+            &editor.replace_function_body(&result, &body, "return (")
+        ));
+    }
+
+    #[test]
+    fn test_replace_rust_body() {
+        let editor = Editor::new();
+        let path = PathBuf::from("test.rs");
+        let content =
+            "impl A {\n    fn f(&self) -> i32 {\n        1\n    }\n\n    fn g(&self) {}\n}\n";
+        let body = editor.find_function_body(&path, content, "f").unwrap();
+        let result = editor.replace_function_body(content, &body, "let x = 2;\nx * 3");
+        assert_eq!(
+            result,
+            "impl A {\n    fn f(&self) -> i32 {\n        let x = 2;\n        x * 3\n    }\n\n    fn g(&self) {}\n}\n"
+        );
+
+        let body = editor.find_function_body(&path, &result, "g").unwrap();
+        let result = editor.replace_function_body(&result, &body, "todo!()");
+        assert!(result.contains("    fn g(&self) {\n        todo!()\n    }\n"));
+        assert!(editor.find_function_body(&path, content, "A").is_none());
+    }
+
+    #[test]
+    fn test_transaction_commits_all_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.py"), "def f():\n    return 1\n").unwrap();
+
+        let mut tx = EditTransaction::new(dir.path());
+        tx.edit("a.py", |editor, path, content| {
+            let body = editor.find_function_body(path, content, "f").unwrap();
+            Ok(editor.replace_function_body(content, &body, "return 2"))
+        })
+        .unwrap();
+        tx.write("pkg/b.py", "X = 1\n".to_string()).unwrap();
+        assert_eq!(tx.commit().unwrap(), vec!["a.py", "pkg/b.py"]);
+
+        let read = |p: &str| std::fs::read_to_string(dir.path().join(p)).unwrap();
+        assert_eq!(read("a.py"), "def f():\n    return 2\n");
+        assert_eq!(read("pkg/b.py"), "X = 1\n");
+    }
+
+    #[test]
+    fn test_transaction_broken_file_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = "def f():\n    return 1\n";
+        std::fs::write(dir.path().join("a.py"), a).unwrap();
+        std::fs::write(dir.path().join("b.py"), "def g():\n    pass\n").unwrap();
+
+        let mut tx = EditTransaction::new(dir.path());
+        tx.write("a.py", "def f():\n    return 2\n".to_string())
+            .unwrap();
+        tx.write("b.py", "def g(:\n".to_string()).unwrap();
+        let err = tx.commit().unwrap_err();
+        assert!(err.contains("b.py"), "{}", err);
+
+        assert_eq!(std::fs::read_to_string(dir.path().join("a.py")).unwrap(), a);
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(leftovers.len(), 2, "{:?}", leftovers);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_transaction_keeps_mode_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("run.py");
+        std::fs::write(&script, "X = 1\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink("run.py", dir.path().join("link.py")).unwrap();
+
+        let mut tx = EditTransaction::new(dir.path());
+        tx.write("link.py", "X = 2\n".to_string()).unwrap();
+        tx.commit().unwrap();
+
+        let link = std::fs::symlink_metadata(dir.path().join("link.py")).unwrap();
+        assert!(link.file_type().is_symlink());
+        assert_eq!(std::fs::read_to_string(&script).unwrap(), "X = 2\n");
+        let mode = std::fs::metadata(&script).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
 }
@@ -5,7 +5,8 @@
 
 use crate::parsers::Parsers;
 use arborium::tree_sitter;
-use moss_languages::{support_for_grammar, support_for_path, Language, Symbol, Visibility};
+use moss_languages::{support_for_grammar, support_for_path, Language, Param, Symbol, Visibility};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Result of extracting symbols from a file.
@@ -16,11 +17,22 @@ pub struct ExtractResult {
     pub file_path: String,
 }
 
+/// A function's parameters and return type, for `--json` detail output.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FunctionDetails {
+    pub parameters: Vec<Param>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+}
+
 /// Options for symbol extraction.
 #[derive(Clone, Default)]
 pub struct ExtractOptions {
     /// Include private/non-public symbols
     pub include_private: bool,
+    /// Include nested functions and anonymous functions (closures, lambdas).
+    /// When false, only top-level and named symbols are extracted.
+    pub include_nested: bool,
 }
 
 /// Shared symbol extractor using the Language trait.
@@ -57,6 +69,53 @@ impl Extractor {
         }
     }
 
+    /// Parameters and return type of every function in a file, keyed by
+    /// 1-based start line (matching `Symbol::start_line`).
+    pub fn function_details(&self, path: &Path, content: &str) -> HashMap<usize, FunctionDetails> {
+        let mut details = HashMap::new();
+        let Some(support) = support_for_path(path) else {
+            return details;
+        };
+        let Some(tree) = self
+            .parsers
+            .parse_with_grammar(support.grammar_name(), content)
+        else {
+            return details;
+        };
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if support.function_kinds().contains(&node.kind()) {
+                details
+                    .entry(node.start_position().row + 1)
+                    .or_insert_with(|| FunctionDetails {
+                        parameters: support.parameters(&node, content),
+                        return_type: support.return_type(&node, content),
+                    });
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        details
+    }
+
+    /// Extract symbols from an already-parsed tree of `content`.
+    pub fn extract_tree(
+        &self,
+        path: &Path,
+        content: &str,
+        tree: &tree_sitter::Tree,
+    ) -> ExtractResult {
+        let symbols = match support_for_path(path) {
+            Some(support) => self.symbols_from_tree(tree, content, support),
+            None => Vec::new(),
+        };
+
+        ExtractResult {
+            symbols,
+            file_path: path.to_string_lossy().to_string(),
+        }
+    }
+
     fn extract_with_support(&self, content: &str, support: &dyn Language) -> Vec<Symbol> {
         let tree = match self
             .parsers
@@ -65,7 +124,15 @@ impl Extractor {
             Some(t) => t,
             None => return Vec::new(),
         };
+        self.symbols_from_tree(&tree, content, support)
+    }
 
+    fn symbols_from_tree(
+        &self,
+        tree: &tree_sitter::Tree,
+        content: &str,
+        support: &dyn Language,
+    ) -> Vec<Symbol> {
         let mut symbols = Vec::new();
         let root = tree.root_node();
         let mut cursor = root.walk();
@@ -76,6 +143,9 @@ impl Extractor {
         if support.grammar_name() == "rust" {
             Self::merge_rust_impl_blocks(&mut symbols);
         }
+        if support.grammar_name() == "markdown" {
+            symbols = Self::nest_headings(symbols, content);
+        }
 
         symbols
     }
@@ -125,15 +195,44 @@ impl Extractor {
             }
 
             // Check if this is a function
-            if support.function_kinds().contains(&kind) {
-                if let Some(sym) = support.extract_function(&node, content, in_container) {
-                    if self.should_include(&sym) {
-                        symbols.push(sym);
-                    }
+            let is_function = support.function_kinds().contains(&kind);
+            let function = if is_function {
+                support.extract_function(&node, content, in_container)
+            } else {
+                None
+            };
+            if let Some(mut sym) = function {
+                if self.should_include(&sym) {
+                    self.collect_nested(&node, content, support, &mut sym);
+                    symbols.push(sym);
+                }
+                // Nested functions belong to this symbol, not the enclosing scope
+                if cursor.goto_next_sibling() {
+                    continue;
                 }
+                break;
             }
-            // Check if this is a container (class, impl, module)
-            else if support.container_kinds().contains(&kind) {
+            // Check if this is an anonymous function (closure, lambda, arrow function)
+            else if !is_function
+                && self.options.include_nested
+                && support.anonymous_function_kinds().contains(&kind)
+            {
+                let mut sym = extract_anonymous_function(&node, content);
+                if self.should_include(&sym) {
+                    self.collect_nested(&node, content, support, &mut sym);
+                    symbols.push(sym);
+                }
+                if cursor.goto_next_sibling() {
+                    continue;
+                }
+                break;
+            }
+            // Check if this is a container (class, impl, module). Kinds that
+            // can also be functions (Elixir calls, Lisp lists) are ordinary
+            // code unless they extract as one or the other.
+            else if support.container_kinds().contains(&kind)
+                && (!is_function || support.extract_container(&node, content).is_some())
+            {
                 if let Some(mut sym) = support.extract_container(&node, content) {
                     if self.should_include(&sym) {
                         // Recurse into container body
@@ -182,10 +281,72 @@ impl Extractor {
         }
     }
 
+    /// Collect functions nested inside a function body as its children.
+    /// Does nothing unless nested extraction is enabled.
+    fn collect_nested(
+        &self,
+        node: &tree_sitter::Node,
+        content: &str,
+        support: &dyn Language,
+        sym: &mut Symbol,
+    ) {
+        if !self.options.include_nested {
+            return;
+        }
+        let mut cursor = node.walk();
+        if cursor.goto_first_child() {
+            self.collect_symbols(&mut cursor, content, support, &mut sym.children, false);
+        }
+    }
+
     fn should_include(&self, sym: &Symbol) -> bool {
         self.options.include_private || matches!(sym.visibility, Visibility::Public)
     }
 
+    /// Nest flat Markdown headings by level (the `#`s of their signature).
+    /// Each heading's section runs to the next heading at its level or above.
+    fn nest_headings(mut headings: Vec<Symbol>, content: &str) -> Vec<Symbol> {
+        fn level(heading: &Symbol) -> usize {
+            heading.signature.chars().take_while(|&c| c == '#').count()
+        }
+        fn attach(done: Symbol, stack: &mut [Symbol], roots: &mut Vec<Symbol>) {
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(done),
+                None => roots.push(done),
+            }
+        }
+
+        let lines: Vec<&str> = content.lines().collect();
+        for i in 0..headings.len() {
+            let next = headings[i + 1..]
+                .iter()
+                .find(|h| level(h) <= level(&headings[i]))
+                .map_or(lines.len() + 1, |h| h.start_line);
+            let mut end = next - 1;
+            while end > headings[i].end_line && lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            headings[i].end_line = end.max(headings[i].end_line);
+        }
+
+        let mut roots = Vec::new();
+        let mut stack: Vec<Symbol> = Vec::new();
+        for heading in headings {
+            while stack
+                .last()
+                .is_some_and(|top| level(top) >= level(&heading))
+            {
+                let done = stack.pop().unwrap();
+                attach(done, &mut stack, &mut roots);
+            }
+            stack.push(heading);
+        }
+        while let Some(done) = stack.pop() {
+            attach(done, &mut stack, &mut roots);
+        }
+        roots
+    }
+
     /// Merge Rust impl blocks with their corresponding struct/enum types
     fn merge_rust_impl_blocks(symbols: &mut Vec<Symbol>) {
         use std::collections::HashMap;
@@ -235,6 +396,41 @@ impl Extractor {
     }
 }
 
+/// Build a symbol for an anonymous function node.
+///
+/// Anonymous functions bound directly to a name (`const f = () => {}`,
+/// `let f = |x| x`, `f = lambda x: x`) take that name; others are `<anonymous>`.
+fn extract_anonymous_function(node: &tree_sitter::Node, content: &str) -> Symbol {
+    let name = node
+        .parent()
+        .and_then(|parent| {
+            ["name", "pattern", "left"]
+                .iter()
+                .find_map(|field| parent.child_by_field_name(field))
+        })
+        .filter(|binding| binding.kind() == "identifier")
+        .map(|binding| content[binding.byte_range()].to_string())
+        .unwrap_or_else(|| "<anonymous>".to_string());
+
+    let signature = content[node.byte_range()]
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_string();
+
+    Symbol {
+        name,
+        kind: moss_languages::SymbolKind::Function,
+        signature,
+        docstring: None,
+        start_line: node.start_position().row + 1,
+        end_line: node.end_position().row + 1,
+        visibility: Visibility::Private,
+        children: Vec::new(),
+    }
+}
+
 /// Recursively adjust line numbers for symbols (used for embedded content).
 fn adjust_lines(sym: &mut Symbol, offset: usize) {
     sym.start_line += offset;
@@ -337,6 +533,7 @@ impl Foo {
     fn test_include_private() {
         let extractor = Extractor::with_options(ExtractOptions {
             include_private: true,
+            ..Default::default()
         });
         let content = r#"
 fn private_fn() {}
@@ -347,4 +544,46 @@ pub fn public_fn() {}
         assert!(names.contains(&"private_fn"));
         assert!(names.contains(&"public_fn"));
     }
+
+    #[test]
+    fn test_nested_functions_excluded_by_default() {
+        let extractor = Extractor::with_options(ExtractOptions {
+            include_private: true,
+            ..Default::default()
+        });
+        let content = r#"
+def outer():
+    def inner():
+        pass
+    square = lambda x: x * x
+    return inner
+"#;
+        let result = extractor.extract(&PathBuf::from("test.py"), content);
+        assert_eq!(result.symbols.len(), 1);
+        assert_eq!(result.symbols[0].name, "outer");
+        assert!(result.symbols[0].children.is_empty());
+    }
+
+    #[test]
+    fn test_nested_functions_included_when_enabled() {
+        let extractor = Extractor::with_options(ExtractOptions {
+            include_private: true,
+            include_nested: true,
+        });
+        let content = r#"
+fn outer() {
+    fn inner() {}
+    let add = |a, b| a + b;
+    items.iter().map(|x| x + 1);
+}
+"#;
+        let result = extractor.extract(&PathBuf::from("test.rs"), content);
+        assert_eq!(result.symbols.len(), 1);
+        let names: Vec<_> = result.symbols[0]
+            .children
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["inner", "add", "<anonymous>"]);
+    }
 }
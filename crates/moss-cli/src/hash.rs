@@ -0,0 +1,25 @@
+//! Content hashes for cache keys.
+//!
+//! Keys are stored on disk, so the hash must not change between builds;
+//! `std::hash::DefaultHasher` makes no such promise.
+
+use sha2::{Digest, Sha256};
+
+/// Hex SHA-256 of `content`.
+pub fn content_hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_is_stable() {
+        assert_eq!(
+            content_hash(b"hello\n"),
+            "5891b5b522d5df086d0ff0b110fbd9d21bb4fc7163af34d08286a2e846f6be03"
+        );
+        assert_ne!(content_hash(b"a"), content_hash(b"b"));
+    }
+}
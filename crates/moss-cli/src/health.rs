@@ -1,12 +1,16 @@
 //! Codebase health metrics.
 //!
 //! Quick overview of codebase health including file counts,
-//! complexity summary, and structural metrics.
+//! complexity summary, linter diagnostics, dependencies, and structural
+//! metrics, rolled up into a 0-100 score.
 
 use std::collections::HashMap;
 use std::path::Path;
 
+use crate::config::MossConfig;
 use crate::index::FileIndex;
+use crate::tool_cache::{self, ToolCache};
+use moss_tools::{registry_with_custom, run_concurrently, Tool};
 
 /// Large file info for reporting
 #[derive(Debug, Clone)]
@@ -15,6 +19,22 @@ pub struct LargeFile {
     pub lines: usize,
 }
 
+/// Diagnostics reported by the project's linters
+#[derive(Debug, Clone)]
+pub struct DiagnosticCounts {
+    pub errors: usize,
+    pub warnings: usize,
+    /// Tools that ran
+    pub tools: Vec<String>,
+}
+
+/// Declared dependencies of one detected package ecosystem
+#[derive(Debug, Clone)]
+pub struct EcosystemDependencies {
+    pub ecosystem: String,
+    pub count: usize,
+}
+
 /// Thresholds for file size severity
 const LARGE_THRESHOLD: usize = 500;
 const VERY_LARGE_THRESHOLD: usize = 1000;
@@ -26,11 +46,15 @@ pub struct HealthReport {
     pub total_files: usize,
     pub files_by_language: HashMap<String, usize>,
     pub total_lines: usize,
+    pub total_symbols: usize,
     pub avg_complexity: f64,
     pub max_complexity: usize,
     pub high_risk_functions: usize,
     pub total_functions: usize,
     pub large_files: Vec<LargeFile>,
+    /// None when no linter was available to run
+    pub diagnostics: Option<DiagnosticCounts>,
+    pub dependencies: Vec<EcosystemDependencies>,
 }
 
 impl HealthReport {
@@ -48,6 +72,7 @@ impl HealthReport {
             }
         }
         lines.push(format!("  Lines: {}", self.total_lines));
+        lines.push(format!("  Symbols: {}", self.total_symbols));
         lines.push(String::new());
 
         lines.push("## Complexity".to_string());
@@ -56,6 +81,21 @@ impl HealthReport {
         lines.push(format!("  Maximum: {}", self.max_complexity));
         lines.push(format!("  High risk (>10): {}", self.high_risk_functions));
 
+        if let Some(ref diagnostics) = self.diagnostics {
+            lines.push(String::new());
+            lines.push(format!("## Diagnostics ({})", diagnostics.tools.join(", ")));
+            lines.push(format!("  Errors: {}", diagnostics.errors));
+            lines.push(format!("  Warnings: {}", diagnostics.warnings));
+        }
+
+        if !self.dependencies.is_empty() {
+            lines.push(String::new());
+            lines.push("## Dependencies".to_string());
+            for deps in &self.dependencies {
+                lines.push(format!("  {}: {}", deps.ecosystem, deps.count));
+            }
+        }
+
         // Categorize files by severity
         let massive: Vec<_> = self
             .large_files
@@ -118,18 +158,17 @@ impl HealthReport {
             }
         }
 
-        let health_score = self.calculate_health_score();
-        let grade = self.grade();
         lines.push(String::new());
-        lines.push(format!(
-            "## Score: {} ({:.0}%)",
-            grade,
-            health_score * 100.0
-        ));
+        lines.push(format!("## Score: {} ({}/100)", self.grade(), self.score()));
 
         lines.join("\n")
     }
 
+    /// Overall health, 0-100.
+    pub fn score(&self) -> u32 {
+        (self.calculate_health_score() * 100.0).round() as u32
+    }
+
     fn calculate_health_score(&self) -> f64 {
         // Scoring based on complexity and file sizes
         // Lower average complexity = better
@@ -195,10 +234,30 @@ impl HealthReport {
 
         // Weight: complexity 30%, risk 30%, file sizes 40%
         // File sizes weighted higher because they're more actionable
-        (complexity_score * 0.3) + (risk_score * 0.3) + (file_size_score * 0.4)
+        let structure = (complexity_score * 0.3) + (risk_score * 0.3) + (file_size_score * 0.4);
+
+        // Linter findings take a fifth of the score when any linter ran.
+        // An error counts as much as five warnings.
+        match self.diagnostics {
+            Some(ref diagnostics) => {
+                let per_file = (diagnostics.errors * 5 + diagnostics.warnings) as f64
+                    / self.total_files.max(1) as f64;
+                let diagnostic_score = if per_file <= 0.05 {
+                    1.0
+                } else if per_file <= 0.2 {
+                    0.8
+                } else if per_file <= 0.5 {
+                    0.6
+                } else {
+                    0.4
+                };
+                structure * 0.8 + diagnostic_score * 0.2
+            }
+            None => structure,
+        }
     }
 
-    fn grade(&self) -> &'static str {
+    pub fn grade(&self) -> &'static str {
         let score = self.calculate_health_score();
         if score >= 0.9 {
             "A"
@@ -253,11 +312,14 @@ pub fn analyze_health(root: &Path) -> HealthReport {
                 total_files: 0,
                 files_by_language: HashMap::new(),
                 total_lines: 0,
+                total_symbols: 0,
                 avg_complexity: 0.0,
                 max_complexity: 0,
                 high_risk_functions: 0,
                 total_functions: 0,
                 large_files: Vec::new(),
+                diagnostics: None,
+                dependencies: Vec::new(),
             };
         }
     };
@@ -312,6 +374,13 @@ pub fn analyze_health(root: &Path) -> HealthReport {
         }
     }
 
+    let total_symbols = conn
+        .query_row("SELECT COUNT(*) FROM symbols", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .map(|n| n as usize)
+        .unwrap_or(0);
+
     // Use cached line counts from index
     let mut total_lines = 0usize;
     let mut large_files = Vec::new();
@@ -349,5 +418,59 @@ pub fn analyze_health(root: &Path) -> HealthReport {
         high_risk_functions,
         total_functions,
         large_files,
+        total_symbols,
+        diagnostics: count_diagnostics(root),
+        dependencies: count_dependencies(root),
     }
 }
+
+/// Count diagnostics from the project's available file-scoped linters, with
+/// results for unchanged files taken from the tool cache. Project-wide
+/// checkers (clippy, tsc, mypy, ...) can take minutes, so they're left to
+/// `moss analyze --lint`.
+fn count_diagnostics(root: &Path) -> Option<DiagnosticCounts> {
+    let registry = registry_with_custom(root);
+    let tools: Vec<&dyn Tool> = registry
+        .detect(root)
+        .into_iter()
+        .map(|(tool, _)| tool)
+        .filter(|tool| tool.info().file_scoped && tool.is_available())
+        .collect();
+    if tools.is_empty() {
+        return None;
+    }
+
+    let cache = ToolCache::open(root).ok();
+    let jobs = MossConfig::load(root).lint.jobs();
+    let outcomes = run_concurrently(&tools, jobs, |tool| match &cache {
+        Some(cache) => tool_cache::run_cached(tool, None, root, cache),
+        None => tool.run(&[], root),
+    });
+
+    let mut counts = DiagnosticCounts {
+        errors: 0,
+        warnings: 0,
+        tools: Vec::new(),
+    };
+    for (tool, outcome) in tools.iter().zip(outcomes) {
+        let Ok(result) = outcome else { continue };
+        counts.tools.push(tool.info().name.to_string());
+        counts.errors += result.error_count();
+        counts.warnings += result.warning_count();
+    }
+    (!counts.tools.is_empty()).then_some(counts)
+}
+
+/// Declared dependencies per detected ecosystem, from the manifests alone.
+fn count_dependencies(root: &Path) -> Vec<EcosystemDependencies> {
+    moss_packages::ecosystems::detect_all(root)
+        .into_iter()
+        .filter_map(|eco| {
+            let deps = eco.list_dependencies(root).ok()?;
+            Some(EcosystemDependencies {
+                ecosystem: eco.name().to_string(),
+                count: deps.len(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,164 @@
+//! Terminal syntax highlighting for source printed by `moss view`.
+//!
+//! Uses arborium's bundled tree-sitter highlight queries, so any language with
+//! a grammar gets colors. Output that isn't going to a terminal, or that has no
+//! grammar, is passed through unchanged.
+
+use arborium::advanced::AnsiOptions;
+use arborium::theme::{builtin, Theme};
+use arborium::{AnsiHighlighter, Config};
+use moss_languages::support_for_path;
+use std::borrow::Cow;
+use std::io::IsTerminal;
+use std::path::Path;
+
+const RESET: &str = "\x1b[0m";
+
+/// Palette used when `--theme` isn't given.
+const DEFAULT_THEME: &str = "catppuccin-mocha";
+
+/// Colorizes source for the terminal, or leaves it as-is.
+pub struct SourceHighlighter {
+    inner: Option<AnsiHighlighter>,
+}
+
+impl SourceHighlighter {
+    /// A highlighter that never colors.
+    pub fn plain() -> Self {
+        Self { inner: None }
+    }
+
+    /// Colors only when `enabled` and stdout is a terminal. Errors on an
+    /// unknown theme name, even when output would stay plain.
+    pub fn new(enabled: bool, theme: Option<&str>) -> Result<Self, String> {
+        let theme = find_theme(theme.unwrap_or(DEFAULT_THEME))?;
+        if !enabled || !std::io::stdout().is_terminal() {
+            return Ok(Self::plain());
+        }
+        let options = AnsiOptions {
+            width: None,
+            pad_to_width: false,
+            ..Default::default()
+        };
+        Ok(Self {
+            inner: Some(AnsiHighlighter::with_options(
+                theme,
+                Config::default(),
+                options,
+            )),
+        })
+    }
+
+    /// Whether output will be colored at all.
+    pub fn is_active(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Highlight `source` as the language of `path`, one entry per source
+    /// line. Each line opens and closes its own styles, so any subset of them
+    /// can be printed, with or without line numbers.
+    pub fn lines<'a>(&mut self, path: &Path, source: &'a str) -> Vec<Cow<'a, str>> {
+        let colored = self.inner.as_mut().and_then(|inner| {
+            let lang = support_for_path(path)?;
+            inner.highlight(lang.grammar_name(), source).ok()
+        });
+        let Some(colored) = colored else {
+            return source.lines().map(Cow::Borrowed).collect();
+        };
+
+        let mut lines: Vec<Cow<str>> = Vec::new();
+        // Styles can span lines (docstrings, block comments)
+        let mut open: Option<&str> = None;
+        for line in colored.split('\n') {
+            let mut out = open.unwrap_or_default().to_string();
+            out.push_str(line);
+            let mut rest = line;
+            while let Some(start) = rest.find("\x1b[") {
+                let Some(len) = rest[start..].find('m') else {
+                    break;
+                };
+                let seq = &rest[start..start + len + 1];
+                open = (seq != RESET).then_some(seq);
+                rest = &rest[start + len + 1..];
+            }
+            if open.is_some() {
+                out.push_str(RESET);
+            }
+            lines.push(Cow::Owned(out));
+        }
+        lines.truncate(source.lines().count());
+        lines
+    }
+}
+
+/// Theme names accepted by `--theme`.
+pub fn theme_names() -> Vec<String> {
+    builtin::all().iter().map(|t| slug(&t.name)).collect()
+}
+
+fn find_theme(name: &str) -> Result<Theme, String> {
+    let wanted = slug(name);
+    builtin::all()
+        .into_iter()
+        .find(|t| slug(&t.name) == wanted)
+        .ok_or_else(|| {
+            format!(
+                "unknown theme '{}' (available: {})",
+                name,
+                theme_names().join(", ")
+            )
+        })
+}
+
+/// "Rosé Pine Moon" -> "rose-pine-moon"
+fn slug(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.to_lowercase().chars() {
+        match c {
+            'é' | 'è' | 'ê' => out.push('e'),
+            c if c.is_ascii_alphanumeric() => out.push(c),
+            _ if !out.ends_with('-') => out.push('-'),
+            _ => {}
+        }
+    }
+    out.trim_end_matches('-').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_lookup() {
+        assert!(theme_names().contains(&DEFAULT_THEME.to_string()));
+        assert!(find_theme("Rosé Pine Moon").is_ok());
+        assert!(find_theme("rose_pine_moon").is_ok());
+        assert!(find_theme("nope").unwrap_err().contains("one-dark"));
+    }
+
+    #[test]
+    fn test_plain_passthrough() {
+        let mut highlighter = SourceHighlighter::plain();
+        assert_eq!(
+            highlighter.lines(Path::new("main.rs"), "fn main() {}\n"),
+            ["fn main() {}"]
+        );
+    }
+
+    #[test]
+    fn test_lines_are_self_contained() {
+        let mut highlighter = SourceHighlighter {
+            inner: Some(AnsiHighlighter::new(find_theme(DEFAULT_THEME).unwrap())),
+        };
+        let source = "def f():\n    \"\"\"doc\n    more\"\"\"\n    return 1\n";
+        let lines = highlighter.lines(Path::new("a.py"), source);
+        assert_eq!(lines.len(), 4);
+        // The docstring's style is closed on its first line and reopened on the next
+        assert!(lines[1].ends_with(RESET));
+        assert!(lines[2].starts_with("\x1b["));
+        assert!(lines[3].contains("return"));
+
+        // No grammar for the extension: left alone
+        assert_eq!(highlighter.lines(Path::new("notes.zzz"), "x\n"), ["x"]);
+    }
+}
@@ -1,38 +1,230 @@
+use crate::config::{MossConfig, WalkConfig};
+use crate::filter::Filter;
 use crate::paths::get_moss_dir;
-use ignore::WalkBuilder;
+use crate::walk::project_walker;
+use moss_languages::go_build::{go_build_exclusion, GoTarget};
 use moss_languages::support_for_path;
 use rayon::prelude::*;
 use rusqlite::{params, Connection};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::hash::content_hash;
 use crate::symbols::{Import, Symbol, SymbolParser};
 
 /// Parsed data for a single file, ready for database insertion
 struct ParsedFileData {
     file_path: String,
-    /// (name, kind, start_line, end_line, parent, complexity)
-    symbols: Vec<(String, String, usize, usize, Option<String>, Option<usize>)>,
+    symbols: Vec<Symbol>,
     /// (caller_symbol, callee_name, callee_qualifier, line)
     calls: Vec<(String, String, Option<String>, usize)>,
     /// imports (for Python files only)
     imports: Vec<Import>,
+    /// Symbols found before the per-file cap, if the cap was reached
+    capped_total: Option<usize>,
+    /// Build constraint that leaves the file out for the target platform
+    excluded: Option<String>,
+    /// File state the data was parsed from
+    mtime: i64,
+    hash: String,
+}
+
+/// File state the call graph was last built from (a `parsed_files` row).
+struct ParsedFile {
+    mtime: i64,
+    hash: String,
+}
+
+/// Outcome of checking one file during an incremental call graph refresh.
+enum CallGraphUpdate {
+    /// Modified time changed but content didn't: only the mtime is recorded.
+    Touched { path: String, mtime: i64 },
+    /// Content changed: the file was re-parsed.
+    Changed(ParsedFileData),
+}
+
+/// Modification time in seconds since the epoch, or 0 if unavailable.
+fn file_mtime(path: &Path) -> i64 {
+    path.metadata()
+        .ok()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Extract a file's symbols, calls, and imports for the call graph.
+/// Go files excluded by build constraints for `go_target` are recorded
+/// without any of them, so `_windows.go` and `_linux.go` variants don't clash.
+fn parse_for_call_graph(
+    parser: &mut SymbolParser,
+    full_path: &Path,
+    file_path: &str,
+    content: &str,
+    go_target: &GoTarget,
+) -> ParsedFileData {
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+    if file_name.ends_with(".go") {
+        if let Some(reason) = go_build_exclusion(file_name, content, go_target) {
+            return ParsedFileData {
+                file_path: file_path.to_string(),
+                symbols: Vec::new(),
+                calls: Vec::new(),
+                imports: Vec::new(),
+                capped_total: None,
+                excluded: Some(reason),
+                mtime: file_mtime(full_path),
+                hash: content_hash(content.as_bytes()),
+            };
+        }
+    }
+
+    let extracted = parser.extract_file(full_path, content);
+    let capped_total = extracted.is_capped().then_some(extracted.total);
+    let symbols = extracted.symbols;
+
+    let mut call_data = Vec::new();
+
+    for sym in &symbols {
+        // Only index calls for functions/methods
+        let kind = sym.kind.as_str();
+        if kind == "function" || kind == "method" {
+            let calls = parser.find_callees_for_symbol(full_path, content, sym);
+            for (callee_name, line, qualifier) in calls {
+                call_data.push((sym.name.clone(), callee_name, qualifier, line));
+            }
+        }
+    }
+
+    // Parse imports using trait-based extraction (works for all supported languages)
+    let imports = parser.parse_imports(full_path, content);
+
+    ParsedFileData {
+        file_path: file_path.to_string(),
+        symbols,
+        calls: call_data,
+        imports,
+        capped_total,
+        excluded: None,
+        mtime: file_mtime(full_path),
+        hash: content_hash(content.as_bytes()),
+    }
+}
+
+/// Insert parsed files into the call graph tables, recording the file state
+/// each was parsed from.
+fn insert_parsed(
+    tx: &rusqlite::Transaction,
+    parsed_data: &[ParsedFileData],
+) -> rusqlite::Result<CallGraphStats> {
+    let mut symbol_count = 0;
+    let mut call_count = 0;
+    let mut import_count = 0;
+    let mut partial_count = 0;
+    let mut excluded_count = 0;
+
+    // Pre-compile statements for batch insertion (much faster than tx.execute per row)
+    let mut sym_stmt = tx.prepare_cached(
+        "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, complexity, cfg, signature) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+    )?;
+    let mut call_stmt = tx.prepare_cached(
+        "INSERT INTO calls (caller_file, caller_symbol, callee_name, callee_qualifier, line) VALUES (?1, ?2, ?3, ?4, ?5)"
+    )?;
+    let mut import_stmt = tx.prepare_cached(
+        "INSERT INTO imports (file, module, name, alias, line) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    let mut partial_stmt =
+        tx.prepare_cached("INSERT INTO partial_files (path, total_symbols) VALUES (?1, ?2)")?;
+    let mut excluded_stmt =
+        tx.prepare_cached("INSERT INTO excluded_files (path, reason) VALUES (?1, ?2)")?;
+    let mut parsed_stmt = tx.prepare_cached(
+        "INSERT OR REPLACE INTO parsed_files (path, mtime, hash) VALUES (?1, ?2, ?3)",
+    )?;
+
+    for data in parsed_data {
+        parsed_stmt.execute(params![data.file_path, data.mtime, data.hash])?;
+
+        if let Some(total) = data.capped_total {
+            partial_stmt.execute(params![data.file_path, total])?;
+            partial_count += 1;
+        }
+
+        if let Some(reason) = &data.excluded {
+            excluded_stmt.execute(params![data.file_path, reason])?;
+            excluded_count += 1;
+        }
+
+        for sym in &data.symbols {
+            sym_stmt.execute(params![
+                data.file_path,
+                sym.name,
+                sym.kind.as_str(),
+                sym.start_line,
+                sym.end_line,
+                sym.parent,
+                sym.complexity,
+                sym.cfg,
+                sym.signature
+            ])?;
+            symbol_count += 1;
+        }
+
+        for (caller_symbol, callee_name, qualifier, line) in &data.calls {
+            call_stmt.execute(params![
+                data.file_path,
+                caller_symbol,
+                callee_name,
+                qualifier,
+                line
+            ])?;
+            call_count += 1;
+        }
+
+        for imp in &data.imports {
+            import_stmt.execute(params![
+                data.file_path,
+                imp.module,
+                imp.name,
+                imp.alias,
+                imp.line
+            ])?;
+            import_count += 1;
+        }
+    }
+
+    Ok(CallGraphStats {
+        symbols: symbol_count,
+        calls: call_count,
+        imports: import_count,
+        partial_files: partial_count,
+        excluded_files: excluded_count,
+    })
 }
 
 // Not yet public - just delete .moss/index.sqlite on schema changes
-const SCHEMA_VERSION: i64 = 5;
+const SCHEMA_VERSION: i64 = 11;
 
 /// Supported source file extensions for call graph indexing
 const SOURCE_EXTENSIONS: &[&str] = &[
-    ".py", ".rs", ".java", ".ts", ".tsx", ".js", ".mjs", ".cjs", ".go", ".json", ".yaml", ".yml",
+    ".py",
+    ".rs",
+    ".java",
+    ".ts",
+    ".tsx",
+    ".js",
+    ".mjs",
+    ".cjs",
+    ".go",
+    ".json",
+    ".yaml",
+    ".yml",
     ".toml",
+    ".md",
+    ".markdown",
+    ".sql",
 ];
 
-/// Check if a file path has a supported source extension
-fn is_source_file(path: &str) -> bool {
-    SOURCE_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
-}
-
 /// Generate SQL WHERE clause for filtering source files
 /// Returns: "path LIKE '%.py' OR path LIKE '%.rs' OR ..."
 fn source_extensions_sql_filter() -> String {
@@ -43,12 +235,27 @@ fn source_extensions_sql_filter() -> String {
         .join(" OR ")
 }
 
+/// Narrow the definitions a call from `file` may refer to: the calling file's
+/// own definition wins, and `self.x()` never leaves the calling file.
+fn retain_reachable_definitions(
+    definitions: &mut Vec<(String, usize)>,
+    file: &str,
+    qualifier: Option<&str>,
+) {
+    let local = definitions.iter().any(|(f, _)| f == file);
+    if local || qualifier == Some("self") {
+        definitions.retain(|(f, _)| f == file);
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct IndexedFile {
     pub path: String,
     pub is_dir: bool,
     pub mtime: i64,
     pub lines: usize,
+    /// Null bytes in the first 8KB, checked once when the file is indexed.
+    pub is_binary: bool,
 }
 
 /// Result from symbol search
@@ -60,6 +267,22 @@ pub struct SymbolMatch {
     pub start_line: usize,
     pub end_line: usize,
     pub parent: Option<String>,
+    /// Rust `#[cfg(...)]` predicate guarding the item, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<String>,
+}
+
+/// A symbol table row with its signature, for project-wide symbol maps
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SymbolEntry {
+    pub name: String,
+    pub kind: String,
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    pub signature: String,
 }
 
 /// Files that changed since last index
@@ -76,11 +299,200 @@ pub struct CallGraphStats {
     pub symbols: usize,
     pub calls: usize,
     pub imports: usize,
+    /// Files that hit the per-file symbol cap
+    pub partial_files: usize,
+    /// Files left out by build constraints
+    pub excluded_files: usize,
+}
+
+/// A file whose symbols were only partially indexed.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PartialFile {
+    pub path: String,
+    /// Symbols in the file (only the first `symbols.max_per_file` are indexed)
+    pub total_symbols: usize,
+}
+
+/// A file left out of the call graph because it isn't built for the target platform.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ExcludedFile {
+    pub path: String,
+    /// The constraint that excludes it, e.g. `//go:build windows`
+    pub reason: String,
+}
+
+/// A call made by a symbol, paired with a definition the callee may refer to.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CalleeMatch {
+    /// File containing the call
+    pub file: String,
+    /// Line of the call
+    pub line: usize,
+    /// Name being called
+    pub callee: String,
+    /// Where the callee is defined; None if not indexed (external or builtin)
+    pub definition_file: Option<String>,
+    pub definition_line: Option<usize>,
+    /// Resolved by name only and several definitions match; each is listed
+    pub ambiguous: bool,
+}
+
+/// A call from one indexed symbol to another.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize)]
+pub struct CallGraphEdge {
+    pub caller_file: String,
+    pub caller: String,
+    pub callee_file: String,
+    pub callee: String,
+}
+
+/// An import from one indexed file that resolves to another.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FileImport {
+    pub file: String,
+    pub target: String,
+}
+
+/// An import statement's entry for one name.
+#[derive(Debug, Clone)]
+pub struct NameImport {
+    pub file: String,
+    /// None for `import name`
+    pub module: Option<String>,
+    pub name: String,
+    pub alias: Option<String>,
+    pub line: usize,
+}
+
+/// A call site, by callee name.
+#[derive(Debug, Clone)]
+pub struct NamedCall {
+    pub file: String,
+    pub line: usize,
+    /// What the call went through (`self`, a module), if anything
+    pub qualifier: Option<String>,
+}
+
+/// The distinct names a symbol calls, resolved or not.
+#[derive(Debug, Clone)]
+pub struct CallProfile {
+    pub file: String,
+    pub symbol: String,
+    pub callees: HashSet<String>,
+}
+
+/// Which way to follow call edges when building a call tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallDirection {
+    Callers,
+    Callees,
+}
+
+/// Which callers to report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallerScope {
+    /// Callers in every indexed file.
+    All,
+    /// Leave out callers in files matched by `[index] call_graph_exclude`.
+    SkipExcluded,
+}
+
+/// A symbol in a transitive call tree.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CallTreeNode {
+    pub symbol: String,
+    /// Call site linking this node to its parent; None for the root
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    /// Already on the path from the root (recursion), so not expanded
+    pub cycle: bool,
+    pub children: Vec<CallTreeNode>,
+}
+
+/// An indexed source file whose content no longer matches what was parsed.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ChangedSource {
+    pub path: String,
+    /// The file no longer exists
+    pub deleted: bool,
 }
 
 pub struct FileIndex {
     conn: Connection,
     root: PathBuf,
+    walk: WalkConfig,
+}
+
+/// Column values of a `files` row.
+struct FileStat {
+    is_dir: bool,
+    mtime: i64,
+    lines: usize,
+    is_binary: bool,
+}
+
+impl FileStat {
+    fn read(path: &Path) -> Self {
+        let metadata = path.metadata().ok();
+        let is_dir = metadata.as_ref().is_some_and(|m| m.is_dir());
+        let mtime = metadata
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        let is_binary = !is_dir && is_binary_file(path);
+        // Count lines for text files under 1MB (skip binary/large files)
+        let lines = if is_dir || is_binary {
+            0
+        } else {
+            metadata
+                .filter(|m| m.len() < 1_000_000)
+                .and_then(|_| std::fs::read_to_string(path).ok())
+                .map(|s| s.lines().count())
+                .unwrap_or(0)
+        };
+        Self {
+            is_dir,
+            mtime,
+            lines,
+            is_binary,
+        }
+    }
+}
+
+/// Check if a file is binary by looking for null bytes
+fn is_binary_file(path: &Path) -> bool {
+    use std::io::Read;
+
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+
+    let mut buffer = Vec::with_capacity(8192);
+    if file.take(8192).read_to_end(&mut buffer).is_err() {
+        return false;
+    }
+
+    buffer.contains(&0)
+}
+
+/// Build the `[index] call_graph_exclude` matcher. Invalid patterns are
+/// reported and ignored rather than failing the refresh.
+fn call_graph_exclusion(root: &Path, config: &MossConfig) -> Option<Filter> {
+    let patterns = config.index.call_graph_exclude();
+    if patterns.is_empty() {
+        return None;
+    }
+    let languages = crate::commands::filter::detect_project_languages(root);
+    let lang_refs: Vec<&str> = languages.iter().map(|s| s.as_str()).collect();
+    match Filter::new(patterns, &[], &config.filter, &lang_refs) {
+        Ok(filter) => Some(filter),
+        Err(e) => {
+            eprintln!("warning: ignoring [index] call_graph_exclude: {}", e);
+            None
+        }
+    }
 }
 
 impl FileIndex {
@@ -148,7 +560,8 @@ impl FileIndex {
                 path TEXT PRIMARY KEY,
                 is_dir INTEGER NOT NULL,
                 mtime INTEGER NOT NULL,
-                lines INTEGER NOT NULL DEFAULT 0
+                lines INTEGER NOT NULL DEFAULT 0,
+                is_binary INTEGER NOT NULL DEFAULT 0
             );
             CREATE INDEX IF NOT EXISTS idx_files_name ON files(path);
 
@@ -173,11 +586,37 @@ impl FileIndex {
                 start_line INTEGER NOT NULL,
                 end_line INTEGER NOT NULL,
                 parent TEXT,
-                complexity INTEGER
+                complexity INTEGER,
+                cfg TEXT,
+                signature TEXT
             );
             CREATE INDEX IF NOT EXISTS idx_symbols_name ON symbols(name);
             CREATE INDEX IF NOT EXISTS idx_symbols_file ON symbols(file);
 
+            -- Files whose symbols were cut off at symbols.max_per_file
+            CREATE TABLE IF NOT EXISTS partial_files (
+                path TEXT PRIMARY KEY,
+                total_symbols INTEGER NOT NULL
+            );
+
+            -- Files whose build constraints don't match the target platform
+            CREATE TABLE IF NOT EXISTS excluded_files (
+                path TEXT PRIMARY KEY,
+                reason TEXT NOT NULL
+            );
+
+            -- Files matched by [index] call_graph_exclude, hidden from caller results
+            CREATE TABLE IF NOT EXISTS excluded_callers (
+                path TEXT PRIMARY KEY
+            );
+
+            -- Source file state the call graph was built from
+            CREATE TABLE IF NOT EXISTS parsed_files (
+                path TEXT PRIMARY KEY,
+                mtime INTEGER NOT NULL,
+                hash TEXT NOT NULL
+            );
+
             -- Import tracking for cross-file resolution
             -- module = source module (e.g. 'pathlib', 'moss.gen.serialize')
             -- name = imported name (e.g. 'Path', 'emit_tool_definition', or '*' for wildcard)
@@ -226,6 +665,22 @@ impl FileIndex {
             conn.execute("DELETE FROM files", [])?;
             conn.execute("DELETE FROM calls", []).ok();
             conn.execute("DELETE FROM symbols", []).ok();
+            // Added in schema 9; fails harmlessly on tables created with it
+            conn.execute("ALTER TABLE symbols ADD COLUMN cfg TEXT", [])
+                .ok();
+            // Added in schema 11
+            conn.execute("ALTER TABLE symbols ADD COLUMN signature TEXT", [])
+                .ok();
+            // Added in schema 10; refilled by the next refresh
+            conn.execute(
+                "ALTER TABLE files ADD COLUMN is_binary INTEGER NOT NULL DEFAULT 0",
+                [],
+            )
+            .ok();
+            conn.execute("DELETE FROM partial_files", []).ok();
+            conn.execute("DELETE FROM excluded_files", []).ok();
+            conn.execute("DELETE FROM excluded_callers", []).ok();
+            conn.execute("DELETE FROM parsed_files", []).ok();
             conn.execute("DELETE FROM imports", []).ok();
             conn.execute("DELETE FROM cross_refs", []).ok();
             conn.execute(
@@ -234,12 +689,46 @@ impl FileIndex {
             )?;
         }
 
+        let mut walk = MossConfig::load(root).walk;
+        if Self::follows_symlinks(&conn) {
+            walk.follow_symlinks = Some(true);
+        }
+
         Ok(Self {
             conn,
             root: root.to_path_buf(),
+            walk,
         })
     }
 
+    /// Whether `moss index rebuild --follow-symlinks` built this index.
+    fn follows_symlinks(conn: &Connection) -> bool {
+        conn.query_row(
+            "SELECT value FROM meta WHERE key = 'follow_symlinks'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .is_ok_and(|value| value == "1")
+    }
+
+    /// Follow symlinked directories in this index's walks, in addition to
+    /// `[walk] follow_symlinks`. The choice is stored with the index, so
+    /// later refreshes keep the linked files instead of dropping them.
+    pub fn set_follow_symlinks(&mut self, follow: bool) -> rusqlite::Result<()> {
+        if follow {
+            self.conn.execute(
+                "INSERT OR REPLACE INTO meta (key, value) VALUES ('follow_symlinks', '1')",
+                [],
+            )?;
+            self.walk.follow_symlinks = Some(true);
+        } else {
+            self.conn
+                .execute("DELETE FROM meta WHERE key = 'follow_symlinks'", [])?;
+            self.walk.follow_symlinks = MossConfig::load(&self.root).walk.follow_symlinks;
+        }
+        Ok(())
+    }
+
     /// Get a reference to the underlying SQLite connection for direct queries
     pub fn connection(&self) -> &Connection {
         &self.conn
@@ -264,12 +753,7 @@ impl FileIndex {
         }
 
         // Walk current filesystem
-        let walker = WalkBuilder::new(&self.root)
-            .hidden(false)
-            .git_ignore(true)
-            .git_global(true)
-            .git_exclude(true)
-            .build();
+        let walker = project_walker(&self.root, &self.walk).build();
 
         let mut seen = std::collections::HashSet::new();
         for entry in walker.flatten() {
@@ -279,8 +763,7 @@ impl FileIndex {
             }
             if let Ok(rel) = path.strip_prefix(&self.root) {
                 let rel_str = rel.to_string_lossy().to_string();
-                // Skip internal directories
-                if rel_str.is_empty() || rel_str == ".git" || rel_str.starts_with(".git/") {
+                if rel_str.is_empty() {
                     continue;
                 }
                 seen.insert(rel_str.clone());
@@ -415,31 +898,16 @@ impl FileIndex {
 
         // Update/insert changed files
         for path in changed.added.iter().chain(changed.modified.iter()) {
-            let full_path = self.root.join(path);
-            let is_dir = full_path.is_dir();
-            let mtime = full_path
-                .metadata()
-                .ok()
-                .and_then(|m| m.modified().ok())
-                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
-                .map(|d| d.as_secs() as i64)
-                .unwrap_or(0);
-            // Count lines for text files under 1MB (skip binary/large files)
-            let lines = if is_dir {
-                0
-            } else {
-                full_path
-                    .metadata()
-                    .ok()
-                    .filter(|m| m.len() < 1_000_000)
-                    .and_then(|_| std::fs::read_to_string(&full_path).ok())
-                    .map(|s| s.lines().count())
-                    .unwrap_or(0)
-            };
-
+            let stat = FileStat::read(&self.root.join(path));
             tx.execute(
-                "INSERT OR REPLACE INTO files (path, is_dir, mtime, lines) VALUES (?1, ?2, ?3, ?4)",
-                params![path, is_dir as i64, mtime, lines as i64],
+                "INSERT OR REPLACE INTO files (path, is_dir, mtime, lines, is_binary) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    path,
+                    stat.is_dir as i64,
+                    stat.mtime,
+                    stat.lines as i64,
+                    stat.is_binary as i64
+                ],
             )?;
         }
 
@@ -459,12 +927,7 @@ impl FileIndex {
 
     /// Refresh the index by walking the filesystem
     pub fn refresh(&mut self) -> rusqlite::Result<usize> {
-        let walker = WalkBuilder::new(&self.root)
-            .hidden(false)
-            .git_ignore(true)
-            .git_global(true)
-            .git_exclude(true)
-            .build();
+        let walker = project_walker(&self.root, &self.walk).build();
 
         // Start transaction for batch insert
         let tx = self.conn.transaction()?;
@@ -475,34 +938,20 @@ impl FileIndex {
             let path = entry.path();
             if let Ok(rel) = path.strip_prefix(&self.root) {
                 let rel_str = rel.to_string_lossy().to_string();
-                // Skip internal directories
-                if rel_str.is_empty() || rel_str == ".git" || rel_str.starts_with(".git/") {
+                if rel_str.is_empty() {
                     continue;
                 }
 
-                let is_dir = path.is_dir();
-                let mtime = path
-                    .metadata()
-                    .ok()
-                    .and_then(|m| m.modified().ok())
-                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
-                    .map(|d| d.as_secs() as i64)
-                    .unwrap_or(0);
-                // Count lines for text files under 1MB (skip binary/large files)
-                let lines = if is_dir {
-                    0
-                } else {
-                    path.metadata()
-                        .ok()
-                        .filter(|m| m.len() < 1_000_000)
-                        .and_then(|_| std::fs::read_to_string(path).ok())
-                        .map(|s| s.lines().count())
-                        .unwrap_or(0)
-                };
-
+                let stat = FileStat::read(path);
                 tx.execute(
-                    "INSERT INTO files (path, is_dir, mtime, lines) VALUES (?1, ?2, ?3, ?4)",
-                    params![rel_str, is_dir as i64, mtime, lines as i64],
+                    "INSERT INTO files (path, is_dir, mtime, lines, is_binary) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                    rel_str,
+                    stat.is_dir as i64,
+                    stat.mtime,
+                    stat.lines as i64,
+                    stat.is_binary as i64
+                ],
                 )?;
                 count += 1;
             }
@@ -522,11 +971,111 @@ impl FileIndex {
         Ok(count)
     }
 
+    /// Update the rows for specific paths, as reported by a file watcher.
+    ///
+    /// Existing paths are upserted if the index walker would visit them
+    /// (directories together with everything under them); missing paths
+    /// are removed along with anything under them. Ignored paths are left
+    /// alone. Symbols and calls are not touched: follow up with
+    /// `incremental_call_graph_refresh`.
+    pub fn update_paths(&mut self, paths: &[PathBuf]) -> rusqlite::Result<ChangedFiles> {
+        let mut changed = ChangedFiles::default();
+        let mut present = Vec::new();
+        let mut missing = Vec::new();
+        for path in paths {
+            let Ok(rel) = path.strip_prefix(&self.root) else {
+                continue;
+            };
+            let rel_str = rel.to_string_lossy().to_string();
+            if rel_str.is_empty() || rel_str == ".git" || rel_str.starts_with(".git/") {
+                continue;
+            }
+            if path.exists() {
+                present.push(path.clone());
+            } else {
+                missing.push(rel_str);
+            }
+        }
+
+        // List each parent directory once to see which paths the walker keeps
+        let parents: HashSet<&Path> = present.iter().filter_map(|p| p.parent()).collect();
+        let mut visible: HashSet<PathBuf> = HashSet::new();
+        for parent in parents {
+            for entry in project_walker(parent, &self.walk)
+                .max_depth(Some(1))
+                .build()
+                .flatten()
+            {
+                visible.insert(entry.into_path());
+            }
+        }
+        let mut upserts: Vec<PathBuf> = Vec::new();
+        for path in present.iter().filter(|p| visible.contains(*p)) {
+            if path.is_dir() {
+                upserts.extend(
+                    project_walker(path, &self.walk)
+                        .build()
+                        .flatten()
+                        .map(|e| e.into_path()),
+                );
+            } else {
+                upserts.push(path.clone());
+            }
+        }
+        // A new directory and files inside it can arrive in the same batch
+        upserts.sort();
+        upserts.dedup();
+
+        let tx = self.conn.transaction()?;
+        for rel in missing {
+            let removed = tx.execute(
+                "DELETE FROM files WHERE path = ?1 OR path LIKE ?2",
+                params![rel, format!("{}/%", rel)],
+            )?;
+            if removed > 0 {
+                changed.deleted.push(rel);
+            }
+        }
+        for path in upserts {
+            let Ok(rel) = path.strip_prefix(&self.root) else {
+                continue;
+            };
+            let rel_str = rel.to_string_lossy().to_string();
+            let stat = FileStat::read(&path);
+            let previous: Option<i64> = tx
+                .query_row(
+                    "SELECT mtime FROM files WHERE path = ?1",
+                    params![rel_str],
+                    |row| row.get(0),
+                )
+                .ok();
+            match previous {
+                None => changed.added.push(rel_str.clone()),
+                Some(_) if stat.is_dir => continue,
+                Some(_) => changed.modified.push(rel_str.clone()),
+            }
+            tx.execute(
+                "INSERT OR REPLACE INTO files (path, is_dir, mtime, lines, is_binary) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    rel_str,
+                    stat.is_dir as i64,
+                    stat.mtime,
+                    stat.lines as i64,
+                    stat.is_binary as i64
+                ],
+            )?;
+        }
+        tx.commit()?;
+
+        changed.deleted.sort();
+        Ok(changed)
+    }
+
     /// Get all files from the index
     pub fn all_files(&self) -> rusqlite::Result<Vec<IndexedFile>> {
         let mut stmt = self
             .conn
-            .prepare("SELECT path, is_dir, mtime, lines FROM files")?;
+            .prepare("SELECT path, is_dir, mtime, lines, is_binary FROM files")?;
         let files = stmt
             .query_map([], |row| {
                 Ok(IndexedFile {
@@ -534,6 +1083,7 @@ impl FileIndex {
                     is_dir: row.get::<_, i64>(1)? != 0,
                     mtime: row.get(2)?,
                     lines: row.get::<_, i64>(3)? as usize,
+                    is_binary: row.get::<_, i64>(4)? != 0,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -544,7 +1094,7 @@ impl FileIndex {
     pub fn find_by_name(&self, name: &str) -> rusqlite::Result<Vec<IndexedFile>> {
         let pattern = format!("%/{}", name);
         let mut stmt = self.conn.prepare(
-            "SELECT path, is_dir, mtime, lines FROM files WHERE path LIKE ?1 OR path = ?2",
+            "SELECT path, is_dir, mtime, lines, is_binary FROM files WHERE path LIKE ?1 OR path = ?2",
         )?;
         let files = stmt
             .query_map(params![pattern, name], |row| {
@@ -553,6 +1103,7 @@ impl FileIndex {
                     is_dir: row.get::<_, i64>(1)? != 0,
                     mtime: row.get(2)?,
                     lines: row.get::<_, i64>(3)? as usize,
+                    is_binary: row.get::<_, i64>(4)? != 0,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -562,9 +1113,9 @@ impl FileIndex {
     /// Search files by stem (filename without extension)
     pub fn find_by_stem(&self, stem: &str) -> rusqlite::Result<Vec<IndexedFile>> {
         let pattern = format!("%/{}%", stem);
-        let mut stmt = self
-            .conn
-            .prepare("SELECT path, is_dir, mtime, lines FROM files WHERE path LIKE ?1")?;
+        let mut stmt = self.conn.prepare(
+            "SELECT path, is_dir, mtime, lines, is_binary FROM files WHERE path LIKE ?1",
+        )?;
         let files = stmt
             .query_map(params![pattern], |row| {
                 Ok(IndexedFile {
@@ -572,6 +1123,7 @@ impl FileIndex {
                     is_dir: row.get::<_, i64>(1)? != 0,
                     mtime: row.get(2)?,
                     lines: row.get::<_, i64>(3)? as usize,
+                    is_binary: row.get::<_, i64>(4)? != 0,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -595,8 +1147,8 @@ impl FileIndex {
         // Insert symbols
         for sym in symbols {
             self.conn.execute(
-                "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, complexity) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                params![path, sym.name, sym.kind.as_str(), sym.start_line, sym.end_line, sym.parent, sym.complexity],
+                "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, complexity, cfg, signature) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![path, sym.name, sym.kind.as_str(), sym.start_line, sym.end_line, sym.parent, sym.complexity, sym.cfg, sym.signature],
             )?;
         }
 
@@ -615,9 +1167,70 @@ impl FileIndex {
     /// Resolves through imports: if file A imports X as Y and calls Y(), finds that as a caller of X
     /// Also handles qualified calls: if file A does `import foo` and calls `foo.bar()`, finds caller of `bar`
     /// Also handles method calls: `self.method()` is resolved to the containing class's method
+    ///
+    /// With `CallerScope::SkipExcluded`, callers in excluded files are dropped
+    /// after matching, so a symbol only called from excluded files has no
+    /// callers rather than falling through to the fuzzy matches.
     pub fn find_callers(
         &self,
         symbol_name: &str,
+        scope: CallerScope,
+    ) -> rusqlite::Result<Vec<(String, String, usize)>> {
+        let mut callers = self.find_any_callers(symbol_name)?;
+        if scope == CallerScope::SkipExcluded {
+            let excluded = self.excluded_callers()?;
+            callers.retain(|(file, _, _)| !excluded.contains(file));
+        }
+        Ok(callers)
+    }
+
+    fn find_any_callers(
+        &self,
+        symbol_name: &str,
+    ) -> rusqlite::Result<Vec<(String, String, usize)>> {
+        let callers = self.find_exact_callers(symbol_name)?;
+        if !callers.is_empty() {
+            return Ok(callers);
+        }
+        let method_name = symbol_name
+            .split_once('.')
+            .map_or(symbol_name, |(_, method)| method);
+
+        // Try case-insensitive match (direct only for simplicity)
+        let mut stmt = self.conn.prepare(
+            "SELECT caller_file, caller_symbol, line FROM calls WHERE LOWER(callee_name) = LOWER(?1)"
+        )?;
+        let callers: Vec<(String, String, usize)> = stmt
+            .query_map(params![method_name], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        if !callers.is_empty() {
+            return Ok(callers);
+        }
+
+        // Try LIKE pattern match (contains)
+        let pattern = format!("%{}%", method_name);
+        let mut stmt = self.conn.prepare(
+            "SELECT caller_file, caller_symbol, line FROM calls WHERE LOWER(callee_name) LIKE LOWER(?1) LIMIT 100"
+        )?;
+        let callers = stmt
+            .query_map(params![pattern], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(callers)
+    }
+
+    /// Callers that call the symbol by its exact name, directly or through
+    /// an import, without `find_callers`' fuzzy fallbacks.
+    fn find_exact_callers(
+        &self,
+        symbol_name: &str,
     ) -> rusqlite::Result<Vec<(String, String, usize)>> {
         // Handle Class.method format - split and search for method within class
         let (class_filter, method_name) = if symbol_name.contains('.') {
@@ -670,45 +1283,98 @@ impl FileIndex {
              JOIN symbols s ON c.caller_file = s.file AND c.caller_symbol = s.name
              WHERE c.callee_name = ?1 AND c.callee_qualifier = 'self' AND s.parent IS NOT NULL"
         )?;
-        let callers: Vec<(String, String, usize)> = stmt
+        let callers = stmt
             .query_map(params![method_name], |row| {
                 Ok((row.get(0)?, row.get(1)?, row.get(2)?))
             })?
             .filter_map(|r| r.ok())
             .collect();
 
-        if !callers.is_empty() {
-            return Ok(callers);
-        }
+        Ok(callers)
+    }
 
-        // Try case-insensitive match (direct only for simplicity)
-        let mut stmt = self.conn.prepare(
-            "SELECT caller_file, caller_symbol, line FROM calls WHERE LOWER(callee_name) = LOWER(?1)"
-        )?;
-        let callers: Vec<(String, String, usize)> = stmt
-            .query_map(params![method_name], |row| {
-                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
+    /// Transitive callers or callees of a symbol, up to `max_depth` levels
+    /// below it. Only exact name matches are followed, and callees are only
+    /// expanded when they're defined in the index. A symbol that's already on
+    /// the path from the root is marked as a cycle instead of expanded.
+    pub fn call_tree(
+        &self,
+        symbol_name: &str,
+        max_depth: usize,
+        direction: CallDirection,
+        scope: CallerScope,
+    ) -> rusqlite::Result<CallTreeNode> {
+        let excluded = match scope {
+            CallerScope::All => HashSet::new(),
+            CallerScope::SkipExcluded => self.excluded_callers()?,
+        };
+        let mut path = Vec::new();
+        self.call_tree_node(
+            symbol_name,
+            None,
+            max_depth,
+            direction,
+            &excluded,
+            &mut path,
+        )
+    }
 
-        if !callers.is_empty() {
-            return Ok(callers);
+    fn call_tree_node(
+        &self,
+        symbol_name: &str,
+        call_site: Option<(String, usize)>,
+        depth: usize,
+        direction: CallDirection,
+        excluded: &HashSet<String>,
+        path: &mut Vec<String>,
+    ) -> rusqlite::Result<CallTreeNode> {
+        let (file, line) = call_site.unzip();
+        let mut node = CallTreeNode {
+            symbol: symbol_name.to_string(),
+            file,
+            line,
+            cycle: path.iter().any(|s| s == symbol_name),
+            children: Vec::new(),
+        };
+        if node.cycle || depth == 0 {
+            return Ok(node);
         }
 
-        // Try LIKE pattern match (contains)
-        let pattern = format!("%{}%", method_name);
-        let mut stmt = self.conn.prepare(
-            "SELECT caller_file, caller_symbol, line FROM calls WHERE LOWER(callee_name) LIKE LOWER(?1) LIMIT 100"
-        )?;
-        let callers = stmt
-            .query_map(params![pattern], |row| {
-                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(callers)
+        // One child per symbol and file, at its first call site
+        let mut edges: Vec<(String, String, usize, bool)> = match direction {
+            CallDirection::Callers => self
+                .find_exact_callers(symbol_name)?
+                .into_iter()
+                .filter(|(file, _, _)| !excluded.contains(file))
+                .map(|(file, caller, line)| (caller, file, line, true))
+                .collect(),
+            CallDirection::Callees => self
+                .find_callees_of(symbol_name)?
+                .into_iter()
+                .map(|c| {
+                    let defined = c.definition_file.is_some();
+                    (c.callee, c.file, c.line, defined)
+                })
+                .collect(),
+        };
+        edges.sort_by(|a, b| (&a.1, a.2, &a.0).cmp(&(&b.1, b.2, &b.0)));
+        let mut seen = HashSet::new();
+        edges.retain(|(symbol, file, _, _)| seen.insert((symbol.clone(), file.clone())));
+
+        path.push(symbol_name.to_string());
+        for (symbol, file, line, expand) in edges {
+            let child_depth = if expand { depth - 1 } else { 0 };
+            node.children.push(self.call_tree_node(
+                &symbol,
+                Some((file, line)),
+                child_depth,
+                direction,
+                excluded,
+                path,
+            )?);
+        }
+        path.pop();
+        Ok(node)
     }
 
     /// Find callees of a symbol (what it calls)
@@ -728,6 +1394,147 @@ impl FileIndex {
         Ok(callees)
     }
 
+    /// Find what a symbol calls, across every file defining it, with the
+    /// definitions of each callee. `Class.method` restricts to that class.
+    ///
+    /// Calls are resolved by name: definitions in the calling file win, and
+    /// `self.x()` only matches the calling file. Otherwise every same-named
+    /// definition is returned, flagged as ambiguous.
+    pub fn find_callees_of(&self, symbol_name: &str) -> rusqlite::Result<Vec<CalleeMatch>> {
+        let (class_filter, method_name) = match symbol_name.split_once('.') {
+            Some((class, method)) => (Some(class), method),
+            None => (None, symbol_name),
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT caller_file, line, callee_name, callee_qualifier FROM calls
+             WHERE caller_symbol = ?1
+               AND (?2 IS NULL OR caller_file IN
+                    (SELECT file FROM symbols WHERE name = ?1 AND parent = ?2))
+             ORDER BY caller_file, line, callee_name",
+        )?;
+        let calls: Vec<(String, usize, String, Option<String>)> = stmt
+            .query_map(params![method_name, class_filter], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut def_stmt = self.conn.prepare(
+            "SELECT file, start_line FROM symbols WHERE name = ?1 ORDER BY file, start_line",
+        )?;
+        let mut callees = Vec::new();
+        for (file, line, callee, qualifier) in calls {
+            let mut definitions: Vec<(String, usize)> = def_stmt
+                .query_map(params![callee], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            retain_reachable_definitions(&mut definitions, &file, qualifier.as_deref());
+
+            if definitions.is_empty() {
+                callees.push(CalleeMatch {
+                    file,
+                    line,
+                    callee,
+                    definition_file: None,
+                    definition_line: None,
+                    ambiguous: false,
+                });
+                continue;
+            }
+            let ambiguous = definitions.len() > 1;
+            for (def_file, def_line) in definitions {
+                callees.push(CalleeMatch {
+                    file: file.clone(),
+                    line,
+                    callee: callee.clone(),
+                    definition_file: Some(def_file),
+                    definition_line: Some(def_line),
+                    ambiguous,
+                });
+            }
+        }
+        Ok(callees)
+    }
+
+    /// Every call between indexed symbols, resolved like [`Self::find_callees_of`].
+    /// Calls to names with no indexed definition are left out. Sorted and
+    /// deduplicated, so each caller/callee pair appears once.
+    pub fn call_edges(&self) -> rusqlite::Result<Vec<CallGraphEdge>> {
+        let mut definitions: HashMap<String, Vec<(String, usize)>> = HashMap::new();
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name, file, start_line FROM symbols")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, usize>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (name, file, line) = row?;
+            definitions.entry(name).or_default().push((file, line));
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT caller_file, caller_symbol, callee_name, callee_qualifier FROM calls",
+        )?;
+        let calls: Vec<(String, String, String, Option<String>)> = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut edges = Vec::new();
+        for (caller_file, caller, callee, qualifier) in calls {
+            let Some(candidates) = definitions.get(&callee) else {
+                continue;
+            };
+            let mut candidates = candidates.clone();
+            retain_reachable_definitions(&mut candidates, &caller_file, qualifier.as_deref());
+            for (callee_file, _) in candidates {
+                edges.push(CallGraphEdge {
+                    caller_file: caller_file.clone(),
+                    caller: caller.clone(),
+                    callee_file,
+                    callee: callee.clone(),
+                });
+            }
+        }
+        edges.sort();
+        edges.dedup();
+        Ok(edges)
+    }
+
+    /// What each calling symbol calls, by name, in caller order.
+    pub fn call_profiles(&self) -> rusqlite::Result<Vec<CallProfile>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT caller_file, caller_symbol, callee_name FROM calls
+             ORDER BY caller_file, caller_symbol",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut profiles: Vec<CallProfile> = Vec::new();
+        for row in rows {
+            let (file, symbol, callee) = row?;
+            match profiles.last_mut() {
+                Some(p) if p.file == file && p.symbol == symbol => {
+                    p.callees.insert(callee);
+                }
+                _ => profiles.push(CallProfile {
+                    file,
+                    symbol,
+                    callees: HashSet::from([callee]),
+                }),
+            }
+        }
+        Ok(profiles)
+    }
+
     /// Find callees with resolved import info (name, line, source_module)
     /// Returns: (local_name, line, Option<(source_module, original_name)>)
     pub fn find_callees_resolved(
@@ -759,6 +1566,99 @@ impl FileIndex {
         Ok(symbols)
     }
 
+    /// Get every indexed symbol, ordered by file and line.
+    pub fn all_symbols(&self) -> rusqlite::Result<Vec<SymbolMatch>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, kind, file, start_line, end_line, parent, cfg FROM symbols
+             ORDER BY file, start_line, name",
+        )?;
+        let symbols = stmt
+            .query_map([], |row| {
+                Ok(SymbolMatch {
+                    name: row.get(0)?,
+                    kind: row.get(1)?,
+                    file: row.get(2)?,
+                    start_line: row.get(3)?,
+                    end_line: row.get(4)?,
+                    parent: row.get(5)?,
+                    cfg: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(symbols)
+    }
+
+    /// Visit every indexed symbol (of one of `kinds`, if any are given) in
+    /// file and line order, one row at a time, so the whole table is never
+    /// held in memory. Stops early when `visit` returns false.
+    pub fn for_each_symbol(
+        &self,
+        kinds: &[String],
+        mut visit: impl FnMut(SymbolEntry) -> bool,
+    ) -> rusqlite::Result<()> {
+        let placeholders = vec!["?"; kinds.len()].join(", ");
+        let filter = if kinds.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE kind IN ({})", placeholders)
+        };
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT name, kind, file, start_line, end_line, parent, signature FROM symbols
+             {} ORDER BY file, start_line, name",
+            filter
+        ))?;
+        let mut rows = stmt.query(rusqlite::params_from_iter(kinds))?;
+        while let Some(row) = rows.next()? {
+            let entry = SymbolEntry {
+                name: row.get(0)?,
+                kind: row.get(1)?,
+                file: row.get(2)?,
+                start_line: row.get(3)?,
+                end_line: row.get(4)?,
+                parent: row.get(5)?,
+                signature: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+            };
+            if !visit(entry) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the symbols defined in one file, ordered by line.
+    pub fn file_symbols(&self, file: &str) -> rusqlite::Result<Vec<SymbolMatch>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, kind, file, start_line, end_line, parent, cfg FROM symbols
+             WHERE file = ?1 ORDER BY start_line, name",
+        )?;
+        let symbols = stmt
+            .query_map(params![file], |row| {
+                Ok(SymbolMatch {
+                    name: row.get(0)?,
+                    kind: row.get(1)?,
+                    file: row.get(2)?,
+                    start_line: row.get(3)?,
+                    end_line: row.get(4)?,
+                    parent: row.get(5)?,
+                    cfg: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(symbols)
+    }
+
+    /// Names that are called or imported anywhere. A definition whose name
+    /// isn't in this set has no recorded callers.
+    pub fn referenced_names(&self) -> rusqlite::Result<HashSet<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT callee_name FROM calls UNION SELECT name FROM imports")?;
+        let names = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(names)
+    }
+
     /// Get all distinct symbol names as a HashSet.
     pub fn all_symbol_names(&self) -> rusqlite::Result<std::collections::HashSet<String>> {
         let mut stmt = self.conn.prepare("SELECT DISTINCT name FROM symbols")?;
@@ -795,7 +1695,7 @@ impl FileIndex {
         let (sql, params_vec): (String, Vec<Box<dyn rusqlite::ToSql>>) = if fuzzy {
             let pattern = format!("%{}%", query_lower);
             let sql = if kind.is_some() {
-                "SELECT name, kind, file, start_line, end_line, parent FROM symbols
+                "SELECT name, kind, file, start_line, end_line, parent, cfg FROM symbols
                  WHERE LOWER(name) LIKE ?1 AND kind = ?2
                  ORDER BY
                    CASE WHEN LOWER(name) = ?3 THEN 0
@@ -805,7 +1705,7 @@ impl FileIndex {
                  LIMIT ?5"
                     .to_string()
             } else {
-                "SELECT name, kind, file, start_line, end_line, parent FROM symbols
+                "SELECT name, kind, file, start_line, end_line, parent, cfg FROM symbols
                  WHERE LOWER(name) LIKE ?1
                  ORDER BY
                    CASE WHEN LOWER(name) = ?2 THEN 0
@@ -843,12 +1743,12 @@ impl FileIndex {
         } else {
             // Exact match
             let sql = if kind.is_some() {
-                "SELECT name, kind, file, start_line, end_line, parent FROM symbols
+                "SELECT name, kind, file, start_line, end_line, parent, cfg FROM symbols
                  WHERE LOWER(name) = LOWER(?1) AND kind = ?2
                  LIMIT ?3"
                     .to_string()
             } else {
-                "SELECT name, kind, file, start_line, end_line, parent FROM symbols
+                "SELECT name, kind, file, start_line, end_line, parent, cfg FROM symbols
                  WHERE LOWER(name) = LOWER(?1)
                  LIMIT ?2"
                     .to_string()
@@ -887,6 +1787,7 @@ impl FileIndex {
                     start_line: row.get(3)?,
                     end_line: row.get(4)?,
                     parent: row.get(5)?,
+                    cfg: row.get(6)?,
                 })
             })?
             .filter_map(|r| r.ok())
@@ -908,6 +1809,16 @@ impl FileIndex {
                 .conn
                 .query_row("SELECT COUNT(*) FROM imports", [], |row| row.get(0))
                 .unwrap_or(0),
+            partial_files: self.conn.query_row(
+                "SELECT COUNT(*) FROM partial_files",
+                [],
+                |row| row.get(0),
+            )?,
+            excluded_files: self.conn.query_row(
+                "SELECT COUNT(*) FROM excluded_files",
+                [],
+                |row| row.get(0),
+            )?,
         })
     }
 
@@ -936,6 +1847,31 @@ impl FileIndex {
             .collect()
     }
 
+    /// Resolve an import in `source_file` to an indexed file with the
+    /// language's local-import resolver.
+    fn resolve_local_file(&self, module: &str, source_file: &str) -> Option<String> {
+        let lang = support_for_path(Path::new(source_file))?;
+        let path = lang.resolve_local_import(module, &self.root.join(source_file), &self.root)?;
+        // Resolvers join `../` onto the importing directory as-is
+        let mut clean = PathBuf::new();
+        for component in path.components() {
+            match component {
+                std::path::Component::ParentDir => {
+                    clean.pop();
+                }
+                std::path::Component::CurDir => {}
+                other => clean.push(other),
+            }
+        }
+        let rel = clean.strip_prefix(&self.root).ok()?.to_str()?.to_string();
+        self.conn
+            .query_row("SELECT 1 FROM files WHERE path = ?1", params![rel], |_| {
+                Ok(())
+            })
+            .ok()?;
+        Some(rel)
+    }
+
     /// Check if a file exports (defines) a given symbol
     fn file_exports_symbol(&self, file: &str, symbol: &str) -> rusqlite::Result<bool> {
         // Check if symbol is defined in this file (top-level only, parent IS NULL)
@@ -999,6 +1935,108 @@ impl FileIndex {
         Ok(None)
     }
 
+    /// Imports between indexed files, with modules resolved to the files
+    /// that define them. External and unresolvable imports are left out.
+    ///
+    /// Each import goes through its language's local-import resolver, so
+    /// relative imports (`from . import x`, `./util`, `crate::`) resolve
+    /// against the importing file. Imports it can't place fall back to the
+    /// module-name mapping.
+    pub fn resolved_imports(&self) -> rusqlite::Result<Vec<FileImport>> {
+        self.import_edges(false)
+    }
+
+    /// Groups of files that import each other, directly or through other
+    /// files, over the graph of [`resolved_imports`](Self::resolved_imports).
+    /// A file that imports itself is a cycle of one. Unresolved imports
+    /// (external packages, missing files) simply aren't edges.
+    pub fn import_cycles(&self) -> rusqlite::Result<Vec<Vec<String>>> {
+        Ok(import_cycles_among(&self.import_edges(true)?))
+    }
+
+    fn import_edges(&self, include_self: bool) -> rusqlite::Result<Vec<FileImport>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT DISTINCT file, COALESCE(module, name) FROM imports")?;
+        let imports: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut resolved = Vec::new();
+        for (file, module) in imports {
+            for target in self.resolve_module(&module, &file) {
+                if include_self || target != file {
+                    resolved.push(FileImport {
+                        file: file.clone(),
+                        target,
+                    });
+                }
+            }
+        }
+        resolved.sort();
+        resolved.dedup();
+        Ok(resolved)
+    }
+
+    /// Indexed files an import of `module` in `source_file` refers to: the
+    /// language's local-import resolution, else the module-name mapping.
+    pub fn resolve_module(&self, module: &str, source_file: &str) -> Vec<String> {
+        match self.resolve_local_file(module, source_file) {
+            Some(target) => vec![target],
+            None => self.module_to_files(module, source_file),
+        }
+    }
+
+    /// Imports of `name`, in any file.
+    pub fn imports_named(&self, name: &str) -> rusqlite::Result<Vec<NameImport>> {
+        self.query_imports(
+            "SELECT file, module, name, alias, line FROM imports WHERE name = ?1",
+            name,
+        )
+    }
+
+    /// Every import in `file`.
+    pub fn imports_in(&self, file: &str) -> rusqlite::Result<Vec<NameImport>> {
+        self.query_imports(
+            "SELECT file, module, name, alias, line FROM imports WHERE file = ?1",
+            file,
+        )
+    }
+
+    fn query_imports(&self, sql: &str, param: &str) -> rusqlite::Result<Vec<NameImport>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let imports = stmt
+            .query_map(params![param], |row| {
+                Ok(NameImport {
+                    file: row.get(0)?,
+                    module: row.get(1)?,
+                    name: row.get(2)?,
+                    alias: row.get(3)?,
+                    line: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(imports)
+    }
+
+    /// Calls to exactly `name`, with the qualifier they were made through.
+    pub fn calls_named(&self, name: &str) -> rusqlite::Result<Vec<NamedCall>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT caller_file, line, callee_qualifier FROM calls WHERE callee_name = ?1
+             ORDER BY caller_file, line",
+        )?;
+        let calls = stmt
+            .query_map(params![name], |row| {
+                Ok(NamedCall {
+                    file: row.get(0)?,
+                    line: row.get(1)?,
+                    qualifier: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(calls)
+    }
+
     /// Find which files import a given module
     pub fn find_importers(&self, module: &str) -> rusqlite::Result<Vec<(String, String, usize)>> {
         let mut stmt = self
@@ -1017,67 +2055,29 @@ impl FileIndex {
     /// This is more expensive than file refresh since it parses every file
     /// Uses parallel processing for parsing, sequential insertion for SQLite
     pub fn refresh_call_graph(&mut self) -> rusqlite::Result<CallGraphStats> {
-        // Get all indexed source files BEFORE starting transaction
-        let files: Vec<String> = {
-            let sql = format!(
-                "SELECT path FROM files WHERE is_dir = 0 AND ({})",
-                source_extensions_sql_filter()
-            );
-            let mut stmt = self.conn.prepare(&sql)?;
-            let mut files = Vec::new();
-            let mut rows = stmt.query([])?;
-            while let Some(row) = rows.next()? {
-                let path: String = row.get(0)?;
-                files.push(path);
-            }
-            files
-        };
+        // Get all indexed files BEFORE starting transaction
+        let files = self.call_graph_source_files()?;
 
         // Parse all files in parallel
         // Each thread gets its own SymbolParser (tree-sitter parsers have mutable state)
         let root = self.root.clone();
+        let config = MossConfig::load(&root);
+        let exclude = call_graph_exclusion(&root, &config);
+        let symbols_config = config.symbols;
+        let go_target = config.index.go_target();
         let parsed_data: Vec<ParsedFileData> = files
             .par_iter()
             .filter_map(|file_path| {
                 let full_path = root.join(file_path);
                 let content = std::fs::read_to_string(&full_path).ok()?;
-
-                // Each thread creates its own parser
-                let mut parser = SymbolParser::new();
-                let symbols = parser.parse_file(&full_path, &content);
-
-                let mut sym_data = Vec::with_capacity(symbols.len());
-                let mut call_data = Vec::new();
-
-                for sym in &symbols {
-                    sym_data.push((
-                        sym.name.clone(),
-                        sym.kind.as_str().to_string(),
-                        sym.start_line,
-                        sym.end_line,
-                        sym.parent.clone(),
-                        sym.complexity,
-                    ));
-
-                    // Only index calls for functions/methods
-                    let kind = sym.kind.as_str();
-                    if kind == "function" || kind == "method" {
-                        let calls = parser.find_callees_for_symbol(&full_path, &content, sym);
-                        for (callee_name, line, qualifier) in calls {
-                            call_data.push((sym.name.clone(), callee_name, qualifier, line));
-                        }
-                    }
-                }
-
-                // Parse imports using trait-based extraction (works for all supported languages)
-                let imports = parser.parse_imports(&full_path, &content);
-
-                Some(ParsedFileData {
-                    file_path: file_path.clone(),
-                    symbols: sym_data,
-                    calls: call_data,
-                    imports,
-                })
+                let mut parser = SymbolParser::from_config(&symbols_config);
+                Some(parse_for_call_graph(
+                    &mut parser,
+                    &full_path,
+                    file_path,
+                    &content,
+                    &go_target,
+                ))
             })
             .collect();
 
@@ -1086,157 +2086,219 @@ impl FileIndex {
         tx.execute("DELETE FROM symbols", [])?;
         tx.execute("DELETE FROM calls", [])?;
         tx.execute("DELETE FROM imports", [])?;
-
-        let mut symbol_count = 0;
-        let mut call_count = 0;
-        let mut import_count = 0;
-
-        // Pre-compile statements for batch insertion (much faster than tx.execute per row)
-        {
-            let mut sym_stmt = tx.prepare_cached(
-                "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, complexity) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"
-            )?;
-            let mut call_stmt = tx.prepare_cached(
-                "INSERT INTO calls (caller_file, caller_symbol, callee_name, callee_qualifier, line) VALUES (?1, ?2, ?3, ?4, ?5)"
-            )?;
-            let mut import_stmt = tx.prepare_cached(
-                "INSERT INTO imports (file, module, name, alias, line) VALUES (?1, ?2, ?3, ?4, ?5)",
-            )?;
-
-            for data in &parsed_data {
-                for (name, kind, start_line, end_line, parent, complexity) in &data.symbols {
-                    sym_stmt.execute(params![
-                        data.file_path,
-                        name,
-                        kind,
-                        start_line,
-                        end_line,
-                        parent,
-                        complexity
-                    ])?;
-                    symbol_count += 1;
-                }
-
-                for (caller_symbol, callee_name, qualifier, line) in &data.calls {
-                    call_stmt.execute(params![
-                        data.file_path,
-                        caller_symbol,
-                        callee_name,
-                        qualifier,
-                        line
-                    ])?;
-                    call_count += 1;
-                }
-
-                for imp in &data.imports {
-                    import_stmt.execute(params![
-                        data.file_path,
-                        imp.module,
-                        imp.name,
-                        imp.alias,
-                        imp.line
-                    ])?;
-                    import_count += 1;
-                }
-            }
-        }
-
+        tx.execute("DELETE FROM partial_files", [])?;
+        tx.execute("DELETE FROM excluded_files", [])?;
+        tx.execute("DELETE FROM parsed_files", [])?;
+        let stats = insert_parsed(&tx, &parsed_data)?;
         tx.commit()?;
-        Ok(CallGraphStats {
-            symbols: symbol_count,
-            calls: call_count,
-            imports: import_count,
-        })
+        self.refresh_excluded_callers(&files, exclude.as_ref())?;
+        Ok(stats)
     }
 
-    /// Incrementally update call graph for changed files only
-    /// Much faster than full refresh when few files changed
+    /// Incrementally update the call graph, re-parsing only source files whose
+    /// content changed since they were last parsed. Files that were merely
+    /// touched keep their rows. Returns counts of what was re-indexed.
     pub fn incremental_call_graph_refresh(&mut self) -> rusqlite::Result<CallGraphStats> {
-        let changed = self.get_changed_files()?;
-
-        // Only process supported source and data files
-        let changed_files: Vec<String> = changed
-            .added
-            .into_iter()
-            .chain(changed.modified.into_iter())
-            .filter(|f| is_source_file(f))
-            .collect();
+        let files = self.call_graph_source_files()?;
+        let mut parsed: HashMap<String, ParsedFile> = HashMap::new();
+        {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT path, mtime, hash FROM parsed_files")?;
+            let mut rows = stmt.query([])?;
+            while let Some(row) = rows.next()? {
+                parsed.insert(
+                    row.get(0)?,
+                    ParsedFile {
+                        mtime: row.get(1)?,
+                        hash: row.get(2)?,
+                    },
+                );
+            }
+        }
 
-        let deleted_source_files: Vec<String> = changed
-            .deleted
-            .into_iter()
-            .filter(|f| is_source_file(f))
+        // Checking mtimes first avoids reading unchanged files at all
+        let root = self.root.clone();
+        let config = MossConfig::load(&root);
+        let exclude = call_graph_exclusion(&root, &config);
+        let symbols_config = config.symbols;
+        let go_target = config.index.go_target();
+        let updates: Vec<CallGraphUpdate> = files
+            .par_iter()
+            .filter_map(|file_path| {
+                let full_path = root.join(file_path);
+                let previous = parsed.get(file_path);
+                let mtime = file_mtime(&full_path);
+                if previous.is_some_and(|p| p.mtime == mtime) {
+                    return None;
+                }
+                let content = std::fs::read_to_string(&full_path).ok()?;
+                if previous.is_some_and(|p| p.hash == content_hash(content.as_bytes())) {
+                    return Some(CallGraphUpdate::Touched {
+                        path: file_path.clone(),
+                        mtime,
+                    });
+                }
+                let mut parser = SymbolParser::from_config(&symbols_config);
+                Some(CallGraphUpdate::Changed(parse_for_call_graph(
+                    &mut parser,
+                    &full_path,
+                    file_path,
+                    &content,
+                    &go_target,
+                )))
+            })
             .collect();
 
-        if changed_files.is_empty() && deleted_source_files.is_empty() {
+        let current: HashSet<&String> = files.iter().collect();
+        let deleted: Vec<&String> = parsed.keys().filter(|p| !current.contains(p)).collect();
+        // Re-applied every time so config edits take effect without re-parsing
+        self.refresh_excluded_callers(&files, exclude.as_ref())?;
+        if updates.is_empty() && deleted.is_empty() {
             return Ok(CallGraphStats::default());
         }
 
+        let mut changed = Vec::new();
         let tx = self.conn.transaction()?;
+        for update in updates {
+            match update {
+                CallGraphUpdate::Touched { path, mtime } => {
+                    tx.execute(
+                        "UPDATE parsed_files SET mtime = ?1 WHERE path = ?2",
+                        params![mtime, path],
+                    )?;
+                }
+                CallGraphUpdate::Changed(data) => changed.push(data),
+            }
+        }
 
-        // Remove data for deleted/modified files
-        for path in deleted_source_files.iter().chain(changed_files.iter()) {
+        // Remove stale rows for deleted and changed files
+        let stale = deleted
+            .into_iter()
+            .chain(changed.iter().map(|data| &data.file_path));
+        for path in stale {
             tx.execute("DELETE FROM symbols WHERE file = ?1", params![path])?;
             tx.execute("DELETE FROM calls WHERE caller_file = ?1", params![path])?;
             tx.execute("DELETE FROM imports WHERE file = ?1", params![path])?;
+            tx.execute("DELETE FROM partial_files WHERE path = ?1", params![path])?;
+            tx.execute("DELETE FROM excluded_files WHERE path = ?1", params![path])?;
+            tx.execute("DELETE FROM parsed_files WHERE path = ?1", params![path])?;
         }
 
-        let mut parser = SymbolParser::new();
-        let mut symbol_count = 0;
-        let mut call_count = 0;
-        let mut import_count = 0;
-
-        // Parse changed files
-        for file_path in &changed_files {
-            let full_path = self.root.join(file_path);
-            let content = match std::fs::read_to_string(&full_path) {
-                Ok(c) => c,
-                Err(_) => continue,
-            };
+        let stats = insert_parsed(&tx, &changed)?;
+        tx.commit()?;
+        Ok(stats)
+    }
 
-            let symbols = parser.parse_file(&full_path, &content);
+    /// Parsed source files whose content changed on disk since they were
+    /// indexed, so their symbols may be stale. Unlike the incremental refresh,
+    /// this doesn't touch the index.
+    pub fn changed_since_index(&self) -> rusqlite::Result<Vec<ChangedSource>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, hash FROM parsed_files ORDER BY path")?;
+        let parsed: Vec<(String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
 
-            for sym in &symbols {
-                tx.execute(
-                    "INSERT INTO symbols (file, name, kind, start_line, end_line, parent, complexity) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-                    params![file_path, sym.name, sym.kind.as_str(), sym.start_line, sym.end_line, sym.parent, sym.complexity],
-                )?;
-                symbol_count += 1;
-
-                let kind = sym.kind.as_str();
-                if kind == "function" || kind == "method" {
-                    let calls = parser.find_callees_for_symbol(&full_path, &content, sym);
-                    for (callee_name, line, qualifier) in calls {
-                        tx.execute(
-                            "INSERT INTO calls (caller_file, caller_symbol, callee_name, callee_qualifier, line) VALUES (?1, ?2, ?3, ?4, ?5)",
-                            params![file_path, sym.name, callee_name, qualifier, line],
-                        )?;
-                        call_count += 1;
-                    }
+        // Hash every file: mtimes have second granularity and miss quick edits
+        let root = &self.root;
+        let changed = parsed
+            .par_iter()
+            .filter_map(|(path, hash)| {
+                let full_path = root.join(path);
+                match std::fs::read(&full_path) {
+                    Ok(content) if content_hash(&content) == *hash => None,
+                    Ok(_) => Some(ChangedSource {
+                        path: path.clone(),
+                        deleted: false,
+                    }),
+                    Err(_) => Some(ChangedSource {
+                        path: path.clone(),
+                        deleted: !full_path.exists(),
+                    }),
                 }
-            }
+            })
+            .collect();
+        Ok(changed)
+    }
 
-            // Parse imports using trait-based extraction (works for all supported languages)
-            let imports = parser.parse_imports(&full_path, &content);
-            for imp in imports {
-                tx.execute(
-                    "INSERT INTO imports (file, module, name, alias, line) VALUES (?1, ?2, ?3, ?4, ?5)",
-                    params![file_path, imp.module, imp.name, imp.alias, imp.line],
-                )?;
-                import_count += 1;
+    /// Record which source files `exclude` hides from caller results.
+    fn refresh_excluded_callers(
+        &mut self,
+        files: &[String],
+        exclude: Option<&Filter>,
+    ) -> rusqlite::Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM excluded_callers", [])?;
+        if let Some(exclude) = exclude {
+            let mut stmt = tx.prepare("INSERT INTO excluded_callers (path) VALUES (?1)")?;
+            for file in files.iter().filter(|f| !exclude.matches(Path::new(f))) {
+                stmt.execute(params![file])?;
             }
         }
+        tx.commit()
+    }
 
-        tx.commit()?;
-        Ok(CallGraphStats {
-            symbols: symbol_count,
-            calls: call_count,
-            imports: import_count,
-        })
+    /// Files whose callers are hidden by `[index] call_graph_exclude`.
+    pub fn excluded_callers(&self) -> rusqlite::Result<HashSet<String>> {
+        let mut stmt = self.conn.prepare("SELECT path FROM excluded_callers")?;
+        let paths = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(paths)
+    }
+
+    /// Indexed files that the call graph is built from. Binary files are
+    /// skipped even when their extension is a source one.
+    fn call_graph_source_files(&self) -> rusqlite::Result<Vec<String>> {
+        let sql = format!(
+            "SELECT path FROM files WHERE is_dir = 0 AND is_binary = 0 AND ({})",
+            source_extensions_sql_filter()
+        );
+        let mut stmt = self.conn.prepare(&sql)?;
+        let mut files = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let path: String = row.get(0)?;
+            files.push(path);
+        }
+        Ok(files)
+    }
+
+    /// Files whose symbols were cut off at the per-file cap.
+    pub fn partial_files(&self) -> rusqlite::Result<Vec<PartialFile>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, total_symbols FROM partial_files ORDER BY path")?;
+        let files = stmt
+            .query_map([], |row| {
+                Ok(PartialFile {
+                    path: row.get(0)?,
+                    total_symbols: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(files)
+    }
+
+    /// Files left out of the call graph by build constraints.
+    pub fn excluded_files(&self) -> rusqlite::Result<Vec<ExcludedFile>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, reason FROM excluded_files ORDER BY path")?;
+        let files = stmt
+            .query_map([], |row| {
+                Ok(ExcludedFile {
+                    path: row.get(0)?,
+                    reason: row.get(1)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(files)
     }
 
     /// Check if call graph needs refresh
-    #[allow(dead_code)] // FileIndex API - used by daemon
     pub fn needs_call_graph_refresh(&self) -> bool {
         self.call_graph_stats().unwrap_or_default().symbols == 0
     }
@@ -1248,7 +2310,7 @@ impl FileIndex {
         // Handle extension patterns (e.g., ".rs", ".py")
         if query.starts_with('.') && !query.contains('/') {
             let sql =
-                "SELECT path, is_dir, mtime, lines FROM files WHERE LOWER(path) LIKE ?1 LIMIT 1000";
+                "SELECT path, is_dir, mtime, lines, is_binary FROM files WHERE LOWER(path) LIKE ?1 LIMIT 1000";
             let pattern = format!("%{}", query.to_lowercase());
             let mut stmt = self.conn.prepare(sql)?;
             let files = stmt
@@ -1258,6 +2320,7 @@ impl FileIndex {
                         is_dir: row.get::<_, i64>(1)? != 0,
                         mtime: row.get(2)?,
                         lines: row.get::<_, i64>(3)? as usize,
+                        is_binary: row.get::<_, i64>(4)? != 0,
                     })
                 })?
                 .filter_map(|r| r.ok())
@@ -1280,7 +2343,7 @@ impl FileIndex {
             .map(|i| format!("LOWER(path) LIKE ?{}", i + 1))
             .collect();
         let sql = format!(
-            "SELECT path, is_dir, mtime, lines FROM files WHERE {} LIMIT 50",
+            "SELECT path, is_dir, mtime, lines, is_binary FROM files WHERE {} LIMIT 50",
             conditions.join(" AND ")
         );
 
@@ -1300,6 +2363,7 @@ impl FileIndex {
                     is_dir: row.get::<_, i64>(1)? != 0,
                     mtime: row.get(2)?,
                     lines: row.get::<_, i64>(3)? as usize,
+                    is_binary: row.get::<_, i64>(4)? != 0,
                 })
             })?
             .filter_map(|r| r.ok())
@@ -1543,6 +2607,89 @@ pub struct CrossRef {
     pub line: usize,
 }
 
+/// The strongly connected components of the import graph (Tarjan's
+/// algorithm) that contain a cycle: more than one file, or a file importing
+/// itself. Files within a cycle and the cycles themselves are sorted.
+fn import_cycles_among(imports: &[FileImport]) -> Vec<Vec<String>> {
+    let mut graph: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for import in imports {
+        graph.entry(&import.file).or_default().push(&import.target);
+        graph.entry(&import.target).or_default();
+    }
+
+    let mut tarjan = Tarjan {
+        graph: &graph,
+        next_index: 0,
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        components: Vec::new(),
+    };
+    for node in graph.keys() {
+        if !tarjan.index.contains_key(node) {
+            tarjan.connect(node);
+        }
+    }
+
+    let mut cycles: Vec<Vec<String>> = tarjan
+        .components
+        .into_iter()
+        .filter(|c| c.len() > 1 || graph[c[0]].contains(&c[0]))
+        .map(|c| {
+            let mut files: Vec<String> = c.into_iter().map(String::from).collect();
+            files.sort();
+            files
+        })
+        .collect();
+    cycles.sort();
+    cycles
+}
+
+struct Tarjan<'a> {
+    graph: &'a BTreeMap<&'a str, Vec<&'a str>>,
+    next_index: usize,
+    index: HashMap<&'a str, usize>,
+    low: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: HashSet<&'a str>,
+    components: Vec<Vec<&'a str>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn connect(&mut self, node: &'a str) {
+        self.index.insert(node, self.next_index);
+        self.low.insert(node, self.next_index);
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack.insert(node);
+
+        for &next in &self.graph[node] {
+            if !self.index.contains_key(next) {
+                self.connect(next);
+                let low = self.low[node].min(self.low[next]);
+                self.low.insert(node, low);
+            } else if self.on_stack.contains(next) {
+                let low = self.low[node].min(self.index[next]);
+                self.low.insert(node, low);
+            }
+        }
+
+        // Root of a component: everything above it on the stack belongs to it
+        if self.low[node] == self.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1652,7 +2799,7 @@ class MyClass:
         index.refresh_call_graph().unwrap();
 
         // Find callers of method_b - should include method_a and method_c
-        let callers = index.find_callers("method_b").unwrap();
+        let callers = index.find_callers("method_b", CallerScope::All).unwrap();
         assert!(!callers.is_empty(), "Should find callers of method_b");
 
         let caller_names: Vec<&str> = callers.iter().map(|(_, name, _)| name.as_str()).collect();
@@ -1666,10 +2813,418 @@ class MyClass:
         );
 
         // Find callers of MyClass.method_b - more specific
-        let callers = index.find_callers("MyClass.method_b").unwrap();
+        let callers = index
+            .find_callers("MyClass.method_b", CallerScope::All)
+            .unwrap();
         assert!(
             !callers.is_empty(),
             "Should find callers of MyClass.method_b"
         );
     }
+
+    #[test]
+    fn test_call_tree() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.py"),
+            "def main():\n    parse()\n    print(1)\n\ndef parse():\n    walk()\n\ndef walk():\n    walk()\n    emit()\n\ndef emit():\n    pass\n",
+        )
+        .unwrap();
+
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+
+        fn render(node: &CallTreeNode, out: &mut Vec<String>, indent: usize) {
+            let cycle = if node.cycle { " (cycle)" } else { "" };
+            out.push(format!("{}{}{}", "  ".repeat(indent), node.symbol, cycle));
+            for child in &node.children {
+                render(child, out, indent + 1);
+            }
+        }
+
+        let tree = index
+            .call_tree("main", 5, CallDirection::Callees, CallerScope::All)
+            .unwrap();
+        let mut lines = Vec::new();
+        render(&tree, &mut lines, 0);
+        assert_eq!(
+            lines,
+            vec![
+                "main",
+                "  parse",
+                "    walk",
+                // Recursion is marked, not followed
+                "      walk (cycle)",
+                "      emit",
+                "  print",
+            ]
+        );
+        assert_eq!(tree.children[0].line, Some(2));
+
+        // Depth limits how far the tree goes
+        let tree = index
+            .call_tree("main", 1, CallDirection::Callees, CallerScope::All)
+            .unwrap();
+        assert!(tree.children.iter().all(|c| c.children.is_empty()));
+
+        let tree = index
+            .call_tree("emit", 5, CallDirection::Callers, CallerScope::All)
+            .unwrap();
+        let mut lines = Vec::new();
+        render(&tree, &mut lines, 0);
+        assert_eq!(
+            lines,
+            vec![
+                "emit",
+                "  walk",
+                "    parse",
+                "      main",
+                // The back edge comes after the call from parse (line 6)
+                "    walk (cycle)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_callees_of() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("a.py"),
+            "def main():\n    helper()\n    save()\n    local()\n    print(1)\n\ndef local():\n    pass\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("b.py"),
+            "def helper():\n    pass\n\ndef local():\n    pass\n\nclass A:\n    def save(self):\n        pass\n\nclass B:\n    def save(self):\n        pass\n",
+        )
+        .unwrap();
+
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+        let callees = index.find_callees_of("main").unwrap();
+        let found: Vec<(&str, Option<String>, bool)> = callees
+            .iter()
+            .map(|c| {
+                let def = c
+                    .definition_file
+                    .as_ref()
+                    .zip(c.definition_line)
+                    .map(|(f, l)| format!("{}:{}", f, l));
+                (c.callee.as_str(), def, c.ambiguous)
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("helper", Some("b.py:1".to_string()), false),
+                // Two classes define save(): both listed, flagged
+                ("save", Some("b.py:8".to_string()), true),
+                ("save", Some("b.py:12".to_string()), true),
+                // The calling file's own definition wins
+                ("local", Some("a.py:7".to_string()), false),
+                ("print", None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_incremental_call_graph_refresh() {
+        let dir = tempdir().unwrap();
+        let set_mtime = |name: &str, secs: u64| {
+            let file = fs::File::options()
+                .write(true)
+                .open(dir.path().join(name))
+                .unwrap();
+            file.set_modified(UNIX_EPOCH + std::time::Duration::from_secs(secs))
+                .unwrap();
+        };
+        fs::write(dir.path().join("a.py"), "def foo():\n    bar()\n").unwrap();
+        fs::write(dir.path().join("b.py"), "def bar():\n    pass\n").unwrap();
+        set_mtime("a.py", 1_000);
+        set_mtime("b.py", 1_000);
+
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+        let stats = index.incremental_call_graph_refresh().unwrap();
+        assert_eq!(stats.symbols, 0, "nothing changed");
+
+        // Only the edited file is re-parsed
+        fs::write(
+            dir.path().join("a.py"),
+            "def foo():\n    baz()\n\ndef baz():\n    pass\n",
+        )
+        .unwrap();
+        set_mtime("a.py", 2_000);
+        // Touched without changing content: not re-parsed
+        set_mtime("b.py", 2_000);
+        index.refresh().unwrap();
+        let stats = index.incremental_call_graph_refresh().unwrap();
+        assert_eq!(stats.symbols, 2);
+        assert_eq!(stats.calls, 1);
+        let callers = index.find_callers("baz", CallerScope::All).unwrap();
+        assert_eq!(callers.len(), 1);
+        assert!(index
+            .find_callers("bar", CallerScope::All)
+            .unwrap()
+            .is_empty());
+
+        // Deleted files lose their rows
+        fs::remove_file(dir.path().join("b.py")).unwrap();
+        index.refresh().unwrap();
+        index.incremental_call_graph_refresh().unwrap();
+        let symbols = index.all_symbols().unwrap();
+        assert!(symbols.iter().all(|s| s.file == "a.py"));
+        assert_eq!(symbols.len(), 2);
+    }
+
+    #[test]
+    fn test_call_graph_exclude() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".moss")).unwrap();
+        fs::create_dir_all(dir.path().join("tests")).unwrap();
+        fs::write(
+            dir.path().join("lib.py"),
+            "def used():\n    helper()\n\ndef helper():\n    pass\n\ndef only_tested():\n    pass\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("tests/test_lib.py"),
+            "def test_it():\n    helper()\n    only_tested()\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(".moss/config.toml"),
+            "[index]\ncall_graph_exclude = [\"tests/**\"]\n",
+        )
+        .unwrap();
+
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+
+        let callers = index
+            .find_callers("helper", CallerScope::SkipExcluded)
+            .unwrap();
+        assert_eq!(callers.len(), 1);
+        assert_eq!(callers[0].0, "lib.py");
+        assert_eq!(
+            index
+                .find_callers("helper", CallerScope::All)
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Hidden, not guessed at through the fuzzy fallbacks
+        assert!(index
+            .find_callers("only_tested", CallerScope::SkipExcluded)
+            .unwrap()
+            .is_empty());
+        // Excluded calls still count as uses
+        assert!(index.referenced_names().unwrap().contains("only_tested"));
+
+        // Config changes apply on the next incremental refresh
+        fs::write(dir.path().join(".moss/config.toml"), "").unwrap();
+        index.incremental_call_graph_refresh().unwrap();
+        assert_eq!(
+            index
+                .find_callers("helper", CallerScope::SkipExcluded)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_binary_files_flagged_and_skipped() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("real.py"), "def real():\n    pass\n").unwrap();
+        fs::write(root.join("blob.py"), b"def fake():\n\0\0\0").unwrap();
+        fs::write(root.join("LICENSE"), "MIT\n").unwrap();
+
+        let mut index = FileIndex::open(root).unwrap();
+        index.refresh().unwrap();
+        let binary: Vec<String> = index
+            .all_files()
+            .unwrap()
+            .into_iter()
+            .filter(|f| f.is_binary && !f.path.starts_with(".moss/"))
+            .map(|f| f.path)
+            .collect();
+        assert_eq!(binary, vec!["blob.py"]);
+
+        let stats = index.refresh_call_graph().unwrap();
+        assert_eq!(stats.symbols, 1);
+    }
+
+    #[test]
+    fn test_update_paths() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join(".ignore"), "*.tmp\n").unwrap();
+        fs::write(root.join("a.py"), "def a():\n    pass\n").unwrap();
+        fs::create_dir(root.join("pkg")).unwrap();
+        fs::write(root.join("pkg/b.py"), "def b():\n    pass\n").unwrap();
+
+        let mut index = FileIndex::open(root).unwrap();
+        index.refresh().unwrap();
+
+        // Created file, edited file, ignored editor temp file
+        fs::write(root.join("c.py"), "def c():\n    pass\n").unwrap();
+        fs::write(root.join("a.py"), "def a():\n    c()\n").unwrap();
+        fs::write(root.join("a.py.tmp"), "").unwrap();
+        let changed = index
+            .update_paths(&[root.join("c.py"), root.join("a.py"), root.join("a.py.tmp")])
+            .unwrap();
+        assert_eq!(changed.added, vec!["c.py"]);
+        assert_eq!(changed.modified, vec!["a.py"]);
+        assert!(changed.deleted.is_empty());
+
+        // Symbols follow through the incremental call graph refresh
+        index.incremental_call_graph_refresh().unwrap();
+        let callers = index.find_callers("c", CallerScope::All).unwrap();
+        assert_eq!(callers.len(), 1);
+
+        // Deleting a directory drops everything under it
+        fs::remove_dir_all(root.join("pkg")).unwrap();
+        let changed = index.update_paths(&[root.join("pkg")]).unwrap();
+        assert_eq!(changed.deleted, vec!["pkg"]);
+        let files: Vec<String> = index
+            .all_files()
+            .unwrap()
+            .into_iter()
+            .map(|f| f.path)
+            .collect();
+        assert!(files.iter().all(|f| !f.starts_with("pkg")));
+        assert!(!files.contains(&"a.py.tmp".to_string()));
+    }
+
+    #[test]
+    fn test_symbol_cap_per_file() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".moss")).unwrap();
+        fs::write(
+            dir.path().join(".moss/config.toml"),
+            "[symbols]\nmax_per_file = 5\n",
+        )
+        .unwrap();
+        let generated: String = (0..20)
+            .map(|i| format!("def func_{}():\n    pass\n\n", i))
+            .collect();
+        fs::write(dir.path().join("generated.py"), generated).unwrap();
+        fs::write(dir.path().join("small.py"), "def only():\n    pass\n").unwrap();
+
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        let stats = index.refresh_call_graph().unwrap();
+        assert_eq!(stats.partial_files, 1);
+
+        let symbols = index.all_symbols().unwrap();
+        let generated: Vec<&str> = symbols
+            .iter()
+            .filter(|s| s.file == "generated.py")
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(
+            generated,
+            vec!["func_0", "func_1", "func_2", "func_3", "func_4"]
+        );
+        assert!(symbols.iter().any(|s| s.file == "small.py"));
+
+        let partial = index.partial_files().unwrap();
+        assert_eq!(partial.len(), 1);
+        assert_eq!(partial[0].path, "generated.py");
+        assert_eq!(partial[0].total_symbols, 20);
+    }
+
+    #[test]
+    fn test_go_build_constraints() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".moss")).unwrap();
+        fs::write(
+            dir.path().join(".moss/config.toml"),
+            "[index]\ngo_target = \"linux/amd64\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("fs_linux.go"),
+            "package fs\n\nfunc openLinux() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("fs_windows.go"),
+            "package fs\n\nfunc openWindows() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("registry.go"),
+            "//go:build windows\n\npackage fs\n\nfunc readRegistry() {}\n",
+        )
+        .unwrap();
+
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        let stats = index.refresh_call_graph().unwrap();
+        assert_eq!(stats.excluded_files, 2);
+
+        let names: Vec<String> = index
+            .all_symbols()
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.file.ends_with(".go"))
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["openLinux"]);
+
+        let excluded: Vec<(String, String)> = index
+            .excluded_files()
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.path, f.reason))
+            .collect();
+        assert_eq!(
+            excluded,
+            vec![
+                (
+                    "fs_windows.go".to_string(),
+                    "file name suffix _windows".to_string()
+                ),
+                ("registry.go".to_string(), "//go:build windows".to_string()),
+            ]
+        );
+    }
+
+    fn import(file: &str, target: &str) -> FileImport {
+        FileImport {
+            file: file.to_string(),
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_import_cycles() {
+        let imports = [
+            import("a.py", "b.py"),
+            import("b.py", "c.py"),
+            import("c.py", "a.py"),
+            import("c.py", "d.py"),
+            import("d.py", "e.py"),
+            import("e.py", "d.py"),
+            import("f.py", "a.py"),
+        ];
+        assert_eq!(
+            import_cycles_among(&imports),
+            [vec!["a.py", "b.py", "c.py"], vec!["d.py", "e.py"]]
+        );
+        assert!(import_cycles_among(&imports[3..5]).is_empty());
+        // Self-imports are cycles too
+        assert_eq!(
+            import_cycles_among(&[import("a.py", "a.py"), import("a.py", "b.py")]),
+            [vec!["a.py"]]
+        );
+    }
 }
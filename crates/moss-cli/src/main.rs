@@ -1,28 +1,35 @@
-use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use clap::{CommandFactory, Parser, Subcommand};
+use std::path::{Path, PathBuf};
 
+mod alias;
 mod analyze;
 mod commands;
 mod complexity;
 mod config;
 mod daemon;
+mod dead_code;
 mod deps;
 mod edit;
 mod extract;
 mod filter;
 mod grep;
+mod hash;
 mod health;
+mod highlight;
 mod index;
 mod output;
 mod overview;
 mod parsers;
 mod path_resolve;
 mod paths;
+mod rename;
 mod serve;
 mod sessions;
 mod skeleton;
 mod symbols;
+mod tool_cache;
 mod tree;
+mod walk;
 mod workflow;
 
 #[derive(Parser)]
@@ -39,8 +46,15 @@ struct Cli {
     /// Filter JSON output with jq expression (implies --json)
     #[arg(long, global = true, value_name = "EXPR")]
     jq: Option<String>,
+
+    /// Write output to a file instead of stdout (errors still go to stderr).
+    /// Unix only, except for `generate`, which writes the file itself
+    #[arg(short, long, global = true, value_name = "PATH")]
+    output: Option<PathBuf>,
 }
 
+// Parsed once per run; boxing the big variants buys nothing
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// View a node in the codebase tree (directory, file, or symbol)
@@ -73,6 +87,10 @@ enum Commands {
         #[arg(long = "types-only")]
         types_only: bool,
 
+        /// Group same-named symbols (overloads, multiple impls) into one entry
+        #[arg(long)]
+        group: bool,
+
         /// Disable smart display (no collapsing single-child dirs)
         #[arg(long)]
         raw: bool,
@@ -100,6 +118,30 @@ enum Commands {
         #[arg(long)]
         context: bool,
 
+        /// Print only lines START:END (1-based, inclusive; either end may be omitted)
+        #[arg(long, value_name = "START:END", conflicts_with = "around")]
+        lines: Option<String>,
+
+        /// Print the lines around LINE (5 on either side unless -C is given)
+        #[arg(long, value_name = "LINE")]
+        around: Option<usize>,
+
+        /// Lines of context around --around, or around a symbol target's own lines
+        #[arg(short = 'C', long = "context-lines", value_name = "N")]
+        context_lines: Option<usize>,
+
+        /// Outline only: imports and signatures, nested by scope, bodies elided as `...`
+        #[arg(long, conflicts_with_all = ["full", "context", "deps", "focus"])]
+        skeleton: bool,
+
+        /// Syntax-highlight printed source when stdout is a terminal
+        #[arg(long)]
+        highlight: bool,
+
+        /// Color palette for --highlight (e.g. one-dark, gruvbox-light; default catppuccin-mocha)
+        #[arg(long, value_name = "NAME", requires = "highlight")]
+        theme: Option<String>,
+
         /// Exclude paths matching pattern or @alias (repeatable)
         /// Patterns: globs like "*.test.js", "**/tests/**"
         /// Aliases: @tests, @config, @build, @docs, @generated
@@ -111,6 +153,152 @@ enum Commands {
         only: Vec<String>,
     },
 
+    /// Resolve a fuzzy path query to matching files and directories
+    Path {
+        /// Path query (exact path, file name, stem, or fuzzy pattern)
+        query: String,
+
+        /// Root directory (defaults to current directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
+
+        /// Return matches from every tier (exact, name, fuzzy), not just the first
+        #[arg(long)]
+        all: bool,
+
+        /// Only return these kinds: file, directory, or a symbol kind such as
+        /// function, method, class, struct (comma-separated). Symbol kinds
+        /// search indexed symbol names too.
+        #[arg(long, value_delimiter = ',')]
+        kind: Vec<String>,
+
+        /// Maximum number of results
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Fuzzy case sensitivity: ignore, smart, respect (overrides [fuzzy] case)
+        #[arg(long)]
+        case: Option<config::FuzzyCase>,
+
+        /// Rank fuzzy matches at path separators higher (overrides [fuzzy] match_paths)
+        #[arg(long, value_name = "BOOL")]
+        match_paths: Option<bool>,
+
+        /// Let ASCII letters match accented ones (overrides [fuzzy] normalize)
+        #[arg(long, value_name = "BOOL")]
+        normalize: Option<bool>,
+
+        /// Drop fuzzy matches scoring below this (overrides [fuzzy] min_score)
+        #[arg(long)]
+        min_score: Option<u32>,
+
+        /// Fuzzy match file names only, not whole paths (overrides [fuzzy] basename)
+        #[arg(long)]
+        basename: bool,
+    },
+
+    /// Pick a file interactively, fuzzy matching as you type (requires the tui feature)
+    Pick {
+        /// Initial query
+        query: Option<String>,
+
+        /// Open the chosen file in $VISUAL/$EDITOR instead of printing its path
+        #[arg(long)]
+        open: bool,
+
+        /// Root directory (defaults to current directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
+    },
+
+    /// Print a symbol's signature only (name, Parent.name, or file:name)
+    Signature {
+        /// Symbol to look up
+        symbol: String,
+
+        /// Root directory (defaults to current directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
+    },
+
+    /// Find functions similar to a symbol (shared callees, signature, name)
+    Similar {
+        /// Symbol to compare against (name or file:name)
+        symbol: String,
+
+        /// Root directory (defaults to current directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
+
+        /// Maximum matches to show
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+    },
+
+    /// Export the call graph (text edge list or Graphviz DOT)
+    Callgraph {
+        /// Output format: text, dot
+        #[arg(long, default_value = "text")]
+        format: commands::callgraph::CallGraphFormat,
+
+        /// Only export calls near this symbol
+        #[arg(long)]
+        focus: Option<String>,
+
+        /// Levels of calls to follow from --focus
+        #[arg(long, default_value = "1", requires = "focus")]
+        depth: usize,
+
+        /// With --focus, follow only calls into the symbol
+        #[arg(long, requires = "focus")]
+        callers: bool,
+
+        /// With --focus, follow only calls out of the symbol
+        #[arg(long, requires = "focus")]
+        callees: bool,
+
+        /// Root directory (defaults to current directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
+    },
+
+    /// Export the symbol index as a symbol map or editor tag files (ctags, etags)
+    Symbols {
+        /// List every indexed symbol: name, kind, file, line range, signature
+        #[arg(long, conflicts_with_all = ["export_ctags", "export_etags", "changed_since_index"])]
+        all: bool,
+
+        /// With --all, only these symbol kinds (comma-separated: function,method,...)
+        #[arg(long, value_delimiter = ',', requires = "all")]
+        kind: Vec<String>,
+
+        /// With --all, only symbols from these languages (comma-separated: rust,python,...)
+        #[arg(long, value_delimiter = ',', requires = "all")]
+        lang: Vec<String>,
+
+        /// Write a Universal Ctags file (default: tags)
+        #[arg(
+            long,
+            visible_alias = "tags",
+            value_name = "PATH",
+            num_args = 0..=1,
+            default_missing_value = "tags"
+        )]
+        export_ctags: Option<PathBuf>,
+
+        /// Write an Emacs etags file (default: TAGS)
+        #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = "TAGS")]
+        export_etags: Option<PathBuf>,
+
+        /// List symbols from files that changed on disk since they were indexed
+        #[arg(long)]
+        changed_since_index: bool,
+
+        /// Root directory (defaults to current directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
+    },
+
     /// Edit a node in the codebase tree (structural code modification)
     Edit {
         /// Target to edit (path like src/main.py/Foo/bar)
@@ -180,6 +368,26 @@ enum Commands {
         #[arg(long)]
         swap: Option<String>,
 
+        /// Replace the target function's body, keeping its signature (reads stdin without a value or with -)
+        #[arg(long, value_name = "BODY", num_args = 0..=1, default_missing_value = "-")]
+        replace_body: Option<String>,
+
+        /// Don't keep a .bak of the file when using --replace-body
+        #[arg(long, requires = "replace_body")]
+        no_backup: bool,
+
+        /// Rename the target symbol and its call sites and imports (previews unless --apply)
+        #[arg(long, value_name = "NEW")]
+        rename: Option<String>,
+
+        /// Which references --rename touches: file, project
+        #[arg(long, default_value = "project", requires = "rename")]
+        scope: rename::RenameScope,
+
+        /// Write the --rename edits instead of previewing them
+        #[arg(long, requires = "rename")]
+        apply: bool,
+
         /// Dry run - show what would be changed without applying
         #[arg(long)]
         dry_run: bool,
@@ -203,6 +411,17 @@ enum Commands {
         root: Option<PathBuf>,
     },
 
+    /// Keep the index up to date as files change (until Ctrl-C)
+    Watch {
+        /// Root directory (defaults to current directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
+
+        /// Only track the file list; don't re-parse symbols and calls
+        #[arg(long)]
+        files_only: bool,
+    },
+
     /// Manage the moss daemon
     Daemon {
         #[command(subcommand)]
@@ -237,6 +456,10 @@ enum Commands {
         #[arg(long)]
         complexity: bool,
 
+        /// Run cognitive complexity analysis (nesting-weighted)
+        #[arg(long)]
+        cognitive: bool,
+
         /// Run security analysis (vulnerability scanning)
         #[arg(long)]
         security: bool,
@@ -253,7 +476,7 @@ enum Commands {
         #[arg(short, long)]
         compact: bool,
 
-        /// Complexity threshold - only show functions above this
+        /// Complexity threshold - flag functions above this (default 10, or 15 for --cognitive)
         #[arg(short, long)]
         threshold: Option<usize>,
 
@@ -269,10 +492,42 @@ enum Commands {
         #[arg(long)]
         callers: bool,
 
+        /// Follow --callers/--callees transitively, as a tree
+        #[arg(long)]
+        tree: bool,
+
+        /// Levels to follow with --tree
+        #[arg(long, default_value = "3", requires = "tree")]
+        depth: usize,
+
+        /// With --callers, also show callers in files matched by [index] call_graph_exclude
+        #[arg(long)]
+        include_tests: bool,
+
         /// Run linters and include results in analysis
         #[arg(long)]
         lint: bool,
 
+        /// Apply fixes with linters that support it (implies --lint)
+        #[arg(long)]
+        fix: bool,
+
+        /// Allow --fix on a git tree with uncommitted changes
+        #[arg(long)]
+        allow_dirty: bool,
+
+        /// Re-run linters on every file instead of reusing cached results
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Only report diagnostics at or above this severity: error, warning, info, hint (implies --lint)
+        #[arg(long)]
+        min_severity: Option<moss_tools::DiagnosticSeverity>,
+
+        /// Exit non-zero if any diagnostic is at or above this severity (implies --lint)
+        #[arg(long)]
+        error_on: Option<moss_tools::DiagnosticSeverity>,
+
         /// Show git history hotspots (high churn + high complexity)
         #[arg(long)]
         hotspots: bool,
@@ -281,6 +536,14 @@ enum Commands {
         #[arg(long)]
         check_refs: bool,
 
+        /// List functions with no recorded callers (possibly unused)
+        #[arg(long)]
+        dead_code: bool,
+
+        /// List groups of files that import each other (exits 1 if any)
+        #[arg(long)]
+        import_cycles: bool,
+
         /// Exclude paths matching pattern or @alias (repeatable)
         #[arg(long, value_name = "PATTERN")]
         exclude: Vec<String>,
@@ -300,6 +563,16 @@ enum Commands {
         root: Option<PathBuf>,
     },
 
+    /// Dependency graphs between modules
+    Graph {
+        #[command(subcommand)]
+        action: GraphAction,
+
+        /// Root directory (defaults to current directory)
+        #[arg(short, long, global = true)]
+        root: Option<PathBuf>,
+    },
+
     /// Search for text patterns in files (fast ripgrep-based search)
     Grep {
         /// Regex pattern to search for
@@ -364,6 +637,37 @@ enum Commands {
         /// Root directory (defaults to current directory)
         #[arg(short, long, global = true)]
         root: Option<PathBuf>,
+
+        /// Bypass the registry cache and query the network
+        #[arg(long, global = true, conflicts_with = "offline")]
+        refresh: bool,
+
+        /// Only use cached registry data, never the network
+        #[arg(long, global = true)]
+        offline: bool,
+    },
+
+    /// Report and trim the global cache (~/.cache/moss)
+    Cache {
+        /// Report cache usage per entry (the default)
+        #[arg(long, conflicts_with = "clean")]
+        size: bool,
+
+        /// Remove stale entries, oldest first
+        #[arg(long)]
+        clean: bool,
+
+        /// With --clean: remove entries older than DAYS (default: [cache] max_age_days)
+        #[arg(long, value_name = "DAYS", requires = "clean")]
+        older_than: Option<u64>,
+
+        /// With --clean: evict entries until the cache fits in MB (default: [cache] max_size_mb)
+        #[arg(long, value_name = "MB", requires = "clean")]
+        max_size: Option<u64>,
+
+        /// Root directory for config lookup (defaults to current directory)
+        #[arg(short, long)]
+        root: Option<PathBuf>,
     },
 
     /// List and view Claude Code plans from ~/.claude/plans/
@@ -423,10 +727,6 @@ enum GenerateTarget {
         /// Target language: typescript, python, rust
         #[arg(short, long)]
         lang: String,
-
-        /// Output file (stdout if not specified)
-        #[arg(short, long)]
-        output: Option<PathBuf>,
     },
     /// Generate types from JSON Schema
     Types {
@@ -440,10 +740,6 @@ enum GenerateTarget {
         /// Target language: typescript, python, rust
         #[arg(short, long)]
         lang: String,
-
-        /// Output file (stdout if not specified)
-        #[arg(short, long)]
-        output: Option<PathBuf>,
     },
 }
 
@@ -458,6 +754,10 @@ enum LintAction {
         #[arg(short, long)]
         fix: bool,
 
+        /// Allow --fix on a git tree with uncommitted changes
+        #[arg(long)]
+        allow_dirty: bool,
+
         /// Specific tools to run (comma-separated, e.g., "ruff,oxlint")
         #[arg(short, long)]
         tools: Option<String>,
@@ -496,7 +796,37 @@ enum ServeProtocol {
     },
 
     /// Start LSP server for IDE integration
-    Lsp,
+    Lsp {
+        /// Preferred column encoding: utf-16, utf-8, utf-32.
+        /// Falls back to utf-16 if the client doesn't support it.
+        #[arg(long, default_value = "utf-16")]
+        position_encoding: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum GraphAction {
+    /// File-level import graph: which files import which
+    Files {
+        /// Output format: text, dot, mermaid
+        #[arg(long, default_value = "text")]
+        format: commands::graph::GraphFormat,
+
+        /// Only list import cycles (exits 1 if there are any)
+        #[arg(long)]
+        cycles: bool,
+    },
+
+    /// Directory-level import graph, checked against [graph.allow] layering rules
+    Modules {
+        /// Output format: text, dot, mermaid
+        #[arg(long, default_value = "text")]
+        format: commands::graph::GraphFormat,
+
+        /// Path components that name a module (2 groups src/a/x.py under src/a)
+        #[arg(long, default_value = "1")]
+        depth: usize,
+    },
 }
 
 #[derive(Subcommand)]
@@ -516,9 +846,58 @@ fn reset_sigpipe() {
 #[cfg(not(unix))]
 fn reset_sigpipe() {}
 
+/// Expand config-defined command aliases and the default command in argv.
+fn expand_aliases() -> Vec<std::ffi::OsString> {
+    let args: Vec<std::ffi::OsString> = std::env::args_os().collect();
+    let root = paths::current_project_root();
+    let config = config::MossConfig::load(&root);
+
+    let mut builtins = vec!["help".to_string()];
+    for sub in Cli::command().get_subcommands() {
+        builtins.push(sub.get_name().to_string());
+        builtins.extend(sub.get_all_aliases().map(String::from));
+    }
+
+    match alias::expand_args(
+        args,
+        &builtins,
+        &alias::with_defaults(&config.aliases),
+        config.cli.default_command.as_deref(),
+    ) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Write generated code to `output`, or stdout if not given.
+fn write_generated(code: &str, output: Option<&Path>) -> i32 {
+    let Some(path) = output else {
+        print!("{}", code);
+        return 0;
+    };
+    if let Err(e) = std::fs::write(path, code) {
+        eprintln!("Failed to write {}: {}", path.display(), e);
+        return 1;
+    }
+    eprintln!("Generated {}", path.display());
+    0
+}
+
 fn main() {
     reset_sigpipe();
-    let cli = Cli::parse();
+    let cli = Cli::parse_from(expand_aliases());
+
+    // generate writes its file directly, so -o works there on every platform
+    let generate = matches!(cli.command, Commands::Generate { .. });
+    if let Some(path) = cli.output.as_ref().filter(|_| !generate) {
+        if let Err(e) = output::redirect_stdout(path) {
+            eprintln!("error: cannot write to {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
 
     let exit_code = match cli.command {
         Commands::View {
@@ -529,12 +908,19 @@ fn main() {
             deps,
             kind,
             types_only,
+            group,
             raw,
             focus,
             resolve_imports,
             include_private,
             full,
             context,
+            lines,
+            around,
+            context_lines,
+            skeleton,
+            highlight,
+            theme,
             exclude,
             only,
         } => commands::view::cmd_view(
@@ -545,16 +931,98 @@ fn main() {
             deps,
             kind.as_deref(),
             types_only,
+            group,
             raw,
             focus.as_deref(),
             resolve_imports,
             include_private,
             full,
             context,
+            commands::view::LineWindow {
+                lines: lines.as_deref(),
+                around,
+                context: context_lines,
+            },
+            skeleton,
+            highlight,
+            theme.as_deref(),
             cli.json,
             &exclude,
             &only,
         ),
+        Commands::Path {
+            query,
+            root,
+            all,
+            kind,
+            limit,
+            case,
+            match_paths,
+            normalize,
+            min_score,
+            basename,
+        } => commands::path::cmd_path(
+            &query,
+            root.as_deref(),
+            all,
+            &kind,
+            limit,
+            config::FuzzyConfig {
+                match_paths,
+                case,
+                normalize,
+                limit,
+                min_score,
+                basename: basename.then_some(true),
+            },
+            cli.json,
+        ),
+        Commands::Pick { query, open, root } => {
+            commands::pick::cmd_pick(query.as_deref(), open, root.as_deref())
+        }
+        Commands::Signature { symbol, root } => {
+            commands::signature::cmd_signature(&symbol, root.as_deref(), cli.json)
+        }
+        Commands::Similar {
+            symbol,
+            root,
+            limit,
+        } => commands::similar::cmd_similar(&symbol, root.as_deref(), limit, cli.json),
+        Commands::Callgraph {
+            format,
+            focus,
+            depth,
+            callers,
+            callees,
+            root,
+        } => commands::callgraph::cmd_callgraph(
+            root.as_deref(),
+            format,
+            focus.as_deref(),
+            depth,
+            callers,
+            callees,
+            cli.json,
+        ),
+        Commands::Symbols {
+            all,
+            kind,
+            lang,
+            export_ctags,
+            export_etags,
+            changed_since_index,
+            root,
+        } => commands::symbols::cmd_symbols(
+            all.then_some(commands::symbols::SymbolFilter {
+                kinds: kind,
+                langs: lang,
+            }),
+            export_ctags.as_deref(),
+            export_etags.as_deref(),
+            changed_since_index,
+            root.as_deref(),
+            cli.json,
+        ),
         Commands::Edit {
             target,
             root,
@@ -573,6 +1041,11 @@ fn main() {
             copy_prepend,
             copy_append,
             swap,
+            replace_body,
+            no_backup,
+            rename,
+            scope,
+            apply,
             dry_run,
             exclude,
             only,
@@ -594,6 +1067,11 @@ fn main() {
             copy_prepend.as_deref(),
             copy_append.as_deref(),
             swap.as_deref(),
+            replace_body.as_deref(),
+            no_backup,
+            rename.as_deref(),
+            scope,
+            apply,
             dry_run,
             cli.json,
             &exclude,
@@ -602,6 +1080,9 @@ fn main() {
         Commands::Index { action, root } => {
             commands::index::cmd_index(action, root.as_deref(), cli.json)
         }
+        Commands::Watch { root, files_only } => {
+            commands::watch::cmd_watch(root.as_deref(), files_only, cli.json)
+        }
         Commands::Daemon { action, root } => {
             commands::daemon::cmd_daemon(action, root.as_deref(), cli.json)
         }
@@ -611,6 +1092,7 @@ fn main() {
             root,
             health,
             complexity,
+            cognitive,
             security,
             overview,
             storage,
@@ -619,9 +1101,19 @@ fn main() {
             kind,
             callees,
             callers,
+            tree,
+            depth,
+            include_tests,
             lint,
+            fix,
+            allow_dirty,
+            no_cache,
+            min_severity,
+            error_on,
             hotspots,
             check_refs,
+            dead_code,
+            import_cycles,
             exclude,
             only,
         } => commands::analyze::cmd_analyze(
@@ -629,6 +1121,7 @@ fn main() {
             root.as_deref(),
             health,
             complexity,
+            cognitive,
             security,
             overview,
             storage,
@@ -637,9 +1130,18 @@ fn main() {
             kind.as_deref(),
             callees,
             callers,
+            tree.then_some(depth),
+            include_tests,
             lint,
+            fix,
+            allow_dirty,
+            no_cache,
+            min_severity,
+            error_on,
             hotspots,
             check_refs,
+            dead_code,
+            import_cycles,
             cli.json,
             &exclude,
             &only,
@@ -647,6 +1149,9 @@ fn main() {
         Commands::Filter { action, root } => {
             commands::filter::cmd_filter(action, root.as_deref(), cli.json)
         }
+        Commands::Graph { action, root } => {
+            commands::graph::cmd_graph(action, root.as_deref(), cli.json)
+        }
         Commands::Grep {
             pattern,
             root,
@@ -685,6 +1190,13 @@ fn main() {
                 commands::sessions::cmd_sessions_list(project.as_deref(), limit, cli.json)
             }
         }
+        Commands::Cache {
+            size: _,
+            clean,
+            older_than,
+            max_size,
+            root,
+        } => commands::cache::cmd_cache(clean, older_than, max_size, root.as_deref(), cli.json),
         Commands::Plans { name, limit } => {
             commands::plans::cmd_plans(name.as_deref(), limit, cli.json)
         }
@@ -692,8 +1204,23 @@ fn main() {
             action,
             ecosystem,
             root,
+            refresh,
+            offline,
         } => {
-            commands::package::cmd_package(action, ecosystem.as_deref(), root.as_deref(), cli.json)
+            let cache_mode = if offline {
+                moss_packages::CacheMode::Offline
+            } else if refresh {
+                moss_packages::CacheMode::Refresh
+            } else {
+                moss_packages::CacheMode::Default
+            };
+            commands::package::cmd_package(
+                action,
+                ecosystem.as_deref(),
+                root.as_deref(),
+                cache_mode,
+                cli.json,
+            )
         }
         Commands::Workflow { action, root } => {
             commands::workflow::cmd_workflow(action, root.as_deref(), cli.json)
@@ -702,6 +1229,7 @@ fn main() {
             let action = action.unwrap_or(LintAction::Run {
                 target: None,
                 fix: false,
+                allow_dirty: false,
                 tools: None,
                 category: None,
                 sarif: false,
@@ -711,6 +1239,7 @@ fn main() {
                 LintAction::Run {
                     target,
                     fix,
+                    allow_dirty,
                     tools,
                     category,
                     sarif,
@@ -721,6 +1250,7 @@ fn main() {
                             target.as_deref(),
                             root.as_deref(),
                             fix,
+                            allow_dirty,
                             tools.as_deref(),
                             category.as_deref(),
                             cli.json,
@@ -730,6 +1260,7 @@ fn main() {
                             target.as_deref(),
                             root.as_deref(),
                             fix,
+                            allow_dirty,
                             tools.as_deref(),
                             category.as_deref(),
                             sarif,
@@ -755,18 +1286,25 @@ fn main() {
                     );
                     0
                 } else {
-                    let root = root.unwrap_or_else(|| std::path::PathBuf::from("."));
+                    let root = root.unwrap_or_else(paths::current_project_root);
                     let rt = tokio::runtime::Runtime::new().unwrap();
                     rt.block_on(serve::http::run_http_server(&root, port))
                 }
             }
-            ServeProtocol::Lsp => {
+            ServeProtocol::Lsp { position_encoding } => {
+                let Some(encoding) = serve::lsp::PositionEncoding::parse(&position_encoding) else {
+                    eprintln!(
+                        "Unknown position encoding: {}. Use utf-8, utf-16, or utf-32",
+                        position_encoding
+                    );
+                    std::process::exit(1);
+                };
                 let rt = tokio::runtime::Runtime::new().unwrap();
-                rt.block_on(serve::lsp::run_lsp_server(root.as_deref()))
+                rt.block_on(serve::lsp::run_lsp_server(root.as_deref(), encoding))
             }
         },
         Commands::Generate { target } => match target {
-            GenerateTarget::Client { spec, lang, output } => {
+            GenerateTarget::Client { spec, lang } => {
                 let Some(generator) = moss_openapi::find_generator(&lang) else {
                     eprintln!("Unknown language: {}. Available:", lang);
                     for (lang, variant) in moss_openapi::list_generators() {
@@ -792,23 +1330,9 @@ fn main() {
 
                 let code = generator.generate(&spec_json);
 
-                if let Some(path) = output {
-                    if let Err(e) = std::fs::write(&path, &code) {
-                        eprintln!("Failed to write {}: {}", path.display(), e);
-                        std::process::exit(1);
-                    }
-                    eprintln!("Generated {}", path.display());
-                } else {
-                    print!("{}", code);
-                }
-                0
+                write_generated(&code, cli.output.as_deref())
             }
-            GenerateTarget::Types {
-                schema,
-                name,
-                lang,
-                output,
-            } => {
+            GenerateTarget::Types { schema, name, lang } => {
                 let Some(generator) = moss_jsonschema::find_generator(&lang) else {
                     eprintln!("Unknown language: {}. Available:", lang);
                     for l in moss_jsonschema::list_generators() {
@@ -834,16 +1358,7 @@ fn main() {
 
                 let code = generator.generate(&schema_json, &name);
 
-                if let Some(path) = output {
-                    if let Err(e) = std::fs::write(&path, &code) {
-                        eprintln!("Failed to write {}: {}", path.display(), e);
-                        std::process::exit(1);
-                    }
-                    eprintln!("Generated {}", path.display());
-                } else {
-                    print!("{}", code);
-                }
-                0
+                write_generated(&code, cli.output.as_deref())
             }
         },
     };
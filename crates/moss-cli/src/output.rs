@@ -102,6 +102,68 @@ pub fn apply_jq(value: &serde_json::Value, filter: &str) -> Result<Vec<String>,
     Ok(results)
 }
 
+/// The `--json` envelope for commands that return a list of results:
+/// `{"results": [...], "error": null}` when the command ran, even if nothing
+/// matched, and `{"results": null, "error": {"kind", "message"}}` when it failed.
+///
+/// Used by `path`, `signature`, `similar`, `package symbol` and `symbols --all`;
+/// `view` uses the error form for an invalid line range. Report commands
+/// (`view`, `analyze`, `lint`, `symbols --tags`, ...) print a single object
+/// and don't wrap it.
+pub fn json_results<T: Serialize>(results: &[T]) -> serde_json::Value {
+    serde_json::json!({ "results": results, "error": null })
+}
+
+/// The failure form of [`json_results`]. `kind` is a stable identifier to
+/// branch on, such as `not_found` or `index`.
+pub fn json_error(kind: &str, message: &str) -> serde_json::Value {
+    serde_json::json!({
+        "results": null,
+        "error": { "kind": kind, "message": message },
+    })
+}
+
+/// Print [`json_results`]. Exit code is 0 with results, 1 without.
+pub fn print_json_results<T: Serialize>(results: &[T]) -> i32 {
+    println!("{}", json_results(results));
+    if results.is_empty() {
+        1
+    } else {
+        0
+    }
+}
+
+/// Print [`json_error`] and return exit code 1.
+pub fn print_json_error(kind: &str, message: &str) -> i32 {
+    println!("{}", json_error(kind, message));
+    1
+}
+
+/// Route everything the process writes to stdout into `path` (for `--output`).
+///
+/// Redirects the file descriptor itself, so every `println!` in every command
+/// lands in the file while stderr stays on the terminal.
+#[cfg(unix)]
+pub fn redirect_stdout(path: &std::path::Path) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::io::AsRawFd;
+
+    let file = std::fs::File::create(path)?;
+    std::io::stdout().flush()?;
+    if unsafe { libc::dup2(file.as_raw_fd(), libc::STDOUT_FILENO) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn redirect_stdout(_path: &std::path::Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "--output is not supported on this platform",
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,4 +203,22 @@ mod tests {
         let results = apply_jq(&value, ".count").unwrap();
         assert_eq!(results, vec!["42"]);
     }
+
+    #[test]
+    fn test_empty_results_differ_from_error() {
+        let empty = json_results::<TestOutput>(&[]);
+        assert_eq!(empty["results"], serde_json::json!([]));
+        assert!(empty["error"].is_null());
+
+        let error = json_error("not_found", "Function not found: foo");
+        assert!(error["results"].is_null());
+        assert_eq!(error["error"]["kind"], "not_found");
+        assert_ne!(empty, error);
+
+        let found = json_results(&[TestOutput {
+            name: "a".to_string(),
+            count: 1,
+        }]);
+        assert_eq!(found["results"][0]["name"], "a");
+    }
 }
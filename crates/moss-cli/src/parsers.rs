@@ -1,7 +1,10 @@
 //! Tree-sitter parser initialization and management.
 
-use arborium::tree_sitter::Parser;
+use arborium::tree_sitter::{InputEdit, Parser, Tree};
 use arborium::GrammarStore;
+use moss_languages::support_for_path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Collection of tree-sitter parsers using arborium's grammar store.
@@ -45,3 +48,113 @@ impl Default for Parsers {
         Self::new()
     }
 }
+
+/// A file's current content and the tree parsed from it.
+pub struct CachedTree {
+    pub content: String,
+    pub tree: Tree,
+    grammar: String,
+}
+
+/// Last parse of each open file, keyed by path.
+///
+/// Edits are applied to the stored tree so the next parse only revisits the
+/// changed region instead of the whole file.
+#[derive(Default)]
+pub struct TreeCache {
+    parsers: Parsers,
+    entries: HashMap<PathBuf, CachedTree>,
+}
+
+impl TreeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a file from scratch and cache the result.
+    /// Returns None for files without a supported grammar.
+    pub fn open(&mut self, path: &Path, content: String) -> Option<&CachedTree> {
+        let grammar = support_for_path(path)?.grammar_name().to_string();
+        let tree = self.parsers.parse_with_grammar(&grammar, &content)?;
+        self.entries.insert(
+            path.to_path_buf(),
+            CachedTree {
+                content,
+                tree,
+                grammar,
+            },
+        );
+        self.entries.get(path)
+    }
+
+    /// Apply `edit` to the cached tree and reparse `new_content` incrementally.
+    /// Falls back to a full parse if the file isn't cached.
+    pub fn update(
+        &mut self,
+        path: &Path,
+        edit: &InputEdit,
+        new_content: String,
+    ) -> Option<&CachedTree> {
+        if !self.entries.contains_key(path) {
+            return self.open(path, new_content);
+        }
+        let entry = self.entries.get_mut(path)?;
+        entry.tree.edit(edit);
+        let reparsed = self
+            .parsers
+            .parser_for(&entry.grammar)
+            .and_then(|mut parser| parser.parse(&new_content, Some(&entry.tree)));
+        let Some(tree) = reparsed else {
+            // The edited tree no longer matches any content we hold: drop it.
+            self.entries.remove(path);
+            return None;
+        };
+        let entry = self.entries.get_mut(path)?;
+        entry.tree = tree;
+        entry.content = new_content;
+        Some(entry)
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&CachedTree> {
+        self.entries.get(path)
+    }
+
+    pub fn close(&mut self, path: &Path) {
+        self.entries.remove(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arborium::tree_sitter::Point;
+
+    #[test]
+    fn test_incremental_update_matches_full_parse() {
+        let path = Path::new("a.py");
+        let old = "def foo():\n    return 1\n";
+        let new = "def foo():\n    return 1\n\ndef bar(x):\n    return x\n";
+
+        let mut cache = TreeCache::new();
+        cache.open(path, old.to_string()).unwrap();
+        let edit = InputEdit {
+            start_byte: old.len(),
+            old_end_byte: old.len(),
+            new_end_byte: new.len(),
+            start_position: Point::new(2, 0),
+            old_end_position: Point::new(2, 0),
+            new_end_position: Point::new(5, 0),
+        };
+        let updated = cache.update(path, &edit, new.to_string()).unwrap();
+        assert_eq!(updated.content, new);
+
+        let full = Parsers::new().parse_with_grammar("python", new).unwrap();
+        assert_eq!(
+            updated.tree.root_node().to_sexp(),
+            full.root_node().to_sexp()
+        );
+
+        cache.close(path);
+        assert!(cache.get(path).is_none());
+    }
+}
@@ -1,8 +1,9 @@
-use ignore::WalkBuilder;
+use crate::walk::project_walker;
 use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
 use nucleo_matcher::{Config, Matcher};
 use std::path::Path;
 
+use crate::config::{FuzzyCase, FuzzyConfig, MossConfig};
 use crate::index::FileIndex;
 
 #[derive(Debug, Clone)]
@@ -10,6 +11,28 @@ pub struct PathMatch {
     pub path: String,
     pub kind: String,
     pub score: u32,
+    pub tier: MatchTier,
+}
+
+/// Which resolution tier produced a match, strongest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchTier {
+    /// The query is the path (modulo `_`/`-`/`.` and case)
+    Exact,
+    /// The query is the file or directory name, or its stem
+    Name,
+    /// Fuzzy match on the whole path
+    Fuzzy,
+}
+
+impl MatchTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MatchTier::Exact => "exact",
+            MatchTier::Name => "name",
+            MatchTier::Fuzzy => "fuzzy",
+        }
+    }
 }
 
 /// Result of resolving a unified path like `src/main.py/Foo/bar`
@@ -42,6 +65,25 @@ fn normalize_separators(query: &str) -> String {
         .collect::<String>()
 }
 
+/// Re-root a relative query typed from below `root`, the way git treats paths
+/// given in a subdirectory: when the working directory is inside the project
+/// and the query's first segment exists there, the query is relative to it.
+fn from_working_dir(segments: &[&str], root: &Path) -> Option<String> {
+    let cwd = std::env::current_dir().ok()?;
+    let prefix = cwd.strip_prefix(root).ok()?.to_str()?;
+    let first = segments.first()?;
+    if prefix.is_empty() || !cwd.join(first).exists() {
+        return None;
+    }
+    let rest = segments.iter().filter(|s| **s != ".");
+    Some(
+        std::iter::once(prefix)
+            .chain(rest.copied())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
 /// Resolve a unified path like `src/main.py/Foo/bar` to file + symbol components.
 ///
 /// Uses filesystem as source of truth: walks segments left-to-right, checking
@@ -64,6 +106,13 @@ pub fn resolve_unified(query: &str, root: &Path) -> Option<UnifiedPath> {
         (segs, root.to_path_buf())
     };
     let is_absolute = normalized.starts_with('/');
+    let rerooted = (!is_absolute)
+        .then(|| from_working_dir(&segments, root))
+        .flatten();
+    let segments: Vec<&str> = match &rerooted {
+        Some(path) => path.split('/').collect(),
+        None => segments,
+    };
 
     if segments.is_empty() {
         return None;
@@ -163,6 +212,11 @@ pub fn resolve_unified_all(query: &str, root: &Path) -> Vec<UnifiedPath> {
     }
 
     let segments: Vec<&str> = normalized.split('/').filter(|s| !s.is_empty()).collect();
+    let rerooted = from_working_dir(&segments, root);
+    let segments: Vec<&str> = match &rerooted {
+        Some(path) => path.split('/').collect(),
+        None => segments,
+    };
     if segments.is_empty() {
         return vec![];
     }
@@ -227,6 +281,63 @@ pub fn resolve_unified_all(query: &str, root: &Path) -> Vec<UnifiedPath> {
     vec![]
 }
 
+/// Tuning for the fuzzy match tier, from the `[fuzzy]` config section.
+#[derive(Debug, Clone, Copy)]
+pub struct MatchOptions {
+    /// Score matches starting at path separators higher
+    pub match_paths: bool,
+    pub case: FuzzyCase,
+    /// Let ASCII letters match accented ones
+    pub normalize: bool,
+    /// Fuzzy matches kept, best first
+    pub limit: usize,
+    /// Fuzzy matches scoring below this are dropped
+    pub min_score: u32,
+    /// Score file names only, so directories in the path can't match
+    pub basename: bool,
+}
+
+impl MatchOptions {
+    pub fn from_config(config: &FuzzyConfig) -> Self {
+        Self {
+            match_paths: config.match_paths(),
+            case: config.case(),
+            normalize: config.normalize(),
+            limit: config.limit(),
+            min_score: config.min_score(),
+            basename: config.basename(),
+        }
+    }
+
+    pub fn load(root: &Path) -> Self {
+        Self::from_config(&MossConfig::load(root).fuzzy)
+    }
+
+    fn matcher_config(&self) -> Config {
+        if self.match_paths {
+            Config::DEFAULT.match_paths()
+        } else {
+            Config::DEFAULT
+        }
+    }
+
+    fn case_matching(&self) -> CaseMatching {
+        match self.case {
+            FuzzyCase::Ignore => CaseMatching::Ignore,
+            FuzzyCase::Smart => CaseMatching::Smart,
+            FuzzyCase::Respect => CaseMatching::Respect,
+        }
+    }
+
+    fn normalization(&self) -> Normalization {
+        if self.normalize {
+            Normalization::Smart
+        } else {
+            Normalization::Never
+        }
+    }
+}
+
 /// Get all files in the repository (uses index if available)
 pub fn all_files(root: &Path) -> Vec<PathMatch> {
     get_paths_for_query(root, "")
@@ -235,6 +346,7 @@ pub fn all_files(root: &Path) -> Vec<PathMatch> {
             path,
             kind: if is_dir { "directory" } else { "file" }.to_string(),
             score: 0,
+            tier: MatchTier::Fuzzy,
         })
         .collect()
 }
@@ -248,6 +360,31 @@ pub fn all_files(root: &Path) -> Vec<PathMatch> {
 /// - Partial filenames: dwim.py, dwim
 /// - Directory names: moss, src
 pub fn resolve(query: &str, root: &Path) -> Vec<PathMatch> {
+    resolve_tiers(query, root, false, &MatchOptions::load(root))
+}
+
+/// Like [`resolve`], with explicit fuzzy matching options instead of the
+/// project's `[fuzzy]` config. With `all`, returns the union of every match
+/// tier instead of stopping at the first tier that matches; each path appears
+/// once, in its strongest tier.
+pub fn resolve_with_options(
+    query: &str,
+    root: &Path,
+    all: bool,
+    options: &MatchOptions,
+) -> Vec<PathMatch> {
+    resolve_tiers(query, root, all, options)
+}
+
+/// Rank preloaded `(path, is_dir)` pairs against a query with [`resolve`]'s
+/// tiers and scoring, returning matches from every tier. Interactive pickers
+/// load the paths once and call this on each keystroke.
+#[cfg_attr(not(feature = "tui"), allow(dead_code))] // Used by `moss pick`
+pub fn rank_paths(query: &str, paths: &[(String, bool)], options: &MatchOptions) -> Vec<PathMatch> {
+    resolve_from_paths(query, paths, true, options)
+}
+
+fn resolve_tiers(query: &str, root: &Path, all: bool, options: &MatchOptions) -> Vec<PathMatch> {
     // Handle absolute paths first - check if file exists directly
     if query.starts_with('/') {
         let abs_path = std::path::Path::new(query);
@@ -256,12 +393,14 @@ pub fn resolve(query: &str, root: &Path) -> Vec<PathMatch> {
                 path: query.to_string(),
                 kind: "file".to_string(),
                 score: u32::MAX,
+                tier: MatchTier::Exact,
             }];
         } else if abs_path.is_dir() {
             return vec![PathMatch {
                 path: query.to_string(),
                 kind: "directory".to_string(),
                 score: u32::MAX,
+                tier: MatchTier::Exact,
             }];
         }
         // Absolute path doesn't exist - return empty
@@ -271,7 +410,7 @@ pub fn resolve(query: &str, root: &Path) -> Vec<PathMatch> {
     // Handle file:symbol syntax (defer symbol resolution to Python for now)
     if query.contains(':') {
         let file_part = query.split(':').next().unwrap();
-        return resolve(file_part, root);
+        return resolve_tiers(file_part, root, all, options);
     }
 
     // Handle extension patterns (e.g., ".rs", ".py") - return all matches directly
@@ -285,6 +424,7 @@ pub fn resolve(query: &str, root: &Path) -> Vec<PathMatch> {
                         path: f.path,
                         kind: if f.is_dir { "directory" } else { "file" }.to_string(),
                         score: u32::MAX,
+                        tier: MatchTier::Name,
                     })
                     .collect();
             }
@@ -294,7 +434,7 @@ pub fn resolve(query: &str, root: &Path) -> Vec<PathMatch> {
     // Get candidate paths (uses LIKE for fast filtering when possible)
     let all_paths = get_paths_for_query(root, query);
 
-    resolve_from_paths(query, &all_paths)
+    resolve_from_paths(query, &all_paths, all, options)
 }
 
 /// Get paths matching query using LIKE, fallback to all files
@@ -316,19 +456,13 @@ fn get_paths_for_query(root: &Path, query: &str) -> Vec<(String, bool)> {
     }
     // Fall back to filesystem walk
     let mut all_paths: Vec<(String, bool)> = Vec::new();
-    let walker = WalkBuilder::new(root)
-        .hidden(false)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true)
-        .build();
+    let walker = project_walker(root, &MossConfig::load(root).walk).build();
 
     for entry in walker.flatten() {
         let path = entry.path();
         if let Ok(rel) = path.strip_prefix(root) {
             let rel_str = rel.to_string_lossy().to_string();
-            // Skip empty paths and .git directory
-            if rel_str.is_empty() || rel_str == ".git" || rel_str.starts_with(".git/") {
+            if rel_str.is_empty() {
                 continue;
             }
             let is_dir = path.is_dir();
@@ -366,24 +500,47 @@ fn normalize_for_match(s: &str) -> String {
     s.chars().map(normalize_char).collect()
 }
 
-/// Resolve from a pre-loaded list of paths
-fn resolve_from_paths(query: &str, all_paths: &[(String, bool)]) -> Vec<PathMatch> {
+/// Order matches by score (highest first), breaking ties by shorter path, then path.
+/// Walk order differs across platforms and runs, so ties must not depend on it.
+fn compare_matches(a: &PathMatch, b: &PathMatch) -> std::cmp::Ordering {
+    b.score
+        .cmp(&a.score)
+        .then_with(|| a.path.len().cmp(&b.path.len()))
+        .then_with(|| a.path.cmp(&b.path))
+}
+
+/// Resolve from a pre-loaded list of paths.
+///
+/// Tiers are tried in order (exact path, exact name, fuzzy); unless `all` is set,
+/// the first tier with matches wins.
+fn resolve_from_paths(
+    query: &str,
+    all_paths: &[(String, bool)],
+    all: bool,
+    options: &MatchOptions,
+) -> Vec<PathMatch> {
     let query_lower = query.to_lowercase();
     let query_normalized = normalize_for_match(query);
+    let mut matches: Vec<PathMatch> = Vec::new();
 
     // Try normalized path match (handles exact match too, no allocation)
     for (path, is_dir) in all_paths {
         if eq_normalized(path, query) {
-            return vec![PathMatch {
+            matches.push(PathMatch {
                 path: path.clone(),
                 kind: if *is_dir { "directory" } else { "file" }.to_string(),
                 score: u32::MAX,
-            }];
+                tier: MatchTier::Exact,
+            });
+            break;
         }
     }
 
+    if !all && !matches.is_empty() {
+        return matches;
+    }
+
     // Try exact filename/dirname match (case-insensitive, _ and - equivalent)
-    let mut exact_matches: Vec<PathMatch> = Vec::new();
     for (path, is_dir) in all_paths {
         let name = Path::new(path)
             .file_name()
@@ -396,47 +553,96 @@ fn resolve_from_paths(query: &str, all_paths: &[(String, bool)]) -> Vec<PathMatc
         let name_normalized = normalize_for_match(&name);
         let stem_normalized = normalize_for_match(&stem);
 
-        if name == query_lower
+        if (name == query_lower
             || stem == query_lower
             || name_normalized == query_normalized
-            || stem_normalized == query_normalized
+            || stem_normalized == query_normalized)
+            && !matches.iter().any(|m| &m.path == path)
         {
-            exact_matches.push(PathMatch {
+            matches.push(PathMatch {
                 path: path.clone(),
                 kind: if *is_dir { "directory" } else { "file" }.to_string(),
                 score: u32::MAX - 1,
+                tier: MatchTier::Name,
             });
         }
     }
 
-    if !exact_matches.is_empty() {
-        return exact_matches;
+    matches.sort_by(compare_matches);
+
+    if !all && !matches.is_empty() {
+        return matches;
     }
 
     // Fuzzy match using nucleo
-    let mut matcher = Matcher::new(Config::DEFAULT);
-    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+    let mut matcher = Matcher::new(options.matcher_config());
+    let pattern = Pattern::parse(query, options.case_matching(), options.normalization());
+    // A query with `/` matches segment by segment against path components
+    let segments: Vec<Pattern> = if query.contains('/') && !options.basename {
+        query
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .map(|s| Pattern::parse(s, options.case_matching(), options.normalization()))
+            .collect()
+    } else {
+        Vec::new()
+    };
 
     let mut fuzzy_matches: Vec<PathMatch> = Vec::new();
 
     for (path, is_dir) in all_paths {
-        let mut buf = Vec::new();
-        if let Some(score) =
-            pattern.score(nucleo_matcher::Utf32Str::new(path, &mut buf), &mut matcher)
-        {
+        if matches.iter().any(|m| &m.path == path) {
+            continue;
+        }
+        let haystack = if options.basename {
+            path.rsplit('/').next().unwrap_or(path)
+        } else {
+            path
+        };
+        let score = if segments.is_empty() {
+            let mut buf = Vec::new();
+            pattern.score(
+                nucleo_matcher::Utf32Str::new(haystack, &mut buf),
+                &mut matcher,
+            )
+        } else {
+            component_score(&segments, path, &mut matcher)
+        };
+        if let Some(score) = score.filter(|&s| s >= options.min_score) {
             fuzzy_matches.push(PathMatch {
                 path: path.clone(),
                 kind: if *is_dir { "directory" } else { "file" }.to_string(),
                 score,
+                tier: MatchTier::Fuzzy,
             });
         }
     }
 
-    // Sort by score descending, take top 10
-    fuzzy_matches.sort_by(|a, b| b.score.cmp(&a.score));
-    fuzzy_matches.truncate(10);
+    // Sort by score descending, keep the best
+    fuzzy_matches.sort_by(compare_matches);
+    fuzzy_matches.truncate(options.limit);
+
+    matches.extend(fuzzy_matches);
+    matches
+}
 
-    fuzzy_matches
+/// Total score of matching each query segment to its own path component,
+/// in order, maximized over placements. None if some segment has no place.
+fn component_score(segments: &[Pattern], path: &str, matcher: &mut Matcher) -> Option<u32> {
+    let components: Vec<&str> = path.split('/').collect();
+    let mut buf = Vec::new();
+    // best[c]: best total for the segments placed so far, within components[..c]
+    let mut best: Vec<Option<u32>> = vec![Some(0); components.len() + 1];
+    for segment in segments {
+        let mut next = vec![None; components.len() + 1];
+        for (c, component) in components.iter().enumerate() {
+            let score = segment.score(nucleo_matcher::Utf32Str::new(component, &mut buf), matcher);
+            let placed = best[c].zip(score).map(|(total, score)| total + score);
+            next[c + 1] = next[c].max(placed);
+        }
+        best = next;
+    }
+    best[components.len()]
 }
 
 #[cfg(test)]
@@ -500,6 +706,181 @@ mod tests {
         assert_eq!(matches[0].path, "docs/prior-art.md");
     }
 
+    #[test]
+    fn test_fuzzy_tie_breaking() {
+        let paths: Vec<(String, bool)> = [
+            "src/zeta/handler.rs",
+            "src/beta/handler.rs",
+            "src/alpha/handler.rs",
+            "lib/handler.rs",
+        ]
+        .iter()
+        .map(|p| (p.to_string(), false))
+        .collect();
+
+        let expected = vec![
+            "lib/handler.rs",
+            "src/beta/handler.rs",
+            "src/zeta/handler.rs",
+            "src/alpha/handler.rs",
+        ];
+
+        // Same order regardless of input order
+        let options = MatchOptions::from_config(&FuzzyConfig::default());
+        let mut reversed = paths.clone();
+        reversed.reverse();
+        for input in [paths, reversed] {
+            let matches = resolve_from_paths("hndlr", &input, false, &options);
+            let got: Vec<_> = matches.iter().map(|m| m.path.as_str()).collect();
+            assert_eq!(got, expected);
+        }
+    }
+
+    #[test]
+    fn test_match_paths_prefers_directory_boundaries() {
+        let paths: Vec<(String, bool)> = ["my src main.rs", "src/main.rs", "s r c/main.rs"]
+            .iter()
+            .map(|p| (p.to_string(), false))
+            .collect();
+        let mut options = MatchOptions::from_config(&FuzzyConfig::default());
+        assert!(options.match_paths);
+
+        // "src" then "main" right after a separator beats the same run after spaces
+        let matches = resolve_from_paths("srcmain", &paths, false, &options);
+        assert_eq!(matches[0].path, "src/main.rs");
+
+        // Plain matching treats whitespace as the strongest boundary
+        options.match_paths = false;
+        let matches = resolve_from_paths("srcmain", &paths, false, &options);
+        assert_eq!(matches[0].path, "my src main.rs");
+    }
+
+    #[test]
+    fn test_rank_paths_per_keystroke() {
+        let paths: Vec<(String, bool)> = [
+            ("src/main.rs", false),
+            ("src/commands/pick.rs", false),
+            ("src/path_resolve.rs", false),
+            ("src", true),
+        ]
+        .iter()
+        .map(|(p, d)| (p.to_string(), *d))
+        .collect();
+        let options = MatchOptions::from_config(&FuzzyConfig::default());
+
+        // The exact name ranks first and fuzzy matches from later tiers follow
+        let matches = rank_paths("pick", &paths, &options);
+        assert_eq!(matches[0].path, "src/commands/pick.rs");
+        assert_eq!(matches[0].tier, MatchTier::Name);
+
+        // Each extra character narrows the list
+        let broad = rank_paths("sr", &paths, &options).len();
+        let narrow = rank_paths("srpr", &paths, &options);
+        assert!(narrow.len() < broad);
+        assert_eq!(narrow[0].path, "src/path_resolve.rs");
+        assert!(rank_paths("zzz", &paths, &options).is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_limit_score_and_basename() {
+        let paths: Vec<(String, bool)> = (0..15)
+            .map(|i| (format!("parsers/m{}.rs", i), false))
+            .chain([("src/parse.rs".to_string(), false)])
+            .collect();
+        let mut options = MatchOptions::from_config(&FuzzyConfig::default());
+
+        // Default keeps 10; "prs" matches every path through "parsers/"
+        assert_eq!(resolve_from_paths("prs", &paths, false, &options).len(), 10);
+        options.limit = 20;
+        let all = resolve_from_paths("prs", &paths, false, &options);
+        assert_eq!(all.len(), 16);
+
+        // Only the file name is scored, so parsers/ no longer matches
+        options.basename = true;
+        let matches = resolve_from_paths("prs", &paths, false, &options);
+        let got: Vec<_> = matches.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(got, vec!["src/parse.rs"]);
+
+        options.basename = false;
+        options.min_score = all[0].score + 1;
+        assert!(resolve_from_paths("prs", &paths, false, &options).is_empty());
+    }
+
+    #[test]
+    fn test_segments_match_path_components() {
+        let paths: Vec<(String, bool)> = [
+            "cl/i/main.rs",
+            "crates/moss-cli/src/main.rs",
+            "crates/moss-languages/src/lib.rs",
+            "docs/main.md",
+        ]
+        .iter()
+        .map(|p| (p.to_string(), false))
+        .collect();
+        let options = MatchOptions::from_config(&FuzzyConfig::default());
+        let ranked = |query: &str| -> Vec<String> {
+            resolve_from_paths(query, &paths, false, &options)
+                .into_iter()
+                .map(|m| m.path)
+                .collect()
+        };
+
+        // "cli" must fit inside one component, so cl/i/ doesn't match
+        assert_eq!(ranked("cli/main"), vec!["crates/moss-cli/src/main.rs"]);
+        // One segment per component, in order
+        assert_eq!(ranked("c/m/main"), vec!["crates/moss-cli/src/main.rs"]);
+        assert!(ranked("main/c").is_empty());
+        // Without a slash the whole path is scored as before
+        assert!(ranked("climain").contains(&"cl/i/main.rs".to_string()));
+    }
+
+    #[test]
+    fn test_case_respect() {
+        let paths: Vec<(String, bool)> = ["src/Parser.rs", "src/parser_util.rs"]
+            .iter()
+            .map(|p| (p.to_string(), false))
+            .collect();
+        let mut options = MatchOptions::from_config(&FuzzyConfig::default());
+        options.case = FuzzyCase::Respect;
+        let matches = resolve_from_paths("Prsr", &paths, false, &options);
+        let got: Vec<_> = matches.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(got, vec!["src/Parser.rs"]);
+    }
+
+    #[test]
+    fn test_resolve_all_tiers() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/a")).unwrap();
+        fs::create_dir_all(dir.path().join("src/b")).unwrap();
+        fs::write(dir.path().join("mod.rs"), "").unwrap();
+        fs::write(dir.path().join("src/a/mod.rs"), "").unwrap();
+        fs::write(dir.path().join("src/b/mod.rs"), "").unwrap();
+
+        // Default: the exact path match wins
+        let matches = resolve("mod.rs", dir.path());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].path, "mod.rs");
+        assert_eq!(matches[0].tier, MatchTier::Exact);
+
+        // --all: every mod.rs, each once, with its tier
+        let matches =
+            resolve_with_options("mod.rs", dir.path(), true, &MatchOptions::load(dir.path()));
+        let mut names: Vec<_> = matches
+            .iter()
+            .filter(|m| m.path.ends_with("mod.rs"))
+            .map(|m| (m.path.as_str(), m.tier))
+            .collect();
+        names.sort_by_key(|(path, _)| *path);
+        assert_eq!(
+            names,
+            vec![
+                ("mod.rs", MatchTier::Exact),
+                ("src/a/mod.rs", MatchTier::Name),
+                ("src/b/mod.rs", MatchTier::Name),
+            ]
+        );
+    }
+
     #[test]
     fn test_unified_path_file_only() {
         let dir = tempdir().unwrap();
@@ -1,4 +1,4 @@
-//! Path utilities for moss data directories.
+//! Path utilities for moss data directories and project roots.
 //!
 //! Supports external index locations via MOSS_INDEX_DIR environment variable.
 //! This allows repos without `.moss` in `.gitignore` to store indexes elsewhere.
@@ -35,6 +35,35 @@ pub fn get_moss_dir(root: &Path) -> PathBuf {
     root.join(".moss")
 }
 
+/// Find the project root containing `start`, the way git finds a repository.
+///
+/// Precedence, each searched upwards from `start`:
+/// 1. The nearest directory with a `.moss/` (within the VCS root, if any -
+///    one above it belongs to something else, like a stray index in $HOME)
+/// 2. The VCS root (a directory with `.git`)
+/// 3. The nearest directory with an ecosystem manifest (Cargo.toml, package.json, ...)
+/// 4. `start` itself
+pub fn find_project_root(start: &Path) -> PathBuf {
+    let vcs_root = start.ancestors().find(|dir| dir.join(".git").exists());
+    start
+        .ancestors()
+        .find(|dir| vcs_root.is_none_or(|vcs| dir.starts_with(vcs)) && dir.join(".moss").is_dir())
+        .or(vcs_root)
+        .or_else(|| {
+            start
+                .ancestors()
+                .find(|dir| moss_packages::detect_ecosystem(dir).is_some())
+        })
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+/// The project root for the working directory, used when `--root` isn't given.
+pub fn current_project_root() -> PathBuf {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    find_project_root(&cwd)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +96,23 @@ mod tests {
         env::remove_var("MOSS_INDEX_DIR");
         env::remove_var("XDG_DATA_HOME");
     }
+
+    #[test]
+    fn test_find_project_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let deep = root.join("crates/a/src/bin");
+        std::fs::create_dir_all(&deep).unwrap();
+        assert_eq!(find_project_root(&deep), deep);
+
+        std::fs::write(root.join("crates/a/Cargo.toml"), "[package]\n").unwrap();
+        assert_eq!(find_project_root(&deep), root.join("crates/a"));
+
+        std::fs::create_dir(root.join(".git")).unwrap();
+        assert_eq!(find_project_root(&deep), root);
+
+        std::fs::create_dir(root.join("crates/.moss")).unwrap();
+        assert_eq!(find_project_root(&deep), root.join("crates"));
+        assert_eq!(find_project_root(root), root);
+    }
 }
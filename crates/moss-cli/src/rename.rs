@@ -0,0 +1,494 @@
+//! Renaming a symbol across the project.
+//!
+//! The index says where the definition is and which call sites and imports
+//! name it; the edits land on the identifier nodes tree-sitter finds on
+//! those lines. A rename only goes ahead when every reference can be tied
+//! to the one definition - anything the call graph can't resolve (a method
+//! call on an unknown receiver, a second definition with the same name)
+//! stops it, as does the new name already being in use in a touched file or
+//! the old name being used there in a way the index doesn't record (passed
+//! as a value, a decorator, a string in `__all__`).
+
+use crate::index::FileIndex;
+use crate::parsers::Parsers;
+use moss_languages::support_for_path;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Receivers that refer to the enclosing type from inside its own file.
+const SELF_QUALIFIERS: &[&str] = &["self", "this", "cls", "Self"];
+
+/// Which references a rename touches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameScope {
+    /// Only the file with the definition
+    File,
+    /// Every file that references the symbol
+    Project,
+}
+
+impl FromStr for RenameScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "file" => Ok(RenameScope::File),
+            "project" => Ok(RenameScope::Project),
+            _ => Err(format!("unknown scope '{}' (expected file or project)", s)),
+        }
+    }
+}
+
+/// A line as it reads before and after the rename.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LineChange {
+    pub line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+/// The renamed content of one file.
+#[derive(Debug, Clone)]
+pub struct FileRename {
+    pub file: String,
+    pub content: String,
+    pub changes: Vec<LineChange>,
+}
+
+/// Everything a rename would change.
+#[derive(Debug, Clone)]
+pub struct RenamePlan {
+    pub files: Vec<FileRename>,
+    /// References left alone because they're outside the scope, as file:line
+    pub skipped: Vec<String>,
+}
+
+/// Work out the edits renaming `old` (defined in `file`) to `new`.
+pub fn plan_rename(
+    index: &FileIndex,
+    root: &Path,
+    file: &str,
+    old: &str,
+    new: &str,
+    scope: RenameScope,
+) -> Result<RenamePlan, String> {
+    if !is_identifier(new) {
+        return Err(format!("'{}' is not a valid identifier", new));
+    }
+    if new == old {
+        return Err(format!("'{}' already has that name", old));
+    }
+
+    let mut definitions = index.find_symbol(old).map_err(|e| e.to_string())?;
+    // Keys in config files (TOML, JSON, YAML) share names with code but can't be called
+    definitions.retain(|(def_file, ..)| {
+        support_for_path(Path::new(def_file)).is_some_and(|s| !s.function_kinds().is_empty())
+    });
+    let (def_start, def_end) = match definitions.as_slice() {
+        [(def_file, _, start, end)] if def_file == file => (*start, *end),
+        [] => return Err(format!("No definition of '{}' in the index", old)),
+        [(def_file, _, start, _)] => {
+            return Err(format!(
+                "'{}' is defined in {}:{}, not {}",
+                old, def_file, start, file
+            ))
+        }
+        many => {
+            let sites: Vec<String> = many
+                .iter()
+                .map(|(f, _, start, _)| format!("{}:{}", f, start))
+                .collect();
+            return Err(format!(
+                "'{}' has {} definitions, so references are ambiguous: {}",
+                old,
+                many.len(),
+                sites.join(", ")
+            ));
+        }
+    };
+
+    // Reference lines per file; the definition is placed separately
+    let mut references: BTreeMap<String, BTreeSet<usize>> = BTreeMap::new();
+    // Files where `old` is in scope unqualified through an import
+    let mut importers = HashSet::new();
+    for import in index.imports_named(old).map_err(|e| e.to_string())? {
+        let Some(module) = &import.module else {
+            continue;
+        };
+        if index
+            .resolve_module(module, &import.file)
+            .iter()
+            .any(|f| f == file)
+        {
+            if import.alias.is_none() {
+                importers.insert(import.file.clone());
+            }
+            references
+                .entry(import.file)
+                .or_default()
+                .insert(import.line);
+        }
+    }
+
+    let mut ambiguous = Vec::new();
+    for call in index.calls_named(old).map_err(|e| e.to_string())? {
+        let resolved = match call.qualifier.as_deref() {
+            None => call.file == file || importers.contains(&call.file),
+            Some(q) if SELF_QUALIFIERS.contains(&q) => call.file == file,
+            Some(q) => module_alias_resolves(index, &call.file, q, file)?,
+        };
+        if resolved {
+            references.entry(call.file).or_default().insert(call.line);
+        } else {
+            ambiguous.push(format!("{}:{}", call.file, call.line));
+        }
+    }
+    if !ambiguous.is_empty() {
+        return Err(format!(
+            "Can't tell whether these calls refer to {}/{}: {}",
+            file,
+            old,
+            ambiguous.join(", ")
+        ));
+    }
+
+    let mut skipped = Vec::new();
+    if scope == RenameScope::File {
+        references.retain(|f, lines| {
+            if f == file {
+                return true;
+            }
+            skipped.extend(lines.iter().map(|line| format!("{}:{}", f, line)));
+            false
+        });
+    }
+    references.entry(file.to_string()).or_default();
+
+    let parsers = Parsers::new();
+    let mut files = Vec::new();
+    let mut leftovers = Vec::new();
+    for (path, lines) in references {
+        let content = std::fs::read_to_string(root.join(&path))
+            .map_err(|e| format!("Error reading {}: {}", path, e))?;
+        let tokens = leaf_tokens(&parsers, &path, &content)?;
+        let identifiers: Vec<&Token> = tokens.iter().filter(|t| t.is_identifier()).collect();
+
+        if let Some(taken) = identifiers.iter().find(|id| id.text == new) {
+            return Err(format!(
+                "'{}' is already used in {}:{}",
+                new, path, taken.line
+            ));
+        }
+
+        let named_old = |line: usize| {
+            identifiers
+                .iter()
+                .filter(move |id| id.text == old && id.line == line)
+        };
+        let mut ranges = Vec::new();
+        if path == file {
+            let def = identifiers
+                .iter()
+                .find(|id| id.text == old && id.line >= def_start && id.line <= def_end)
+                .ok_or_else(|| stale(&path, def_start))?;
+            ranges.push(def.start);
+        }
+        for &line in &lines {
+            let before = ranges.len();
+            ranges.extend(named_old(line).map(|id| id.start));
+            if ranges.len() == before {
+                return Err(stale(&path, line));
+            }
+        }
+        ranges.sort_unstable();
+        ranges.dedup();
+
+        // Uses of `old` the index doesn't know about would be left dangling
+        leftovers.extend(
+            tokens
+                .iter()
+                .filter(|t| {
+                    t.text == old
+                        && (t.is_identifier() || t.is_string())
+                        && !ranges.contains(&t.start)
+                })
+                .map(|t| format!("{}:{}", path, t.line)),
+        );
+
+        let mut renamed = content.clone();
+        for &start in ranges.iter().rev() {
+            renamed.replace_range(start..start + old.len(), new);
+        }
+
+        let changed_lines: BTreeSet<usize> = identifiers
+            .iter()
+            .filter(|id| ranges.contains(&id.start))
+            .map(|id| id.line)
+            .collect();
+        let before_lines: Vec<&str> = content.lines().collect();
+        let after_lines: Vec<&str> = renamed.lines().collect();
+        let changes = changed_lines
+            .into_iter()
+            .map(|line| LineChange {
+                line,
+                before: before_lines[line - 1].to_string(),
+                after: after_lines[line - 1].to_string(),
+            })
+            .collect();
+
+        files.push(FileRename {
+            file: path,
+            content: renamed,
+            changes,
+        });
+    }
+
+    if !leftovers.is_empty() {
+        leftovers.dedup();
+        return Err(format!(
+            "'{}' is also used where the rename can't follow it (as a value, decorator or string?): {}",
+            old,
+            leftovers.join(", ")
+        ));
+    }
+
+    Ok(RenamePlan { files, skipped })
+}
+
+/// Whether `qualifier` in `caller_file` is an imported module that is `file`.
+fn module_alias_resolves(
+    index: &FileIndex,
+    caller_file: &str,
+    qualifier: &str,
+    file: &str,
+) -> Result<bool, String> {
+    let imports = index.imports_in(caller_file).map_err(|e| e.to_string())?;
+    Ok(imports.iter().any(|import| {
+        if import.alias.as_deref().unwrap_or(&import.name) != qualifier {
+            return false;
+        }
+        // `import mod` names the module itself; `import * as mod from "./m"` names its source
+        let module = match (&import.module, import.name.as_str()) {
+            (None, name) => name,
+            (Some(module), "*") => module,
+            _ => return false,
+        };
+        index
+            .resolve_module(module, caller_file)
+            .iter()
+            .any(|f| f == file)
+    }))
+}
+
+fn stale(file: &str, line: usize) -> String {
+    format!(
+        "Index is out of date: no identifier at {}:{}. Run: moss index rebuild --call-graph",
+        file, line
+    )
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// A leaf token in a parsed file.
+struct Token<'a> {
+    kind: &'static str,
+    text: &'a str,
+    line: usize,
+    start: usize,
+}
+
+impl Token<'_> {
+    /// Plain, field, property, type... identifiers.
+    fn is_identifier(&self) -> bool {
+        self.kind.contains("identifier")
+    }
+
+    /// Text inside a string literal (`string_content`, `string_fragment`).
+    fn is_string(&self) -> bool {
+        self.kind.starts_with("string_")
+    }
+}
+
+/// Every leaf token in `content`.
+fn leaf_tokens<'a>(
+    parsers: &Parsers,
+    path: &str,
+    content: &'a str,
+) -> Result<Vec<Token<'a>>, String> {
+    let support = support_for_path(Path::new(path))
+        .ok_or_else(|| format!("Unsupported language: {}", path))?;
+    let tree = parsers
+        .parse_with_grammar(support.grammar_name(), content)
+        .ok_or_else(|| format!("Failed to parse {}", path))?;
+
+    let mut tokens = Vec::new();
+    let mut cursor = tree.walk();
+    loop {
+        let node = cursor.node();
+        if node.child_count() == 0 {
+            tokens.push(Token {
+                kind: node.kind(),
+                text: &content[node.byte_range()],
+                line: node.start_position().row + 1,
+                start: node.start_byte(),
+            });
+        }
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                return Ok(tokens);
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index_of(files: &[(&str, &str)]) -> (tempfile::TempDir, FileIndex) {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let mut index = FileIndex::open(dir.path()).unwrap();
+        index.refresh().unwrap();
+        index.refresh_call_graph().unwrap();
+        (dir, index)
+    }
+
+    #[test]
+    fn test_rename_across_files() {
+        let (dir, index) = index_of(&[
+            ("util.py", "def parse(s):\n    return s\n\ndef twice(s):\n    return parse(parse(s))\n"),
+            ("main.py", "from util import parse\nimport util\n\ndef main():\n    parse('a')\n    util.parse('b')\n"),
+        ]);
+        let plan = plan_rename(
+            &index,
+            dir.path(),
+            "util.py",
+            "parse",
+            "load",
+            RenameScope::Project,
+        )
+        .unwrap();
+        let main = &plan.files[0];
+        assert_eq!(main.file, "main.py");
+        assert_eq!(
+            main.content,
+            "from util import load\nimport util\n\ndef main():\n    load('a')\n    util.load('b')\n"
+        );
+        let util = &plan.files[1];
+        assert_eq!(
+            util.content,
+            "def load(s):\n    return s\n\ndef twice(s):\n    return load(load(s))\n"
+        );
+        assert_eq!(util.changes.len(), 2);
+        assert_eq!(util.changes[1].before, "    return parse(parse(s))");
+
+        let plan = plan_rename(
+            &index,
+            dir.path(),
+            "util.py",
+            "parse",
+            "load",
+            RenameScope::File,
+        )
+        .unwrap();
+        assert_eq!(plan.files.len(), 1);
+        assert_eq!(plan.skipped, vec!["main.py:1", "main.py:5", "main.py:6"]);
+    }
+
+    #[test]
+    fn test_rename_refuses_ambiguity() {
+        let (dir, index) = index_of(&[
+            ("a.py", "def run():\n    pass\n\ndef go(x):\n    x.run()\n"),
+            (
+                "b.py",
+                "def helper():\n    pass\n\ndef run2():\n    helper()\n",
+            ),
+        ]);
+        // x.run() could be any object's run
+        let err = plan_rename(
+            &index,
+            dir.path(),
+            "a.py",
+            "run",
+            "start",
+            RenameScope::Project,
+        )
+        .unwrap_err();
+        assert!(err.contains("a.py:5"), "{}", err);
+
+        // The new name is already bound in the file
+        let err = plan_rename(
+            &index,
+            dir.path(),
+            "b.py",
+            "helper",
+            "run2",
+            RenameScope::Project,
+        )
+        .unwrap_err();
+        assert!(err.contains("already used in b.py:4"), "{}", err);
+
+        assert!(plan_rename(
+            &index,
+            dir.path(),
+            "b.py",
+            "helper",
+            "2x",
+            RenameScope::File
+        )
+        .is_err());
+        assert!(plan_rename(&index, dir.path(), "a.py", "helper", "h", RenameScope::File).is_err());
+    }
+
+    #[test]
+    fn test_rename_refuses_untracked_uses() {
+        let (dir, index) = index_of(&[
+            (
+                "util.py",
+                "def parse(s):\n    return s\n\ndef all_of(xs):\n    return list(map(parse, xs))\n",
+            ),
+            (
+                "api.py",
+                "__all__ = [\"run\"]\n\ndef run():\n    pass\n\ndef go():\n    run()\n",
+            ),
+        ]);
+        // `parse` passed to map() isn't a call the index records
+        let err = plan_rename(
+            &index,
+            dir.path(),
+            "util.py",
+            "parse",
+            "load",
+            RenameScope::Project,
+        )
+        .unwrap_err();
+        assert!(err.contains("util.py:5"), "{}", err);
+
+        let err = plan_rename(
+            &index,
+            dir.path(),
+            "api.py",
+            "run",
+            "start",
+            RenameScope::File,
+        )
+        .unwrap_err();
+        assert!(err.contains("api.py:1"), "{}", err);
+    }
+}
@@ -1,31 +1,176 @@
 //! LSP (Language Server Protocol) server for moss.
 //!
 //! Provides IDE integration with document symbols, workspace symbols, and hover.
+//! Open documents are kept parsed and reparsed incrementally as they change.
+//!
+//! Columns are exchanged in the negotiated position encoding (UTF-16 unless
+//! the client supports the one preferred via `--position-encoding`), and
+//! converted to byte offsets before touching line text.
 
-use crate::index::FileIndex;
+use crate::index::{CallerScope, FileIndex};
+use crate::parsers::TreeCache;
 use crate::skeleton::SkeletonExtractor;
+use arborium::tree_sitter::{InputEdit, Point};
 use std::path::PathBuf;
 use std::sync::Mutex;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 
+/// Unit that LSP `Position.character` counts in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionEncoding {
+    Utf8,
+    Utf16,
+    Utf32,
+}
+
+impl PositionEncoding {
+    /// Parse an encoding name as used on the command line and in LSP.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Self::Utf8),
+            "utf-16" | "utf16" => Some(Self::Utf16),
+            "utf-32" | "utf32" => Some(Self::Utf32),
+            _ => None,
+        }
+    }
+
+    fn kind(self) -> PositionEncodingKind {
+        match self {
+            Self::Utf8 => PositionEncodingKind::UTF8,
+            Self::Utf16 => PositionEncodingKind::UTF16,
+            Self::Utf32 => PositionEncodingKind::UTF32,
+        }
+    }
+
+    /// Pick `preferred` if the client offers it, else UTF-16 (which every
+    /// client must support, and is the only option for pre-3.17 clients).
+    fn negotiate(preferred: Self, offered: Option<&[PositionEncodingKind]>) -> Self {
+        match offered {
+            Some(kinds) if kinds.contains(&preferred.kind()) => preferred,
+            _ => Self::Utf16,
+        }
+    }
+
+    /// Column of byte offset `byte` in `line`, in this encoding's code units.
+    pub fn column(self, line: &str, byte: usize) -> usize {
+        let prefix = &line[..floor_char_boundary(line, byte)];
+        match self {
+            Self::Utf8 => prefix.len(),
+            Self::Utf16 => prefix.encode_utf16().count(),
+            Self::Utf32 => prefix.chars().count(),
+        }
+    }
+
+    /// Byte offset of `column` (in this encoding's code units) in `line`.
+    /// Columns past the end clamp to the line length; columns inside a
+    /// character (e.g. between UTF-16 surrogates) round down to its start.
+    pub fn byte_offset(self, line: &str, column: usize) -> usize {
+        let mut units = 0;
+        for (byte, c) in line.char_indices() {
+            let width = match self {
+                Self::Utf8 => c.len_utf8(),
+                Self::Utf16 => c.len_utf16(),
+                Self::Utf32 => 1,
+            };
+            if units + width > column {
+                return byte;
+            }
+            units += width;
+        }
+        line.len()
+    }
+}
+
+/// Byte offset of an LSP position in `text`. Positions past the end clamp.
+fn position_offset(text: &str, pos: Position, encoding: PositionEncoding) -> usize {
+    let mut line_start = 0;
+    for _ in 0..pos.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |i| line_start + i);
+    line_start + encoding.byte_offset(&text[line_start..line_end], pos.character as usize)
+}
+
+/// Tree-sitter point (row, byte column) of byte offset `byte` in `text`.
+fn point_at(text: &str, byte: usize) -> Point {
+    let before = &text[..byte];
+    let column = byte - before.rfind('\n').map_or(0, |i| i + 1);
+    Point::new(before.matches('\n').count(), column)
+}
+
+/// A ranged `didChange` edit applied to a document.
+struct AppliedChange {
+    content: String,
+    edit: InputEdit,
+}
+
+/// Replace `range` in `text` with `new_text`, describing the edit for tree-sitter.
+fn apply_change(
+    text: &str,
+    range: Range,
+    new_text: &str,
+    encoding: PositionEncoding,
+) -> AppliedChange {
+    let start = position_offset(text, range.start, encoding);
+    let end = position_offset(text, range.end, encoding).max(start);
+    let content = format!("{}{}{}", &text[..start], new_text, &text[end..]);
+    let new_end = start + new_text.len();
+    let edit = InputEdit {
+        start_byte: start,
+        old_end_byte: end,
+        new_end_byte: new_end,
+        start_position: point_at(text, start),
+        old_end_position: point_at(text, end),
+        new_end_position: point_at(&content, new_end),
+    };
+    AppliedChange { content, edit }
+}
+
+/// Largest char boundary in `s` at or before `index`.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    let mut index = index.min(s.len());
+    while !s.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
 /// Moss LSP backend.
 struct MossBackend {
     client: Client,
     root: Mutex<Option<PathBuf>>,
     index: Mutex<Option<FileIndex>>,
+    /// Parsed open documents, updated incrementally on `didChange`.
+    documents: Mutex<TreeCache>,
+    /// Encoding requested with `--position-encoding`.
+    preferred_encoding: PositionEncoding,
+    /// Encoding negotiated with the client during `initialize`.
+    encoding: Mutex<PositionEncoding>,
 }
 
 impl MossBackend {
-    fn new(client: Client) -> Self {
+    fn new(client: Client, preferred_encoding: PositionEncoding) -> Self {
         Self {
             client,
             root: Mutex::new(None),
             index: Mutex::new(None),
+            documents: Mutex::new(TreeCache::new()),
+            preferred_encoding,
+            encoding: Mutex::new(PositionEncoding::Utf16),
         }
     }
 
+    fn encoding(&self) -> PositionEncoding {
+        *self.encoding.lock().unwrap()
+    }
+
     /// Initialize index for the workspace root.
     fn init_index(&self, root: PathBuf) {
         if let Ok(idx) = FileIndex::open(&root) {
@@ -56,6 +201,14 @@ impl MossBackend {
 #[tower_lsp::async_trait]
 impl LanguageServer for MossBackend {
     async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        let offered = params
+            .capabilities
+            .general
+            .as_ref()
+            .and_then(|g| g.position_encodings.as_deref());
+        let encoding = PositionEncoding::negotiate(self.preferred_encoding, offered);
+        *self.encoding.lock().unwrap() = encoding;
+
         // Get workspace root from params
         if let Some(root_uri) = params.root_uri {
             if let Ok(path) = root_uri.to_file_path() {
@@ -71,6 +224,7 @@ impl LanguageServer for MossBackend {
 
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
+                position_encoding: Some(encoding.kind()),
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
                     TextDocumentSyncKind::INCREMENTAL,
                 )),
@@ -102,6 +256,42 @@ impl LanguageServer for MossBackend {
         Ok(())
     }
 
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        if let Ok(path) = params.text_document.uri.to_file_path() {
+            let mut documents = self.documents.lock().unwrap();
+            documents.open(&path, params.text_document.text);
+        }
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let Ok(path) = params.text_document.uri.to_file_path() else {
+            return;
+        };
+        let encoding = self.encoding();
+        let mut documents = self.documents.lock().unwrap();
+        // Changes apply in order, each to the result of the previous one
+        for change in params.content_changes {
+            match change.range {
+                Some(range) => {
+                    let Some(doc) = documents.get(&path) else {
+                        continue;
+                    };
+                    let applied = apply_change(&doc.content, range, &change.text, encoding);
+                    documents.update(&path, &applied.edit, applied.content);
+                }
+                None => {
+                    documents.open(&path, change.text);
+                }
+            }
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        if let Ok(path) = params.text_document.uri.to_file_path() {
+            self.documents.lock().unwrap().close(&path);
+        }
+    }
+
     async fn document_symbol(
         &self,
         params: DocumentSymbolParams,
@@ -112,15 +302,21 @@ impl LanguageServer for MossBackend {
             Err(_) => return Ok(None),
         };
 
-        // Read file content
-        let content = match std::fs::read_to_string(&file_path) {
-            Ok(c) => c,
-            Err(_) => return Ok(None),
-        };
-
-        // Extract symbols using skeleton extractor
+        // Open documents use their cached tree; others are read from disk
         let extractor = SkeletonExtractor::new();
-        let result = extractor.extract(&file_path, &content);
+        let cached = self
+            .documents
+            .lock()
+            .unwrap()
+            .get(&file_path)
+            .map(|doc| extractor.extract_tree(&file_path, &doc.content, &doc.tree));
+        let result = match cached {
+            Some(result) => result,
+            None => match std::fs::read_to_string(&file_path) {
+                Ok(content) => extractor.extract(&file_path, &content),
+                Err(_) => return Ok(None),
+            },
+        };
 
         // Convert to LSP document symbols (nested structure)
         fn to_document_symbol(sym: &crate::skeleton::SkeletonSymbol) -> DocumentSymbol {
@@ -315,7 +511,9 @@ impl LanguageServer for MossBackend {
         }
 
         let line = lines[line_idx];
-        let col = position.character as usize;
+        let col = self
+            .encoding()
+            .byte_offset(line, position.character as usize);
 
         // Find word boundaries
         let word = extract_word_at_position(line, col);
@@ -387,7 +585,9 @@ impl LanguageServer for MossBackend {
         }
 
         let line = lines[line_idx];
-        let col = position.character as usize;
+        let col = self
+            .encoding()
+            .byte_offset(line, position.character as usize);
         let word = extract_word_at_position(line, col);
         if word.is_empty() {
             return Ok(None);
@@ -428,7 +628,7 @@ impl LanguageServer for MossBackend {
         }
 
         // Find callers (references)
-        if let Ok(callers) = index.find_callers(&word) {
+        if let Ok(callers) = index.find_callers(&word, CallerScope::All) {
             for (file, _caller_name, line) in callers {
                 let target_path = root.join(&file);
                 if let Ok(target_uri) = Url::from_file_path(&target_path) {
@@ -480,7 +680,9 @@ impl LanguageServer for MossBackend {
         }
 
         let line = lines[line_idx];
-        let col = position.character as usize;
+        let col = self
+            .encoding()
+            .byte_offset(line, position.character as usize);
         let word_info = extract_word_with_range(line, col);
 
         if word_info.word.is_empty() {
@@ -502,7 +704,7 @@ impl LanguageServer for MossBackend {
         {
             // Also check if it's a caller (referenced symbol)
             if index
-                .find_callers(&word_info.word)
+                .find_callers(&word_info.word, CallerScope::All)
                 .map(|m| m.is_empty())
                 .unwrap_or(true)
             {
@@ -510,14 +712,15 @@ impl LanguageServer for MossBackend {
             }
         }
 
+        let encoding = self.encoding();
         Ok(Some(PrepareRenameResponse::Range(Range {
             start: Position {
                 line: position.line,
-                character: word_info.start_col as u32,
+                character: encoding.column(line, word_info.start) as u32,
             },
             end: Position {
                 line: position.line,
-                character: word_info.end_col as u32,
+                character: encoding.column(line, word_info.end) as u32,
             },
         })))
     }
@@ -544,13 +747,16 @@ impl LanguageServer for MossBackend {
         }
 
         let line = lines[line_idx];
-        let col = position.character as usize;
+        let col = self
+            .encoding()
+            .byte_offset(line, position.character as usize);
         let old_name = extract_word_with_range(line, col).word;
 
         if old_name.is_empty() {
             return Ok(None);
         }
 
+        let encoding = self.encoding();
         let index = self.index.lock().unwrap();
         let root = self.root.lock().unwrap();
 
@@ -570,9 +776,13 @@ impl LanguageServer for MossBackend {
                 if let Ok(target_uri) = Url::from_file_path(&target_path) {
                     // Read file to find exact position
                     if let Ok(file_content) = std::fs::read_to_string(&target_path) {
-                        if let Some(edit) =
-                            find_rename_edit(&file_content, start_line, &old_name, &new_name)
-                        {
+                        if let Some(edit) = find_rename_edit(
+                            &file_content,
+                            start_line,
+                            &old_name,
+                            &new_name,
+                            encoding,
+                        ) {
                             file_edits.entry(target_uri).or_default().push(edit);
                         }
                     }
@@ -581,13 +791,13 @@ impl LanguageServer for MossBackend {
         }
 
         // Find reference sites (callers)
-        if let Ok(callers) = index.find_callers(&old_name) {
+        if let Ok(callers) = index.find_callers(&old_name, CallerScope::All) {
             for (file, _caller_name, line) in callers {
                 let target_path = root.join(&file);
                 if let Ok(target_uri) = Url::from_file_path(&target_path) {
                     if let Ok(file_content) = std::fs::read_to_string(&target_path) {
                         if let Some(edit) =
-                            find_rename_edit(&file_content, line, &old_name, &new_name)
+                            find_rename_edit(&file_content, line, &old_name, &new_name, encoding)
                         {
                             file_edits.entry(target_uri).or_default().push(edit);
                         }
@@ -611,40 +821,44 @@ impl LanguageServer for MossBackend {
     }
 }
 
-/// Word at a position with its range.
+/// Word at a position with its byte range in the line.
 struct WordAtPosition {
     word: String,
-    start_col: usize,
-    end_col: usize,
+    start: usize,
+    end: usize,
 }
 
-/// Extract the word at a given column position in a line, with start/end positions.
+/// Extract the word around byte offset `col` in a line, with its byte range.
 fn extract_word_with_range(line: &str, col: usize) -> WordAtPosition {
-    let chars: Vec<char> = line.chars().collect();
-    if col >= chars.len() {
+    let col = floor_char_boundary(line, col);
+    if col >= line.len() {
         return WordAtPosition {
             word: String::new(),
-            start_col: 0,
-            end_col: 0,
+            start: 0,
+            end: 0,
         };
     }
 
     // Find start of word
-    let mut start = col;
-    while start > 0 && is_identifier_char(chars[start - 1]) {
-        start -= 1;
-    }
+    let start = line[..col]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_identifier_char(*c))
+        .last()
+        .map(|(i, _)| i)
+        .unwrap_or(col);
 
     // Find end of word
-    let mut end = col;
-    while end < chars.len() && is_identifier_char(chars[end]) {
-        end += 1;
-    }
+    let end = line[col..]
+        .char_indices()
+        .find(|(_, c)| !is_identifier_char(*c))
+        .map(|(i, _)| col + i)
+        .unwrap_or(line.len());
 
     WordAtPosition {
-        word: chars[start..end].iter().collect(),
-        start_col: start,
-        end_col: end,
+        word: line[start..end].to_string(),
+        start,
+        end,
     }
 }
 
@@ -654,6 +868,7 @@ fn find_rename_edit(
     line_num: usize,
     old_name: &str,
     new_name: &str,
+    encoding: PositionEncoding,
 ) -> Option<TextEdit> {
     let lines: Vec<&str> = content.lines().collect();
     let line_idx = line_num.saturating_sub(1);
@@ -668,52 +883,40 @@ fn find_rename_edit(
     let mut pos = 0;
     while let Some(idx) = line[pos..].find(old_name) {
         let abs_idx = pos + idx;
-        let before_ok =
-            abs_idx == 0 || !is_identifier_char(line.chars().nth(abs_idx - 1).unwrap_or(' '));
-        let after_ok = abs_idx + old_name.len() >= line.len()
-            || !is_identifier_char(line.chars().nth(abs_idx + old_name.len()).unwrap_or(' '));
+        let end_idx = abs_idx + old_name.len();
+        let before_ok = !line[..abs_idx]
+            .chars()
+            .next_back()
+            .is_some_and(is_identifier_char);
+        let after_ok = !line[end_idx..]
+            .chars()
+            .next()
+            .is_some_and(is_identifier_char);
 
         if before_ok && after_ok {
             return Some(TextEdit {
                 range: Range {
                     start: Position {
                         line: line_idx as u32,
-                        character: abs_idx as u32,
+                        character: encoding.column(line, abs_idx) as u32,
                     },
                     end: Position {
                         line: line_idx as u32,
-                        character: (abs_idx + old_name.len()) as u32,
+                        character: encoding.column(line, end_idx) as u32,
                     },
                 },
                 new_text: new_name.to_string(),
             });
         }
-        pos = abs_idx + old_name.len();
+        pos = end_idx;
     }
 
     None
 }
 
-/// Extract the word at a given column position in a line.
+/// Extract the word around byte offset `col` in a line.
 fn extract_word_at_position(line: &str, col: usize) -> String {
-    let chars: Vec<char> = line.chars().collect();
-    if col >= chars.len() {
-        return String::new();
-    }
-
-    // Find start of word
-    let mut start = col;
-    while start > 0 && is_identifier_char(chars[start - 1]) {
-        start -= 1;
-    }
-
-    // Find end of word
-    let mut end = col;
-    while end < chars.len() && is_identifier_char(chars[end]) {
-        end += 1;
-    }
-
-    chars[start..end].iter().collect()
+    extract_word_with_range(line, col).word
 }
 
 /// Check if a character is valid in an identifier.
@@ -722,11 +925,14 @@ fn is_identifier_char(c: char) -> bool {
 }
 
 /// Start the LSP server on stdio.
-pub async fn run_lsp_server(root: Option<&std::path::Path>) -> i32 {
+pub async fn run_lsp_server(
+    root: Option<&std::path::Path>,
+    position_encoding: PositionEncoding,
+) -> i32 {
     let stdin = tokio::io::stdin();
     let stdout = tokio::io::stdout();
 
-    let (service, socket) = LspService::new(MossBackend::new);
+    let (service, socket) = LspService::new(|client| MossBackend::new(client, position_encoding));
 
     // If root is provided, initialize early (will be overridden by client's root)
     if let Some(_root) = root {
@@ -736,3 +942,95 @@ pub async fn run_lsp_server(root: Option<&std::path::Path>) -> i32 {
     Server::new(stdin, stdout, socket).serve(service).await;
     0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_encoding_columns() {
+        // "🦀" is 4 bytes in UTF-8, 2 units in UTF-16, 1 char
+        let line = "let 🦀 = crab();";
+        let byte = line.find("crab").unwrap();
+        assert_eq!(byte, 11);
+        assert_eq!(PositionEncoding::Utf8.column(line, byte), 11);
+        assert_eq!(PositionEncoding::Utf16.column(line, byte), 9);
+        assert_eq!(PositionEncoding::Utf32.column(line, byte), 8);
+
+        for encoding in [
+            PositionEncoding::Utf8,
+            PositionEncoding::Utf16,
+            PositionEncoding::Utf32,
+        ] {
+            let column = encoding.column(line, byte);
+            assert_eq!(encoding.byte_offset(line, column), byte);
+        }
+        // Between the emoji's surrogates rounds down to its start
+        assert_eq!(PositionEncoding::Utf16.byte_offset(line, 5), 4);
+        assert_eq!(PositionEncoding::Utf16.byte_offset(line, 100), line.len());
+    }
+
+    #[test]
+    fn test_rename_edit_after_emoji() {
+        let content = "x = \"🦀\"; crab()\n";
+        let edit =
+            find_rename_edit(content, 1, "crab", "lobster", PositionEncoding::Utf16).unwrap();
+        assert_eq!(edit.range.start.character, 10);
+        assert_eq!(edit.range.end.character, 14);
+
+        let word = extract_word_with_range(content.lines().next().unwrap(), 14);
+        assert_eq!(word.word, "crab");
+    }
+
+    #[test]
+    fn test_apply_change() {
+        let text = "let s = \"😀\";\nfoo();\n";
+        // Replace `foo` on line 1
+        let range = Range {
+            start: Position {
+                line: 1,
+                character: 0,
+            },
+            end: Position {
+                line: 1,
+                character: 3,
+            },
+        };
+        let applied = apply_change(text, range, "bar", PositionEncoding::Utf16);
+        assert_eq!(applied.content, "let s = \"😀\";\nbar();\n");
+        assert_eq!(applied.edit.start_position, Point::new(1, 0));
+        assert_eq!(applied.edit.new_end_position, Point::new(1, 3));
+
+        // UTF-16 column 11 is just after the emoji (two code units)
+        let range = Range {
+            start: Position {
+                line: 0,
+                character: 11,
+            },
+            end: Position {
+                line: 0,
+                character: 11,
+            },
+        };
+        let applied = apply_change(text, range, "!", PositionEncoding::Utf16);
+        assert_eq!(applied.content, "let s = \"😀!\";\nfoo();\n");
+        assert_eq!(applied.edit.start_byte, "let s = \"😀".len());
+    }
+
+    #[test]
+    fn test_negotiate_position_encoding() {
+        let offered = [PositionEncodingKind::UTF8, PositionEncodingKind::UTF16];
+        assert_eq!(
+            PositionEncoding::negotiate(PositionEncoding::Utf8, Some(&offered)),
+            PositionEncoding::Utf8
+        );
+        assert_eq!(
+            PositionEncoding::negotiate(PositionEncoding::Utf32, Some(&offered)),
+            PositionEncoding::Utf16
+        );
+        assert_eq!(
+            PositionEncoding::negotiate(PositionEncoding::Utf8, None),
+            PositionEncoding::Utf16
+        );
+    }
+}
@@ -6,6 +6,7 @@
 use crate::extract::{ExtractOptions, Extractor};
 use crate::tree::{ViewNode, ViewNodeKind};
 use moss_languages::{Symbol as LangSymbol, SymbolKind as LangSymbolKind};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// A code symbol with its signature
@@ -43,6 +44,23 @@ impl SkeletonSymbol {
             signature: Some(self.signature.clone()),
             docstring: self.docstring.clone(),
             line_range: Some((self.start_line, self.end_line)),
+            details: None,
+        }
+    }
+
+    /// Append this symbol's outline: its signature at `depth`, then its
+    /// children one level deeper, or `...` in place of a body.
+    pub fn outline(&self, depth: usize, lines: &mut Vec<String>) {
+        let indent = "    ".repeat(depth);
+        for line in self.signature.lines() {
+            lines.push(format!("{}{}", indent, line.trim_end()));
+        }
+        if self.children.is_empty() {
+            lines.push(format!("{}    ...", indent));
+        } else {
+            for child in &self.children {
+                child.outline(depth + 1, lines);
+            }
         }
     }
 }
@@ -54,6 +72,16 @@ pub struct SkeletonResult {
 }
 
 impl SkeletonResult {
+    /// The file's table of contents: signatures only, indented by nesting,
+    /// with bodies elided.
+    pub fn outline(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for sym in &self.symbols {
+            sym.outline(0, &mut lines);
+        }
+        lines
+    }
+
     /// Convert to a ViewNode with file as root and symbols as children.
     pub fn to_view_node(&self) -> ViewNode {
         let file_name = Path::new(&self.file_path)
@@ -113,6 +141,51 @@ impl SkeletonResult {
             file_path: self.file_path.clone(),
         }
     }
+
+    /// Group same-named sibling symbols (overloads, multiple impls) into one entry.
+    /// The grouped entry's signature reads `foo (3 defs)` and its children are the
+    /// individual definitions, each keeping its own signature and line range.
+    pub fn group_overloads(&self) -> SkeletonResult {
+        SkeletonResult {
+            symbols: group_symbols(&self.symbols),
+            file_path: self.file_path.clone(),
+        }
+    }
+}
+
+/// Group same-named siblings, recursing into children. Preserves first-seen order.
+fn group_symbols(symbols: &[SkeletonSymbol]) -> Vec<SkeletonSymbol> {
+    let mut groups: Vec<Vec<SkeletonSymbol>> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for sym in symbols {
+        let i = *index.entry(&sym.name).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[i].push(SkeletonSymbol {
+            children: group_symbols(&sym.children),
+            ..sym.clone()
+        });
+    }
+
+    groups
+        .into_iter()
+        .map(|mut group| {
+            if group.len() == 1 {
+                return group.pop().unwrap();
+            }
+            let first = &group[0];
+            SkeletonSymbol {
+                name: first.name.clone(),
+                kind: first.kind,
+                signature: format!("{} ({} defs)", first.name, group.len()),
+                docstring: None,
+                start_line: group.iter().map(|s| s.start_line).min().unwrap_or(0),
+                end_line: group.iter().map(|s| s.end_line).max().unwrap_or(0),
+                children: group,
+            }
+        })
+        .collect()
 }
 
 /// Convert a moss_languages::Symbol to SkeletonSymbol
@@ -160,6 +233,7 @@ impl SkeletonExtractor {
         Self {
             extractor: Extractor::with_options(ExtractOptions {
                 include_private: true,
+                ..Default::default()
             }),
         }
     }
@@ -172,6 +246,20 @@ impl SkeletonExtractor {
         }
     }
 
+    /// Extract from a tree already parsed from `content`.
+    pub fn extract_tree(
+        &self,
+        path: &Path,
+        content: &str,
+        tree: &arborium::tree_sitter::Tree,
+    ) -> SkeletonResult {
+        let result = self.extractor.extract_tree(path, content, tree);
+        SkeletonResult {
+            symbols: result.symbols.iter().map(convert_symbol).collect(),
+            file_path: result.file_path,
+        }
+    }
+
     /// Trait-based extraction (for future use when implementations are complete)
     #[allow(dead_code)]
     pub fn extract_with_support(&self, path: &Path, content: &str) -> Option<SkeletonResult> {
@@ -195,6 +283,23 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_outline_nests_and_elides_bodies() {
+        let content =
+            "class Bar:\n    def method(self):\n        return 1\n\ndef foo():\n    pass\n";
+        let result = SkeletonExtractor::new().extract(&PathBuf::from("test.py"), content);
+        assert_eq!(
+            result.outline(),
+            [
+                "class Bar",
+                "    def method(self)",
+                "        ...",
+                "def foo()",
+                "    ...",
+            ]
+        );
+    }
+
     #[test]
     fn test_python_skeleton() {
         let extractor = SkeletonExtractor::new();
@@ -252,6 +357,226 @@ impl Foo {
         assert_eq!(foo.children[0].name, "new");
     }
 
+    #[test]
+    fn test_nix_skeleton() {
+        let extractor = SkeletonExtractor::new();
+        let content = r#"{
+  description = "Example flake";
+
+  inputs = {
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+  };
+
+  # Build outputs
+  outputs = { self, nixpkgs }:
+    let
+      pkgs = import nixpkgs { system = "x86_64-linux"; };
+      mkGreeting = name: greeting: "${greeting}, ${name}";
+    in {
+      packages.default = pkgs.hello;
+    };
+}
+"#;
+        let result = extractor.extract(&PathBuf::from("flake.nix"), content);
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["description", "inputs", "outputs"]);
+
+        assert_eq!(result.symbols[0].kind, "variable");
+        let inputs = &result.symbols[1];
+        assert_eq!(inputs.kind, "module");
+        assert_eq!(inputs.children[0].name, "nixpkgs.url");
+
+        let outputs = &result.symbols[2];
+        assert_eq!(outputs.kind, "function");
+        assert_eq!(outputs.signature, "outputs = { self, nixpkgs }:");
+        assert_eq!(outputs.docstring.as_deref(), Some("Build outputs"));
+        // let bindings, then the bindings of the `in` attrset
+        let children: Vec<(&str, &str)> = outputs
+            .children
+            .iter()
+            .map(|c| (c.name.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            children,
+            vec![
+                ("pkgs", "variable"),
+                ("mkGreeting", "function"),
+                ("packages.default", "variable"),
+            ]
+        );
+        assert_eq!(
+            outputs.children[1].signature,
+            "mkGreeting = name: greeting:"
+        );
+    }
+
+    #[test]
+    fn test_sql_skeleton() {
+        let extractor = SkeletonExtractor::new();
+        let content = r#"-- Registered users
+CREATE TABLE users (
+    id SERIAL PRIMARY KEY,
+    email TEXT NOT NULL
+);
+
+ALTER SYSTEM FROBNICATE WITH (x = 1);
+
+CREATE TABLE IF NOT EXISTS public.orders (
+    id INTEGER PRIMARY KEY,
+    user_id INTEGER REFERENCES users(id)
+);
+
+CREATE INDEX idx_orders_user ON orders (user_id);
+"#;
+        let result = extractor.extract(&PathBuf::from("schema.sql"), content);
+        // The unparseable statement doesn't hide the ones around it
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["users", "orders", "idx_orders_user"]);
+
+        let users = &result.symbols[0];
+        assert_eq!(users.signature, "CREATE TABLE users");
+        assert_eq!(users.docstring.as_deref(), Some("Registered users"));
+        assert_eq!((users.start_line, users.end_line), (2, 5));
+        let columns: Vec<(&str, usize)> = users
+            .children
+            .iter()
+            .map(|c| (c.name.as_str(), c.start_line))
+            .collect();
+        assert_eq!(columns, vec![("id", 3), ("email", 4)]);
+        assert_eq!(users.children[1].signature, "email TEXT NOT NULL");
+
+        let orders = &result.symbols[1];
+        assert_eq!(orders.signature, "CREATE TABLE public.orders");
+        assert_eq!((orders.start_line, orders.end_line), (9, 12));
+        assert_eq!(orders.children[1].name, "user_id");
+        assert_eq!(result.symbols[2].kind, "variable");
+    }
+
+    #[test]
+    fn test_sql_routines_and_views() {
+        let extractor = SkeletonExtractor::new();
+        let content = r#"CREATE OR REPLACE VIEW public.recent AS
+  SELECT * FROM orders;
+
+CREATE INDEX ON public.orders (user_id, created);
+
+CREATE FUNCTION add(a integer, b integer) RETURNS integer AS $$
+  SELECT a + b;
+$$ LANGUAGE sql;
+
+-- Load a row
+CREATE OR REPLACE PROCEDURE app.insert_data(a integer)
+LANGUAGE SQL
+AS $body$
+INSERT INTO tbl VALUES (a);
+$body$;
+"#;
+        let result = extractor.extract(&PathBuf::from("migration.sql"), content);
+        let outline: Vec<(&str, &str, usize, usize)> = result
+            .symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.start_line, s.end_line))
+            .collect();
+        // Unnamed indexes get PostgreSQL's default name; the grammar can't
+        // parse procedures, so their extent comes from the dollar quote
+        assert_eq!(
+            outline,
+            vec![
+                ("recent", "struct", 1, 2),
+                ("orders_user_id_created_idx", "variable", 4, 4),
+                ("add", "function", 6, 8),
+                ("insert_data", "function", 11, 15),
+            ]
+        );
+        assert_eq!(result.symbols[0].signature, "CREATE VIEW public.recent");
+        assert_eq!(result.symbols[3].docstring.as_deref(), Some("Load a row"));
+    }
+
+    #[test]
+    fn test_graphql_skeleton() {
+        let extractor = SkeletonExtractor::new();
+        let content = r#""""A user account"""
+type User {
+  id: ID!
+  "Display name"
+  name: String
+  posts(first: Int): [Post!]!
+}
+
+enum Role {
+  ADMIN
+  USER
+}
+
+query GetUser($id: ID!) {
+  user(id: $id) { name }
+}
+"#;
+        let result = extractor.extract(&PathBuf::from("schema.graphql"), content);
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["User", "Role", "GetUser"]);
+
+        let user = &result.symbols[0];
+        assert_eq!(user.signature, "type User");
+        assert_eq!(user.docstring.as_deref(), Some("A user account"));
+        let fields: Vec<&str> = user.children.iter().map(|c| c.signature.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["id: ID!", "name: String", "posts(first: Int): [Post!]!"]
+        );
+        assert_eq!(user.children[1].docstring.as_deref(), Some("Display name"));
+
+        let values: Vec<&str> = result.symbols[1]
+            .children
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(values, vec!["ADMIN", "USER"]);
+
+        let query = &result.symbols[2];
+        assert_eq!(query.kind, "function");
+        assert_eq!(query.signature, "query GetUser($id: ID!)");
+    }
+
+    #[test]
+    fn test_terraform_skeleton() {
+        let extractor = SkeletonExtractor::new();
+        let content = r#"# Web server
+resource "aws_instance" "web" {
+  ami = "ami-123"
+  lifecycle {
+    create_before_destroy = true
+  }
+}
+
+module "vpc" {
+  source = "./modules/vpc"
+}
+
+variable "region" {
+  type = string
+}
+
+output "ip" {
+  value = aws_instance.web.public_ip
+}
+"#;
+        let result = extractor.extract(&PathBuf::from("main.tf"), content);
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["aws_instance.web", "vpc", "region", "ip"]);
+
+        let web = &result.symbols[0];
+        assert_eq!(web.signature, r#"resource "aws_instance" "web""#);
+        assert_eq!(web.docstring.as_deref(), Some("Web server"));
+        assert_eq!((web.start_line, web.end_line), (2, 7));
+        // Nested blocks are children, not top-level symbols
+        assert_eq!(web.children.len(), 1);
+        assert_eq!(web.children[0].signature, "lifecycle");
+
+        assert_eq!(result.symbols[1].kind, "module");
+        assert_eq!(result.symbols[2].signature, r#"variable "region""#);
+    }
+
     #[test]
     fn test_to_view_node() {
         let extractor = SkeletonExtractor::new();
@@ -319,6 +644,172 @@ More content.
         );
     }
 
+    #[test]
+    fn test_markdown_outline_nesting() {
+        let extractor = SkeletonExtractor::new();
+        let content = "# Guide\n\nIntro.\n\n## Install\n\n### From source\n\nmake\n\nUsage\n-----\n\nRun it.\n\n# Appendix\n";
+        let result = extractor.extract(&PathBuf::from("README.md"), content);
+
+        let names: Vec<&str> = result.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Guide", "Appendix"]);
+        assert_eq!(result.symbols[1].end_line, 16);
+        let guide = &result.symbols[0];
+        assert_eq!((guide.start_line, guide.end_line), (1, 14));
+        let sections: Vec<(&str, &str, usize, usize)> = guide
+            .children
+            .iter()
+            .map(|s| {
+                (
+                    s.name.as_str(),
+                    s.signature.as_str(),
+                    s.start_line,
+                    s.end_line,
+                )
+            })
+            .collect();
+        // The setext heading is a level-2 sibling of Install, not its child
+        assert_eq!(
+            sections,
+            [
+                ("Install", "## Install", 5, 9),
+                ("Usage", "## Usage", 11, 14)
+            ]
+        );
+        assert_eq!(guide.children[0].children[0].name, "From source");
+    }
+
+    #[test]
+    fn test_dart_outline() {
+        let extractor = SkeletonExtractor::with_all();
+        let content = "/// A counter.\nabstract class Counter extends Base with Logging {\n  Counter.zero();\n\n  /// Adds one.\n  @override\n  void increment() {\n    _count++;\n  }\n\n  int get count => _count;\n  void _reset();\n}\n\nmixin Logging on Base {}\n\nint topLevel(int a) {\n  return a;\n}\n";
+        let result = extractor.extract(&PathBuf::from("lib/counter.dart"), content);
+
+        let outline: Vec<(&str, &str, usize, usize)> = result
+            .symbols
+            .iter()
+            .map(|s| {
+                (
+                    s.name.as_str(),
+                    s.signature.as_str(),
+                    s.start_line,
+                    s.end_line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            outline,
+            [
+                (
+                    "Counter",
+                    "abstract class Counter extends Base with Logging",
+                    2,
+                    13
+                ),
+                ("Logging", "mixin Logging on Base", 15, 15),
+                ("topLevel", "int topLevel(int a)", 17, 19),
+            ]
+        );
+        let counter = &result.symbols[0];
+        assert_eq!(counter.docstring.as_deref(), Some("A counter."));
+        let members: Vec<(&str, &str, usize, usize)> = counter
+            .children
+            .iter()
+            .map(|s| {
+                (
+                    s.name.as_str(),
+                    s.signature.as_str(),
+                    s.start_line,
+                    s.end_line,
+                )
+            })
+            .collect();
+        assert_eq!(
+            members,
+            [
+                ("Counter.zero", "Counter.zero()", 3, 3),
+                ("increment", "void increment()", 7, 9),
+                ("count", "int get count", 11, 11),
+                ("_reset", "void _reset()", 12, 12),
+            ]
+        );
+        assert_eq!(counter.children[1].docstring.as_deref(), Some("Adds one."));
+    }
+
+    #[test]
+    fn test_elixir_module_outline() {
+        let extractor = SkeletonExtractor::with_all();
+        let content = "defmodule MyApp.Foo do\n  @moduledoc \"Foo things.\"\n  defstruct [:name]\n\n  @doc \"Greets.\"\n  @spec greet(String.t()) :: String.t()\n  def greet(name) when is_binary(name) do\n    name\n  end\n\n  defp secret, do: 1\n\n  defimpl String.Chars do\n    def to_string(_), do: \"foo\"\n  end\nend\n";
+        let result = extractor.extract(&PathBuf::from("lib/my_app/foo.ex"), content);
+
+        assert_eq!(result.symbols.len(), 1);
+        let module = &result.symbols[0];
+        assert_eq!(module.name, "MyApp.Foo");
+        assert_eq!((module.start_line, module.end_line), (1, 16));
+        assert_eq!(module.docstring.as_deref(), Some("Foo things."));
+        let defs: Vec<(&str, &str)> = module
+            .children
+            .iter()
+            .map(|s| (s.name.as_str(), s.signature.as_str()))
+            .collect();
+        assert_eq!(
+            defs,
+            [
+                ("%MyApp.Foo{}", "defstruct [:name]"),
+                ("greet", "def greet(name) when is_binary(name)"),
+                ("secret", "defp secret"),
+                ("String.Chars.MyApp.Foo", "defimpl String.Chars"),
+            ]
+        );
+        assert_eq!(module.children[1].docstring.as_deref(), Some("Greets."));
+        assert_eq!(module.children[3].children[0].name, "to_string");
+    }
+
+    #[test]
+    fn test_config_skeletons() {
+        let extractor = SkeletonExtractor::new();
+        let outline = |file: &str, content: &str| {
+            extractor
+                .extract(&PathBuf::from(file), content)
+                .symbols
+                .iter()
+                .map(|s| {
+                    let children: Vec<&str> = s.children.iter().map(|c| c.name.as_str()).collect();
+                    format!(
+                        "{}@{}-{}[{}]",
+                        s.name,
+                        s.start_line,
+                        s.end_line,
+                        children.join(",")
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let toml = "edition = \"2021\"\n\n[package]\nname = \"x\"\n\n[dependencies]\nserde = { version = \"1\" }\n\n[[bin]]\nname = \"y\"\n";
+        assert_eq!(
+            outline("Cargo.toml", toml),
+            [
+                "edition@1-1[]",
+                "package@3-4[name]",
+                "dependencies@6-7[serde]",
+                "bin@9-10[name]"
+            ]
+        );
+
+        let json =
+            "{\n  \"name\": \"x\",\n  \"scripts\": {\n    \"build\": {\"cmd\": \"tsc\"}\n  }\n}\n";
+        assert_eq!(
+            outline("package.json", json),
+            ["name@2-2[]", "scripts@3-5[build]"]
+        );
+
+        let yaml = "name: CI\njobs:\n  build:\n    steps:\n      - run: make\n---\nother: 1\n";
+        assert_eq!(
+            outline("ci.yml", yaml),
+            ["name@1-1[]", "jobs@2-5[build]", "other@7-7[]"]
+        );
+    }
+
     #[test]
     fn test_javascript_skeleton() {
         let extractor = SkeletonExtractor::new();
@@ -679,4 +1170,32 @@ class Bar:
         assert_eq!(bar.children[0].name, "method");
         assert_eq!(bar.children[0].kind, "method");
     }
+
+    #[test]
+    fn test_group_overloads_java() {
+        let extractor = SkeletonExtractor::new();
+        let content = r#"
+public class Calculator {
+    public int add(int a, int b) { return a + b; }
+    public double add(double a, double b) { return a + b; }
+    public int add(int a, int b, int c) { return a + b + c; }
+    public int negate(int a) { return -a; }
+}
+"#;
+        let result = extractor.extract(&PathBuf::from("Calculator.java"), content);
+        let calc = &result.symbols[0];
+        assert_eq!(calc.children.len(), 4);
+
+        let grouped = result.group_overloads();
+        let calc = &grouped.symbols[0];
+        let names: Vec<_> = calc.children.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["add", "negate"]);
+
+        let add = &calc.children[0];
+        assert_eq!(add.signature, "add (3 defs)");
+        assert_eq!(add.children.len(), 3);
+        assert!(add.children[1].signature.contains("double a"));
+        assert_eq!(add.start_line, 3);
+        assert_eq!(add.end_line, 5);
+    }
 }
@@ -1,6 +1,8 @@
+use crate::config::SymbolsConfig;
 use crate::extract::{compute_complexity, ExtractOptions, Extractor};
 use crate::parsers::Parsers;
 use arborium::tree_sitter;
+use moss_languages::rust_cfg;
 use moss_languages::{
     support_for_grammar, support_for_path, Language, Symbol as LangSymbol,
     SymbolKind as LangSymbolKind,
@@ -16,6 +18,10 @@ pub struct Symbol {
     pub parent: Option<String>,
     /// Cyclomatic complexity (only for functions/methods)
     pub complexity: Option<usize>,
+    /// `#[cfg(...)]` predicate guarding the item, including enclosing items' (Rust only)
+    pub cfg: Option<String>,
+    /// Declaration line(s) as written, e.g. `def foo(x: int) -> str`
+    pub signature: String,
 }
 
 /// An import statement (from X import Y as Z)
@@ -70,29 +76,68 @@ fn convert_symbol_kind(kind: LangSymbolKind) -> SymbolKind {
     }
 }
 
+/// Symbols extracted from one file, possibly cut off at the per-file cap.
+pub struct FileSymbols {
+    pub symbols: Vec<Symbol>,
+    /// Number of symbols in the file before the cap was applied.
+    pub total: usize,
+}
+
+impl FileSymbols {
+    /// Whether the symbol cap dropped some of the file's symbols.
+    pub fn is_capped(&self) -> bool {
+        self.total > self.symbols.len()
+    }
+}
+
 pub struct SymbolParser {
     extractor: Extractor,
     parsers: Parsers, // Keep for import parsing and call graph analysis
+    /// Stop after this many symbols per file (None = unlimited).
+    max_symbols: Option<usize>,
 }
 
 impl SymbolParser {
     pub fn new() -> Self {
+        Self::from_config(&SymbolsConfig::default())
+    }
+
+    /// Create a parser for indexing with the `[symbols]` config: nested and
+    /// anonymous functions per `include_nested`, capped at `max_per_file`.
+    pub fn from_config(config: &SymbolsConfig) -> Self {
         Self {
             extractor: Extractor::with_options(ExtractOptions {
                 include_private: true, // symbols.rs includes all symbols for indexing
+                include_nested: config.include_nested,
             }),
             parsers: Parsers::new(),
+            max_symbols: config.max_per_file(),
         }
     }
 
     pub fn parse_file(&self, path: &Path, content: &str) -> Vec<Symbol> {
+        self.extract_file(path, content).symbols
+    }
+
+    /// Extract symbols, stopping at the per-file cap so one huge (usually
+    /// generated) file can't dominate the index.
+    pub fn extract_file(&self, path: &Path, content: &str) -> FileSymbols {
         let support = match support_for_path(path) {
             Some(s) => s,
-            None => return Vec::new(),
+            None => {
+                return FileSymbols {
+                    symbols: Vec::new(),
+                    total: 0,
+                }
+            }
         };
 
         // Use shared extractor for symbol extraction
         let result = self.extractor.extract(path, content);
+        fn count(sym: &LangSymbol) -> usize {
+            1 + sym.children.iter().map(count).sum::<usize>()
+        }
+        let total = result.symbols.iter().map(count).sum();
 
         // Parse once for complexity computation
         let tree = self
@@ -104,10 +149,11 @@ impl SymbolParser {
         for sym in &result.symbols {
             self.flatten_symbol(sym, None, &mut symbols, content, support, tree.as_ref());
         }
-        symbols
+        FileSymbols { symbols, total }
     }
 
-    /// Flatten a nested symbol into the flat list with parent references
+    /// Flatten a nested symbol into the flat list with parent references,
+    /// stopping once the per-file cap is reached
     fn flatten_symbol(
         &self,
         sym: &LangSymbol,
@@ -117,6 +163,9 @@ impl SymbolParser {
         support: &dyn Language,
         tree: Option<&tree_sitter::Tree>,
     ) {
+        if self.max_symbols.is_some_and(|max| symbols.len() >= max) {
+            return;
+        }
         let kind = convert_symbol_kind(sym.kind);
         let is_function = matches!(kind, SymbolKind::Function | SymbolKind::Method);
 
@@ -130,6 +179,14 @@ impl SymbolParser {
             None
         };
 
+        let cfg = match tree {
+            Some(t) if support.grammar_name() == "rust" => {
+                rust_cfg::item_at_line(&t.root_node(), content, sym.start_line)
+                    .and_then(|node| rust_cfg::item_cfg(&node, content))
+            }
+            _ => None,
+        };
+
         symbols.push(Symbol {
             name: sym.name.clone(),
             kind,
@@ -137,6 +194,8 @@ impl SymbolParser {
             end_line: sym.end_line,
             parent: parent.map(String::from),
             complexity,
+            cfg,
+            signature: sym.signature.clone(),
         });
 
         // Recurse into children with current symbol as parent
@@ -0,0 +1,245 @@
+//! Cache of tool diagnostics per file content.
+//!
+//! Stored in .moss/tool-cache.sqlite, keyed by (tool, tool version and config,
+//! content hash), so unchanged files aren't re-checked. Only used for tools
+//! whose results depend on a single file (`ToolInfo::file_scoped`).
+
+use crate::hash::content_hash;
+use crate::paths::get_moss_dir;
+use moss_tools::{Diagnostic, Tool, ToolError, ToolResult};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub struct ToolCache {
+    // Shared by tools running concurrently
+    conn: Mutex<Connection>,
+}
+
+impl ToolCache {
+    /// Open or create the cache for a project.
+    pub fn open(root: &Path) -> rusqlite::Result<Self> {
+        let moss_dir = get_moss_dir(root);
+        std::fs::create_dir_all(&moss_dir).ok();
+        let conn = Connection::open(Self::path(root))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS tool_results (
+                tool TEXT NOT NULL,
+                version TEXT NOT NULL,
+                hash TEXT NOT NULL,
+                diagnostics TEXT NOT NULL,
+                PRIMARY KEY (tool, version, hash)
+            );",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Location of the cache database.
+    pub fn path(root: &Path) -> PathBuf {
+        get_moss_dir(root).join("tool-cache.sqlite")
+    }
+
+    /// Cached diagnostics for a file's content, reported against `file`.
+    /// None if this content hasn't been checked by this tool version.
+    pub fn get(
+        &self,
+        tool: &str,
+        version: &str,
+        hash: &str,
+        file: &Path,
+    ) -> Option<Vec<Diagnostic>> {
+        let json: String = self
+            .conn
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .query_row(
+                "SELECT diagnostics FROM tool_results WHERE tool = ?1 AND version = ?2 AND hash = ?3",
+                params![tool, version, hash],
+                |row| row.get(0),
+            )
+            .optional()
+            .ok()??;
+        let mut diagnostics: Vec<Diagnostic> = serde_json::from_str(&json).ok()?;
+        // Identical content may live at another path
+        for diag in &mut diagnostics {
+            diag.location.file = file.to_path_buf();
+        }
+        Some(diagnostics)
+    }
+
+    /// Record the diagnostics a tool reported for a file's content.
+    pub fn put(
+        &self,
+        tool: &str,
+        version: &str,
+        hash: &str,
+        diagnostics: &[Diagnostic],
+    ) -> rusqlite::Result<()> {
+        let json = serde_json::to_string(diagnostics).unwrap_or_else(|_| "[]".to_string());
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.execute(
+            "INSERT OR REPLACE INTO tool_results (tool, version, hash, diagnostics)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![tool, version, hash, json],
+        )?;
+        Ok(())
+    }
+}
+
+/// More stale files than this are checked by running the tool over the
+/// target as usual, rather than listing them on its command line.
+const MAX_LISTED_FILES: usize = 200;
+
+/// A file whose content has no cached result.
+struct StaleFile {
+    /// Path relative to the project root.
+    path: PathBuf,
+    hash: String,
+}
+
+/// Run a file-scoped tool on `target` (or the whole project), taking results
+/// for unchanged files from the cache and invoking the tool only on the rest.
+pub fn run_cached(
+    tool: &dyn Tool,
+    target: Option<&str>,
+    root: &Path,
+    cache: &ToolCache,
+) -> Result<ToolResult, ToolError> {
+    let info = tool.info();
+    let search = target
+        .map(|t| root.join(t))
+        .unwrap_or_else(|| root.to_path_buf());
+    let target_paths: Vec<&Path> = target.map(|t| vec![Path::new(t)]).unwrap_or_default();
+    // Without a version, a tool upgrade couldn't invalidate the cache
+    let Some(version) = tool.version() else {
+        return tool.run(&target_paths, root);
+    };
+    let version = config_version(&version, root, info.config_files);
+
+    let mut diagnostics = Vec::new();
+    let mut stale = Vec::new();
+    for file in moss_tools::find_files_with_extensions(&search, info.extensions) {
+        let Ok(content) = std::fs::read(&file) else {
+            continue;
+        };
+        let path = file.strip_prefix(root).unwrap_or(&file).to_path_buf();
+        let hash = content_hash(&content);
+        match cache.get(info.name, &version, &hash, &path) {
+            Some(cached) => diagnostics.extend(cached),
+            None => stale.push(StaleFile { path, hash }),
+        }
+    }
+    if stale.is_empty() {
+        return Ok(ToolResult::success(info.name, diagnostics));
+    }
+
+    // Many changed files (a first run, say): check the whole target, which
+    // also keeps the tool's own excludes in force
+    let listed = stale.len() <= MAX_LISTED_FILES;
+    let paths: Vec<&Path> = if listed {
+        stale.iter().map(|f| f.path.as_path()).collect()
+    } else {
+        target_paths
+    };
+    let result = tool.run(&paths, root)?;
+    if !result.success {
+        return Ok(result);
+    }
+
+    // Tools report paths as given, as ./path, or absolute
+    let mut by_file: HashMap<PathBuf, Vec<Diagnostic>> = HashMap::new();
+    for mut diag in result.diagnostics {
+        let file = &diag.location.file;
+        let file = file.strip_prefix(root).unwrap_or(file);
+        let file = file.strip_prefix(".").unwrap_or(file).to_path_buf();
+        diag.location.file = file.clone();
+        by_file.entry(file).or_default().push(diag);
+    }
+    for file in stale {
+        let found = by_file.remove(&file.path).unwrap_or_default();
+        if let Err(e) = cache.put(info.name, &version, &file.hash, &found) {
+            eprintln!("warning: cannot update tool cache: {}", e);
+        }
+        diagnostics.extend(found);
+    }
+    if !listed {
+        // The run rechecked unchanged files too; take its results for them
+        diagnostics.retain(|d| !by_file.contains_key(&d.location.file));
+        diagnostics.extend(by_file.into_values().flatten());
+    }
+    Ok(ToolResult::success(info.name, diagnostics))
+}
+
+/// The tool version, plus a hash of whichever of its config files exist at
+/// the root, so editing the config invalidates results like an upgrade does.
+fn config_version(version: &str, root: &Path, config_files: &[&str]) -> String {
+    let mut configs = Vec::new();
+    for name in config_files {
+        if let Ok(content) = std::fs::read(root.join(name)) {
+            configs.extend_from_slice(name.as_bytes());
+            configs.push(0);
+            configs.extend(content);
+            configs.push(0);
+        }
+    }
+    if configs.is_empty() {
+        return version.to_string();
+    }
+    format!("{} config:{}", version, content_hash(&configs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moss_tools::Location;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_tool_cache_roundtrip() {
+        let dir = tempdir().unwrap();
+        let cache = ToolCache::open(dir.path()).unwrap();
+        let hash = content_hash(b"import os\n");
+        let diag =
+            Diagnostic::warning("ruff", "F401", "unused import", Location::new("a.py", 1, 8));
+
+        assert!(cache
+            .get("ruff", "0.5.0", &hash, Path::new("a.py"))
+            .is_none());
+        cache.put("ruff", "0.5.0", &hash, &[diag]).unwrap();
+
+        // Same content elsewhere reuses the result under the new path
+        let hit = cache
+            .get("ruff", "0.5.0", &hash, Path::new("b.py"))
+            .unwrap();
+        assert_eq!(hit.len(), 1);
+        assert_eq!(hit[0].location.file, PathBuf::from("b.py"));
+        assert_eq!(hit[0].rule_id, "F401");
+
+        // A new tool version or different content misses
+        assert!(cache
+            .get("ruff", "0.6.0", &hash, Path::new("a.py"))
+            .is_none());
+        let other = content_hash(b"import sys\n");
+        assert!(cache
+            .get("ruff", "0.5.0", &other, Path::new("a.py"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_config_change_changes_key() {
+        let dir = tempdir().unwrap();
+        let configs = &["ruff.toml", "pyproject.toml"];
+        assert_eq!(config_version("0.5.0", dir.path(), configs), "0.5.0");
+
+        std::fs::write(dir.path().join("ruff.toml"), "line-length = 100\n").unwrap();
+        let before = config_version("0.5.0", dir.path(), configs);
+        assert_ne!(before, "0.5.0");
+        assert_eq!(before, config_version("0.5.0", dir.path(), configs));
+
+        std::fs::write(dir.path().join("ruff.toml"), "line-length = 120\n").unwrap();
+        assert_ne!(before, config_version("0.5.0", dir.path(), configs));
+    }
+}
@@ -2,11 +2,13 @@
 //!
 //! Git-aware tree display using the `ignore` crate for gitignore support.
 
+use crate::config::WalkConfig;
+use crate::extract::FunctionDetails;
 use crate::skeleton::{SkeletonExtractor, SkeletonSymbol};
-use ignore::WalkBuilder;
+use crate::walk::project_walker;
 use moss_languages::support_for_path;
 use serde::Serialize;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::Path;
 
 /// Unified node for viewing directories, files, and symbols.
@@ -33,6 +35,9 @@ pub struct ViewNode {
     /// Line range in file (start, end)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line_range: Option<(usize, usize)>,
+    /// Parameters and return type (for functions, in `--json` output)
+    #[serde(flatten)]
+    pub details: Option<FunctionDetails>,
 }
 
 /// Type of node in the view tree.
@@ -57,6 +62,7 @@ impl ViewNode {
             signature: None,
             docstring: None,
             line_range: None,
+            details: None,
         }
     }
 
@@ -65,6 +71,19 @@ impl ViewNode {
         self.children = children;
         self
     }
+
+    /// Attach function details to symbol nodes, matched by start line.
+    pub fn with_details(mut self, details: &HashMap<usize, FunctionDetails>) -> Self {
+        if let (ViewNodeKind::Symbol(_), Some((start, _))) = (&self.kind, self.line_range) {
+            self.details = details.get(&start).cloned();
+        }
+        self.children = self
+            .children
+            .into_iter()
+            .map(|c| c.with_details(details))
+            .collect();
+        self
+    }
 }
 
 /// Options for formatting ViewNodes.
@@ -214,6 +233,8 @@ pub struct TreeOptions {
     pub boilerplate_dirs: HashSet<String>,
     /// Include symbols inside files (requires depth > 1)
     pub include_symbols: bool,
+    /// Which files the walk yields
+    pub walk: WalkConfig,
 }
 
 impl Default for TreeOptions {
@@ -226,6 +247,7 @@ impl Default for TreeOptions {
                 .map(|s| s.to_string())
                 .collect(),
             include_symbols: false,
+            walk: WalkConfig::default(),
         }
     }
 }
@@ -289,12 +311,7 @@ pub fn generate_view_tree(root: &Path, options: &TreeOptions) -> ViewNode {
         .unwrap_or_else(|| ".".to_string());
 
     // Don't use WalkBuilder's max_depth - we handle it with smart depth (boilerplate awareness)
-    let walker = WalkBuilder::new(root)
-        .hidden(false)
-        .git_ignore(true)
-        .git_global(true)
-        .git_exclude(true)
-        .build();
+    let walker = project_walker(root, &options.walk).build();
 
     let mut tree = InternalTreeNode::default();
     tree.is_dir = true;
@@ -406,6 +423,7 @@ fn tree_node_to_view_node(
         signature: None,
         docstring: None,
         line_range: None,
+        details: None,
     }
 }
 
@@ -453,6 +471,7 @@ fn skeleton_to_view_node(sym: &SkeletonSymbol, parent_path: &str) -> ViewNode {
         signature: Some(sym.signature.clone()),
         docstring: sym.docstring.clone(),
         line_range: Some((sym.start_line, sym.end_line)),
+        details: None,
     }
 }
 
@@ -523,6 +542,7 @@ mod tests {
                 collapse_single: false,
                 boilerplate_dirs: HashSet::new(),
                 include_symbols: false,
+                walk: WalkConfig::default(),
             },
         );
 
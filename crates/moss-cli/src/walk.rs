@@ -0,0 +1,233 @@
+//! Shared directory walker for project files.
+//!
+//! The index, tree view and path resolution must agree on which files exist,
+//! so they all build their walks here. Options that change the file set come
+//! from the `[walk]` config section.
+
+use crate::config::WalkConfig;
+use ignore::WalkBuilder;
+use std::path::Path;
+
+/// Walker over a project's files.
+///
+/// Hidden files are included; `.gitignore`, `.ignore`, global gitignore and
+/// `.git/info/exclude` rules apply, including nested ignore files and `!`
+/// negations. Ignore files are honored even outside a git checkout, and
+/// ignore files in parent directories apply too, so a walk rooted in a
+/// subdirectory sees the same files as a full walk. The `.git` directory
+/// itself is never yielded, nor are files over `max_file_size_kb`.
+///
+/// With `follow_symlinks`, linked directories are walked as if they were part
+/// of the tree, so files outside the root can appear under the link's path.
+/// A link back to one of its own ancestors is reported as a loop error and
+/// not descended into, which callers skip like any other walk error.
+pub fn project_walker(path: &Path, config: &WalkConfig) -> WalkBuilder {
+    let mut builder = WalkBuilder::new(path);
+    builder
+        .hidden(false)
+        .git_ignore(true)
+        .git_global(true)
+        .git_exclude(true)
+        .require_git(false)
+        .max_filesize(config.max_file_size())
+        .follow_links(config.follow_symlinks())
+        .filter_entry(|entry| entry.file_name() != ".git");
+    builder
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MossConfig;
+    use crate::index::FileIndex;
+    use crate::path_resolve;
+    use crate::tree::{generate_view_tree, TreeOptions, ViewNode, ViewNodeKind};
+    use tempfile::TempDir;
+
+    fn walk(root: &Path) -> Vec<String> {
+        walk_with(root, &WalkConfig::default())
+    }
+
+    fn walk_with(root: &Path, config: &WalkConfig) -> Vec<String> {
+        let mut paths: Vec<String> = project_walker(root, config)
+            .build()
+            .flatten()
+            .filter_map(|e| {
+                let rel = e
+                    .path()
+                    .strip_prefix(root)
+                    .ok()?
+                    .to_string_lossy()
+                    .to_string();
+                (!rel.is_empty()).then_some(rel)
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_nested_gitignore_negation() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+        std::fs::write(root.join("sub/.gitignore"), "!keep.log\n").unwrap();
+        for file in [
+            "top.log",
+            "main.py",
+            "sub/drop.log",
+            "sub/keep.log",
+            ".git/HEAD",
+        ] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        assert_eq!(
+            walk(root),
+            [
+                ".gitignore",
+                "main.py",
+                "sub",
+                "sub/.gitignore",
+                "sub/keep.log"
+            ]
+        );
+        // Rooted below the top-level ignore file, the same rules still apply
+        assert_eq!(walk(&root.join("sub")), [".gitignore", "keep.log"]);
+    }
+
+    fn tree_files(node: &ViewNode, out: &mut Vec<String>) {
+        if node.kind == ViewNodeKind::File {
+            // Tree paths start with the root directory's name
+            out.push(node.path.split_once('/').unwrap().1.to_string());
+        }
+        for child in &node.children {
+            tree_files(child, out);
+        }
+    }
+
+    #[test]
+    fn test_consumers_agree() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::create_dir_all(root.join("build")).unwrap();
+        std::fs::write(root.join(".gitignore"), "build/\n*.log\n").unwrap();
+        std::fs::write(root.join("src/.gitignore"), "!keep.log\n").unwrap();
+        for file in [
+            "build/out.o",
+            "debug.log",
+            "src/keep.log",
+            "src/lib.rs",
+            "src/nested/mod.rs",
+        ] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+        std::fs::write(root.join("src/big.bin"), vec![0u8; 4096]).unwrap();
+        std::fs::create_dir_all(root.join(".moss")).unwrap();
+        std::fs::write(
+            root.join(".moss/config.toml"),
+            "[walk]\nmax_file_size_kb = 2\n",
+        )
+        .unwrap();
+
+        let config = MossConfig::load(root);
+        let mut walked: Vec<String> = walk_with(root, &config.walk)
+            .into_iter()
+            .filter(|p| root.join(p).is_file())
+            .collect();
+        walked.sort();
+        assert!(walked.contains(&"src/keep.log".to_string()));
+        assert!(!walked.contains(&"src/big.bin".to_string()));
+        assert!(!walked.contains(&"debug.log".to_string()));
+
+        // Path lookup before an index exists walks the tree itself
+        let mut resolved: Vec<String> = path_resolve::all_files(root)
+            .into_iter()
+            .filter(|m| m.kind == "file" && !m.path.starts_with(".moss/"))
+            .map(|m| m.path)
+            .collect();
+        resolved.sort();
+
+        let mut index = FileIndex::open(root).unwrap();
+        index.refresh().unwrap();
+        let mut indexed: Vec<String> = index
+            .all_files()
+            .unwrap()
+            .into_iter()
+            .filter(|f| !f.is_dir && !f.path.starts_with(".moss/"))
+            .map(|f| f.path)
+            .collect();
+        indexed.sort();
+
+        let tree = generate_view_tree(
+            root,
+            &TreeOptions {
+                collapse_single: false,
+                walk: config.walk.clone(),
+                ..Default::default()
+            },
+        );
+        let mut shown = Vec::new();
+        tree_files(&tree, &mut shown);
+        shown.retain(|p| !p.starts_with(".moss/"));
+        shown.sort();
+
+        let expected: Vec<String> = walked
+            .into_iter()
+            .filter(|p| !p.starts_with(".moss/"))
+            .collect();
+        assert_eq!(resolved, expected);
+        assert_eq!(indexed, expected);
+        assert_eq!(shown, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follow_symlinks() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().join("project");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("shared")).unwrap();
+        std::fs::write(root.join("src/main.py"), "").unwrap();
+        std::fs::write(dir.path().join("shared/util.py"), "").unwrap();
+        std::os::unix::fs::symlink("../shared", root.join("shared")).unwrap();
+        std::os::unix::fs::symlink("..", root.join("src/loop")).unwrap();
+
+        assert_eq!(walk(&root), ["shared", "src", "src/loop", "src/main.py"]);
+
+        let follow = WalkConfig {
+            follow_symlinks: Some(true),
+            ..Default::default()
+        };
+        // The self-referential link is skipped rather than walked forever
+        assert_eq!(
+            walk_with(&root, &follow),
+            ["shared", "shared/util.py", "src", "src/main.py"]
+        );
+
+        let mut index = FileIndex::open(&root).unwrap();
+        index.set_follow_symlinks(true).unwrap();
+        drop(index);
+        // The choice sticks to the index for later refreshes
+        let mut index = FileIndex::open(&root).unwrap();
+        index.refresh().unwrap();
+        assert!(index
+            .all_files()
+            .unwrap()
+            .iter()
+            .any(|f| f.path == "shared/util.py"));
+    }
+
+    #[test]
+    fn test_gitignore_without_git_dir() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "*.log\n").unwrap();
+        std::fs::write(dir.path().join("a.log"), "").unwrap();
+        std::fs::write(dir.path().join("a.py"), "").unwrap();
+
+        assert_eq!(walk(dir.path()), [".gitignore", "a.py"]);
+    }
+}
@@ -1,13 +1,23 @@
 //! LLM client for workflow engine.
 //!
 //! Supports all providers from rig: anthropic, openai, google, cohere, groq, etc.
+//! Ollama runs against a local server and needs no API key, so workflows can
+//! run fully offline.
 
 #[cfg(feature = "llm")]
 use rig::{
-    client::{CompletionClient, ProviderClient},
+    agent::MultiTurnStreamItem,
+    client::{CompletionClient, Nothing, ProviderClient},
     completion::Prompt,
     providers,
+    streaming::{StreamedAssistantContent, StreamingPrompt},
 };
+#[cfg(feature = "llm")]
+use tokio_stream::{Stream, StreamExt};
+
+/// Where Ollama is served unless `OLLAMA_HOST` says otherwise.
+#[cfg(feature = "llm")]
+const OLLAMA_DEFAULT_HOST: &str = "http://localhost:11434";
 
 /// Supported LLM providers.
 #[cfg(feature = "llm")]
@@ -61,7 +71,7 @@ impl Provider {
             Self::DeepSeek => "deepseek-chat",
             Self::Groq => "llama-3.3-70b-versatile",
             Self::Mistral => "mistral-large-latest",
-            Self::Ollama => "llama3.2",
+            Self::Ollama => "llama3.1",
             Self::OpenRouter => "anthropic/claude-3.5-sonnet",
             Self::Perplexity => "llama-3.1-sonar-large-128k-online",
             Self::Together => "meta-llama/Meta-Llama-3.1-70B-Instruct-Turbo",
@@ -69,22 +79,23 @@ impl Provider {
         }
     }
 
-    /// Get environment variable name for API key.
-    pub fn env_var(&self) -> &'static str {
+    /// Get the environment variable this provider reads, and whether it
+    /// must be set. Ollama only reads an optional `OLLAMA_HOST` override.
+    pub fn env_var(&self) -> EnvVar {
         match self {
-            Self::Anthropic => "ANTHROPIC_API_KEY",
-            Self::OpenAI => "OPENAI_API_KEY",
-            Self::Azure => "AZURE_OPENAI_API_KEY",
-            Self::Gemini => "GEMINI_API_KEY",
-            Self::Cohere => "COHERE_API_KEY",
-            Self::DeepSeek => "DEEPSEEK_API_KEY",
-            Self::Groq => "GROQ_API_KEY",
-            Self::Mistral => "MISTRAL_API_KEY",
-            Self::Ollama => "OLLAMA_API_KEY",
-            Self::OpenRouter => "OPENROUTER_API_KEY",
-            Self::Perplexity => "PERPLEXITY_API_KEY",
-            Self::Together => "TOGETHER_API_KEY",
-            Self::XAI => "XAI_API_KEY",
+            Self::Anthropic => EnvVar::Required("ANTHROPIC_API_KEY"),
+            Self::OpenAI => EnvVar::Required("OPENAI_API_KEY"),
+            Self::Azure => EnvVar::Required("AZURE_OPENAI_API_KEY"),
+            Self::Gemini => EnvVar::Required("GEMINI_API_KEY"),
+            Self::Cohere => EnvVar::Required("COHERE_API_KEY"),
+            Self::DeepSeek => EnvVar::Required("DEEPSEEK_API_KEY"),
+            Self::Groq => EnvVar::Required("GROQ_API_KEY"),
+            Self::Mistral => EnvVar::Required("MISTRAL_API_KEY"),
+            Self::Ollama => EnvVar::Optional("OLLAMA_HOST"),
+            Self::OpenRouter => EnvVar::Required("OPENROUTER_API_KEY"),
+            Self::Perplexity => EnvVar::Required("PERPLEXITY_API_KEY"),
+            Self::Together => EnvVar::Required("TOGETHER_API_KEY"),
+            Self::XAI => EnvVar::Required("XAI_API_KEY"),
         }
     }
 
@@ -108,6 +119,27 @@ impl Provider {
     }
 }
 
+/// An environment variable a provider reads.
+#[cfg(feature = "llm")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvVar {
+    /// API key the provider can't run without
+    Required(&'static str),
+    /// Override with a usable default, such as a local endpoint
+    Optional(&'static str),
+}
+
+/// Ollama base URL from `OLLAMA_HOST` (which may omit the scheme, as in
+/// `127.0.0.1:11434`), else the local default.
+#[cfg(feature = "llm")]
+pub fn ollama_base_url() -> String {
+    match std::env::var("OLLAMA_HOST") {
+        Ok(host) if host.contains("://") => host,
+        Ok(host) if !host.is_empty() => format!("http://{}", host),
+        _ => OLLAMA_DEFAULT_HOST.to_string(),
+    }
+}
+
 /// LLM client.
 #[cfg(feature = "llm")]
 pub struct LlmClient {
@@ -131,13 +163,14 @@ impl LlmClient {
             )
         })?;
 
-        // Check for API key (ollama is optional since it can be local)
-        if provider != Provider::Ollama && std::env::var(provider.env_var()).is_err() {
-            return Err(format!(
-                "Missing {} environment variable for {} provider",
-                provider.env_var(),
-                provider_str
-            ));
+        // Check for API key (Ollama runs locally and needs none)
+        if let EnvVar::Required(var) = provider.env_var() {
+            if std::env::var(var).is_err() {
+                return Err(format!(
+                    "Missing {} environment variable for {} provider",
+                    var, provider_str
+                ));
+            }
         }
 
         let model = model
@@ -149,12 +182,37 @@ impl LlmClient {
 
     /// Generate a completion.
     pub fn complete(&self, system: Option<&str>, prompt: &str) -> Result<String, String> {
+        self.run(system, prompt, None)
+    }
+
+    /// Generate a completion, passing text to `on_token` as it arrives.
+    /// Returns the full text, as `complete` does.
+    pub fn complete_streaming(
+        &self,
+        system: Option<&str>,
+        prompt: &str,
+        mut on_token: impl FnMut(&str),
+    ) -> Result<String, String> {
+        self.run(system, prompt, Some(&mut on_token))
+    }
+
+    fn run(
+        &self,
+        system: Option<&str>,
+        prompt: &str,
+        on_token: Option<&mut dyn FnMut(&str)>,
+    ) -> Result<String, String> {
         let rt = tokio::runtime::Runtime::new()
             .map_err(|e| format!("Failed to create runtime: {}", e))?;
-        rt.block_on(self.complete_async(system, prompt))
+        rt.block_on(self.complete_async(system, prompt, on_token))
     }
 
-    async fn complete_async(&self, system: Option<&str>, prompt: &str) -> Result<String, String> {
+    async fn complete_async(
+        &self,
+        system: Option<&str>,
+        prompt: &str,
+        on_token: Option<&mut dyn FnMut(&str)>,
+    ) -> Result<String, String> {
         macro_rules! run_provider {
             ($client:expr) => {{
                 let client = $client;
@@ -163,10 +221,15 @@ impl LlmClient {
                     builder = builder.preamble(sys);
                 }
                 let agent = builder.build();
-                agent
-                    .prompt(prompt)
-                    .await
-                    .map_err(|e| format!("LLM request failed: {}", e))
+                match on_token {
+                    None => agent
+                        .prompt(prompt)
+                        .await
+                        .map_err(|e| format!("LLM request failed: {}", e)),
+                    Some(on_token) => {
+                        collect_text(agent.stream_prompt(prompt).await, on_token).await
+                    }
+                }
             }};
         }
 
@@ -179,7 +242,14 @@ impl LlmClient {
             Provider::DeepSeek => run_provider!(providers::deepseek::Client::from_env()),
             Provider::Groq => run_provider!(providers::groq::Client::from_env()),
             Provider::Mistral => run_provider!(providers::mistral::Client::from_env()),
-            Provider::Ollama => run_provider!(providers::ollama::Client::from_env()),
+            Provider::Ollama => {
+                let client: providers::ollama::Client = providers::ollama::Client::builder()
+                    .api_key(Nothing)
+                    .base_url(ollama_base_url())
+                    .build()
+                    .map_err(|e| format!("Failed to create Ollama client: {}", e))?;
+                run_provider!(client)
+            }
             Provider::OpenRouter => run_provider!(providers::openrouter::Client::from_env()),
             Provider::Perplexity => run_provider!(providers::perplexity::Client::from_env()),
             Provider::Together => run_provider!(providers::together::Client::from_env()),
@@ -188,6 +258,27 @@ impl LlmClient {
     }
 }
 
+/// Drain a streamed response, passing each text chunk to `on_token`.
+/// Tool calls, reasoning and the final summary are skipped.
+#[cfg(feature = "llm")]
+async fn collect_text<R, E: std::fmt::Display>(
+    mut stream: impl Stream<Item = Result<MultiTurnStreamItem<R>, E>> + Unpin,
+    on_token: &mut dyn FnMut(&str),
+) -> Result<String, String> {
+    let mut text = String::new();
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(MultiTurnStreamItem::StreamAssistantItem(StreamedAssistantContent::Text(chunk))) => {
+                on_token(&chunk.text);
+                text.push_str(&chunk.text);
+            }
+            Ok(_) => {}
+            Err(e) => return Err(format!("LLM request failed: {}", e)),
+        }
+    }
+    Ok(text)
+}
+
 /// Agent response with optional command to execute.
 #[derive(Debug)]
 pub enum AgentAction {
@@ -293,7 +384,53 @@ mod tests {
     fn test_all_providers_have_defaults() {
         for provider in Provider::all() {
             assert!(!provider.default_model().is_empty());
-            assert!(!provider.env_var().is_empty());
+            let (EnvVar::Required(name) | EnvVar::Optional(name)) = provider.env_var();
+            assert!(!name.is_empty());
         }
     }
+
+    #[cfg(feature = "llm")]
+    #[test]
+    fn test_ollama_needs_no_key() {
+        assert_eq!(Provider::Ollama.env_var(), EnvVar::Optional("OLLAMA_HOST"));
+        assert_eq!(Provider::Ollama.default_model(), "llama3.1");
+        assert!(LlmClient::new("ollama", None).is_ok());
+        assert!(ollama_base_url().starts_with("http"));
+    }
+
+    #[cfg(feature = "llm")]
+    #[test]
+    fn test_collect_text_streams_text_chunks() {
+        use rig::message::{Reasoning, Text};
+
+        let text = |t: &str| {
+            Ok::<_, String>(MultiTurnStreamItem::<()>::StreamAssistantItem(
+                StreamedAssistantContent::Text(Text {
+                    text: t.to_string(),
+                }),
+            ))
+        };
+        let items = vec![
+            text("Hello, "),
+            Ok(MultiTurnStreamItem::StreamAssistantItem(
+                StreamedAssistantContent::Reasoning(Reasoning::new("thinking")),
+            )),
+            text("world"),
+        ];
+        let mut tokens = Vec::new();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let full = rt
+            .block_on(collect_text(tokio_stream::iter(items), &mut |t: &str| {
+                tokens.push(t.to_string())
+            }))
+            .unwrap();
+        assert_eq!(full, "Hello, world");
+        assert_eq!(tokens, vec!["Hello, ", "world"]);
+
+        let failed = vec![text("partial"), Err("connection reset".to_string())];
+        let err = rt
+            .block_on(collect_text(tokio_stream::iter(failed), &mut |_: &str| {}))
+            .unwrap_err();
+        assert!(err.contains("connection reset"));
+    }
 }
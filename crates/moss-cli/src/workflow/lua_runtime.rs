@@ -1,14 +1,19 @@
 //! Lua-based workflow runtime using LuaJIT.
 
-use std::path::Path;
-#[cfg(feature = "llm")]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::LazyLock;
 
 use mlua::{FromLua, Lua, Result as LuaResult, Table, Thread, UserData, UserDataMethods, Value};
 
+use crate::config::MossConfig;
+use crate::edit::{EditTransaction, Editor};
+use crate::path_resolve;
+
 #[cfg(feature = "llm")]
 use super::llm::{parse_agent_response, AgentAction, LlmClient, AGENT_SYSTEM_PROMPT};
+#[cfg(feature = "llm")]
+use std::io::{IsTerminal, Write};
 
 /// What the runtime is waiting for from the frontend.
 #[derive(Debug, Clone)]
@@ -44,12 +49,15 @@ pub struct WorkflowSession {
 #[derive(Debug, Clone)]
 pub struct CommandResult {
     pub output: String,
+    /// What the process wrote to stderr (empty for in-process actions)
+    pub stderr: String,
     pub success: bool,
 }
 
 impl UserData for CommandResult {
     fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
         fields.add_field_method_get("output", |_, this| Ok(this.output.clone()));
+        fields.add_field_method_get("stderr", |_, this| Ok(this.stderr.clone()));
         fields.add_field_method_get("success", |_, this| Ok(this.success));
     }
 
@@ -58,6 +66,200 @@ impl UserData for CommandResult {
     }
 }
 
+/// Context accumulated for an LLM prompt (`context()` in Lua).
+#[derive(Debug, Clone)]
+pub enum Context {
+    /// `key: value` lines
+    Flat(Vec<String>),
+    /// One JSON object; values holding JSON text (such as `--json` output)
+    /// are kept structured
+    Json(serde_json::Map<String, serde_json::Value>),
+}
+
+impl Context {
+    /// An empty context for a strategy name: "flat" or "json".
+    fn new(strategy: &str) -> LuaResult<Self> {
+        match strategy {
+            "flat" => Ok(Context::Flat(Vec::new())),
+            "json" => Ok(Context::Json(serde_json::Map::new())),
+            other => Err(mlua::Error::external(format!(
+                "Unknown context strategy '{}' (expected flat or json)",
+                other
+            ))),
+        }
+    }
+
+    fn add(&mut self, key: &str, value: serde_json::Value) {
+        match self {
+            Context::Flat(lines) => {
+                let text = match value {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                lines.push(format!("{}: {}", key, text));
+            }
+            Context::Json(map) => {
+                map.insert(key.to_string(), structured(value));
+            }
+        }
+    }
+
+    fn get_context(&self) -> String {
+        match self {
+            Context::Flat(lines) => lines.join("\n"),
+            Context::Json(map) => serde_json::to_string_pretty(map).unwrap_or_default(),
+        }
+    }
+
+    /// An empty context with the same strategy, for a sub-task.
+    fn child(&self) -> Self {
+        match self {
+            Context::Flat(_) => Context::Flat(Vec::new()),
+            Context::Json(_) => Context::Json(serde_json::Map::new()),
+        }
+    }
+}
+
+impl UserData for Context {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method_mut("add", |_, this, (key, value): (String, Value)| {
+            // A command result is its output in flat context
+            let value = match (&*this, &value) {
+                (Context::Flat(_), Value::UserData(ud)) => match ud.borrow::<CommandResult>() {
+                    Ok(r) => serde_json::Value::String(r.output.clone()),
+                    Err(_) => lua_to_json(&value),
+                },
+                _ => lua_to_json(&value),
+            };
+            this.add(&key, value);
+            Ok(())
+        });
+        methods.add_method("get", |_, this, ()| Ok(this.get_context()));
+        methods.add_method("child", |_, this, ()| Ok(this.child()));
+        methods.add_meta_method("__tostring", |_, this, ()| Ok(this.get_context()));
+    }
+}
+
+/// Edits staged with `edits()` and written all-or-nothing by `commit()`.
+struct EditBatch(EditTransaction);
+
+impl EditBatch {
+    /// Stage an edit to the symbol a `path/symbol` target names.
+    fn edit_symbol(
+        &mut self,
+        lua: &Lua,
+        target: &str,
+        f: impl FnOnce(&Editor, &Path, &str, &str) -> Result<String, String>,
+    ) -> LuaResult<()> {
+        let target = interpolate(lua, target)?;
+        let unified = path_resolve::resolve_unified(&target, self.0.root())
+            .filter(|u| !u.is_directory)
+            .ok_or_else(|| mlua::Error::external(format!("No matches for: {}", target)))?;
+        let name =
+            unified.symbol_path.last().cloned().ok_or_else(|| {
+                mlua::Error::external(format!("{} is not a symbol target", target))
+            })?;
+        self.0
+            .edit(&unified.file_path, |editor, path, content| {
+                f(editor, path, content, &name)
+            })
+            .map_err(mlua::Error::external)
+    }
+}
+
+impl UserData for EditBatch {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_method_mut("write", |lua, this, (path, content): (String, String)| {
+            let path = interpolate(lua, &path)?;
+            this.0.write(&path, content).map_err(mlua::Error::external)
+        });
+        methods.add_method_mut("replace", |lua, this, (target, code): (String, String)| {
+            this.edit_symbol(lua, &target, |editor, path, content, name| {
+                let loc = editor
+                    .find_symbol(path, content, name)
+                    .ok_or_else(|| format!("Symbol not found: {}", name))?;
+                Ok(editor.replace_symbol(content, &loc, &code))
+            })
+        });
+        methods.add_method_mut(
+            "replace_body",
+            |lua, this, (target, body): (String, String)| {
+                this.edit_symbol(lua, &target, |editor, path, content, name| {
+                    let loc = editor
+                        .find_function_body(path, content, name)
+                        .ok_or_else(|| format!("'{}' is not a function with a body", name))?;
+                    Ok(editor.replace_function_body(content, &loc, &body))
+                })
+            },
+        );
+        methods.add_method_mut("delete", |lua, this, target: String| {
+            this.edit_symbol(lua, &target, |editor, path, content, name| {
+                let loc = editor
+                    .find_symbol(path, content, name)
+                    .ok_or_else(|| format!("Symbol not found: {}", name))?;
+                Ok(editor.delete_symbol(content, &loc))
+            })
+        });
+        // Writes everything staged so far and leaves the batch empty
+        methods.add_method_mut("commit", |lua, this, ()| {
+            let fresh = EditTransaction::new(this.0.root());
+            let transaction = std::mem::replace(&mut this.0, fresh);
+            let action = format!("edits {}", transaction.staged_files().join(" "));
+            if let Some(result) = dry_run(lua, action)? {
+                return Ok(result);
+            }
+            let result = match transaction.commit() {
+                Ok(files) => CommandResult {
+                    output: format!("Edited {}", files.join(", ")),
+                    stderr: String::new(),
+                    success: true,
+                },
+                Err(e) => CommandResult {
+                    output: e,
+                    stderr: String::new(),
+                    success: false,
+                },
+            };
+            record(lua, result)
+        });
+    }
+}
+
+/// Parse strings that hold a JSON object or array into values, recursively.
+fn structured(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(s) => match serde_json::from_str(&s) {
+            Ok(v @ (serde_json::Value::Object(_) | serde_json::Value::Array(_))) => v,
+            _ => serde_json::Value::String(s),
+        },
+        serde_json::Value::Object(map) => {
+            serde_json::Value::Object(map.into_iter().map(|(k, v)| (k, structured(v))).collect())
+        }
+        other => other,
+    }
+}
+
+/// Progress saved after each workflow step, for `moss workflow run --resume`.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct Checkpoint {
+    /// Last step completed through `step()`
+    step: Option<String>,
+    /// Commands run so far
+    turns: i64,
+    /// Results of completed steps by name; these are skipped on resume
+    steps: serde_json::Value,
+    /// The `state` table
+    state: serde_json::Value,
+    previous_output: Option<String>,
+    task: Option<String>,
+}
+
+/// Where checkpoints are written, when enabled (stored as Lua app data).
+struct CheckpointFile(PathBuf);
+
+/// Actions recorded instead of run under `--dry-run` (stored as Lua app data).
+struct DryRun(Vec<String>);
+
 /// Options for `view` command.
 #[derive(Debug, Default)]
 struct ViewOpts {
@@ -155,6 +357,10 @@ impl LuaRuntime {
             let root = root.to_path_buf();
 
             globals.set("_moss_root", root.to_string_lossy().to_string())?;
+            // Scratch table for values shared between steps, as `{{state.x}}`
+            globals.set("state", lua.create_table()?)?;
+            globals.set("_moss_steps", lua.create_table()?)?;
+            globals.set("_moss_turns", 0)?;
 
             Self::register_commands(&lua, &globals)?;
             Self::register_helpers(&lua, &globals, &root)?;
@@ -174,6 +380,46 @@ impl LuaRuntime {
         self.lua.load(script).exec()
     }
 
+    /// Save progress to `path` after every command and `step()`.
+    pub fn enable_checkpoints(&self, path: PathBuf) {
+        self.lua.set_app_data(CheckpointFile(path));
+    }
+
+    /// Record commands, shell calls and LLM loops instead of running them.
+    /// Each returns a successful placeholder result so control flow continues.
+    pub fn enable_dry_run(&self) {
+        self.lua.set_app_data(DryRun(Vec::new()));
+    }
+
+    /// Actions recorded so far in dry-run mode, in order.
+    pub fn dry_run_actions(&self) -> Vec<String> {
+        self.lua
+            .app_data_ref::<DryRun>()
+            .map(|log| log.0.clone())
+            .unwrap_or_default()
+    }
+
+    /// Restore `state`, completed steps and the last output from a checkpoint.
+    pub fn resume(&self, path: &Path) -> LuaResult<()> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| mlua::Error::external(format!("Failed to read checkpoint: {}", e)))?;
+        let checkpoint: Checkpoint = serde_json::from_str(&text)
+            .map_err(|e| mlua::Error::external(format!("Invalid checkpoint: {}", e)))?;
+
+        let globals = self.lua.globals();
+        if let Value::Table(state) = json_to_lua(&self.lua, &checkpoint.state)? {
+            globals.set("state", state)?;
+        }
+        if let Value::Table(steps) = json_to_lua(&self.lua, &checkpoint.steps)? {
+            globals.set("_moss_steps", steps)?;
+        }
+        globals.set("_moss_step", checkpoint.step)?;
+        globals.set("_moss_turns", checkpoint.turns)?;
+        globals.set("previous_output", checkpoint.previous_output)?;
+        globals.set("task", checkpoint.task)?;
+        Ok(())
+    }
+
     /// Create an interactive workflow session from a script.
     /// The script runs as a coroutine that can yield for user input.
     pub fn create_session(&self, script: &str) -> LuaResult<WorkflowSession> {
@@ -272,10 +518,10 @@ impl LuaRuntime {
         // view(opts: ViewOpts) -> CommandResult
         globals.set(
             "view",
-            lua.create_function(|_, opts: ViewOpts| {
+            lua.create_function(|lua, opts: ViewOpts| {
                 let mut args = vec!["view".to_string()];
                 if let Some(t) = opts.target {
-                    args.push(t);
+                    args.push(interpolate(lua, &t)?);
                 }
                 if opts.deps {
                     args.push("--deps".to_string());
@@ -287,14 +533,14 @@ impl LuaRuntime {
                     args.push("--depth".to_string());
                     args.push(d.to_string());
                 }
-                run_subprocess(&args)
+                dispatch(lua, args)
             })?,
         )?;
 
         // analyze(opts: AnalyzeOpts) -> CommandResult
         globals.set(
             "analyze",
-            lua.create_function(|_, opts: AnalyzeOpts| {
+            lua.create_function(|lua, opts: AnalyzeOpts| {
                 let mut args = vec!["analyze".to_string()];
                 if opts.health {
                     args.push("--health".to_string());
@@ -303,25 +549,25 @@ impl LuaRuntime {
                     args.push("--complexity".to_string());
                 }
                 if let Some(t) = opts.target {
-                    args.push(t);
+                    args.push(interpolate(lua, &t)?);
                 }
-                run_subprocess(&args)
+                dispatch(lua, args)
             })?,
         )?;
 
         // grep(opts: GrepOpts) -> CommandResult
         globals.set(
             "grep",
-            lua.create_function(|_, opts: GrepOpts| {
-                let mut args = vec!["grep".to_string(), opts.pattern];
+            lua.create_function(|lua, opts: GrepOpts| {
+                let mut args = vec!["grep".to_string(), interpolate(lua, &opts.pattern)?];
                 if let Some(p) = opts.path {
-                    args.push(p);
+                    args.push(interpolate(lua, &p)?);
                 }
                 if let Some(t) = opts.file_type {
                     args.push("--type".to_string());
                     args.push(t);
                 }
-                run_subprocess(&args)
+                dispatch(lua, args)
             })?,
         )?;
 
@@ -330,12 +576,12 @@ impl LuaRuntime {
             ($name:literal) => {{
                 globals.set(
                     $name,
-                    lua.create_function(|_, arg: Option<String>| {
+                    lua.create_function(|lua, arg: Option<String>| {
                         let mut args = vec![$name.to_string()];
                         if let Some(a) = arg {
-                            args.push(a);
+                            args.push(interpolate(lua, &a)?);
                         }
-                        run_subprocess(&args)
+                        dispatch(lua, args)
                     })?,
                 )?;
             }};
@@ -357,9 +603,13 @@ impl LuaRuntime {
         let root_clone = root_path.clone();
         globals.set(
             "shell",
-            lua.create_function(move |_, cmd: String| {
+            lua.create_function(move |lua, cmd: String| {
                 let shell = if cfg!(windows) { "cmd" } else { "sh" };
                 let flag = if cfg!(windows) { "/C" } else { "-c" };
+                let cmd = interpolate_shell(lua, &cmd)?;
+                if let Some(result) = dry_run(lua, format!("shell {}", cmd))? {
+                    return Ok(result);
+                }
 
                 let output = Command::new(shell)
                     .args([flag, &cmd])
@@ -367,18 +617,67 @@ impl LuaRuntime {
                     .output()
                     .map_err(mlua::Error::external)?;
 
-                Ok(CommandResult {
-                    output: String::from_utf8_lossy(&output.stdout).to_string(),
-                    success: output.status.success(),
-                })
+                record(
+                    lua,
+                    CommandResult {
+                        output: String::from_utf8_lossy(&output.stdout).to_string(),
+                        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                        success: output.status.success(),
+                    },
+                )
+            })?,
+        )?;
+
+        // evaluate_condition(result: CommandResult | string, condition: string) -> boolean
+        globals.set(
+            "evaluate_condition",
+            lua.create_function(|_, (result, condition): (Value, String)| {
+                let (output, success) = match &result {
+                    Value::UserData(ud) => {
+                        let r = ud.borrow::<CommandResult>()?;
+                        (r.output.clone(), r.success)
+                    }
+                    Value::String(s) => (s.to_str()?.to_string(), true),
+                    _ => (String::new(), false),
+                };
+                Ok(evaluate_condition(&condition, &output, success))
             })?,
         )?;
 
-        // is_dirty() -> boolean
+        // _moss_checkpoint() - called by step() once a step completes
+        globals.set(
+            "_moss_checkpoint",
+            lua.create_function(|lua, ()| save_checkpoint(lua))?,
+        )?;
+
+        // context(strategy?) -> Context with add(key, value), get(), child()
+        // Strategy defaults to [workflow.context] strategy
+        let strategy = MossConfig::load(root)
+            .workflow
+            .context
+            .strategy()
+            .to_string();
+        globals.set(
+            "context",
+            lua.create_function(move |_, name: Option<String>| {
+                Context::new(name.as_deref().unwrap_or(&strategy))
+            })?,
+        )?;
+
+        // interpolate(template: string) -> string
+        globals.set(
+            "interpolate",
+            lua.create_function(|lua, template: String| interpolate(lua, &template))?,
+        )?;
+
+        // is_dirty() -> boolean (false in dry-run mode, which edits nothing)
         let root_clone = root_path.clone();
         globals.set(
             "is_dirty",
-            lua.create_function(move |_, ()| {
+            lua.create_function(move |lua, ()| {
+                if dry_run(lua, "is_dirty".to_string())?.is_some() {
+                    return Ok(false);
+                }
                 let output = Command::new("git")
                     .args(["status", "--porcelain"])
                     .current_dir(&root_clone)
@@ -392,7 +691,10 @@ impl LuaRuntime {
         let root_clone = root_path.clone();
         globals.set(
             "tests_pass",
-            lua.create_function(move |_, ()| {
+            lua.create_function(move |lua, ()| {
+                if let Some(result) = dry_run(lua, "tests_pass".to_string())? {
+                    return Ok(result.success);
+                }
                 let status = Command::new("cargo")
                     .args(["test", "--quiet"])
                     .current_dir(&root_clone)
@@ -409,6 +711,13 @@ impl LuaRuntime {
             lua.create_function(move |_, path: String| Ok(root_clone.join(&path).exists()))?,
         )?;
 
+        // edits() -> EditBatch with write/replace/replace_body/delete and commit()
+        let root_clone = root_path.clone();
+        globals.set(
+            "edits",
+            lua.create_function(move |_, ()| Ok(EditBatch(EditTransaction::new(&root_clone))))?,
+        )?;
+
         // read_file(path: string) -> string
         let root_clone = root_path.clone();
         globals.set(
@@ -444,9 +753,30 @@ impl LuaRuntime {
             })?,
         )?;
 
+        // parallel(actions, opts?) -> { name = CommandResult, ... }
+        // actions maps names to moss argument lists, e.g. { a = {"analyze", "src/a"} }.
+        // opts.fail_fast (default true) aborts the rest when one fails.
+        globals.set(
+            "parallel",
+            lua.create_function(|lua, (actions, opts): (Table, Option<Table>)| {
+                let fail_fast = opts
+                    .and_then(|o| o.get::<Option<bool>>("fail_fast").ok().flatten())
+                    .unwrap_or(true);
+                run_parallel(lua, &actions, fail_fast)
+            })?,
+        )?;
+
         // prompt(message) -> string (yields to frontend)
         // menu(options) -> string (yields to frontend)
         // These are Lua functions because yield must happen from Lua, not Rust
+        //
+        // step(name, fn) -> result
+        // Runs fn once and records its result; on --resume, completed steps
+        // return their saved { output, success } without running again.
+        //
+        // repeat_until(step, done?, max_iterations?) -> result, iterations, converged
+        // Bounded retry loop: runs step(i) until done(result) (default: result.success)
+        // or max_iterations (default 10) runs. Lua too, so steps can prompt.
         lua.load(
             r#"
             function prompt(message)
@@ -456,6 +786,38 @@ impl LuaRuntime {
             function menu(options)
                 return coroutine.yield("menu", options)
             end
+
+            function step(name, fn)
+                local saved = _moss_steps[name]
+                if saved then
+                    return saved
+                end
+                local result = fn()
+                local output, success = "", true
+                if type(result) == "userdata" or type(result) == "table" then
+                    output = result.output or ""
+                    if result.success ~= nil then success = result.success end
+                elseif result ~= nil then
+                    output = tostring(result)
+                end
+                _moss_steps[name] = { output = output, success = success }
+                _moss_step = name
+                _moss_checkpoint()
+                return result
+            end
+
+            function repeat_until(step, done, max_iterations)
+                done = done or function(result) return result.success end
+                max_iterations = max_iterations or 10
+                local result
+                for i = 1, max_iterations do
+                    result = step(i)
+                    if done(result) then
+                        return result, i, true
+                    end
+                end
+                return result, max_iterations, false
+            end
             "#,
         )
         .exec()?;
@@ -470,7 +832,12 @@ impl LuaRuntime {
             let root_path = root.to_path_buf();
             globals.set(
                 "auto",
-                lua.create_function(move |_, config: Table| run_auto_loop(&config, &root_path))?,
+                lua.create_function(move |lua, config: Table| {
+                    if let Some(result) = dry_run(lua, auto_action(&config))? {
+                        return Ok(result);
+                    }
+                    run_auto_loop(&config, &root_path)
+                })?,
             )?;
         }
 
@@ -479,7 +846,10 @@ impl LuaRuntime {
             let _ = root; // suppress unused warning
             globals.set(
                 "auto",
-                lua.create_function(|_, _config: Table| {
+                lua.create_function(|lua, config: Table| {
+                    if let Some(result) = dry_run(lua, auto_action(&config))? {
+                        return Ok(result);
+                    }
                     Err::<CommandResult, _>(mlua::Error::external(
                         "auto{} requires the 'llm' feature. Rebuild with: cargo build --features llm",
                     ))
@@ -542,6 +912,22 @@ fn run_auto_loop(config: &Table, root: &PathBuf) -> LuaResult<CommandResult> {
         .get("prompt")
         .unwrap_or_else(|_| "Help me with this codebase.".to_string());
     let max_turns: usize = config.get("max_turns").unwrap_or(10);
+    // Print tokens as they arrive when someone is watching
+    let stream: bool = config
+        .get("stream")
+        .unwrap_or_else(|_| std::io::stdout().is_terminal());
+    // context = "json" keeps turns structured instead of a flat transcript
+    let strategy: String = config.get("context").unwrap_or_else(|_| {
+        MossConfig::load(root)
+            .workflow
+            .context
+            .strategy()
+            .to_string()
+    });
+    let mut json_context = match Context::new(&strategy)? {
+        Context::Flat(_) => None,
+        json => Some(json),
+    };
 
     // Extract provider from model (format: "provider/model" or just "provider")
     let (provider, model_name) = if let Some(ref m) = model {
@@ -559,17 +945,39 @@ fn run_auto_loop(config: &Table, root: &PathBuf) -> LuaResult<CommandResult> {
 
     // Build conversation
     let mut conversation = format!("Task: {}\n\nCurrent directory: {}", prompt, root.display());
+    if let Some(ctx) = json_context.as_mut() {
+        ctx.add("task", serde_json::Value::String(prompt.clone()));
+        ctx.add(
+            "directory",
+            serde_json::Value::String(root.display().to_string()),
+        );
+    }
     let mut all_output = String::new();
 
     for turn in 0..max_turns {
         println!("[auto] Turn {}/{}", turn + 1, max_turns);
 
         // Get LLM response
-        let response = client
-            .complete(Some(AGENT_SYSTEM_PROMPT), &conversation)
-            .map_err(mlua::Error::external)?;
-
-        println!("{}", response);
+        let prompt_text = match &json_context {
+            Some(ctx) => ctx.get_context(),
+            None => conversation.clone(),
+        };
+        let response = if stream {
+            let response = client
+                .complete_streaming(Some(AGENT_SYSTEM_PROMPT), &prompt_text, |token| {
+                    print!("{}", token);
+                    let _ = std::io::stdout().flush();
+                })
+                .map_err(mlua::Error::external)?;
+            println!();
+            response
+        } else {
+            let response = client
+                .complete(Some(AGENT_SYSTEM_PROMPT), &prompt_text)
+                .map_err(mlua::Error::external)?;
+            println!("{}", response);
+            response
+        };
         all_output.push_str(&response);
         all_output.push('\n');
 
@@ -577,13 +985,25 @@ fn run_auto_loop(config: &Table, root: &PathBuf) -> LuaResult<CommandResult> {
         match parse_agent_response(&response) {
             AgentAction::Command { name, args } => {
                 // Execute command
-                let mut cmd_args = vec![name.clone()];
-                cmd_args.extend(args);
+                let cmd_args = agent_command(name, args, json_context.is_some());
 
                 println!("[auto] Executing: {}", cmd_args.join(" "));
 
                 let result = run_subprocess_in_dir(&cmd_args, root)?;
 
+                if let Some(ctx) = json_context.as_mut() {
+                    ctx.add(
+                        &format!("turn_{}", turn + 1),
+                        serde_json::json!({
+                            "response": response,
+                            "command": cmd_args.join(" "),
+                            "output": result.output,
+                            "success": result.success,
+                        }),
+                    );
+                    continue;
+                }
+
                 // Add result to conversation
                 conversation.push_str("\n\nAssistant: ");
                 conversation.push_str(&response);
@@ -603,10 +1023,24 @@ fn run_auto_loop(config: &Table, root: &PathBuf) -> LuaResult<CommandResult> {
 
     Ok(CommandResult {
         output: all_output,
+        stderr: String::new(),
         success: true,
     })
 }
 
+/// Arguments for a command the agent asked for. With JSON context, moss
+/// commands (not `shell`) are asked for `--json` output.
+#[cfg(feature = "llm")]
+fn agent_command(name: String, args: Vec<String>, json: bool) -> Vec<String> {
+    let wants_json = json && name != "shell" && !args.iter().any(|a| a == "--json");
+    let mut cmd_args = vec![name];
+    cmd_args.extend(args);
+    if wants_json {
+        cmd_args.push("--json".to_string());
+    }
+    cmd_args
+}
+
 /// Run moss subprocess in a specific directory.
 #[cfg(feature = "llm")]
 fn run_subprocess_in_dir(args: &[String], dir: &Path) -> LuaResult<CommandResult> {
@@ -619,10 +1053,476 @@ fn run_subprocess_in_dir(args: &[String], dir: &Path) -> LuaResult<CommandResult
 
     Ok(CommandResult {
         output: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
         success: output.status.success(),
     })
 }
 
+/// Whether a command's result satisfies a transition condition:
+/// `success`, `has_errors`, `empty`, `contains:<text>`, `matches:<regex>`,
+/// a numeric comparison on the integer next to a key in the output (`errors==0`
+/// matches `0 errors` or `errors: 0`, with `<`, `<=`, `>`, `>=`, `==`, `!=`),
+/// or `not:<condition>`.
+/// Unknown conditions and invalid regexes are false.
+fn evaluate_condition(condition: &str, output: &str, success: bool) -> bool {
+    if let Some(inner) = condition.strip_prefix("not:") {
+        return !evaluate_condition(inner, output, success);
+    }
+    if let Some(text) = condition.strip_prefix("contains:") {
+        return output.contains(text);
+    }
+    if let Some(pattern) = condition.strip_prefix("matches:") {
+        return regex::Regex::new(pattern).is_ok_and(|re| re.is_match(output));
+    }
+    match condition {
+        "success" => success,
+        "has_errors" => !success || output.to_lowercase().contains("error"),
+        "empty" => output.trim().is_empty(),
+        _ => compare_integer(condition, output).unwrap_or(false),
+    }
+}
+
+/// Any integer in command output.
+static INTEGER: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"-?\d+").unwrap());
+
+/// Evaluate `name<op>n` against the integer next to `name` in `output`
+/// (`name: 3`, `name=3` or `3 name`), or the first integer if `name` doesn't
+/// appear. None if the condition isn't a comparison; false if there's no integer.
+fn compare_integer(condition: &str, output: &str) -> Option<bool> {
+    let op_start = condition.find(['<', '>', '=', '!'])?;
+    let name = &condition[..op_start];
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    let rest = &condition[op_start..];
+    let (op, rhs) = ["<=", ">=", "==", "!=", "<", ">"]
+        .into_iter()
+        .find_map(|op| rest.strip_prefix(op).map(|rhs| (op, rhs)))?;
+    let rhs: i64 = rhs.trim().parse().ok()?;
+
+    let Some(value) = keyed_integer(name, output).or_else(|| {
+        INTEGER
+            .find(output)
+            .and_then(|m| m.as_str().parse::<i64>().ok())
+    }) else {
+        return Some(false);
+    };
+    Some(match op {
+        "<=" => value <= rhs,
+        ">=" => value >= rhs,
+        "==" => value == rhs,
+        "!=" => value != rhs,
+        "<" => value < rhs,
+        _ => value > rhs,
+    })
+}
+
+/// The integer after (`errors: 3`, `errors=3`) or before (`3 errors`) the word `name`.
+fn keyed_integer(name: &str, output: &str) -> Option<i64> {
+    let name = regex::escape(name);
+    let after = format!(r"\b{}\s*[:=]?\s*(-?\d+)", name);
+    let before = format!(r"(-?\d+)\s+{}\b", name);
+    [after, before].iter().find_map(|pattern| {
+        regex::Regex::new(pattern)
+            .ok()?
+            .captures(output)?
+            .get(1)?
+            .as_str()
+            .parse()
+            .ok()
+    })
+}
+
+/// Remember a command's output as `previous_output` for later interpolation
+/// and count it as a turn.
+fn record(lua: &Lua, result: CommandResult) -> LuaResult<CommandResult> {
+    let globals = lua.globals();
+    globals.set("previous_output", result.output.as_str())?;
+    let turns: i64 = globals.get("_moss_turns").unwrap_or(0);
+    globals.set("_moss_turns", turns + 1)?;
+    save_checkpoint(lua)?;
+    Ok(result)
+}
+
+/// A moss command started by `parallel()`.
+struct ParallelAction {
+    name: String,
+    args: Vec<String>,
+    child: std::process::Child,
+    output: std::thread::JoinHandle<CapturedOutput>,
+    success: Option<bool>,
+    /// Killed by fail_fast before it finished
+    cancelled: bool,
+}
+
+/// What a `parallel()` command wrote.
+struct CapturedOutput {
+    stdout: String,
+    stderr: String,
+}
+
+/// Read a child's pipe to the end (empty if it wasn't piped).
+fn read_pipe(pipe: Option<impl std::io::Read>) -> String {
+    let mut out = String::new();
+    if let Some(mut pipe) = pipe {
+        let _ = pipe.read_to_string(&mut out);
+    }
+    out
+}
+
+/// Kill and reap the children of actions that haven't finished.
+fn cancel_running(running: &mut [ParallelAction]) {
+    for action in running.iter_mut().filter(|a| a.success.is_none()) {
+        let _ = action.child.kill();
+        let _ = action.child.wait();
+        action.success = Some(false);
+        action.cancelled = true;
+    }
+}
+
+/// Run moss commands concurrently and wait for all of them.
+///
+/// With `fail_fast`, the first failure kills the commands still running and
+/// raises an error; otherwise failures are reported and returned as results.
+fn run_parallel(lua: &Lua, actions: &Table, fail_fast: bool) -> LuaResult<Table> {
+    let mut commands = Vec::new();
+    for pair in actions.pairs::<String, Vec<String>>() {
+        let (name, args) = pair?;
+        let args = args
+            .iter()
+            .map(|a| interpolate(lua, a))
+            .collect::<LuaResult<Vec<_>>>()?;
+        commands.push((name, args));
+    }
+    commands.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let results = lua.create_table()?;
+    if lua.app_data_ref::<DryRun>().is_some() {
+        for (name, args) in commands {
+            results.set(name, dispatch(lua, args)?)?;
+        }
+        return Ok(results);
+    }
+
+    let exe = std::env::current_exe().map_err(mlua::Error::external)?;
+    // Each action's reader thread sends its index once both pipes close
+    let (closed_tx, closed_rx) = std::sync::mpsc::channel();
+    let mut running = Vec::new();
+    for (name, args) in commands {
+        let spawned = Command::new(&exe)
+            .args(&args)
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                cancel_running(&mut running);
+                return Err(mlua::Error::external(e));
+            }
+        };
+        // Drain both pipes on their own threads so neither can fill and
+        // stall the child
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let index = running.len();
+        let closed_tx = closed_tx.clone();
+        let output = std::thread::spawn(move || {
+            let stderr = std::thread::spawn(move || read_pipe(stderr));
+            let stdout = read_pipe(stdout);
+            let stderr = stderr.join().unwrap_or_default();
+            let _ = closed_tx.send(index);
+            CapturedOutput { stdout, stderr }
+        });
+        running.push(ParallelAction {
+            name,
+            args,
+            child,
+            output,
+            success: None,
+            cancelled: false,
+        });
+    }
+    drop(closed_tx);
+
+    let mut aborted_by = None;
+    for index in closed_rx {
+        let action = &mut running[index];
+        if action.success.is_some() {
+            continue;
+        }
+        let status = action.child.wait().map_err(mlua::Error::external)?;
+        action.success = Some(status.success());
+        if !status.success() && fail_fast {
+            aborted_by = Some(action.name.clone());
+            cancel_running(&mut running);
+            break;
+        }
+    }
+
+    let mut combined = String::new();
+    let mut all_succeeded = true;
+    for action in running {
+        let CapturedOutput {
+            stdout: output,
+            stderr,
+        } = action.output.join().unwrap_or(CapturedOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+        });
+        let success = action.success.unwrap_or(false);
+        if !success {
+            all_succeeded = false;
+            let outcome = if action.cancelled {
+                "cancelled"
+            } else {
+                "failed"
+            };
+            eprintln!(
+                "[parallel] {} {}: moss {}",
+                action.name,
+                outcome,
+                action.args.join(" ")
+            );
+            if !stderr.trim().is_empty() {
+                eprintln!("{}", stderr.trim_end());
+            }
+        }
+        combined.push_str(&format!("[{}]\n{}\n", action.name, output));
+        results.set(
+            action.name,
+            CommandResult {
+                output,
+                stderr,
+                success,
+            },
+        )?;
+    }
+    record(
+        lua,
+        CommandResult {
+            output: combined,
+            stderr: String::new(),
+            success: all_succeeded,
+        },
+    )?;
+
+    match aborted_by {
+        Some(name) => Err(mlua::Error::external(format!(
+            "parallel action '{}' failed",
+            name
+        ))),
+        None => Ok(results),
+    }
+}
+
+/// Run a moss command, or only record it in dry-run mode.
+fn dispatch(lua: &Lua, args: Vec<String>) -> LuaResult<CommandResult> {
+    if let Some(result) = dry_run(lua, format!("moss {}", args.join(" ")))? {
+        return Ok(result);
+    }
+    record(lua, run_subprocess(&args)?)
+}
+
+/// In dry-run mode, log `action` and return a placeholder result for it.
+fn dry_run(lua: &Lua, action: String) -> LuaResult<Option<CommandResult>> {
+    let Some(mut log) = lua.app_data_mut::<DryRun>() else {
+        return Ok(None);
+    };
+    let output = format!("[dry-run] {}", action);
+    log.0.push(action);
+    drop(log);
+    record(
+        lua,
+        CommandResult {
+            output,
+            stderr: String::new(),
+            success: true,
+        },
+    )
+    .map(Some)
+}
+
+/// Describe an auto{} call for the dry-run log.
+fn auto_action(config: &Table) -> String {
+    let model: String = config
+        .get("model")
+        .unwrap_or_else(|_| "default".to_string());
+    let prompt: String = config.get("prompt").unwrap_or_default();
+    format!("auto ({}) {}", model, prompt)
+}
+
+/// Write the current progress to the checkpoint file, if checkpoints are on.
+fn save_checkpoint(lua: &Lua) -> LuaResult<()> {
+    let Some(file) = lua.app_data_ref::<CheckpointFile>() else {
+        return Ok(());
+    };
+    let globals = lua.globals();
+    let checkpoint = Checkpoint {
+        step: globals.get("_moss_step")?,
+        turns: globals.get("_moss_turns").unwrap_or(0),
+        steps: lua_to_json(&globals.get("_moss_steps")?),
+        state: lua_to_json(&globals.get("state")?),
+        previous_output: globals.get("previous_output").ok(),
+        task: globals.get("task").ok(),
+    };
+    if let Some(dir) = file.0.parent() {
+        std::fs::create_dir_all(dir).map_err(mlua::Error::external)?;
+    }
+    let json = serde_json::to_string_pretty(&checkpoint).map_err(mlua::Error::external)?;
+    // Write a sibling and rename it over the checkpoint, so an interrupted
+    // write can't leave a truncated file for --resume
+    let name = file
+        .0
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp = file.0.with_file_name(format!(".{}.tmp", name));
+    std::fs::write(&temp, json)
+        .and_then(|()| std::fs::rename(&temp, &file.0))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&temp);
+            mlua::Error::external(e)
+        })
+}
+
+/// Convert a Lua value to JSON. Tables with keys 1..n become arrays;
+/// functions and other unrepresentable values become null.
+fn lua_to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Boolean(b) => serde_json::Value::Bool(*b),
+        Value::Integer(i) => serde_json::Value::from(*i),
+        Value::Number(n) => serde_json::Value::from(*n),
+        Value::String(s) => serde_json::Value::String(s.to_string_lossy().to_string()),
+        Value::UserData(ud) => match ud.borrow::<CommandResult>() {
+            Ok(r) => {
+                serde_json::json!({ "output": r.output, "stderr": r.stderr, "success": r.success })
+            }
+            Err(_) => serde_json::Value::Null,
+        },
+        Value::Table(t) => {
+            let len = t.raw_len();
+            let pairs: Vec<(Value, Value)> = t.clone().pairs().filter_map(|p| p.ok()).collect();
+            if len > 0 && pairs.len() == len {
+                serde_json::Value::Array(
+                    (1..=len)
+                        .map(|i| lua_to_json(&t.raw_get(i).unwrap_or(Value::Nil)))
+                        .collect(),
+                )
+            } else {
+                let map = pairs
+                    .iter()
+                    .filter_map(|(k, v)| {
+                        let key = match k {
+                            Value::String(s) => s.to_string_lossy().to_string(),
+                            Value::Integer(i) => i.to_string(),
+                            _ => return None,
+                        };
+                        Some((key, lua_to_json(v)))
+                    })
+                    .collect();
+                serde_json::Value::Object(map)
+            }
+        }
+        _ => serde_json::Value::Null,
+    }
+}
+
+/// Convert JSON back to a Lua value (arrays become 1-based tables).
+fn json_to_lua(lua: &Lua, value: &serde_json::Value) -> LuaResult<Value> {
+    Ok(match value {
+        serde_json::Value::Null => Value::Nil,
+        serde_json::Value::Bool(b) => Value::Boolean(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Number(n.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(s) => Value::String(lua.create_string(s)?),
+        serde_json::Value::Array(items) => {
+            let table = lua.create_table()?;
+            for (i, item) in items.iter().enumerate() {
+                table.raw_set(i + 1, json_to_lua(lua, item)?)?;
+            }
+            Value::Table(table)
+        }
+        serde_json::Value::Object(map) => {
+            let table = lua.create_table()?;
+            for (k, v) in map {
+                table.raw_set(k.as_str(), json_to_lua(lua, v)?)?;
+            }
+            Value::Table(table)
+        }
+    })
+}
+
+/// Expand `{{name}}` and `{{table.key}}` in a command argument from Lua
+/// globals such as `task`, `previous_output` and `state`. `\{{` stays a
+/// literal `{{`; unknown variables expand to "" with a warning.
+fn interpolate(lua: &Lua, template: &str) -> LuaResult<String> {
+    expand(lua, template, str::to_string)
+}
+
+/// Like `interpolate`, for a command line run by the shell: each value is
+/// quoted as one word, so `;`, `$()` and backticks in command or LLM output
+/// stay literal text.
+fn interpolate_shell(lua: &Lua, template: &str) -> LuaResult<String> {
+    expand(lua, template, shell_quote)
+}
+
+/// Quote `value` as a single word for `sh -c` (or `cmd /C` on Windows).
+fn shell_quote(value: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+fn expand(lua: &Lua, template: &str, quote: fn(&str) -> String) -> LuaResult<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        if rest[..start].ends_with('\\') {
+            out.push_str(&rest[..start - 1]);
+            out.push_str("{{");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = rest[start + 2..start + 2 + len].trim();
+        match lookup(lua, name)? {
+            Some(value) => out.push_str(&quote(&value)),
+            None => eprintln!("warning: unknown workflow variable {{{{{}}}}}", name),
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Resolve a dotted variable path against Lua globals, as a string.
+fn lookup(lua: &Lua, name: &str) -> LuaResult<Option<String>> {
+    let mut value = Value::Table(lua.globals());
+    for key in name.split('.') {
+        value = match value {
+            Value::Table(t) => t.get(key)?,
+            _ => return Ok(None),
+        };
+    }
+    Ok(match value {
+        Value::String(s) => Some(s.to_str()?.to_string()),
+        Value::Integer(i) => Some(i.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Boolean(b) => Some(b.to_string()),
+        Value::UserData(ud) => ud.borrow::<CommandResult>().ok().map(|r| r.output.clone()),
+        _ => None,
+    })
+}
+
 /// Fallback: run moss as subprocess (for commands not yet refactored).
 fn run_subprocess(args: &[String]) -> LuaResult<CommandResult> {
     let exe = std::env::current_exe().map_err(mlua::Error::external)?;
@@ -633,6 +1533,7 @@ fn run_subprocess(args: &[String]) -> LuaResult<CommandResult> {
 
     Ok(CommandResult {
         output: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
         success: output.status.success(),
     })
 }
@@ -662,6 +1563,278 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn test_repeat_until() {
+        let runtime = LuaRuntime::new(std::path::Path::new(".")).unwrap();
+        runtime
+            .run_string(
+                r#"
+                local attempts = 0
+                local result, n, converged = repeat_until(function(i)
+                    attempts = attempts + 1
+                    return { success = i >= 3 }
+                end)
+                assert(converged and n == 3 and attempts == 3)
+
+                -- The cap stops a loop whose condition never holds
+                local _, n, converged = repeat_until(
+                    function(i) return i end,
+                    function(i) return i > 100 end,
+                    4
+                )
+                assert(not converged and n == 4)
+                "#,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_evaluate_condition() {
+        let out = "checked 12 files\n0 errors, 3 warnings";
+        assert!(evaluate_condition("success", out, true));
+        assert!(!evaluate_condition("success", out, false));
+        assert!(evaluate_condition("has_errors", out, true));
+        assert!(evaluate_condition("empty", "  \n", true));
+        assert!(evaluate_condition("contains:3 warnings", out, true));
+
+        assert!(evaluate_condition("matches:\\b0 errors", out, true));
+        assert!(!evaluate_condition("matches:^0 errors", out, true));
+        // Invalid regexes never match
+        assert!(!evaluate_condition("matches:(", out, true));
+
+        // Comparisons use the integer next to the key...
+        assert!(evaluate_condition("errors==0", out, true));
+        assert!(evaluate_condition("warnings==3", out, true));
+        assert!(evaluate_condition("errors>1", "errors: 2", true));
+        assert!(evaluate_condition("failed==4", "passed=10 failed=4", true));
+        // ...or the first integer if the key isn't in the output
+        assert!(evaluate_condition("count>5", out, true));
+        assert!(evaluate_condition("count==12", out, true));
+        assert!(evaluate_condition("files<=12", out, true));
+        assert!(!evaluate_condition("count<12", out, true));
+        assert!(evaluate_condition("count!=3", out, true));
+        assert!(!evaluate_condition("count>0", "no numbers here", true));
+
+        assert!(evaluate_condition("not:matches:[1-9] errors", out, true));
+        assert!(!evaluate_condition("not:success", out, true));
+        assert!(evaluate_condition("not:not:count>=12", out, true));
+
+        // Unknown conditions stay false
+        assert!(!evaluate_condition("finished", out, true));
+        assert!(!evaluate_condition("count>many", out, true));
+
+        let runtime = LuaRuntime::new(std::path::Path::new(".")).unwrap();
+        runtime
+            .run_string(
+                r#"
+                local r = shell("echo 0 errors")
+                assert(evaluate_condition(r, "matches:^0 errors"))
+                assert(evaluate_condition("7 left", "count>5"))
+                "#,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_context_strategies() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = LuaRuntime::new(dir.path()).unwrap();
+        runtime
+            .run_string(
+                r#"
+                flat = context("flat")
+                flat:add("task", "fix it")
+                flat:add("symbols", '[{"name":"main"}]')
+                json = context("json")
+                json:add("task", "fix it")
+                json:add("symbols", '[{"name":"main"}]')
+                json:add("count", 2)
+                sub = json:child()
+                "#,
+            )
+            .unwrap();
+        let get = |name: &str| -> String {
+            runtime
+                .lua
+                .load(format!("return {}:get()", name))
+                .eval()
+                .unwrap()
+        };
+
+        assert_eq!(get("flat"), "task: fix it\nsymbols: [{\"name\":\"main\"}]");
+        let json: serde_json::Value = serde_json::from_str(&get("json")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "task": "fix it",
+                "symbols": [{"name": "main"}],
+                "count": 2,
+            })
+        );
+        assert_eq!(get("sub"), "{}");
+        assert!(runtime.run_string("context('yaml')").is_err());
+    }
+
+    #[cfg(feature = "llm")]
+    #[test]
+    fn test_agent_command_json() {
+        let args = |a: &[&str]| a.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            agent_command("view".into(), args(&["src/main.rs"]), true),
+            args(&["view", "src/main.rs", "--json"])
+        );
+        assert_eq!(
+            agent_command("view".into(), args(&["--json", "src"]), true),
+            args(&["view", "--json", "src"])
+        );
+        assert_eq!(
+            agent_command("shell".into(), args(&["ls"]), true),
+            args(&["shell", "ls"])
+        );
+        assert_eq!(
+            agent_command("view".into(), args(&["src"]), false),
+            args(&["view", "src"])
+        );
+    }
+
+    #[test]
+    fn test_dry_run_records_actions() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = LuaRuntime::new(dir.path()).unwrap();
+        runtime.enable_dry_run();
+        runtime
+            .run_string(
+                r#"
+                local r = edit("src/lib.rs")
+                if r.success then
+                    shell("touch marker")
+                end
+                auto { prompt = "fix the build" }
+                assert(previous_output == "[dry-run] auto (default) fix the build")
+                assert(tests_pass())
+                assert(not is_dirty())
+                "#,
+            )
+            .unwrap();
+        assert_eq!(
+            runtime.dry_run_actions(),
+            vec![
+                "moss edit src/lib.rs",
+                "shell touch marker",
+                "auto (default) fix the build",
+                "tests_pass",
+                "is_dirty",
+            ]
+        );
+        assert!(!dir.path().join("marker").exists());
+    }
+
+    #[test]
+    fn test_edits_commit_together() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.py"), "def f():\n    return 1\n").unwrap();
+        std::fs::write(dir.path().join("b.py"), "def g():\n    return 1\n").unwrap();
+        let runtime = LuaRuntime::new(dir.path()).unwrap();
+        runtime
+            .run_string(
+                r#"
+                local batch = edits()
+                batch:replace_body("a.py/f", "return 2")
+                batch:replace_body("b.py/g", "return (")
+                local r = batch:commit()
+                assert(not r.success and r.output:find("b.py"))
+
+                batch:replace_body("a.py/f", "return 2")
+                batch:write("c.py", "X = 1\n")
+                assert(batch:commit().output == "Edited a.py, c.py")
+                "#,
+            )
+            .unwrap();
+        let read = |p: &str| std::fs::read_to_string(dir.path().join(p)).unwrap();
+        assert_eq!(read("a.py"), "def f():\n    return 2\n");
+        assert_eq!(read("b.py"), "def g():\n    return 1\n");
+        assert_eq!(read("c.py"), "X = 1\n");
+    }
+
+    #[test]
+    fn test_checkpoint_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("workflow-state/w.json");
+
+        let first = LuaRuntime::new(dir.path()).unwrap();
+        first.enable_checkpoints(path.clone());
+        let interrupted = first.run_string(
+            r#"
+            task = "migrate"
+            state.files = { "a.rs", "b.rs" }
+            step("scan", function() return "2 files" end)
+            error("interrupted")
+            "#,
+        );
+        assert!(interrupted.is_err());
+        let saved: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved["step"], "scan");
+        assert_eq!(saved["state"]["files"], serde_json::json!(["a.rs", "b.rs"]));
+        assert!(!dir.path().join("workflow-state/.w.json.tmp").exists());
+
+        let second = LuaRuntime::new(dir.path()).unwrap();
+        second.resume(&path).unwrap();
+        second
+            .run_string(
+                r#"
+                local reran = false
+                local scan = step("scan", function() reran = true end)
+                assert(not reran and scan.output == "2 files")
+                assert(task == "migrate" and state.files[2] == "b.rs")
+                "#,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let runtime = LuaRuntime::new(std::path::Path::new(".")).unwrap();
+        runtime
+            .run_string(
+                r#"
+                task = "tighten errors"
+                state.file = "src/lib.rs"
+                state.attempt = 2
+                assert(interpolate('edit {{state.file}} "fix {{ task }}" #{{state.attempt}}')
+                    == 'edit src/lib.rs "fix tighten errors" #2')
+
+                -- Escaped braces stay literal, unknown variables are empty
+                assert(interpolate([[\{{task}} {{missing}}{{state.nope}}]]) == "{{task}} ")
+                assert(interpolate("unclosed {{task") == "unclosed {{task")
+
+                shell("echo {{task}}")
+                assert(interpolate("{{previous_output}}") == "tighten errors\n")
+                "#,
+            )
+            .unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_quotes_interpolated_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let runtime = LuaRuntime::new(dir.path()).unwrap();
+        runtime
+            .run_string(
+                r#"
+                previous_output = "x; touch pwned"
+                state.file = "$(touch pwned2) `touch pwned3` it's"
+                local r = shell("echo {{previous_output}} {{state.file}}")
+                assert(r.output == "x; touch pwned $(touch pwned2) `touch pwned3` it's\n", r.output)
+                "#,
+            )
+            .unwrap();
+        for file in ["pwned", "pwned2", "pwned3"] {
+            assert!(!dir.path().join(file).exists(), "{} was created", file);
+        }
+    }
+
     #[test]
     fn test_session_prompt() {
         let runtime = LuaRuntime::new(std::path::Path::new(".")).unwrap();
@@ -0,0 +1,174 @@
+//! End-to-end tests for `moss analyze`.
+
+mod common;
+
+#[test]
+fn test_complexity_threshold_flags() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("src/a.py"),
+        "def simple():\n    return 1\n\ndef branchy(x):\n    if x:\n        return 1\n    for i in x:\n        if i:\n            return i\n    return 0\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("src/b.go"),
+        "package b\n\nfunc Pick(ok bool) int {\n\tif ok {\n\t\treturn 1\n\t}\n\treturn 0\n}\n",
+    )
+    .unwrap();
+
+    let analyze = |args: &[&str]| {
+        let output = common::moss(root)
+            .args(["analyze", "--complexity"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    // Rows are file:line, name, complexity; only functions over the threshold are flagged
+    let text = analyze(&["-t", "2"]);
+    assert!(text.contains("Above threshold (>2): 1"), "{}", text);
+    let rows: Vec<&str> = text
+        .lines()
+        .skip_while(|l| !l.starts_with("## Functions"))
+        .skip(1)
+        .collect();
+    assert!(rows[0].contains("a.py:4") && rows[0].contains("branchy"));
+    assert!(rows[0].trim_end().ends_with('!'));
+    assert!(rows[1].contains("b.go:3") && !rows[1].ends_with('!'));
+
+    // JSON has every function, not just the flagged ones
+    let json: serde_json::Value = serde_json::from_str(&analyze(&["--json"])).unwrap();
+    let complexity = &json["complexity"];
+    assert_eq!(complexity["threshold"], 10);
+    let functions = complexity["functions"].as_array().unwrap();
+    assert_eq!(functions.len(), 3);
+    assert_eq!(functions[0]["name"], "branchy");
+    assert_eq!(functions[0]["complexity"], 4);
+    assert_eq!(functions[0]["flagged"], false);
+}
+
+#[test]
+fn test_health_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"x\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = \"1\"\nregex = \"1\"\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+
+    let output = common::moss(root)
+        .args(["analyze", "--health", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let health = &json["health"];
+    assert_eq!(health["dependencies"]["cargo"], 2);
+    assert!(health["score"].as_u64().unwrap() <= 100);
+    assert!(health["grade"].is_string());
+    assert!(health["total_symbols"].is_u64());
+    // null when no linter is installed, counts otherwise
+    assert!(health["diagnostics"].is_null() || health["diagnostics"]["errors"].is_u64());
+}
+
+#[test]
+fn test_import_cycles() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("a.py"),
+        "import os\nimport b\n\ndef main():\n    b.go()\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("b.py"),
+        "from c import run\n\ndef go():\n    run()\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("c.py"),
+        "import a\nimport requests\n\ndef run():\n    a.main()\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("d.py"), "import a\n").unwrap();
+
+    let moss = |args: &[&str]| common::moss(root).args(args).output().unwrap();
+    assert!(moss(&["index", "rebuild", "--call-graph"]).status.success());
+
+    // os and requests aren't project files, so they're no part of the graph
+    let output = moss(&["analyze", "--import-cycles", "--json"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        json["cycles"],
+        serde_json::json!([["a.py", "b.py", "c.py"]])
+    );
+
+    std::fs::write(root.join("c.py"), "def run():\n    pass\n").unwrap();
+    // mtimes are compared in whole seconds; make sure the rewrite is seen as newer
+    std::fs::File::options()
+        .write(true)
+        .open(root.join("c.py"))
+        .unwrap()
+        .set_modified(std::time::SystemTime::now() + std::time::Duration::from_secs(2))
+        .unwrap();
+    let output = moss(&["analyze", "--import-cycles"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No import cycles"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_error_on_sets_exit_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join(".moss")).unwrap();
+    // A custom tool that reports one warning
+    std::fs::write(
+        root.join("report.sarif"),
+        r#"{"version": "2.1.0", "runs": [{"tool": {"driver": {"name": "fake"}}, "results": [
+            {"ruleId": "W1", "level": "warning", "message": {"text": "careful"},
+             "locations": [{"physicalLocation": {"artifactLocation": {"uri": "notes.txt"}}}]}
+        ]}]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        root.join(".moss/tools.toml"),
+        r#"
+        [tools.fake]
+        command = ["cat", "report.sarif"]
+        check_cmd = ["true"]
+        detect = ["report.sarif"]
+        "#,
+    )
+    .unwrap();
+
+    let lint = |args: &[&str]| {
+        common::moss(root)
+            .args(["analyze", "--lint", "--no-cache"])
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = lint(&[]);
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("careful"),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(lint(&["--error-on", "warning"]).status.code(), Some(1));
+    assert_eq!(lint(&["--error-on", "error"]).status.code(), Some(0));
+    // Filtered out before the threshold is checked
+    let filtered = lint(&["--min-severity", "error", "--error-on", "warning"]);
+    assert_eq!(filtered.status.code(), Some(0));
+    assert!(lint(&["--error-on", "fatal"]).status.code() != Some(0));
+}
@@ -0,0 +1,13 @@
+//! Shared helpers for the end-to-end tests.
+
+use std::path::Path;
+use std::process::Command;
+
+/// A `moss` invocation run from `dir`, with its index kept under `dir/.moss`.
+pub fn moss(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_moss"));
+    command
+        .current_dir(dir)
+        .env("MOSS_INDEX_DIR", dir.join(".moss"));
+    command
+}
@@ -0,0 +1,122 @@
+//! End-to-end tests for the daemon's JSON-RPC interface.
+
+mod common;
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::time::{Duration, Instant};
+
+fn call(stream: &mut UnixStream, request: serde_json::Value) -> serde_json::Value {
+    stream
+        .write_all(format!("{}\n", request).as_bytes())
+        .unwrap();
+    let mut line = String::new();
+    BufReader::new(&*stream).read_line(&mut line).unwrap();
+    serde_json::from_str(&line).unwrap()
+}
+
+#[test]
+fn test_json_rpc_methods() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.py"),
+        "def helper():\n    return 1\n\nclass Runner:\n    def run(self):\n        return helper()\n",
+    )
+    .unwrap();
+
+    let mut daemon = common::moss(dir.path())
+        .env_remove("MOSS_INDEX_DIR")
+        .args(["daemon", "run"])
+        .spawn()
+        .unwrap();
+
+    let socket = dir.path().join(".moss/daemon.sock");
+    let start = Instant::now();
+    while !socket.exists() {
+        assert!(
+            start.elapsed() < Duration::from_secs(30),
+            "daemon never bound"
+        );
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let mut stream = UnixStream::connect(&socket).unwrap();
+
+    let resolved = call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "resolvePath", "params": {"query": "app.py/Runner/run"}}),
+    );
+    assert_eq!(resolved["id"], 1);
+    assert_eq!(resolved["result"]["file"], "app.py");
+    assert_eq!(
+        resolved["result"]["symbol"],
+        serde_json::json!(["Runner", "run"])
+    );
+
+    let view = call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "view", "params": {"target": "app.py/Runner"}}),
+    );
+    assert_eq!(view["result"]["name"], "Runner");
+    assert_eq!(view["result"]["children"][0]["name"], "run");
+
+    let callers = call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "2.0", "id": 3, "method": "findCallers", "params": {"symbol": "helper"}}),
+    );
+    assert_eq!(callers["result"][0]["caller"], "run");
+
+    let symbols = call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "2.0", "id": 4, "method": "symbols", "params": {"file": "app.py"}}),
+    );
+    let names: Vec<_> = symbols["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["helper", "Runner", "run"]);
+
+    let search = call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "2.0", "id": 5, "method": "searchTree", "params": {"query": "runn"}}),
+    );
+    assert_eq!(search["result"]["symbols"][0]["name"], "Runner");
+
+    let unknown = call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "2.0", "id": 6, "method": "nope"}),
+    );
+    assert_eq!(unknown["error"]["code"], -32601);
+
+    let no_method = call(&mut stream, serde_json::json!({"jsonrpc": "2.0", "id": 8}));
+    assert_eq!(no_method["id"], 8);
+    assert_eq!(no_method["error"]["code"], -32600);
+
+    let wrong_version = call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "1.0", "id": 9, "method": "symbols", "params": {"file": "app.py"}}),
+    );
+    assert_eq!(wrong_version["error"]["code"], -32600);
+
+    // Notifications get no response: the next line read answers the request after it
+    stream
+        .write_all(b"{\"jsonrpc\": \"2.0\", \"method\": \"symbols\", \"params\": {\"file\": \"app.py\"}}\n")
+        .unwrap();
+    let after = call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "2.0", "id": 10, "method": "nope"}),
+    );
+    assert_eq!(after["id"], 10);
+
+    // The legacy protocol still works on the same socket
+    let status = call(&mut stream, serde_json::json!({"cmd": "status"}));
+    assert_eq!(status["ok"], true);
+
+    call(
+        &mut stream,
+        serde_json::json!({"jsonrpc": "2.0", "id": 7, "method": "shutdown"}),
+    );
+    assert!(daemon.wait().unwrap().success());
+    assert!(!socket.exists());
+}
@@ -0,0 +1,74 @@
+//! End-to-end tests for the global `--output` flag.
+
+mod common;
+
+#[test]
+fn test_output_writes_json_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.py"), "def foo():\n    pass\n").unwrap();
+    let out_path = dir.path().join("out.json");
+
+    let result = common::moss(dir.path())
+        .args(["--json", "view", "a.py", "--output"])
+        .arg(&out_path)
+        .output()
+        .unwrap();
+
+    assert!(result.status.success());
+    assert!(result.stdout.is_empty());
+
+    let written = std::fs::read_to_string(&out_path).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&written).unwrap();
+    assert_eq!(json["children"][0]["name"], "foo");
+}
+
+#[test]
+fn test_json_no_results_differs_from_error() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a.py"), "def foo():\n    pass\n").unwrap();
+    let run = |args: &[&str]| {
+        let result = common::moss(dir.path()).args(args).output().unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+        (result.status.code(), json)
+    };
+
+    let (code, empty) = run(&["--json", "path", "zzzz"]);
+    assert_eq!(code, Some(1));
+    assert_eq!(empty["results"], serde_json::json!([]));
+    assert!(empty["error"].is_null());
+
+    // No call graph has been built, so this fails rather than matching nothing
+    let (code, error) = run(&["--json", "similar", "foo"]);
+    assert_eq!(code, Some(1));
+    assert!(error["results"].is_null());
+    assert_eq!(error["error"]["kind"], "index");
+}
+
+#[test]
+fn test_generate_writes_output_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = r#"{"type": "object", "properties": {"name": {"type": "string"}}}"#;
+    std::fs::write(dir.path().join("schema.json"), schema).unwrap();
+    let out_path = dir.path().join("types.ts");
+
+    let result = common::moss(dir.path())
+        .args([
+            "generate",
+            "types",
+            "schema.json",
+            "--name",
+            "Config",
+            "--lang",
+            "typescript",
+            "-o",
+        ])
+        .arg(&out_path)
+        .output()
+        .unwrap();
+
+    assert!(result.status.success());
+    assert!(result.stdout.is_empty());
+    assert!(std::fs::read_to_string(&out_path)
+        .unwrap()
+        .contains("Config"));
+}
@@ -0,0 +1,52 @@
+//! End-to-end tests for `moss package` over the global package index.
+
+mod common;
+
+#[test]
+fn test_package_symbol_search() {
+    let dir = tempfile::tempdir().unwrap();
+    let goroot = dir.path().join("goroot");
+    let project = dir.path().join("project");
+    std::fs::create_dir_all(goroot.join("src/strings")).unwrap();
+    std::fs::create_dir_all(dir.path().join("gomod")).unwrap();
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(
+        goroot.join("src/strings/strings.go"),
+        "package strings\n\nfunc Contains(s, substr string) bool { return false }\n",
+    )
+    .unwrap();
+
+    let moss = |args: &[&str]| {
+        common::moss(&project)
+            .env("XDG_CACHE_HOME", dir.path().join("cache"))
+            .env("GOROOT", &goroot)
+            .env("GOMODCACHE", dir.path().join("gomod"))
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    assert!(moss(&["index", "packages", "--only", "go"])
+        .status
+        .success());
+
+    let result = moss(&[
+        "--json",
+        "package",
+        "symbol",
+        "Contains",
+        "--lang",
+        "go",
+        "--version",
+        "1.22",
+    ]);
+    assert!(result.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    assert_eq!(json["results"][0]["package"], "strings");
+    assert_eq!(json["results"][0]["line"], 3);
+
+    let result = moss(&["--json", "package", "symbol", "Missing", "--lang", "go"]);
+    assert_eq!(result.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&result.stdout).unwrap();
+    assert_eq!(json["results"], serde_json::json!([]));
+}
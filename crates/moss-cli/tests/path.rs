@@ -0,0 +1,39 @@
+//! End-to-end tests for `moss path`.
+
+mod common;
+
+#[test]
+fn test_path_kind_merges_symbols() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("src/lib.py"),
+        "def parse():\n    pass\n\nclass Parser:\n    def parse_all(self):\n        pass\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("src/parse.txt"), "").unwrap();
+
+    let moss = |args: &[&str]| common::moss(dir.path()).args(args).output().unwrap();
+
+    assert!(moss(&["index", "rebuild", "--call-graph"]).status.success());
+
+    let output = moss(&["path", "parse", "--kind", "file,function", "--json"]);
+    assert!(output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = value["results"].as_array().unwrap();
+    let kinds: Vec<&str> = results
+        .iter()
+        .map(|r| r["kind"].as_str().unwrap())
+        .collect();
+    // The exact symbol name outranks the file matched by its stem
+    assert_eq!(kinds, ["function", "file"]);
+    assert_eq!(results[0]["path"], "src/lib.py");
+    assert_eq!(results[0]["line"], 1);
+    assert_eq!(results[0]["tier"], "exact");
+    assert_eq!(results[1]["path"], "src/parse.txt");
+
+    // Class and method matches, limited
+    let output = moss(&["path", "parse", "--kind", "class,method", "--limit", "1"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "src/lib.py:4 Parser (class)");
+}
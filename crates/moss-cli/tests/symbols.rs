@@ -0,0 +1,80 @@
+//! End-to-end tests for `moss symbols`.
+
+mod common;
+
+#[test]
+fn test_symbols_all() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("a.py"),
+        "class Greeter:\n    def greet(self, name):\n        pass\n\ndef main():\n    pass\n",
+    )
+    .unwrap();
+    std::fs::write(root.join("b.rs"), "fn run() {}\n").unwrap();
+
+    let run = |args: &[&str]| common::moss(root).args(args).output().unwrap();
+    let moss = |args: &[&str]| {
+        let output = run(args);
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    };
+    moss(&["index", "rebuild", "--call-graph"]);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&moss(&["symbols", "--all", "--json"])).unwrap();
+    assert_eq!(json["error"], serde_json::Value::Null);
+    let symbols = json["results"].as_array().unwrap();
+    assert_eq!(symbols.len(), 4);
+    assert_eq!(
+        symbols[1],
+        serde_json::json!({
+            "name": "greet",
+            "kind": "method",
+            "file": "a.py",
+            "start_line": 2,
+            "end_line": 3,
+            "parent": "Greeter",
+            "signature": "def greet(self, name)",
+        })
+    );
+
+    // Filters combine; text output is one tab-separated line per symbol
+    let text = moss(&["symbols", "--all", "--kind", "function", "--lang", "rust"]);
+    assert_eq!(text, "b.rs:1-1\tfunction\trun\tfn run()\n");
+    // No matches: an empty list in the envelope, and exit code 1
+    let output = run(&["symbols", "--all", "--json", "--kind", "struct"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json, serde_json::json!({"results": [], "error": null}));
+}
+
+#[test]
+fn test_symbols_tags() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("a.py"),
+        "class Greeter:\n    def greet(self):\n        pass\n\ndef main():\n    pass\n",
+    )
+    .unwrap();
+
+    let moss = |args: &[&str]| {
+        let output = common::moss(root).args(args).output().unwrap();
+        assert!(output.status.success(), "{:?}", output);
+    };
+    moss(&["index", "rebuild", "--call-graph"]);
+    moss(&["symbols", "--tags"]);
+
+    let tags = std::fs::read_to_string(root.join("tags")).unwrap();
+    let entries: Vec<&str> = tags.lines().filter(|l| !l.starts_with("!_")).collect();
+    assert_eq!(
+        entries,
+        [
+            "Greeter\ta.py\t/^class Greeter:$/;\"\tc\tline:1",
+            "greet\ta.py\t/^    def greet(self):$/;\"\tm\tline:2\tclass:Greeter",
+            "main\ta.py\t/^def main():$/;\"\tf\tline:5",
+        ]
+    );
+    assert!(tags.contains("!_TAG_FILE_SORTED\t1"));
+}
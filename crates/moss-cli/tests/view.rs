@@ -0,0 +1,68 @@
+//! End-to-end tests for `moss view`.
+
+mod common;
+
+#[test]
+fn test_view_line_windows() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut source: String = (1..=10).map(|i| format!("# line {}\n", i)).collect();
+    source.push_str("def foo():\n    return 1\n");
+    std::fs::write(dir.path().join("a.py"), source).unwrap();
+
+    let moss = |args: &[&str]| common::moss(dir.path()).args(args).output().unwrap();
+    let stdout = |args: &[&str]| {
+        let output = moss(args);
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(
+        stdout(&["view", "a.py", "--lines", "2:3", "-n"]),
+        "   2 # line 2\n   3 # line 3\n"
+    );
+    assert_eq!(stdout(&["view", "a.py", "--lines", ":1"]), "# line 1\n");
+    assert_eq!(
+        stdout(&["view", "a.py", "--around", "1", "-C", "1"]),
+        "# line 1\n# line 2\n"
+    );
+    // A symbol target defaults to the symbol's own lines
+    assert_eq!(
+        stdout(&["view", "a.py:foo", "-C", "1"]),
+        "# line 10\ndef foo():\n    return 1\n"
+    );
+
+    let output = moss(&["view", "a.py", "--lines", "5:13"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("past the end of the file (12 lines)"));
+
+    let output = moss(&["--json", "view", "a.py", "--around", "13"]);
+    assert!(!output.status.success());
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["error"]["kind"], "invalid_range");
+}
+
+#[test]
+fn test_view_from_subdirectory() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join(".git")).unwrap();
+    std::fs::create_dir_all(root.join("pkg")).unwrap();
+    std::fs::write(root.join("pkg/a.py"), "def foo():\n    return 1\n").unwrap();
+
+    // Run below the repository root: targets are relative to the working
+    // directory, and the index lives at the root
+    let moss = |args: &[&str]| {
+        common::moss(&root.join("pkg"))
+            .env_remove("MOSS_INDEX_DIR")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let output = moss(&["view", "a.py/foo"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("def foo()"));
+
+    assert!(moss(&["index", "rebuild"]).status.success());
+    assert!(root.join(".moss").is_dir());
+    assert!(!root.join("pkg/.moss").exists());
+}
@@ -0,0 +1,59 @@
+//! End-to-end tests for Lua workflows.
+
+mod common;
+
+#[test]
+fn test_parallel_analyze() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::create_dir_all(root.join("a")).unwrap();
+    std::fs::create_dir_all(root.join("b")).unwrap();
+    std::fs::create_dir_all(root.join(".moss/workflows")).unwrap();
+    std::fs::write(root.join("a/x.py"), "def f():\n    return 1\n").unwrap();
+    std::fs::write(root.join("b/y.py"), "def g():\n    return 2\n").unwrap();
+    std::fs::write(
+        root.join(".moss/workflows/p.lua"),
+        r#"
+        local r = parallel({
+            a = {"analyze", "--health", "a"},
+            b = {"analyze", "--health", "b"},
+        })
+        assert(r.a.success and r.b.success)
+        assert(r.a.output:find("Analysis: a", 1, true))
+        assert(r.b.output:find("Analysis: b", 1, true))
+
+        -- Without fail_fast, one failure doesn't stop the others
+        local r = parallel({
+            ok = {"analyze", "--health", "a"},
+            bad = {"view", "missing.py"},
+        }, { fail_fast = false })
+        assert(r.ok.success and not r.bad.success)
+        assert(r.bad.stderr:find("No matches for: missing.py", 1, true))
+        "#,
+    )
+    .unwrap();
+
+    let run = |workflow: &str| {
+        common::moss(root)
+            .args(["workflow", "run", workflow])
+            .output()
+            .unwrap()
+    };
+
+    let result = run("p");
+    assert!(
+        result.status.success(),
+        "{}",
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    // fail_fast (the default) turns the failure into a workflow error
+    std::fs::write(
+        root.join(".moss/workflows/f.lua"),
+        r#"parallel({ bad = {"view", "missing.py"} })"#,
+    )
+    .unwrap();
+    let result = run("f");
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("parallel action 'bad' failed"));
+}
@@ -1,7 +1,7 @@
 //! Ada language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -39,6 +39,10 @@ impl Language for Ada {
         ]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[
             "full_type_declaration",
@@ -195,6 +199,14 @@ impl Language for Ada {
         None
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         match node.kind() {
             "with_clause" => {
@@ -1,7 +1,7 @@
 //! AWK language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -31,6 +31,10 @@ impl Language for Awk {
         &["func_def"]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -142,6 +146,14 @@ impl Language for Awk {
         Some(doc_lines.join(" "))
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         Vec::new()
     }
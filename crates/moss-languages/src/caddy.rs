@@ -1,7 +1,7 @@
 //! Caddyfile configuration support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -30,6 +30,9 @@ impl Language for Caddy {
     fn function_kinds(&self) -> &'static [&'static str] {
         &[]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -111,6 +114,14 @@ impl Language for Caddy {
         None
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "import" {
             return Vec::new();
@@ -2,7 +2,7 @@
 
 use crate::c_cpp;
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -30,6 +30,9 @@ impl Language for Cpp {
     fn function_kinds(&self) -> &'static [&'static str] {
         &["function_definition"]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["lambda_expression"]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &[
             "class_specifier",
@@ -86,9 +89,7 @@ impl Language for Cpp {
             "do_statement",
             "switch_statement",
             "case_statement",
-            "try_statement",
             "catch_clause",
-            "throw_statement",
             "&&",
             "||",
             "conditional_expression",
@@ -160,6 +161,14 @@ impl Language for Cpp {
         None
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "preproc_include" {
             return Vec::new();
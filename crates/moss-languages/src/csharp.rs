@@ -1,9 +1,183 @@
 //! C# language support.
 
-use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::external_packages::{version_cmp, ResolvedPackage, Version};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// ============================================================================
+// C# external package resolution (NuGet)
+// ============================================================================
+
+/// Get .NET SDK version.
+pub fn get_dotnet_version() -> Option<String> {
+    let output = Command::new("dotnet").args(["--version"]).output().ok()?;
+
+    if output.status.success() {
+        // "8.0.100" -> "8.0"
+        let version_str = String::from_utf8_lossy(&output.stdout);
+        let parts: Vec<&str> = version_str.trim().split('.').collect();
+        if parts.len() >= 2 {
+            return Some(format!("{}.{}", parts[0], parts[1]));
+        }
+    }
+
+    None
+}
+
+/// Find the NuGet global packages folder.
+/// Structure: ~/.nuget/packages/<id>/<version>/lib/<tfm>/
+pub fn find_nuget_packages() -> Option<PathBuf> {
+    // Check NUGET_PACKAGES env var
+    if let Ok(packages) = std::env::var("NUGET_PACKAGES") {
+        let packages = PathBuf::from(packages);
+        if packages.is_dir() {
+            return Some(packages);
+        }
+    }
+
+    // Fall back to ~/.nuget/packages (USERPROFILE on Windows)
+    for var in ["HOME", "USERPROFILE"] {
+        if let Ok(home) = std::env::var(var) {
+            let packages = PathBuf::from(home).join(".nuget").join("packages");
+            if packages.is_dir() {
+                return Some(packages);
+            }
+        }
+    }
+
+    None
+}
+
+/// Resolve a `using` namespace to a NuGet package's `lib/<tfm>/` directory.
+///
+/// Package ids conventionally match their root namespace, so the longest
+/// namespace prefix with a package directory wins: `Newtonsoft.Json.Linq`
+/// tries `newtonsoft.json.linq`, then `newtonsoft.json`, then `newtonsoft`.
+fn resolve_nuget_package(
+    namespace: &str,
+    packages: &Path,
+    runtime: Option<Version>,
+) -> Option<ResolvedPackage> {
+    let segments: Vec<&str> = namespace.split('.').collect();
+    for len in (1..=segments.len()).rev() {
+        let id = segments[..len].join(".");
+        // The global packages folder stores ids lowercased
+        let pkg_dir = packages.join(id.to_lowercase());
+        if !pkg_dir.is_dir() {
+            continue;
+        }
+        let Some(version_dir) = latest_version_dir(&pkg_dir) else {
+            continue;
+        };
+        let path = best_framework_dir(&version_dir.join("lib"), runtime).unwrap_or(version_dir);
+        return Some(ResolvedPackage {
+            path,
+            name: id,
+            is_namespace: false,
+        });
+    }
+
+    None
+}
+
+/// Pick the highest version directory of a package (`<id>/<version>/`).
+fn latest_version_dir(pkg_dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(pkg_dir)
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .max_by(|a, b| version_cmp(a, b))
+        .map(|version| pkg_dir.join(version))
+}
+
+/// Ordering key for a target framework moniker: family, then version, then
+/// platform-neutral over platform-specific (`net8.0` over `net8.0-windows`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct FrameworkRank {
+    family: FrameworkFamily,
+    version: Version,
+    portable: bool,
+}
+
+/// Target framework families, least to most preferred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum FrameworkFamily {
+    /// .NET Framework: net45, net472
+    Framework,
+    /// .NET Core 1-3: netcoreapp3.1
+    CoreApp,
+    /// netstandard2.0
+    Standard,
+    /// .NET 5+: net6.0, net8.0-windows
+    Net,
+}
+
+fn parse_tfm(tfm: &str) -> Option<FrameworkRank> {
+    let tfm = tfm.to_lowercase();
+    let (base, portable) = match tfm.split_once('-') {
+        Some((base, _platform)) => (base, false),
+        None => (tfm.as_str(), true),
+    };
+    let (family, version) = if let Some(v) = base.strip_prefix("netstandard") {
+        (FrameworkFamily::Standard, Version::parse(v)?)
+    } else if let Some(v) = base.strip_prefix("netcoreapp") {
+        (FrameworkFamily::CoreApp, Version::parse(v)?)
+    } else if let Some(v) = base.strip_prefix("net") {
+        if v.contains('.') {
+            (FrameworkFamily::Net, Version::parse(v)?)
+        } else {
+            // net472 -> 4.72
+            let major = v.get(..1)?.parse().ok()?;
+            let minor = v.get(1..).filter(|m| !m.is_empty()).unwrap_or("0");
+            let minor = minor.parse().ok()?;
+            (FrameworkFamily::Framework, Version { major, minor })
+        }
+    } else {
+        return None;
+    };
+    Some(FrameworkRank {
+        family,
+        version,
+        portable,
+    })
+}
+
+/// Pick the most suitable `lib/<tfm>/` directory for the given runtime.
+///
+/// .NET Core/.NET 5+ frameworks newer than the runtime are not loadable and
+/// are skipped; otherwise the newest modern framework is preferred over
+/// netstandard, and netstandard over .NET Framework.
+fn best_framework_dir(lib: &Path, runtime: Option<Version>) -> Option<PathBuf> {
+    std::fs::read_dir(lib)
+        .ok()?
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let rank = parse_tfm(&e.file_name().to_string_lossy())?;
+            let modern = matches!(rank.family, FrameworkFamily::CoreApp | FrameworkFamily::Net);
+            if modern && runtime.is_some_and(|rt| rank.version > rt) {
+                return None;
+            }
+            Some(FrameworkCandidate {
+                rank,
+                path: e.path(),
+            })
+        })
+        .max_by_key(|c| c.rank)
+        .map(|c| c.path)
+}
+
+struct FrameworkCandidate {
+    rank: FrameworkRank,
+    path: PathBuf,
+}
+
+// ============================================================================
+// C# language support
+// ============================================================================
 
 /// C# language support.
 pub struct CSharp;
@@ -44,6 +218,10 @@ impl Language for CSharp {
         ]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["lambda_expression", "anonymous_method_expression"]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[
             "class_declaration",
@@ -271,6 +449,14 @@ impl Language for CSharp {
         }
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "using_directive" {
             return Vec::new();
@@ -407,15 +593,18 @@ impl Language for CSharp {
 
     fn resolve_external_import(
         &self,
-        _import_name: &str,
-        _project_root: &Path,
+        import_name: &str,
+        project_root: &Path,
     ) -> Option<ResolvedPackage> {
-        // NuGet package resolution would go here
-        None
+        let packages = find_nuget_packages()?;
+        let runtime = self
+            .get_version(project_root)
+            .and_then(|v| Version::parse(&v));
+        resolve_nuget_package(import_name, &packages, runtime)
     }
 
     fn get_version(&self, project_root: &Path) -> Option<String> {
-        // Try to find .NET version from global.json or .csproj
+        // Prefer the SDK pinned in global.json, then the installed SDK
         let global_json = project_root.join("global.json");
         if global_json.is_file() {
             if let Ok(content) = std::fs::read_to_string(&global_json) {
@@ -434,24 +623,11 @@ impl Language for CSharp {
                 }
             }
         }
-        None
+        get_dotnet_version()
     }
 
     fn find_package_cache(&self, _project_root: &Path) -> Option<PathBuf> {
-        // NuGet cache location
-        if let Ok(home) = std::env::var("HOME") {
-            let cache = PathBuf::from(home).join(".nuget").join("packages");
-            if cache.is_dir() {
-                return Some(cache);
-            }
-        }
-        if let Ok(home) = std::env::var("USERPROFILE") {
-            let cache = PathBuf::from(home).join(".nuget").join("packages");
-            if cache.is_dir() {
-                return Some(cache);
-            }
-        }
-        None
+        find_nuget_packages()
     }
 
     fn indexable_extensions(&self) -> &'static [&'static str] {
@@ -531,6 +707,38 @@ mod tests {
     use super::*;
     use crate::validate_unused_kinds_audit;
 
+    #[test]
+    fn test_resolve_nuget_package() {
+        let packages = tempfile::tempdir().unwrap();
+        let pkg = packages.path().join("newtonsoft.json");
+        std::fs::create_dir_all(pkg.join("12.0.1/lib/net45")).unwrap();
+        for tfm in [
+            "net45",
+            "netstandard2.0",
+            "net6.0",
+            "net9.0",
+            "net6.0-windows",
+        ] {
+            std::fs::create_dir_all(pkg.join("13.0.3/lib").join(tfm)).unwrap();
+        }
+
+        let net8 = Some(Version { major: 8, minor: 0 });
+        let resolved =
+            resolve_nuget_package("Newtonsoft.Json.Linq", packages.path(), net8).unwrap();
+        assert_eq!(resolved.name, "Newtonsoft.Json");
+        assert_eq!(resolved.path, pkg.join("13.0.3/lib/net6.0"));
+
+        let resolved = resolve_nuget_package("Newtonsoft.Json", packages.path(), None).unwrap();
+        assert_eq!(resolved.path, pkg.join("13.0.3/lib/net9.0"));
+
+        let old_runtime = Some(Version { major: 3, minor: 1 });
+        let resolved =
+            resolve_nuget_package("Newtonsoft.Json", packages.path(), old_runtime).unwrap();
+        assert_eq!(resolved.path, pkg.join("13.0.3/lib/netstandard2.0"));
+
+        assert!(resolve_nuget_package("Serilog", packages.path(), net8).is_none());
+    }
+
     #[test]
     fn unused_node_kinds_audit() {
         #[rustfmt::skip]
@@ -1,10 +1,21 @@
 //! Dart language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
+/// Function and method heads. A head followed by a `function_body` is
+/// reported through the body, which is where its statements are.
+const SIGNATURE_KINDS: &[&str] = &[
+    "function_signature",
+    "method_signature",
+    "getter_signature",
+    "setter_signature",
+    "constructor_signature",
+    "factory_constructor_signature",
+];
+
 /// Dart language support.
 pub struct Dart;
 
@@ -39,9 +50,15 @@ impl Language for Dart {
             "function_body",
             "getter_signature",
             "setter_signature",
+            "constructor_signature",
+            "factory_constructor_signature",
         ]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[
             "class_definition",
@@ -52,15 +69,19 @@ impl Language for Dart {
     }
 
     fn import_kinds(&self) -> &'static [&'static str] {
-        &["import_specification", "library_export"]
+        &["import_or_export"]
     }
 
     fn public_symbol_kinds(&self) -> &'static [&'static str] {
         &[
             "class_definition",
+            "mixin_declaration",
+            "extension_declaration",
+            "enum_declaration",
+            "type_alias",
             "function_signature",
             "method_signature",
-            "enum_declaration",
+            "function_body",
         ]
     }
 
@@ -80,12 +101,11 @@ impl Language for Dart {
         }
 
         let kind = match node.kind() {
-            "class_definition" => SymbolKind::Class,
+            "class_definition" | "mixin_declaration" | "extension_declaration" => SymbolKind::Class,
             "enum_declaration" => SymbolKind::Enum,
-            "mixin_declaration" => SymbolKind::Class,
-            "function_signature" | "function_body" => SymbolKind::Function,
-            "method_signature" => SymbolKind::Method,
-            _ => return Vec::new(),
+            "type_alias" => SymbolKind::Type,
+            _ if Self::is_member(node) => SymbolKind::Method,
+            _ => SymbolKind::Function,
         };
 
         vec![Export {
@@ -150,38 +170,25 @@ impl Language for Dart {
         ]
     }
 
-    fn extract_function(&self, node: &Node, content: &str, _in_container: bool) -> Option<Symbol> {
+    fn extract_function(&self, node: &Node, content: &str, in_container: bool) -> Option<Symbol> {
         let name = self.node_name(node, content)?;
-
-        let return_type = node
-            .child_by_field_name("return_type")
-            .map(|t| content[t.byte_range()].to_string());
-
-        let params = node
-            .child_by_field_name("formal_parameters")
-            .or_else(|| node.child_by_field_name("parameters"))
-            .map(|p| content[p.byte_range()].to_string())
-            .unwrap_or_else(|| "()".to_string());
-
-        let is_method = node.kind().contains("method");
-        let kind = if is_method {
-            SymbolKind::Method
-        } else {
-            SymbolKind::Function
-        };
-
-        let signature = if let Some(ret) = return_type {
-            format!("{} {}{}", ret, name, params)
+        // A body is reported from its head, which carries the signature and docs
+        let head = if node.kind() == "function_body" {
+            node.prev_named_sibling()?
         } else {
-            format!("{}{}", name, params)
+            *node
         };
 
         Some(Symbol {
             name: name.to_string(),
-            kind,
-            signature,
-            docstring: self.extract_docstring(node, content),
-            start_line: node.start_position().row + 1,
+            kind: if in_container {
+                SymbolKind::Method
+            } else {
+                SymbolKind::Function
+            },
+            signature: Self::collapse(&content[head.byte_range()]),
+            docstring: self.extract_docstring(&head, content),
+            start_line: head.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: self.get_visibility(node, content),
             children: Vec::new(),
@@ -190,32 +197,22 @@ impl Language for Dart {
 
     fn extract_container(&self, node: &Node, content: &str) -> Option<Symbol> {
         let name = self.node_name(node, content)?;
-        let (kind, keyword) = match node.kind() {
-            "enum_declaration" => (SymbolKind::Enum, "enum"),
-            "mixin_declaration" => (SymbolKind::Class, "mixin"),
-            "extension_declaration" => (SymbolKind::Class, "extension"),
-            _ => (SymbolKind::Class, "class"),
+        let kind = match node.kind() {
+            "enum_declaration" => SymbolKind::Enum,
+            "type_alias" => SymbolKind::Type,
+            _ => SymbolKind::Class,
         };
 
-        // Check for abstract
-        let is_abstract = node
-            .parent()
-            .map(|p| {
-                let text = &content[p.byte_range()];
-                text.contains("abstract ")
-            })
-            .unwrap_or(false);
-
-        let prefix = if is_abstract {
-            format!("abstract {}", keyword)
-        } else {
-            keyword.to_string()
-        };
+        // Everything before the body: modifiers, supertypes, `on` clauses
+        let end = self
+            .container_body(node)
+            .map_or(node.end_byte(), |body| body.start_byte());
+        let head = Self::collapse(&content[node.start_byte()..end]);
 
         Some(Symbol {
             name: name.to_string(),
             kind,
-            signature: format!("{} {}", prefix, name),
+            signature: head.trim_end_matches(';').to_string(),
             docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
@@ -229,75 +226,108 @@ impl Language for Dart {
     }
 
     fn extract_docstring(&self, node: &Node, content: &str) -> Option<String> {
-        // Dart uses /// for doc comments
-        let mut prev = node.prev_sibling();
+        // Dart uses /// for doc comments, one node per line; annotations sit
+        // between the comment and the declaration
+        let mut prev = node.prev_named_sibling();
+        while let Some(sibling) = prev.filter(|s| s.kind() == "annotation") {
+            prev = sibling.prev_named_sibling();
+        }
         let mut doc_lines = Vec::new();
 
         while let Some(sibling) = prev {
             let text = &content[sibling.byte_range()];
             if sibling.kind() == "documentation_comment" || text.starts_with("///") {
-                let line = text.strip_prefix("///").unwrap_or(text).trim();
-                doc_lines.push(line.to_string());
-                prev = sibling.prev_sibling();
+                for line in text.lines().rev() {
+                    let line = line.trim();
+                    doc_lines.push(line.strip_prefix("///").unwrap_or(line).trim().to_string());
+                }
+                prev = sibling.prev_named_sibling();
             } else {
                 break;
             }
         }
 
+        doc_lines.retain(|l| !l.is_empty());
         if doc_lines.is_empty() {
             return None;
         }
-
         doc_lines.reverse();
         Some(doc_lines.join(" "))
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
-        if node.kind() != "import_specification" && node.kind() != "library_export" {
+        if node.kind() != "import_or_export" {
             return Vec::new();
         }
-
-        let text = &content[node.byte_range()];
-        let line = node.start_position().row + 1;
-
-        // Extract the import URI
-        if let Some(start) = text.find('\'').or_else(|| text.find('"')) {
-            let quote = text.chars().nth(start).unwrap();
-            let rest = &text[start + 1..];
-            if let Some(end) = rest.find(quote) {
-                let module = rest[..end].to_string();
-                let is_relative = module.starts_with('.') || module.starts_with('/');
-
-                // Check for 'as' alias
-                let alias = if text.contains(" as ") {
-                    text.split(" as ")
-                        .nth(1)
-                        .and_then(|s| s.split(';').next())
-                        .map(|s| s.trim().to_string())
-                } else {
-                    None
-                };
-
-                return vec![Import {
-                    module,
-                    names: Vec::new(),
-                    alias,
-                    is_wildcard: text.contains(" show ") || text.contains(" hide "),
-                    is_relative,
-                    line,
-                }];
+        // import_or_export > library_import > import_specification, or
+        // import_or_export > library_export
+        let Some(mut spec) = node.named_child(0) else {
+            return Vec::new();
+        };
+        if spec.kind() == "library_import" {
+            match spec.named_child(0) {
+                Some(inner) => spec = inner,
+                None => return Vec::new(),
+            }
+        }
+        let Some(uri) = spec
+            .named_children(&mut spec.walk())
+            .find(|c| c.kind() == "configurable_uri")
+        else {
+            return Vec::new();
+        };
+        let module = content[uri.byte_range()]
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_matches(|c| c == '\'' || c == '"')
+            .to_string();
+
+        // `as prefix`, then `show a, b` or `hide c`
+        let alias = spec
+            .named_children(&mut spec.walk())
+            .find(|c| c.kind() == "identifier")
+            .map(|a| content[a.byte_range()].to_string());
+        let mut names = Vec::new();
+        let mut hides = false;
+        for combinator in spec
+            .named_children(&mut spec.walk())
+            .filter(|c| c.kind() == "combinator")
+        {
+            if combinator.child(0).is_some_and(|k| k.kind() == "hide") {
+                hides = true;
+                continue;
             }
+            names.extend(
+                combinator
+                    .named_children(&mut combinator.walk())
+                    .map(|n| content[n.byte_range()].to_string()),
+            );
         }
 
-        Vec::new()
+        vec![Import {
+            is_wildcard: names.is_empty() || hides,
+            // package: and dart: URIs are absolute; anything else is a path
+            is_relative: !module.contains(':'),
+            module,
+            names,
+            alias,
+            line: node.start_position().row + 1,
+        }]
     }
 
     fn is_public(&self, node: &Node, content: &str) -> bool {
-        if let Some(name) = self.node_name(node, content) {
-            !name.starts_with('_')
-        } else {
-            true
-        }
+        // Library-private names start with _, including named constructors (Foo._)
+        self.node_name(node, content)
+            .is_none_or(|name| !name.rsplit('.').next().unwrap_or(name).starts_with('_'))
     }
 
     fn get_visibility(&self, node: &Node, content: &str) -> Visibility {
@@ -313,7 +343,10 @@ impl Language for Dart {
     }
 
     fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
-        node.child_by_field_name("body")
+        node.child_by_field_name("body").or_else(|| {
+            node.named_children(&mut node.walk())
+                .find(|c| c.kind() == "class_body")
+        })
     }
 
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
@@ -321,8 +354,36 @@ impl Language for Dart {
     }
 
     fn node_name<'a>(&self, node: &Node, content: &'a str) -> Option<&'a str> {
-        node.child_by_field_name("name")
-            .map(|n| &content[n.byte_range()])
+        if node.kind() == "function_body" {
+            let head = node
+                .prev_named_sibling()
+                .filter(|h| SIGNATURE_KINDS.contains(&h.kind()))?;
+            return Self::signature_name(&head, content);
+        }
+        if SIGNATURE_KINDS.contains(&node.kind()) {
+            // Named through the body that follows, or the method_signature around it
+            let has_body = node
+                .next_named_sibling()
+                .is_some_and(|n| n.kind() == "function_body");
+            let wrapped = node
+                .parent()
+                .is_some_and(|p| p.kind() == "method_signature");
+            if has_body || wrapped {
+                return None;
+            }
+            return Self::signature_name(node, content);
+        }
+        let name = node.child_by_field_name("name").or_else(|| {
+            // Mixins and typedefs don't label their name
+            let kind = match node.kind() {
+                "mixin_declaration" => "identifier",
+                "type_alias" => "type_identifier",
+                _ => return None,
+            };
+            node.named_children(&mut node.walk())
+                .find(|c| c.kind() == kind)
+        })?;
+        Some(&content[name.byte_range()])
     }
 
     fn file_path_to_module_name(&self, path: &Path) -> Option<String> {
@@ -463,11 +524,87 @@ impl Language for Dart {
     }
 }
 
+impl Dart {
+    fn collapse(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Whether a declaration sits directly in a class, mixin, enum or extension.
+    fn is_member(node: &Node) -> bool {
+        let mut parent = node.parent();
+        while let Some(p) = parent.filter(|p| p.kind() == "declaration") {
+            parent = p.parent();
+        }
+        parent.is_some_and(|p| matches!(p.kind(), "class_body" | "enum_body" | "extension_body"))
+    }
+
+    /// The name a function head declares; constructors are `Class` or `Class.name`.
+    fn signature_name<'a>(head: &Node, content: &'a str) -> Option<&'a str> {
+        let head = if head.kind() == "method_signature" {
+            head.named_children(&mut head.walk())
+                .find(|c| SIGNATURE_KINDS.contains(&c.kind()))?
+        } else {
+            *head
+        };
+        let parts: Vec<Node> = match head.kind() {
+            "constructor_signature" => head
+                .children_by_field_name("name", &mut head.walk())
+                .collect(),
+            "factory_constructor_signature" => head
+                .named_children(&mut head.walk())
+                .take_while(|c| c.kind() == "identifier")
+                .collect(),
+            _ => head.child_by_field_name("name").into_iter().collect(),
+        };
+        let (first, last) = (parts.first()?, parts.last()?);
+        Some(&content[first.start_byte()..last.end_byte()])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::validate_unused_kinds_audit;
 
+    #[test]
+    fn test_dart_imports() {
+        let content = "import 'package:flutter/material.dart';\nimport 'dart:async' as async show Future, Stream;\nimport 'src/util.dart' hide internal;\nexport 'src/model.dart';\n";
+        let store = arborium::GrammarStore::new();
+        let mut parser = arborium::tree_sitter::Parser::new();
+        parser
+            .set_language(store.get("dart").unwrap().language())
+            .unwrap();
+        let tree = parser.parse(content, None).unwrap();
+        let root = tree.root_node();
+        let imports: Vec<Import> = root
+            .named_children(&mut root.walk())
+            .flat_map(|node| Dart.extract_imports(&node, content))
+            .collect();
+        let summary: Vec<(&str, Option<&str>, Vec<&str>, bool)> = imports
+            .iter()
+            .map(|i| {
+                (
+                    i.module.as_str(),
+                    i.alias.as_deref(),
+                    i.names.iter().map(String::as_str).collect(),
+                    i.is_wildcard,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("package:flutter/material.dart", None, vec![], true),
+                ("dart:async", Some("async"), vec!["Future", "Stream"], false),
+                ("src/util.dart", None, vec![], true),
+                ("src/model.dart", None, vec![], true),
+            ]
+        );
+        // package: and dart: URIs aren't paths
+        let relative: Vec<bool> = imports.iter().map(|i| i.is_relative).collect();
+        assert_eq!(relative, [false, false, true, true]);
+    }
+
     #[test]
     fn unused_node_kinds_audit() {
         #[rustfmt::skip]
@@ -479,17 +616,17 @@ mod tests {
             "bitwise_xor_expression", "cascade_section", "case_builtin",
             "catch_parameters", "class_body", "const_object_expression",
             "constant_constructor_signature", "constructor_invocation",
-            "constructor_param", "constructor_signature", "constructor_tearoff",
+            "constructor_param", "constructor_tearoff",
             "declaration", "dotted_identifier_list", "enum_body", "enum_constant",
             "equality_expression", "equality_operator", "expression_statement",
-            "extension_body", "extension_type_declaration", "factory_constructor_signature",
+            "extension_body", "extension_type_declaration",
             "finally_clause", "for_element", "for_loop_parts", "formal_parameter",
             "formal_parameter_list", "function_expression_body", "function_type",
             "identifier", "identifier_dollar_escaped", "identifier_list",
-            "if_element", "if_null_expression", "import_or_export", "increment_operator",
+            "if_element", "if_null_expression", "import_specification", "increment_operator",
             "inferred_type", "initialized_identifier", "initialized_identifier_list",
             "initialized_variable_definition", "initializer_list_entry", "interface",
-            "interfaces", "is_operator", "label", "lambda_expression",
+            "interfaces", "is_operator", "label", "lambda_expression", "library_export",
             "library_import", "library_name", "local_function_declaration",
             "local_variable_declaration", "logical_and_operator", "logical_or_operator",
             "minus_operator", "mixin_application_class", "multiplicative_expression",
@@ -1,7 +1,7 @@
 //! Diff/patch file support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -30,6 +30,9 @@ impl Language for Diff {
     fn function_kinds(&self) -> &'static [&'static str] {
         &[]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -126,6 +129,12 @@ impl Language for Diff {
     fn extract_docstring(&self, _node: &Node, _content: &str) -> Option<String> {
         None
     }
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         Vec::new()
     }
@@ -4,7 +4,7 @@
 //! Each language struct delegates to these functions for DRY implementation.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Symbol, SymbolKind, Visibility};
+use crate::{Export, Import, Param, Symbol, SymbolKind, Visibility};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -21,6 +21,7 @@ pub const JS_FUNCTION_KINDS: &[&str] = &[
     "generator_function_declaration",
 ];
 pub const TS_FUNCTION_KINDS: &[&str] = &["function_declaration", "method_definition"];
+pub const ANONYMOUS_FUNCTION_KINDS: &[&str] = &["arrow_function", "function_expression"];
 
 pub const JS_TYPE_KINDS: &[&str] = &["class_declaration"];
 pub const TS_TYPE_KINDS: &[&str] = &[
@@ -77,7 +78,7 @@ pub const NESTING_NODES: &[&str] = &[
     "while_statement",
     "do_statement",
     "switch_statement",
-    "try_statement",
+    "catch_clause",
     "function_declaration",
     "method_definition",
     "class_declaration",
@@ -152,6 +153,74 @@ pub fn extract_type(node: &Node, name: &str) -> Option<Symbol> {
     })
 }
 
+/// Parameters of a function, method or arrow function.
+pub fn parameters(node: &Node, content: &str) -> Vec<Param> {
+    let Some(params) = node.child_by_field_name("parameters") else {
+        // Single-parameter arrow function: `x => x + 1`
+        return node
+            .child_by_field_name("parameter")
+            .map(|p| vec![simple_param(&p, content)])
+            .unwrap_or_default();
+    };
+    let mut cursor = params.walk();
+    params
+        .named_children(&mut cursor)
+        .filter_map(|p| {
+            let text = |field: &str| {
+                p.child_by_field_name(field)
+                    .map(|n| content[n.byte_range()].to_string())
+            };
+            match p.kind() {
+                // TypeScript: `x: number = 1`, `x?: string`
+                "required_parameter" | "optional_parameter" => {
+                    let pattern = p.child_by_field_name("pattern")?;
+                    let name = content[pattern.byte_range()].to_string();
+                    let default = text("value");
+                    Some(Param {
+                        optional: p.kind() == "optional_parameter"
+                            || default.is_some()
+                            || pattern.kind() == "rest_pattern",
+                        ty: p
+                            .child_by_field_name("type")
+                            .map(|t| type_annotation(&content[t.byte_range()])),
+                        default,
+                        name,
+                    })
+                }
+                // JavaScript: `x = 1`
+                "assignment_pattern" => Some(Param {
+                    name: text("left")?,
+                    ty: None,
+                    default: text("right"),
+                    optional: true,
+                }),
+                "comment" => None,
+                _ => Some(simple_param(&p, content)),
+            }
+        })
+        .collect()
+}
+
+/// Declared return type of a function, without the leading `:`.
+pub fn return_type(node: &Node, content: &str) -> Option<String> {
+    let ty = node.child_by_field_name("return_type")?;
+    Some(type_annotation(&content[ty.byte_range()]))
+}
+
+/// An untyped parameter; `...rest` is optional.
+fn simple_param(node: &Node, content: &str) -> Param {
+    Param {
+        name: content[node.byte_range()].to_string(),
+        ty: None,
+        default: None,
+        optional: node.kind() == "rest_pattern",
+    }
+}
+
+fn type_annotation(text: &str) -> String {
+    text.trim_start_matches(':').trim().to_string()
+}
+
 // ============================================================================
 // Import/Export extraction
 // ============================================================================
@@ -1,10 +1,16 @@
 //! Elixir language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
+/// Definition macros whose body becomes the symbol's children.
+const CONTAINER_MACROS: &[&str] = &["defmodule", "defprotocol", "defimpl"];
+/// Definition macros for functions, with their private counterparts.
+const FUNCTION_MACROS: &[&str] = &["def", "defp", "defmacro", "defmacrop"];
+const PRIVATE_MACROS: &[&str] = &["defp", "defmacrop"];
+
 /// Elixir language support.
 pub struct Elixir;
 
@@ -31,6 +37,10 @@ impl Language for Elixir {
         &["call"] // def, defp, defmacro, defmacrop
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &["call"] // defstruct, @type
     }
@@ -48,46 +58,23 @@ impl Language for Elixir {
     }
 
     fn extract_public_symbols(&self, node: &Node, content: &str) -> Vec<Export> {
-        if node.kind() != "call" {
+        if !self.is_public(node, content) {
             return Vec::new();
         }
-
-        let text = &content[node.byte_range()];
-
-        // Check for def (not defp)
-        if text.starts_with("def ") && !text.starts_with("defp") {
-            if let Some(name) = self.extract_def_name(node, content) {
-                return vec![Export {
-                    name,
-                    kind: SymbolKind::Function,
-                    line: node.start_position().row + 1,
-                }];
-            }
-        }
-
-        // Check for defmacro (not defmacrop)
-        if text.starts_with("defmacro ") && !text.starts_with("defmacrop") {
-            if let Some(name) = self.extract_def_name(node, content) {
-                return vec![Export {
-                    name,
-                    kind: SymbolKind::Function,
-                    line: node.start_position().row + 1,
-                }];
-            }
-        }
-
-        // Check for defmodule
-        if text.starts_with("defmodule ") {
-            if let Some(name) = self.extract_module_name(node, content) {
-                return vec![Export {
-                    name,
-                    kind: SymbolKind::Module,
-                    line: node.start_position().row + 1,
-                }];
-            }
-        }
-
-        Vec::new()
+        let Some(name) = self.node_name(node, content) else {
+            return Vec::new();
+        };
+        let kind = match Self::macro_name(node, content) {
+            Some("defmodule") => SymbolKind::Module,
+            Some("defprotocol") => SymbolKind::Interface,
+            Some("defimpl") => return Vec::new(),
+            _ => SymbolKind::Function,
+        };
+        vec![Export {
+            name: name.to_string(),
+            kind,
+            line: node.start_position().row + 1,
+        }]
     }
 
     fn scope_creating_kinds(&self) -> &'static [&'static str] {
@@ -106,58 +93,59 @@ impl Language for Elixir {
         &["call", "do_block", "anonymous_function"]
     }
 
-    fn extract_function(&self, node: &Node, content: &str, _in_container: bool) -> Option<Symbol> {
-        if node.kind() != "call" {
+    fn extract_function(&self, node: &Node, content: &str, in_container: bool) -> Option<Symbol> {
+        if !FUNCTION_MACROS.contains(&Self::macro_name(node, content)?) {
             return None;
         }
-
-        let text = &content[node.byte_range()];
-        let is_private = if text.starts_with("defp ") || text.starts_with("defmacrop ") {
-            true
-        } else if text.starts_with("def ") || text.starts_with("defmacro ") {
-            false
-        } else {
-            return None;
-        };
-
-        let name = self.extract_def_name(node, content)?;
-
-        // Extract first line as signature
-        let first_line = text.lines().next().unwrap_or(text);
-        let signature = first_line.trim_end_matches(" do").to_string();
+        let name = self.node_name(node, content)?;
 
         Some(Symbol {
-            name,
-            kind: SymbolKind::Function,
-            signature,
+            name: name.to_string(),
+            kind: if in_container {
+                SymbolKind::Method
+            } else {
+                SymbolKind::Function
+            },
+            signature: Self::head_signature(node, content),
             docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
-            visibility: if is_private {
-                Visibility::Private
-            } else {
-                Visibility::Public
-            },
+            visibility: self.get_visibility(node, content),
             children: Vec::new(),
         })
     }
 
     fn extract_container(&self, node: &Node, content: &str) -> Option<Symbol> {
-        if node.kind() != "call" {
-            return None;
-        }
-
-        let text = &content[node.byte_range()];
-        if !text.starts_with("defmodule ") {
-            return None;
-        }
-
-        let name = self.extract_module_name(node, content)?;
+        let (kind, name) = match Self::macro_name(node, content)? {
+            "defmodule" => (
+                SymbolKind::Module,
+                self.node_name(node, content)?.to_string(),
+            ),
+            "defprotocol" => (
+                SymbolKind::Interface,
+                self.node_name(node, content)?.to_string(),
+            ),
+            // defimpl Size, for: Foo defines the module Size.Foo
+            "defimpl" => {
+                let protocol = self.node_name(node, content)?;
+                let target = match Self::keyword_value(node, "for", content) {
+                    Some(target) => &content[target.byte_range()],
+                    None => self.enclosing_module(node, content)?,
+                };
+                (SymbolKind::Module, format!("{}.{}", protocol, target))
+            }
+            // A struct has no name of its own; it's written %Module{}
+            "defstruct" => (
+                SymbolKind::Struct,
+                format!("%{}{{}}", self.enclosing_module(node, content)?),
+            ),
+            _ => return None,
+        };
 
         Some(Symbol {
-            name: name.clone(),
-            kind: SymbolKind::Module,
-            signature: format!("defmodule {}", name),
+            name,
+            kind,
+            signature: Self::head_signature(node, content),
             docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
@@ -171,72 +159,100 @@ impl Language for Elixir {
     }
 
     fn extract_docstring(&self, node: &Node, content: &str) -> Option<String> {
-        // Look for @doc or @moduledoc before the node
-        let mut prev = node.prev_sibling();
-        while let Some(sibling) = prev {
-            let text = &content[sibling.byte_range()];
-            if text.contains("@doc") || text.contains("@moduledoc") {
-                // Extract the string content
-                if let Some(start) = text.find("\"\"\"") {
-                    let rest = &text[start + 3..];
-                    if let Some(end) = rest.find("\"\"\"") {
-                        return Some(rest[..end].trim().to_string());
-                    }
-                }
-                if let Some(start) = text.find('"') {
-                    let rest = &text[start + 1..];
-                    if let Some(end) = rest.find('"') {
-                        return Some(rest[..end].to_string());
-                    }
+        // Modules open with @moduledoc; functions follow their @doc, possibly
+        // with other attributes (@spec, @impl) in between
+        let doc = if CONTAINER_MACROS.contains(&Self::macro_name(node, content)?) {
+            let body = self.container_body(node)?;
+            let first = body.named_child(0)?;
+            Self::attribute(&first, "moduledoc", content)?
+        } else {
+            let mut prev = node.prev_named_sibling();
+            loop {
+                let sibling = prev.filter(|s| s.kind() == "unary_operator")?;
+                if let Some(doc) = Self::attribute(&sibling, "doc", content) {
+                    break doc;
                 }
+                prev = sibling.prev_named_sibling();
             }
-            prev = sibling.prev_sibling();
-        }
+        };
+
+        let text = &content[doc.byte_range()];
+        let text = text
+            .strip_prefix("\"\"\"")
+            .and_then(|t| t.strip_suffix("\"\"\""))
+            .or_else(|| text.strip_prefix('"').and_then(|t| t.strip_suffix('"')))?;
+        Some(text.trim().to_string())
+    }
+
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
         None
     }
 
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
-        if node.kind() != "call" {
+        let Some(keyword @ ("import" | "alias" | "require" | "use")) =
+            Self::macro_name(node, content)
+        else {
             return Vec::new();
-        }
-
-        let text = &content[node.byte_range()];
-        let line = node.start_position().row + 1;
-
-        // Handle import, alias, require, use
-        for keyword in &["import ", "alias ", "require ", "use "] {
-            if text.starts_with(keyword) {
-                let rest = text[keyword.len()..].trim();
-                let module = rest
-                    .split(|c: char| c.is_whitespace() || c == ',')
-                    .next()
-                    .unwrap_or(rest)
-                    .to_string();
-
-                if !module.is_empty() {
-                    return vec![Import {
-                        module,
-                        names: Vec::new(),
-                        alias: None,
-                        is_wildcard: false,
-                        is_relative: false,
-                        line,
-                    }];
-                }
-            }
-        }
+        };
+        let Some(args) = node
+            .children(&mut node.walk())
+            .find(|c| c.kind() == "arguments")
+        else {
+            return Vec::new();
+        };
+        let Some(target) = args.named_child(0) else {
+            return Vec::new();
+        };
+        let text = |n: Node| content[n.byte_range()].to_string();
+        let option = |key: &str| Self::keyword_value(node, key, content);
+
+        // alias MyApp.{Bar, Baz} names several modules under one prefix
+        let (module, names) = match (target.kind(), target.child_by_field_name("right")) {
+            ("dot", Some(tuple)) if tuple.kind() == "tuple" => (
+                target.child_by_field_name("left").map(text),
+                tuple.named_children(&mut tuple.walk()).map(text).collect(),
+            ),
+            _ => (Some(text(target)), Vec::new()),
+        };
+        let Some(module) = module else {
+            return Vec::new();
+        };
+        // import Enum, only: [map: 2] brings in just the listed functions
+        let only: Vec<String> = match option("only") {
+            Some(list) if keyword == "import" && list.kind() == "list" => list
+                .named_children(&mut list.walk())
+                .filter(|c| c.kind() == "keywords")
+                .flat_map(|kw| kw.named_children(&mut kw.walk()).collect::<Vec<_>>())
+                .filter_map(|pair| pair.child_by_field_name("key"))
+                .map(|key| {
+                    content[key.byte_range()]
+                        .trim_end()
+                        .trim_end_matches(':')
+                        .to_string()
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
 
-        Vec::new()
+        vec![Import {
+            module,
+            is_wildcard: keyword == "import" && only.is_empty(),
+            names: if only.is_empty() { names } else { only },
+            alias: option("as").map(text),
+            is_relative: false,
+            line: node.start_position().row + 1,
+        }]
     }
 
     fn is_public(&self, node: &Node, content: &str) -> bool {
-        if node.kind() != "call" {
-            return false;
-        }
-        let text = &content[node.byte_range()];
-        (text.starts_with("def ") && !text.starts_with("defp"))
-            || (text.starts_with("defmacro ") && !text.starts_with("defmacrop"))
-            || text.starts_with("defmodule ")
+        Self::macro_name(node, content).is_some_and(|name| {
+            (FUNCTION_MACROS.contains(&name) || CONTAINER_MACROS.contains(&name))
+                && !PRIVATE_MACROS.contains(&name)
+        })
     }
 
     fn get_visibility(&self, node: &Node, content: &str) -> Visibility {
@@ -266,8 +282,24 @@ impl Language for Elixir {
         false
     }
 
-    fn node_name<'a>(&self, _node: &Node, _content: &'a str) -> Option<&'a str> {
-        None
+    fn node_name<'a>(&self, node: &Node, content: &'a str) -> Option<&'a str> {
+        let name = match Self::macro_name(node, content)? {
+            name if FUNCTION_MACROS.contains(&name) => {
+                // def name, def name(args), def name(args) when guard
+                let mut head = Self::arguments(node)?.named_child(0)?;
+                if head.kind() == "binary_operator" {
+                    head = head.child_by_field_name("left")?;
+                }
+                match head.kind() {
+                    "call" => head.child_by_field_name("target")?,
+                    "identifier" => head,
+                    _ => return None,
+                }
+            }
+            name if CONTAINER_MACROS.contains(&name) => Self::arguments(node)?.named_child(0)?,
+            _ => return None,
+        };
+        Some(&content[name.byte_range()])
     }
 
     fn file_path_to_module_name(&self, path: &Path) -> Option<String> {
@@ -468,39 +500,82 @@ impl Language for Elixir {
 }
 
 impl Elixir {
-    fn extract_def_name(&self, node: &Node, content: &str) -> Option<String> {
-        // Look for the function name after def/defp
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            if child.kind() == "call" || child.kind() == "identifier" {
-                let text = &content[child.byte_range()];
-                // Extract just the name (before parentheses)
-                let name = text.split('(').next().unwrap_or(text).trim();
-                if !name.is_empty()
-                    && name != "def"
-                    && name != "defp"
-                    && name != "defmacro"
-                    && name != "defmacrop"
-                {
-                    return Some(name.to_string());
-                }
-            }
+    /// The macro a call invokes (`def`, `defmodule`, `alias`, ...).
+    fn macro_name<'a>(node: &Node, content: &'a str) -> Option<&'a str> {
+        if node.kind() != "call" {
+            return None;
         }
-        None
+        let target = node.child_by_field_name("target")?;
+        (target.kind() == "identifier").then(|| &content[target.byte_range()])
     }
 
-    fn extract_module_name(&self, node: &Node, content: &str) -> Option<String> {
-        // Look for the module name after defmodule
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            if child.kind() == "alias" || child.kind() == "atom" {
-                let text = &content[child.byte_range()];
-                if !text.is_empty() && text != "defmodule" {
-                    return Some(text.to_string());
-                }
-            }
+    fn arguments<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+        node.children(&mut node.walk())
+            .find(|c| c.kind() == "arguments")
+    }
+
+    /// The value of a trailing `key: value` argument.
+    fn keyword_value<'a>(node: &Node<'a>, key: &str, content: &str) -> Option<Node<'a>> {
+        let args = Self::arguments(node)?;
+        let keywords = args
+            .named_children(&mut args.walk())
+            .find(|c| c.kind() == "keywords")?;
+        let pair = keywords
+            .named_children(&mut keywords.walk())
+            .find(|pair| Self::pair_key(pair, content) == Some(key))?;
+        pair.child_by_field_name("value")
+    }
+
+    /// The name in a keyword pair's `key:` node.
+    fn pair_key<'a>(pair: &Node, content: &'a str) -> Option<&'a str> {
+        let key = pair.child_by_field_name("key")?;
+        content[key.byte_range()].trim_end().strip_suffix(':')
+    }
+
+    /// The name of the `defmodule` whose body holds `node`.
+    fn enclosing_module<'a>(&self, node: &Node, content: &'a str) -> Option<&'a str> {
+        let module = node.parent().filter(|p| p.kind() == "do_block")?.parent()?;
+        if Self::macro_name(&module, content)? != "defmodule" {
+            return None;
         }
-        None
+        self.node_name(&module, content)
+    }
+
+    /// The call up to its `do` block or `, do:` keyword, on one line.
+    fn head_signature(node: &Node, content: &str) -> String {
+        let end = Self::arguments(node)
+            .and_then(|args| {
+                let last = args.named_child(args.named_child_count().checked_sub(1)?)?;
+                let inline_do = last.kind() == "keywords"
+                    && last
+                        .named_child(0)
+                        .is_some_and(|pair| Self::pair_key(&pair, content) == Some("do"));
+                Some(if inline_do {
+                    args.named_child(args.named_child_count().checked_sub(2)?)?
+                        .end_byte()
+                } else {
+                    args.end_byte()
+                })
+            })
+            .unwrap_or(node.end_byte());
+        content[node.start_byte()..end]
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The value of a `@name value` attribute.
+    fn attribute<'a>(node: &Node<'a>, name: &str, content: &str) -> Option<Node<'a>> {
+        let is_attribute = node
+            .child_by_field_name("operator")
+            .is_some_and(|op| op.kind() == "@");
+        let call = node
+            .child_by_field_name("operand")
+            .filter(|_| is_attribute)?;
+        if Self::macro_name(&call, content)? != name {
+            return None;
+        }
+        Self::arguments(&call)?.named_child(0)
     }
 }
 
@@ -509,6 +584,64 @@ mod tests {
     use super::*;
     use crate::validate_unused_kinds_audit;
 
+    #[test]
+    fn test_elixir_function_doc_and_signature() {
+        let content = "defmodule Math do\n  @moduledoc \"Arithmetic\"\n\n  @doc \"Adds two numbers\"\n  @spec add(integer, integer) :: integer\n  def add(a, b), do: a + b\n\n  def sub(a, b) do\n    a - b\n  end\nend\n";
+        let tree = crate::test_support::parse("elixir", content);
+        let module = tree.root_node().named_child(0).unwrap();
+        assert_eq!(
+            Elixir.extract_docstring(&module, content).as_deref(),
+            Some("Arithmetic")
+        );
+
+        let body = Elixir.container_body(&module).unwrap();
+        let functions: Vec<Symbol> = body
+            .named_children(&mut body.walk())
+            .filter_map(|node| Elixir.extract_function(&node, content, true))
+            .collect();
+        assert_eq!(functions[0].signature, "def add(a, b)");
+        assert_eq!(functions[0].docstring.as_deref(), Some("Adds two numbers"));
+        assert_eq!(functions[1].signature, "def sub(a, b)");
+        assert_eq!(functions[1].docstring, None);
+    }
+
+    #[test]
+    fn test_elixir_imports() {
+        let content = "alias MyApp.{Bar, Baz}\nalias MyApp.Repo, as: R\nimport Enum, only: [map: 2, filter: 2]\nimport String\nuse GenServer\nIO.puts(1)\n";
+        let store = arborium::GrammarStore::new();
+        let mut parser = arborium::tree_sitter::Parser::new();
+        parser
+            .set_language(store.get("elixir").unwrap().language())
+            .unwrap();
+        let tree = parser.parse(content, None).unwrap();
+        let root = tree.root_node();
+        let imports: Vec<Import> = root
+            .named_children(&mut root.walk())
+            .flat_map(|node| Elixir.extract_imports(&node, content))
+            .collect();
+        let summary: Vec<(&str, Vec<&str>, Option<&str>, bool)> = imports
+            .iter()
+            .map(|i| {
+                (
+                    i.module.as_str(),
+                    i.names.iter().map(String::as_str).collect(),
+                    i.alias.as_deref(),
+                    i.is_wildcard,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("MyApp", vec!["Bar", "Baz"], None, false),
+                ("MyApp.Repo", vec![], Some("R"), false),
+                ("Enum", vec!["map", "filter"], None, false),
+                ("String", vec![], None, true),
+                ("GenServer", vec![], None, false),
+            ]
+        );
+    }
+
     #[test]
     fn unused_node_kinds_audit() {
         #[rustfmt::skip]
@@ -13,7 +13,7 @@
 //! - Global cache: ~/.cache/moss/ for indexed packages
 //! - PackageIndex: SQLite-backed package/symbol index
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // =============================================================================
 // Shared Types
@@ -168,6 +168,13 @@ pub struct SymbolRecord {
     pub line: u32,
 }
 
+/// Rows removed by [`PackageIndex::prune`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PruneCounts {
+    pub packages: usize,
+    pub symbols: usize,
+}
+
 /// Global package index backed by SQLite.
 pub struct PackageIndex {
     conn: Connection,
@@ -389,6 +396,21 @@ impl PackageIndex {
         Ok(count > 0)
     }
 
+    /// Whether this package is indexed from this path, whatever its versions.
+    pub fn is_path_indexed(
+        &self,
+        language: &str,
+        name: &str,
+        path: &str,
+    ) -> Result<bool, rusqlite::Error> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM packages WHERE language = ?1 AND name = ?2 AND path = ?3",
+            params![language, name, path],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
     pub fn delete_package(&self, package_id: i64) -> Result<(), rusqlite::Error> {
         self.conn.execute(
             "DELETE FROM symbols WHERE package_id = ?1",
@@ -399,6 +421,31 @@ impl PackageIndex {
         Ok(())
     }
 
+    /// Remove packages whose indexed path no longer exists on disk.
+    pub fn prune(&self) -> Result<PruneCounts, rusqlite::Error> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.id, p.path, (SELECT COUNT(*) FROM symbols s WHERE s.package_id = p.id)
+             FROM packages p",
+        )?;
+        let stale: Vec<(i64, i64)> = stmt
+            .query_map([], |row| {
+                let path: String = row.get(1)?;
+                Ok((row.get(0)?, path, row.get(2)?))
+            })?
+            .filter_map(|row| row.ok())
+            .filter(|(_, path, _)| !Path::new(path).exists())
+            .map(|(id, _, symbols)| (id, symbols))
+            .collect();
+
+        let mut counts = PruneCounts::default();
+        for (id, symbols) in stale {
+            self.delete_package(id)?;
+            counts.packages += 1;
+            counts.symbols += symbols as usize;
+        }
+        Ok(counts)
+    }
+
     pub fn clear(&self) -> Result<(), rusqlite::Error> {
         self.conn.execute("DELETE FROM symbols", [])?;
         self.conn.execute("DELETE FROM packages", [])?;
@@ -481,4 +528,39 @@ mod tests {
         assert!(index.is_indexed("python", "requests").unwrap());
         assert!(!index.is_indexed("python", "nonexistent").unwrap());
     }
+
+    #[test]
+    fn test_prune_removes_missing_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = PackageIndex::open_in_memory().unwrap();
+        let v = Version { major: 3, minor: 8 };
+
+        let kept = index
+            .insert_package("python", "kept", &dir.path().to_string_lossy(), v, None)
+            .unwrap();
+        index
+            .insert_symbol(kept, "a", "function", "def a()", 1)
+            .unwrap();
+        let gone = index
+            .insert_package("python", "gone", "/nonexistent/moss/gone", v, None)
+            .unwrap();
+        index
+            .insert_symbol(gone, "b", "function", "def b()", 1)
+            .unwrap();
+        index
+            .insert_symbol(gone, "c", "function", "def c()", 2)
+            .unwrap();
+
+        let counts = index.prune().unwrap();
+        assert_eq!(
+            counts,
+            PruneCounts {
+                packages: 1,
+                symbols: 2
+            }
+        );
+        assert!(index.is_indexed("python", "kept").unwrap());
+        assert!(!index.is_indexed("python", "gone").unwrap());
+        assert!(index.get_symbols(gone).unwrap().is_empty());
+    }
 }
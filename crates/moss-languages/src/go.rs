@@ -1,8 +1,9 @@
 //! Go language support.
 
-use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::external_packages::{version_cmp, ResolvedPackage};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -206,6 +207,143 @@ pub fn find_go_mod_cache() -> Option<PathBuf> {
     None
 }
 
+/// Undo the module cache's case escaping: `!a` stands for `A`.
+fn unescape_go_module_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        if c == '!' {
+            if let Some(next) = chars.next() {
+                out.extend(next.to_uppercase());
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Whether `dir` directly contains non-test .go files.
+fn has_go_files(dir: &Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries.flatten().any(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                !Go.should_skip_package_entry(&name, false) && e.path().is_file()
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Collect package directories under `dir` as `(import path, dir)`.
+/// Nested modules (directories with their own go.mod) are left to their own entry.
+fn discover_go_package_dirs(dir: &Path, import_path: &str, packages: &mut Vec<(String, PathBuf)>) {
+    if !import_path.is_empty() && has_go_files(dir) {
+        packages.push((import_path.to_string(), dir.to_path_buf()));
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_dir()
+            || Go.should_skip_package_entry(&name, true)
+            || path.join("go.mod").is_file()
+        {
+            continue;
+        }
+        let child = if import_path.is_empty() {
+            name
+        } else {
+            format!("{}/{}", import_path, name)
+        };
+        discover_go_package_dirs(&path, &child, packages);
+    }
+}
+
+/// Packages in GOROOT/src, named by import path (`net/http`).
+fn discover_go_stdlib_packages(stdlib: &Path) -> Vec<(String, PathBuf)> {
+    let mut packages = Vec::new();
+    discover_go_package_dirs(stdlib, "", &mut packages);
+    // GOROOT/src/cmd holds the toolchain, not importable packages
+    packages.retain(|(name, _)| name != "cmd" && !name.starts_with("cmd/"));
+    packages
+}
+
+/// A `module@version` directory in the module cache.
+struct GoModuleDir {
+    version: String,
+    path: PathBuf,
+}
+
+/// Find `module@version` directories, keeping the newest version of each module.
+fn find_go_module_dirs(dir: &Path, rel: &str, modules: &mut HashMap<String, GoModuleDir>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        // cache/ holds downloaded zips and metadata, not sources
+        if !path.is_dir() || name.starts_with('.') || (rel.is_empty() && name == "cache") {
+            continue;
+        }
+        let joined = |segment: &str| {
+            if rel.is_empty() {
+                segment.to_string()
+            } else {
+                format!("{}/{}", rel, segment)
+            }
+        };
+        match name.split_once('@') {
+            Some((module, version)) => {
+                let module = unescape_go_module_path(&joined(module));
+                let newer = modules.get(&module).is_none_or(|current| {
+                    version_cmp(
+                        version.trim_start_matches('v'),
+                        current.version.trim_start_matches('v'),
+                    ) == std::cmp::Ordering::Greater
+                });
+                if newer {
+                    modules.insert(
+                        module,
+                        GoModuleDir {
+                            version: version.to_string(),
+                            path,
+                        },
+                    );
+                }
+            }
+            None => find_go_module_dirs(&path, &joined(&name), modules),
+        }
+    }
+}
+
+/// Packages in the module cache, named by import path, from the newest
+/// cached version of each module.
+fn discover_go_mod_cache_packages(mod_cache: &Path) -> Vec<(String, PathBuf)> {
+    let mut modules = HashMap::new();
+    find_go_module_dirs(mod_cache, "", &mut modules);
+    let mut modules: Vec<_> = modules.into_iter().collect();
+    modules.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut packages = Vec::new();
+    for (module, dir) in modules {
+        if has_go_files(&dir.path) {
+            packages.push((module.clone(), dir.path.clone()));
+        }
+        let mut nested = Vec::new();
+        discover_go_package_dirs(&dir.path, "", &mut nested);
+        packages.extend(
+            nested
+                .into_iter()
+                .map(|(rel, path)| (format!("{}/{}", module, rel), path)),
+        );
+    }
+    packages
+}
+
 /// Resolve a Go import from mod cache to its source location.
 ///
 /// Import paths like "github.com/user/repo/pkg" are mapped to
@@ -301,6 +439,10 @@ impl Language for Go {
         &["function_declaration", "method_declaration"]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["func_literal"]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &["type_spec"] // The actual type is in type_spec, not type_declaration
     }
@@ -529,6 +671,51 @@ impl Language for Go {
         None
     }
 
+    fn parameters(&self, node: &Node, content: &str) -> Vec<Param> {
+        let Some(params) = node.child_by_field_name("parameters") else {
+            return Vec::new();
+        };
+        let text = |n: Node| content[n.byte_range()].to_string();
+        let mut params_out = Vec::new();
+        let mut cursor = params.walk();
+        for decl in params.named_children(&mut cursor) {
+            let variadic = decl.kind() == "variadic_parameter_declaration";
+            if !variadic && decl.kind() != "parameter_declaration" {
+                continue;
+            }
+            let ty = decl.child_by_field_name("type").map(|t| {
+                if variadic {
+                    format!("...{}", text(t))
+                } else {
+                    text(t)
+                }
+            });
+            // `a, b int` declares two parameters; `func(int)` declares an unnamed one
+            let mut names_cursor = decl.walk();
+            let mut names: Vec<String> = decl
+                .children_by_field_name("name", &mut names_cursor)
+                .map(text)
+                .collect();
+            if names.is_empty() {
+                names.push(String::new());
+            }
+            for name in names {
+                params_out.push(Param {
+                    name,
+                    ty: ty.clone(),
+                    default: None,
+                    optional: variadic,
+                });
+            }
+        }
+        params_out
+    }
+
+    fn return_type(&self, node: &Node, content: &str) -> Option<String> {
+        let result = node.child_by_field_name("result")?;
+        Some(content[result.byte_range()].to_string())
+    }
+
     fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
         node.child_by_field_name("body")
     }
@@ -639,7 +826,7 @@ impl Language for Go {
             sources.push(PackageSource {
                 name: "mod-cache",
                 path: cache,
-                kind: PackageSourceKind::Recursive,
+                kind: PackageSourceKind::GoModCache,
                 version_specific: false,
             });
         }
@@ -674,7 +861,10 @@ impl Language for Go {
     }
 
     fn discover_packages(&self, source: &crate::PackageSource) -> Vec<(String, PathBuf)> {
-        self.discover_recursive_packages(&source.path, &source.path)
+        match source.kind {
+            crate::PackageSourceKind::GoModCache => discover_go_mod_cache_packages(&source.path),
+            _ => discover_go_stdlib_packages(&source.path),
+        }
     }
 
     fn find_package_entry(&self, path: &Path) -> Option<PathBuf> {
@@ -736,6 +926,87 @@ impl Go {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{param, parse};
+
+    #[test]
+    fn test_discover_go_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |rel: &str| {
+            let path = dir.path().join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "package p\n").unwrap();
+        };
+
+        // Module cache: escaped paths, two versions, nested package, test-only dir
+        write("mod/github.com/!burnt!sushi/toml@v1.2.0/decode.go");
+        write("mod/github.com/!burnt!sushi/toml@v1.10.0/decode.go");
+        write("mod/github.com/!burnt!sushi/toml@v1.10.0/internal/tz.go");
+        write("mod/github.com/!burnt!sushi/toml@v1.10.0/sub/sub.go");
+        write("mod/github.com/!burnt!sushi/toml@v1.10.0/only/x_test.go");
+        write("mod/cache/download/github.com/x.go");
+        let source = crate::PackageSource {
+            name: "mod-cache",
+            path: dir.path().join("mod"),
+            kind: crate::PackageSourceKind::GoModCache,
+            version_specific: false,
+        };
+        let packages = Go.discover_packages(&source);
+        let root = dir.path().join("mod/github.com/!burnt!sushi/toml@v1.10.0");
+        assert_eq!(
+            packages,
+            vec![
+                ("github.com/BurntSushi/toml".to_string(), root.clone()),
+                (
+                    "github.com/BurntSushi/toml/sub".to_string(),
+                    root.join("sub")
+                ),
+            ]
+        );
+
+        // Stdlib: packages by import path, toolchain sources skipped
+        write("goroot/src/fmt/print.go");
+        write("goroot/src/net/http/server.go");
+        write("goroot/src/cmd/go/main.go");
+        let source = crate::PackageSource {
+            name: "stdlib",
+            path: dir.path().join("goroot/src"),
+            kind: crate::PackageSourceKind::Recursive,
+            version_specific: true,
+        };
+        let mut names: Vec<_> = Go
+            .discover_packages(&source)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["fmt", "net/http"]);
+    }
+
+    #[test]
+    fn test_go_parameters() {
+        let content = "package p\n\nfunc F(a, b int, s string, rest ...any) (int, error) {}\n";
+        let tree = parse("go", content);
+        let mut cursor = tree.root_node().walk();
+        let func = tree
+            .root_node()
+            .children(&mut cursor)
+            .find(|n| n.kind() == "function_declaration")
+            .unwrap();
+        let params = Go.parameters(&func, content);
+        assert_eq!(
+            params,
+            vec![
+                param("a", Some("int"), None, false),
+                param("b", Some("int"), None, false),
+                param("s", Some("string"), None, false),
+                param("rest", Some("...any"), None, true),
+            ]
+        );
+        assert_eq!(
+            Go.return_type(&func, content),
+            Some("(int, error)".to_string())
+        );
+    }
 
     #[test]
     fn test_parse_go_mod() {
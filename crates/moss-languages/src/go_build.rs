@@ -0,0 +1,320 @@
+//! Go build constraints: which files `go build` selects for a target platform.
+//!
+//! Kept outside the `lang-go` feature since it only reads file names and
+//! comments, no parser needed.
+
+/// Operating systems Go recognizes in `_GOOS` file suffixes and build tags.
+const KNOWN_GOOS: &[&str] = &[
+    "aix",
+    "android",
+    "darwin",
+    "dragonfly",
+    "freebsd",
+    "hurd",
+    "illumos",
+    "ios",
+    "js",
+    "linux",
+    "nacl",
+    "netbsd",
+    "openbsd",
+    "plan9",
+    "solaris",
+    "wasip1",
+    "windows",
+    "zos",
+];
+
+/// Architectures Go recognizes in `_GOARCH` file suffixes and build tags.
+const KNOWN_GOARCH: &[&str] = &[
+    "386", "amd64", "arm", "arm64", "loong64", "mips", "mips64", "mips64le", "mipsle", "ppc64",
+    "ppc64le", "riscv64", "s390x", "wasm",
+];
+
+/// Operating systems that satisfy the `unix` build tag.
+const UNIX_GOOS: &[&str] = &[
+    "aix",
+    "android",
+    "darwin",
+    "dragonfly",
+    "freebsd",
+    "hurd",
+    "illumos",
+    "ios",
+    "linux",
+    "netbsd",
+    "openbsd",
+    "solaris",
+];
+
+/// The platform Go files are selected for, as GOOS/GOARCH.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoTarget {
+    pub goos: String,
+    pub goarch: String,
+}
+
+impl GoTarget {
+    /// Target from the GOOS/GOARCH env vars, falling back to the running platform.
+    pub fn host() -> Self {
+        let goos = std::env::var("GOOS").unwrap_or_else(|_| {
+            match std::env::consts::OS {
+                "macos" => "darwin",
+                other => other,
+            }
+            .to_string()
+        });
+        let goarch = std::env::var("GOARCH").unwrap_or_else(|_| {
+            match std::env::consts::ARCH {
+                "x86_64" => "amd64",
+                "aarch64" => "arm64",
+                "x86" => "386",
+                "powerpc64" => "ppc64",
+                other => other,
+            }
+            .to_string()
+        });
+        Self { goos, goarch }
+    }
+
+    /// Parse "linux/amd64", or "linux" to keep the host architecture.
+    pub fn parse(s: &str) -> Option<Self> {
+        let (goos, goarch) = match s.split_once('/') {
+            Some((os, arch)) => (os.to_string(), arch.to_string()),
+            None => (s.to_string(), Self::host().goarch),
+        };
+        if !KNOWN_GOOS.contains(&goos.as_str()) || !KNOWN_GOARCH.contains(&goarch.as_str()) {
+            return None;
+        }
+        Some(Self { goos, goarch })
+    }
+
+    /// Whether a build tag is satisfied for this target.
+    fn has_tag(&self, tag: &str) -> bool {
+        tag == self.goos
+            || tag == self.goarch
+            || (tag == "unix" && UNIX_GOOS.contains(&self.goos.as_str()))
+            // android and illumos/ios builds also match linux/solaris/darwin
+            || (tag == "linux" && self.goos == "android")
+            || (tag == "solaris" && self.goos == "illumos")
+            || (tag == "darwin" && self.goos == "ios")
+            || tag == "gc"
+            || tag == "cgo"
+            || tag.starts_with("go1.")
+    }
+}
+
+impl std::fmt::Display for GoTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.goos, self.goarch)
+    }
+}
+
+/// Why `go build` would leave a file out for `target`, or None if it's built.
+///
+/// Checks `_GOOS`/`_GOARCH` file name suffixes, then `//go:build` lines
+/// (or legacy `// +build` lines when there's no `//go:build`). Custom tags
+/// are treated as unset, so files behind them are excluded.
+pub fn go_build_exclusion(file_name: &str, content: &str, target: &GoTarget) -> Option<String> {
+    if let Some(suffix) = file_name_constraint(file_name) {
+        let matches = suffix.iter().all(|part| target.has_tag(part));
+        if !matches {
+            return Some(format!("file name suffix _{}", suffix.join("_")));
+        }
+    }
+
+    let header = constraint_header(content);
+    let go_build: Vec<&str> = header
+        .iter()
+        .filter_map(|l| l.strip_prefix("//go:build"))
+        .filter(|rest| rest.starts_with(char::is_whitespace))
+        .map(str::trim)
+        .collect();
+    if let Some(expr) = go_build.first() {
+        return (!eval_build_expr(expr, target)).then(|| format!("//go:build {}", expr));
+    }
+
+    // Legacy form: lines are ANDed, space-separated terms ORed, commas ANDed
+    for line in header.iter().filter_map(|l| {
+        l.strip_prefix("//")
+            .map(str::trim_start)
+            .and_then(|l| l.strip_prefix("+build"))
+            .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    }) {
+        let satisfied = line.split_whitespace().any(|term| {
+            term.split(',').all(|t| match t.strip_prefix('!') {
+                Some(tag) => !target.has_tag(tag),
+                None => target.has_tag(t),
+            })
+        });
+        if !satisfied {
+            return Some(format!("// +build {}", line.trim()));
+        }
+    }
+    None
+}
+
+/// The `_GOOS`, `_GOARCH` or `_GOOS_GOARCH` suffix of a Go file name, if any.
+fn file_name_constraint(file_name: &str) -> Option<Vec<&str>> {
+    let stem = file_name.strip_suffix(".go")?;
+    let stem = stem.strip_suffix("_test").unwrap_or(stem);
+    let parts: Vec<&str> = stem.split('_').collect();
+    // A bare `linux.go` has no constraint; it needs a prefix
+    let n = parts.len();
+    if n >= 3 && KNOWN_GOOS.contains(&parts[n - 2]) && KNOWN_GOARCH.contains(&parts[n - 1]) {
+        return Some(parts[n - 2..].to_vec());
+    }
+    if n >= 2 && (KNOWN_GOOS.contains(&parts[n - 1]) || KNOWN_GOARCH.contains(&parts[n - 1])) {
+        return Some(vec![parts[n - 1]]);
+    }
+    None
+}
+
+/// Comment lines before the package clause, where build constraints live.
+fn constraint_header(content: &str) -> Vec<&str> {
+    let mut lines = Vec::new();
+    let mut in_block = false;
+    for line in content.lines().map(str::trim) {
+        if in_block {
+            in_block = !line.contains("*/");
+            continue;
+        }
+        if line.starts_with("/*") {
+            in_block = !line.contains("*/");
+            continue;
+        }
+        if line.is_empty() || line.starts_with("//") {
+            lines.push(line);
+            continue;
+        }
+        break;
+    }
+    lines
+}
+
+/// Evaluate a `//go:build` expression (`!`, `&&`, `||`, parentheses).
+/// Malformed expressions evaluate to true so the file stays indexed.
+fn eval_build_expr(expr: &str, target: &GoTarget) -> bool {
+    let mut tokens = Vec::new();
+    let mut chars = expr.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            ' ' | '\t' => {
+                chars.next();
+            }
+            '(' | ')' | '!' => {
+                tokens.push(c.to_string());
+                chars.next();
+            }
+            '&' | '|' => {
+                chars.next();
+                if chars.next() != Some(c) {
+                    return true;
+                }
+                tokens.push(format!("{}{}", c, c));
+            }
+            _ => {
+                let mut tag = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '.' {
+                        tag.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if tag.is_empty() {
+                    return true;
+                }
+                tokens.push(tag);
+            }
+        }
+    }
+
+    let mut parser = BuildExprParser {
+        tokens: &tokens,
+        pos: 0,
+        target,
+    };
+    match parser.or() {
+        Some(value) if parser.pos == tokens.len() => value,
+        _ => true,
+    }
+}
+
+/// Recursive descent over `//go:build` tokens; None on a syntax error.
+struct BuildExprParser<'a> {
+    tokens: &'a [String],
+    pos: usize,
+    target: &'a GoTarget,
+}
+
+impl BuildExprParser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(String::as_str)
+    }
+
+    fn or(&mut self) -> Option<bool> {
+        let mut value = self.and()?;
+        while self.peek() == Some("||") {
+            self.pos += 1;
+            value |= self.and()?;
+        }
+        Some(value)
+    }
+
+    fn and(&mut self) -> Option<bool> {
+        let mut value = self.not()?;
+        while self.peek() == Some("&&") {
+            self.pos += 1;
+            value &= self.not()?;
+        }
+        Some(value)
+    }
+
+    fn not(&mut self) -> Option<bool> {
+        let token = self.peek()?.to_string();
+        self.pos += 1;
+        match token.as_str() {
+            "!" => self.not().map(|v| !v),
+            "(" => {
+                let value = self.or()?;
+                if self.peek() != Some(")") {
+                    return None;
+                }
+                self.pos += 1;
+                Some(value)
+            }
+            ")" | "&&" | "||" => None,
+            tag => Some(self.target.has_tag(tag)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_go_build_exclusion() {
+        let linux = GoTarget::parse("linux/amd64").unwrap();
+        let excluded = |name: &str, content: &str| go_build_exclusion(name, content, &linux);
+
+        assert!(excluded("file_linux.go", "package x").is_none());
+        assert!(excluded("file_linux_amd64_test.go", "package x").is_none());
+        assert!(excluded("linux.go", "package x").is_none());
+        assert_eq!(
+            excluded("file_windows.go", "package x").as_deref(),
+            Some("file name suffix _windows")
+        );
+        assert!(excluded("file_linux_arm64.go", "package x").is_some());
+
+        assert!(excluded("a.go", "//go:build linux && !cgo\n\npackage x").is_some());
+        assert!(excluded("a.go", "//go:build (darwin || unix) && amd64\n\npackage x").is_none());
+        assert!(excluded("a.go", "// Copyright\n\n//go:build windows\n\npackage x").is_some());
+        assert!(excluded("a.go", "//go:build integration\n\npackage x").is_some());
+        assert!(excluded("a.go", "package x\n\n//go:build windows\n").is_none());
+        assert!(excluded("a.go", "// +build linux,386 darwin\n\npackage x").is_some());
+        assert!(excluded("a.go", "// +build linux darwin\n\npackage x").is_none());
+    }
+}
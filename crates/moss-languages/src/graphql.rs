@@ -1,7 +1,7 @@
 //! GraphQL language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -37,6 +37,10 @@ impl Language for GraphQL {
         &["field_definition", "operation_definition"]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[
             "object_type_definition",
@@ -49,13 +53,18 @@ impl Language for GraphQL {
     }
 
     fn import_kinds(&self) -> &'static [&'static str] {
-        &[]
+        // `# import` directives (graphql-import, graphql-tag/loader)
+        &["comment"]
     }
 
     fn public_symbol_kinds(&self) -> &'static [&'static str] {
         &[
             "object_type_definition",
             "interface_type_definition",
+            "enum_type_definition",
+            "union_type_definition",
+            "input_object_type_definition",
+            "scalar_type_definition",
             "operation_definition",
         ]
     }
@@ -107,11 +116,16 @@ impl Language for GraphQL {
 
         let text = &content[node.byte_range()];
         let first_line = text.lines().next().unwrap_or(text);
+        let kind = if node.kind() == "operation_definition" {
+            SymbolKind::Function
+        } else {
+            SymbolKind::Method
+        };
 
         Some(Symbol {
             name: name.to_string(),
-            kind: SymbolKind::Method,
-            signature: first_line.trim().to_string(),
+            kind,
+            signature: first_line.trim().trim_end_matches('{').trim().to_string(),
             docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
@@ -139,7 +153,7 @@ impl Language for GraphQL {
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: Visibility::Public,
-            children: Vec::new(),
+            children: self.extract_fields(node, content),
         })
     }
 
@@ -148,28 +162,66 @@ impl Language for GraphQL {
     }
 
     fn extract_docstring(&self, node: &Node, content: &str) -> Option<String> {
-        // GraphQL uses """ for descriptions
-        let mut prev = node.prev_sibling();
-        while let Some(sibling) = prev {
-            let text = &content[sibling.byte_range()];
-            if sibling.kind() == "description" || text.starts_with("\"\"\"") {
-                let inner = text
-                    .trim_start_matches("\"\"\"")
-                    .trim_end_matches("\"\"\"")
-                    .trim();
-                if !inner.is_empty() {
-                    return Some(inner.to_string());
-                }
-            }
-            prev = sibling.prev_sibling();
+        // Descriptions are "string" or """block""" literals leading the definition
+        let mut cursor = node.walk();
+        let description = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "description")?;
+        let text = &content[description.byte_range()];
+        let inner = text
+            .trim_start_matches("\"\"\"")
+            .trim_end_matches("\"\"\"")
+            .trim_matches('"')
+            .trim();
+        if inner.is_empty() {
+            None
+        } else {
+            Some(inner.to_string())
         }
-        None
     }
 
-    fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
         Vec::new()
     }
 
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
+    fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
+        // `# import A, B from "file.graphql"`, `# import * from "file.graphql"`,
+        // or `#import "./fragment.graphql"`
+        let text = &content[node.byte_range()];
+        let Some(rest) = text.trim_start_matches('#').trim().strip_prefix("import") else {
+            return Vec::new();
+        };
+        let (names, path) = match rest.rsplit_once(" from ") {
+            Some((names, path)) => (names.trim(), path),
+            None => ("", rest),
+        };
+        let module = path
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'')
+            .to_string();
+        if module.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Import {
+            is_relative: module.starts_with('.'),
+            module,
+            names: names
+                .split(',')
+                .map(|n| n.trim())
+                .filter(|n| !n.is_empty() && *n != "*")
+                .map(String::from)
+                .collect(),
+            alias: None,
+            is_wildcard: names == "*",
+            line: node.start_position().row + 1,
+        }]
+    }
+
     fn is_public(&self, _node: &Node, _content: &str) -> bool {
         true
     }
@@ -181,8 +233,9 @@ impl Language for GraphQL {
         None
     }
 
-    fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
-        node.child_by_field_name("fields_definition")
+    fn container_body<'a>(&self, _node: &'a Node<'a>) -> Option<Node<'a>> {
+        // Fields are extracted with their container (see `extract_fields`)
+        None
     }
 
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
@@ -190,8 +243,9 @@ impl Language for GraphQL {
     }
 
     fn node_name<'a>(&self, node: &Node, content: &'a str) -> Option<&'a str> {
-        node.child_by_field_name("name")
-            .map(|n| &content[n.byte_range()])
+        let mut cursor = node.walk();
+        let name = node.children(&mut cursor).find(|c| c.kind() == "name")?;
+        Some(&content[name.byte_range()])
     }
 
     fn file_path_to_module_name(&self, path: &Path) -> Option<String> {
@@ -219,11 +273,17 @@ impl Language for GraphQL {
     }
     fn resolve_local_import(
         &self,
-        _import: &str,
-        _current_file: &Path,
+        import: &str,
+        current_file: &Path,
         _project_root: &Path,
     ) -> Option<PathBuf> {
-        None
+        // Import paths are relative to the importing file, with or without ./
+        let full = current_file.parent()?.join(import);
+        if full.is_file() {
+            Some(full)
+        } else {
+            None
+        }
     }
     fn resolve_external_import(
         &self,
@@ -274,6 +334,58 @@ impl Language for GraphQL {
     }
 }
 
+impl GraphQL {
+    /// Fields, input fields, or enum values of a type definition, as children.
+    fn extract_fields(&self, node: &Node, content: &str) -> Vec<Symbol> {
+        let mut cursor = node.walk();
+        let Some(body) = node.children(&mut cursor).find(|c| {
+            matches!(
+                c.kind(),
+                "fields_definition" | "input_fields_definition" | "enum_values_definition"
+            )
+        }) else {
+            return Vec::new();
+        };
+
+        let mut cursor = body.walk();
+        body.children(&mut cursor)
+            .filter_map(|field| {
+                let (kind, name) = match field.kind() {
+                    "field_definition" => (SymbolKind::Method, self.node_name(&field, content)?),
+                    "input_value_definition" => {
+                        (SymbolKind::Variable, self.node_name(&field, content)?)
+                    }
+                    "enum_value_definition" => {
+                        let mut cursor = field.walk();
+                        let value = field
+                            .children(&mut cursor)
+                            .find(|c| c.kind() == "enum_value")?;
+                        (SymbolKind::Constant, &content[value.byte_range()])
+                    }
+                    _ => return None,
+                };
+                // The definition without its description
+                let mut cursor = field.walk();
+                let start = field
+                    .children(&mut cursor)
+                    .find(|c| c.kind() != "description")?;
+                let signature = content[start.start_byte()..field.end_byte()].trim();
+
+                Some(Symbol {
+                    name: name.to_string(),
+                    kind,
+                    signature: signature.to_string(),
+                    docstring: self.extract_docstring(&field, content),
+                    start_line: start.start_position().row + 1,
+                    end_line: field.end_position().row + 1,
+                    visibility: Visibility::Public,
+                    children: Vec::new(),
+                })
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
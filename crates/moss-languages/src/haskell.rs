@@ -1,10 +1,15 @@
 //! Haskell language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+/// Declarations that make up one top-level binding: its type signature and
+/// each of its equations.
+const BINDING_KINDS: &[&str] = &["signature", "function", "bind"];
+
 /// Haskell language support.
 pub struct Haskell;
 
@@ -28,7 +33,11 @@ impl Language for Haskell {
     }
 
     fn function_kinds(&self) -> &'static [&'static str] {
-        &["function", "signature"]
+        &["function", "bind", "signature"]
+    }
+
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
     }
 
     fn type_kinds(&self) -> &'static [&'static str] {
@@ -40,7 +49,15 @@ impl Language for Haskell {
     }
 
     fn public_symbol_kinds(&self) -> &'static [&'static str] {
-        &["function", "data_type", "newtype", "class"]
+        &[
+            "function",
+            "bind",
+            "signature",
+            "data_type",
+            "newtype",
+            "type_synomym",
+            "class",
+        ]
     }
 
     fn visibility_mechanism(&self) -> VisibilityMechanism {
@@ -52,9 +69,12 @@ impl Language for Haskell {
             Some(n) => n.to_string(),
             None => return Vec::new(),
         };
+        if Self::continues_binding(node, content) || !self.is_public(node, content) {
+            return Vec::new();
+        }
 
         let kind = match node.kind() {
-            "function" | "signature" => SymbolKind::Function,
+            "function" | "bind" | "signature" => SymbolKind::Function,
             "data_type" | "newtype" => SymbolKind::Struct,
             "type_synomym" => SymbolKind::Type,
             "class" => SymbolKind::Interface,
@@ -84,19 +104,39 @@ impl Language for Haskell {
         &["function", "let", "where", "do", "case"]
     }
 
-    fn extract_function(&self, node: &Node, content: &str, _in_container: bool) -> Option<Symbol> {
+    fn extract_function(&self, node: &Node, content: &str, in_container: bool) -> Option<Symbol> {
         let name = self.node_name(node, content)?;
+        // One symbol per binding, from its first declaration to its last equation
+        if Self::continues_binding(node, content) {
+            return None;
+        }
+        let mut last = *node;
+        while let Some(next) = Self::next_declaration(&last) {
+            if !Self::same_binding(&next, &last, content) {
+                break;
+            }
+            last = next;
+        }
+
         let text = &content[node.byte_range()];
-        let first_line = text.lines().next().unwrap_or(text);
+        let signature = if node.kind() == "signature" {
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        } else {
+            text.lines().next().unwrap_or(text).trim().to_string()
+        };
 
         Some(Symbol {
             name: name.to_string(),
-            kind: SymbolKind::Function,
-            signature: first_line.trim().to_string(),
+            kind: if in_container {
+                SymbolKind::Method
+            } else {
+                SymbolKind::Function
+            },
+            signature,
             docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
-            end_line: node.end_position().row + 1,
-            visibility: Visibility::Public,
+            end_line: last.end_position().row + 1,
+            visibility: self.get_visibility(node, content),
             children: Vec::new(),
         })
     }
@@ -120,7 +160,7 @@ impl Language for Haskell {
             docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
-            visibility: Visibility::Public,
+            visibility: self.get_visibility(node, content),
             children: Vec::new(),
         })
     }
@@ -136,7 +176,7 @@ impl Language for Haskell {
 
         while let Some(sibling) = prev {
             let text = &content[sibling.byte_range()];
-            if sibling.kind() == "comment" {
+            if sibling.kind() == "comment" || sibling.kind() == "haddock" {
                 if text.starts_with("-- |") || text.starts_with("-- ^") {
                     let line = text
                         .strip_prefix("-- |")
@@ -162,41 +202,86 @@ impl Language for Haskell {
         Some(doc_lines.join(" "))
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "import" {
             return Vec::new();
         }
 
-        let text = &content[node.byte_range()];
-        let line = node.start_position().row + 1;
-
-        // Extract module name after "import" keyword
-        // import qualified Data.Map as M
-        let parts: Vec<&str> = text.split_whitespace().collect();
-        let mut idx = 1;
-        if parts.get(idx) == Some(&"qualified") {
-            idx += 1;
-        }
-
-        if let Some(module) = parts.get(idx) {
-            return vec![Import {
-                module: module.to_string(),
-                names: Vec::new(),
-                alias: None,
-                is_wildcard: !text.contains('('),
-                is_relative: false,
-                line,
-            }];
-        }
+        // import qualified Data.Map as M / import Data.List (sortBy, nub)
+        let Some(module) = node.child_by_field_name("module") else {
+            return Vec::new();
+        };
+        let alias = node
+            .child_by_field_name("alias")
+            .map(|a| content[a.byte_range()].to_string());
+        // `hiding (...)` lists what isn't imported
+        let hiding = node
+            .children(&mut node.walk())
+            .any(|c| c.kind() == "hiding");
+        let names: Vec<String> = match node.child_by_field_name("names") {
+            Some(list) if !hiding => list
+                .named_children(&mut list.walk())
+                .filter(|c| c.kind() == "import_name")
+                .map(|c| content[c.byte_range()].to_string())
+                .collect(),
+            _ => Vec::new(),
+        };
 
-        Vec::new()
+        vec![Import {
+            module: content[module.byte_range()].to_string(),
+            is_wildcard: names.is_empty(),
+            names,
+            alias,
+            is_relative: false,
+            line: node.start_position().row + 1,
+        }]
     }
 
-    fn is_public(&self, _node: &Node, _content: &str) -> bool {
-        true
-    }
-    fn get_visibility(&self, _node: &Node, _content: &str) -> Visibility {
-        Visibility::Public
+    fn is_public(&self, node: &Node, content: &str) -> bool {
+        // Instances can't be hidden, and neither can their methods
+        let mut ancestor = node.parent();
+        while let Some(parent) = ancestor {
+            if parent.kind() == "instance" {
+                return true;
+            }
+            ancestor = parent.parent();
+        }
+        if node.kind() == "instance" {
+            return true;
+        }
+
+        let Some(exports) = Self::exported_names(node, content) else {
+            // No export list: the module exports everything at top level
+            return true;
+        };
+        let Some(name) = self.node_name(node, content) else {
+            return false;
+        };
+        if exports.contains(name) {
+            return true;
+        }
+        // Class methods exported as `Class(..)`
+        let class = node
+            .parent()
+            .filter(|p| p.kind() == "class_declarations")
+            .and_then(|decls| decls.parent())
+            .and_then(|class| self.node_name(&class, content));
+        class.is_some_and(|class| exports.contains(format!("{}(..)", class).as_str()))
+    }
+    fn get_visibility(&self, node: &Node, content: &str) -> Visibility {
+        if self.is_public(node, content) {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        }
     }
 
     fn embedded_content(&self, _node: &Node, _content: &str) -> Option<crate::EmbeddedBlock> {
@@ -204,7 +289,7 @@ impl Language for Haskell {
     }
 
     fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
-        node.child_by_field_name("where")
+        node.child_by_field_name("declarations")
     }
 
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
@@ -348,11 +433,178 @@ impl Language for Haskell {
     }
 }
 
+impl Haskell {
+    /// The next declaration after `node`, skipping comments.
+    fn next_declaration<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+        let mut next = node.next_named_sibling();
+        while let Some(sibling) = next {
+            if sibling.kind() != "comment" && sibling.kind() != "haddock" {
+                return Some(sibling);
+            }
+            next = sibling.next_named_sibling();
+        }
+        None
+    }
+
+    /// The declaration before `node`, skipping comments.
+    fn prev_declaration<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+        let mut prev = node.prev_named_sibling();
+        while let Some(sibling) = prev {
+            if sibling.kind() != "comment" && sibling.kind() != "haddock" {
+                return Some(sibling);
+            }
+            prev = sibling.prev_named_sibling();
+        }
+        None
+    }
+
+    /// Whether two declarations belong to the same binding (`f :: ...`, `f 0 = ...`).
+    fn same_binding(a: &Node, b: &Node, content: &str) -> bool {
+        let name = |n: &Node| {
+            n.child_by_field_name("name")
+                .map(|name| &content[name.byte_range()])
+        };
+        BINDING_KINDS.contains(&a.kind())
+            && BINDING_KINDS.contains(&b.kind())
+            && name(a).is_some()
+            && name(a) == name(b)
+    }
+
+    /// Whether `node` is a later equation of a binding that starts earlier.
+    fn continues_binding(node: &Node, content: &str) -> bool {
+        Self::prev_declaration(node).is_some_and(|prev| Self::same_binding(&prev, node, content))
+    }
+
+    /// Names in the module's export list, with `T(..)` entries also kept as
+    /// `T(..)`. None when there is no export list.
+    fn exported_names(node: &Node, content: &str) -> Option<HashSet<String>> {
+        let mut root = *node;
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+        let header = root
+            .children(&mut root.walk())
+            .find(|c| c.kind() == "header")?;
+        let exports = header.child_by_field_name("exports")?;
+
+        let mut names = HashSet::new();
+        for export in exports.named_children(&mut exports.walk()) {
+            if export.kind() != "export" {
+                continue;
+            }
+            let Some(name) = export
+                .child_by_field_name("variable")
+                .or_else(|| export.child_by_field_name("type"))
+            else {
+                continue;
+            };
+            let name = &content[name.byte_range()];
+            names.insert(name.to_string());
+            if let Some(children) = export.child_by_field_name("children") {
+                for child in children.named_children(&mut children.walk()) {
+                    if child.kind() == "all_names" {
+                        names.insert(format!("{}(..)", name));
+                    } else {
+                        names.insert(content[child.byte_range()].to_string());
+                    }
+                }
+            }
+        }
+        Some(names)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::validate_unused_kinds_audit;
 
+    fn extract(content: &str) -> Vec<Symbol> {
+        let store = arborium::GrammarStore::new();
+        let mut parser = arborium::tree_sitter::Parser::new();
+        parser
+            .set_language(store.get("haskell").unwrap().language())
+            .unwrap();
+        let tree = parser.parse(content, None).unwrap();
+        let decls = tree
+            .root_node()
+            .child_by_field_name("declarations")
+            .unwrap();
+        let mut symbols = Vec::new();
+        for node in decls.named_children(&mut decls.walk()) {
+            let symbol = if Haskell.function_kinds().contains(&node.kind()) {
+                Haskell.extract_function(&node, content, false)
+            } else {
+                Haskell.extract_container(&node, content)
+            };
+            symbols.extend(symbol);
+        }
+        symbols
+    }
+
+    #[test]
+    fn test_haskell_bindings_and_exports() {
+        let content = "module Shapes (area, Shape(..), Pretty(pretty)) where\n\ndata Shape = Circle Double\n\nclass Pretty a where\n  pretty :: a -> String\n\narea :: Shape\n     -> Double\narea (Circle r) = pi * r * r\n\nhelper :: Int\nhelper = 1\n";
+        let symbols = extract(content);
+        let summary: Vec<(&str, &str, usize, usize, Visibility)> = symbols
+            .iter()
+            .map(|s| {
+                (
+                    s.name.as_str(),
+                    s.signature.as_str(),
+                    s.start_line,
+                    s.end_line,
+                    s.visibility,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Shape", "data Shape", 3, 3, Visibility::Public),
+                ("Pretty", "class Pretty", 5, 6, Visibility::Public),
+                ("area", "area :: Shape -> Double", 8, 10, Visibility::Public),
+                ("helper", "helper :: Int", 12, 13, Visibility::Private),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_haskell_imports() {
+        let content =
+            "import qualified Data.Map as M\nimport Data.List (sortBy, nub)\nimport Data.Maybe\n";
+        let store = arborium::GrammarStore::new();
+        let mut parser = arborium::tree_sitter::Parser::new();
+        parser
+            .set_language(store.get("haskell").unwrap().language())
+            .unwrap();
+        let tree = parser.parse(content, None).unwrap();
+        let imports_node = tree.root_node().child_by_field_name("imports").unwrap();
+        let imports: Vec<Import> = imports_node
+            .named_children(&mut imports_node.walk())
+            .flat_map(|node| Haskell.extract_imports(&node, content))
+            .collect();
+        let summary: Vec<(&str, Option<&str>, Vec<&str>, bool)> = imports
+            .iter()
+            .map(|i| {
+                (
+                    i.module.as_str(),
+                    i.alias.as_deref(),
+                    i.names.iter().map(String::as_str).collect(),
+                    i.is_wildcard,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Data.Map", Some("M"), vec![], true),
+                ("Data.List", None, vec!["sortBy", "nub"], false),
+                ("Data.Maybe", None, vec![], true),
+            ]
+        );
+    }
+
     #[test]
     fn unused_node_kinds_audit() {
         #[rustfmt::skip]
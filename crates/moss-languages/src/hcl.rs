@@ -1,7 +1,7 @@
 //! HCL (HashiCorp Configuration Language) support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -30,6 +30,9 @@ impl Language for Hcl {
     fn function_kinds(&self) -> &'static [&'static str] {
         &[]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -47,16 +50,17 @@ impl Language for Hcl {
     }
 
     fn extract_public_symbols(&self, node: &Node, content: &str) -> Vec<Export> {
-        if node.kind() != "block" {
+        // Nested blocks (lifecycle, provisioner, ...) belong to their parent
+        if node.kind() != "block" || !is_top_level(node) {
             return Vec::new();
         }
 
-        let (block_type, name) = match self.extract_block_info(node, content) {
-            Some(info) => info,
-            None => return Vec::new(),
+        let Some(block) = self.extract_block_info(node, content) else {
+            return Vec::new();
         };
+        let name = block.name();
 
-        let kind = match block_type.as_str() {
+        let kind = match block.block_type.as_str() {
             "resource" | "data" => SymbolKind::Struct,
             "variable" | "output" | "locals" => SymbolKind::Variable,
             "module" => SymbolKind::Module,
@@ -101,19 +105,25 @@ impl Language for Hcl {
             return None;
         }
 
-        let (block_type, name) = self.extract_block_info(node, content)?;
+        let block = self.extract_block_info(node, content)?;
 
-        let kind = match block_type.as_str() {
+        let kind = match block.block_type.as_str() {
             "resource" | "data" => SymbolKind::Struct,
             "module" => SymbolKind::Module,
             "provider" => SymbolKind::Class,
             _ => SymbolKind::Variable,
         };
 
+        // `resource "aws_instance" "web"`, as written
+        let mut signature = block.block_type.clone();
+        for label in &block.labels {
+            signature.push_str(&format!(" \"{}\"", label));
+        }
+
         Some(Symbol {
-            name: name.clone(),
+            name: block.name(),
             kind,
-            signature: format!("{} \"{}\"", block_type, name),
+            signature,
             docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
@@ -127,8 +137,15 @@ impl Language for Hcl {
     }
 
     fn extract_docstring(&self, node: &Node, content: &str) -> Option<String> {
-        // HCL uses # or // for comments
-        let mut prev = node.prev_sibling();
+        // HCL uses # or // for comments. Comments before the first block are
+        // siblings of the enclosing body rather than of the block.
+        let mut prev = match node.prev_sibling() {
+            Some(prev) => Some(prev),
+            None => node
+                .parent()
+                .filter(|p| p.kind() == "body")
+                .and_then(|p| p.prev_sibling()),
+        };
         let mut doc_lines = Vec::new();
 
         while let Some(sibling) = prev {
@@ -150,18 +167,22 @@ impl Language for Hcl {
         Some(doc_lines.join(" "))
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "block" {
             return Vec::new();
         }
 
-        let (block_type, _name) = match self.extract_block_info(node, content) {
-            Some(info) => info,
-            None => return Vec::new(),
-        };
-
-        if block_type != "module" {
-            return Vec::new();
+        match self.extract_block_info(node, content) {
+            Some(block) if block.block_type == "module" => {}
+            _ => return Vec::new(),
         }
 
         // Look for source attribute in the block
@@ -173,11 +194,11 @@ impl Language for Hcl {
                     if let Some(end) = rest.find('"') {
                         let module = rest[..end].to_string();
                         return vec![Import {
+                            is_relative: module.starts_with("./") || module.starts_with("../"),
                             module,
                             names: Vec::new(),
                             alias: None,
                             is_wildcard: false,
-                            is_relative: !rest.starts_with("registry") && !rest.starts_with("git"),
                             line: node.start_position().row + 1,
                         }];
                     }
@@ -200,7 +221,9 @@ impl Language for Hcl {
     }
 
     fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
-        node.child_by_field_name("body")
+        let mut cursor = node.walk();
+        let body = node.children(&mut cursor).find(|c| c.kind() == "body");
+        body
     }
 
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
@@ -237,17 +260,13 @@ impl Language for Hcl {
     fn resolve_local_import(
         &self,
         import: &str,
-        _current_file: &Path,
-        project_root: &Path,
+        current_file: &Path,
+        _project_root: &Path,
     ) -> Option<PathBuf> {
+        // Local module sources are directories relative to the calling module
         if import.starts_with("./") || import.starts_with("../") {
-            let full = project_root.join(import);
-            if full.is_dir() {
-                let main_tf = full.join("main.tf");
-                if main_tf.is_file() {
-                    return Some(main_tf);
-                }
-            }
+            let dir = current_file.parent()?.join(import);
+            return module_entry(&dir);
         }
         None
     }
@@ -327,16 +346,53 @@ impl Language for Hcl {
         if path.is_file() {
             return Some(path.to_path_buf());
         }
-        let main = path.join("main.tf");
-        if main.is_file() {
-            return Some(main);
+        module_entry(path)
+    }
+}
+
+/// A block's type and labels: `resource "aws_instance" "web"`.
+struct BlockInfo {
+    block_type: String,
+    labels: Vec<String>,
+}
+
+impl BlockInfo {
+    /// Labels joined with dots (`aws_instance.web`), or the type if unlabeled.
+    fn name(&self) -> String {
+        if self.labels.is_empty() {
+            self.block_type.clone()
+        } else {
+            self.labels.join(".")
         }
-        None
     }
 }
 
+/// Whether a block is at the top level of the file rather than nested in another block.
+fn is_top_level(node: &Node) -> bool {
+    node.parent()
+        .and_then(|body| body.parent())
+        .is_some_and(|p| p.kind() == "config_file")
+}
+
+/// The file representing a module directory: main.tf by convention, otherwise
+/// the first .tf file, since Terraform reads all of them.
+fn module_entry(dir: &Path) -> Option<PathBuf> {
+    let main = dir.join("main.tf");
+    if main.is_file() {
+        return Some(main);
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e == "tf"))
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
 impl Hcl {
-    fn extract_block_info(&self, node: &Node, content: &str) -> Option<(String, String)> {
+    fn extract_block_info(&self, node: &Node, content: &str) -> Option<BlockInfo> {
         let mut cursor = node.walk();
         let mut block_type = None;
         let mut labels = Vec::new();
@@ -354,16 +410,10 @@ impl Hcl {
             }
         }
 
-        let block_type = block_type?;
-        let name = if labels.len() >= 2 {
-            format!("{}.{}", labels[0], labels[1])
-        } else if !labels.is_empty() {
-            labels[0].clone()
-        } else {
-            block_type.clone()
-        };
-
-        Some((block_type, name))
+        Some(BlockInfo {
+            block_type: block_type?,
+            labels,
+        })
     }
 }
 
@@ -1,7 +1,7 @@
 //! Java language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -342,6 +342,10 @@ impl Language for Java {
         &["method_declaration", "constructor_declaration"]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["lambda_expression"]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[
             "class_declaration",
@@ -497,6 +501,14 @@ impl Language for Java {
         None
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "import_declaration" {
             return Vec::new();
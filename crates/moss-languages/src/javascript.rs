@@ -2,7 +2,7 @@
 
 use crate::ecmascript;
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -30,6 +30,9 @@ impl Language for JavaScript {
     fn function_kinds(&self) -> &'static [&'static str] {
         ecmascript::JS_FUNCTION_KINDS
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        ecmascript::ANONYMOUS_FUNCTION_KINDS
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         ecmascript::JS_TYPE_KINDS
     }
@@ -80,6 +83,14 @@ impl Language for JavaScript {
         None
     }
 
+    fn parameters(&self, node: &Node, content: &str) -> Vec<Param> {
+        ecmascript::parameters(node, content)
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         ecmascript::extract_imports(node, content)
     }
@@ -301,8 +312,37 @@ fn find_deno_version_dir(pkg_path: &Path, pkg_name: &str) -> Option<(String, Pat
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{param, parse};
     use crate::validate_unused_kinds_audit;
 
+    #[test]
+    fn test_javascript_parameters() {
+        let content = "function f(a, b = 1, ...rest) {}\nconst g = (...args) => args;\n";
+        let tree = parse("javascript", content);
+        let root = tree.root_node();
+        let func = root.child(0).unwrap();
+        assert_eq!(
+            JavaScript.parameters(&func, content),
+            vec![
+                param("a", None, None, false),
+                param("b", None, Some("1"), true),
+                param("...rest", None, None, true),
+            ]
+        );
+
+        let arrow = root
+            .child(1)
+            .unwrap()
+            .named_child(0)
+            .unwrap()
+            .child_by_field_name("value")
+            .unwrap();
+        assert_eq!(
+            JavaScript.parameters(&arrow, content),
+            vec![param("...args", None, None, true)]
+        );
+    }
+
     /// Documents node kinds that exist in the JavaScript grammar but aren't used in trait methods.
     /// Run `cross_check_node_kinds` in registry.rs to see all potentially useful kinds.
     #[test]
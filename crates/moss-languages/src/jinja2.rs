@@ -1,7 +1,7 @@
 //! Jinja2 template support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -31,6 +31,10 @@ impl Language for Jinja2 {
         &[]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -90,6 +94,14 @@ impl Language for Jinja2 {
         None
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         // Jinja2 grammar is minimal - only basic tokens, no structured nodes
         Vec::new()
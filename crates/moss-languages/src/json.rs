@@ -1,7 +1,7 @@
 //! JSON language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -20,16 +20,20 @@ impl Language for Json {
     }
 
     fn has_symbols(&self) -> bool {
-        false
+        true
     }
 
-    // JSON is data, not code - no functions/types/control flow
+    // JSON is data, not code - no functions/types/control flow. Top-level
+    // keys are the symbols, with the keys of an object value as children.
     fn container_kinds(&self) -> &'static [&'static str] {
-        &["object"]
+        &["pair"]
     }
     fn function_kinds(&self) -> &'static [&'static str] {
         &[]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -65,15 +69,19 @@ impl Language for Json {
     }
 
     fn extract_container(&self, node: &Node, content: &str) -> Option<Symbol> {
-        // Extract top-level object keys
-        if node.kind() == "pair" {
+        if node.kind() == "pair" && pair_depth(node) <= 1 {
             let key = node.child_by_field_name("key")?;
             let key_text = content[key.byte_range()].trim_matches('"');
+            let text = &content[node.byte_range()];
 
             return Some(Symbol {
                 name: key_text.to_string(),
                 kind: SymbolKind::Variable,
-                signature: key_text.to_string(),
+                signature: if text.contains('\n') {
+                    key_text.to_string()
+                } else {
+                    text.to_string()
+                },
                 docstring: None,
                 start_line: node.start_position().row + 1,
                 end_line: node.end_position().row + 1,
@@ -90,6 +98,12 @@ impl Language for Json {
     fn extract_docstring(&self, _node: &Node, _content: &str) -> Option<String> {
         None
     }
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         Vec::new()
     }
@@ -108,8 +122,9 @@ impl Language for Json {
         None
     }
 
-    fn container_body<'a>(&self, _node: &'a Node<'a>) -> Option<Node<'a>> {
-        None
+    fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
+        let value = node.child_by_field_name("value")?;
+        (pair_depth(node) == 0 && value.kind() == "object").then_some(value)
     }
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
         false
@@ -171,6 +186,13 @@ impl Language for Json {
     }
 }
 
+/// How many keys a pair is nested under (0 for a top-level key).
+fn pair_depth(node: &Node) -> usize {
+    std::iter::successors(node.parent(), |n| n.parent())
+        .filter(|n| n.kind() == "pair")
+        .count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,7 +2,7 @@
 
 use crate::external_packages::ResolvedPackage;
 use crate::java::{find_gradle_cache, find_maven_repository, get_java_version};
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -36,6 +36,10 @@ impl Language for Kotlin {
         ]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["lambda_literal", "anonymous_function"]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &["class_declaration", "object_declaration", "type_alias"]
     }
@@ -230,6 +234,14 @@ impl Language for Kotlin {
         None
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "import_header" {
             return Vec::new();
@@ -29,7 +29,11 @@ pub mod c_cpp;
 pub mod ecmascript;
 pub mod external_packages;
 pub mod ffi;
+pub mod go_build;
 mod registry;
+pub mod rust_cfg;
+#[cfg(test)]
+mod test_support;
 mod traits;
 
 // Language implementations
@@ -333,7 +337,7 @@ pub use registry::{
 // Re-exports from traits
 pub use traits::{
     has_extension, skip_dotfiles, EmbeddedBlock, Export, Import, Language, PackageSource,
-    PackageSourceKind, Symbol, SymbolKind, Visibility, VisibilityMechanism,
+    PackageSourceKind, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism,
 };
 
 // Re-export language structs
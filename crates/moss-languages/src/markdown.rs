@@ -1,7 +1,7 @@
 //! Markdown language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -30,6 +30,9 @@ impl Language for Markdown {
     fn function_kinds(&self) -> &'static [&'static str] {
         &[]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -65,11 +68,10 @@ impl Language for Markdown {
     }
 
     fn extract_container(&self, node: &Node, content: &str) -> Option<Symbol> {
-        // Extract heading text
-        let mut cursor = node.walk();
+        // Headings come out flat; the extractor nests them by the level in
+        // the signature
         let text = node
-            .children(&mut cursor)
-            .find(|c| c.kind() == "heading_content" || c.kind() == "inline")
+            .child_by_field_name("heading_content")
             .map(|c| content[c.byte_range()].trim().to_string())
             .unwrap_or_default();
 
@@ -77,16 +79,14 @@ impl Language for Markdown {
             return None;
         }
 
-        // Determine heading level
+        // Level from the marker: atx_h2_marker, setext_h1_underline
+        let mut cursor = node.walk();
         let level = node
             .children(&mut cursor)
-            .find(|c| c.kind().starts_with("atx_h"))
-            .map(|c| {
+            .find_map(|c| {
                 c.kind()
-                    .chars()
-                    .last()
-                    .and_then(|c| c.to_digit(10))
-                    .unwrap_or(1) as usize
+                    .split('_')
+                    .find_map(|part| part.strip_prefix('h')?.parse::<usize>().ok())
             })
             .unwrap_or(1);
 
@@ -96,7 +96,11 @@ impl Language for Markdown {
             signature: format!("{} {}", "#".repeat(level), text),
             docstring: None,
             start_line: node.start_position().row + 1,
-            end_line: node.end_position().row + 1,
+            // The heading node takes its line break, ending on the next line
+            end_line: match node.end_position() {
+                end if end.column == 0 => end.row,
+                end => end.row + 1,
+            },
             visibility: Visibility::Public,
             children: Vec::new(),
         })
@@ -108,6 +112,12 @@ impl Language for Markdown {
     fn extract_docstring(&self, _node: &Node, _content: &str) -> Option<String> {
         None
     }
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         Vec::new()
     }
@@ -1,7 +1,11 @@
 //! Nix language support.
+//!
+//! Symbols are attribute and `let` bindings: a binding whose value is a
+//! function becomes a function, one holding an attrset becomes a module with
+//! the nested bindings as children, anything else is a variable.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -24,17 +28,18 @@ impl Language for Nix {
     }
 
     fn container_kinds(&self) -> &'static [&'static str] {
-        &[
-            "attrset_expression",
-            "let_expression",
-            "rec_attrset_expression",
-        ]
+        &["binding"]
     }
 
     fn function_kinds(&self) -> &'static [&'static str] {
+        // Named through their binding; see extract_function
         &["function_expression"]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -52,7 +57,8 @@ impl Language for Nix {
     }
 
     fn extract_public_symbols(&self, node: &Node, content: &str) -> Vec<Export> {
-        if node.kind() != "binding" {
+        // Only the file's top-level attrset is visible to importers
+        if node.kind() != "binding" || !is_top_level_binding(node) {
             return Vec::new();
         }
 
@@ -63,7 +69,7 @@ impl Language for Nix {
 
         vec![Export {
             name,
-            kind: SymbolKind::Variable,
+            kind: binding_kind(node),
             line: node.start_position().row + 1,
         }]
     }
@@ -88,60 +94,45 @@ impl Language for Nix {
         ]
     }
 
-    fn extract_function(&self, node: &Node, content: &str, _in_container: bool) -> Option<Symbol> {
-        if node.kind() != "function_expression" {
-            return None;
-        }
-
-        let text = &content[node.byte_range()];
-        let first_line = text.lines().next().unwrap_or(text);
-
-        // Try to get name from parent binding
-        let name = node
-            .parent()
-            .filter(|p| p.kind() == "binding")
-            .and_then(|p| p.child_by_field_name("attrpath"))
-            .map(|n| content[n.byte_range()].to_string())
-            .unwrap_or_else(|| "<lambda>".to_string());
-
-        Some(Symbol {
-            name,
-            kind: SymbolKind::Function,
-            signature: first_line.trim().chars().take(80).collect(),
-            docstring: self.extract_docstring(node, content),
-            start_line: node.start_position().row + 1,
-            end_line: node.end_position().row + 1,
-            visibility: Visibility::Public,
-            children: Vec::new(),
-        })
+    fn extract_function(
+        &self,
+        _node: &Node,
+        _content: &str,
+        _in_container: bool,
+    ) -> Option<Symbol> {
+        // Named functions are bindings (extract_container). Bare lambdas,
+        // including a file that is one big function, aren't symbols: their
+        // bodies are walked so the bindings inside still show up.
+        None
     }
 
     fn extract_container(&self, node: &Node, content: &str) -> Option<Symbol> {
-        let kind_str = node.kind();
-        if !matches!(
-            kind_str,
-            "attrset_expression" | "let_expression" | "rec_attrset_expression"
-        ) {
+        if node.kind() != "binding" {
             return None;
         }
+        let name = self.node_name(node, content)?;
+        let kind = binding_kind(node);
 
-        // Try to get name from parent binding
-        let name = node
-            .parent()
-            .filter(|p| p.kind() == "binding")
-            .and_then(|p| p.child_by_field_name("attrpath"))
-            .map(|n| content[n.byte_range()].to_string())
-            .unwrap_or_else(|| match kind_str {
-                "let_expression" => "let".to_string(),
-                "rec_attrset_expression" => "rec { }".to_string(),
-                _ => "{ }".to_string(),
-            });
+        // Functions: up to the body (`mkShell = { pkgs, ... }:`),
+        // everything else: the first line (`inputs = {`)
+        let text = &content[node.byte_range()];
+        let signature = match (kind, function_body(node)) {
+            (SymbolKind::Function, Some(body)) => {
+                content[node.start_byte()..body.start_byte()].trim_end()
+            }
+            _ => text
+                .lines()
+                .next()
+                .unwrap_or(text)
+                .trim_end()
+                .trim_end_matches(';'),
+        };
 
         Some(Symbol {
-            name: name.clone(),
-            kind: SymbolKind::Module,
-            signature: name,
-            docstring: None,
+            name: name.to_string(),
+            kind,
+            signature: signature.chars().take(80).collect(),
+            docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: Visibility::Public,
@@ -177,26 +168,34 @@ impl Language for Nix {
         Some(doc_lines.join(" "))
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "apply_expression" {
             return Vec::new();
         }
 
-        let text = &content[node.byte_range()];
-        if !text.starts_with("import ") {
+        // `import ./path.nix` - the inner application of `import ./x.nix { ... }`
+        let is_import = node.child_by_field_name("function").is_some_and(|f| {
+            f.kind() == "variable_expression" && &content[f.byte_range()] == "import"
+        });
+        let Some(argument) = node.child_by_field_name("argument").filter(|_| is_import) else {
             return Vec::new();
-        }
-
-        // Extract path after "import"
-        let rest = text.strip_prefix("import ").unwrap_or("").trim();
-        let module = rest.split_whitespace().next().unwrap_or(rest).to_string();
+        };
 
+        let module = content[argument.byte_range()].to_string();
         vec![Import {
+            is_relative: argument.kind() == "path_expression" && module.starts_with('.'),
             module,
             names: Vec::new(),
             alias: None,
             is_wildcard: false,
-            is_relative: rest.starts_with('.'),
             line: node.start_position().row + 1,
         }]
     }
@@ -212,15 +211,35 @@ impl Language for Nix {
         None
     }
 
-    fn container_body<'a>(&self, _node: &'a Node<'a>) -> Option<Node<'a>> {
-        None
+    fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
+        // The walk descends into everything that isn't a binding, so the
+        // value itself works as body: attrset values yield their bindings,
+        // `let ... in` yields both the let bindings and those of the result.
+        let value = match function_body(node) {
+            Some(body) => body,
+            None => node.child_by_field_name("expression")?,
+        };
+        matches!(
+            value.kind(),
+            "attrset_expression"
+                | "rec_attrset_expression"
+                | "let_attrset_expression"
+                | "let_expression"
+        )
+        .then_some(value)
     }
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
         false
     }
 
     fn node_name<'a>(&self, node: &Node, content: &'a str) -> Option<&'a str> {
-        node.child_by_field_name("attrpath")
+        // A function is named by the binding it's assigned to
+        let binding = match node.kind() {
+            "function_expression" => node.parent().filter(|p| p.kind() == "binding")?,
+            _ => *node,
+        };
+        binding
+            .child_by_field_name("attrpath")
             .map(|n| &content[n.byte_range()])
     }
 
@@ -334,6 +353,57 @@ impl Language for Nix {
     }
 }
 
+/// Symbol kind for a binding, from the kind of its value.
+fn binding_kind(binding: &Node) -> SymbolKind {
+    match binding.child_by_field_name("expression").map(|v| v.kind()) {
+        Some("function_expression") => SymbolKind::Function,
+        Some("attrset_expression" | "rec_attrset_expression" | "let_attrset_expression") => {
+            SymbolKind::Module
+        }
+        _ => SymbolKind::Variable,
+    }
+}
+
+/// Body of a function-valued binding, past any curried arguments
+/// (`mkGreeting = name: greeting: ...`).
+fn function_body<'a>(binding: &Node<'a>) -> Option<Node<'a>> {
+    let mut value = binding.child_by_field_name("expression")?;
+    if value.kind() != "function_expression" {
+        return None;
+    }
+    while value.kind() == "function_expression" {
+        value = value.child_by_field_name("body")?;
+    }
+    Some(value)
+}
+
+/// Whether a binding belongs to the attrset the file evaluates to, possibly
+/// as the body of a top-level function, `let`, or `with`
+/// (`{ pkgs, ... }: let ... in { ... }`).
+fn is_top_level_binding(binding: &Node) -> bool {
+    let Some(attrset) = binding.parent().and_then(|set| set.parent()) else {
+        return false;
+    };
+    if !matches!(
+        attrset.kind(),
+        "attrset_expression" | "rec_attrset_expression"
+    ) {
+        return false;
+    }
+    let mut node = attrset;
+    while let Some(parent) = node.parent() {
+        match parent.kind() {
+            "source_code" => return true,
+            "function_expression"
+            | "let_expression"
+            | "with_expression"
+            | "parenthesized_expression" => node = parent,
+            _ => return false,
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,7 +417,7 @@ mod tests {
             "formal", "formals", "has_attr_expression", "hpath_expression",
             "identifier", "indented_string_expression", "integer_expression",
             "list_expression", "let_attrset_expression", "parenthesized_expression",
-            "path_expression", "select_expression", "spath_expression",
+            "path_expression", "rec_attrset_expression", "select_expression", "spath_expression",
             "string_expression", "unary_expression", "uri_expression",
             "variable_expression",
         ];
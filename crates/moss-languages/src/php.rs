@@ -1,7 +1,7 @@
 //! PHP language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -41,6 +41,10 @@ impl Language for Php {
         ]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["anonymous_function", "arrow_function"]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &[
             "class_declaration",
@@ -245,6 +249,14 @@ impl Language for Php {
         None
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "namespace_use_declaration" {
             return Vec::new();
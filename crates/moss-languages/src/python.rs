@@ -1,10 +1,12 @@
 //! Python language support.
 
-use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::external_packages::{version_cmp, ResolvedPackage};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
 
 // ============================================================================
 // Python path cache (filesystem-based detection, no subprocess calls)
@@ -17,6 +19,8 @@ static PYTHON_CACHE: Mutex<Option<PythonPathCache>> = Mutex::new(None);
 struct PythonPathCache {
     /// Canonical project root used as cache key
     root: PathBuf,
+    /// Activated environment ($VIRTUAL_ENV or $CONDA_PREFIX), also part of the key
+    active_env: Option<PathBuf>,
     /// Python version (e.g., "3.13")
     version: Option<String>,
     /// Stdlib path (e.g., /usr/.../lib/python3.13/)
@@ -26,40 +30,54 @@ struct PythonPathCache {
 }
 
 impl PythonPathCache {
-    fn new(root: &Path) -> Self {
+    /// Detect paths for `root`, preferring the activated environment if given.
+    fn new(root: &Path, active_env: Option<&Path>) -> Self {
         let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let mut cache = Self::from_interpreter(root, active_env);
+        // An activated env is what `python` would import from, whichever interpreter we found.
+        if let Some(site) = active_env.and_then(find_site_packages_in_venv) {
+            cache.site_packages = Some(site);
+        }
+        cache.active_env = active_env.map(Path::to_path_buf);
+        cache
+    }
 
-        // Try to find Python from venv or PATH
-        let python_bin = if root.join(".venv/bin/python").exists() {
-            Some(root.join(".venv/bin/python"))
-        } else if root.join("venv/bin/python").exists() {
-            Some(root.join("venv/bin/python"))
-        } else {
-            // Look in PATH
-            std::env::var("PATH").ok().and_then(|path| {
-                for dir in path.split(':') {
-                    let python = PathBuf::from(dir).join("python3");
-                    if python.exists() {
-                        return Some(python);
-                    }
-                    let python = PathBuf::from(dir).join("python");
-                    if python.exists() {
-                        return Some(python);
-                    }
-                }
-                None
-            })
-        };
-
-        let Some(python_bin) = python_bin else {
+    fn from_interpreter(root: PathBuf, active_env: Option<&Path>) -> Self {
+        let Some(python_bin) = find_python_interpreter(&root, active_env) else {
             return Self {
                 root,
+                active_env: None,
                 version: None,
                 stdlib: None,
                 site_packages: None,
             };
         };
 
+        // Windows layout: python.exe, Lib/ and DLLs/ directly in the prefix
+        if let Some(prefix) = windows_prefix(&python_bin) {
+            let venv = python_bin
+                .parent()
+                .filter(|dir| dir.file_name().is_some_and(|n| n == "Scripts"))
+                .and_then(|scripts| scripts.parent());
+            let version = venv
+                .and_then(|venv| pyvenv_cfg_value(venv, "version_info"))
+                .or_else(|| venv.and_then(|venv| pyvenv_cfg_value(venv, "version")))
+                .and_then(|v| major_minor(&v))
+                .or_else(|| windows_dll_version(&prefix));
+            let site_packages = venv
+                .map(|venv| venv.join("Lib").join("site-packages"))
+                .filter(|site| site.is_dir())
+                .or_else(|| Some(prefix.join("Lib").join("site-packages")))
+                .filter(|site| site.is_dir());
+            return Self {
+                root,
+                active_env: None,
+                version,
+                stdlib: Some(prefix.join("Lib")),
+                site_packages,
+            };
+        }
+
         // Resolve symlinks to find the actual Python installation
         let python_real = std::fs::canonicalize(&python_bin).unwrap_or(python_bin.clone());
 
@@ -140,6 +158,7 @@ impl PythonPathCache {
 
         Self {
             root,
+            active_env: None,
             version,
             stdlib,
             site_packages,
@@ -147,21 +166,112 @@ impl PythonPathCache {
     }
 }
 
+/// Python interpreter inside a venv.
+///
+/// Checks the Windows `Scripts/python.exe` before the Unix `bin/python`.
+fn venv_python(venv: &Path) -> Option<PathBuf> {
+    [
+        venv.join("Scripts").join("python.exe"),
+        venv.join("bin").join("python"),
+    ]
+    .into_iter()
+    .find(|python| python.exists())
+}
+
+/// Find the interpreter for a project: the activated env, its venv, else the first on PATH.
+fn find_python_interpreter(root: &Path, active_env: Option<&Path>) -> Option<PathBuf> {
+    if let Some(python) = active_env.and_then(venv_python) {
+        return Some(python);
+    }
+    if let Some(python) = [".venv", "venv"]
+        .iter()
+        .find_map(|dir| venv_python(&root.join(dir)))
+    {
+        return Some(python);
+    }
+
+    let names: &[&str] = if cfg!(windows) {
+        &["python.exe", "python3.exe"]
+    } else {
+        &["python3", "python"]
+    };
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|python| python.exists())
+}
+
+/// Prefix of a Windows-layout install (`python.exe` next to `Lib/` and `DLLs/`).
+///
+/// For a venv's `Scripts/python.exe` this is the base install recorded as
+/// `home` in `pyvenv.cfg`, since Windows venvs copy rather than symlink it.
+fn windows_prefix(python_bin: &Path) -> Option<PathBuf> {
+    let dir = python_bin.parent()?;
+    let prefix = if dir.file_name().is_some_and(|n| n == "Scripts") {
+        PathBuf::from(pyvenv_cfg_value(dir.parent()?, "home")?)
+    } else {
+        dir.to_path_buf()
+    };
+    prefix.join("Lib").join("os.py").is_file().then_some(prefix)
+}
+
+/// Read a `key = value` entry from a venv's `pyvenv.cfg`.
+fn pyvenv_cfg_value(venv: &Path, key: &str) -> Option<String> {
+    let content = std::fs::read_to_string(venv.join("pyvenv.cfg")).ok()?;
+    content.lines().find_map(|line| {
+        let (k, v) = line.split_once('=')?;
+        (k.trim() == key).then(|| v.trim().to_string())
+    })
+}
+
+/// Truncate a version like "3.12.1" to "3.12".
+fn major_minor(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major = parts.next().filter(|p| p.parse::<u32>().is_ok())?;
+    let minor = parts.next().filter(|p| p.parse::<u32>().is_ok())?;
+    Some(format!("{}.{}", major, minor))
+}
+
+/// Detect the version from `python3XY.dll` in a Windows prefix or its `DLLs/`.
+fn windows_dll_version(prefix: &Path) -> Option<String> {
+    [prefix.to_path_buf(), prefix.join("DLLs")]
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .find_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            let digits = name.strip_prefix("python3")?.strip_suffix(".dll")?;
+            digits
+                .parse::<u32>()
+                .ok()
+                .map(|minor| format!("3.{}", minor))
+        })
+}
+
+/// The activated environment: $VIRTUAL_ENV, then $CONDA_PREFIX.
+fn active_python_env() -> Option<PathBuf> {
+    ["VIRTUAL_ENV", "CONDA_PREFIX"]
+        .iter()
+        .find_map(|var| std::env::var_os(var).filter(|v| !v.is_empty()))
+        .map(PathBuf::from)
+}
+
 /// Get cached Python paths for a project.
 fn get_python_cache(project_root: &Path) -> PythonPathCache {
     let canonical = project_root
         .canonicalize()
         .unwrap_or_else(|_| project_root.to_path_buf());
+    let active_env = active_python_env();
 
     let mut cache_guard = PYTHON_CACHE.lock().unwrap();
 
     if let Some(ref cache) = *cache_guard {
-        if cache.root == canonical {
+        if cache.root == canonical && cache.active_env == active_env {
             return cache.clone();
         }
     }
 
-    let new_cache = PythonPathCache::new(project_root);
+    let new_cache = PythonPathCache::new(project_root, active_env.as_deref());
     *cache_guard = Some(new_cache.clone());
     new_cache
 }
@@ -180,6 +290,71 @@ pub fn find_python_stdlib(project_root: &Path) -> Option<PathBuf> {
     get_python_cache(project_root).stdlib
 }
 
+/// Library directories of one installed Python version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonInstall {
+    pub stdlib: PathBuf,
+    pub site_packages: Option<PathBuf>,
+}
+
+/// Find an installed Python `version` (X.Y) without running it: the project's
+/// own interpreter if it is that version, then pyenv's `versions/` directory
+/// ($PYENV_ROOT or ~/.pyenv), then `pythonX.Y` on PATH.
+pub fn find_python_install(project_root: &Path, version: &str) -> Option<PythonInstall> {
+    if get_python_version(project_root).as_deref() == Some(version) {
+        if let Some(stdlib) = find_python_stdlib(project_root) {
+            return Some(PythonInstall {
+                stdlib,
+                site_packages: find_python_site_packages(project_root),
+            });
+        }
+    }
+
+    let pyenv_root = std::env::var_os("PYENV_ROOT")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".pyenv")));
+    if let Some(install) = pyenv_root.and_then(|root| find_pyenv_install(&root, version)) {
+        return Some(install);
+    }
+
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(format!("python{}", version)))
+        .filter(|python| python.exists())
+        .find_map(|python| {
+            let real = std::fs::canonicalize(&python).unwrap_or(python);
+            install_in_prefix(real.parent()?.parent()?, version)
+        })
+}
+
+/// The newest pyenv install of `version`, e.g. `versions/3.11.9` for 3.11.
+fn find_pyenv_install(pyenv_root: &Path, version: &str) -> Option<PythonInstall> {
+    let versions = pyenv_root.join("versions");
+    let mut names: Vec<String> = std::fs::read_dir(&versions)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| major_minor(name).as_deref() == Some(version))
+        .collect();
+    names.sort_by(|a, b| version_cmp(b, a));
+    names
+        .iter()
+        .find_map(|name| install_in_prefix(&versions.join(name), version))
+}
+
+/// `lib/pythonX.Y` under an install prefix, with its site-packages.
+fn install_in_prefix(prefix: &Path, version: &str) -> Option<PythonInstall> {
+    let stdlib = prefix.join("lib").join(format!("python{}", version));
+    if !stdlib.join("os.py").is_file() {
+        return None;
+    }
+    let site = stdlib.join("site-packages");
+    Some(PythonInstall {
+        site_packages: site.is_dir().then_some(site),
+        stdlib,
+    })
+}
+
 /// Check if a module name is a Python stdlib module.
 fn is_python_stdlib_module(module_name: &str, stdlib_path: &Path) -> bool {
     let top_level = module_name.split('.').next().unwrap_or(module_name);
@@ -263,10 +438,11 @@ fn resolve_python_stdlib_import(import_name: &str, stdlib_path: &Path) -> Option
 /// Find Python site-packages directory for a project.
 ///
 /// Search order:
-/// 1. .venv/lib/pythonX.Y/site-packages/ (uv, poetry, standard venv)
-/// 2. Walk up looking for venv directories
+/// 1. Activated environment: $VIRTUAL_ENV, then $CONDA_PREFIX
+/// 2. .venv/lib/pythonX.Y/site-packages/ (uv, poetry, standard venv)
+/// 3. Walk up looking for venv directories
 pub fn find_python_site_packages(project_root: &Path) -> Option<PathBuf> {
-    // Use cached result from filesystem detection
+    // Use cached result from filesystem detection (covers the activated env)
     if let Some(site) = get_python_cache(project_root).site_packages {
         return Some(site);
     }
@@ -316,76 +492,248 @@ fn find_site_packages_in_venv(venv: &Path) -> Option<PathBuf> {
 
 /// Resolve a Python import to its source location.
 ///
+/// Searches site-packages, then the directories listed in its `.pth` files,
+/// then editable-install finder mappings.
+///
 /// Handles:
 /// - Package imports (requests -> requests/__init__.py)
 /// - Module imports (six -> six.py)
 /// - Submodule imports (requests.api -> requests/api.py)
 /// - Namespace packages (no __init__.py)
+/// - Editable installs (`pip install -e`, setuptools and hatchling finders)
 fn resolve_python_import(import_name: &str, site_packages: &Path) -> Option<ResolvedPackage> {
-    // Split on dots for submodule resolution
-    let parts: Vec<&str> = import_name.split('.').collect();
-    let top_level = parts[0];
+    let extra = site_packages_extras(site_packages);
+    std::iter::once(site_packages)
+        .chain(extra.pth_dirs.iter().map(PathBuf::as_path))
+        .find_map(|dir| resolve_in_search_path(import_name, dir))
+        .or_else(|| resolve_editable_import(import_name, &extra.editable))
+}
+
+/// Search paths a site-packages directory adds beyond itself.
+struct SitePackagesExtras {
+    /// Directory mtime when read; installing or removing a package changes it
+    modified: Option<SystemTime>,
+    pth_dirs: Vec<PathBuf>,
+    editable: Vec<EditableMapping>,
+}
+
+static SITE_PACKAGES_CACHE: LazyLock<Mutex<HashMap<PathBuf, Arc<SitePackagesExtras>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// `.pth` directories and editable mappings of `site_packages`, read once per
+/// directory and re-read when its mtime changes.
+fn site_packages_extras(site_packages: &Path) -> Arc<SitePackagesExtras> {
+    let modified = std::fs::metadata(site_packages)
+        .and_then(|m| m.modified())
+        .ok();
+    let mut cache = SITE_PACKAGES_CACHE.lock().unwrap();
+    if let Some(extras) = cache.get(site_packages) {
+        if extras.modified == modified {
+            return extras.clone();
+        }
+    }
+    let extras = Arc::new(SitePackagesExtras {
+        modified,
+        pth_dirs: pth_search_paths(site_packages),
+        editable: editable_mappings(site_packages),
+    });
+    cache.insert(site_packages.to_path_buf(), extras.clone());
+    extras
+}
+
+/// Resolve an import against one sys.path entry.
+fn resolve_in_search_path(import_name: &str, dir: &Path) -> Option<ResolvedPackage> {
+    let top_level = import_name.split('.').next().unwrap_or(import_name);
 
     // Check for package (directory)
-    let pkg_dir = site_packages.join(top_level);
+    let pkg_dir = dir.join(top_level);
     if pkg_dir.is_dir() {
-        if parts.len() == 1 {
-            // Just the package - look for __init__.py
-            let init = pkg_dir.join("__init__.py");
-            if init.is_file() {
-                return Some(ResolvedPackage {
-                    path: pkg_dir,
-                    name: import_name.to_string(),
-                    is_namespace: false,
-                });
+        return resolve_in_package_dir(import_name, &pkg_dir);
+    }
+
+    // Check for single-file module
+    let py_file = dir.join(format!("{}.py", top_level));
+    if py_file.is_file() {
+        return Some(ResolvedPackage {
+            path: py_file,
+            name: import_name.to_string(),
+            is_namespace: false,
+        });
+    }
+
+    None
+}
+
+/// Resolve an import whose top-level package lives in `pkg_dir`.
+fn resolve_in_package_dir(import_name: &str, pkg_dir: &Path) -> Option<ResolvedPackage> {
+    let parts: Vec<&str> = import_name.split('.').collect();
+
+    if parts.len() == 1 {
+        // Just the package - namespace package if there's no __init__.py
+        return Some(ResolvedPackage {
+            path: pkg_dir.to_path_buf(),
+            name: import_name.to_string(),
+            is_namespace: !pkg_dir.join("__init__.py").is_file(),
+        });
+    }
+
+    // Submodule - build path
+    let mut path = pkg_dir.to_path_buf();
+    for part in &parts[1..] {
+        path = path.join(part);
+    }
+
+    // Try as package first
+    if path.is_dir() {
+        let init = path.join("__init__.py");
+        return Some(ResolvedPackage {
+            path: path.clone(),
+            name: import_name.to_string(),
+            is_namespace: !init.is_file(),
+        });
+    }
+
+    // Try as module
+    let py_file = path.with_extension("py");
+    if py_file.is_file() {
+        return Some(ResolvedPackage {
+            path: py_file,
+            name: import_name.to_string(),
+            is_namespace: false,
+        });
+    }
+
+    None
+}
+
+/// Directories added to sys.path by `.pth` files in site-packages.
+///
+/// Like `site.py`, files are read in name order, blank lines and comments are
+/// skipped, `import` lines are executable (not paths), and relative paths are
+/// relative to site-packages. Only existing directories are returned.
+fn pth_search_paths(site_packages: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(site_packages) else {
+        return Vec::new();
+    };
+    let mut pth_files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "pth"))
+        .collect();
+    pth_files.sort();
+
+    let mut dirs = Vec::new();
+    for pth in pth_files {
+        let Ok(content) = std::fs::read_to_string(&pth) else {
+            continue;
+        };
+        for line in content.lines() {
+            let line = line.trim_end();
+            if line.is_empty()
+                || line.starts_with('#')
+                || line.starts_with("import ")
+                || line.starts_with("import\t")
+            {
+                continue;
             }
-            // Namespace package (no __init__.py)
-            return Some(ResolvedPackage {
-                path: pkg_dir,
-                name: import_name.to_string(),
-                is_namespace: true,
-            });
-        } else {
-            // Submodule - build path
-            let mut path = pkg_dir.clone();
-            for part in &parts[1..] {
-                path = path.join(part);
+            let dir = site_packages.join(line);
+            if dir.is_dir() && !dirs.contains(&dir) {
+                dirs.push(dir);
             }
+        }
+    }
+    dirs
+}
 
-            // Try as package first
-            if path.is_dir() {
-                let init = path.join("__init__.py");
-                return Some(ResolvedPackage {
-                    path: path.clone(),
-                    name: import_name.to_string(),
-                    is_namespace: !init.is_file(),
-                });
-            }
+/// A top-level name mapped to its source by an editable-install finder.
+struct EditableMapping {
+    name: String,
+    path: PathBuf,
+}
 
-            // Try as module
-            let py_file = path.with_extension("py");
-            if py_file.is_file() {
-                return Some(ResolvedPackage {
-                    path: py_file,
-                    name: import_name.to_string(),
-                    is_namespace: false,
+/// Read the module mappings of editable-install finders in site-packages.
+///
+/// Newer editable installs don't add a source directory to sys.path; the
+/// `.pth` file imports a finder module that maps names to paths instead:
+/// - setuptools: `__editable___<dist>_finder.py` with `MAPPING = {'pkg': '/src/pkg'}`
+/// - editables (hatchling): `_editable_impl_<dist>.py` with `map_module('pkg', '/src/pkg/__init__.py')`
+fn editable_mappings(site_packages: &Path) -> Vec<EditableMapping> {
+    let Ok(entries) = std::fs::read_dir(site_packages) else {
+        return Vec::new();
+    };
+    let mut mappings = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        let is_setuptools =
+            file_name.starts_with("__editable__") && file_name.ends_with("_finder.py");
+        let is_editables = file_name.starts_with("_editable_impl_") && file_name.ends_with(".py");
+        if !is_setuptools && !is_editables {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        for line in content.lines() {
+            let line = line.trim_start();
+            if !line.starts_with("MAPPING") && !line.contains("map_module(") {
+                continue;
+            }
+            // Both forms are sequences of (name, path) string literal pairs.
+            let strings = python_string_literals(line);
+            for pair in strings.chunks_exact(2) {
+                mappings.push(EditableMapping {
+                    name: pair[0].clone(),
+                    path: PathBuf::from(&pair[1]),
                 });
             }
+        }
+    }
+    mappings
+}
 
-            return None;
+/// Extract the single- or double-quoted string literals from a line of Python.
+fn python_string_literals(line: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c != '\'' && c != '"' {
+            continue;
+        }
+        let mut literal = String::new();
+        while let Some(next) = chars.next() {
+            match next {
+                '\\' => literal.extend(chars.next()),
+                _ if next == c => break,
+                _ => literal.push(next),
+            }
         }
+        literals.push(literal);
     }
+    literals
+}
 
-    // Check for single-file module
-    let py_file = site_packages.join(format!("{}.py", top_level));
-    if py_file.is_file() {
+/// Resolve an import through editable-install finder mappings.
+fn resolve_editable_import(
+    import_name: &str,
+    mappings: &[EditableMapping],
+) -> Option<ResolvedPackage> {
+    let top_level = import_name.split('.').next().unwrap_or(import_name);
+    let mapping = mappings.iter().find(|m| m.name == top_level)?;
+
+    if mapping.path.is_dir() {
+        return resolve_in_package_dir(import_name, &mapping.path);
+    }
+    if mapping.path.file_name().is_some_and(|n| n == "__init__.py") {
+        return resolve_in_package_dir(import_name, mapping.path.parent()?);
+    }
+    if mapping.path.is_file() {
         return Some(ResolvedPackage {
-            path: py_file,
+            path: mapping.path.clone(),
             name: import_name.to_string(),
             is_namespace: false,
         });
     }
-
     None
 }
 
@@ -419,6 +767,10 @@ impl Language for Python {
         &["function_definition"]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["lambda"]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &["class_definition"]
     }
@@ -440,7 +792,6 @@ impl Language for Python {
             "if_statement",
             "for_statement",
             "while_statement",
-            "try_statement",
             "except_clause",
             "with_statement",
             "match_statement",
@@ -460,7 +811,7 @@ impl Language for Python {
             "if_statement",
             "for_statement",
             "while_statement",
-            "try_statement",
+            "except_clause",
             "with_statement",
             "match_statement",
             "function_definition",
@@ -614,6 +965,62 @@ impl Language for Python {
         }
     }
 
+    fn parameters(&self, node: &Node, content: &str) -> Vec<Param> {
+        let Some(params) = node.child_by_field_name("parameters") else {
+            return Vec::new();
+        };
+        let text = |n: Node| content[n.byte_range()].to_string();
+        let mut cursor = params.walk();
+        params
+            .named_children(&mut cursor)
+            .filter_map(|p| {
+                let field = |name: &str| p.child_by_field_name(name).map(text);
+                match p.kind() {
+                    "identifier" => Some(Param {
+                        name: text(p),
+                        ty: None,
+                        default: None,
+                        optional: false,
+                    }),
+                    // `*args`, `**kwargs`
+                    "list_splat_pattern" | "dictionary_splat_pattern" => Some(Param {
+                        name: text(p),
+                        ty: None,
+                        default: None,
+                        optional: true,
+                    }),
+                    // `x: int`, `*args: str`; the name is the first child
+                    "typed_parameter" => {
+                        let name = p.named_child(0)?;
+                        Some(Param {
+                            name: text(name),
+                            ty: field("type"),
+                            default: None,
+                            optional: matches!(
+                                name.kind(),
+                                "list_splat_pattern" | "dictionary_splat_pattern"
+                            ),
+                        })
+                    }
+                    // `x=1`, `x: int = 1`
+                    "default_parameter" | "typed_default_parameter" => Some(Param {
+                        name: field("name")?,
+                        ty: field("type"),
+                        default: field("value"),
+                        optional: true,
+                    }),
+                    // `*` and `/` separators, comments
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    fn return_type(&self, node: &Node, content: &str) -> Option<String> {
+        let ty = node.child_by_field_name("return_type")?;
+        Some(content[ty.byte_range()].to_string())
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         let line = node.start_position().row + 1;
 
@@ -929,7 +1336,12 @@ impl Language for Python {
         if init_py.is_file() {
             return Some(init_py);
         }
-        None
+        // Namespace packages (PEP 420) have none: index their top-level modules
+        let has_modules = std::fs::read_dir(path).ok()?.flatten().any(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            entry.path().is_file() && !self.should_skip_package_entry(&name, false)
+        });
+        has_modules.then(|| path.to_path_buf())
     }
 
     fn package_module_name(&self, entry_name: &str) -> String {
@@ -1009,14 +1421,157 @@ impl Language for Python {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arborium::{tree_sitter::Parser, GrammarStore};
+    use crate::test_support::{param, parse};
 
-    fn parse_python(content: &str) -> arborium::tree_sitter::Tree {
-        let store = GrammarStore::new();
-        let grammar = store.get("python").unwrap();
-        let mut parser = Parser::new();
-        parser.set_language(grammar.language()).unwrap();
-        parser.parse(content, None).unwrap()
+    #[test]
+    fn test_resolve_through_pth_and_editable_finder() {
+        let dir = tempfile::tempdir().unwrap();
+        let site = dir.path().join("site-packages");
+        std::fs::create_dir_all(&site).unwrap();
+
+        // Legacy `pip install -e`: a .pth file pointing at the checkout's src/
+        let src = dir.path().join("checkout/src");
+        std::fs::create_dir_all(src.join("mypkg/sub")).unwrap();
+        std::fs::write(src.join("mypkg/__init__.py"), "").unwrap();
+        std::fs::write(src.join("mypkg/sub/mod.py"), "").unwrap();
+        std::fs::write(
+            site.join("mypkg.pth"),
+            "# editable\nimport sys\n../checkout/src\n",
+        )
+        .unwrap();
+
+        let pkg = resolve_python_import("mypkg", &site).unwrap();
+        assert_eq!(pkg.path, site.join("../checkout/src/mypkg"));
+        assert!(!pkg.is_namespace);
+        let sub = resolve_python_import("mypkg.sub.mod", &site).unwrap();
+        assert_eq!(sub.path, site.join("../checkout/src/mypkg/sub/mod.py"));
+
+        // setuptools >= 64: a finder module mapping names to source paths.
+        // Adding it changes the site-packages mtime, so the cached extras are re-read.
+        let other = dir.path().join("other/lib/otherpkg");
+        std::fs::create_dir_all(&other).unwrap();
+        std::fs::write(other.join("__init__.py"), "").unwrap();
+        std::fs::write(
+            site.join("__editable___otherpkg_1_0_finder.py"),
+            format!(
+                "import sys\nMAPPING: dict[str, str] = {{'otherpkg': '{}'}}\n",
+                other.display()
+            ),
+        )
+        .unwrap();
+
+        let pkg = resolve_python_import("otherpkg", &site).unwrap();
+        assert_eq!(pkg.path, other);
+        assert!(resolve_python_import("missing", &site).is_none());
+    }
+
+    #[test]
+    fn test_venv_python_prefers_scripts() {
+        let venv = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(venv.path().join("bin")).unwrap();
+        std::fs::write(venv.path().join("bin/python"), "").unwrap();
+        assert_eq!(
+            venv_python(venv.path()),
+            Some(venv.path().join("bin").join("python"))
+        );
+
+        std::fs::create_dir_all(venv.path().join("Scripts")).unwrap();
+        std::fs::write(venv.path().join("Scripts/python.exe"), "").unwrap();
+        assert_eq!(
+            venv_python(venv.path()),
+            Some(venv.path().join("Scripts").join("python.exe"))
+        );
+    }
+
+    #[test]
+    fn test_windows_venv_layout() {
+        let base = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(base.path().join("Lib/site-packages")).unwrap();
+        std::fs::create_dir_all(base.path().join("DLLs")).unwrap();
+        std::fs::write(base.path().join("Lib/os.py"), "").unwrap();
+        std::fs::write(base.path().join("python.exe"), "").unwrap();
+        std::fs::write(base.path().join("python312.dll"), "").unwrap();
+
+        let project = tempfile::tempdir().unwrap();
+        let venv = project.path().join(".venv");
+        std::fs::create_dir_all(venv.join("Scripts")).unwrap();
+        std::fs::create_dir_all(venv.join("Lib/site-packages")).unwrap();
+        std::fs::write(venv.join("Scripts/python.exe"), "").unwrap();
+        std::fs::write(
+            venv.join("pyvenv.cfg"),
+            format!(
+                "home = {}\ninclude-system-site-packages = false\n",
+                base.path().display()
+            ),
+        )
+        .unwrap();
+
+        let cache = PythonPathCache::new(project.path(), None);
+        assert_eq!(cache.version.as_deref(), Some("3.12"));
+        assert_eq!(cache.stdlib, Some(base.path().join("Lib")));
+        let site = cache.site_packages.unwrap();
+        assert!(site.ends_with(".venv/Lib/site-packages"));
+
+        std::fs::write(
+            venv.join("pyvenv.cfg"),
+            "home = nowhere\nversion = 3.11.4\n",
+        )
+        .unwrap();
+        assert!(windows_prefix(&venv.join("Scripts/python.exe")).is_none());
+        assert_eq!(
+            pyvenv_cfg_value(&venv, "version").and_then(|v| major_minor(&v)),
+            Some("3.11".to_string())
+        );
+    }
+
+    #[test]
+    fn test_site_packages_from_virtual_env() {
+        let env = tempfile::tempdir().unwrap();
+        let site = env.path().join("lib/python3.12/site-packages");
+        std::fs::create_dir_all(&site).unwrap();
+        let project = tempfile::tempdir().unwrap();
+
+        let cache = PythonPathCache::new(project.path(), Some(env.path()));
+        assert_eq!(cache.site_packages, Some(site));
+    }
+
+    #[test]
+    fn test_conda_env_version_and_stdlib() {
+        // Conda prefixes hold a real interpreter and their own stdlib
+        let env = tempfile::tempdir().unwrap();
+        let stdlib = env.path().canonicalize().unwrap().join("lib/python3.11");
+        std::fs::create_dir_all(stdlib.join("site-packages")).unwrap();
+        std::fs::create_dir_all(env.path().join("bin")).unwrap();
+        std::fs::write(env.path().join("bin/python"), "").unwrap();
+        let project = tempfile::tempdir().unwrap();
+
+        let cache = PythonPathCache::new(project.path(), Some(env.path()));
+        assert_eq!(cache.version.as_deref(), Some("3.11"));
+        assert_eq!(cache.stdlib, Some(stdlib.clone()));
+        assert_eq!(cache.site_packages, Some(stdlib.join("site-packages")));
+    }
+
+    #[test]
+    fn test_find_pyenv_install() {
+        let pyenv = tempfile::tempdir().unwrap();
+        for version in ["3.9.18", "3.11.2", "3.11.9"] {
+            let minor = major_minor(version).unwrap();
+            let stdlib = pyenv
+                .path()
+                .join("versions")
+                .join(version)
+                .join("lib")
+                .join(format!("python{}", minor));
+            std::fs::create_dir_all(stdlib.join("site-packages")).unwrap();
+            std::fs::write(stdlib.join("os.py"), "").unwrap();
+        }
+
+        let install = find_pyenv_install(pyenv.path(), "3.11").unwrap();
+        let stdlib = pyenv.path().join("versions/3.11.9/lib/python3.11");
+        assert_eq!(install.site_packages, Some(stdlib.join("site-packages")));
+        assert_eq!(install.stdlib, stdlib);
+        assert!(find_pyenv_install(pyenv.path(), "3.9").is_some());
+        assert!(find_pyenv_install(pyenv.path(), "3.12").is_none());
     }
 
     #[test]
@@ -1033,7 +1588,7 @@ mod tests {
     """Convert to string."""
     return str(x)
 "#;
-        let tree = parse_python(content);
+        let tree = parse("python", content);
         let root = tree.root_node();
 
         // Find function node
@@ -1050,6 +1605,32 @@ mod tests {
         assert_eq!(sym.docstring, Some("Convert to string.".to_string()));
     }
 
+    #[test]
+    fn test_python_parameters() {
+        let support = Python;
+        let content =
+            "def f(self, a, b: int, c=1, d: str = \"x\", *args: str, **kw) -> list[int]:\n    pass\n";
+        let tree = parse("python", content);
+        let func = tree.root_node().child(0).unwrap();
+        let params = support.parameters(&func, content);
+        assert_eq!(
+            params,
+            vec![
+                param("self", None, None, false),
+                param("a", None, None, false),
+                param("b", Some("int"), None, false),
+                param("c", None, Some("1"), true),
+                param("d", Some("str"), Some("\"x\""), true),
+                param("*args", Some("str"), None, true),
+                param("**kw", None, None, true),
+            ]
+        );
+        assert_eq!(
+            support.return_type(&func, content),
+            Some("list[int]".to_string())
+        );
+    }
+
     #[test]
     fn test_python_extract_class() {
         let support = Python;
@@ -1057,7 +1638,7 @@ mod tests {
     """A foo class."""
     pass
 "#;
-        let tree = parse_python(content);
+        let tree = parse("python", content);
         let root = tree.root_node();
 
         let mut cursor = root.walk();
@@ -1081,7 +1662,7 @@ def _protected(): pass
 def __private(): pass
 def __dunder__(): pass
 "#;
-        let tree = parse_python(content);
+        let tree = parse("python", content);
         let root = tree.root_node();
 
         let mut cursor = root.walk();
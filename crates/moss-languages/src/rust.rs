@@ -1,7 +1,7 @@
 //! Rust language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -68,6 +68,8 @@ pub fn find_cargo_registry() -> Option<PathBuf> {
 
 /// Resolve a Rust crate import to its source location.
 fn resolve_rust_crate(crate_name: &str, registry: &Path) -> Option<ResolvedPackage> {
+    // `use tokio_util` names the package tokio-util
+    let wanted = crate_name.replace('-', "_");
     // Registry structure: registry/src/index.crates.io-*/crate-version/
     if let Ok(indices) = std::fs::read_dir(registry) {
         for index_entry in indices.flatten() {
@@ -82,7 +84,8 @@ fn resolve_rust_crate(crate_name: &str, registry: &Path) -> Option<ResolvedPacka
                     let dir_name = crate_entry.file_name().to_string_lossy().to_string();
 
                     // Check if this is our crate (name-version pattern)
-                    if dir_name.starts_with(&format!("{}-", crate_name)) {
+                    let package = registry_package_name(&dir_name);
+                    if package.is_some_and(|p| p.replace('-', "_") == wanted) {
                         let lib_rs = crate_dir.join("src").join("lib.rs");
                         if lib_rs.is_file() {
                             return Some(ResolvedPackage {
@@ -100,6 +103,15 @@ fn resolve_rust_crate(crate_name: &str, registry: &Path) -> Option<ResolvedPacka
     None
 }
 
+/// Package name of a registry directory: `tokio-util-0.7.10` -> `tokio-util`.
+/// The version starts at the first `-` followed by a digit.
+fn registry_package_name(dir_name: &str) -> Option<&str> {
+    dir_name
+        .match_indices('-')
+        .find(|(i, _)| dir_name[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+        .map(|(i, _)| &dir_name[..i])
+}
+
 // ============================================================================
 // Rust language support
 // ============================================================================
@@ -130,6 +142,10 @@ impl Language for Rust {
         &["function_item"]
     }
 
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["closure_expression"]
+    }
+
     fn type_kinds(&self) -> &'static [&'static str] {
         &["struct_item", "enum_item", "type_item", "trait_item"]
     }
@@ -330,6 +346,38 @@ impl Language for Rust {
         }
     }
 
+    fn parameters(&self, node: &Node, content: &str) -> Vec<Param> {
+        let Some(params) = node.child_by_field_name("parameters") else {
+            return Vec::new();
+        };
+        let text = |n: Node| content[n.byte_range()].to_string();
+        let mut cursor = params.walk();
+        params
+            .named_children(&mut cursor)
+            .filter_map(|p| match p.kind() {
+                "parameter" => Some(Param {
+                    name: text(p.child_by_field_name("pattern")?),
+                    ty: p.child_by_field_name("type").map(text),
+                    default: None,
+                    optional: false,
+                }),
+                // `self`, `&self`, `&mut self`, `self: Box<Self>`
+                "self_parameter" => Some(Param {
+                    name: "self".to_string(),
+                    ty: Some(text(p)).filter(|t| t != "self"),
+                    default: None,
+                    optional: false,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn return_type(&self, node: &Node, content: &str) -> Option<String> {
+        let ty = node.child_by_field_name("return_type")?;
+        Some(content[ty.byte_range()].to_string())
+    }
+
     fn extract_imports(&self, node: &Node, content: &str) -> Vec<Import> {
         if node.kind() != "use_declaration" {
             return Vec::new();
@@ -551,6 +599,13 @@ impl Language for Rust {
             if mod_file.exists() {
                 return Some(mod_file);
             }
+        } else {
+            // other_crate::foo -> a workspace member's src/foo.rs, or its lib.rs
+            let (crate_name, rest) = module.split_once("::").unwrap_or((module, ""));
+            let member = workspace_crates(&crate_root, project_root)
+                .into_iter()
+                .find(|c| c.name == crate_name)?;
+            return resolve_in_crate(&member.dir.join("src"), rest);
         }
 
         None
@@ -708,6 +763,99 @@ fn find_crate_root(start: &Path, root: &Path) -> Option<PathBuf> {
     None
 }
 
+/// A package in the enclosing Cargo workspace, by the name `use` refers to it.
+struct WorkspaceCrate {
+    /// Package name with `-` as `_`
+    name: String,
+    dir: PathBuf,
+}
+
+/// Members of the workspace containing `crate_root`: the nearest manifest
+/// with a `[workspace]` table, at or above the crate but within `root`.
+fn workspace_crates(crate_root: &Path, root: &Path) -> Vec<WorkspaceCrate> {
+    let mut current = crate_root;
+    let (workspace_root, manifest) = loop {
+        if let Some(manifest) = read_manifest(&current.join("Cargo.toml")) {
+            if manifest.contains_key("workspace") {
+                break (current, manifest);
+            }
+        }
+        match current.parent() {
+            Some(parent) if parent.starts_with(root) => current = parent,
+            _ => return Vec::new(),
+        }
+    };
+
+    let mut crates = Vec::new();
+    for member in workspace_members(&manifest) {
+        // Only trailing `*` globs (`crates/*`), the common case
+        let dirs = match member.strip_suffix("/*") {
+            Some(parent) => std::fs::read_dir(workspace_root.join(parent))
+                .map(|entries| entries.flatten().map(|e| e.path()).collect())
+                .unwrap_or_default(),
+            None => vec![workspace_root.join(&member)],
+        };
+        for dir in dirs {
+            let name = read_manifest(&dir.join("Cargo.toml")).and_then(|m| package_name(&m));
+            if let Some(name) = name {
+                crates.push(WorkspaceCrate {
+                    name: name.replace('-', "_"),
+                    dir,
+                });
+            }
+        }
+    }
+    crates
+}
+
+/// A parsed Cargo.toml; None if it's missing or invalid.
+fn read_manifest(path: &Path) -> Option<toml::Table> {
+    std::fs::read_to_string(path).ok()?.parse().ok()
+}
+
+/// Paths in `[workspace] members`.
+fn workspace_members(manifest: &toml::Table) -> Vec<String> {
+    manifest
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .map(|members| {
+            members
+                .iter()
+                .filter_map(|m| m.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `name` from a manifest's `[package]` table.
+fn package_name(manifest: &toml::Table) -> Option<String> {
+    manifest
+        .get("package")?
+        .get("name")?
+        .as_str()
+        .map(String::from)
+}
+
+/// The file defining `path` (`foo::bar::Item`) under a crate's `src`: the
+/// deepest module that exists, falling back to the crate root.
+fn resolve_in_crate(src_dir: &Path, path: &str) -> Option<PathBuf> {
+    let segments: Vec<&str> = path.split("::").filter(|s| !s.is_empty()).collect();
+    for len in (1..=segments.len()).rev() {
+        let module = segments[..len].join("/");
+        let direct = src_dir.join(format!("{}.rs", module));
+        if direct.exists() {
+            return Some(direct);
+        }
+        let mod_file = src_dir.join(&module).join("mod.rs");
+        if mod_file.exists() {
+            return Some(mod_file);
+        }
+    }
+    let lib = src_dir.join("lib.rs");
+    lib.exists().then_some(lib)
+}
+
 impl Rust {
     fn extract_visibility_prefix(&self, node: &Node, content: &str) -> String {
         let mut cursor = node.walk();
@@ -723,8 +871,88 @@ impl Rust {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{param, parse};
     use crate::validate_unused_kinds_audit;
 
+    #[test]
+    fn test_rust_parameters() {
+        let content = "fn f(&mut self, name: &str, (a, b): (u8, u8)) -> Result<(), Error> {}\n";
+        let tree = parse("rust", content);
+        let func = tree.root_node().child(0).unwrap();
+        let params = Rust.parameters(&func, content);
+        assert_eq!(
+            params,
+            vec![
+                param("self", Some("&mut self"), None, false),
+                param("name", Some("&str"), None, false),
+                param("(a, b)", Some("(u8, u8)"), None, false),
+            ]
+        );
+        assert_eq!(
+            Rust.return_type(&func, content),
+            Some("Result<(), Error>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_workspace_member() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "Cargo.toml",
+            "[workspace] # all crates\nmembers = [\n    'crates/*',\n]\nresolver = \"2\"\n",
+        );
+        write(
+            "crates/app/Cargo.toml",
+            "[package]\nname = \"app\"\n\n[dependencies]\nmy-util = { path = \"../util\" }\n",
+        );
+        write("crates/app/src/main.rs", "use my_util::fmt::pad;\n");
+        write(
+            "crates/util/Cargo.toml",
+            "[package]\nname = \"my-util\" # published name\n",
+        );
+        write(
+            "crates/util/src/lib.rs",
+            "pub mod fmt;\npub fn helper() {}\n",
+        );
+        write("crates/util/src/fmt.rs", "pub fn pad() {}\n");
+
+        let main = root.join("crates/app/src/main.rs");
+        let resolve = |module: &str| Rust.resolve_local_import(module, &main, root);
+        assert_eq!(
+            resolve("my_util::fmt::pad"),
+            Some(root.join("crates/util/src/fmt.rs"))
+        );
+        assert_eq!(
+            resolve("my_util::helper"),
+            Some(root.join("crates/util/src/lib.rs"))
+        );
+        assert_eq!(resolve("serde::Serialize"), None);
+    }
+
+    #[test]
+    fn test_resolve_crate_hyphenated_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = dir.path().join("index.crates.io-6f17d22bba15001f");
+        for package in ["tokio-util-0.7.10", "serde_json-1.0.108"] {
+            let src = index.join(package).join("src");
+            std::fs::create_dir_all(&src).unwrap();
+            std::fs::write(src.join("lib.rs"), "").unwrap();
+        }
+
+        let resolved = resolve_rust_crate("tokio_util", dir.path()).unwrap();
+        assert_eq!(resolved.path, index.join("tokio-util-0.7.10/src/lib.rs"));
+        assert!(resolve_rust_crate("serde_json", dir.path()).is_some());
+        // tokio-util isn't a version of tokio
+        assert!(resolve_rust_crate("tokio", dir.path()).is_none());
+        assert_eq!(registry_package_name("foo-1.0.0-beta.1"), Some("foo"));
+    }
+
     /// Documents node kinds that exist in the Rust grammar but aren't used in trait methods.
     /// Run `cross_check_node_kinds` in registry.rs to see all potentially useful kinds.
     #[test]
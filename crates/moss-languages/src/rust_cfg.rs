@@ -0,0 +1,301 @@
+//! Rust conditional compilation: `#[cfg(...)]` predicates on items and the
+//! set of cfg options they're evaluated against.
+//!
+//! Kept outside the `lang-rust` feature so analysis can filter on recorded
+//! predicates without a Rust grammar.
+
+use arborium::tree_sitter::{Node, Point};
+use std::collections::HashSet;
+
+/// Active cfg options, as for one `cargo build`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CfgSet {
+    /// Bare options such as `unix` or `test`
+    names: HashSet<String>,
+    /// Key-value options such as `target_os = "linux"`
+    values: HashSet<(String, String)>,
+    /// Treat every `feature = "..."` as enabled, like `--all-features`
+    all_features: bool,
+}
+
+impl CfgSet {
+    /// The host target's options with every feature enabled and `test` off.
+    pub fn host() -> Self {
+        let mut set = Self {
+            names: HashSet::from([
+                std::env::consts::FAMILY.to_string(),
+                "debug_assertions".to_string(),
+            ]),
+            values: HashSet::new(),
+            all_features: true,
+        };
+        for (key, value) in [
+            ("target_os", std::env::consts::OS),
+            ("target_family", std::env::consts::FAMILY),
+            ("target_arch", std::env::consts::ARCH),
+            (
+                "target_pointer_width",
+                if cfg!(target_pointer_width = "64") {
+                    "64"
+                } else {
+                    "32"
+                },
+            ),
+            (
+                "target_endian",
+                if cfg!(target_endian = "little") {
+                    "little"
+                } else {
+                    "big"
+                },
+            ),
+        ] {
+            set.values.insert((key.to_string(), value.to_string()));
+        }
+        set
+    }
+
+    /// The host target's options plus `entries` such as `test` or
+    /// `feature = "llm"`. Listing any feature turns off `all_features`.
+    pub fn with_options(entries: &[String]) -> Self {
+        let mut set = Self::host();
+        for entry in entries {
+            match entry.split_once('=') {
+                Some((key, value)) => {
+                    let key = key.trim();
+                    if key == "feature" {
+                        set.all_features = false;
+                    }
+                    let value = value.trim().trim_matches('"');
+                    set.values.insert((key.to_string(), value.to_string()));
+                }
+                None => {
+                    set.names.insert(entry.trim().to_string());
+                }
+            }
+        }
+        set
+    }
+
+    /// Whether a cfg predicate (the inside of `#[cfg(...)]`) holds.
+    /// Malformed predicates hold, so the item isn't filtered out.
+    pub fn eval(&self, predicate: &str) -> bool {
+        let mut parser = CfgParser {
+            input: predicate.as_bytes(),
+            pos: 0,
+            set: self,
+        };
+        match parser.predicate() {
+            Some(value) => {
+                parser.skip_whitespace();
+                value || parser.pos != parser.input.len()
+            }
+            None => true,
+        }
+    }
+
+    fn has(&self, key: &str, value: Option<&str>) -> bool {
+        match value {
+            None => self.names.contains(key),
+            Some(_) if key == "feature" && self.all_features => true,
+            Some(v) => self.values.contains(&(key.to_string(), v.to_string())),
+        }
+    }
+}
+
+/// Recursive descent over `name`, `key = "value"`, `all(..)`, `any(..)` and `not(..)`.
+struct CfgParser<'a> {
+    input: &'a [u8],
+    pos: usize,
+    set: &'a CfgSet,
+}
+
+impl CfgParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        if self.input.get(self.pos) == Some(&byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn ident(&mut self) -> Option<&str> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'_')
+        {
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| std::str::from_utf8(&self.input[start..self.pos]).unwrap())
+    }
+
+    fn string(&mut self) -> Option<String> {
+        if !self.eat(b'"') {
+            return None;
+        }
+        let start = self.pos;
+        while self.input.get(self.pos).is_some_and(|b| *b != b'"') {
+            self.pos += 1;
+        }
+        let value = String::from_utf8_lossy(&self.input[start..self.pos]).to_string();
+        self.eat(b'"').then_some(value)
+    }
+
+    fn predicate(&mut self) -> Option<bool> {
+        let name = self.ident()?.to_string();
+        match name.as_str() {
+            "all" | "any" | "not" => {
+                if !self.eat(b'(') {
+                    return None;
+                }
+                let mut args = Vec::new();
+                while !self.eat(b')') {
+                    args.push(self.predicate()?);
+                    if !self.eat(b',') {
+                        if !self.eat(b')') {
+                            return None;
+                        }
+                        break;
+                    }
+                }
+                match name.as_str() {
+                    "all" => Some(args.iter().all(|v| *v)),
+                    "any" => Some(args.iter().any(|v| *v)),
+                    _ => (args.len() == 1).then(|| !args[0]),
+                }
+            }
+            _ if self.eat(b'=') => {
+                let value = self.string()?;
+                Some(self.set.has(&name, Some(&value)))
+            }
+            _ => Some(self.set.has(&name, None)),
+        }
+    }
+}
+
+/// The predicate of a `#[cfg(...)]` attribute, or None for other attributes.
+fn attribute_cfg(attribute: &str) -> Option<&str> {
+    let inner = attribute
+        .trim()
+        .strip_prefix("#[")?
+        .strip_suffix(']')?
+        .trim();
+    let args = inner.strip_prefix("cfg")?.trim_start();
+    Some(args.strip_prefix('(')?.strip_suffix(')')?.trim())
+}
+
+/// The combined cfg predicate guarding a Rust item: its own `#[cfg]`
+/// attributes and those of enclosing items such as `#[cfg(test)] mod tests`.
+pub fn item_cfg(node: &Node, content: &str) -> Option<String> {
+    let mut predicates = Vec::new();
+    let mut current = Some(*node);
+    while let Some(item) = current {
+        // Attributes are either an `attributes` child or preceding siblings,
+        // depending on the grammar version
+        if let Some(attributes) = item.child_by_field_name("attributes") {
+            let mut cursor = attributes.walk();
+            let own: Vec<String> = attributes
+                .children(&mut cursor)
+                .filter_map(|a| attribute_cfg(&content[a.byte_range()]))
+                .map(str::to_string)
+                .collect();
+            predicates.extend(own.into_iter().rev());
+        }
+        let mut sibling = item.prev_sibling();
+        while let Some(s) = sibling {
+            match s.kind() {
+                "attribute_item" => {
+                    if let Some(p) = attribute_cfg(&content[s.byte_range()]) {
+                        predicates.push(p.to_string());
+                    }
+                }
+                "line_comment" | "block_comment" => {}
+                _ => break,
+            }
+            sibling = s.prev_sibling();
+        }
+        current = item.parent();
+    }
+    match predicates.len() {
+        0 => None,
+        1 => predicates.pop(),
+        _ => {
+            predicates.reverse();
+            Some(format!("all({})", predicates.join(", ")))
+        }
+    }
+}
+
+/// The Rust item starting on 1-based `line`, skipping over its attributes
+/// if the line holds those instead.
+pub fn item_at_line<'a>(root: &Node<'a>, content: &str, line: usize) -> Option<Node<'a>> {
+    let text = content.lines().nth(line.checked_sub(1)?)?;
+    let column = text.len() - text.trim_start().len();
+    let point = Point {
+        row: line - 1,
+        column,
+    };
+    let mut node = root.named_descendant_for_point_range(point, point)?;
+    while let Some(parent) = node.parent() {
+        if parent.start_position().row != line - 1 || parent.parent().is_none() {
+            break;
+        }
+        node = parent;
+    }
+    while matches!(
+        node.kind(),
+        "attribute_item" | "line_comment" | "block_comment"
+    ) {
+        node = node.next_named_sibling()?;
+    }
+    Some(node)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_cfg() {
+        let set = CfgSet::with_options(&["feature = \"llm\"".to_string()]);
+        assert!(!set.eval("test"));
+        assert!(set.eval("feature = \"llm\""));
+        assert!(!set.eval("feature = \"mcp\""));
+        assert!(set.eval("not(test)"));
+        assert!(set.eval("any(test, debug_assertions)"));
+        assert!(!set.eval("all(feature = \"llm\", test)"));
+        assert!(set.eval(&format!("target_os = \"{}\"", std::env::consts::OS)));
+        // Malformed predicates keep the item
+        assert!(set.eval("all(test"));
+
+        let host = CfgSet::host();
+        assert!(host.eval("feature = \"anything\""));
+        assert!(CfgSet::with_options(&["test".to_string()]).eval("test"));
+    }
+
+    #[test]
+    fn test_attribute_cfg() {
+        assert_eq!(attribute_cfg("#[cfg(test)]"), Some("test"));
+        assert_eq!(
+            attribute_cfg("#[cfg(all(unix, feature = \"x\"))]"),
+            Some("all(unix, feature = \"x\")")
+        );
+        assert_eq!(attribute_cfg("#[cfg_attr(test, derive(Debug))]"), None);
+        assert_eq!(attribute_cfg("#[derive(Debug)]"), None);
+    }
+}
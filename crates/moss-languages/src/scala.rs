@@ -1,7 +1,7 @@
 //! Scala language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -29,6 +29,9 @@ impl Language for Scala {
     fn function_kinds(&self) -> &'static [&'static str] {
         &["function_definition"]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &["lambda_expression"]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &["class_definition", "trait_definition"]
     }
@@ -173,6 +176,12 @@ impl Language for Scala {
     fn extract_docstring(&self, _node: &Node, _content: &str) -> Option<String> {
         None
     }
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         Vec::new()
     }
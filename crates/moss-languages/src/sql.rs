@@ -1,7 +1,7 @@
 //! SQL language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -24,11 +24,22 @@ impl Language for Sql {
     }
 
     fn container_kinds(&self) -> &'static [&'static str] {
-        &["create_table", "create_view", "create_schema"]
+        &[
+            "create_table",
+            "create_view",
+            "create_materialized_view",
+            "create_schema",
+            "create_index",
+        ]
     }
 
     fn function_kinds(&self) -> &'static [&'static str] {
-        &["create_function"]
+        // The grammar has no CREATE PROCEDURE; those statements parse as errors
+        &["create_function", "ERROR"]
+    }
+
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
     }
 
     fn type_kinds(&self) -> &'static [&'static str] {
@@ -40,7 +51,13 @@ impl Language for Sql {
     }
 
     fn public_symbol_kinds(&self) -> &'static [&'static str] {
-        &["create_table", "create_view", "create_function"]
+        &[
+            "create_table",
+            "create_view",
+            "create_materialized_view",
+            "create_function",
+            "create_index",
+        ]
     }
 
     fn visibility_mechanism(&self) -> VisibilityMechanism {
@@ -86,6 +103,9 @@ impl Language for Sql {
     }
 
     fn extract_function(&self, node: &Node, content: &str, _in_container: bool) -> Option<Symbol> {
+        if node.kind() == "ERROR" {
+            return self.extract_procedure(node, content);
+        }
         let name = self.extract_sql_name(node, content)?;
 
         // Extract first line as signature
@@ -106,31 +126,48 @@ impl Language for Sql {
 
     fn extract_container(&self, node: &Node, content: &str) -> Option<Symbol> {
         let name = self.extract_sql_name(node, content)?;
-        let (kind, keyword) = match node.kind() {
-            "create_view" | "create_materialized_view" => (SymbolKind::Struct, "VIEW"),
-            "create_schema" => (SymbolKind::Module, "SCHEMA"),
-            _ => (SymbolKind::Struct, "TABLE"),
+        // Signatures keep the schema (public.users); the name doesn't, so a
+        // search for the table finds it
+        let qualified =
+            Self::object_reference(node).map_or(name.as_str(), |r| &content[r.byte_range()]);
+        let (kind, signature) = match node.kind() {
+            "create_view" => (SymbolKind::Struct, format!("CREATE VIEW {}", qualified)),
+            "create_materialized_view" => (
+                SymbolKind::Struct,
+                format!("CREATE MATERIALIZED VIEW {}", qualified),
+            ),
+            "create_schema" => (SymbolKind::Module, format!("CREATE SCHEMA {}", name)),
+            // Index definitions are short; the statement itself is the signature
+            "create_index" => {
+                let text = &content[node.byte_range()];
+                let first_line = text.lines().next().unwrap_or(text);
+                (SymbolKind::Variable, first_line.trim().to_string())
+            }
+            _ => (SymbolKind::Struct, format!("CREATE TABLE {}", qualified)),
         };
 
         Some(Symbol {
-            name: name.clone(),
+            name,
             kind,
-            signature: format!("CREATE {} {}", keyword, name),
+            signature,
             docstring: self.extract_docstring(node, content),
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
             visibility: Visibility::Public,
-            children: Vec::new(),
+            children: self.extract_columns(node, content),
         })
     }
 
     fn extract_type(&self, node: &Node, content: &str) -> Option<Symbol> {
         let name = self.extract_sql_name(node, content)?;
 
+        let qualified =
+            Self::object_reference(node).map_or(name.as_str(), |r| &content[r.byte_range()]);
+
         Some(Symbol {
-            name: name.clone(),
             kind: SymbolKind::Type,
-            signature: format!("CREATE TYPE {}", name),
+            signature: format!("CREATE TYPE {}", qualified),
+            name,
             docstring: None,
             start_line: node.start_position().row + 1,
             end_line: node.end_position().row + 1,
@@ -140,7 +177,11 @@ impl Language for Sql {
     }
 
     fn extract_docstring(&self, node: &Node, content: &str) -> Option<String> {
-        // SQL uses -- for comments
+        // SQL uses -- for comments, which precede the enclosing statement
+        let node = match node.parent() {
+            Some(parent) if parent.kind() == "statement" => parent,
+            _ => *node,
+        };
         let mut prev = node.prev_sibling();
         let mut doc_lines = Vec::new();
 
@@ -163,6 +204,14 @@ impl Language for Sql {
         Some(doc_lines.join(" "))
     }
 
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
+
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         Vec::new()
     }
@@ -268,24 +317,131 @@ impl Language for Sql {
 }
 
 impl Sql {
+    /// The object a CREATE statement names (`public.users`), or the table an
+    /// index is on.
+    fn object_reference<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+        node.children(&mut node.walk())
+            .find(|c| c.kind() == "object_reference")
+    }
+
     fn extract_sql_name(&self, node: &Node, content: &str) -> Option<String> {
-        // Look for identifier after CREATE TABLE/VIEW/FUNCTION etc.
+        let reference = Self::object_reference(node);
+        let unqualified = |r: Node| {
+            let name = r.child_by_field_name("name").unwrap_or(r);
+            content[name.byte_range()].to_string()
+        };
+        if node.kind() == "create_index" {
+            if let Some(name) = node.child_by_field_name("column") {
+                return Some(content[name.byte_range()].to_string());
+            }
+            // Unnamed indexes get PostgreSQL's default name, table_columns_idx
+            let mut parts = vec![unqualified(reference?)];
+            let fields = node
+                .children(&mut node.walk())
+                .find(|c| c.kind() == "index_fields");
+            if let Some(fields) = fields {
+                parts.extend(
+                    fields
+                        .named_children(&mut fields.walk())
+                        .filter_map(|f| f.child_by_field_name("column"))
+                        .map(|c| content[c.byte_range()].to_string()),
+                );
+            }
+            parts.push("idx".to_string());
+            return Some(parts.join("_"));
+        }
+        if let Some(reference) = reference {
+            return Some(unqualified(reference));
+        }
+        // CREATE SCHEMA names an identifier directly
         let mut cursor = node.walk();
         let mut found_create = false;
         for child in node.children(&mut cursor) {
-            if child.kind() == "keyword" {
-                let text = &content[child.byte_range()].to_uppercase();
-                if text == "CREATE" {
-                    found_create = true;
-                }
+            if child.kind() == "keyword_create" {
+                found_create = true;
             }
-            if found_create && (child.kind() == "identifier" || child.kind() == "object_reference")
-            {
+            if found_create && child.kind() == "identifier" {
                 return Some(content[child.byte_range()].to_string());
             }
         }
         None
     }
+
+    /// A `CREATE [OR REPLACE] PROCEDURE` statement, recovered from the error
+    /// node it parses as. The body is the dollar-quoted string the error ends on.
+    fn extract_procedure(&self, node: &Node, content: &str) -> Option<Symbol> {
+        let text = &content[node.byte_range()];
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let keyword = words
+            .iter()
+            .position(|w| w.eq_ignore_ascii_case("procedure"))?;
+        let head = &words[..keyword];
+        let is_create = head.first()?.eq_ignore_ascii_case("create")
+            && head[1..]
+                .iter()
+                .all(|w| w.eq_ignore_ascii_case("or") || w.eq_ignore_ascii_case("replace"));
+        if !is_create {
+            return None;
+        }
+        let qualified = words.get(keyword + 1)?.split('(').next()?;
+        let name = qualified.rsplit('.').next()?;
+        if name.is_empty() {
+            return None;
+        }
+
+        // The body runs to the closing tag of the dollar quote the error ends on
+        let mut end = node.end_byte();
+        let last = node.named_child(node.named_child_count().checked_sub(1)?)?;
+        if last.kind() == "dollar_quote" {
+            let tag = &content[last.byte_range()];
+            if let Some(close) = content[end..].find(tag) {
+                end += close + tag.len();
+            }
+        }
+        let end_line = content[..end].matches('\n').count() + 1;
+
+        let first_line = text.lines().next().unwrap_or(text);
+        Some(Symbol {
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            signature: first_line.trim().to_string(),
+            docstring: self.extract_docstring(node, content),
+            start_line: node.start_position().row + 1,
+            end_line,
+            visibility: Visibility::Public,
+            children: Vec::new(),
+        })
+    }
+
+    /// Columns of a CREATE TABLE, as children of the table symbol.
+    fn extract_columns(&self, node: &Node, content: &str) -> Vec<Symbol> {
+        let mut cursor = node.walk();
+        let Some(definitions) = node
+            .children(&mut cursor)
+            .find(|c| c.kind() == "column_definitions")
+        else {
+            return Vec::new();
+        };
+
+        let mut cursor = definitions.walk();
+        definitions
+            .children(&mut cursor)
+            .filter(|c| c.kind() == "column_definition")
+            .filter_map(|column| {
+                let name = column.child(0).filter(|n| n.kind() == "identifier")?;
+                Some(Symbol {
+                    name: content[name.byte_range()].to_string(),
+                    kind: SymbolKind::Variable,
+                    signature: content[column.byte_range()].trim().to_string(),
+                    docstring: None,
+                    start_line: column.start_position().row + 1,
+                    end_line: column.end_position().row + 1,
+                    visibility: Visibility::Public,
+                    children: Vec::new(),
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,25 @@
+//! Helpers shared by the language modules' tests.
+
+use crate::Param;
+use arborium::tree_sitter::{Parser, Tree};
+use arborium::GrammarStore;
+
+/// Parse `content` with the named arborium grammar.
+pub fn parse(grammar: &str, content: &str) -> Tree {
+    let store = GrammarStore::new();
+    let mut parser = Parser::new();
+    parser
+        .set_language(store.get(grammar).unwrap().language())
+        .unwrap();
+    parser.parse(content, None).unwrap()
+}
+
+/// An expected parameter.
+pub fn param(name: &str, ty: Option<&str>, default: Option<&str>, optional: bool) -> Param {
+    Param {
+        name: name.to_string(),
+        ty: ty.map(str::to_string),
+        default: default.map(str::to_string),
+        optional,
+    }
+}
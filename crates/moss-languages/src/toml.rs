@@ -1,7 +1,7 @@
 //! TOML language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -20,16 +20,20 @@ impl Language for Toml {
     }
 
     fn has_symbols(&self) -> bool {
-        false
+        true
     }
 
-    // TOML is config, not code - no functions/types/control flow
+    // TOML is config, not code - no functions/types/control flow. Tables are
+    // containers, with their keys as children; top-level keys stand alone.
     fn container_kinds(&self) -> &'static [&'static str] {
-        &["table", "table_array_element"]
+        &["table", "table_array_element", "pair"]
     }
     fn function_kinds(&self) -> &'static [&'static str] {
         &[]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -65,26 +69,50 @@ impl Language for Toml {
     }
 
     fn extract_container(&self, node: &Node, content: &str) -> Option<Symbol> {
-        let mut cursor = node.walk();
-        for child in node.children(&mut cursor) {
-            if child.kind() == "bare_key"
-                || child.kind() == "dotted_key"
-                || child.kind() == "quoted_key"
-            {
-                let name = content[child.byte_range()].to_string();
-                return Some(Symbol {
-                    name: name.clone(),
-                    kind: SymbolKind::Module,
-                    signature: format!("[{}]", name),
-                    docstring: None,
-                    start_line: node.start_position().row + 1,
-                    end_line: node.end_position().row + 1,
-                    visibility: Visibility::Public,
-                    children: Vec::new(),
-                });
+        let key = key_node(node)?;
+        let name = content[key.byte_range()]
+            .trim_matches(['"', '\''])
+            .to_string();
+
+        if node.kind() == "pair" {
+            // Keys of inline tables aren't surfaced
+            let parent = node.parent()?.kind();
+            if !matches!(parent, "document" | "table" | "table_array_element") {
+                return None;
             }
+            let text = &content[node.byte_range()];
+            return Some(Symbol {
+                signature: if text.contains('\n') {
+                    name.clone()
+                } else {
+                    text.to_string()
+                },
+                name,
+                kind: SymbolKind::Variable,
+                docstring: None,
+                start_line: node.start_position().row + 1,
+                end_line: node.end_position().row + 1,
+                visibility: Visibility::Public,
+                children: Vec::new(),
+            });
         }
-        None
+
+        // A table runs to its last key, not up to the next header
+        let last = node.named_child(node.named_child_count().saturating_sub(1))?;
+        Some(Symbol {
+            signature: if node.kind() == "table_array_element" {
+                format!("[[{}]]", name)
+            } else {
+                format!("[{}]", name)
+            },
+            name,
+            kind: SymbolKind::Module,
+            docstring: None,
+            start_line: node.start_position().row + 1,
+            end_line: last.end_position().row + 1,
+            visibility: Visibility::Public,
+            children: Vec::new(),
+        })
     }
 
     fn extract_type(&self, _node: &Node, _content: &str) -> Option<Symbol> {
@@ -93,6 +121,12 @@ impl Language for Toml {
     fn extract_docstring(&self, _node: &Node, _content: &str) -> Option<String> {
         None
     }
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         Vec::new()
     }
@@ -111,8 +145,9 @@ impl Language for Toml {
         None
     }
 
-    fn container_body<'a>(&self, _node: &'a Node<'a>) -> Option<Node<'a>> {
-        None
+    fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
+        // A table's keys are its own children
+        (node.kind() != "pair").then_some(*node)
     }
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
         false
@@ -174,6 +209,15 @@ impl Language for Toml {
     }
 }
 
+/// The key naming a table or pair.
+fn key_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    let mut cursor = node.walk();
+    let key = node
+        .children(&mut cursor)
+        .find(|c| matches!(c.kind(), "bare_key" | "dotted_key" | "quoted_key"));
+    key
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +80,20 @@ pub struct Symbol {
     pub children: Vec<Symbol>,
 }
 
+/// A function parameter, taken from the grammar rather than the signature text
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Param {
+    pub name: String,
+    /// Declared type, if annotated
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub ty: Option<String>,
+    /// Default value expression
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    /// Whether callers may omit it (default value, `?`, `*args`, variadic)
+    pub optional: bool,
+}
+
 /// An import statement
 #[derive(Debug, Clone)]
 pub struct Import {
@@ -154,6 +168,10 @@ pub trait Language: Send + Sync {
     /// Function/method definition nodes
     fn function_kinds(&self) -> &'static [&'static str];
 
+    /// Anonymous function nodes (lambdas, closures, arrow functions).
+    /// Only extracted as symbols when nested extraction is enabled.
+    fn anonymous_function_kinds(&self) -> &'static [&'static str];
+
     /// Type definition nodes (struct, enum, interface, type alias)
     fn type_kinds(&self) -> &'static [&'static str];
 
@@ -183,6 +201,13 @@ pub trait Language: Send + Sync {
     /// Extract docstring/doc comment for a node
     fn extract_docstring(&self, node: &Node, content: &str) -> Option<String>;
 
+    /// Parameters of a function node, in declaration order.
+    /// Empty where parameter extraction isn't implemented.
+    fn parameters(&self, node: &Node, content: &str) -> Vec<Param>;
+
+    /// Declared return type of a function node, if any.
+    fn return_type(&self, node: &Node, content: &str) -> Option<String>;
+
     // === Import/Export ===
 
     /// Extract imports from an import node (may return multiple)
@@ -444,4 +469,6 @@ pub enum PackageSourceKind {
     Cargo,
     /// Deno cache structure (needs special handling for npm vs URL deps)
     Deno,
+    /// Go module cache (module@version directories, escaped paths)
+    GoModCache,
 }
@@ -2,7 +2,7 @@
 
 use crate::ecmascript;
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -33,6 +33,9 @@ impl Language for TypeScript {
     fn function_kinds(&self) -> &'static [&'static str] {
         ecmascript::TS_FUNCTION_KINDS
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        ecmascript::ANONYMOUS_FUNCTION_KINDS
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         ecmascript::TS_TYPE_KINDS
     }
@@ -90,6 +93,14 @@ impl Language for TypeScript {
         None
     }
 
+    fn parameters(&self, node: &Node, content: &str) -> Vec<Param> {
+        ecmascript::parameters(node, content)
+    }
+
+    fn return_type(&self, node: &Node, content: &str) -> Option<String> {
+        ecmascript::return_type(node, content)
+    }
+
     fn is_public(&self, _node: &Node, _content: &str) -> bool {
         true
     }
@@ -244,6 +255,9 @@ impl Language for Tsx {
     fn function_kinds(&self) -> &'static [&'static str] {
         ecmascript::TS_FUNCTION_KINDS
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        ecmascript::ANONYMOUS_FUNCTION_KINDS
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         ecmascript::TS_TYPE_KINDS
     }
@@ -301,6 +315,14 @@ impl Language for Tsx {
         None
     }
 
+    fn parameters(&self, node: &Node, content: &str) -> Vec<Param> {
+        ecmascript::parameters(node, content)
+    }
+
+    fn return_type(&self, node: &Node, content: &str) -> Option<String> {
+        ecmascript::return_type(node, content)
+    }
+
     fn is_public(&self, _node: &Node, _content: &str) -> bool {
         true
     }
@@ -432,8 +454,31 @@ impl Language for Tsx {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{param, parse};
     use crate::validate_unused_kinds_audit;
 
+    #[test]
+    fn test_typescript_parameters() {
+        let content =
+            "function f(a: number, b?: string, c = 1, ...rest: string[]): Promise<void> {}\n";
+        let tree = parse("typescript", content);
+        let func = tree.root_node().child(0).unwrap();
+        let params = TypeScript.parameters(&func, content);
+        assert_eq!(
+            params,
+            vec![
+                param("a", Some("number"), None, false),
+                param("b", Some("string"), None, true),
+                param("c", None, Some("1"), true),
+                param("...rest", Some("string[]"), None, true),
+            ]
+        );
+        assert_eq!(
+            TypeScript.return_type(&func, content),
+            Some("Promise<void>".to_string())
+        );
+    }
+
     /// Documents node kinds that exist in the TypeScript grammar but aren't used in trait methods.
     /// Run `cross_check_node_kinds` in registry.rs to see all potentially useful kinds.
     #[test]
@@ -1,7 +1,7 @@
 //! YAML language support.
 
 use crate::external_packages::ResolvedPackage;
-use crate::{Export, Import, Language, Symbol, SymbolKind, Visibility, VisibilityMechanism};
+use crate::{Export, Import, Language, Param, Symbol, SymbolKind, Visibility, VisibilityMechanism};
 use arborium::tree_sitter::Node;
 use std::path::{Path, PathBuf};
 
@@ -20,16 +20,20 @@ impl Language for Yaml {
     }
 
     fn has_symbols(&self) -> bool {
-        false
+        true
     }
 
-    // YAML is data, not code - no functions/types/control flow
+    // YAML is data, not code - no functions/types/control flow. Each
+    // document's keys are the symbols, with the keys under them as children.
     fn container_kinds(&self) -> &'static [&'static str] {
-        &["block_mapping", "flow_mapping"]
+        &["block_mapping_pair"]
     }
     fn function_kinds(&self) -> &'static [&'static str] {
         &[]
     }
+    fn anonymous_function_kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
     fn type_kinds(&self) -> &'static [&'static str] {
         &[]
     }
@@ -65,17 +69,26 @@ impl Language for Yaml {
     }
 
     fn extract_container(&self, node: &Node, content: &str) -> Option<Symbol> {
-        if node.kind() == "block_mapping_pair" {
+        if node.kind() == "block_mapping_pair" && pair_depth(node) <= 1 {
             let key = node.child_by_field_name("key")?;
-            let key_text = &content[key.byte_range()];
+            let key_text = content[key.byte_range()].trim_matches(['"', '\'']);
+            let text = &content[node.byte_range()];
 
             return Some(Symbol {
                 name: key_text.to_string(),
                 kind: SymbolKind::Variable,
-                signature: key_text.to_string(),
+                signature: if text.contains('\n') {
+                    key_text.to_string()
+                } else {
+                    text.to_string()
+                },
                 docstring: None,
                 start_line: node.start_position().row + 1,
-                end_line: node.end_position().row + 1,
+                // A nested mapping ends at the start of the line after it
+                end_line: match node.end_position() {
+                    end if end.column == 0 => end.row,
+                    end => end.row + 1,
+                },
                 visibility: Visibility::Public,
                 children: Vec::new(),
             });
@@ -89,6 +102,12 @@ impl Language for Yaml {
     fn extract_docstring(&self, _node: &Node, _content: &str) -> Option<String> {
         None
     }
+    fn parameters(&self, _node: &Node, _content: &str) -> Vec<Param> {
+        Vec::new()
+    }
+    fn return_type(&self, _node: &Node, _content: &str) -> Option<String> {
+        None
+    }
     fn extract_imports(&self, _node: &Node, _content: &str) -> Vec<Import> {
         Vec::new()
     }
@@ -107,8 +126,10 @@ impl Language for Yaml {
         None
     }
 
-    fn container_body<'a>(&self, _node: &'a Node<'a>) -> Option<Node<'a>> {
-        None
+    fn container_body<'a>(&self, node: &'a Node<'a>) -> Option<Node<'a>> {
+        (pair_depth(node) == 0)
+            .then(|| node.child_by_field_name("value"))
+            .flatten()
     }
     fn body_has_docstring(&self, _body: &Node, _content: &str) -> bool {
         false
@@ -170,6 +191,13 @@ impl Language for Yaml {
     }
 }
 
+/// How many keys a pair is nested under (0 for a document's own keys).
+fn pair_depth(node: &Node) -> usize {
+    std::iter::successors(node.parent(), |n| n.parent())
+        .filter(|n| n.kind() == "block_mapping_pair")
+        .count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,7 +207,7 @@ mod tests {
     fn unused_node_kinds_audit() {
         #[rustfmt::skip]
         let documented_unused: &[&str] = &[
-            "block_mapping_pair", "block_node", "block_scalar",
+            "block_mapping", "block_node", "block_scalar",
             "block_sequence", "block_sequence_item",
         ];
         validate_unused_kinds_audit(&Yaml, documented_unused)
@@ -1,18 +1,13 @@
 //! Local cache for package info (offline support).
+//!
+//! Stored in SQLite at ~/.cache/moss/registry.db, keyed by (ecosystem, package@version).
 
 use crate::PackageInfo;
-use std::fs;
+use rusqlite::{params, Connection, OptionalExtension};
 use std::path::PathBuf;
 use std::time::{Duration, SystemTime};
 
-/// Cache entry with timestamp.
-#[derive(serde::Serialize, serde::Deserialize)]
-struct CacheEntry {
-    info: PackageInfo,
-    cached_at: u64, // Unix timestamp
-}
-
-/// Get cache directory: ~/.cache/moss/packages
+/// Get the moss cache directory: ~/.cache/moss
 fn cache_dir() -> Option<PathBuf> {
     let base = if let Ok(cache) = std::env::var("XDG_CACHE_HOME") {
         PathBuf::from(cache)
@@ -23,66 +18,134 @@ fn cache_dir() -> Option<PathBuf> {
     } else {
         return None;
     };
-    Some(base.join("moss").join("packages"))
+    Some(base.join("moss"))
 }
 
-/// Get cache file path for a package.
-fn cache_path(ecosystem: &str, package: &str) -> Option<PathBuf> {
-    let dir = cache_dir()?;
-    // Sanitize package name for filesystem
-    let safe_name = package.replace('/', "_").replace(':', "_");
-    Some(dir.join(ecosystem).join(format!("{}.json", safe_name)))
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
-/// Read from cache if exists and not expired.
-pub fn read(ecosystem: &str, package: &str, max_age: Duration) -> Option<PackageInfo> {
-    let path = cache_path(ecosystem, package)?;
-    let content = fs::read_to_string(&path).ok()?;
-    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+/// Registry query cache.
+pub struct RegistryCache {
+    conn: Connection,
+}
 
-    // Check expiry
-    let now = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .ok()?
-        .as_secs();
+impl RegistryCache {
+    /// Open (or create) the cache database in the moss cache directory.
+    pub fn open() -> Option<Self> {
+        let dir = cache_dir()?;
+        std::fs::create_dir_all(&dir).ok()?;
+        let conn = Connection::open(dir.join("registry.db")).ok()?;
+        Self::with_connection(conn)
+    }
 
-    if now - entry.cached_at > max_age.as_secs() {
-        return None; // Expired
+    /// Open an in-memory cache (for testing).
+    #[cfg(test)]
+    pub fn open_in_memory() -> Option<Self> {
+        Self::with_connection(Connection::open_in_memory().ok()?)
     }
 
-    Some(entry.info)
-}
+    fn with_connection(conn: Connection) -> Option<Self> {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS package_info (
+                ecosystem TEXT NOT NULL,
+                package TEXT NOT NULL,
+                info TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL,
+                PRIMARY KEY (ecosystem, package)
+            )",
+            [],
+        )
+        .ok()?;
+        Some(Self { conn })
+    }
 
-/// Read from cache regardless of age (for offline fallback).
-pub fn read_any(ecosystem: &str, package: &str) -> Option<PackageInfo> {
-    let path = cache_path(ecosystem, package)?;
-    let content = fs::read_to_string(&path).ok()?;
-    let entry: CacheEntry = serde_json::from_str(&content).ok()?;
-    Some(entry.info)
+    /// Read from cache if present and fetched within `max_age`.
+    pub fn read(&self, ecosystem: &str, package: &str, max_age: Duration) -> Option<PackageInfo> {
+        let (info, fetched_at) = self.read_entry(ecosystem, package)?;
+        if now().saturating_sub(fetched_at) > max_age.as_secs() {
+            return None; // Expired
+        }
+        Some(info)
+    }
+
+    /// Read from cache regardless of age (for offline fallback).
+    pub fn read_any(&self, ecosystem: &str, package: &str) -> Option<PackageInfo> {
+        self.read_entry(ecosystem, package).map(|(info, _)| info)
+    }
+
+    fn read_entry(&self, ecosystem: &str, package: &str) -> Option<(PackageInfo, u64)> {
+        let (json, fetched_at): (String, i64) = self
+            .conn
+            .query_row(
+                "SELECT info, fetched_at FROM package_info WHERE ecosystem = ?1 AND package = ?2",
+                params![ecosystem, package],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .ok()??;
+        let info = serde_json::from_str(&json).ok()?;
+        Some((info, fetched_at as u64))
+    }
+
+    /// Write to cache, replacing any previous entry.
+    pub fn write(&self, ecosystem: &str, package: &str, info: &PackageInfo) {
+        let Ok(json) = serde_json::to_string(info) else {
+            return;
+        };
+        let _ = self.conn.execute(
+            "INSERT OR REPLACE INTO package_info (ecosystem, package, info, fetched_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![ecosystem, package, json, now() as i64],
+        );
+    }
 }
 
-/// Write to cache.
-pub fn write(ecosystem: &str, package: &str, info: &PackageInfo) {
-    let Some(path) = cache_path(ecosystem, package) else {
-        return;
-    };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Create directory if needed
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
+    fn info(version: &str) -> PackageInfo {
+        PackageInfo {
+            name: "serde".to_string(),
+            version: version.to_string(),
+            description: None,
+            license: None,
+            homepage: None,
+            repository: None,
+            features: Vec::new(),
+            dependencies: Vec::new(),
+        }
     }
 
-    let now = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .map(|d| d.as_secs())
-        .unwrap_or(0);
+    #[test]
+    fn test_registry_cache_roundtrip() {
+        let cache = RegistryCache::open_in_memory().unwrap();
+        assert!(cache.read_any("cargo", "serde@latest").is_none());
 
-    let entry = CacheEntry {
-        info: info.clone(),
-        cached_at: now,
-    };
+        cache.write("cargo", "serde@latest", &info("1.0.0"));
+        cache.write("cargo", "serde@latest", &info("1.0.1"));
+
+        let hit = cache.read("cargo", "serde@latest", Duration::from_secs(60));
+        assert_eq!(hit.unwrap().version, "1.0.1");
+        assert!(cache.read_any("npm", "serde@latest").is_none());
+    }
+
+    #[test]
+    fn test_registry_cache_expiry() {
+        let cache = RegistryCache::open_in_memory().unwrap();
+        cache.write("cargo", "serde@latest", &info("1.0.0"));
+        cache
+            .conn
+            .execute("UPDATE package_info SET fetched_at = fetched_at - 7200", [])
+            .unwrap();
 
-    if let Ok(json) = serde_json::to_string(&entry) {
-        let _ = fs::write(&path, json);
+        assert!(cache
+            .read("cargo", "serde@latest", Duration::from_secs(3600))
+            .is_none());
+        assert!(cache.read_any("cargo", "serde@latest").is_some());
     }
 }
@@ -1,8 +1,9 @@
 //! Cargo (Rust) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, Feature, LockfileManager, PackageError,
-    PackageInfo, PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyGraph, DependencyTree, Ecosystem, EdgeKind, Feature,
+    LockfileManager, PackageError, PackageInfo, PackageQuery, ResolvedDependency, TreeNode,
+    Vulnerability, VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -34,7 +35,7 @@ impl Ecosystem for Cargo {
     }
 
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
-        let lockfile = project_root.join("Cargo.lock");
+        let (lockfile, _) = find_cargo_lock(project_root).ok()?;
         let content = std::fs::read_to_string(lockfile).ok()?;
         let parsed: toml::Value = toml::from_str(&content).ok()?;
 
@@ -188,6 +189,44 @@ impl Ecosystem for Cargo {
         Ok(DependencyTree { roots })
     }
 
+    fn dependency_graph(&self, project_root: &Path) -> Result<DependencyGraph, PackageError> {
+        let (lockfile, _) = find_cargo_lock(project_root)?;
+        let content = std::fs::read_to_string(&lockfile)
+            .map_err(|e| PackageError::ParseError(format!("failed to read Cargo.lock: {}", e)))?;
+        let parsed: toml::Value = toml::from_str(&content)
+            .map_err(|e| PackageError::ParseError(format!("invalid TOML: {}", e)))?;
+        let manifest = std::fs::read_to_string(project_root.join("Cargo.toml"))
+            .ok()
+            .and_then(|c| toml::from_str::<toml::Value>(&c).ok());
+        Ok(build_cargo_graph(&parsed, manifest.as_ref()))
+    }
+
+    fn resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<Vec<ResolvedDependency>, PackageError> {
+        // Workspace members share the workspace root's Cargo.lock
+        let (lockfile, _) = find_cargo_lock(project_root)?;
+        let content = std::fs::read_to_string(&lockfile)
+            .map_err(|e| PackageError::ParseError(format!("failed to read Cargo.lock: {}", e)))?;
+        let parsed: toml::Value = toml::from_str(&content)
+            .map_err(|e| PackageError::ParseError(format!("invalid TOML: {}", e)))?;
+
+        // A member manifest narrows the result to that package's dependencies;
+        // a virtual workspace manifest (or none) yields everything in the lockfile.
+        let root_package = std::fs::read_to_string(project_root.join("Cargo.toml"))
+            .ok()
+            .and_then(|c| toml::from_str::<toml::Value>(&c).ok())
+            .and_then(|m| {
+                m.get("package")
+                    .and_then(|p| p.get("name"))
+                    .and_then(|n| n.as_str())
+                    .map(String::from)
+            });
+
+        Ok(parse_cargo_resolved(&parsed, root_package.as_deref()))
+    }
+
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
         // Try cargo audit (requires cargo-audit installed)
         let output = Command::new("cargo")
@@ -306,20 +345,172 @@ impl Ecosystem for Cargo {
 fn find_cargo_lock(
     project_root: &Path,
 ) -> Result<(std::path::PathBuf, std::path::PathBuf), PackageError> {
-    let mut current = project_root.to_path_buf();
-    loop {
-        let lockfile = current.join("Cargo.lock");
-        if lockfile.exists() {
-            return Ok((lockfile, current));
+    super::find_upwards(project_root, |dir| {
+        let lockfile = dir.join("Cargo.lock");
+        lockfile.exists().then(|| (lockfile, dir.to_path_buf()))
+    })
+    .ok_or_else(|| {
+        PackageError::ParseError(format!(
+            "Cargo.lock not found in {} or parent directories",
+            project_root.display()
+        ))
+    })
+}
+
+/// A `[[package]]` entry from Cargo.lock.
+struct LockedPackage<'a> {
+    name: &'a str,
+    version: &'a str,
+    /// Has a `source` (registry or git); workspace members and path deps don't
+    external: bool,
+    dependencies: Vec<&'a str>,
+}
+
+fn locked_packages(lock: &toml::Value) -> Vec<LockedPackage<'_>> {
+    lock.get("package")
+        .and_then(|p| p.as_array())
+        .map(|pkgs| {
+            pkgs.iter()
+                .map(|pkg| LockedPackage {
+                    name: pkg.get("name").and_then(|n| n.as_str()).unwrap_or(""),
+                    version: pkg.get("version").and_then(|v| v.as_str()).unwrap_or(""),
+                    external: pkg.get("source").is_some(),
+                    dependencies: pkg
+                        .get("dependencies")
+                        .and_then(|d| d.as_array())
+                        .map(|arr| arr.iter().filter_map(|d| d.as_str()).collect())
+                        .unwrap_or_default(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Index of the package a dependency entry refers to.
+/// Entries are "name", or "name version [(source)]" when several versions are locked.
+fn find_locked(packages: &[LockedPackage], dep: &str) -> Option<usize> {
+    let mut parts = dep.split_whitespace();
+    let name = parts.next()?;
+    let version = parts.next();
+    packages
+        .iter()
+        .position(|p| p.name == name && version.is_none_or(|v| p.version == v))
+}
+
+/// Dependency graph from Cargo.lock.
+///
+/// Cargo.lock doesn't record dependency kinds, so edges from the project's own
+/// package are classified using its manifest; all other edges are normal.
+fn build_cargo_graph(lock: &toml::Value, manifest: Option<&toml::Value>) -> DependencyGraph {
+    let packages = locked_packages(lock);
+    let root_name = manifest
+        .and_then(|m| m.get("package"))
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str());
+    let root_kinds = manifest.map(manifest_edge_kinds).unwrap_or_default();
+
+    let mut graph = DependencyGraph::default();
+    let ids: Vec<String> = packages
+        .iter()
+        .map(|pkg| graph.add_node(pkg.name, pkg.version, "cargo"))
+        .collect();
+
+    for (index, pkg) in packages.iter().enumerate() {
+        let is_root = !pkg.external && Some(pkg.name) == root_name;
+        for dep in &pkg.dependencies {
+            let Some(target) = find_locked(&packages, dep) else {
+                continue;
+            };
+            let kind = if is_root {
+                root_kinds
+                    .get(packages[target].name)
+                    .copied()
+                    .unwrap_or(EdgeKind::Normal)
+            } else {
+                EdgeKind::Normal
+            };
+            graph.add_edge(&ids[index], &ids[target], kind);
         }
-        if !current.pop() {
-            break;
+    }
+    graph
+}
+
+/// Dependency kinds declared in a Cargo.toml, keyed by package name.
+/// A dependency listed in several sections keeps its strongest kind (normal over build over dev).
+fn manifest_edge_kinds(manifest: &toml::Value) -> std::collections::HashMap<String, EdgeKind> {
+    let mut kinds = std::collections::HashMap::new();
+
+    let mut tables = vec![manifest];
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        tables.extend(targets.values());
+    }
+
+    for (section, section_kind) in [
+        ("dev-dependencies", EdgeKind::Dev),
+        ("build-dependencies", EdgeKind::Build),
+        ("dependencies", EdgeKind::Normal),
+    ] {
+        for table in &tables {
+            let Some(deps) = table.get(section).and_then(|d| d.as_table()) else {
+                continue;
+            };
+            for (key, value) in deps {
+                // Renamed dependencies: `alias = { package = "real-name" }`
+                let name = value.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+                let optional = value
+                    .get("optional")
+                    .and_then(|o| o.as_bool())
+                    .unwrap_or(false);
+                let kind = if section_kind == EdgeKind::Normal && optional {
+                    EdgeKind::Optional
+                } else {
+                    section_kind
+                };
+                kinds.insert(name.to_string(), kind);
+            }
         }
     }
-    Err(PackageError::ParseError(format!(
-        "Cargo.lock not found in {} or parent directories",
-        project_root.display()
-    )))
+    kinds
+}
+
+/// Registry and git packages pinned in Cargo.lock.
+///
+/// With `root`, only packages reachable from that workspace member are returned.
+/// Packages without a `source` are workspace members or path dependencies and are skipped.
+fn parse_cargo_resolved(lock: &toml::Value, root: Option<&str>) -> Vec<ResolvedDependency> {
+    let packages = locked_packages(lock);
+    let find = |dep: &str| find_locked(&packages, dep);
+
+    let root_index =
+        root.and_then(|name| packages.iter().position(|p| p.name == name && !p.external));
+
+    let included: Vec<bool> = match root_index {
+        Some(start) => {
+            let mut reachable = vec![false; packages.len()];
+            let mut stack = vec![start];
+            while let Some(index) = stack.pop() {
+                if reachable[index] {
+                    continue;
+                }
+                reachable[index] = true;
+                stack.extend(packages[index].dependencies.iter().filter_map(|d| find(d)));
+            }
+            reachable
+        }
+        None => vec![true; packages.len()],
+    };
+
+    let mut resolved: Vec<ResolvedDependency> = packages
+        .iter()
+        .zip(included)
+        .filter(|(pkg, included)| *included && pkg.external)
+        .map(|(pkg, _)| ResolvedDependency {
+            name: pkg.name.to_string(),
+            version: pkg.version.to_string(),
+        })
+        .collect();
+    crate::sort_resolved(&mut resolved);
+    resolved
 }
 
 fn parse_cargo_dep(name: &str, value: &toml::Value, optional: bool) -> Dependency {
@@ -499,4 +690,67 @@ mod tests {
         assert_eq!(eco.name(), "cargo");
         assert_eq!(eco.manifest_files(), &["Cargo.toml"]);
     }
+
+    #[test]
+    fn test_parse_cargo_resolved_workspace() {
+        let lock: toml::Value = toml::from_str(
+            r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["core", "serde", "rand 0.8.5"]
+
+[[package]]
+name = "core"
+version = "0.1.0"
+dependencies = ["rand 0.7.3"]
+
+[[package]]
+name = "tool"
+version = "0.1.0"
+dependencies = ["clap"]
+
+[[package]]
+name = "serde"
+version = "1.0.200"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.7.3"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "rand"
+version = "0.8.5"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "clap"
+version = "4.5.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+        )
+        .unwrap();
+
+        let resolved = |name: &str, version: &str| ResolvedDependency {
+            name: name.to_string(),
+            version: version.to_string(),
+        };
+
+        // Member: only its own (transitive) registry dependencies
+        assert_eq!(
+            parse_cargo_resolved(&lock, Some("app")),
+            vec![
+                resolved("rand", "0.7.3"),
+                resolved("rand", "0.8.5"),
+                resolved("serde", "1.0.200"),
+            ]
+        );
+
+        // Virtual workspace root: everything, without the members themselves
+        assert_eq!(parse_cargo_resolved(&lock, None).len(), 4);
+    }
 }
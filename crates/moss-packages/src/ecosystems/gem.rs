@@ -1,8 +1,9 @@
 //! RubyGems ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyGraph, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ResolvedDependency, TreeNode, Vulnerability,
+    VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -144,6 +145,18 @@ impl Ecosystem for Gem {
         })
     }
 
+    fn dependency_graph(&self, project_root: &Path) -> Result<DependencyGraph, PackageError> {
+        let tree = self.dependency_tree(project_root)?;
+        Ok(DependencyGraph::from_tree(&tree, self.name()))
+    }
+
+    fn resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<Vec<ResolvedDependency>, PackageError> {
+        Ok(self.dependency_tree(project_root)?.resolved())
+    }
+
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
         // Try bundler-audit (requires bundler-audit gem)
         let output = Command::new("bundle-audit")
@@ -1,8 +1,9 @@
 //! Go modules ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyGraph, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ResolvedDependency, TreeNode, Vulnerability,
+    VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -26,11 +27,14 @@ impl Ecosystem for Go {
     }
 
     fn tools(&self) -> &'static [&'static str] {
-        &["curl"] // Uses Go module proxy API
+        &["go", "curl"] // go list, or Go module proxy API directly
     }
 
-    fn fetch_info(&self, query: &PackageQuery, _tool: &str) -> Result<PackageInfo, PackageError> {
-        fetch_go_proxy_info(&query.name)
+    fn fetch_info(&self, query: &PackageQuery, tool: &str) -> Result<PackageInfo, PackageError> {
+        match tool {
+            "go" => fetch_go_list_info(query),
+            _ => fetch_go_proxy_info(query),
+        }
     }
 
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
@@ -150,6 +154,21 @@ impl Ecosystem for Go {
         })
     }
 
+    fn dependency_graph(&self, project_root: &Path) -> Result<DependencyGraph, PackageError> {
+        let tree = self.dependency_tree(project_root)?;
+        Ok(DependencyGraph::from_tree(&tree, self.name()))
+    }
+
+    fn resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<Vec<ResolvedDependency>, PackageError> {
+        let go_sum = project_root.join("go.sum");
+        let content = std::fs::read_to_string(&go_sum)
+            .map_err(|e| PackageError::ParseError(format!("failed to read go.sum: {}", e)))?;
+        Ok(parse_go_sum_resolved(&content))
+    }
+
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
         // Try govulncheck (requires golang.org/x/vuln/cmd/govulncheck)
         let output = Command::new("govulncheck")
@@ -210,9 +229,103 @@ impl Ecosystem for Go {
     }
 }
 
-fn fetch_go_proxy_info(package: &str) -> Result<PackageInfo, PackageError> {
-    // Get latest version from proxy
-    let url = format!("https://proxy.golang.org/{}/@latest", package);
+/// Module versions from go.sum that are part of the build.
+///
+/// Lines ending in `/go.mod` only record go.mod hashes of versions consulted during
+/// minimal version selection; modules whose code is built also have a content hash line.
+fn parse_go_sum_resolved(content: &str) -> Vec<ResolvedDependency> {
+    let mut resolved: Vec<ResolvedDependency> = content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let module = parts.next()?;
+            let version = parts.next()?;
+            if version.ends_with("/go.mod") {
+                return None;
+            }
+            Some(ResolvedDependency {
+                name: module.to_string(),
+                version: version.to_string(),
+            })
+        })
+        .collect();
+    crate::sort_resolved(&mut resolved);
+    resolved
+}
+
+fn fetch_go_list_info(query: &PackageQuery) -> Result<PackageInfo, PackageError> {
+    let version = query.version.as_deref().unwrap_or("latest");
+    let target = format!("{}@{}", query.name, version);
+
+    let output = Command::new("go")
+        .args(["list", "-m", "-json", &target])
+        .output()
+        .map_err(|e| PackageError::ToolFailed(format!("go list failed: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("no matching versions") || stderr.contains("not found") {
+            return Err(PackageError::NotFound(query.name.clone()));
+        }
+        return Err(PackageError::ToolFailed(stderr.trim().to_string()));
+    }
+
+    parse_go_list_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `go list -m -json` output for a single module.
+fn parse_go_list_output(stdout: &str) -> Result<PackageInfo, PackageError> {
+    let v: serde_json::Value = serde_json::from_str(stdout)
+        .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+
+    let name = v
+        .get("Path")
+        .and_then(|p| p.as_str())
+        .ok_or_else(|| PackageError::ParseError("missing Path".to_string()))?
+        .to_string();
+    let version = v
+        .get("Version")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| PackageError::ParseError("missing Version".to_string()))?
+        .to_string();
+
+    // Origin is reported for module queries since Go 1.19
+    let repository = v
+        .get("Origin")
+        .and_then(|o| o.get("URL"))
+        .and_then(|u| u.as_str())
+        .map(String::from)
+        .or_else(|| infer_repository(&name));
+
+    Ok(PackageInfo {
+        homepage: Some(format!("https://pkg.go.dev/{}", name)),
+        name,
+        version,
+        description: None,
+        license: None,
+        repository,
+        features: Vec::new(),
+        dependencies: Vec::new(),
+    })
+}
+
+/// Guess the source repository from a module path for well-known hosts.
+fn infer_repository(package: &str) -> Option<String> {
+    if package.starts_with("github.com/") {
+        Some(format!("https://{}", package))
+    } else {
+        package
+            .strip_prefix("golang.org/x/")
+            .map(|name| format!("https://go.googlesource.com/{}", name))
+    }
+}
+
+fn fetch_go_proxy_info(query: &PackageQuery) -> Result<PackageInfo, PackageError> {
+    let package = query.name.as_str();
+    let url = match &query.version {
+        Some(v) => format!("https://proxy.golang.org/{}/@v/{}.info", package, v),
+        None => format!("https://proxy.golang.org/{}/@latest", package),
+    };
 
     let output = Command::new("curl")
         .args(["-sS", "-f", &url])
@@ -233,17 +346,7 @@ fn fetch_go_proxy_info(package: &str) -> Result<PackageInfo, PackageError> {
         .ok_or_else(|| PackageError::ParseError("missing Version".to_string()))?
         .to_string();
 
-    // Try to get more info from pkg.go.dev (optional, may fail)
-    let repository = if package.starts_with("github.com/") {
-        Some(format!("https://{}", package))
-    } else if package.starts_with("golang.org/x/") {
-        Some(format!(
-            "https://go.googlesource.com/{}",
-            package.strip_prefix("golang.org/x/").unwrap()
-        ))
-    } else {
-        None
-    };
+    let repository = infer_repository(package);
 
     Ok(PackageInfo {
         name: package.to_string(),
@@ -267,4 +370,29 @@ mod tests {
         assert_eq!(eco.name(), "go");
         assert_eq!(eco.manifest_files(), &["go.mod"]);
     }
+
+    #[test]
+    fn test_parse_go_list_output() {
+        let stdout = r#"{
+	"Path": "github.com/pkg/errors",
+	"Version": "v0.9.1",
+	"Time": "2020-01-14T19:47:44Z",
+	"Origin": {
+		"VCS": "git",
+		"URL": "https://github.com/pkg/errors",
+		"Ref": "refs/tags/v0.9.1"
+	}
+}"#;
+        let info = parse_go_list_output(stdout).unwrap();
+        assert_eq!(info.name, "github.com/pkg/errors");
+        assert_eq!(info.version, "v0.9.1");
+        assert_eq!(
+            info.repository.as_deref(),
+            Some("https://github.com/pkg/errors")
+        );
+        assert_eq!(
+            info.homepage.as_deref(),
+            Some("https://pkg.go.dev/github.com/pkg/errors")
+        );
+    }
 }
@@ -1,8 +1,8 @@
 //! Hex (Elixir/Erlang) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyGraph, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ResolvedDependency, TreeNode,
 };
 use std::path::Path;
 use std::process::Command;
@@ -127,6 +127,18 @@ impl Ecosystem for Hex {
         })
     }
 
+    fn dependency_graph(&self, project_root: &Path) -> Result<DependencyGraph, PackageError> {
+        let tree = self.dependency_tree(project_root)?;
+        Ok(DependencyGraph::from_tree(&tree, self.name()))
+    }
+
+    fn resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<Vec<ResolvedDependency>, PackageError> {
+        Ok(self.dependency_tree(project_root)?.resolved())
+    }
+
     fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
         Err(PackageError::ToolFailed(
             "audit not yet supported for Hex. Use: mix deps.audit".to_string(),
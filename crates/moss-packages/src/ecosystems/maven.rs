@@ -1,8 +1,8 @@
 //! Maven (Java) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode,
+    AuditResult, Dependency, DependencyGraph, DependencyTree, Ecosystem, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ResolvedDependency, TreeNode,
 };
 use std::path::Path;
 use std::process::Command;
@@ -36,7 +36,31 @@ impl Ecosystem for Maven {
     }
 
     fn fetch_info(&self, query: &PackageQuery, _tool: &str) -> Result<PackageInfo, PackageError> {
-        fetch_maven_info(&query.name)
+        let mut info = match &query.version {
+            Some(version) if query.name.contains(':') => {
+                let (group_id, artifact_id) = query.name.split_once(':').unwrap();
+                PackageInfo {
+                    name: query.name.clone(),
+                    version: version.clone(),
+                    description: None,
+                    license: None,
+                    homepage: Some(format!(
+                        "https://central.sonatype.com/artifact/{}/{}",
+                        group_id, artifact_id
+                    )),
+                    repository: None,
+                    features: Vec::new(),
+                    dependencies: Vec::new(),
+                }
+            }
+            _ => fetch_maven_info(&query.name)?,
+        };
+
+        // Search results carry only coordinates; the POM has the rest
+        if let Some(pom) = fetch_pom(&info.name, &info.version) {
+            apply_pom_metadata(&mut info, &pom);
+        }
+        Ok(info)
     }
 
     fn installed_version(&self, package: &str, project_root: &Path) -> Option<String> {
@@ -138,6 +162,24 @@ impl Ecosystem for Maven {
         })
     }
 
+    fn dependency_graph(&self, project_root: &Path) -> Result<DependencyGraph, PackageError> {
+        let tree = self.dependency_tree(project_root)?;
+        Ok(DependencyGraph::from_tree(&tree, self.name()))
+    }
+
+    fn resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<Vec<ResolvedDependency>, PackageError> {
+        // Without a lockfile, dependency_tree falls back to manifest version ranges
+        if !project_root.join("gradle.lockfile").exists() {
+            return Err(PackageError::ParseError(
+                "no lockfile found (gradle.lockfile)".to_string(),
+            ));
+        }
+        Ok(self.dependency_tree(project_root)?.resolved())
+    }
+
     fn audit(&self, _project_root: &Path) -> Result<AuditResult, PackageError> {
         Err(PackageError::ToolFailed(
             "audit not yet supported for Maven. Use OWASP dependency-check or Snyk".to_string(),
@@ -197,6 +239,109 @@ fn parse_pom_dependencies(content: &str) -> Result<Vec<Dependency>, PackageError
     Ok(deps)
 }
 
+/// Project-level metadata from a POM file.
+#[derive(Debug, Default)]
+struct PomMetadata {
+    description: Option<String>,
+    homepage: Option<String>,
+    license: Option<String>,
+    repository: Option<String>,
+}
+
+/// Parse project-level metadata, ignoring same-named tags in nested sections
+/// (e.g. `<parent>`, `<developers>`, `<organization>`).
+fn parse_pom_metadata(content: &str) -> PomMetadata {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let Ok(doc) = roxmltree::Document::parse_with_options(content, options) else {
+        return PomMetadata::default();
+    };
+    let project = doc.root_element();
+    if project.tag_name().name() != "project" {
+        return PomMetadata::default();
+    }
+
+    let license = child(project, "licenses")
+        .and_then(|licenses| child(licenses, "license"))
+        .and_then(|license| child_text(license, "name"));
+    PomMetadata {
+        description: child_text(project, "description"),
+        homepage: child_text(project, "url"),
+        license,
+        repository: child(project, "scm").and_then(|scm| child_text(scm, "url")),
+    }
+}
+
+/// First child element of `node` named `name`, ignoring the POM namespace.
+fn child<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children()
+        .find(|c| c.is_element() && c.tag_name().name() == name)
+}
+
+/// Text of the child element `name`, with runs of whitespace collapsed.
+fn child_text(node: roxmltree::Node, name: &str) -> Option<String> {
+    let text: String = child(node, name)?
+        .descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Fill in description, license, links, and dependencies from a POM.
+fn apply_pom_metadata(info: &mut PackageInfo, pom: &str) {
+    let meta = parse_pom_metadata(pom);
+    info.description = meta.description.or(info.description.take());
+    info.license = meta.license.or(info.license.take());
+    info.homepage = meta.homepage.or(info.homepage.take());
+    info.repository = meta.repository.or(info.repository.take());
+    if let Ok(deps) = parse_pom_dependencies(pom) {
+        info.dependencies = deps;
+    }
+}
+
+/// Get the POM for `group:artifact` at a version: local ~/.m2 first, then Maven Central.
+fn fetch_pom(coordinate: &str, version: &str) -> Option<String> {
+    let (group_id, artifact_id) = coordinate.split_once(':')?;
+    let rel_path = format!(
+        "{}/{}/{}/{}-{}.pom",
+        group_id.replace('.', "/"),
+        artifact_id,
+        version,
+        artifact_id,
+        version
+    );
+
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok();
+    if let Some(home) = home {
+        let local = Path::new(&home)
+            .join(".m2")
+            .join("repository")
+            .join(&rel_path);
+        if let Ok(content) = std::fs::read_to_string(local) {
+            return Some(content);
+        }
+    }
+
+    let url = format!("https://repo1.maven.org/maven2/{}", rel_path);
+    let output = Command::new("curl")
+        .args(["-sS", "-f", &url])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 fn extract_xml_value(line: &str, tag: &str) -> Option<String> {
     let start_tag = format!("<{}>", tag);
     let end_tag = format!("</{}>", tag);
@@ -346,3 +491,115 @@ fn parse_maven_response(json: &str, package: &str) -> Result<PackageInfo, Packag
         dependencies: Vec::new(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_pom_metadata() {
+        let pom = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0">
+  <modelVersion>4.0.0</modelVersion>
+  <parent>
+    <groupId>com.google.guava</groupId>
+    <artifactId>guava-parent</artifactId>
+    <version>33.0.0-jre</version>
+  </parent>
+  <artifactId>guava</artifactId>
+  <description>Guava is a suite of core and expanded libraries.</description>
+  <url>https://github.com/google/guava</url>
+  <organization>
+    <name>Google LLC</name>
+    <url>http://www.google.com</url>
+  </organization>
+  <licenses>
+    <license>
+      <name>Apache License, Version 2.0</name>
+      <url>http://www.apache.org/licenses/LICENSE-2.0.txt</url>
+    </license>
+  </licenses>
+  <scm>
+    <url>https://github.com/google/guava/guava</url>
+  </scm>
+  <dependencies>
+    <dependency>
+      <groupId>com.google.guava</groupId>
+      <artifactId>failureaccess</artifactId>
+      <version>1.0.2</version>
+    </dependency>
+  </dependencies>
+</project>
+"#;
+        let mut info = PackageInfo {
+            name: "com.google.guava:guava".to_string(),
+            version: "33.0.0-jre".to_string(),
+            description: None,
+            license: None,
+            homepage: None,
+            repository: None,
+            features: Vec::new(),
+            dependencies: Vec::new(),
+        };
+        apply_pom_metadata(&mut info, pom);
+
+        assert_eq!(
+            info.description.as_deref(),
+            Some("Guava is a suite of core and expanded libraries.")
+        );
+        assert_eq!(info.license.as_deref(), Some("Apache License, Version 2.0"));
+        assert_eq!(
+            info.homepage.as_deref(),
+            Some("https://github.com/google/guava")
+        );
+        assert_eq!(
+            info.repository.as_deref(),
+            Some("https://github.com/google/guava/guava")
+        );
+        assert_eq!(info.dependencies.len(), 1);
+        assert_eq!(info.dependencies[0].name, "com.google.guava:failureaccess");
+    }
+
+    #[test]
+    fn test_pom_metadata_multiline_tags() {
+        let pom = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xmlns="http://maven.apache.org/POM/4.0.0"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://maven.apache.org/POM/4.0.0
+                             https://maven.apache.org/xsd/maven-4.0.0.xsd">
+  <modelVersion>4.0.0</modelVersion>
+  <artifactId>commons-lang3</artifactId>
+  <description>
+    Apache Commons Lang, a package of Java utility classes for the
+    classes that are in java.lang's hierarchy.
+  </description>
+  <url>https://commons.apache.org/proper/commons-lang/</url>
+  <licenses>
+    <license>
+      <name>Apache-2.0</name>
+    </license>
+  </licenses>
+  <scm>
+    <url>https://gitbox.apache.org/repos/asf?p=commons-lang.git</url>
+  </scm>
+</project>
+"#;
+        let meta = parse_pom_metadata(pom);
+        assert_eq!(
+            meta.description.as_deref(),
+            Some(
+                "Apache Commons Lang, a package of Java utility classes for the \
+                 classes that are in java.lang's hierarchy."
+            )
+        );
+        assert_eq!(
+            meta.homepage.as_deref(),
+            Some("https://commons.apache.org/proper/commons-lang/")
+        );
+        assert_eq!(meta.license.as_deref(), Some("Apache-2.0"));
+        assert_eq!(
+            meta.repository.as_deref(),
+            Some("https://gitbox.apache.org/repos/asf?p=commons-lang.git")
+        );
+    }
+}
@@ -69,6 +69,18 @@ pub fn detect_all(project_root: &Path) -> Vec<&'static dyn Ecosystem> {
     found
 }
 
+/// Search `project_root` and then each parent directory, returning the first hit.
+pub(crate) fn find_upwards<T>(
+    project_root: &Path,
+    found: impl FnMut(&Path) -> Option<T>,
+) -> Option<T> {
+    // Canonicalize so a relative root like "." can walk up to parents
+    let start = project_root
+        .canonicalize()
+        .unwrap_or_else(|_| project_root.to_path_buf());
+    start.ancestors().find_map(found)
+}
+
 /// Get all registered ecosystems.
 pub fn all() -> &'static [&'static dyn Ecosystem] {
     ECOSYSTEMS
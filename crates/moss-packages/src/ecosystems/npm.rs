@@ -1,8 +1,9 @@
 //! npm/yarn/pnpm (Node.js) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, LockfileManager, PackageError, PackageInfo,
-    PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyGraph, DependencyTree, Ecosystem, EdgeKind, LockfileManager,
+    PackageError, PackageInfo, PackageQuery, ResolvedDependency, TreeNode, Vulnerability,
+    VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -167,6 +168,35 @@ impl Ecosystem for Npm {
         build_npm_tree(&parsed)
     }
 
+    fn dependency_graph(&self, project_root: &Path) -> Result<DependencyGraph, PackageError> {
+        let lockfile = find_npm_lockfile(project_root)?;
+        let content = std::fs::read_to_string(&lockfile)
+            .map_err(|e| PackageError::ParseError(format!("failed to read lockfile: {}", e)))?;
+        let parsed: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+
+        if parsed.get("packages").is_some() {
+            Ok(build_npm_graph(&parsed))
+        } else {
+            // v1 lockfiles don't record dependency kinds per edge
+            let tree = build_npm_tree(&parsed)?;
+            Ok(DependencyGraph::from_tree(&tree, self.name()))
+        }
+    }
+
+    fn resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<Vec<ResolvedDependency>, PackageError> {
+        // A workspace root lockfile covers all members
+        let lockfile = find_npm_lockfile(project_root)?;
+        let content = std::fs::read_to_string(&lockfile)
+            .map_err(|e| PackageError::ParseError(format!("failed to read lockfile: {}", e)))?;
+        let parsed: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
+        Ok(parse_npm_resolved(&parsed))
+    }
+
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
         // Try npm audit (built into npm)
         let output = Command::new("npm")
@@ -262,26 +292,155 @@ impl Ecosystem for Npm {
 
 /// Find package-lock.json, searching up from project_root
 fn find_npm_lockfile(project_root: &Path) -> Result<std::path::PathBuf, crate::PackageError> {
-    let mut current = project_root.to_path_buf();
-    loop {
-        // Try various lockfile names
-        for name in ["package-lock.json", "pnpm-lock.yaml", "yarn.lock"] {
-            let lockfile = current.join(name);
-            if lockfile.exists() {
-                // For now, only fully support package-lock.json
-                if name == "package-lock.json" {
-                    return Ok(lockfile);
+    // For now, only fully support package-lock.json
+    super::find_upwards(project_root, |dir| {
+        let lockfile = dir.join("package-lock.json");
+        lockfile.exists().then_some(lockfile)
+    })
+    .ok_or_else(|| {
+        crate::PackageError::ParseError(format!(
+            "package-lock.json not found in {} or parent directories",
+            project_root.display()
+        ))
+    })
+}
+
+/// Dependency graph from a v2/v3 package-lock.json.
+///
+/// Each dependency is resolved like Node does: the closest `node_modules/<name>`
+/// walking up from the dependent's install path. Workspace links are followed.
+fn build_npm_graph(lock: &serde_json::Value) -> DependencyGraph {
+    let mut graph = DependencyGraph::default();
+    let Some(packages) = lock.get("packages").and_then(|p| p.as_object()) else {
+        return graph;
+    };
+
+    // Install path -> node id (links resolve to their target's id)
+    let mut ids: std::collections::HashMap<&str, String> = std::collections::HashMap::new();
+    for (path, info) in packages {
+        if info.get("link").and_then(|l| l.as_bool()) == Some(true) {
+            continue;
+        }
+        let name = info
+            .get("name")
+            .and_then(|n| n.as_str())
+            .or_else(|| path.rsplit_once("node_modules/").map(|(_, name)| name))
+            .or_else(|| {
+                lock.get("name")
+                    .and_then(|n| n.as_str())
+                    .filter(|_| path.is_empty())
+            })
+            .unwrap_or(path);
+        let version = info.get("version").and_then(|v| v.as_str()).unwrap_or("");
+        ids.insert(path, graph.add_node(name, version, "npm"));
+    }
+    for (path, info) in packages {
+        if let Some(target) = info.get("resolved").and_then(|r| r.as_str()) {
+            if info.get("link").and_then(|l| l.as_bool()) == Some(true) {
+                if let Some(id) = ids.get(target).cloned() {
+                    ids.insert(path, id);
+                }
+            }
+        }
+    }
+
+    let resolve = |from: &str, name: &str| -> Option<&String> {
+        let mut base = from;
+        loop {
+            let candidate = if base.is_empty() {
+                format!("node_modules/{}", name)
+            } else {
+                format!("{}/node_modules/{}", base, name)
+            };
+            if let Some(id) = ids.get(candidate.as_str()) {
+                return Some(id);
+            }
+            if base.is_empty() {
+                return None;
+            }
+            base = base
+                .rsplit_once("/node_modules/")
+                .map(|(b, _)| b)
+                .unwrap_or("");
+        }
+    };
+
+    for (path, info) in packages {
+        if info.get("link").and_then(|l| l.as_bool()) == Some(true) {
+            continue;
+        }
+        let Some(from) = ids.get(path.as_str()) else {
+            continue;
+        };
+        for (field, kind) in [
+            ("dependencies", EdgeKind::Normal),
+            ("devDependencies", EdgeKind::Dev),
+            ("optionalDependencies", EdgeKind::Optional),
+            ("peerDependencies", EdgeKind::Peer),
+        ] {
+            let Some(deps) = info.get(field).and_then(|d| d.as_object()) else {
+                continue;
+            };
+            for name in deps.keys() {
+                // Unmet peer dependencies have no install path
+                if let Some(to) = resolve(path, name) {
+                    graph.add_edge(from, to, kind);
                 }
             }
         }
-        if !current.pop() {
-            break;
+    }
+
+    graph
+}
+
+/// Installed packages from package-lock.json (v1, v2 or v3).
+///
+/// Workspace members appear as `link` entries or outside node_modules and are skipped.
+fn parse_npm_resolved(lock: &serde_json::Value) -> Vec<ResolvedDependency> {
+    let mut resolved = Vec::new();
+
+    if let Some(packages) = lock.get("packages").and_then(|p| p.as_object()) {
+        // v2/v3: keys are install paths, e.g. "node_modules/foo/node_modules/bar"
+        for (path, info) in packages {
+            let Some((_, name)) = path.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if info.get("link").and_then(|l| l.as_bool()) == Some(true) {
+                continue;
+            }
+            if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
+                resolved.push(ResolvedDependency {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                });
+            }
         }
+    } else if let Some(deps) = lock.get("dependencies") {
+        // v1: nested "dependencies" objects
+        fn collect(deps: &serde_json::Value, out: &mut Vec<ResolvedDependency>) {
+            let Some(deps) = deps.as_object() else {
+                return;
+            };
+            for (name, info) in deps {
+                if let Some(version) = info.get("version").and_then(|v| v.as_str()) {
+                    // Linked workspace members have "file:" versions
+                    if !version.starts_with("file:") {
+                        out.push(ResolvedDependency {
+                            name: name.clone(),
+                            version: version.to_string(),
+                        });
+                    }
+                }
+                if let Some(nested) = info.get("dependencies") {
+                    collect(nested, out);
+                }
+            }
+        }
+        collect(deps, &mut resolved);
     }
-    Err(crate::PackageError::ParseError(format!(
-        "package-lock.json not found in {} or parent directories",
-        project_root.display()
-    )))
+
+    crate::sort_resolved(&mut resolved);
+    resolved
 }
 
 fn build_npm_tree(parsed: &serde_json::Value) -> Result<DependencyTree, crate::PackageError> {
@@ -530,4 +689,91 @@ mod tests {
         assert_eq!(info.dependencies.len(), 1);
         assert_eq!(info.dependencies[0].name, "loose-envify");
     }
+
+    #[test]
+    fn test_parse_npm_resolved() {
+        let lock: serde_json::Value = serde_json::from_str(
+            r#"{
+                "lockfileVersion": 3,
+                "packages": {
+                    "": { "name": "root", "workspaces": ["packages/*"] },
+                    "node_modules/lodash": { "version": "4.17.21" },
+                    "node_modules/web": { "resolved": "packages/web", "link": true },
+                    "packages/web": { "version": "1.0.0" },
+                    "packages/web/node_modules/lodash": { "version": "3.10.1" },
+                    "node_modules/@scope/pkg": { "version": "2.0.0" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let resolved: Vec<String> = parse_npm_resolved(&lock)
+            .into_iter()
+            .map(|d| format!("{}@{}", d.name, d.version))
+            .collect();
+        assert_eq!(
+            resolved,
+            vec!["@scope/pkg@2.0.0", "lodash@3.10.1", "lodash@4.17.21"]
+        );
+    }
+
+    #[test]
+    fn test_build_npm_graph() {
+        let lock: serde_json::Value = serde_json::from_str(
+            r#"{
+                "name": "app",
+                "lockfileVersion": 3,
+                "packages": {
+                    "": {
+                        "name": "app",
+                        "version": "1.0.0",
+                        "dependencies": { "react": "^18.0.0" },
+                        "devDependencies": { "jest": "^29.0.0" },
+                        "optionalDependencies": { "fsevents": "^2.0.0" }
+                    },
+                    "node_modules/react": {
+                        "version": "18.2.0",
+                        "dependencies": { "loose-envify": "^1.1.0" }
+                    },
+                    "node_modules/react-dom": {
+                        "version": "18.2.0",
+                        "peerDependencies": { "react": "^18.2.0" }
+                    },
+                    "node_modules/loose-envify": { "version": "1.4.0" },
+                    "node_modules/jest": {
+                        "version": "29.7.0",
+                        "dependencies": { "loose-envify": "^2.0.0" }
+                    },
+                    "node_modules/jest/node_modules/loose-envify": { "version": "2.0.0" },
+                    "node_modules/fsevents": { "version": "2.3.3" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let graph = build_npm_graph(&lock);
+        assert_eq!(graph.nodes.len(), 7);
+        assert!(graph.nodes.iter().all(|n| n.ecosystem == "npm"));
+
+        let edges: Vec<String> = graph
+            .edges
+            .iter()
+            .map(|e| format!("{} -> {} ({})", e.from, e.to, e.kind.as_str()))
+            .collect();
+        assert_eq!(edges.len(), 6, "unexpected edges: {:?}", edges);
+        for expected in [
+            "app@1.0.0 -> react@18.2.0 (normal)",
+            "app@1.0.0 -> jest@29.7.0 (dev)",
+            "app@1.0.0 -> fsevents@2.3.3 (optional)",
+            "react@18.2.0 -> loose-envify@1.4.0 (normal)",
+            "react-dom@18.2.0 -> react@18.2.0 (peer)",
+            "jest@29.7.0 -> loose-envify@2.0.0 (normal)",
+        ] {
+            assert!(
+                edges.contains(&expected.to_string()),
+                "missing {}",
+                expected
+            );
+        }
+    }
 }
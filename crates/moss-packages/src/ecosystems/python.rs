@@ -1,8 +1,9 @@
 //! Python (pip/uv/poetry) ecosystem.
 
 use crate::{
-    AuditResult, Dependency, DependencyTree, Ecosystem, Feature, LockfileManager, PackageError,
-    PackageInfo, PackageQuery, TreeNode, Vulnerability, VulnerabilitySeverity,
+    AuditResult, Dependency, DependencyGraph, DependencyTree, Ecosystem, EdgeKind, Feature,
+    LockfileManager, PackageError, PackageInfo, PackageQuery, ResolvedDependency, TreeNode,
+    Vulnerability, VulnerabilitySeverity,
 };
 use std::path::Path;
 use std::process::Command;
@@ -219,6 +220,21 @@ impl Ecosystem for Python {
         ))
     }
 
+    fn dependency_graph(&self, project_root: &Path) -> Result<DependencyGraph, PackageError> {
+        let lock = read_python_lockfile(project_root)?;
+        let pyproject = std::fs::read_to_string(project_root.join("pyproject.toml"))
+            .ok()
+            .and_then(|c| toml::from_str::<toml::Value>(&c).ok());
+        Ok(build_python_graph(&lock, pyproject.as_ref()))
+    }
+
+    fn resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<Vec<ResolvedDependency>, PackageError> {
+        Ok(parse_python_resolved(&read_python_lockfile(project_root)?))
+    }
+
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError> {
         // Try pip-audit (requires pip-audit installed)
         let output = Command::new("pip-audit")
@@ -307,6 +323,175 @@ impl Ecosystem for Python {
     }
 }
 
+/// Find and parse uv.lock or poetry.lock, searching up from project_root.
+/// uv workspaces keep a single uv.lock at the workspace root.
+fn read_python_lockfile(project_root: &Path) -> Result<toml::Value, PackageError> {
+    let content = super::find_upwards(project_root, |dir| {
+        ["uv.lock", "poetry.lock"]
+            .iter()
+            .find_map(|name| std::fs::read_to_string(dir.join(name)).ok())
+    })
+    .ok_or_else(|| {
+        PackageError::ParseError("no lockfile found (uv.lock or poetry.lock)".to_string())
+    })?;
+    toml::from_str(&content).map_err(|e| PackageError::ParseError(format!("invalid TOML: {}", e)))
+}
+
+fn normalize_python_name(name: &str) -> String {
+    name.to_lowercase().replace(['-', '.'], "_")
+}
+
+/// Dependency graph from uv.lock or poetry.lock.
+///
+/// uv.lock records extras (`optional-dependencies`) and dependency groups
+/// (`dev-dependencies`) per package. poetry.lock has no entry for the project
+/// itself, so its edges come from pyproject.toml's poetry sections.
+fn build_python_graph(lock: &toml::Value, pyproject: Option<&toml::Value>) -> DependencyGraph {
+    let mut graph = DependencyGraph::default();
+    let packages = lock
+        .get("package")
+        .and_then(|p| p.as_array())
+        .map(|p| p.as_slice())
+        .unwrap_or_default();
+
+    let mut ids: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    for pkg in packages {
+        let name = pkg.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        let version = pkg.get("version").and_then(|v| v.as_str()).unwrap_or("");
+        let id = graph.add_node(name, version, "python");
+        ids.entry(normalize_python_name(name)).or_insert(id);
+    }
+
+    // uv: [{ name = "x" }, ...]; poetry: { x = "^1.0" | { version, optional } }
+    fn dep_entries(deps: &toml::Value, kind: EdgeKind) -> Vec<(String, EdgeKind)> {
+        match deps {
+            toml::Value::Array(arr) => arr
+                .iter()
+                .filter_map(|d| d.get("name").and_then(|n| n.as_str()))
+                .map(|name| (name.to_string(), kind))
+                .collect(),
+            toml::Value::Table(table) => table
+                .iter()
+                .map(|(name, spec)| {
+                    let optional = spec
+                        .get("optional")
+                        .and_then(|o| o.as_bool())
+                        .unwrap_or(false);
+                    let kind = if optional { EdgeKind::Optional } else { kind };
+                    (name.clone(), kind)
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn grouped_entries(groups: Option<&toml::Value>, kind: EdgeKind) -> Vec<(String, EdgeKind)> {
+        groups
+            .and_then(|g| g.as_table())
+            .map(|table| {
+                table
+                    .values()
+                    .flat_map(|deps| dep_entries(deps, kind))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    let add_edges = |graph: &mut DependencyGraph, from: &str, entries: Vec<(String, EdgeKind)>| {
+        for (name, kind) in entries {
+            if let Some(to) = ids.get(&normalize_python_name(&name)) {
+                graph.add_edge(from, to, kind);
+            }
+        }
+    };
+
+    for pkg in packages {
+        let name = pkg.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        let version = pkg.get("version").and_then(|v| v.as_str()).unwrap_or("");
+        let from = format!("{}@{}", name, version);
+
+        let mut entries = pkg
+            .get("dependencies")
+            .map(|d| dep_entries(d, EdgeKind::Normal))
+            .unwrap_or_default();
+        entries.extend(grouped_entries(
+            pkg.get("optional-dependencies"),
+            EdgeKind::Optional,
+        ));
+        entries.extend(grouped_entries(pkg.get("dev-dependencies"), EdgeKind::Dev));
+        add_edges(&mut graph, &from, entries);
+    }
+
+    // Poetry project root
+    if let Some(poetry) = pyproject
+        .and_then(|p| p.get("tool"))
+        .and_then(|t| t.get("poetry"))
+    {
+        let name = poetry
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or("root");
+        let version = poetry.get("version").and_then(|v| v.as_str()).unwrap_or("");
+        let root = graph.add_node(name, version, "python");
+
+        let mut entries = poetry
+            .get("dependencies")
+            .map(|d| dep_entries(d, EdgeKind::Normal))
+            .unwrap_or_default();
+        entries.extend(
+            poetry
+                .get("dev-dependencies")
+                .map(|d| dep_entries(d, EdgeKind::Dev))
+                .unwrap_or_default(),
+        );
+        if let Some(groups) = poetry.get("group").and_then(|g| g.as_table()) {
+            for group in groups.values() {
+                if let Some(deps) = group.get("dependencies") {
+                    entries.extend(dep_entries(deps, EdgeKind::Dev));
+                }
+            }
+        }
+        add_edges(&mut graph, &root, entries);
+    }
+
+    graph
+}
+
+/// Packages pinned in uv.lock or poetry.lock.
+///
+/// The project, uv workspace members and poetry path dependencies are locked
+/// from local sources and are skipped.
+fn parse_python_resolved(lock: &toml::Value) -> Vec<ResolvedDependency> {
+    let mut resolved: Vec<ResolvedDependency> = lock
+        .get("package")
+        .and_then(|p| p.as_array())
+        .map(|pkgs| {
+            pkgs.iter()
+                .filter(|pkg| {
+                    let Some(source) = pkg.get("source") else {
+                        return true;
+                    };
+                    // uv: source = { editable = "." }; poetry: [package.source] type = "directory"
+                    let local_uv = ["editable", "virtual", "directory"]
+                        .iter()
+                        .any(|kind| source.get(kind).is_some());
+                    let local_poetry =
+                        source.get("type").and_then(|t| t.as_str()) == Some("directory");
+                    !local_uv && !local_poetry
+                })
+                .filter_map(|pkg| {
+                    Some(ResolvedDependency {
+                        name: pkg.get("name")?.as_str()?.to_string(),
+                        version: pkg.get("version")?.as_str()?.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    crate::sort_resolved(&mut resolved);
+    resolved
+}
+
 fn build_python_tree(
     parsed: &toml::Value,
     project_root: &Path,
@@ -434,6 +619,66 @@ fn fetch_pypi_info(query: &PackageQuery) -> Result<PackageInfo, PackageError> {
     parse_pypi_json(&stdout, &query.name)
 }
 
+/// License from PyPI metadata (mirrors the package's METADATA file).
+///
+/// Prefers the PEP 639 `license_expression`, then a one-line `license` field
+/// (many packages paste the full license text there), then trove classifiers.
+fn pypi_license(info: &serde_json::Value) -> Option<String> {
+    let field = |key: &str| {
+        info.get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    if let Some(expression) = field("license_expression") {
+        return Some(expression.to_string());
+    }
+    if let Some(license) = field("license").filter(|l| !l.contains('\n')) {
+        return Some(license.to_string());
+    }
+
+    let licenses: Vec<&str> = info
+        .get("classifiers")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|c| c.as_str()?.strip_prefix("License :: "))
+        .map(|c| {
+            let name = c.rsplit(" :: ").next().unwrap_or(c);
+            classifier_to_spdx(name).unwrap_or(name)
+        })
+        .collect();
+    if licenses.is_empty() {
+        None
+    } else {
+        Some(licenses.join(" OR "))
+    }
+}
+
+/// SPDX identifier for a trove license classifier name, where unambiguous.
+/// ("BSD License" could be any BSD variant, so it stays as-is.)
+fn classifier_to_spdx(name: &str) -> Option<&'static str> {
+    let id = match name {
+        "MIT License" => "MIT",
+        "MIT No Attribution License (MIT-0)" => "MIT-0",
+        "Apache Software License" => "Apache-2.0",
+        "ISC License (ISCL)" => "ISC",
+        "Mozilla Public License 2.0 (MPL 2.0)" => "MPL-2.0",
+        "GNU General Public License v2 (GPLv2)" => "GPL-2.0",
+        "GNU General Public License v3 (GPLv3)" => "GPL-3.0",
+        "GNU Lesser General Public License v2 (LGPLv2)" => "LGPL-2.0",
+        "GNU Lesser General Public License v3 (LGPLv3)" => "LGPL-3.0",
+        "GNU Affero General Public License v3" => "AGPL-3.0",
+        "Python Software Foundation License" => "PSF-2.0",
+        "The Unlicense (Unlicense)" => "Unlicense",
+        "CC0 1.0 Universal (CC0 1.0) Public Domain Dedication" => "CC0-1.0",
+        "zlib/libpng License" => "Zlib",
+        "Boost Software License 1.0 (BSL-1.0)" => "BSL-1.0",
+        _ => return None,
+    };
+    Some(id)
+}
+
 fn parse_pypi_json(json_str: &str, package: &str) -> Result<PackageInfo, PackageError> {
     let v: serde_json::Value = serde_json::from_str(json_str)
         .map_err(|e| PackageError::ParseError(format!("invalid JSON: {}", e)))?;
@@ -459,11 +704,7 @@ fn parse_pypi_json(json_str: &str, package: &str) -> Result<PackageInfo, Package
         .and_then(|v| v.as_str())
         .map(String::from);
 
-    let license = info
-        .get("license")
-        .and_then(|v| v.as_str())
-        .filter(|s| !s.is_empty())
-        .map(String::from);
+    let license = pypi_license(info);
 
     let homepage = info
         .get("home_page")
@@ -620,4 +861,25 @@ mod tests {
         assert_eq!(info.license, Some("Apache-2.0".to_string()));
         assert_eq!(info.dependencies.len(), 2);
     }
+
+    #[test]
+    fn test_pypi_license_from_classifiers() {
+        let info: serde_json::Value = serde_json::from_str(
+            r#"{
+                "license": "Copyright (c) 2024\nPermission is hereby granted...",
+                "classifiers": [
+                    "Programming Language :: Python :: 3",
+                    "License :: OSI Approved :: MIT License",
+                    "License :: OSI Approved :: Apache Software License"
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(pypi_license(&info).as_deref(), Some("MIT OR Apache-2.0"));
+
+        let info: serde_json::Value =
+            serde_json::from_str(r#"{"license_expression": "BSD-3-Clause", "license": "BSD"}"#)
+                .unwrap();
+        assert_eq!(pypi_license(&info).as_deref(), Some("BSD-3-Clause"));
+    }
 }
@@ -6,13 +6,13 @@
 //! # Example
 //!
 //! ```ignore
-//! use moss_packages::{detect_ecosystem, PackageInfo};
+//! use moss_packages::{detect_ecosystem, PackageInfo, QueryOptions};
 //! use std::path::Path;
 //!
 //! // Detect ecosystem from project files
 //! if let Some(ecosystem) = detect_ecosystem(Path::new(".")) {
 //!     // Query package info (with offline cache)
-//!     if let Ok(info) = ecosystem.query("serde", Path::new(".")) {
+//!     if let Ok(info) = ecosystem.query("serde", Path::new("."), &QueryOptions::default()) {
 //!         println!("{}: {}", info.name, info.version);
 //!     }
 //! }
@@ -20,9 +20,18 @@
 
 mod cache;
 pub mod ecosystems;
+mod license;
+mod version;
+
+pub use license::{
+    parse_spdx_expression, LicenseCount, LicenseStatus, LicenseSummary, PackageLicense,
+};
+pub use version::compare_versions;
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::Path;
+use std::time::Duration;
 
 /// Parsed package query (name with optional version).
 #[derive(Debug, Clone)]
@@ -99,6 +108,144 @@ pub struct DependencyTree {
     pub roots: Vec<TreeNode>,
 }
 
+impl DependencyTree {
+    /// All versioned packages below the roots, deduplicated and sorted.
+    ///
+    /// Roots are the project itself (or the lockfile name) and are not included.
+    pub fn resolved(&self) -> Vec<ResolvedDependency> {
+        fn collect(node: &TreeNode, out: &mut Vec<ResolvedDependency>) {
+            for child in &node.dependencies {
+                if !child.version.is_empty() {
+                    out.push(ResolvedDependency {
+                        name: child.name.clone(),
+                        version: child.version.clone(),
+                    });
+                }
+                collect(child, out);
+            }
+        }
+
+        let mut resolved = Vec::new();
+        for root in &self.roots {
+            collect(root, &mut resolved);
+        }
+        sort_resolved(&mut resolved);
+        resolved
+    }
+}
+
+/// A dependency pinned to an exact version by a lockfile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedDependency {
+    pub name: String,
+    pub version: String,
+}
+
+/// Sort by name then version and drop duplicates.
+pub(crate) fn sort_resolved(resolved: &mut Vec<ResolvedDependency>) {
+    resolved.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+    resolved.dedup();
+}
+
+/// Resolved dependency graph from a lockfile.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// Ids in `nodes` and entries in `edges`, for dedup in `add_node`/`add_edge`
+    #[serde(skip)]
+    node_ids: HashSet<String>,
+    #[serde(skip)]
+    edge_set: HashSet<GraphEdge>,
+}
+
+/// A package in the dependency graph. `id` is "name@version".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub ecosystem: String,
+}
+
+/// A dependency relationship between two graph nodes (by id).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+/// How a dependency is required, where the lockfile records it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    Normal,
+    Dev,
+    Build,
+    Optional,
+    Peer,
+}
+
+impl EdgeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeKind::Normal => "normal",
+            EdgeKind::Dev => "dev",
+            EdgeKind::Build => "build",
+            EdgeKind::Optional => "optional",
+            EdgeKind::Peer => "peer",
+        }
+    }
+}
+
+impl DependencyGraph {
+    /// Add a node, returning its id. Nodes with the same name and version are merged.
+    pub fn add_node(&mut self, name: &str, version: &str, ecosystem: &str) -> String {
+        let id = format!("{}@{}", name, version);
+        if self.node_ids.insert(id.clone()) {
+            self.nodes.push(GraphNode {
+                id: id.clone(),
+                name: name.to_string(),
+                version: version.to_string(),
+                ecosystem: ecosystem.to_string(),
+            });
+        }
+        id
+    }
+
+    /// Add an edge unless an identical one exists.
+    pub fn add_edge(&mut self, from: &str, to: &str, kind: EdgeKind) {
+        let edge = GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+        };
+        if self.edge_set.insert(edge.clone()) {
+            self.edges.push(edge);
+        }
+    }
+
+    /// Build a graph from a dependency tree, for lockfiles without edge kinds.
+    /// Every parent-child relationship becomes a normal edge.
+    pub fn from_tree(tree: &DependencyTree, ecosystem: &str) -> Self {
+        fn walk(node: &TreeNode, id: &str, ecosystem: &str, graph: &mut DependencyGraph) {
+            for child in &node.dependencies {
+                let child_id = graph.add_node(&child.name, &child.version, ecosystem);
+                graph.add_edge(id, &child_id, EdgeKind::Normal);
+                walk(child, &child_id, ecosystem, graph);
+            }
+        }
+
+        let mut graph = DependencyGraph::default();
+        for root in &tree.roots {
+            let id = graph.add_node(&root.name, &root.version, ecosystem);
+            walk(root, &id, ecosystem, &mut graph);
+        }
+        graph
+    }
+}
+
 /// Security vulnerability found by audit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vulnerability {
@@ -140,6 +287,35 @@ pub struct AuditResult {
     pub vulnerabilities: Vec<Vulnerability>,
 }
 
+/// How [`Ecosystem::query`] uses the local registry cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Use fresh cache entries, fall back to stale ones when the network fails.
+    #[default]
+    Default,
+    /// Always query the registry, then update the cache.
+    Refresh,
+    /// Never touch the network; fail if the package isn't cached.
+    Offline,
+}
+
+/// Options for [`Ecosystem::query`].
+#[derive(Debug, Clone)]
+pub struct QueryOptions {
+    /// Cache entries older than this are refetched (in `CacheMode::Default`).
+    pub cache_ttl: Duration,
+    pub cache_mode: CacheMode,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            cache_ttl: Duration::from_secs(24 * 60 * 60),
+            cache_mode: CacheMode::Default,
+        }
+    }
+}
+
 /// Error type for package operations.
 #[derive(Debug)]
 pub enum PackageError {
@@ -153,6 +329,8 @@ pub enum PackageError {
     NotFound(String),
     /// Network or registry error
     RegistryError(String),
+    /// Offline mode and the package isn't in the cache
+    NotCached(String),
 }
 
 impl std::fmt::Display for PackageError {
@@ -163,6 +341,7 @@ impl std::fmt::Display for PackageError {
             PackageError::ParseError(msg) => write!(f, "parse error: {}", msg),
             PackageError::NotFound(name) => write!(f, "package not found: {}", name),
             PackageError::RegistryError(msg) => write!(f, "registry error: {}", msg),
+            PackageError::NotCached(name) => write!(f, "not in cache (offline): {}", name),
         }
     }
 }
@@ -210,6 +389,17 @@ pub trait Ecosystem: Send + Sync {
     /// Returns structured tree data.
     fn dependency_tree(&self, project_root: &Path) -> Result<DependencyTree, PackageError>;
 
+    /// Resolved dependency graph from the lockfile, with edge kinds (dev, optional, ...)
+    /// where the lockfile records them.
+    fn dependency_graph(&self, project_root: &Path) -> Result<DependencyGraph, PackageError>;
+
+    /// Exact versions of every package pinned by the lockfile, including transitive ones.
+    /// Workspace members sharing a lockfile with project_root are not included.
+    fn resolved_dependencies(
+        &self,
+        project_root: &Path,
+    ) -> Result<Vec<ResolvedDependency>, PackageError>;
+
     /// Run security audit for known vulnerabilities.
     /// Default implementation returns empty result (no audit tool available).
     fn audit(&self, project_root: &Path) -> Result<AuditResult, PackageError>;
@@ -240,40 +430,66 @@ pub trait Ecosystem: Send + Sync {
 
     /// Convenience method: detect tool and fetch info with caching.
     ///
-    /// Accepts "package" or "package@version" format.
+    /// Accepts "package", "package@version" or "package@latest" format.
     /// If no version specified, checks lockfile for installed version first.
-    /// Strategy: try cache first if fresh, else network, cache on success, stale cache as fallback.
-    fn query(&self, package: &str, project_root: &Path) -> Result<PackageInfo, PackageError> {
-        use std::time::Duration;
-
+    /// Strategy depends on `options.cache_mode`:
+    /// - Default: fresh cache, else network (cached on success), stale cache as fallback
+    /// - Refresh: network only, result is cached
+    /// - Offline: cache only, regardless of age
+    fn query(
+        &self,
+        package: &str,
+        project_root: &Path,
+        options: &QueryOptions,
+    ) -> Result<PackageInfo, PackageError> {
         let mut query = PackageQuery::parse(package);
 
         // If no explicit version, check lockfile for installed version
-        if query.version.is_none() {
-            query.version = self.installed_version(&query.name, project_root);
+        match query.version.as_deref() {
+            Some("latest") => query.version = None,
+            Some(_) => {}
+            None => query.version = self.installed_version(&query.name, project_root),
         }
 
-        let tool = self
-            .detect_tool(project_root)
-            .ok_or(PackageError::NoToolFound)?;
         let cache_key = query.cache_key();
-        let cache_ttl = Duration::from_secs(24 * 60 * 60); // 24 hours
+        let cache = cache::RegistryCache::open();
 
-        // Check fresh cache first (avoid network if recently cached)
-        if let Some(cached) = cache::read(self.name(), &cache_key, cache_ttl) {
-            return Ok(cached);
+        match options.cache_mode {
+            CacheMode::Offline => {
+                return cache
+                    .and_then(|c| c.read_any(self.name(), &cache_key))
+                    .ok_or(PackageError::NotCached(cache_key));
+            }
+            CacheMode::Default => {
+                // Check fresh cache first (avoid network if recently cached)
+                if let Some(cached) = cache
+                    .as_ref()
+                    .and_then(|c| c.read(self.name(), &cache_key, options.cache_ttl))
+                {
+                    return Ok(cached);
+                }
+            }
+            CacheMode::Refresh => {}
         }
 
+        let tool = self
+            .detect_tool(project_root)
+            .ok_or(PackageError::NoToolFound)?;
+
         // Try network
         match self.fetch_info(&query, tool) {
             Ok(info) => {
-                cache::write(self.name(), &cache_key, &info);
+                if let Some(cache) = &cache {
+                    cache.write(self.name(), &cache_key, &info);
+                }
                 Ok(info)
             }
             Err(e) => {
-                // Network failed - try stale cache
-                if let Some(cached) = cache::read_any(self.name(), &cache_key) {
-                    return Ok(cached);
+                // Network failed - try stale cache (unless the caller asked to bypass it)
+                if options.cache_mode == CacheMode::Default {
+                    if let Some(cached) = cache.and_then(|c| c.read_any(self.name(), &cache_key)) {
+                        return Ok(cached);
+                    }
                 }
                 Err(e)
             }
@@ -0,0 +1,283 @@
+//! License aggregation across dependencies.
+//!
+//! Classifies each package's declared license as a valid SPDX expression,
+//! a non-SPDX string, or missing, and counts packages per license identifier.
+//! Compound expressions (`MIT OR Apache-2.0`) count toward every identifier.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How a package's license was classified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseStatus {
+    /// Valid SPDX expression.
+    Spdx,
+    /// A license string that isn't a valid SPDX expression.
+    NonSpdx,
+    /// No license declared (or metadata unavailable).
+    Missing,
+}
+
+impl LicenseStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LicenseStatus::Spdx => "spdx",
+            LicenseStatus::NonSpdx => "non-spdx",
+            LicenseStatus::Missing => "missing",
+        }
+    }
+}
+
+/// License of a single dependency.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageLicense {
+    pub name: String,
+    pub version: String,
+    pub license: Option<String>,
+    pub status: LicenseStatus,
+    /// SPDX identifiers in the expression (empty unless status is Spdx).
+    pub identifiers: Vec<String>,
+}
+
+impl PackageLicense {
+    /// Classify a declared license.
+    pub fn new(name: &str, version: &str, license: Option<&str>) -> Self {
+        let license = license.map(str::trim).filter(|l| !l.is_empty());
+        let identifiers = license.and_then(parse_spdx_expression);
+        let status = match (license, &identifiers) {
+            (None, _) => LicenseStatus::Missing,
+            (Some(_), Some(_)) => LicenseStatus::Spdx,
+            (Some(_), None) => LicenseStatus::NonSpdx,
+        };
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            license: license.map(String::from),
+            status,
+            identifiers: identifiers.unwrap_or_default(),
+        }
+    }
+}
+
+/// Number of packages under one license.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LicenseCount {
+    /// SPDX identifier, or the raw string for non-SPDX licenses.
+    pub license: String,
+    pub count: usize,
+}
+
+/// Licenses of all dependencies with per-license counts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseSummary {
+    pub packages: Vec<PackageLicense>,
+    /// Sorted by count (descending), then license.
+    pub counts: Vec<LicenseCount>,
+    pub missing: usize,
+    pub non_spdx: usize,
+}
+
+impl LicenseSummary {
+    pub fn from_packages(mut packages: Vec<PackageLicense>) -> Self {
+        packages.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut missing = 0;
+        let mut non_spdx = 0;
+        for package in &packages {
+            match (package.status, &package.license) {
+                (LicenseStatus::Spdx, _) => {
+                    for id in &package.identifiers {
+                        *counts.entry(id).or_default() += 1;
+                    }
+                }
+                (LicenseStatus::NonSpdx, Some(license)) => {
+                    non_spdx += 1;
+                    *counts.entry(license).or_default() += 1;
+                }
+                _ => missing += 1,
+            }
+        }
+
+        let mut counts: Vec<LicenseCount> = counts
+            .into_iter()
+            .map(|(license, count)| LicenseCount {
+                license: license.to_string(),
+                count,
+            })
+            .collect();
+        counts.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.license.cmp(&b.license))
+        });
+
+        Self {
+            packages,
+            counts,
+            missing,
+            non_spdx,
+        }
+    }
+}
+
+/// Parse an SPDX license expression, returning its distinct license identifiers
+/// (canonical case, without `+` or exceptions). None if it isn't valid SPDX.
+///
+/// Grammar: `term ((AND | OR) term)*`, where a term is a parenthesized
+/// expression or `id[+] [WITH exception]`.
+pub fn parse_spdx_expression(expr: &str) -> Option<Vec<String>> {
+    let spaced = expr.replace('(', " ( ").replace(')', " ) ");
+    let tokens: Vec<&str> = spaced.split_whitespace().collect();
+    let mut parser = SpdxParser {
+        tokens: &tokens,
+        pos: 0,
+        ids: Vec::new(),
+    };
+    parser.expression()?;
+    if parser.pos != tokens.len() {
+        return None;
+    }
+    Some(parser.ids)
+}
+
+struct SpdxParser<'a> {
+    tokens: &'a [&'a str],
+    pos: usize,
+    ids: Vec<String>,
+}
+
+impl SpdxParser<'_> {
+    fn advance(&mut self) -> Option<&str> {
+        let token = self.tokens.get(self.pos).copied();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_operator(&self) -> bool {
+        self.tokens
+            .get(self.pos)
+            .is_some_and(|t| t.eq_ignore_ascii_case("AND") || t.eq_ignore_ascii_case("OR"))
+    }
+
+    fn expression(&mut self) -> Option<()> {
+        self.term()?;
+        while self.peek_operator() {
+            self.pos += 1;
+            self.term()?;
+        }
+        Some(())
+    }
+
+    fn term(&mut self) -> Option<()> {
+        let token = self.advance()?;
+        if token == "(" {
+            self.expression()?;
+            return (self.advance()? == ")").then_some(());
+        }
+
+        let id = canonical_license(token.strip_suffix('+').unwrap_or(token))?;
+        if !self.ids.contains(&id) {
+            self.ids.push(id);
+        }
+
+        if self
+            .tokens
+            .get(self.pos)
+            .is_some_and(|t| t.eq_ignore_ascii_case("WITH"))
+        {
+            self.pos += 1;
+            let exception = self.advance()?;
+            spdx::identifiers::EXCEPTIONS
+                .iter()
+                .find(|(e, _)| e.eq_ignore_ascii_case(exception))?;
+        }
+        Some(())
+    }
+}
+
+/// Canonical form of a license identifier from the SPDX license list
+/// (matching is case-insensitive; deprecated identifiers are still valid).
+fn canonical_license(id: &str) -> Option<String> {
+    if id.starts_with("LicenseRef-") || id.starts_with("DocumentRef-") {
+        return Some(id.to_string());
+    }
+    spdx::identifiers::LICENSES
+        .iter()
+        .find(|(known, ..)| known.eq_ignore_ascii_case(id))
+        .map(|(known, ..)| known.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spdx_expression() {
+        assert_eq!(parse_spdx_expression("MIT"), Some(vec!["MIT".to_string()]));
+        assert_eq!(
+            parse_spdx_expression("MIT OR Apache-2.0"),
+            Some(vec!["MIT".to_string(), "Apache-2.0".to_string()])
+        );
+        assert_eq!(
+            parse_spdx_expression("(apache-2.0 WITH LLVM-exception) OR mit"),
+            Some(vec!["Apache-2.0".to_string(), "MIT".to_string()])
+        );
+        assert_eq!(
+            parse_spdx_expression("GPL-2.0+"),
+            Some(vec!["GPL-2.0".to_string()])
+        );
+        assert_eq!(
+            parse_spdx_expression("Apache-2.0 AND ISC AND CDLA-Permissive-2.0"),
+            Some(vec![
+                "Apache-2.0".to_string(),
+                "ISC".to_string(),
+                "CDLA-Permissive-2.0".to_string()
+            ])
+        );
+        assert_eq!(
+            parse_spdx_expression("unicode-3.0"),
+            Some(vec!["Unicode-3.0".to_string()])
+        );
+        assert_eq!(parse_spdx_expression("MIT/Apache-2.0"), None);
+        assert_eq!(parse_spdx_expression("MIT OR"), None);
+        assert_eq!(parse_spdx_expression("(MIT"), None);
+        assert_eq!(parse_spdx_expression("Apache License, Version 2.0"), None);
+    }
+
+    #[test]
+    fn test_license_summary() {
+        let summary = LicenseSummary::from_packages(vec![
+            PackageLicense::new("serde", "1.0.200", Some("MIT OR Apache-2.0")),
+            PackageLicense::new("anyhow", "1.0.80", Some("MIT OR Apache-2.0")),
+            PackageLicense::new("ring", "0.17.8", Some("ISC AND MIT")),
+            PackageLicense::new("guava", "33.0.0", Some("Apache License, Version 2.0")),
+            PackageLicense::new("internal", "0.1.0", None),
+            PackageLicense::new("blank", "0.1.0", Some("  ")),
+        ]);
+
+        assert_eq!(summary.packages[0].name, "anyhow");
+        assert_eq!(summary.missing, 2);
+        assert_eq!(summary.non_spdx, 1);
+
+        let counts: Vec<(&str, usize)> = summary
+            .counts
+            .iter()
+            .map(|c| (c.license.as_str(), c.count))
+            .collect();
+        assert_eq!(
+            counts,
+            vec![
+                ("MIT", 3),
+                ("Apache-2.0", 2),
+                ("Apache License, Version 2.0", 1),
+                ("ISC", 1),
+            ]
+        );
+
+        let guava = summary.packages.iter().find(|p| p.name == "guava").unwrap();
+        assert_eq!(guava.status, LicenseStatus::NonSpdx);
+        assert!(guava.identifiers.is_empty());
+    }
+}
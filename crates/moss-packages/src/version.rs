@@ -0,0 +1,109 @@
+//! Version comparison across ecosystems.
+//!
+//! Follows semver precedence (numeric core, then pre-release identifiers) while
+//! tolerating the variations other ecosystems use: a leading `v` (Go), missing
+//! components (`1.2`), and extra components (`1.2.3.4`).
+
+use std::cmp::Ordering;
+
+/// Compare two version strings by semver precedence.
+///
+/// Build metadata (`+...`) is ignored. A version with a pre-release tag
+/// (`1.0.0-rc.1`) sorts before the same version without one.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a = ParsedVersion::parse(a);
+    let b = ParsedVersion::parse(b);
+
+    let len = a.core.len().max(b.core.len());
+    for i in 0..len {
+        let x = a.core.get(i).copied().unwrap_or(0);
+        let y = b.core.get(i).copied().unwrap_or(0);
+        match x.cmp(&y) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+    }
+
+    match (a.pre.is_empty(), b.pre.is_empty()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => compare_pre_release(&a.pre, &b.pre),
+    }
+}
+
+struct ParsedVersion<'a> {
+    core: Vec<u64>,
+    pre: Vec<&'a str>,
+}
+
+impl<'a> ParsedVersion<'a> {
+    fn parse(version: &'a str) -> Self {
+        let version = version.trim();
+        let version = version
+            .strip_prefix('v')
+            .or_else(|| version.strip_prefix('V'))
+            .unwrap_or(version);
+        let version = version.split('+').next().unwrap_or(version);
+        let (core, pre) = match version.split_once('-') {
+            Some((core, pre)) => (core, pre.split('.').collect()),
+            None => (version, Vec::new()),
+        };
+
+        // Leading digits of each component; "0rc1" counts as 0
+        let core = core
+            .split('.')
+            .map(|part| {
+                let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+                digits.parse().unwrap_or(0)
+            })
+            .collect();
+
+        ParsedVersion { core, pre }
+    }
+}
+
+/// Semver rules: numeric identifiers compare numerically and sort before
+/// alphanumeric ones; a shorter list sorts first when all shared identifiers match.
+fn compare_pre_release(a: &[&str], b: &[&str]) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.0.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2.3", "1.10.0"), Ordering::Less);
+        assert_eq!(compare_versions("v1.4.0", "1.3.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0.0+build.5", "1.0.0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_compare_pre_release() {
+        assert_eq!(compare_versions("1.0.0-rc.1", "1.0.0"), Ordering::Less);
+        assert_eq!(
+            compare_versions("1.0.0-alpha", "1.0.0-alpha.1"),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_versions("1.0.0-alpha.2", "1.0.0-alpha.10"),
+            Ordering::Less
+        );
+        assert_eq!(compare_versions("1.0.0-2", "1.0.0-beta"), Ordering::Less);
+    }
+}
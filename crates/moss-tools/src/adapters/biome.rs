@@ -7,6 +7,7 @@ use crate::{
     Diagnostic, DiagnosticSeverity, Location, Tool, ToolCategory, ToolError, ToolInfo, ToolResult,
 };
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::Command;
 
@@ -29,6 +30,8 @@ impl BiomeLint {
                 extensions: &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts", "json"],
                 check_cmd: &["biome", "--version"],
                 website: "https://biomejs.dev/",
+                file_scoped: true,
+                config_files: &["biome.json", "biome.jsonc"],
             },
         }
     }
@@ -54,6 +57,8 @@ impl BiomeFormat {
                 extensions: &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts", "json"],
                 check_cmd: &["biome", "--version"],
                 website: "https://biomejs.dev/",
+                file_scoped: true,
+                config_files: &["biome.json", "biome.jsonc"],
             },
         }
     }
@@ -86,7 +91,6 @@ struct BiomeDiagnostic {
 #[derive(Debug, Deserialize)]
 struct BiomeLocation {
     path: Option<BiomePath>,
-    #[allow(dead_code)]
     span: Option<BiomeSpan>,
 }
 
@@ -95,7 +99,6 @@ struct BiomePath {
     file: String,
 }
 
-#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 struct BiomeSpan {
     start: usize,
@@ -142,7 +145,9 @@ fn biome_version() -> Option<String> {
         .map(|s| s.trim().to_string())
 }
 
-fn parse_biome_output(stdout: &str) -> Result<Vec<Diagnostic>, ToolError> {
+/// Parse biome's JSON reporter output. Spans are byte offsets, so each
+/// reported file is read (relative to `root`) to convert them to line/column.
+fn parse_biome_output(stdout: &str, root: &Path) -> Result<Vec<Diagnostic>, ToolError> {
     if stdout.trim().is_empty() {
         return Ok(vec![]);
     }
@@ -150,6 +155,7 @@ fn parse_biome_output(stdout: &str) -> Result<Vec<Diagnostic>, ToolError> {
     let output: BiomeOutput = serde_json::from_str(stdout)
         .map_err(|e| ToolError::ParseError(format!("failed to parse biome output: {}", e)))?;
 
+    let mut sources: HashMap<String, Option<String>> = HashMap::new();
     let diagnostics = output
         .diagnostics
         .into_iter()
@@ -163,22 +169,22 @@ fn parse_biome_output(stdout: &str) -> Result<Vec<Diagnostic>, ToolError> {
                 _ => DiagnosticSeverity::Warning,
             };
 
-            // Biome uses byte offsets, not line/column
-            // We'd need to read the file to convert, so we use 0,0 as placeholder
-            let (line, column) = (1, 1);
+            let source = sources
+                .entry(path.file.clone())
+                .or_insert_with(|| std::fs::read_to_string(root.join(&path.file)).ok());
+            let location = match (loc.span, source) {
+                (Some(span), Some(content)) => {
+                    Location::from_byte_range(&path.file, content, span.start, span.end)
+                }
+                _ => Location::new(&path.file, 1, 1),
+            };
 
             Some(Diagnostic {
                 tool: "biome".to_string(),
                 rule_id: d.category.unwrap_or_else(|| "unknown".to_string()),
                 message: d.message,
                 severity,
-                location: Location {
-                    file: path.file.into(),
-                    line,
-                    column,
-                    end_line: None,
-                    end_column: None,
-                },
+                location,
                 fix: None,
                 help_url: None,
             })
@@ -222,7 +228,7 @@ impl Tool for BiomeLint {
         let output = command.args(&path_args).current_dir(root).output()?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let diagnostics = parse_biome_output(&stdout)?;
+        let diagnostics = parse_biome_output(&stdout, root)?;
 
         Ok(ToolResult::success("biome", diagnostics))
     }
@@ -248,7 +254,7 @@ impl Tool for BiomeLint {
         let output = command.args(&path_args).current_dir(root).output()?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let diagnostics = parse_biome_output(&stdout)?;
+        let diagnostics = parse_biome_output(&stdout, root)?;
 
         Ok(ToolResult::success("biome", diagnostics))
     }
@@ -288,7 +294,7 @@ impl Tool for BiomeFormat {
         let output = command.args(&path_args).current_dir(root).output()?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let diagnostics = parse_biome_output(&stdout)?;
+        let diagnostics = parse_biome_output(&stdout, root)?;
 
         Ok(ToolResult::success("biome-fmt", diagnostics))
     }
@@ -314,7 +320,7 @@ impl Tool for BiomeFormat {
         let output = command.args(&path_args).current_dir(root).output()?;
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let diagnostics = parse_biome_output(&stdout)?;
+        let diagnostics = parse_biome_output(&stdout, root)?;
 
         Ok(ToolResult::success("biome-fmt", diagnostics))
     }
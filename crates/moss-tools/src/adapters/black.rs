@@ -0,0 +1,176 @@
+//! Black adapter - Python formatter.
+//!
+//! Black is the uncompromising Python code formatter.
+//! https://black.readthedocs.io/
+
+use crate::{
+    Diagnostic, DiagnosticSeverity, Location, Tool, ToolCategory, ToolError, ToolInfo, ToolResult,
+};
+use std::path::Path;
+use std::process::Command;
+
+fn black_command() -> Option<(&'static str, Vec<&'static str>)> {
+    crate::tools::find_python_tool("black")
+}
+
+/// Black Python formatter adapter.
+pub struct Black {
+    info: ToolInfo,
+}
+
+impl Black {
+    pub fn new() -> Self {
+        Self {
+            info: ToolInfo {
+                name: "black",
+                category: ToolCategory::Formatter,
+                extensions: &["py", "pyi"],
+                check_cmd: &["black", "--version"],
+                website: "https://black.readthedocs.io/",
+                file_scoped: true,
+                config_files: &["pyproject.toml"],
+            },
+        }
+    }
+}
+
+impl Default for Black {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for Black {
+    fn info(&self) -> &ToolInfo {
+        &self.info
+    }
+
+    fn is_available(&self) -> bool {
+        black_command().is_some()
+    }
+
+    fn version(&self) -> Option<String> {
+        let (cmd, base_args) = black_command()?;
+        Command::new(cmd)
+            .args(&base_args)
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .and_then(|s| s.lines().next().map(|l| l.trim().to_string()))
+    }
+
+    fn detect(&self, root: &Path) -> f32 {
+        // Avoid double-formatting: ruff format covers black's style
+        if super::ruff::ruff_detected(root) {
+            return 0.0;
+        }
+        if crate::tools::has_files_with_extensions(root, &["py", "pyi"]) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn run(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        let (cmd, base_args) = black_command()
+            .ok_or_else(|| ToolError::NotAvailable("black not found".to_string()))?;
+
+        let path_args: Vec<&str> = if paths.is_empty() {
+            vec!["."]
+        } else {
+            paths.iter().map(|p| p.to_str().unwrap_or(".")).collect()
+        };
+
+        // Not --quiet: it also hides the "would reformat" lines we report
+        let output = Command::new(cmd)
+            .args(&base_args)
+            .arg("--check")
+            .args(&path_args)
+            .current_dir(root)
+            .output()?;
+
+        // Exit code 0 = formatted, 1 = would reformat, 123 = internal error
+        // (e.g. a file that doesn't parse, reported per file below)
+        if output.status.success() {
+            return Ok(ToolResult::success("black", vec![]));
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let diagnostics = parse_black_output(&stderr);
+        if diagnostics.is_empty() {
+            return Ok(ToolResult::failure("black", stderr.trim()));
+        }
+
+        Ok(ToolResult::success("black", diagnostics))
+    }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    fn fix(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        let (cmd, base_args) = black_command()
+            .ok_or_else(|| ToolError::NotAvailable("black not found".to_string()))?;
+
+        let path_args: Vec<&str> = if paths.is_empty() {
+            vec!["."]
+        } else {
+            paths.iter().map(|p| p.to_str().unwrap_or(".")).collect()
+        };
+
+        let output = Command::new(cmd)
+            .args(&base_args)
+            .arg("--quiet")
+            .args(&path_args)
+            .current_dir(root)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(ToolResult::failure("black", stderr.to_string()));
+        }
+
+        Ok(ToolResult::success("black", vec![]))
+    }
+}
+
+/// Parse `black --check` stderr:
+/// - `would reformat path/to/file.py`
+/// - `error: cannot format path/to/file.py: <reason>`
+fn parse_black_output(stderr: &str) -> Vec<Diagnostic> {
+    stderr
+        .lines()
+        .filter_map(|line| {
+            if let Some(file) = line.strip_prefix("would reformat ") {
+                return Some(black_diagnostic(
+                    file,
+                    "File needs formatting",
+                    DiagnosticSeverity::Warning,
+                ));
+            }
+            let rest = line.strip_prefix("error: cannot format ")?;
+            let (file, reason) = rest.split_once(": ")?;
+            Some(black_diagnostic(file, reason, DiagnosticSeverity::Error))
+        })
+        .collect()
+}
+
+fn black_diagnostic(file: &str, message: &str, severity: DiagnosticSeverity) -> Diagnostic {
+    Diagnostic {
+        tool: "black".to_string(),
+        rule_id: "formatting".to_string(),
+        message: message.to_string(),
+        severity,
+        location: Location {
+            file: file.trim().to_string().into(),
+            line: 1,
+            column: 1,
+            end_line: None,
+            end_column: None,
+        },
+        fix: None,
+        help_url: None,
+    }
+}
@@ -24,6 +24,8 @@ impl Clippy {
                 extensions: &["rs"],
                 check_cmd: &["cargo", "clippy", "--version"],
                 website: "https://doc.rust-lang.org/clippy/",
+                file_scoped: false,
+                config_files: &["clippy.toml", ".clippy.toml"],
             },
         }
     }
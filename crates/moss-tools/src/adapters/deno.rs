@@ -32,6 +32,8 @@ impl Deno {
                 extensions: &["ts", "tsx", "js", "jsx"],
                 check_cmd: &["deno", "--version"],
                 website: "https://deno.land/",
+                file_scoped: false,
+                config_files: &["deno.json", "deno.jsonc"],
             },
         }
     }
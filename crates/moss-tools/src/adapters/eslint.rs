@@ -28,6 +28,19 @@ impl Eslint {
                 extensions: &["js", "jsx", "ts", "tsx", "mjs", "cjs"],
                 check_cmd: &["eslint", "--version"],
                 website: "https://eslint.org/",
+                file_scoped: true,
+                config_files: &[
+                    ".eslintrc",
+                    ".eslintrc.js",
+                    ".eslintrc.cjs",
+                    ".eslintrc.json",
+                    ".eslintrc.yml",
+                    ".eslintrc.yaml",
+                    "eslint.config.js",
+                    "eslint.config.mjs",
+                    "eslint.config.cjs",
+                    ".eslintignore",
+                ],
             },
         }
     }
@@ -127,7 +140,12 @@ impl Tool for Eslint {
         let diagnostics = eslint_files
             .into_iter()
             .flat_map(|file| {
-                file.messages.into_iter().map(move |msg| {
+                // Ignored files named explicitly are skipped with a warning
+                // that has no rule; they have nothing to report
+                let messages = file.messages.into_iter().filter(|msg| {
+                    msg.rule_id.is_some() || !msg.message.starts_with("File ignored")
+                });
+                messages.map(move |msg| {
                     let severity = if msg.severity >= 2 {
                         DiagnosticSeverity::Error
                     } else {
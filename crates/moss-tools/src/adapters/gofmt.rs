@@ -23,6 +23,8 @@ impl Gofmt {
                 extensions: &["go"],
                 check_cmd: &["gofmt", "-h"],
                 website: "https://pkg.go.dev/cmd/gofmt",
+                file_scoped: true,
+                config_files: &[],
             },
         }
     }
@@ -151,6 +153,8 @@ impl Govet {
                 extensions: &["go"],
                 check_cmd: &["go", "vet", "-h"],
                 website: "https://pkg.go.dev/cmd/vet",
+                file_scoped: false,
+                config_files: &[],
             },
         }
     }
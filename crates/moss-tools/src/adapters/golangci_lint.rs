@@ -0,0 +1,223 @@
+//! golangci-lint adapter - Go linters aggregator.
+//!
+//! golangci-lint runs many Go linters (errcheck, staticcheck, govet, ...) in parallel.
+//! https://golangci-lint.run/
+
+use crate::{
+    Diagnostic, DiagnosticSeverity, Location, Tool, ToolCategory, ToolError, ToolInfo, ToolResult,
+};
+use serde::Deserialize;
+use std::path::Path;
+use std::process::Command;
+
+/// golangci-lint adapter.
+pub struct GolangciLint {
+    info: ToolInfo,
+}
+
+impl GolangciLint {
+    pub fn new() -> Self {
+        Self {
+            info: ToolInfo {
+                name: "golangci-lint",
+                category: ToolCategory::Linter,
+                extensions: &["go"],
+                check_cmd: &["golangci-lint", "--version"],
+                website: "https://golangci-lint.run/",
+                file_scoped: false,
+                config_files: &[
+                    ".golangci.yml",
+                    ".golangci.yaml",
+                    ".golangci.toml",
+                    ".golangci.json",
+                ],
+            },
+        }
+    }
+}
+
+impl Default for GolangciLint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// golangci-lint JSON output format.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GolangciOutput {
+    issues: Option<Vec<GolangciIssue>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GolangciIssue {
+    from_linter: String,
+    text: String,
+    #[serde(default)]
+    severity: String,
+    pos: GolangciPos,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct GolangciPos {
+    filename: String,
+    line: usize,
+    #[serde(default)]
+    column: usize,
+}
+
+impl Tool for GolangciLint {
+    fn info(&self) -> &ToolInfo {
+        &self.info
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("golangci-lint")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    fn version(&self) -> Option<String> {
+        Command::new("golangci-lint")
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .map(|s| s.trim().to_string())
+    }
+
+    fn detect(&self, root: &Path) -> f32 {
+        if crate::tools::has_config_file(root, &["go.mod"]) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn run(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        self.run_golangci(paths, root, false)
+    }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    fn fix(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        self.run_golangci(paths, root, true)
+    }
+}
+
+/// Major version from `golangci-lint --version`
+/// ("golangci-lint has version v1.55.2 built ..." or "... version 2.1.6 ...").
+fn major_version(version: &str) -> Option<u32> {
+    let number = version
+        .split_whitespace()
+        .skip_while(|word| *word != "version")
+        .nth(1)?;
+    number
+        .trim_start_matches('v')
+        .split('.')
+        .next()?
+        .parse()
+        .ok()
+}
+
+impl GolangciLint {
+    fn run_golangci(
+        &self,
+        paths: &[&Path],
+        root: &Path,
+        fix: bool,
+    ) -> Result<ToolResult, ToolError> {
+        let path_args: Vec<&str> = if paths.is_empty() {
+            vec!["./..."]
+        } else {
+            paths.iter().map(|p| p.to_str().unwrap_or(".")).collect()
+        };
+
+        let mut command = Command::new("golangci-lint");
+        command.arg("run");
+        // v2 replaced --out-format with per-format output flags
+        match self.version().as_deref().and_then(major_version) {
+            Some(major) if major >= 2 => command.args(["--output.json.path", "stdout"]),
+            _ => command.args(["--out-format", "json"]),
+        };
+        if fix {
+            command.arg("--fix");
+        }
+        let output = command.args(&path_args).current_dir(root).output()?;
+
+        // Exit code 1 means issues were found, which is expected
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let Some(json) = stdout
+            .lines()
+            .find(|line| line.trim_start().starts_with('{'))
+        else {
+            if output.status.success() {
+                return Ok(ToolResult::success("golangci-lint", vec![]));
+            }
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(ToolResult::failure("golangci-lint", stderr.trim()));
+        };
+
+        let parsed: GolangciOutput = serde_json::from_str(json).map_err(|e| {
+            ToolError::ParseError(format!("failed to parse golangci-lint output: {}", e))
+        })?;
+
+        let diagnostics = parsed
+            .issues
+            .unwrap_or_default()
+            .into_iter()
+            .map(|issue| {
+                // Severity is only set when configured in .golangci.yml
+                let severity = match issue.severity.to_lowercase().as_str() {
+                    "error" => DiagnosticSeverity::Error,
+                    "info" => DiagnosticSeverity::Info,
+                    "hint" => DiagnosticSeverity::Hint,
+                    _ => DiagnosticSeverity::Warning,
+                };
+
+                Diagnostic {
+                    tool: "golangci-lint".to_string(),
+                    rule_id: issue.from_linter,
+                    message: issue.text,
+                    severity,
+                    location: Location {
+                        file: issue.pos.filename.into(),
+                        line: issue.pos.line,
+                        column: issue.pos.column.max(1),
+                        end_line: None,
+                        end_column: None,
+                    },
+                    fix: None,
+                    help_url: None,
+                }
+            })
+            .collect();
+
+        Ok(ToolResult::success("golangci-lint", diagnostics))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_major_version() {
+        assert_eq!(
+            major_version("golangci-lint has version v1.55.2 built with go1.21.3 from e3c2265f"),
+            Some(1)
+        );
+        assert_eq!(
+            major_version("golangci-lint has version 2.1.6 built with go1.24.3 from eabc2638"),
+            Some(2)
+        );
+        assert_eq!(major_version("golangci-lint"), None);
+    }
+}
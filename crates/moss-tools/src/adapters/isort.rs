@@ -0,0 +1,195 @@
+//! isort adapter - Python import sorter.
+//!
+//! isort sorts imports alphabetically and separates them into sections.
+//! https://pycqa.github.io/isort/
+
+use crate::{
+    Diagnostic, DiagnosticSeverity, Location, Tool, ToolCategory, ToolError, ToolInfo, ToolResult,
+};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+fn isort_command() -> Option<(&'static str, Vec<&'static str>)> {
+    crate::tools::find_python_tool("isort")
+}
+
+/// isort Python import sorter adapter.
+pub struct Isort {
+    info: ToolInfo,
+}
+
+impl Isort {
+    pub fn new() -> Self {
+        Self {
+            info: ToolInfo {
+                name: "isort",
+                category: ToolCategory::Formatter,
+                extensions: &["py", "pyi"],
+                check_cmd: &["isort", "--version"],
+                website: "https://pycqa.github.io/isort/",
+                file_scoped: true,
+                config_files: &[
+                    "pyproject.toml",
+                    ".isort.cfg",
+                    "setup.cfg",
+                    "tox.ini",
+                    ".editorconfig",
+                ],
+            },
+        }
+    }
+}
+
+impl Default for Isort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for Isort {
+    fn info(&self) -> &ToolInfo {
+        &self.info
+    }
+
+    fn is_available(&self) -> bool {
+        isort_command().is_some()
+    }
+
+    fn version(&self) -> Option<String> {
+        // --version prints an ASCII-art banner; --vn prints just the number
+        let (cmd, base_args) = isort_command()?;
+        Command::new(cmd)
+            .args(&base_args)
+            .arg("--vn")
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .map(|s| s.trim().to_string())
+    }
+
+    fn detect(&self, root: &Path) -> f32 {
+        // Avoid double-formatting: ruff's isort rules (I001) cover import sorting
+        if super::ruff::ruff_detected(root) {
+            return 0.0;
+        }
+        if crate::tools::has_files_with_extensions(root, &["py", "pyi"]) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn run(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        let (cmd, base_args) = isort_command()
+            .ok_or_else(|| ToolError::NotAvailable("isort not found".to_string()))?;
+
+        let path_args: Vec<&str> = if paths.is_empty() {
+            vec!["."]
+        } else {
+            paths.iter().map(|p| p.to_str().unwrap_or(".")).collect()
+        };
+
+        let output = Command::new(cmd)
+            .args(&base_args)
+            // --filter-files: skip configured excludes even when named explicitly
+            .args(["--check-only", "--diff", "--filter-files"])
+            .args(&path_args)
+            .current_dir(root)
+            .output()?;
+
+        if output.status.success() {
+            return Ok(ToolResult::success("isort", vec![]));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let diagnostics = parse_isort_output(&stdout, &stderr);
+        if diagnostics.is_empty() {
+            return Ok(ToolResult::failure("isort", stderr.trim()));
+        }
+
+        Ok(ToolResult::success("isort", diagnostics))
+    }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    fn fix(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        let (cmd, base_args) = isort_command()
+            .ok_or_else(|| ToolError::NotAvailable("isort not found".to_string()))?;
+
+        let path_args: Vec<&str> = if paths.is_empty() {
+            vec!["."]
+        } else {
+            paths.iter().map(|p| p.to_str().unwrap_or(".")).collect()
+        };
+
+        let output = Command::new(cmd)
+            .args(&base_args)
+            .arg("--quiet")
+            .args(&path_args)
+            .current_dir(root)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(ToolResult::failure("isort", stderr.to_string()));
+        }
+
+        Ok(ToolResult::success("isort", vec![]))
+    }
+}
+
+/// Parse `isort --check-only --diff` output.
+///
+/// stderr names each unsorted file:
+/// `ERROR: path/to/file.py Imports are incorrectly sorted and/or formatted.`
+/// stdout has a unified diff per file (`--- path/to/file.py:before`), whose
+/// first hunk gives the line to report.
+fn parse_isort_output(stdout: &str, stderr: &str) -> Vec<Diagnostic> {
+    let mut first_hunk: HashMap<&str, usize> = HashMap::new();
+    let mut current: Option<&str> = None;
+    for line in stdout.lines() {
+        if let Some(header) = line.strip_prefix("--- ") {
+            current = header
+                .split('\t')
+                .next()
+                .and_then(|h| h.strip_suffix(":before"));
+        } else if let Some(hunk) = line.strip_prefix("@@ -") {
+            if let Some(file) = current.take() {
+                let start = hunk
+                    .split([',', ' '])
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or(1);
+                first_hunk.insert(file, start);
+            }
+        }
+    }
+
+    stderr
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("ERROR: ")?;
+            let file = rest.strip_suffix(" Imports are incorrectly sorted and/or formatted.")?;
+            Some(Diagnostic {
+                tool: "isort".to_string(),
+                rule_id: "import-order".to_string(),
+                message: "Imports are incorrectly sorted and/or formatted".to_string(),
+                severity: DiagnosticSeverity::Warning,
+                location: Location {
+                    file: file.to_string().into(),
+                    line: first_hunk.get(file).copied().unwrap_or(1).max(1),
+                    column: 1,
+                    end_line: None,
+                    end_column: None,
+                },
+                fix: None,
+                help_url: None,
+            })
+        })
+        .collect()
+}
@@ -6,10 +6,13 @@
 //! - Output parsing to diagnostics
 
 mod biome;
+mod black;
 mod clippy;
 mod deno;
 mod eslint;
 mod gofmt;
+mod golangci_lint;
+mod isort;
 mod mypy;
 mod oxfmt;
 mod oxlint;
@@ -17,14 +20,19 @@ mod prettier;
 mod pyright;
 mod ruff;
 mod rustfmt;
+mod shellcheck;
+mod stylua;
 mod tsc;
 mod tsgo;
 
 pub use biome::{BiomeFormat, BiomeLint};
+pub use black::Black;
 pub use clippy::Clippy;
 pub use deno::Deno;
 pub use eslint::Eslint;
 pub use gofmt::{Gofmt, Govet};
+pub use golangci_lint::GolangciLint;
+pub use isort::Isort;
 pub use mypy::Mypy;
 pub use oxfmt::Oxfmt;
 pub use oxlint::Oxlint;
@@ -32,6 +40,8 @@ pub use prettier::Prettier;
 pub use pyright::Pyright;
 pub use ruff::Ruff;
 pub use rustfmt::Rustfmt;
+pub use shellcheck::Shellcheck;
+pub use stylua::Stylua;
 pub use tsc::Tsc;
 pub use tsgo::Tsgo;
 
@@ -42,6 +52,8 @@ pub fn all_adapters() -> Vec<Box<dyn Tool>> {
     vec![
         // Python
         Box::new(Ruff::new()),
+        Box::new(Black::new()), // Defers to ruff when detected
+        Box::new(Isort::new()), // Defers to ruff when detected
         Box::new(Mypy::new()),
         Box::new(Pyright::new()),
         // JavaScript/TypeScript (oxc toolchain preferred over eslint/prettier)
@@ -59,6 +71,11 @@ pub fn all_adapters() -> Vec<Box<dyn Tool>> {
         Box::new(Rustfmt::new()),
         // Go
         Box::new(Gofmt::new()),
+        Box::new(GolangciLint::new()), // Aggregates govet and many more
         Box::new(Govet::new()),
+        // Lua
+        Box::new(Stylua::new()),
+        // Shell
+        Box::new(Shellcheck::new()),
     ]
 }
@@ -28,6 +28,8 @@ impl Mypy {
                 extensions: &["py", "pyi"],
                 check_cmd: &["mypy", "--version"],
                 website: "https://mypy-lang.org/",
+                file_scoped: false,
+                config_files: &["mypy.ini", ".mypy.ini", "pyproject.toml", "setup.cfg"],
             },
         }
     }
@@ -27,6 +27,8 @@ impl Oxfmt {
                 extensions: &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts"],
                 check_cmd: &["oxfmt", "--version"],
                 website: "https://oxc.rs/",
+                file_scoped: true,
+                config_files: &[".oxfmtrc.json", ".oxfmtrc.jsonc", "oxfmt.json"],
             },
         }
     }
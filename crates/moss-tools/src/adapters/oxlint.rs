@@ -25,6 +25,8 @@ impl Oxlint {
                 extensions: &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts"],
                 check_cmd: &["oxlint", "--version"],
                 website: "https://oxc.rs/",
+                file_scoped: true,
+                config_files: &["oxlintrc.json", ".oxlintrc.json"],
             },
         }
     }
@@ -30,6 +30,19 @@ impl Prettier {
                 ],
                 check_cmd: &["prettier", "--version"],
                 website: "https://prettier.io/",
+                file_scoped: true,
+                config_files: &[
+                    ".prettierrc",
+                    ".prettierrc.json",
+                    ".prettierrc.yaml",
+                    ".prettierrc.yml",
+                    ".prettierrc.js",
+                    ".prettierrc.cjs",
+                    "prettier.config.js",
+                    "prettier.config.cjs",
+                    ".prettierignore",
+                    ".editorconfig",
+                ],
             },
         }
     }
@@ -33,6 +33,8 @@ impl Pyright {
                 extensions: &["py", "pyi"],
                 check_cmd: &["pyright", "--version"],
                 website: "https://github.com/microsoft/pyright",
+                file_scoped: false,
+                config_files: &["pyrightconfig.json", "pyproject.toml"],
             },
         }
     }
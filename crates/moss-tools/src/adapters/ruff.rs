@@ -14,6 +14,11 @@ fn ruff_command() -> Option<(&'static str, Vec<&'static str>)> {
     crate::tools::find_python_tool("ruff")
 }
 
+/// Whether ruff covers this project, so black/isort should defer to `ruff format`.
+pub(crate) fn ruff_detected(root: &Path) -> bool {
+    Ruff::new().detect(root) > 0.0 && ruff_command().is_some()
+}
+
 /// Ruff Python linter/formatter adapter.
 pub struct Ruff {
     info: ToolInfo,
@@ -28,6 +33,8 @@ impl Ruff {
                 extensions: &["py", "pyi"],
                 check_cmd: &["ruff", "--version"],
                 website: "https://docs.astral.sh/ruff/",
+                file_scoped: true,
+                config_files: &["pyproject.toml", "ruff.toml", ".ruff.toml"],
             },
         }
     }
@@ -118,6 +125,8 @@ impl Tool for Ruff {
         let mut command = Command::new(cmd);
         command.args(&base_args);
         command.arg("check").arg("--output-format=json");
+        // Apply the configured excludes to files named explicitly too
+        command.arg("--force-exclude");
 
         let output = command.args(&path_args).current_dir(root).output()?;
 
@@ -24,6 +24,8 @@ impl Rustfmt {
                 extensions: &["rs"],
                 check_cmd: &["rustfmt", "--version"],
                 website: "https://rust-lang.github.io/rustfmt/",
+                file_scoped: true,
+                config_files: &["rustfmt.toml", ".rustfmt.toml"],
             },
         }
     }
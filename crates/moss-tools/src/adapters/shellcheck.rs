@@ -0,0 +1,163 @@
+//! ShellCheck adapter - shell script linter.
+//!
+//! ShellCheck finds bugs and pitfalls in sh/bash scripts.
+//! https://www.shellcheck.net/
+
+use crate::{
+    Diagnostic, DiagnosticSeverity, Location, Tool, ToolCategory, ToolError, ToolInfo, ToolResult,
+};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const EXTENSIONS: &[&str] = &["sh", "bash"];
+
+/// ShellCheck adapter.
+pub struct Shellcheck {
+    info: ToolInfo,
+}
+
+impl Shellcheck {
+    pub fn new() -> Self {
+        Self {
+            info: ToolInfo {
+                name: "shellcheck",
+                category: ToolCategory::Linter,
+                extensions: EXTENSIONS,
+                check_cmd: &["shellcheck", "--version"],
+                website: "https://www.shellcheck.net/",
+                file_scoped: true,
+                config_files: &[".shellcheckrc"],
+            },
+        }
+    }
+}
+
+impl Default for Shellcheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ShellCheck JSON output format (`-f json`): a flat array of comments.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShellcheckComment {
+    file: String,
+    line: usize,
+    end_line: Option<usize>,
+    column: usize,
+    end_column: Option<usize>,
+    level: String,
+    code: u32,
+    message: String,
+}
+
+impl Tool for Shellcheck {
+    fn info(&self) -> &ToolInfo {
+        &self.info
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("shellcheck")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    fn version(&self) -> Option<String> {
+        // Output includes a line like "version: 0.10.0"
+        Command::new("shellcheck")
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .and_then(|s| {
+                s.lines()
+                    .find_map(|line| line.strip_prefix("version:"))
+                    .map(|v| v.trim().to_string())
+            })
+    }
+
+    fn detect(&self, root: &Path) -> f32 {
+        if crate::tools::has_files_with_extensions(root, EXTENSIONS) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn run(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        // shellcheck doesn't recurse into directories, so expand them to scripts
+        let files: Vec<PathBuf> = if paths.is_empty() {
+            crate::tools::find_files_with_extensions(root, EXTENSIONS)
+        } else {
+            paths
+                .iter()
+                .flat_map(|p| crate::tools::find_files_with_extensions(&root.join(p), EXTENSIONS))
+                .collect()
+        };
+        if files.is_empty() {
+            return Ok(ToolResult::success("shellcheck", vec![]));
+        }
+        let files: Vec<&Path> = files
+            .iter()
+            .map(|f| f.strip_prefix(root).unwrap_or(f))
+            .collect();
+
+        let output = Command::new("shellcheck")
+            .args(["-f", "json"])
+            .args(&files)
+            .current_dir(root)
+            .output()?;
+
+        // Exit code 1 means issues were found; anything above is a real failure
+        // (unreadable files, bad options)
+        if !matches!(output.status.code(), Some(0 | 1)) {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(ToolResult::failure("shellcheck", stderr.trim()));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.trim().is_empty() {
+            return Ok(ToolResult::success("shellcheck", vec![]));
+        }
+
+        let comments: Vec<ShellcheckComment> = serde_json::from_str(&stdout).map_err(|e| {
+            ToolError::ParseError(format!("failed to parse shellcheck output: {}", e))
+        })?;
+
+        let diagnostics = comments
+            .into_iter()
+            .map(|comment| {
+                let severity = match comment.level.as_str() {
+                    "error" => DiagnosticSeverity::Error,
+                    "warning" => DiagnosticSeverity::Warning,
+                    "info" => DiagnosticSeverity::Info,
+                    _ => DiagnosticSeverity::Hint, // "style"
+                };
+                let rule_id = format!("SC{}", comment.code);
+
+                Diagnostic {
+                    tool: "shellcheck".to_string(),
+                    help_url: Some(format!("https://www.shellcheck.net/wiki/{}", rule_id)),
+                    rule_id,
+                    message: comment.message,
+                    severity,
+                    location: Location {
+                        file: comment.file.into(),
+                        line: comment.line,
+                        column: comment.column,
+                        end_line: comment.end_line,
+                        end_column: comment.end_column,
+                    },
+                    fix: None,
+                }
+            })
+            .collect();
+
+        Ok(ToolResult::success("shellcheck", diagnostics))
+    }
+}
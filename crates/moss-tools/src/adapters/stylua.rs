@@ -0,0 +1,144 @@
+//! StyLua adapter - Lua formatter.
+//!
+//! StyLua is an opinionated Lua code formatter.
+//! https://github.com/JohnnyMorganz/StyLua
+
+use crate::{
+    Diagnostic, DiagnosticSeverity, Location, Tool, ToolCategory, ToolError, ToolInfo, ToolResult,
+};
+use std::path::Path;
+use std::process::Command;
+
+/// StyLua formatter adapter.
+pub struct Stylua {
+    info: ToolInfo,
+}
+
+impl Stylua {
+    pub fn new() -> Self {
+        Self {
+            info: ToolInfo {
+                name: "stylua",
+                category: ToolCategory::Formatter,
+                extensions: &["lua"],
+                check_cmd: &["stylua", "--version"],
+                website: "https://github.com/JohnnyMorganz/StyLua",
+                file_scoped: true,
+                config_files: &["stylua.toml", ".stylua.toml", ".styluaignore"],
+            },
+        }
+    }
+}
+
+impl Default for Stylua {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tool for Stylua {
+    fn info(&self) -> &ToolInfo {
+        &self.info
+    }
+
+    fn is_available(&self) -> bool {
+        Command::new("stylua")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    fn version(&self) -> Option<String> {
+        Command::new("stylua")
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .and_then(|o| String::from_utf8(o.stdout).ok())
+            .map(|s| s.trim().to_string())
+    }
+
+    fn detect(&self, root: &Path) -> f32 {
+        if crate::tools::has_config_file(root, &["stylua.toml", ".stylua.toml"]) {
+            1.0
+        } else if crate::tools::has_files_with_extensions(root, &["lua"]) {
+            0.8
+        } else {
+            0.0
+        }
+    }
+
+    fn run(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        let path_args: Vec<&str> = if paths.is_empty() {
+            vec!["."]
+        } else {
+            paths.iter().map(|p| p.to_str().unwrap_or(".")).collect()
+        };
+
+        let output = Command::new("stylua")
+            // --respect-ignores: honor .styluaignore for files named explicitly
+            .args(["--check", "--respect-ignores"])
+            .args(&path_args)
+            .current_dir(root)
+            .output()?;
+
+        // Exit code 0 = formatted, 1 = needs formatting, anything else = error
+        if output.status.success() {
+            return Ok(ToolResult::success("stylua", vec![]));
+        }
+        if output.status.code() != Some(1) {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(ToolResult::failure("stylua", stderr.trim()));
+        }
+
+        // Each unformatted file starts a diff block: "Diff in path/to/file.lua:"
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let diagnostics: Vec<Diagnostic> = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("Diff in "))
+            .map(|rest| Diagnostic {
+                tool: "stylua".to_string(),
+                rule_id: "formatting".to_string(),
+                message: "File needs formatting".to_string(),
+                severity: DiagnosticSeverity::Warning,
+                location: Location {
+                    file: rest.trim_end_matches(':').to_string().into(),
+                    line: 1,
+                    column: 1,
+                    end_line: None,
+                    end_column: None,
+                },
+                fix: None,
+                help_url: None,
+            })
+            .collect();
+
+        Ok(ToolResult::success("stylua", diagnostics))
+    }
+
+    fn can_fix(&self) -> bool {
+        true
+    }
+
+    fn fix(&self, paths: &[&Path], root: &Path) -> Result<ToolResult, ToolError> {
+        // Without --check, stylua rewrites files in place
+        let path_args: Vec<&str> = if paths.is_empty() {
+            vec!["."]
+        } else {
+            paths.iter().map(|p| p.to_str().unwrap_or(".")).collect()
+        };
+
+        let output = Command::new("stylua")
+            .args(&path_args)
+            .current_dir(root)
+            .output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Ok(ToolResult::failure("stylua", stderr.to_string()));
+        }
+
+        Ok(ToolResult::success("stylua", vec![]))
+    }
+}
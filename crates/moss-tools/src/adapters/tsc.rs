@@ -28,6 +28,8 @@ impl Tsc {
                 extensions: &["ts", "tsx", "mts", "cts"],
                 check_cmd: &["tsc", "--version"],
                 website: "https://www.typescriptlang.org/",
+                file_scoped: false,
+                config_files: &["tsconfig.json"],
             },
         }
     }
@@ -29,6 +29,8 @@ impl Tsgo {
                 extensions: &["ts", "tsx", "mts", "cts"],
                 check_cmd: &["tsgo", "--version"],
                 website: "https://github.com/microsoft/typescript-go",
+                file_scoped: false,
+                config_files: &["tsconfig.json"],
             },
         }
     }
@@ -148,6 +148,9 @@ impl CustomTool {
             extensions,
             check_cmd,
             website: website_static,
+            // Unknown tool: don't assume its results can be cached per file
+            file_scoped: false,
+            config_files: &[],
         };
 
         Self { name, config, info }
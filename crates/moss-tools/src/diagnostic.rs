@@ -1,6 +1,7 @@
 //! Diagnostic types shared across all tools.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::PathBuf;
 
 /// Severity levels for diagnostics.
@@ -27,6 +28,11 @@ impl DiagnosticSeverity {
         }
     }
 
+    /// Whether this is at least as severe as `threshold` (errors are the most severe).
+    pub fn at_least(&self, threshold: Self) -> bool {
+        *self <= threshold
+    }
+
     /// Convert to SARIF level string.
     pub fn to_sarif_level(&self) -> &'static str {
         match self {
@@ -47,6 +53,23 @@ impl DiagnosticSeverity {
     }
 }
 
+impl std::str::FromStr for DiagnosticSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "warning" => Ok(Self::Warning),
+            "info" => Ok(Self::Info),
+            "hint" => Ok(Self::Hint),
+            _ => Err(format!(
+                "unknown severity '{}' (expected error, warning, info, or hint)",
+                s
+            )),
+        }
+    }
+}
+
 /// Source location of a diagnostic.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Location {
@@ -80,6 +103,51 @@ impl Location {
         self.end_column = Some(end_column);
         self
     }
+
+    /// Build a range location from byte offsets into `content`, for tools
+    /// (like biome) that report spans instead of line/column.
+    pub fn from_byte_range(
+        file: impl Into<PathBuf>,
+        content: &str,
+        start: usize,
+        end: usize,
+    ) -> Self {
+        let (line, column) = line_column(content, start);
+        let (end_line, end_column) = line_column(content, end.max(start));
+        Self::new(file, line, column).with_end(end_line, end_column)
+    }
+}
+
+/// 1-based line and column (in chars) of a byte offset, clamped to `content`.
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(content.len());
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// `file:line:col`, extended with the end of the range when known:
+/// `file:line:col-end_col` on one line, `file:line:col-end_line:end_col` across lines.
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file.display(), self.line, self.column)?;
+        match (self.end_line, self.end_column) {
+            (Some(end_line), Some(end_column)) if end_line == self.line => {
+                if end_column > self.column {
+                    write!(f, "-{}", end_column)?;
+                }
+                Ok(())
+            }
+            (Some(end_line), Some(end_column)) => write!(f, "-{}:{}", end_line, end_column),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// A single diagnostic from a tool.
@@ -150,3 +218,29 @@ impl Diagnostic {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_order() {
+        use DiagnosticSeverity::*;
+        assert!(Error < Warning && Warning < Info && Info < Hint);
+        assert!(Error.at_least(Warning));
+        assert!(Warning.at_least(Warning));
+        assert!(!Info.at_least(Warning));
+        assert!(Hint.at_least(Hint));
+        assert!(!Hint.at_least(Error));
+    }
+
+    #[test]
+    fn test_severity_from_str() {
+        assert_eq!("error".parse(), Ok(DiagnosticSeverity::Error));
+        assert_eq!("Warning".parse(), Ok(DiagnosticSeverity::Warning));
+        assert_eq!("INFO".parse(), Ok(DiagnosticSeverity::Info));
+        assert_eq!("hint".parse(), Ok(DiagnosticSeverity::Hint));
+        let err = "fatal".parse::<DiagnosticSeverity>().unwrap_err();
+        assert!(err.contains("unknown severity 'fatal'"), "{}", err);
+    }
+}
@@ -76,28 +76,50 @@ impl ToolRegistry {
         relevant
     }
 
-    /// Run all relevant tools on a project.
-    pub fn run_detected(&self, root: &Path, paths: &[&Path]) -> Vec<ToolResult> {
-        let detected = self.detect(root);
-        detected
-            .into_iter()
-            .filter_map(|(tool, _)| match tool.run(paths, root) {
-                Ok(result) => Some(result),
-                Err(e) => Some(ToolResult::failure(tool.info().name, e)),
-            })
-            .collect()
+    /// Run all relevant tools on a project, at most `jobs` at a time.
+    pub fn run_detected(&self, root: &Path, paths: &[&Path], jobs: usize) -> Vec<ToolResult> {
+        let detected: Vec<&dyn Tool> = self.detect(root).into_iter().map(|(t, _)| t).collect();
+        let mut results = run_concurrently(&detected, jobs, |tool| match tool.run(paths, root) {
+            Ok(result) => result,
+            Err(e) => ToolResult::failure(tool.info().name, e),
+        });
+        Self::sort_results(&mut results);
+        results
     }
 
-    /// Run specific tools by name.
-    pub fn run_named(&self, names: &[&str], root: &Path, paths: &[&Path]) -> Vec<ToolResult> {
-        self.tools
+    /// Run specific tools by name, at most `jobs` at a time.
+    pub fn run_named(
+        &self,
+        names: &[&str],
+        root: &Path,
+        paths: &[&Path],
+        jobs: usize,
+    ) -> Vec<ToolResult> {
+        let named: Vec<&dyn Tool> = self
+            .tools
             .iter()
             .filter(|t| names.contains(&t.info().name))
-            .filter_map(|tool| match tool.run(paths, root) {
-                Ok(result) => Some(result),
-                Err(e) => Some(ToolResult::failure(tool.info().name, e)),
-            })
-            .collect()
+            .map(|t| t.as_ref())
+            .collect();
+        let mut results = run_concurrently(&named, jobs, |tool| match tool.run(paths, root) {
+            Ok(result) => result,
+            Err(e) => ToolResult::failure(tool.info().name, e),
+        });
+        Self::sort_results(&mut results);
+        results
+    }
+
+    /// Order results by tool name, and each result's diagnostics by path and
+    /// position, so output doesn't depend on which tool finished first.
+    pub fn sort_results(results: &mut [ToolResult]) {
+        results.sort_by(|a, b| a.tool.cmp(&b.tool));
+        for result in results {
+            result.diagnostics.sort_by(|a, b| {
+                let a = &a.location;
+                let b = &b.location;
+                (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column))
+            });
+        }
     }
 
     /// Collect all diagnostics from multiple tool results.
@@ -105,3 +127,104 @@ impl ToolRegistry {
         results.iter().flat_map(|r| r.diagnostics.clone()).collect()
     }
 }
+
+/// Call `f` for each tool concurrently, with at most `jobs` running at once
+/// (0 = one per CPU). Results are in the same order as `tools`.
+///
+/// Tools mostly wait on their own subprocess, so this is I/O-bound.
+pub fn run_concurrently<'a, T, F>(tools: &[&'a dyn Tool], jobs: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&'a dyn Tool) -> T + Sync,
+{
+    match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool.install(|| tools.par_iter().map(|t| f(*t)).collect()),
+        Err(_) => tools.iter().map(|t| f(*t)).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Location, ToolError, ToolInfo};
+    use std::time::Duration;
+
+    /// Reports out-of-order diagnostics after a delay.
+    struct SlowTool {
+        info: ToolInfo,
+        delay: Duration,
+    }
+
+    impl SlowTool {
+        fn boxed(name: &'static str, delay_ms: u64) -> Box<dyn Tool> {
+            Box::new(Self {
+                info: ToolInfo {
+                    name,
+                    category: ToolCategory::Linter,
+                    extensions: &["py"],
+                    check_cmd: &[],
+                    website: "",
+                    file_scoped: true,
+                    config_files: &[],
+                },
+                delay: Duration::from_millis(delay_ms),
+            })
+        }
+    }
+
+    impl Tool for SlowTool {
+        fn info(&self) -> &ToolInfo {
+            &self.info
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn version(&self) -> Option<String> {
+            None
+        }
+
+        fn detect(&self, _root: &Path) -> f32 {
+            1.0
+        }
+
+        fn run(&self, _paths: &[&Path], _root: &Path) -> Result<ToolResult, ToolError> {
+            std::thread::sleep(self.delay);
+            let name = self.info.name;
+            let diag =
+                |file, line| Diagnostic::warning(name, "W1", "w", Location::new(file, line, 1));
+            Ok(ToolResult::success(
+                name,
+                vec![diag("b.py", 2), diag("a.py", 9), diag("a.py", 1)],
+            ))
+        }
+    }
+
+    #[test]
+    fn test_concurrent_results_are_ordered() {
+        let mut registry = ToolRegistry::new();
+        // The first registered finishes last
+        registry.register(SlowTool::boxed("charlie", 60));
+        registry.register(SlowTool::boxed("alpha", 30));
+        registry.register(SlowTool::boxed("bravo", 0));
+
+        for jobs in [1, 3] {
+            let named =
+                registry.run_named(&["alpha", "bravo", "charlie"], Path::new("."), &[], jobs);
+            let detected = registry.run_detected(Path::new("."), &[], jobs);
+            for results in [named, detected] {
+                let tools: Vec<&str> = results.iter().map(|r| r.tool.as_str()).collect();
+                assert_eq!(tools, ["alpha", "bravo", "charlie"], "jobs = {}", jobs);
+                for result in &results {
+                    let positions: Vec<String> = result
+                        .diagnostics
+                        .iter()
+                        .map(|d| format!("{}:{}", d.location.file.display(), d.location.line))
+                        .collect();
+                    assert_eq!(positions, ["a.py:1", "a.py:9", "b.py:2"]);
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Tool trait and common types.
 
 use crate::Diagnostic;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Category of tool.
@@ -38,6 +38,13 @@ pub struct ToolInfo {
     pub check_cmd: &'static [&'static str],
     /// URL to tool website.
     pub website: &'static str,
+    /// Whether a file's diagnostics depend only on that file's contents, so
+    /// results can be cached per file. False for type checkers and tools that
+    /// analyze whole crates or packages.
+    pub file_scoped: bool,
+    /// Config files at the project root that change what the tool reports.
+    /// Cached results are keyed on their contents as well as the tool version.
+    pub config_files: &'static [&'static str],
 }
 
 /// Result of running a tool.
@@ -151,6 +158,48 @@ pub fn has_config_file(root: &Path, names: &[&str]) -> bool {
     names.iter().any(|name| root.join(name).exists())
 }
 
+/// Find files under `path` with one of the given extensions, respecting .gitignore.
+///
+/// For tools that only accept explicit file arguments (no directory recursion).
+/// A file `path` is returned as-is if its extension matches.
+pub fn find_files_with_extensions(path: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let matches = |p: &Path| {
+        p.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| extensions.contains(&e))
+    };
+    if path.is_file() {
+        return if matches(path) {
+            vec![path.to_path_buf()]
+        } else {
+            Vec::new()
+        };
+    }
+
+    let mut files: Vec<PathBuf> = walk_files(path).filter(|p| matches(p)).collect();
+    files.sort();
+    files
+}
+
+/// Whether any file under `root` has one of the given extensions, respecting
+/// .gitignore. Stops at the first match, so it's cheap enough for `detect`.
+pub fn has_files_with_extensions(root: &Path, extensions: &[&str]) -> bool {
+    walk_files(root).any(|p| {
+        p.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| extensions.contains(&e))
+    })
+}
+
+/// Files under `path`, respecting .gitignore, in walk order.
+fn walk_files(path: &Path) -> impl Iterator<Item = PathBuf> {
+    ignore::WalkBuilder::new(path)
+        .build()
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .map(|entry| entry.into_path())
+}
+
 /// Find a JS ecosystem tool (local installs only, no remote downloads).
 ///
 /// Tries in order: